@@ -47,6 +47,8 @@ export const FeedVersionSchema = z.object({
   supports_trades: z.boolean().optional(),
   supports_historical: z.boolean().optional(),
   parser_config: z.record(z.string()).optional(),
+  poll_interval_secs: z.number().int().positive().optional(),
+  channels: z.array(z.string()).min(1).optional(),
 });
 
 // Main feed schema
@@ -671,6 +671,10 @@ export default function DocsPage() {
   "global_max_notional": "10000.00",
   "open_notional": "420.00",
   "available_notional": "4580.00",
+  "max_open_contracts": "500",
+  "global_max_open_contracts": null,
+  "open_contracts": "120",
+  "available_contracts": "380",
   "session_id": "sess_001"
 }`}
             curl={`curl $HARMAN_URL/v1/admin/risk \\
@@ -709,9 +713,9 @@ export default function DocsPage() {
             method="PUT"
             path="/v1/admin/sessions/:id/risk"
             scope="harman:admin"
-            description="Update risk limits for a session."
-            body={`{ "max_notional": "10000.00" }`}
-            response={`{ "session_id": "sess_001", "max_notional": "10000.00" }`}
+            description="Update risk limits for a session. Omitted limits reset to the global default."
+            body={`{ "max_notional": "10000.00", "max_open_contracts": "500" }`}
+            response={`{ "session_id": "sess_001", "max_notional": "10000.00", "max_open_contracts": "500" }`}
             curl={`curl -X PUT $HARMAN_URL/v1/admin/sessions/sess_001/risk \\
   -H "Authorization: Bearer $HARMAN_TOKEN" \\
   -H "Content-Type: application/json" \\
@@ -89,6 +89,10 @@ export interface RiskResponse {
   open_notional: string;
   max_notional: string;
   available_notional: string;
+  /** null when no contract cap is configured */
+  max_open_contracts: string | null;
+  open_contracts: string;
+  available_contracts: string | null;
 }
 
 export interface CreateOrderRequest {
@@ -0,0 +1,72 @@
+//! Subscription channel selection from feed config
+//!
+//! Feeds may narrow the set of channels a connector subscribes to via
+//! `channels` in the feed version. Absent means the connector's full set.
+
+use crate::error::ConnectorError;
+
+/// Resolve the channels to subscribe to.
+///
+/// `None` yields all of `supported`. An empty list or a channel not in
+/// `supported` is a config error; duplicates are dropped, order is kept.
+pub fn resolve_channels(
+    requested: Option<Vec<String>>,
+    supported: &[&str],
+) -> Result<Vec<String>, ConnectorError> {
+    let Some(requested) = requested else {
+        return Ok(supported.iter().map(|c| c.to_string()).collect());
+    };
+
+    if requested.is_empty() {
+        return Err(ConnectorError::InvalidConfig(
+            "channels must not be empty".to_string(),
+        ));
+    }
+
+    let mut channels: Vec<String> = Vec::with_capacity(requested.len());
+    for channel in requested {
+        if !supported.contains(&channel.as_str()) {
+            return Err(ConnectorError::InvalidConfig(format!(
+                "unsupported channel '{}' (supported: {})",
+                channel,
+                supported.join(", ")
+            )));
+        }
+        if !channels.contains(&channel) {
+            channels.push(channel);
+        }
+    }
+    Ok(channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPORTED: &[&str] = &["ticker", "trade"];
+
+    #[test]
+    fn test_none_yields_full_set() {
+        let channels = resolve_channels(None, SUPPORTED).unwrap();
+        assert_eq!(channels, vec!["ticker", "trade"]);
+    }
+
+    #[test]
+    fn test_subset_dedupes() {
+        let requested = vec!["trade".to_string(), "trade".to_string()];
+        let channels = resolve_channels(Some(requested), SUPPORTED).unwrap();
+        assert_eq!(channels, vec!["trade"]);
+    }
+
+    #[test]
+    fn test_empty_is_config_error() {
+        let err = resolve_channels(Some(vec![]), SUPPORTED).unwrap_err();
+        assert!(matches!(err, ConnectorError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_unknown_channel_is_config_error() {
+        let err = resolve_channels(Some(vec!["book".to_string()]), SUPPORTED).unwrap_err();
+        assert!(matches!(err, ConnectorError::InvalidConfig(_)));
+    }
+}
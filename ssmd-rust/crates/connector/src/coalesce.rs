@@ -0,0 +1,352 @@
+//! Optional outbound coalescing for NATS publishes
+//!
+//! Wraps a `Transport` and batches publishes per subject over a short window
+//! into a single publish. Batched payloads are the original messages joined
+//! with `\n` and carry a `Ssmd-Coalesced-Count` header; a window holding a
+//! single message is published unchanged. All publishes go through one
+//! flusher task, so ordering within a subject is preserved.
+//!
+//! Publishes whose only headers are capture metadata (`Ssmd-Capture-Ts`,
+//! `Ssmd-Source-Seq`) are still coalesced; a batch carries its first
+//! message's capture headers. Any other header bypasses coalescing.
+//!
+//! Payloads must not contain newlines (compact JSON is fine).
+
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use ssmd_middleware::{
+    MessageFilter, Subscription, Transport, TransportError, TransportMessage, CAPTURE_TS_HEADER,
+    SOURCE_SEQ_HEADER,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::error;
+
+use crate::metrics;
+
+/// Header carrying the number of messages in a coalesced payload
+pub const COALESCED_COUNT_HEADER: &str = "Ssmd-Coalesced-Count";
+
+/// Flush a subject early once its batch reaches this size (NATS default max
+/// payload is 1MB)
+const MAX_BATCH_BYTES: usize = 512 * 1024;
+
+/// Queue depth between publishers and the flusher task
+const CHANNEL_CAPACITY: usize = 4096;
+
+enum Outbound {
+    /// Eligible for coalescing; `headers` holds only capture metadata
+    Plain {
+        subject: String,
+        payload: Bytes,
+        headers: HashMap<String, String>,
+    },
+    /// Published as-is, after anything already pending on the subject
+    WithHeaders {
+        subject: String,
+        payload: Bytes,
+        headers: HashMap<String, String>,
+    },
+}
+
+struct Batch {
+    started: Instant,
+    payloads: Vec<Bytes>,
+    bytes: usize,
+    /// Capture headers of the first message in the batch
+    headers: HashMap<String, String>,
+}
+
+/// `Transport` decorator that coalesces publishes per subject.
+///
+/// Publish errors surface on the next publish call, since batches are sent
+/// from a background task.
+pub struct CoalescingTransport {
+    inner: Arc<dyn Transport>,
+    tx: mpsc::Sender<Outbound>,
+    failed: Arc<Mutex<Option<String>>>,
+}
+
+impl CoalescingTransport {
+    pub fn new(inner: Arc<dyn Transport>, feed: impl Into<String>, window: Duration) -> Self {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let failed = Arc::new(Mutex::new(None));
+        tokio::spawn(run_flusher(
+            Arc::clone(&inner),
+            feed.into(),
+            window,
+            rx,
+            Arc::clone(&failed),
+        ));
+        Self { inner, tx, failed }
+    }
+
+    async fn enqueue(&self, item: Outbound) -> Result<(), TransportError> {
+        if let Some(e) = self.failed.lock().unwrap().clone() {
+            return Err(TransportError::PublishFailed(e));
+        }
+        self.tx
+            .send(item)
+            .await
+            .map_err(|_| TransportError::PublishFailed("coalescer stopped".to_string()))
+    }
+}
+
+async fn flush_batch(
+    inner: &dyn Transport,
+    feed: &str,
+    subject: &str,
+    batch: Batch,
+) -> Result<(), TransportError> {
+    let count = batch.payloads.len();
+    if count == 1 {
+        let payload = batch.payloads.into_iter().next().unwrap_or_default();
+        if batch.headers.is_empty() {
+            return inner.publish(subject, payload).await;
+        }
+        return inner.publish_with_headers(subject, payload, batch.headers).await;
+    }
+
+    let mut joined = BytesMut::with_capacity(batch.bytes + count);
+    for (i, payload) in batch.payloads.iter().enumerate() {
+        if i > 0 {
+            joined.put_u8(b'\n');
+        }
+        joined.extend_from_slice(payload);
+    }
+    let mut headers = batch.headers;
+    headers.insert(COALESCED_COUNT_HEADER.to_string(), count.to_string());
+    inner
+        .publish_with_headers(subject, joined.freeze(), headers)
+        .await?;
+    metrics::inc_publisher_coalesced(feed, count as u64);
+    Ok(())
+}
+
+async fn run_flusher(
+    inner: Arc<dyn Transport>,
+    feed: String,
+    window: Duration,
+    mut rx: mpsc::Receiver<Outbound>,
+    failed: Arc<Mutex<Option<String>>>,
+) {
+    let mut pending: HashMap<String, Batch> = HashMap::new();
+
+    loop {
+        let next_deadline = pending.values().map(|b| b.started + window).min();
+
+        let result = tokio::select! {
+            item = rx.recv() => match item {
+                Some(Outbound::Plain { subject, payload, headers }) => {
+                    let batch = pending.entry(subject.clone()).or_insert_with(|| Batch {
+                        started: Instant::now(),
+                        payloads: Vec::new(),
+                        bytes: 0,
+                        headers,
+                    });
+                    batch.bytes += payload.len();
+                    batch.payloads.push(payload);
+                    if batch.bytes >= MAX_BATCH_BYTES {
+                        let batch = pending.remove(&subject).expect("batch just inserted");
+                        flush_batch(inner.as_ref(), &feed, &subject, batch).await
+                    } else {
+                        Ok(())
+                    }
+                }
+                Some(Outbound::WithHeaders { subject, payload, headers }) => {
+                    let flushed = match pending.remove(&subject) {
+                        Some(batch) => flush_batch(inner.as_ref(), &feed, &subject, batch).await,
+                        None => Ok(()),
+                    };
+                    match flushed {
+                        Ok(()) => inner.publish_with_headers(&subject, payload, headers).await,
+                        Err(e) => Err(e),
+                    }
+                }
+                None => {
+                    // All senders dropped: flush what's left and stop
+                    for (subject, batch) in pending.drain() {
+                        if let Err(e) = flush_batch(inner.as_ref(), &feed, &subject, batch).await {
+                            error!(error = %e, subject = %subject, "Coalesced publish failed on shutdown");
+                        }
+                    }
+                    return;
+                }
+            },
+            _ = async {
+                match next_deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                let now = Instant::now();
+                let due: Vec<String> = pending
+                    .iter()
+                    .filter(|(_, b)| b.started + window <= now)
+                    .map(|(s, _)| s.clone())
+                    .collect();
+                let mut result = Ok(());
+                for subject in due {
+                    if let Some(batch) = pending.remove(&subject) {
+                        result = flush_batch(inner.as_ref(), &feed, &subject, batch).await;
+                        if result.is_err() {
+                            break;
+                        }
+                    }
+                }
+                result
+            }
+        };
+
+        if let Err(e) = result {
+            error!(error = %e, "Coalesced publish failed");
+            *failed.lock().unwrap() = Some(e.to_string());
+            return;
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for CoalescingTransport {
+    async fn publish(&self, subject: &str, payload: Bytes) -> Result<(), TransportError> {
+        self.enqueue(Outbound::Plain {
+            subject: subject.to_string(),
+            payload,
+            headers: HashMap::new(),
+        })
+        .await
+    }
+
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        payload: Bytes,
+        headers: HashMap<String, String>,
+    ) -> Result<(), TransportError> {
+        let capture_only = headers
+            .keys()
+            .all(|k| k == CAPTURE_TS_HEADER || k == SOURCE_SEQ_HEADER);
+        if capture_only {
+            return self
+                .enqueue(Outbound::Plain {
+                    subject: subject.to_string(),
+                    payload,
+                    headers,
+                })
+                .await;
+        }
+        self.enqueue(Outbound::WithHeaders {
+            subject: subject.to_string(),
+            payload,
+            headers,
+        })
+        .await
+    }
+
+    async fn subscribe(&self, subject: &str) -> Result<Box<dyn Subscription>, TransportError> {
+        self.inner.subscribe(subject).await
+    }
+
+    async fn subscribe_filtered(
+        &self,
+        subject: &str,
+        filter: MessageFilter,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        self.inner.subscribe_filtered(subject, filter).await
+    }
+
+    async fn request(
+        &self,
+        subject: &str,
+        payload: Bytes,
+        timeout: Duration,
+    ) -> Result<TransportMessage, TransportError> {
+        self.inner.request(subject, payload, timeout).await
+    }
+
+    async fn health_check(&self) -> Result<(), TransportError> {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssmd_middleware::InMemoryTransport;
+
+    const WINDOW: Duration = Duration::from_millis(20);
+
+    async fn next(sub: &mut Box<dyn Subscription>) -> TransportMessage {
+        tokio::time::timeout(Duration::from_secs(1), sub.next())
+            .await
+            .expect("message within timeout")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_coalesces_per_subject_in_order() {
+        let inner = Arc::new(InMemoryTransport::new());
+        let transport = CoalescingTransport::new(inner.clone(), "test-feed", WINDOW);
+        let mut sub_a = inner.subscribe("dev.feed.a").await.unwrap();
+        let mut sub_b = inner.subscribe("dev.feed.b").await.unwrap();
+
+        for p in ["{\"n\":1}", "{\"n\":2}", "{\"n\":3}"] {
+            transport.publish("dev.feed.a", Bytes::from(p)).await.unwrap();
+        }
+        transport.publish("dev.feed.b", Bytes::from("{\"n\":9}")).await.unwrap();
+
+        let a = next(&mut sub_a).await;
+        assert_eq!(a.headers.get(COALESCED_COUNT_HEADER).map(String::as_str), Some("3"));
+        assert_eq!(a.payload.as_ref(), b"{\"n\":1}\n{\"n\":2}\n{\"n\":3}");
+
+        // Single-message window is published unchanged
+        let b = next(&mut sub_b).await;
+        assert!(b.headers.get(COALESCED_COUNT_HEADER).is_none());
+        assert_eq!(b.payload.as_ref(), b"{\"n\":9}");
+    }
+
+    #[tokio::test]
+    async fn test_capture_headers_still_coalesce() {
+        let inner = Arc::new(InMemoryTransport::new());
+        let transport = CoalescingTransport::new(inner.clone(), "test-feed", WINDOW);
+        let mut sub = inner.subscribe("dev.feed.a").await.unwrap();
+
+        for (p, ts) in [("1", "100"), ("2", "200")] {
+            let headers = HashMap::from([(CAPTURE_TS_HEADER.to_string(), ts.to_string())]);
+            transport
+                .publish_with_headers("dev.feed.a", Bytes::from(p), headers)
+                .await
+                .unwrap();
+        }
+
+        let batch = next(&mut sub).await;
+        assert_eq!(batch.payload.as_ref(), b"1\n2");
+        assert_eq!(batch.headers.get(COALESCED_COUNT_HEADER).map(String::as_str), Some("2"));
+        assert_eq!(batch.headers.get(CAPTURE_TS_HEADER).map(String::as_str), Some("100"));
+    }
+
+    #[tokio::test]
+    async fn test_headers_publish_flushes_pending_first() {
+        let inner = Arc::new(InMemoryTransport::new());
+        let transport = CoalescingTransport::new(inner.clone(), "test-feed", WINDOW);
+        let mut sub = inner.subscribe("dev.feed.a").await.unwrap();
+
+        transport.publish("dev.feed.a", Bytes::from("1")).await.unwrap();
+        transport.publish("dev.feed.a", Bytes::from("2")).await.unwrap();
+        let headers = HashMap::from([("Nats-Msg-Id".to_string(), "x".to_string())]);
+        transport
+            .publish_with_headers("dev.feed.a", Bytes::from("3"), headers)
+            .await
+            .unwrap();
+        transport.publish("dev.feed.a", Bytes::from("4")).await.unwrap();
+
+        assert_eq!(next(&mut sub).await.payload.as_ref(), b"1\n2");
+        let with_headers = next(&mut sub).await;
+        assert_eq!(with_headers.payload.as_ref(), b"3");
+        assert_eq!(with_headers.headers.get("Nats-Msg-Id").map(String::as_str), Some("x"));
+        assert_eq!(next(&mut sub).await.payload.as_ref(), b"4");
+    }
+}
@@ -10,6 +10,8 @@ pub enum ConnectorError {
     Disconnected(String),
     #[error("write failed: {0}")]
     WriteFailed(String),
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
 }
 
 #[derive(Error, Debug)]
@@ -18,6 +20,13 @@ pub enum WriterError {
     WriteFailed(String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+    /// One sink of a `TeeWriter` failed
+    #[error("{sink} sink: {source}")]
+    Sink {
+        sink: &'static str,
+        #[source]
+        source: Box<WriterError>,
+    },
 }
 
 #[derive(Error, Debug)]
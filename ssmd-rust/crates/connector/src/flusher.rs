@@ -9,6 +9,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use crate::metrics::record_ring_stats;
 use crate::ring_buffer::RingBuffer;
 
 /// Batch size: drain up to this many messages before yielding
@@ -27,6 +28,8 @@ pub struct DiskFlusher {
     feed_name: String,
     current_writer: Option<BufWriter<File>>,
     current_date: String,
+    /// Ring overflow count already exported to metrics
+    reported_overflows: u64,
 }
 
 impl DiskFlusher {
@@ -38,6 +41,7 @@ impl DiskFlusher {
             feed_name,
             current_writer: None,
             current_date: String::new(),
+            reported_overflows: 0,
         }
     }
 
@@ -49,6 +53,7 @@ impl DiskFlusher {
 
             if count > 0 {
                 self.flush();
+                self.report_ring_stats();
             } else {
                 // Ring empty, sleep briefly to avoid busy-spin
                 std::thread::sleep(std::time::Duration::from_micros(EMPTY_SLEEP_MICROS));
@@ -58,6 +63,18 @@ impl DiskFlusher {
         // Shutdown: drain all remaining messages
         self.drain_all();
         self.flush();
+        self.report_ring_stats();
+    }
+
+    /// Export producer-side drops and peak occupancy to metrics
+    fn report_ring_stats(&mut self) {
+        let overflows = self.ring.overflow_count();
+        record_ring_stats(
+            &self.feed_name,
+            overflows - self.reported_overflows,
+            self.ring.high_watermark(),
+        );
+        self.reported_overflows = overflows;
     }
 
     /// Drain up to BATCH_SIZE messages from ring
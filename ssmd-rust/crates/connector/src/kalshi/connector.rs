@@ -21,6 +21,7 @@
 //! manager routes new subscriptions to shards with available capacity.
 
 use crate::error::ConnectorError;
+use crate::keepalive::Keepalive;
 use crate::kalshi::auth::KalshiCredentials;
 use crate::kalshi::cdc_consumer::{CdcConfig as CdcConsumerConfig, CdcSubscriptionConsumer};
 use crate::kalshi::shard_manager::{ShardEvent, ShardManager};
@@ -34,10 +35,14 @@ use ssmd_metadata::{CdcConfig, LifecycleConfig, SecmasterConfig, SubscriptionCon
 use ssmd_middleware::now_tsc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::task::JoinSet;
 use tracing::{debug, error, info, trace, warn};
 
+/// Default WS ping interval
+pub const PING_INTERVAL_SECS: u64 = 30;
+
 /// Commands that can be sent to a shard's receiver task
 #[derive(Debug)]
 pub enum ShardCommand {
@@ -67,6 +72,8 @@ pub struct KalshiConnector {
     lifecycle_config: Option<LifecycleConfig>,
     /// NATS URL for CDC (from transport config)
     nats_url: Option<String>,
+    /// WS ping interval and pong timeout
+    keepalive: Keepalive,
     tx: Option<mpsc::Sender<TimestampedMsg>>,
     rx: Option<mpsc::Receiver<TimestampedMsg>>,
     /// Last WebSocket activity timestamp (epoch seconds) - updated ONLY on received data/pong, never on ping send
@@ -150,6 +157,7 @@ impl KalshiConnector {
             cdc_config: None,
             lifecycle_config: None,
             nats_url: None,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -183,6 +191,7 @@ impl KalshiConnector {
             cdc_config: None,
             lifecycle_config: None,
             nats_url: None,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -217,6 +226,7 @@ impl KalshiConnector {
             cdc_config: Some(cdc_config),
             lifecycle_config: None,
             nats_url: Some(nats_url),
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -244,6 +254,7 @@ impl KalshiConnector {
             cdc_config: None,
             lifecycle_config: Some(lifecycle_config),
             nats_url: None,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -251,6 +262,12 @@ impl KalshiConnector {
         }
     }
 
+    /// Override the ping interval and pong timeout
+    pub fn with_keepalive(mut self, keepalive: Keepalive) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
     /// Subscribe globally to all markets (original behavior)
     async fn subscribe_global(&self, ws: &mut KalshiWebSocket) -> Result<(), ConnectorError> {
         info!("Using global subscription (all markets)");
@@ -401,9 +418,11 @@ impl KalshiConnector {
     /// The task is added to the provided JoinSet so the runner can detect exits/panics
     /// instead of silently losing data from fire-and-forget spawns.
     /// Optionally accepts a command receiver for dynamic subscription updates (CDC).
+    #[allow(clippy::too_many_arguments)]
     fn spawn_receiver_task(
         task_set: &mut JoinSet<()>,
         mut ws: KalshiWebSocket,
+        keepalive: Keepalive,
         tx: mpsc::Sender<TimestampedMsg>,
         activity_tracker: Arc<AtomicU64>,
         shard_id: usize,
@@ -432,10 +451,8 @@ impl KalshiConnector {
         shard_metrics.set_connected();
 
         task_set.spawn(async move {
-            use std::time::Duration;
             use tokio::time::{interval, Instant};
 
-            const PING_INTERVAL_SECS: u64 = 30;
             const CMD_CHECK_INTERVAL_SECS: u64 = 5;
             /// If no data or pong received for this long, the connection is dead.
             /// Must be > PING_INTERVAL_SECS to allow at least one ping/pong round-trip.
@@ -445,7 +462,7 @@ impl KalshiConnector {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(DEFAULT_RECV_STALENESS_SECS);
 
-            let mut ping_interval = interval(Duration::from_secs(PING_INTERVAL_SECS));
+            let mut ping_interval = interval(keepalive.ping_interval);
             ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
             let mut cmd_check_interval = interval(Duration::from_secs(CMD_CHECK_INTERVAL_SECS));
@@ -469,6 +486,22 @@ impl KalshiConnector {
                         debug!(shard_id, idle_secs, "Sending WebSocket ping keepalive");
                         shard_metrics.set_idle_seconds(idle_secs as f64);
 
+                        // A pong for our own ping is overdue even though data may still
+                        // be flowing — the link is degrading, restart before it drops.
+                        if ws.pongs().timed_out(keepalive.pong_timeout) {
+                            let uptime_secs = connected_at.elapsed().as_secs();
+                            error!(
+                                shard_id,
+                                uptime_secs,
+                                message_count,
+                                pong_timeout_secs = keepalive.pong_timeout.as_secs(),
+                                reason = "pong_timeout",
+                                "Kalshi pong overdue, exiting for restart"
+                            );
+                            shard_metrics.set_disconnected();
+                            std::process::exit(1);
+                        }
+
                         // Check if we've received ANY data or pong recently.
                         // A successful ping send only means the OS TCP buffer accepted it —
                         // it does NOT prove the remote end is alive. Only received data or
@@ -847,6 +880,7 @@ impl Connector for KalshiConnector {
                     Self::spawn_receiver_task(
                         &mut task_set,
                         ws,
+                        self.keepalive,
                         tx.clone(),
                         Arc::clone(&activity_tracker),
                         shard_id,
@@ -938,7 +972,7 @@ impl Connector for KalshiConnector {
                 self.subscribe_global(&mut ws).await?;
                 let shard_metrics = connector_metrics.for_shard(0);
                 shard_metrics.init(&["ticker", "trade", "orderbook", "lifecycle", "event_lifecycle"]);
-                Self::spawn_receiver_task(&mut task_set, ws, tx, activity_tracker, 0, shard_metrics, None);
+                Self::spawn_receiver_task(&mut task_set, ws, self.keepalive, tx, activity_tracker, 0, shard_metrics, None);
             }
         } else if let Some(ref lifecycle) = self.lifecycle_config {
             if lifecycle.enabled {
@@ -954,7 +988,7 @@ impl Connector for KalshiConnector {
                 self.subscribe_lifecycle_only(&mut ws).await?;
                 let shard_metrics = connector_metrics.for_shard(0);
                 shard_metrics.init(&["lifecycle", "event_lifecycle"]);
-                Self::spawn_receiver_task(&mut task_set, ws, tx, activity_tracker, 0, shard_metrics, None);
+                Self::spawn_receiver_task(&mut task_set, ws, self.keepalive, tx, activity_tracker, 0, shard_metrics, None);
             } else {
                 return Err(ConnectorError::ConnectionFailed(
                     "Lifecycle config present but disabled".to_string()
@@ -973,7 +1007,7 @@ impl Connector for KalshiConnector {
             self.subscribe_global(&mut ws).await?;
             let shard_metrics = connector_metrics.for_shard(0);
             shard_metrics.init(&["ticker", "trade", "orderbook", "lifecycle", "event_lifecycle"]);
-            Self::spawn_receiver_task(&mut task_set, ws, tx, activity_tracker, 0, shard_metrics, None);
+            Self::spawn_receiver_task(&mut task_set, ws, self.keepalive, tx, activity_tracker, 0, shard_metrics, None);
         }
 
         self.task_set = Some(task_set);
@@ -4,6 +4,7 @@
 
 use crate::kalshi::auth::{AuthError, KalshiCredentials};
 use crate::kalshi::messages::{WsCommand, WsMessage, WsParams};
+use crate::keepalive::PongTracker;
 use futures_util::{SinkExt, StreamExt};
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -143,6 +144,8 @@ pub struct KalshiWebSocket {
     /// the server replies to our Ping. Shared with the connector loop so the
     /// staleness check can verify the remote end is actually alive.
     pong_tracker: Arc<AtomicU64>,
+    /// Outstanding client pings, for pong latency and the pong timeout
+    pongs: PongTracker,
 }
 
 impl KalshiWebSocket {
@@ -220,6 +223,7 @@ impl KalshiWebSocket {
             subscribed_markets: HashSet::new(),
             sid_tracker: SidTracker::new(),
             pong_tracker: Arc::new(AtomicU64::new(0)),
+            pongs: PongTracker::new("kalshi"),
         })
     }
 
@@ -532,7 +536,8 @@ impl KalshiWebSocket {
                     self.update_pong_tracker();
                 }
                 Ok(Some(Ok(Message::Pong(_)))) => {
-                    trace!("Received pong");
+                    let rtt = self.pongs.pong_received();
+                    trace!(?rtt, "Received pong");
                     self.update_pong_tracker();
                 }
                 Ok(Some(Ok(Message::Close(frame)))) => {
@@ -675,9 +680,15 @@ impl KalshiWebSocket {
     /// Send a ping to keep connection alive
     pub async fn ping(&mut self) -> Result<(), WebSocketError> {
         self.ws.send(Message::Ping(vec![])).await?;
+        self.pongs.ping_sent();
         Ok(())
     }
 
+    /// Pongs outstanding for pings sent on this connection
+    pub fn pongs(&self) -> &PongTracker {
+        &self.pongs
+    }
+
     /// Get an Arc handle to the pong tracker for external staleness checks.
     /// The value is epoch seconds of the last received Pong (or server Ping) frame.
     /// The connector loop's staleness check (RECV_STALENESS_SECS in connector.rs)
@@ -0,0 +1,108 @@
+//! WebSocket ping/pong keepalive shared by the connectors.
+//!
+//! Each connector pings on its own schedule (WS ping frames or the exchange's
+//! app-level ping) and feeds ping sends and pong arrivals into a
+//! [`PongTracker`]. A ping left unanswered past the pong timeout means the
+//! link is dead even if the socket still looks open; round-trip times go to
+//! `ssmd_connector_pong_latency_seconds` so degrading links show up first.
+
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::metrics::observe_pong_latency;
+
+/// Ping schedule and how long to wait for the matching pong
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keepalive {
+    pub ping_interval: Duration,
+    pub pong_timeout: Duration,
+}
+
+impl Keepalive {
+    /// Ping every `ping_interval`, allowing two intervals for the pong
+    pub fn every(ping_interval: Duration) -> Self {
+        Self {
+            ping_interval,
+            pong_timeout: ping_interval * 2,
+        }
+    }
+
+    pub fn with_pong_timeout(mut self, pong_timeout: Duration) -> Self {
+        self.pong_timeout = pong_timeout;
+        self
+    }
+}
+
+/// Tracks the oldest unanswered ping for one connection
+#[derive(Debug)]
+pub struct PongTracker {
+    feed: String,
+    outstanding_since: Option<Instant>,
+}
+
+impl PongTracker {
+    pub fn new(feed: impl Into<String>) -> Self {
+        Self {
+            feed: feed.into(),
+            outstanding_since: None,
+        }
+    }
+
+    /// Record a ping send. While a ping is unanswered, later pings do not
+    /// reset the clock, so a dead link still times out.
+    pub fn ping_sent(&mut self) {
+        self.outstanding_since.get_or_insert_with(Instant::now);
+    }
+
+    /// Record a pong, returning the round-trip time if a ping was outstanding
+    pub fn pong_received(&mut self) -> Option<Duration> {
+        let rtt = self.outstanding_since.take()?.elapsed();
+        observe_pong_latency(&self.feed, rtt.as_secs_f64());
+        Some(rtt)
+    }
+
+    /// How long the oldest ping has gone unanswered
+    pub fn awaiting(&self) -> Option<Duration> {
+        self.outstanding_since.map(|t| t.elapsed())
+    }
+
+    /// True if a ping has gone unanswered for longer than `timeout`
+    pub fn timed_out(&self, timeout: Duration) -> bool {
+        self.awaiting().is_some_and(|waited| waited > timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_defaults_pong_timeout() {
+        let k = Keepalive::every(Duration::from_secs(15));
+        assert_eq!(k.ping_interval, Duration::from_secs(15));
+        assert_eq!(k.pong_timeout, Duration::from_secs(30));
+
+        let k = k.with_pong_timeout(Duration::from_secs(5));
+        assert_eq!(k.pong_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_pong_latency_and_timeout() {
+        let mut pongs = PongTracker::new("test-feed");
+        assert!(pongs.pong_received().is_none());
+        assert!(!pongs.timed_out(Duration::ZERO));
+
+        pongs.ping_sent();
+        std::thread::sleep(Duration::from_millis(20));
+        let rtt = pongs.pong_received().expect("ping outstanding");
+        assert!(rtt >= Duration::from_millis(20));
+        assert!(pongs.awaiting().is_none());
+
+        // A second unanswered ping keeps the first send time
+        pongs.ping_sent();
+        std::thread::sleep(Duration::from_millis(20));
+        pongs.ping_sent();
+        assert!(pongs.timed_out(Duration::from_millis(10)));
+        assert!(!pongs.timed_out(Duration::from_secs(10)));
+    }
+}
@@ -3,7 +3,9 @@
 //! Implements the ssmd Connector trait for Kraken v2 WebSocket.
 //! Much simpler than Kalshi - no auth, no sharding, no CDC.
 
+use crate::channels::resolve_channels;
 use crate::error::ConnectorError;
+use crate::keepalive::{Keepalive, PongTracker};
 use crate::kraken::messages::KrakenWsMessage;
 use crate::kraken::websocket::{KrakenWebSocket, KrakenWebSocketError};
 use crate::metrics::ConnectorMetrics;
@@ -12,16 +14,26 @@ use async_trait::async_trait;
 use ssmd_middleware::now_tsc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, trace};
 
+/// Channels subscribed by default
+pub const KRAKEN_CHANNELS: &[&str] = &["ticker", "trade"];
+
+/// Default app-level ping interval
+pub const PING_INTERVAL_SECS: u64 = 30;
+
 /// Kraken connector implementing the ssmd Connector trait
 pub struct KrakenConnector {
     symbols: Vec<String>,
+    /// Channels to subscribe to (subset of `KRAKEN_CHANNELS`)
+    channels: Vec<String>,
     /// Feed name for metrics labels (e.g., "kraken-spot", "kraken")
     feed_name: String,
     /// WebSocket URL override from feed config (None = use default constant)
     ws_url: Option<String>,
+    keepalive: Keepalive,
     tx: Option<mpsc::Sender<TimestampedMsg>>,
     rx: Option<mpsc::Receiver<TimestampedMsg>>,
     /// Last WebSocket activity timestamp (epoch seconds)
@@ -39,17 +51,33 @@ impl KrakenConnector {
         let (tx, rx) = mpsc::channel(1000);
         Self {
             symbols,
+            channels: KRAKEN_CHANNELS.iter().map(|c| c.to_string()).collect(),
             feed_name,
             ws_url,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Restrict subscriptions to the given channels (`None` = all)
+    pub fn with_channels(mut self, channels: Option<Vec<String>>) -> Result<Self, ConnectorError> {
+        self.channels = resolve_channels(channels, KRAKEN_CHANNELS)?;
+        Ok(self)
+    }
+
+    /// Override the ping interval and pong timeout
+    pub fn with_keepalive(mut self, keepalive: Keepalive) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
     /// Spawn the WebSocket receiver task
     fn spawn_receiver_task(
         mut ws: KrakenWebSocket,
+        keepalive: Keepalive,
+        mut pongs: PongTracker,
         tx: mpsc::Sender<TimestampedMsg>,
         activity_tracker: Arc<AtomicU64>,
         shard_metrics: crate::metrics::ShardMetrics,
@@ -67,23 +95,28 @@ impl KrakenConnector {
         update_activity(&activity_tracker);
 
         tokio::spawn(async move {
-            use std::time::Duration;
             use tokio::time::interval;
 
-            const PING_INTERVAL_SECS: u64 = 30;
-
-            let mut ping_interval = interval(Duration::from_secs(PING_INTERVAL_SECS));
+            let mut ping_interval = interval(keepalive.ping_interval);
             ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
             loop {
                 tokio::select! {
                     // Ping timer - send app-level ping
                     _ = ping_interval.tick() => {
+                        if pongs.timed_out(keepalive.pong_timeout) {
+                            error!(
+                                pong_timeout_secs = keepalive.pong_timeout.as_secs(),
+                                "Kraken pong overdue, exiting for restart"
+                            );
+                            std::process::exit(1);
+                        }
                         trace!("Sending Kraken app-level ping");
                         if let Err(e) = ws.ping().await {
                             error!(error = %e, "Failed to send Kraken ping, connection may be dead");
                             break;
                         }
+                        pongs.ping_sent();
                         update_activity(&activity_tracker);
                     }
 
@@ -110,7 +143,8 @@ impl KrakenConnector {
                                         false
                                     }
                                     KrakenWsMessage::Pong { .. } => {
-                                        trace!("Kraken pong received");
+                                        let rtt = pongs.pong_received();
+                                        trace!(?rtt, "Kraken pong received");
                                         false
                                     }
                                     KrakenWsMessage::SubscriptionResult { .. } => {
@@ -164,38 +198,43 @@ impl Connector for KrakenConnector {
         let connector_metrics = ConnectorMetrics::new(&self.feed_name, "spot");
         connector_metrics.set_shards_total(1);
         // Pre-init MESSAGES_TOTAL so the feed label exists in Prometheus
-        connector_metrics.for_shard(0).init(&["ticker", "trade"]);
+        connector_metrics.for_shard(0).init(KRAKEN_CHANNELS);
 
         // Connect to Kraken WS
         let mut ws = KrakenWebSocket::connect(self.ws_url.as_deref())
             .await
             .map_err(|e| ConnectorError::ConnectionFailed(e.to_string()))?;
 
-        // Subscribe to ticker channel (Kraken sends one result per symbol)
-        info!(symbols = ?self.symbols, count = self.symbols.len(), "Subscribing to Kraken ticker channel");
-        let ticker_symbols = ws.subscribe("ticker", &self.symbols)
-            .await
-            .map_err(|e| ConnectorError::ConnectionFailed(format!("ticker subscription: {}", e)))?;
-
-        // Subscribe to trade channel
-        info!(symbols = ?self.symbols, count = self.symbols.len(), "Subscribing to Kraken trade channel");
-        let trade_symbols = ws.subscribe("trade", &self.symbols)
-            .await
-            .map_err(|e| ConnectorError::ConnectionFailed(format!("trade subscription: {}", e)))?;
+        // Subscribe to each configured channel (Kraken sends one result per symbol)
+        let mut subscribed = 0;
+        for channel in &self.channels {
+            info!(channel = %channel, symbols = ?self.symbols, count = self.symbols.len(), "Subscribing to Kraken channel");
+            let symbols = ws.subscribe(channel, &self.symbols)
+                .await
+                .map_err(|e| ConnectorError::ConnectionFailed(format!("{} subscription: {}", channel, e)))?;
+            subscribed = subscribed.max(symbols.len());
+        }
 
-        // Update metrics with actual subscribed count (use ticker as the canonical count)
-        connector_metrics.set_markets_subscribed(0, ticker_symbols.len());
+        // Update metrics with actual subscribed count
+        connector_metrics.set_markets_subscribed(0, subscribed);
 
         info!(
-            ticker_subscribed = ticker_symbols.len(),
-            trade_subscribed = trade_symbols.len(),
+            channels = ?self.channels,
+            subscribed,
             requested = self.symbols.len(),
-            "Kraken connector subscribed to ticker and trade channels"
+            "Kraken connector subscribed"
         );
 
         // Spawn receiver task with shard metrics for message counting
         let shard_metrics = connector_metrics.for_shard(0);
-        Self::spawn_receiver_task(ws, tx, activity_tracker, shard_metrics);
+        Self::spawn_receiver_task(
+            ws,
+            self.keepalive,
+            PongTracker::new(self.feed_name.clone()),
+            tx,
+            activity_tracker,
+            shard_metrics,
+        );
 
         Ok(())
     }
@@ -243,4 +282,19 @@ mod tests {
         let handle = connector.activity_handle();
         assert!(handle.is_some());
     }
+    #[test]
+    fn test_connector_channels() {
+        let symbols = vec!["BTC/USD".to_string()];
+        let connector = KrakenConnector::new(symbols.clone(), None);
+        assert_eq!(connector.channels, vec!["ticker", "trade"]);
+
+        let connector = KrakenConnector::new(symbols.clone(), None)
+            .with_channels(Some(vec!["trade".to_string()]))
+            .unwrap();
+        assert_eq!(connector.channels, vec!["trade"]);
+
+        assert!(KrakenConnector::new(symbols, None)
+            .with_channels(Some(vec![]))
+            .is_err());
+    }
 }
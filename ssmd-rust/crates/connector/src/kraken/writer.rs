@@ -1,18 +1,22 @@
 //! Kraken NATS Writer - publishes raw JSON messages to NATS
 //!
 //! Routes Kraken ticker and trade messages to appropriate NATS subjects.
-//! Passes through raw bytes - no transformation.
+//! Each frame is deserialized into [`KrakenWsMessage`] and its typed data
+//! before publishing; frames that don't parse are dropped and counted in
+//! `ssmd_connector_writer_dropped_frames_total`. Published payloads are the
+//! raw bytes - no transformation.
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use serde::Deserialize;
 use tracing::{trace, warn};
 
 use ssmd_middleware::{sanitize_subject_token, SubjectBuilder, Transport};
 
 use crate::error::WriterError;
+use crate::kraken::messages::{KrakenTickerData, KrakenTradeData, KrakenWsMessage};
 use crate::message::Message;
+use crate::metrics::{self, DROPPED_MALFORMED, DROPPED_UNKNOWN_VARIANT};
 use crate::traits::Writer;
 
 /// Writer that publishes raw Kraken JSON messages to NATS
@@ -55,47 +59,56 @@ impl KrakenNatsWriter {
     }
 }
 
-#[derive(Deserialize)]
-struct PartialKrakenMsg<'a> {
-    channel: Option<&'a str>,
-    #[serde(borrow)]
-    data: Option<Vec<PartialKrakenData<'a>>>,
-    method: Option<&'a str>,
-}
-
-#[derive(Deserialize)]
-struct PartialKrakenData<'a> {
-    symbol: Option<&'a str>,
+/// Log and count a frame that did not deserialize into Kraken's types
+fn drop_frame(msg: &Message, reason: &'static str, error: &dyn std::fmt::Display) {
+    let preview: String = String::from_utf8_lossy(&msg.data)
+        .chars()
+        .take(500)
+        .collect();
+    warn!(reason, error = %error, preview = %preview, "Dropping Kraken frame");
+    metrics::inc_writer_dropped_frame(&msg.feed, reason);
 }
 
 #[async_trait]
 impl Writer for KrakenNatsWriter {
     async fn write(&mut self, msg: &Message) -> Result<(), WriterError> {
-        // FAST PATH: Parse just enough to extract channel and symbol using borrowed strings.
-        // Bypasses untagged enum and serde_json::Value overhead.
-        let partial: PartialKrakenMsg = match serde_json::from_slice(&msg.data) {
+        // Deserialize fully so upstream format changes are caught here rather
+        // than downstream. Bad frames are counted and dropped, never fatal.
+        let parsed: KrakenWsMessage = match serde_json::from_slice(&msg.data) {
             Ok(m) => m,
             Err(e) => {
-                let preview: String = String::from_utf8_lossy(&msg.data)
-                    .chars()
-                    .take(500)
-                    .collect();
-                return Err(WriterError::WriteFailed(format!(
-                    "Failed to parse Kraken message: {}. Preview: {}",
-                    e, preview
-                )));
+                // Valid JSON that matches no variant is a message type we don't model
+                let reason = if e.is_data() {
+                    DROPPED_UNKNOWN_VARIANT
+                } else {
+                    DROPPED_MALFORMED
+                };
+                drop_frame(msg, reason, &e);
+                return Ok(());
             }
         };
 
-        let channel = partial.channel.unwrap_or("");
+        let (channel, data) = match parsed {
+            KrakenWsMessage::ChannelMessage { channel, data, .. } => (channel, data),
+            // Control frames: heartbeat, pong, subscribe results
+            KrakenWsMessage::Heartbeat { .. }
+            | KrakenWsMessage::Pong { .. }
+            | KrakenWsMessage::SubscriptionResult { .. } => return Ok(()),
+        };
 
-        let subject = match channel {
+        let subject = match channel.as_str() {
             "trade" => {
-                let symbol = partial.data.as_ref()
-                    .and_then(|d| d.first())
-                    .and_then(|item| item.symbol)
-                    .unwrap_or("unknown");
-                let sanitized = sanitize_subject_token(symbol);
+                let trades: Vec<KrakenTradeData> =
+                    match serde_json::from_value(serde_json::Value::Array(data)) {
+                        Ok(t) => t,
+                        Err(e) => {
+                            drop_frame(msg, DROPPED_MALFORMED, &e);
+                            return Ok(());
+                        }
+                    };
+                let sanitized = sanitize_subject_token(
+                    trades.first().map(|t| t.symbol.as_str()).unwrap_or("unknown"),
+                );
                 if sanitized.is_empty() {
                     warn!(channel = %channel, "Empty sanitized symbol, skipping");
                     return Ok(());
@@ -103,31 +116,34 @@ impl Writer for KrakenNatsWriter {
                 self.subjects.json_trade(&sanitized)
             }
             "ticker" => {
-                let symbol = partial.data.as_ref()
-                    .and_then(|d| d.first())
-                    .and_then(|item| item.symbol)
-                    .unwrap_or("unknown");
-                let sanitized = sanitize_subject_token(symbol);
+                let tickers: Vec<KrakenTickerData> =
+                    match serde_json::from_value(serde_json::Value::Array(data)) {
+                        Ok(t) => t,
+                        Err(e) => {
+                            drop_frame(msg, DROPPED_MALFORMED, &e);
+                            return Ok(());
+                        }
+                    };
+                let sanitized = sanitize_subject_token(
+                    tickers.first().map(|t| t.symbol.as_str()).unwrap_or("unknown"),
+                );
                 if sanitized.is_empty() {
                     warn!(channel = %channel, "Empty sanitized symbol, skipping");
                     return Ok(());
                 }
                 self.subjects.json_ticker(&sanitized)
             }
-            "heartbeat" => return Ok(()),
+            // Connection status sent once on connect
+            "status" => return Ok(()),
             _ => {
-                // Check for non-channel messages like "pong" or "subscribe" via "method" field
-                if partial.method.is_some() {
-                    return Ok(());
-                }
-                trace!(channel = %channel, "Skipping unknown Kraken channel");
+                drop_frame(msg, DROPPED_UNKNOWN_VARIANT, &format!("unknown channel {}", channel));
                 return Ok(());
             }
         };
 
         // Publish raw bytes - no transformation
         self.transport
-            .publish(&subject, msg.data.clone())
+            .publish_with_headers(&subject, msg.data.clone(), msg.capture_headers(None))
             .await
             .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
@@ -260,6 +276,34 @@ mod tests {
         assert_eq!(writer.message_count(), 2);
     }
 
+    #[tokio::test]
+    async fn test_drops_malformed_and_unknown_frames() {
+        let transport = Arc::new(InMemoryTransport::new());
+        let mut writer = KrakenNatsWriter::new(transport.clone(), "dev", "kraken");
+
+        // Not JSON, a trade missing its price, and a shape we don't model
+        let frames: [&[u8]; 3] = [
+            b"not json",
+            br#"{"channel":"trade","type":"update","data":[{"symbol":"BTC/USD","side":"buy"}]}"#,
+            br#"{"event":"systemStatus","status":"online"}"#,
+        ];
+        for frame in frames {
+            writer
+                .write(&Message::new("kraken-drop-test", frame.to_vec()))
+                .await
+                .expect("bad frames are dropped, not fatal");
+        }
+        assert_eq!(writer.message_count(), 0);
+
+        let output = metrics::encode_metrics().unwrap();
+        assert!(output.contains(
+            r#"ssmd_connector_writer_dropped_frames_total{feed="kraken-drop-test",reason="malformed"} 2"#
+        ));
+        assert!(output.contains(
+            r#"ssmd_connector_writer_dropped_frames_total{feed="kraken-drop-test",reason="unknown_variant"} 1"#
+        ));
+    }
+
     #[tokio::test]
     async fn test_with_prefix() {
         let transport = Arc::new(InMemoryTransport::new());
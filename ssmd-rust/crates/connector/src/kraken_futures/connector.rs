@@ -2,28 +2,37 @@
 //!
 //! Spawns a receiver task that:
 //! 1. Connects to wss://futures.kraken.com/ws/v1
-//! 2. Subscribes to "trade" and "ticker" feeds for configured product IDs
-//! 3. Sends pings every 15s
+//! 2. Subscribes to the configured feeds ("trade" and "ticker" by default) for configured product IDs
+//! 3. Sends pings every 15s (configurable) and exits if a pong is overdue
 //! 4. Forwards data messages to the MPSC channel
 //! 5. Tracks last activity time for health checks
 
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, trace, warn};
 
 use super::messages::KrakenFuturesWsMessage;
 use super::websocket::{KrakenFuturesWebSocket, KrakenFuturesWsError, PING_INTERVAL_SECS};
+use crate::channels::resolve_channels;
 use crate::error::ConnectorError;
+use crate::keepalive::Keepalive;
 use crate::metrics::{ConnectorMetrics, ShardMetrics};
 use crate::traits::{Connector, TimestampedMsg};
 use ssmd_middleware::now_tsc;
 
+/// Feeds subscribed by default
+pub const KRAKEN_FUTURES_CHANNELS: &[&str] = &["trade", "ticker"];
+
 pub struct KrakenFuturesConnector {
     product_ids: Vec<String>,
+    /// Feeds to subscribe to (subset of `KRAKEN_FUTURES_CHANNELS`)
+    channels: Vec<String>,
     /// WebSocket URL override from feed config (None = use default constant)
     ws_url: Option<String>,
+    keepalive: Keepalive,
     tx: Option<mpsc::Sender<TimestampedMsg>>,
     rx: Option<mpsc::Receiver<TimestampedMsg>>,
     last_ws_activity_epoch_secs: Arc<AtomicU64>,
@@ -34,16 +43,31 @@ impl KrakenFuturesConnector {
         let (tx, rx) = mpsc::channel(4096);
         Self {
             product_ids,
+            channels: KRAKEN_FUTURES_CHANNELS.iter().map(|c| c.to_string()).collect(),
             ws_url,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Restrict subscriptions to the given feeds (`None` = all)
+    pub fn with_channels(mut self, channels: Option<Vec<String>>) -> Result<Self, ConnectorError> {
+        self.channels = resolve_channels(channels, KRAKEN_FUTURES_CHANNELS)?;
+        Ok(self)
+    }
+
+    /// Override the ping interval and pong timeout
+    pub fn with_keepalive(mut self, keepalive: Keepalive) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
     fn spawn_receiver_task(
         mut ws: KrakenFuturesWebSocket,
         _product_ids: Vec<String>,
+        keepalive: Keepalive,
         tx: mpsc::Sender<TimestampedMsg>,
         last_activity: Arc<AtomicU64>,
         shard_metrics: ShardMetrics,
@@ -66,13 +90,12 @@ impl KrakenFuturesConnector {
         update_activity(&last_activity, &shard_metrics, 0.0);
 
         tokio::spawn(async move {
-            use std::time::Duration;
             use tokio::time::{interval, Instant};
 
             let connected_at = Instant::now();
             let mut message_count: u64 = 0;
 
-            let mut ping_interval = interval(Duration::from_secs(PING_INTERVAL_SECS));
+            let mut ping_interval = interval(keepalive.ping_interval);
             ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
             let mut last_activity_instant = Instant::now();
@@ -84,6 +107,18 @@ impl KrakenFuturesConnector {
                         let idle_secs = last_activity_instant.elapsed().as_secs();
                         debug!(idle_secs, "Sending Kraken Futures ping");
                         shard_metrics.set_idle_seconds(idle_secs as f64);
+                        if ws.pongs().timed_out(keepalive.pong_timeout) {
+                            let uptime_secs = connected_at.elapsed().as_secs();
+                            error!(
+                                uptime_secs,
+                                message_count,
+                                pong_timeout_secs = keepalive.pong_timeout.as_secs(),
+                                reason = "pong_timeout",
+                                "Kraken Futures pong overdue, exiting for restart"
+                            );
+                            shard_metrics.set_disconnected();
+                            std::process::exit(1);
+                        }
                         if let Err(e) = ws.ping().await {
                             let uptime_secs = connected_at.elapsed().as_secs();
                             error!(
@@ -183,17 +218,18 @@ impl Connector for KrakenFuturesConnector {
             .await
             .map_err(|e| ConnectorError::ConnectionFailed(e.to_string()))?;
 
-        // Subscribe to trade and ticker feeds
-        for feed in &["trade", "ticker"] {
+        // Subscribe to each configured feed
+        for feed in &self.channels {
             let result: Result<(), KrakenFuturesWsError> = ws.subscribe(feed, &self.product_ids).await;
             result.map_err(|e| ConnectorError::ConnectionFailed(format!("{} subscription: {}", feed, e)))?;
         }
 
-        info!(products = ?self.product_ids, "Kraken Futures connector started");
+        info!(products = ?self.product_ids, channels = ?self.channels, "Kraken Futures connector started");
 
         Self::spawn_receiver_task(
             ws,
             self.product_ids.clone(),
+            self.keepalive,
             tx,
             Arc::clone(&self.last_ws_activity_epoch_secs),
             shard_metrics,
@@ -239,6 +275,19 @@ mod tests {
         assert!(connector.rx.is_none());
     }
 
+    #[test]
+    fn test_connector_channels() {
+        let products = vec!["PF_XBTUSD".to_string()];
+        let connector = KrakenFuturesConnector::new(products.clone(), None)
+            .with_channels(Some(vec!["ticker".to_string()]))
+            .unwrap();
+        assert_eq!(connector.channels, vec!["ticker"]);
+
+        assert!(KrakenFuturesConnector::new(products, None)
+            .with_channels(Some(vec![]))
+            .is_err());
+    }
+
     #[test]
     fn test_connector_activity_handle() {
         let connector = KrakenFuturesConnector::new(vec!["PF_XBTUSD".to_string()], None);
@@ -13,6 +13,7 @@ use tokio_tungstenite::{connect_async_with_config, tungstenite};
 use tracing::{debug, info};
 
 use super::messages::KrakenFuturesWsMessage;
+use crate::keepalive::PongTracker;
 
 pub const KRAKEN_FUTURES_WS_URL: &str = "wss://futures.kraken.com/ws/v1";
 
@@ -41,6 +42,7 @@ pub struct KrakenFuturesWebSocket {
     ws: tokio_tungstenite::WebSocketStream<
         tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
     >,
+    pongs: PongTracker,
 }
 
 impl KrakenFuturesWebSocket {
@@ -58,7 +60,10 @@ impl KrakenFuturesWebSocket {
         let (ws, _) = connect_async_with_config(url, Some(config), false).await?;
         info!("Connected to Kraken Futures WS");
 
-        Ok(Self { ws })
+        Ok(Self {
+            ws,
+            pongs: PongTracker::new("kraken-futures"),
+        })
     }
 
     /// Subscribe to a feed (e.g., "trade", "ticker") for given product IDs.
@@ -136,6 +141,10 @@ impl KrakenFuturesWebSocket {
                 // Recurse to get next real message
                 Box::pin(self.recv_raw()).await
             }
+            tungstenite::Message::Pong(_) => {
+                self.pongs.pong_received();
+                Box::pin(self.recv_raw()).await
+            }
             tungstenite::Message::Close(frame) => {
                 info!(frame = ?frame, "Kraken Futures WebSocket closed");
                 Err(KrakenFuturesWsError::ConnectionClosed)
@@ -150,9 +159,15 @@ impl KrakenFuturesWebSocket {
         self.ws
             .send(tungstenite::Message::Ping(vec![]))
             .await?;
+        self.pongs.ping_sent();
         Ok(())
     }
 
+    /// Pongs outstanding for pings sent on this connection
+    pub fn pongs(&self) -> &PongTracker {
+        &self.pongs
+    }
+
     /// Close the connection.
     pub async fn close(&mut self) -> Result<(), KrakenFuturesWsError> {
         self.ws.close(None).await?;
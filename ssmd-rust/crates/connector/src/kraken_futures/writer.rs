@@ -98,7 +98,7 @@ impl Writer for KrakenFuturesNatsWriter {
 
         // Publish raw bytes - no transformation
         self.transport
-            .publish(&subject, msg.data.clone())
+            .publish_with_headers(&subject, msg.data.clone(), msg.capture_headers(None))
             .await
             .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
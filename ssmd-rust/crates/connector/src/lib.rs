@@ -8,9 +8,12 @@
 #![allow(clippy::manual_is_multiple_of)]
 
 pub mod binance;
+pub mod channels;
+pub mod coalesce;
 pub mod error;
 pub mod flusher;
 pub mod kalshi;
+pub mod keepalive;
 pub mod kraken;
 pub mod kraken_futures;
 pub mod massive;
@@ -20,30 +23,39 @@ pub mod metrics;
 pub mod nats_writer;
 pub mod publisher;
 pub mod resolver;
+pub mod rest;
 pub mod ring_buffer;
 pub mod runner;
 pub mod secmaster;
 pub mod server;
+pub mod snapshot;
+pub mod tee;
 pub mod traits;
+pub mod wal;
 pub mod websocket;
-// writer.rs kept for ring buffer integration tests but not exported
-// TODO: Delete in next major version when archiver replaces file writer
-#[allow(dead_code)]
-mod writer;
+// Local JSONL output; used as the file side of a TeeWriter during feed bring-up
+pub mod writer;
 
+pub use coalesce::CoalescingTransport;
 pub use error::{ConnectorError, ResolverError, WriterError};
 pub use flusher::DiskFlusher;
+pub use keepalive::{Keepalive, PongTracker};
 pub use message::Message;
 pub use metrics::{encode_metrics, ConnectorMetrics, ShardMetrics};
 pub use nats_writer::NatsWriter;
 pub use publisher::{Publisher, TradeData, TradeSide};
 pub use resolver::EnvResolver;
-pub use ring_buffer::{RingBuffer, RING_SIZE, RING_SLOTS, SLOT_SIZE};
-pub use runner::Runner;
+pub use rest::RestConnector;
+pub use ring_buffer::{PushError, RingBuffer, RING_SIZE, RING_SLOTS, SLOT_SIZE};
+pub use runner::{ReconnectPolicy, Runner};
 pub use secmaster::{SecmasterClient, SecmasterError};
 pub use server::{create_router, run_server, ServerState};
+pub use snapshot::LastSeen;
+pub use tee::TeeWriter;
 pub use traits::{Connector, KeyResolver, Writer};
+pub use wal::WalWriter;
 pub use websocket::WebSocketConnector;
+pub use writer::FileWriter;
 
 #[cfg(test)]
 mod integration_tests {
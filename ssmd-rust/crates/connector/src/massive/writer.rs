@@ -134,6 +134,7 @@ impl Writer for MassiveNatsWriter {
         // split_frame_events parses, sanitizes symbols, and drops empty-symbol
         // or malformed elements — never returns an event with an empty symbol.
         let events = split_frame_events(&msg.data);
+        let headers = msg.capture_headers(None);
 
         for event in events {
             // Defensive: split_frame_events guarantees non-empty symbols, but
@@ -165,7 +166,7 @@ impl Writer for MassiveNatsWriter {
             trace!(subject = %subject, "Publishing Polygon aggregate event");
 
             self.transport
-                .publish(&subject, event.payload.into())
+                .publish_with_headers(&subject, event.payload.into(), headers.clone())
                 .await
                 .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
@@ -1,7 +1,8 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use bytes::Bytes;
-use ssmd_middleware::now_tsc;
+use ssmd_middleware::{now_tsc, tsc_to_epoch_micros, CAPTURE_TS_HEADER, SOURCE_SEQ_HEADER};
 
 /// Message wraps raw data with metadata.
 /// Stores raw bytes to avoid JSON parsing overhead in hot path.
@@ -9,6 +10,10 @@ use ssmd_middleware::now_tsc;
 pub struct Message {
     /// TSC timestamp (zero-syscall, convert to wall clock at I/O boundary)
     pub tsc: u64,
+    /// Capture time in Unix epoch microseconds, derived from `tsc`
+    pub capture_ts_micros: i64,
+    /// Exchange-provided sequence number, if the source has one
+    pub source_seq: Option<u64>,
     /// Feed name (shared reference to avoid allocation)
     pub feed: Arc<str>,
     /// Raw message bytes (no parsing in hot path)
@@ -20,11 +25,7 @@ impl Message {
     /// Uses TSC timestamp to avoid syscall overhead.
     #[inline]
     pub fn new(feed: impl Into<Arc<str>>, data: impl Into<Bytes>) -> Self {
-        Self {
-            tsc: now_tsc(),
-            feed: feed.into(),
-            data: data.into(),
-        }
+        Self::new_with_tsc(feed, data, now_tsc())
     }
 
     /// Create a new message with a provided TSC timestamp.
@@ -32,6 +33,26 @@ impl Message {
     pub fn new_with_tsc(feed: impl Into<Arc<str>>, data: impl Into<Bytes>, tsc: u64) -> Self {
         Self {
             tsc,
+            capture_ts_micros: tsc_to_epoch_micros(tsc),
+            source_seq: None,
+            feed: feed.into(),
+            data: data.into(),
+        }
+    }
+
+    /// Rebuild a message captured earlier, possibly by another process.
+    /// TSC values don't carry across processes, so `tsc` is left at 0 and the
+    /// wall-clock capture time is kept as recorded.
+    pub fn from_capture(
+        feed: impl Into<Arc<str>>,
+        data: impl Into<Bytes>,
+        capture_ts_micros: i64,
+        source_seq: Option<u64>,
+    ) -> Self {
+        Self {
+            tsc: 0,
+            capture_ts_micros,
+            source_seq,
             feed: feed.into(),
             data: data.into(),
         }
@@ -40,10 +61,27 @@ impl Message {
     /// Create message from borrowed data (copies bytes).
     #[inline]
     pub fn from_slice(feed: impl Into<Arc<str>>, data: &[u8]) -> Self {
-        Self {
-            tsc: now_tsc(),
-            feed: feed.into(),
-            data: Bytes::copy_from_slice(data),
+        Self::new(feed, Bytes::copy_from_slice(data))
+    }
+
+    /// Attach the exchange's sequence number.
+    #[inline]
+    pub fn with_source_seq(mut self, seq: u64) -> Self {
+        self.source_seq = Some(seq);
+        self
+    }
+
+    /// NATS headers carrying capture time and source sequence.
+    /// `source_seq` overrides `self.source_seq` when a writer parsed one.
+    pub fn capture_headers(&self, source_seq: Option<u64>) -> HashMap<String, String> {
+        let mut headers = HashMap::with_capacity(2);
+        headers.insert(
+            CAPTURE_TS_HEADER.to_string(),
+            self.capture_ts_micros.to_string(),
+        );
+        if let Some(seq) = source_seq.or(self.source_seq) {
+            headers.insert(SOURCE_SEQ_HEADER.to_string(), seq.to_string());
         }
+        headers
     }
 }
@@ -13,6 +13,7 @@ const LABEL_FEED: &str = "feed";
 const LABEL_CATEGORY: &str = "category";
 const LABEL_SHARD: &str = "shard";
 const LABEL_MESSAGE_TYPE: &str = "message_type";
+const LABEL_REASON: &str = "reason";
 
 /// Total messages received per shard and message type
 static MESSAGES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -170,6 +171,78 @@ static NATS_PUBLISH_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
     .expect("Failed to register nats_publish_duration metric")
 });
 
+/// WebSocket ping → pong round-trip time
+static PONG_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "ssmd_connector_pong_latency_seconds",
+        "WebSocket ping to pong round-trip latency",
+        &[LABEL_FEED],
+        vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+    )
+    .expect("Failed to register pong_latency metric")
+});
+
+/// Frames a NATS writer dropped because they did not deserialize into the
+/// feed's typed messages
+static WRITER_DROPPED_FRAMES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "ssmd_connector_writer_dropped_frames_total",
+        "Total frames dropped by the writer (reason: malformed, unknown_variant)",
+        &[LABEL_FEED, LABEL_REASON]
+    )
+    .expect("Failed to register writer_dropped_frames_total metric")
+});
+
+/// Messages published inside a multi-message coalesced batch
+static PUBLISHER_COALESCED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "ssmd_connector_publisher_coalesced_total",
+        "Total messages published as part of a coalesced batch",
+        &[LABEL_FEED]
+    )
+    .expect("Failed to register publisher_coalesced_total metric")
+});
+
+/// Seconds since the runner last received a message, refreshed on each scrape
+static LAST_MESSAGE_AGE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "ssmd_connector_last_message_age_seconds",
+        "Seconds since the last message was received",
+        &[LABEL_FEED]
+    )
+    .expect("Failed to register last_message_age metric")
+});
+
+/// Ring buffer pushes refused because the consumer fell behind
+static RING_OVERFLOWS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "ssmd_connector_ring_overflows_total",
+        "Total messages dropped because the ring buffer was full",
+        &[LABEL_FEED]
+    )
+    .expect("Failed to register ring_overflows_total metric")
+});
+
+/// Peak ring buffer occupancy in slots
+static RING_HIGH_WATERMARK: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "ssmd_connector_ring_high_watermark",
+        "Peak number of occupied ring buffer slots",
+        &[LABEL_FEED]
+    )
+    .expect("Failed to register ring_high_watermark metric")
+});
+
+/// Frames persisted to the local WAL that have not been acked by NATS
+static WAL_PENDING: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "ssmd_connector_wal_pending",
+        "Frames in the local write-ahead log awaiting publish ack",
+        &[LABEL_FEED]
+    )
+    .expect("Failed to register wal_pending metric")
+});
+
 /// Observe end-to-end WebSocket message processing duration
 pub fn observe_ws_process_duration(feed: &str, secs: f64) {
     WS_PROCESS_DURATION
@@ -177,6 +250,11 @@ pub fn observe_ws_process_duration(feed: &str, secs: f64) {
         .observe(secs);
 }
 
+/// Set seconds since the last message for a feed
+pub fn set_last_message_age(feed: &str, secs: f64) {
+    LAST_MESSAGE_AGE.with_label_values(&[feed]).set(secs);
+}
+
 /// Observe NATS publish duration
 pub fn observe_nats_publish_duration(feed: &str, secs: f64) {
     NATS_PUBLISH_DURATION
@@ -184,6 +262,24 @@ pub fn observe_nats_publish_duration(feed: &str, secs: f64) {
         .observe(secs);
 }
 
+/// Observe ping → pong round-trip latency
+pub fn observe_pong_latency(feed: &str, secs: f64) {
+    PONG_LATENCY.with_label_values(&[feed]).observe(secs);
+}
+
+/// Dropped-frame reason: bad JSON or missing/mistyped fields
+pub const DROPPED_MALFORMED: &str = "malformed";
+/// Dropped-frame reason: valid JSON of a message type we don't model
+pub const DROPPED_UNKNOWN_VARIANT: &str = "unknown_variant";
+
+/// Record a frame the writer dropped ([`DROPPED_MALFORMED`] or
+/// [`DROPPED_UNKNOWN_VARIANT`])
+pub fn inc_writer_dropped_frame(feed: &str, reason: &str) {
+    WRITER_DROPPED_FRAMES_TOTAL
+        .with_label_values(&[feed, reason])
+        .inc();
+}
+
 /// Pre-initialize CDC metric time series so GMP discovers the metric names even
 /// when there have been zero failures (otherwise the alert metric is absent and
 /// the policy cannot evaluate during healthy periods).
@@ -206,6 +302,30 @@ pub fn inc_cdc_market_dropped(feed: &str, category: &str) {
         .inc();
 }
 
+/// Record ring buffer overflows since the last report and the current peak occupancy
+pub fn record_ring_stats(feed: &str, new_overflows: u64, high_watermark: u64) {
+    if new_overflows > 0 {
+        RING_OVERFLOWS_TOTAL
+            .with_label_values(&[feed])
+            .inc_by(new_overflows);
+    }
+    RING_HIGH_WATERMARK
+        .with_label_values(&[feed])
+        .set(high_watermark as i64);
+}
+
+/// Set the number of WAL frames awaiting publish ack for a feed
+pub fn set_wal_pending(feed: &str, n: i64) {
+    WAL_PENDING.with_label_values(&[feed]).set(n);
+}
+
+/// Record messages folded into one coalesced publish
+pub fn inc_publisher_coalesced(feed: &str, count: u64) {
+    PUBLISHER_COALESCED_TOTAL
+        .with_label_values(&[feed])
+        .inc_by(count);
+}
+
 /// Handle for recording metrics for a specific connector instance
 #[derive(Clone)]
 pub struct ConnectorMetrics {
@@ -459,6 +579,20 @@ mod tests {
         assert!(output.contains("ssmd_connector_nats_publish_duration_seconds"));
     }
 
+    #[test]
+    fn test_writer_dropped_frames() {
+        inc_writer_dropped_frame("test-feed", "malformed");
+        let output = encode_metrics().unwrap();
+        assert!(output.contains("ssmd_connector_writer_dropped_frames_total"));
+    }
+
+    #[test]
+    fn test_pong_latency() {
+        observe_pong_latency("test-feed", 0.05);
+        let output = encode_metrics().unwrap();
+        assert!(output.contains("ssmd_connector_pong_latency_seconds"));
+    }
+
     #[test]
     fn test_unsubscribe_metrics() {
         let connector_metrics = ConnectorMetrics::new("kalshi", "test_unsub");
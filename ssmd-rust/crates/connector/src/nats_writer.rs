@@ -97,6 +97,8 @@ struct PartialWsMessage<'a> {
     #[serde(rename = "type")]
     msg_type: &'a str,
     id: Option<u64>,
+    /// Per-subscription sequence number (orderbook channels)
+    seq: Option<u64>,
     #[serde(borrow)]
     msg: Option<PartialMsgData<'a>>,
 }
@@ -225,7 +227,7 @@ impl Writer for NatsWriter {
 
         // Publish raw bytes - no transformation
         self.transport
-            .publish(&subject, msg.data.clone())
+            .publish_with_headers(&subject, msg.data.clone(), msg.capture_headers(partial.seq))
             .await
             .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
@@ -264,6 +266,15 @@ mod tests {
         assert_eq!(received.subject, "dev.kalshi.json.trade.KXTEST-123");
         // Raw JSON preserved
         assert_eq!(received.payload.as_ref(), trade_json);
+        // Capture time and exchange seq travel as headers
+        assert_eq!(
+            received.headers.get(ssmd_middleware::CAPTURE_TS_HEADER),
+            Some(&msg.capture_ts_micros.to_string())
+        );
+        assert_eq!(
+            received.headers.get(ssmd_middleware::SOURCE_SEQ_HEADER).map(String::as_str),
+            Some("1")
+        );
     }
 
     #[tokio::test]
@@ -284,6 +295,7 @@ mod tests {
         let received = sub.next().await.unwrap();
         assert_eq!(received.subject, "dev.kalshi.json.ticker.KXTEST-456");
         assert_eq!(received.payload.as_ref(), ticker_json);
+        assert!(!received.headers.contains_key(ssmd_middleware::SOURCE_SEQ_HEADER));
     }
 
     #[tokio::test]
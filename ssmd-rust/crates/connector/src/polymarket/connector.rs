@@ -3,13 +3,17 @@
 //! Implements the ssmd Connector trait for Polymarket CLOB WebSocket.
 //! Key differences from Kraken:
 //! - Sharding: multiple WS connections needed (500 instrument limit)
-//! - Market discovery: Gamma REST API polling (no CDC, no static config)
+//! - Market discovery: Gamma REST API polling (no CDC, no static config), refreshed
+//!   periodically with incremental subscribe/unsubscribe on the live shards
 //! - Keepalive: 10-second PING interval (vs Kraken's 30s)
 //! - Relies on 120s read timeout to detect stale connections (WS may go silent)
 
+use crate::channels::resolve_channels;
 use crate::error::ConnectorError;
+use crate::keepalive::{Keepalive, PongTracker};
 use crate::metrics::{ConnectorMetrics, ShardMetrics};
-use crate::polymarket::market_discovery::MarketDiscovery;
+use crate::polymarket::market_discovery::{MarketDiscovery, DEFAULT_POLL_INTERVAL_SECS};
+use crate::polymarket::subscriptions::{ShardCommand, SubscriptionError, SubscriptionTracker};
 use crate::polymarket::websocket::{
     PolymarketWebSocket, PolymarketWebSocketError, MAX_INSTRUMENTS_PER_CONNECTION,
 };
@@ -20,22 +24,72 @@ use ssmd_metadata::SecmasterConfig;
 use ssmd_middleware::now_tsc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 /// Polymarket PING interval: 10 seconds (required by Polymarket, vs 30s for Kraken)
-const PING_INTERVAL_SECS: u64 = 10;
+pub const PING_INTERVAL_SECS: u64 = 10;
+
+/// Channels published by default, matching the writer's subject routing
+pub const POLYMARKET_CHANNELS: &[&str] = &["trade", "ticker", "orderbook", "lifecycle"];
+
+/// Channel an event element belongs to, using the same mapping as the writer
+fn event_channel(element: &serde_json::Value) -> Option<&'static str> {
+    match element.get("event_type").and_then(|v| v.as_str()) {
+        Some("last_trade_price") => Some("trade"),
+        Some("price_change") | Some("best_bid_ask") => Some("ticker"),
+        Some("book") => Some("orderbook"),
+        Some("new_market") | Some("market_resolved") => Some("lifecycle"),
+        Some(_) => None,
+        None if element.get("bids").is_some() || element.get("asks").is_some() => {
+            Some("orderbook")
+        }
+        None => None,
+    }
+}
+
+/// Drop events outside `channels`.
+///
+/// The market channel has no server-side per-event subscription, so narrowed
+/// feeds are filtered here. Returns `None` when nothing is left to forward;
+/// unparseable payloads are passed through for the writer to handle.
+fn filter_channels(raw: String, channels: &[String]) -> Option<String> {
+    let allowed = |element: &serde_json::Value| {
+        event_channel(element).is_some_and(|c| channels.iter().any(|ch| ch == c))
+    };
+    match serde_json::from_str::<serde_json::Value>(&raw) {
+        Ok(serde_json::Value::Array(elements)) => {
+            let total = elements.len();
+            let kept: Vec<_> = elements.into_iter().filter(|e| allowed(e)).collect();
+            if kept.is_empty() {
+                None
+            } else if kept.len() == total {
+                Some(raw)
+            } else {
+                serde_json::to_string(&kept).ok()
+            }
+        }
+        Ok(element) => allowed(&element).then_some(raw),
+        Err(_) => Some(raw),
+    }
+}
 
 /// Polymarket connector implementing the ssmd Connector trait
 pub struct PolymarketConnector {
     /// Token IDs to subscribe to (can be set statically or via discovery)
     token_ids: Vec<String>,
+    /// Channels to forward (subset of `POLYMARKET_CHANNELS`)
+    channels: Vec<String>,
     /// Optional market discovery client for dynamic subscription
-    discovery: Option<MarketDiscovery>,
+    discovery: Option<Arc<MarketDiscovery>>,
+    /// How often discovery is re-run after startup (zero disables refresh)
+    refresh_interval: Duration,
     /// Optional secmaster config for category-based token filtering
     secmaster_config: Option<SecmasterConfig>,
     /// WebSocket URL override from feed config (None = use default constant)
     ws_url: Option<String>,
+    keepalive: Keepalive,
     tx: Option<mpsc::Sender<TimestampedMsg>>,
     rx: Option<mpsc::Receiver<TimestampedMsg>>,
     /// Last WebSocket activity timestamp (epoch seconds)
@@ -49,8 +103,11 @@ impl PolymarketConnector {
         Self {
             token_ids,
             discovery: None,
+            refresh_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
             secmaster_config: None,
+            channels: POLYMARKET_CHANNELS.iter().map(|c| c.to_string()).collect(),
             ws_url,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -62,9 +119,12 @@ impl PolymarketConnector {
         let (tx, rx) = mpsc::channel(2000);
         Self {
             token_ids: Vec::new(),
-            discovery: Some(discovery),
+            discovery: Some(Arc::new(discovery)),
+            refresh_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
             secmaster_config: None,
+            channels: POLYMARKET_CHANNELS.iter().map(|c| c.to_string()).collect(),
             ws_url,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
@@ -77,14 +137,85 @@ impl PolymarketConnector {
         Self {
             token_ids: Vec::new(),
             discovery: None,
+            refresh_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
             secmaster_config: Some(secmaster_config),
+            channels: POLYMARKET_CHANNELS.iter().map(|c| c.to_string()).collect(),
             ws_url,
+            keepalive: Keepalive::every(Duration::from_secs(PING_INTERVAL_SECS)),
             tx: Some(tx),
             rx: Some(rx),
             last_ws_activity_epoch_secs: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Restrict forwarded events to the given channels (`None` = all)
+    pub fn with_channels(mut self, channels: Option<Vec<String>>) -> Result<Self, ConnectorError> {
+        self.channels = resolve_channels(channels, POLYMARKET_CHANNELS)?;
+        Ok(self)
+    }
+
+    /// Set the market discovery refresh interval (zero disables refresh)
+    pub fn with_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Override the ping interval and pong timeout
+    pub fn with_keepalive(mut self, keepalive: Keepalive) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
+    /// Re-run market discovery every `refresh_interval` and apply the diff to
+    /// the live shards. Exits for restart if new markets no longer fit.
+    fn spawn_discovery_refresh(
+        discovery: Arc<MarketDiscovery>,
+        mut tracker: SubscriptionTracker,
+        refresh_interval: Duration,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(refresh_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // Skip the immediate first tick — discovery just ran in connect()
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let markets = match discovery.fetch_active_markets().await {
+                    Ok(markets) => markets,
+                    Err(e) => {
+                        warn!(error = %e, "Market discovery refresh failed, keeping current subscriptions");
+                        continue;
+                    }
+                };
+                let token_ids = MarketDiscovery::extract_token_ids(&markets);
+                if token_ids.is_empty() {
+                    // Never unsubscribe everything on an empty (likely bad) response
+                    warn!("Market discovery refresh returned no markets, keeping current subscriptions");
+                    continue;
+                }
+
+                match tracker.apply(&token_ids).await {
+                    Ok(diff) => info!(
+                        added = diff.added,
+                        removed = diff.removed,
+                        total = tracker.total_assets(),
+                        "Market discovery refresh applied"
+                    ),
+                    Err(SubscriptionError::CapacityExhausted { pending }) => {
+                        warn!(
+                            pending,
+                            total = tracker.total_assets(),
+                            "All shards at capacity — restarting to re-shard discovered markets"
+                        );
+                        std::process::exit(1);
+                    }
+                }
+            }
+        });
+    }
+
     /// Fetch token IDs from secmaster by categories
     async fn fetch_filtered_tokens(
         secmaster_config: &SecmasterConfig,
@@ -129,13 +260,19 @@ impl PolymarketConnector {
         "unknown"
     }
 
-    /// Spawn a WebSocket receiver task for a shard (subset of token IDs)
+    /// Spawn a WebSocket receiver task for a shard (subset of token IDs).
+    /// Optionally accepts a command receiver for discovery refresh updates.
+    #[allow(clippy::too_many_arguments)]
     fn spawn_shard_receiver(
         shard_id: usize,
         mut ws: PolymarketWebSocket,
+        keepalive: Keepalive,
         tx: mpsc::Sender<TimestampedMsg>,
         activity_tracker: Arc<AtomicU64>,
         shard_metrics: ShardMetrics,
+        channel_filter: Option<Arc<[String]>>,
+        custom_features: bool,
+        mut cmd_rx: Option<mpsc::Receiver<ShardCommand>>,
     ) {
         fn update_activity(tracker: &AtomicU64, metrics: &ShardMetrics, idle_secs: f64) {
             use std::time::{SystemTime, UNIX_EPOCH};
@@ -152,23 +289,36 @@ impl PolymarketConnector {
         update_activity(&activity_tracker, &shard_metrics, 0.0);
 
         tokio::spawn(async move {
-            use std::time::Duration;
             use tokio::time::{interval, Instant};
 
             let connected_at = Instant::now();
             let mut message_count: u64 = 0;
 
-            let mut ping_interval = interval(Duration::from_secs(PING_INTERVAL_SECS));
+            let mut ping_interval = interval(keepalive.ping_interval);
             ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut pongs = PongTracker::new("polymarket");
 
             let mut last_activity_instant = Instant::now();
 
             loop {
                 tokio::select! {
-                    // Ping timer - send app-level "PING" text every 10s
+                    // Ping timer - send app-level "PING" text (every 10s by default)
                     _ = ping_interval.tick() => {
                         let idle_secs = last_activity_instant.elapsed().as_secs();
                         shard_metrics.set_idle_seconds(idle_secs as f64);
+                        if pongs.timed_out(keepalive.pong_timeout) {
+                            let uptime_secs = connected_at.elapsed().as_secs();
+                            error!(
+                                shard = shard_id,
+                                uptime_secs,
+                                message_count,
+                                pong_timeout_secs = keepalive.pong_timeout.as_secs(),
+                                reason = "pong_timeout",
+                                "Polymarket PONG overdue, exiting for restart"
+                            );
+                            shard_metrics.set_disconnected();
+                            std::process::exit(1);
+                        }
                         debug!(shard = shard_id, idle_secs, "Sending Polymarket ping keepalive");
                         if let Err(e) = ws.ping().await {
                             let uptime_secs = connected_at.elapsed().as_secs();
@@ -183,9 +333,58 @@ impl PolymarketConnector {
                             shard_metrics.set_disconnected();
                             std::process::exit(1);
                         }
+                        pongs.ping_sent();
                         update_activity(&activity_tracker, &shard_metrics, idle_secs as f64);
                     }
 
+                    // Handle shard commands (discovery refresh)
+                    cmd = async {
+                        match cmd_rx.as_mut() {
+                            Some(rx) => rx.recv().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        let (result, op, count) = match cmd {
+                            Some(ShardCommand::Subscribe { asset_ids }) => {
+                                let result = ws.subscribe_more(&asset_ids, custom_features).await;
+                                if result.is_ok() {
+                                    let current = shard_metrics.get_markets_subscribed();
+                                    shard_metrics.set_markets_subscribed(current + asset_ids.len());
+                                }
+                                (result, "subscribe", asset_ids.len())
+                            }
+                            Some(ShardCommand::Unsubscribe { asset_ids }) => {
+                                let result = ws.unsubscribe(&asset_ids).await;
+                                if result.is_ok() {
+                                    let current = shard_metrics.get_markets_subscribed();
+                                    shard_metrics.set_markets_subscribed(current.saturating_sub(asset_ids.len()));
+                                    for _ in &asset_ids {
+                                        shard_metrics.inc_unsubscribed();
+                                    }
+                                }
+                                (result, "unsubscribe", asset_ids.len())
+                            }
+                            None => {
+                                // Refresh task gone - keep receiving on the current set
+                                debug!(shard = shard_id, "Command channel closed");
+                                cmd_rx = None;
+                                continue;
+                            }
+                        };
+                        if let Err(e) = result {
+                            error!(
+                                shard = shard_id,
+                                error = %e,
+                                op,
+                                count,
+                                "Polymarket subscription update failed, exiting for restart"
+                            );
+                            shard_metrics.set_disconnected();
+                            std::process::exit(1);
+                        }
+                        info!(shard = shard_id, op, count, "Polymarket subscription updated");
+                    }
+
                     // Receive message from WebSocket
                     // Stale connections detected by 120s read timeout in websocket.rs
                     result = ws.recv_raw() => {
@@ -197,10 +396,19 @@ impl PolymarketConnector {
                                 message_count += 1;
                                 // Skip PONG responses (don't forward to NATS)
                                 if raw_json == "PONG" {
+                                    pongs.pong_received();
                                     shard_metrics.inc_message("pong");
                                     continue;
                                 }
 
+                                let raw_json = match &channel_filter {
+                                    Some(channels) => match filter_channels(raw_json, channels) {
+                                        Some(filtered) => filtered,
+                                        None => continue,
+                                    },
+                                    None => raw_json,
+                                };
+
                                 // Extract event_type for metrics without full deserialization
                                 let event_type = Self::extract_event_type(&raw_json);
                                 match event_type {
@@ -306,6 +514,21 @@ impl Connector for PolymarketConnector {
         let connector_metrics = ConnectorMetrics::new("polymarket", "clob");
         connector_metrics.set_shards_total(num_shards);
 
+        // Discovery-driven subscriptions are refreshed in place on the live shards
+        let refresh_enabled = self.discovery.is_some()
+            && self.secmaster_config.is_none()
+            && !self.refresh_interval.is_zero();
+        let mut tracker = refresh_enabled.then(|| SubscriptionTracker::new(MAX_INSTRUMENTS_PER_CONNECTION));
+
+        // best_bid_ask and lifecycle events are only sent with custom features on
+        let custom_features = self.channels.iter().any(|c| c == "ticker" || c == "lifecycle");
+        let channel_filter: Option<Arc<[String]>> = if self.channels.len() < POLYMARKET_CHANNELS.len() {
+            info!(channels = ?self.channels, "Filtering Polymarket events by channel");
+            Some(self.channels.clone().into())
+        } else {
+            None
+        };
+
         for (shard_id, shard_tokens) in shards.into_iter().enumerate() {
             // Stagger shard startup by 2 seconds + random jitter (0-3s)
             if shard_id > 0 {
@@ -323,7 +546,7 @@ impl Connector for PolymarketConnector {
                 .await
                 .map_err(|e| ConnectorError::ConnectionFailed(format!("shard {}: {}", shard_id, e)))?;
 
-            ws.subscribe(&shard_tokens)
+            ws.subscribe(&shard_tokens, custom_features)
                 .await
                 .map_err(|e| ConnectorError::ConnectionFailed(format!("shard {} subscribe: {}", shard_id, e)))?;
 
@@ -333,12 +556,22 @@ impl Connector for PolymarketConnector {
             shard_metrics.init(&["ticker", "trade", "orderbook"]);
             shard_metrics.set_connected();
 
+            let cmd_rx = tracker.as_mut().map(|tracker| {
+                let (cmd_tx, cmd_rx) = mpsc::channel::<ShardCommand>(100);
+                tracker.register_shard(cmd_tx, &shard_tokens);
+                cmd_rx
+            });
+
             Self::spawn_shard_receiver(
                 shard_id,
                 ws,
+                self.keepalive,
                 tx.clone(),
                 Arc::clone(&activity_tracker),
                 shard_metrics,
+                channel_filter.clone(),
+                custom_features,
+                cmd_rx,
             );
 
             info!(
@@ -354,6 +587,14 @@ impl Connector for PolymarketConnector {
             "All Polymarket shards connected"
         );
 
+        if let (Some(tracker), Some(discovery)) = (tracker, self.discovery.as_ref()) {
+            info!(
+                refresh_interval_secs = self.refresh_interval.as_secs(),
+                "Starting market discovery refresh"
+            );
+            Self::spawn_discovery_refresh(Arc::clone(discovery), tracker, self.refresh_interval);
+        }
+
         Ok(())
     }
 
@@ -458,4 +699,43 @@ mod tests {
             "best_bid_ask"
         );
     }
+    #[test]
+    fn test_refresh_interval_defaults_to_discovery_poll_interval() {
+        let connector = PolymarketConnector::with_discovery(MarketDiscovery::new(), None);
+        assert_eq!(connector.refresh_interval, Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS));
+
+        let connector = connector.with_refresh_interval(Duration::ZERO);
+        assert!(connector.refresh_interval.is_zero());
+    }
+
+    #[test]
+    fn test_with_channels_rejects_empty() {
+        let err = PolymarketConnector::new(vec!["token1".to_string()], None)
+            .with_channels(Some(vec![]))
+            .err()
+            .unwrap();
+        assert!(matches!(err, ConnectorError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_filter_channels() {
+        let trade_only = vec!["trade".to_string()];
+
+        let trade = r#"{"event_type":"last_trade_price","market":"0x1"}"#.to_string();
+        assert_eq!(filter_channels(trade.clone(), &trade_only), Some(trade));
+
+        let book = r#"{"event_type":"book","market":"0x1","bids":[]}"#.to_string();
+        assert_eq!(filter_channels(book, &trade_only), None);
+
+        // Untyped snapshots count as orderbook
+        let snapshot = r#"[{"market":"0x1","bids":[],"asks":[]}]"#.to_string();
+        assert_eq!(filter_channels(snapshot, &trade_only), None);
+
+        // Mixed arrays keep only allowed elements
+        let mixed = r#"[{"event_type":"book","market":"0x1"},{"event_type":"last_trade_price","market":"0x1"}]"#;
+        let filtered = filter_channels(mixed.to_string(), &trade_only).unwrap();
+        let elements: Vec<serde_json::Value> = serde_json::from_str(&filtered).unwrap();
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0]["event_type"], "last_trade_price");
+    }
 }
@@ -94,11 +94,16 @@ pub enum PolymarketWsMessage {
         side: Option<String>,
         timestamp: Option<String>,
     },
+
+    /// Any `event_type` not modelled above, so new upstream event types can
+    /// be counted and skipped instead of failing the whole frame
+    #[serde(other)]
+    Unknown,
 }
 
 impl PolymarketWsMessage {
     /// Extract the condition_id (market field) from the message.
-    /// Used for NATS subject routing.
+    /// Used for NATS subject routing. Empty for `Unknown`.
     pub fn condition_id(&self) -> &str {
         match self {
             PolymarketWsMessage::Book { market, .. }
@@ -108,6 +113,7 @@ impl PolymarketWsMessage {
             | PolymarketWsMessage::NewMarket { market, .. }
             | PolymarketWsMessage::MarketResolved { market, .. }
             | PolymarketWsMessage::TickSizeChange { market, .. } => market,
+            PolymarketWsMessage::Unknown => "",
         }
     }
 }
@@ -328,4 +334,13 @@ mod tests {
             _ => panic!("Expected LastTradePrice"),
         }
     }
+
+    #[test]
+    fn test_parse_unknown_event_type() {
+        let json = r#"{"event_type":"some_new_event","market":"0xdef","foo":1}"#;
+        let msg: PolymarketWsMessage =
+            serde_json::from_str(json).expect("unknown event types parse");
+        assert!(matches!(msg, PolymarketWsMessage::Unknown));
+        assert_eq!(msg.condition_id(), "");
+    }
 }
@@ -6,6 +6,7 @@
 pub mod connector;
 pub mod market_discovery;
 pub mod messages;
+pub mod subscriptions;
 pub mod websocket;
 pub mod writer;
 
@@ -0,0 +1,228 @@
+//! Incremental subscription tracking for market discovery refresh
+//!
+//! Tracks which shard owns each subscribed asset ID and turns a fresh
+//! discovery result into per-shard subscribe/unsubscribe commands, so new
+//! markets are picked up without dropping the shard connections.
+
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Commands that can be sent to a shard's receiver task
+#[derive(Debug)]
+pub enum ShardCommand {
+    /// Subscribe to additional asset IDs on this shard
+    Subscribe { asset_ids: Vec<String> },
+    /// Unsubscribe from asset IDs on this shard
+    Unsubscribe { asset_ids: Vec<String> },
+}
+
+#[derive(Error, Debug)]
+pub enum SubscriptionError {
+    #[error("all shards at capacity: {pending} new asset IDs could not be placed")]
+    CapacityExhausted { pending: usize },
+}
+
+/// Result of applying one discovery refresh
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RefreshDiff {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Tracks asset-to-shard assignment and routes subscription changes
+pub struct SubscriptionTracker {
+    /// Command senders, indexed by shard_id
+    shard_commands: Vec<mpsc::Sender<ShardCommand>>,
+    /// Asset count per shard, indexed by shard_id
+    shard_counts: Vec<usize>,
+    /// Mapping from asset ID to owning shard
+    asset_to_shard: HashMap<String, usize>,
+    max_per_shard: usize,
+}
+
+impl SubscriptionTracker {
+    pub fn new(max_per_shard: usize) -> Self {
+        Self {
+            shard_commands: Vec::new(),
+            shard_counts: Vec::new(),
+            asset_to_shard: HashMap::new(),
+            max_per_shard,
+        }
+    }
+
+    /// Register the next shard with the asset IDs it subscribed at startup.
+    /// Returns the assigned shard_id.
+    pub fn register_shard(
+        &mut self,
+        cmd_tx: mpsc::Sender<ShardCommand>,
+        asset_ids: &[String],
+    ) -> usize {
+        let shard_id = self.shard_commands.len();
+        self.shard_commands.push(cmd_tx);
+        self.shard_counts.push(asset_ids.len());
+        for asset_id in asset_ids {
+            self.asset_to_shard.insert(asset_id.clone(), shard_id);
+        }
+        shard_id
+    }
+
+    /// Total subscribed asset IDs across all shards
+    pub fn total_assets(&self) -> usize {
+        self.asset_to_shard.len()
+    }
+
+    /// Check if an asset ID is currently subscribed
+    pub fn is_subscribed(&self, asset_id: &str) -> bool {
+        self.asset_to_shard.contains_key(asset_id)
+    }
+
+    /// Diff `discovered` against current subscriptions and send the changes.
+    ///
+    /// Removals are applied first so their capacity can be reused. Additions
+    /// fill the least-loaded shards; if they don't fit, nothing is added and
+    /// `CapacityExhausted` is returned (the caller restarts to re-shard).
+    pub async fn apply(&mut self, discovered: &[String]) -> Result<RefreshDiff, SubscriptionError> {
+        let discovered_set: HashSet<&str> = discovered.iter().map(String::as_str).collect();
+
+        // Unsubscribe delisted assets, grouped by owning shard
+        let mut removals: HashMap<usize, Vec<String>> = HashMap::new();
+        self.asset_to_shard.retain(|asset_id, shard_id| {
+            if discovered_set.contains(asset_id.as_str()) {
+                true
+            } else {
+                removals.entry(*shard_id).or_default().push(asset_id.clone());
+                false
+            }
+        });
+        let mut diff = RefreshDiff::default();
+        for (shard_id, asset_ids) in removals {
+            self.shard_counts[shard_id] = self.shard_counts[shard_id].saturating_sub(asset_ids.len());
+            diff.removed += asset_ids.len();
+            self.send(shard_id, ShardCommand::Unsubscribe { asset_ids }).await;
+        }
+
+        // Subscribe newly discovered assets
+        let mut seen = HashSet::new();
+        let mut added: Vec<String> = discovered
+            .iter()
+            .filter(|a| !self.asset_to_shard.contains_key(a.as_str()) && seen.insert(a.as_str()))
+            .cloned()
+            .collect();
+        if added.is_empty() {
+            return Ok(diff);
+        }
+
+        let free: usize = self
+            .shard_counts
+            .iter()
+            .map(|&count| self.max_per_shard.saturating_sub(count))
+            .sum();
+        if added.len() > free {
+            return Err(SubscriptionError::CapacityExhausted { pending: added.len() });
+        }
+
+        diff.added = added.len();
+        while !added.is_empty() {
+            let (shard_id, count) = self
+                .shard_counts
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, count)| *count < self.max_per_shard)
+                .min_by_key(|(_, count)| *count)
+                .expect("capacity checked above");
+            let take = (self.max_per_shard - count).min(added.len());
+            let batch: Vec<String> = added.drain(..take).collect();
+            for asset_id in &batch {
+                self.asset_to_shard.insert(asset_id.clone(), shard_id);
+            }
+            self.shard_counts[shard_id] += batch.len();
+            self.send(shard_id, ShardCommand::Subscribe { asset_ids: batch }).await;
+        }
+
+        Ok(diff)
+    }
+
+    async fn send(&self, shard_id: usize, cmd: ShardCommand) {
+        debug!(shard_id, ?cmd, "Sending shard command");
+        if let Err(e) = self.shard_commands[shard_id].send(cmd).await {
+            warn!(
+                shard_id,
+                error = %e,
+                "Failed to send shard command, shard may be disconnected"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_apply_subscribes_new_and_unsubscribes_delisted() {
+        let mut tracker = SubscriptionTracker::new(10);
+        let (tx, mut rx) = mpsc::channel(10);
+        tracker.register_shard(tx, &ids(&["a", "b"]));
+
+        let diff = tracker.apply(&ids(&["b", "c"])).await.unwrap();
+        assert_eq!(diff, RefreshDiff { added: 1, removed: 1 });
+        assert!(!tracker.is_subscribed("a"));
+        assert!(tracker.is_subscribed("c"));
+        assert_eq!(tracker.total_assets(), 2);
+
+        match rx.recv().await.unwrap() {
+            ShardCommand::Unsubscribe { asset_ids } => assert_eq!(asset_ids, ids(&["a"])),
+            other => panic!("expected unsubscribe, got {:?}", other),
+        }
+        match rx.recv().await.unwrap() {
+            ShardCommand::Subscribe { asset_ids } => assert_eq!(asset_ids, ids(&["c"])),
+            other => panic!("expected subscribe, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_no_change_sends_nothing() {
+        let mut tracker = SubscriptionTracker::new(10);
+        let (tx, mut rx) = mpsc::channel(10);
+        tracker.register_shard(tx, &ids(&["a", "b"]));
+
+        let diff = tracker.apply(&ids(&["a", "b"])).await.unwrap();
+        assert_eq!(diff, RefreshDiff::default());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_fills_least_loaded_shard() {
+        let mut tracker = SubscriptionTracker::new(3);
+        let (tx0, mut rx0) = mpsc::channel(10);
+        let (tx1, mut rx1) = mpsc::channel(10);
+        tracker.register_shard(tx0, &ids(&["a", "b", "c"]));
+        tracker.register_shard(tx1, &ids(&["d"]));
+
+        tracker.apply(&ids(&["a", "b", "c", "d", "e", "f"])).await.unwrap();
+
+        assert!(rx0.try_recv().is_err());
+        match rx1.recv().await.unwrap() {
+            ShardCommand::Subscribe { asset_ids } => assert_eq!(asset_ids, ids(&["e", "f"])),
+            other => panic!("expected subscribe, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_capacity_exhausted() {
+        let mut tracker = SubscriptionTracker::new(2);
+        let (tx, _rx) = mpsc::channel(10);
+        tracker.register_shard(tx, &ids(&["a", "b"]));
+
+        let err = tracker.apply(&ids(&["a", "b", "c"])).await.unwrap_err();
+        assert!(matches!(err, SubscriptionError::CapacityExhausted { pending: 1 }));
+        assert!(!tracker.is_subscribed("c"));
+    }
+}
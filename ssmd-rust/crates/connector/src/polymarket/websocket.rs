@@ -76,13 +76,17 @@ impl PolymarketWebSocket {
 
     /// Subscribe to the market channel for the given asset IDs (token IDs).
     ///
-    /// Sends: `{"assets_ids": [...], "type": "market", "custom_feature_enabled": true}`
+    /// Sends: `{"assets_ids": [...], "type": "market", "custom_feature_enabled": <bool>}`
+    ///
+    /// `custom_features` enables the `best_bid_ask`, `new_market` and
+    /// `market_resolved` events on top of the base market channel.
     ///
     /// Polymarket does NOT send subscription confirmations like Kraken/Kalshi.
     /// Instead, it immediately starts sending `book` snapshots for subscribed instruments.
     pub async fn subscribe(
         &mut self,
         asset_ids: &[String],
+        custom_features: bool,
     ) -> Result<(), PolymarketWebSocketError> {
         if asset_ids.is_empty() {
             return Ok(());
@@ -99,7 +103,7 @@ impl PolymarketWebSocket {
         let subscribe_msg = serde_json::json!({
             "assets_ids": asset_ids,
             "type": "market",
-            "custom_feature_enabled": true
+            "custom_feature_enabled": custom_features
         });
 
         let msg = serde_json::to_string(&subscribe_msg)?;
@@ -115,6 +119,45 @@ impl PolymarketWebSocket {
         Ok(())
     }
 
+    /// Add asset IDs to a live market subscription.
+    ///
+    /// Sends: `{"assets_ids": [...], "operation": "subscribe", "custom_feature_enabled": <bool>}`
+    pub async fn subscribe_more(
+        &mut self,
+        asset_ids: &[String],
+        custom_features: bool,
+    ) -> Result<(), PolymarketWebSocketError> {
+        if asset_ids.is_empty() {
+            return Ok(());
+        }
+
+        let msg = serde_json::to_string(&serde_json::json!({
+            "assets_ids": asset_ids,
+            "operation": "subscribe",
+            "custom_feature_enabled": custom_features
+        }))?;
+        debug!(count = asset_ids.len(), "Sending Polymarket incremental subscribe");
+        self.ws.send(Message::Text(msg)).await?;
+        Ok(())
+    }
+
+    /// Remove asset IDs from a live market subscription.
+    ///
+    /// Sends: `{"assets_ids": [...], "operation": "unsubscribe"}`
+    pub async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<(), PolymarketWebSocketError> {
+        if asset_ids.is_empty() {
+            return Ok(());
+        }
+
+        let msg = serde_json::to_string(&serde_json::json!({
+            "assets_ids": asset_ids,
+            "operation": "unsubscribe"
+        }))?;
+        debug!(count = asset_ids.len(), "Sending Polymarket unsubscribe");
+        self.ws.send(Message::Text(msg)).await?;
+        Ok(())
+    }
+
     /// Receive the next raw text message from the WebSocket.
     /// Returns the raw JSON string for pass-through to NATS.
     /// Handles WS-level ping/pong frames automatically.
@@ -9,12 +9,14 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use serde::Deserialize;
 use tracing::{trace, warn};
 
 use ssmd_middleware::{sanitize_subject_token, SubjectBuilder, Transport};
 
 use crate::error::WriterError;
 use crate::message::Message;
+use crate::metrics::{self, DROPPED_MALFORMED, DROPPED_UNKNOWN_VARIANT};
 use crate::polymarket::messages::PolymarketWsMessage;
 use crate::traits::Writer;
 
@@ -57,27 +59,49 @@ impl PolymarketNatsWriter {
         self.message_count
     }
 
-    fn subject_from_element(&self, element: &serde_json::Value) -> Option<std::sync::Arc<str>> {
-        let market = element
-            .get("market")
-            .and_then(|v| v.as_str())
-            .map(sanitize_subject_token)?;
-
-        match element.get("event_type").and_then(|v| v.as_str()) {
-            Some("last_trade_price") => Some(self.subjects.json_trade(&market)),
-            Some("price_change") | Some("best_bid_ask") => Some(self.subjects.json_ticker(&market)),
-            Some("book") => Some(self.subjects.json_orderbook(&market)),
-            Some("new_market") | Some("market_resolved") => Some(self.subjects.json_lifecycle(&market)),
-            Some("tick_size_change") => None,
-            Some(_) => None,
-            None => {
-                if element.get("bids").is_some() || element.get("asks").is_some() {
-                    Some(self.subjects.json_orderbook(&market))
-                } else {
-                    None
-                }
+    /// Subject for a typed message; None for types that aren't published
+    fn subject_for(&self, ws_msg: &PolymarketWsMessage) -> Option<Arc<str>> {
+        let condition_id = sanitize_subject_token(ws_msg.condition_id());
+        match ws_msg {
+            PolymarketWsMessage::LastTradePrice { .. } => {
+                Some(self.subjects.json_trade(&condition_id))
+            }
+            PolymarketWsMessage::PriceChange { .. } | PolymarketWsMessage::BestBidAsk { .. } => {
+                Some(self.subjects.json_ticker(&condition_id))
+            }
+            PolymarketWsMessage::Book { .. } => Some(self.subjects.json_orderbook(&condition_id)),
+            PolymarketWsMessage::NewMarket { .. } | PolymarketWsMessage::MarketResolved { .. } => {
+                Some(self.subjects.json_lifecycle(&condition_id))
             }
+            PolymarketWsMessage::TickSizeChange { .. } => {
+                trace!(market = %condition_id, "Tick size change event, skipping publish");
+                None
+            }
+            PolymarketWsMessage::Unknown => None,
+        }
+    }
+}
+
+/// Log and count a frame (or array element) that did not deserialize into
+/// [`PolymarketWsMessage`]
+fn drop_frame(msg: &Message, reason: &'static str, error: &dyn std::fmt::Display, preview: &str) {
+    warn!(reason, error = %error, preview = %preview, "Dropping Polymarket frame");
+    metrics::inc_writer_dropped_frame(&msg.feed, reason);
+}
+
+/// Deserialize one array element. Book snapshots arrive without
+/// `event_type`, so elements carrying `bids`/`asks` are tagged as `book`.
+fn typed_element(element: &serde_json::Value) -> Result<PolymarketWsMessage, serde_json::Error> {
+    match element.as_object() {
+        Some(obj)
+            if !obj.contains_key("event_type")
+                && (obj.contains_key("bids") || obj.contains_key("asks")) =>
+        {
+            let mut tagged = obj.clone();
+            tagged.insert("event_type".to_string(), serde_json::Value::from("book"));
+            serde_json::from_value(serde_json::Value::Object(tagged))
         }
+        _ => PolymarketWsMessage::deserialize(element),
     }
 }
 
@@ -99,33 +123,16 @@ impl Writer for PolymarketNatsWriter {
         // Fast path: most payloads are single typed JSON objects. Route and publish
         // raw bytes directly to avoid JSON re-serialization and extra allocation.
         if let Ok(ws_msg) = serde_json::from_slice::<PolymarketWsMessage>(&msg.data) {
-            let condition_id = sanitize_subject_token(ws_msg.condition_id());
-
-            let subject = match ws_msg {
-                PolymarketWsMessage::LastTradePrice { .. } => {
-                    self.subjects.json_trade(&condition_id)
-                }
-                PolymarketWsMessage::PriceChange { .. } => {
-                    self.subjects.json_ticker(&condition_id)
-                }
-                PolymarketWsMessage::Book { .. } => {
-                    self.subjects.json_orderbook(&condition_id)
-                }
-                PolymarketWsMessage::BestBidAsk { .. } => {
-                    self.subjects.json_ticker(&condition_id)
-                }
-                PolymarketWsMessage::NewMarket { .. }
-                | PolymarketWsMessage::MarketResolved { .. } => {
-                    self.subjects.json_lifecycle(&condition_id)
-                }
-                PolymarketWsMessage::TickSizeChange { .. } => {
-                    trace!(market = %condition_id, "Tick size change event, skipping publish");
-                    return Ok(());
-                }
+            if matches!(ws_msg, PolymarketWsMessage::Unknown) {
+                drop_frame(msg, DROPPED_UNKNOWN_VARIANT, &"unknown event_type", &preview_fn());
+                return Ok(());
+            }
+            let Some(subject) = self.subject_for(&ws_msg) else {
+                return Ok(());
             };
 
             self.transport
-                .publish(&subject, msg.data.clone())
+                .publish_with_headers(&subject, msg.data.clone(), msg.capture_headers(None))
                 .await
                 .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
@@ -134,26 +141,36 @@ impl Writer for PolymarketNatsWriter {
         }
 
         // Polymarket sends all messages as JSON arrays: [{...}, {...}]
-        // Parse as array of raw values, then process each element individually.
+        // Parse as array of raw values, then type and route each element.
+        // Malformed frames and elements are dropped and counted, never fatal.
         let elements: Vec<serde_json::Value> = match serde_json::from_slice(&msg.data) {
             Ok(serde_json::Value::Array(arr)) => arr,
             Ok(obj @ serde_json::Value::Object(_)) => vec![obj],
             Ok(_) => {
-                warn!(preview = %preview_fn(), "Unexpected JSON type from Polymarket");
+                drop_frame(msg, DROPPED_MALFORMED, &"unexpected JSON type", &preview_fn());
                 return Ok(());
             }
             Err(e) => {
-                return Err(WriterError::WriteFailed(format!(
-                    "Failed to parse Polymarket message: {}. Preview: {}",
-                    e,
-                    preview_fn()
-                )));
+                drop_frame(msg, DROPPED_MALFORMED, &e, &preview_fn());
+                return Ok(());
             }
         };
 
+        let headers = msg.capture_headers(None);
         for element in elements {
-            let Some(subject) = self.subject_from_element(&element) else {
-                trace!(preview = %element, "Skipping unsupported Polymarket element");
+            let ws_msg = match typed_element(&element) {
+                Ok(PolymarketWsMessage::Unknown) => {
+                    let preview = element.to_string();
+                    drop_frame(msg, DROPPED_UNKNOWN_VARIANT, &"unknown event_type", &preview);
+                    continue;
+                }
+                Ok(m) => m,
+                Err(e) => {
+                    drop_frame(msg, DROPPED_MALFORMED, &e, &element.to_string());
+                    continue;
+                }
+            };
+            let Some(subject) = self.subject_for(&ws_msg) else {
                 continue;
             };
 
@@ -161,7 +178,7 @@ impl Writer for PolymarketNatsWriter {
             let element_bytes = serde_json::to_vec(&element)
                 .map_err(|e| WriterError::WriteFailed(format!("JSON serialize failed: {}", e)))?;
             self.transport
-                .publish(&subject, Bytes::from(element_bytes))
+                .publish_with_headers(&subject, Bytes::from(element_bytes), headers.clone())
                 .await
                 .map_err(|e| WriterError::WriteFailed(format!("NATS publish failed: {}", e)))?;
 
@@ -354,21 +371,47 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_invalid_json_returns_error() {
+    async fn test_invalid_json_is_dropped() {
         let transport = Arc::new(InMemoryTransport::new());
         let mut writer = PolymarketNatsWriter::new(transport.clone(), "dev", "polymarket");
 
-        let garbage = Message::new("polymarket", b"not valid json at all".to_vec());
-        let result = writer.write(&garbage).await;
-
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(
-            err.to_string().contains("Failed to parse Polymarket message"),
-            "Expected parse error, got: {}",
-            err
-        );
+        let garbage = Message::new("polymarket-invalid-test", b"not valid json at all".to_vec());
+        writer.write(&garbage).await.expect("malformed frames are dropped, not fatal");
         assert_eq!(writer.message_count(), 0);
+
+        let output = metrics::encode_metrics().unwrap();
+        assert!(output.contains(
+            r#"ssmd_connector_writer_dropped_frames_total{feed="polymarket-invalid-test",reason="malformed"} 1"#
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_and_malformed_elements_skipped() {
+        let transport = Arc::new(InMemoryTransport::new());
+        let mut writer = PolymarketNatsWriter::new(transport.clone(), "dev", "polymarket");
+
+        let mut sub = transport
+            .subscribe("dev.polymarket.json.trade.0x1234abcd")
+            .await
+            .unwrap();
+
+        // An unknown event type and a trade missing its price around a good trade
+        let array_json = br#"[{"event_type":"some_new_event","market":"0x1234abcd"},{"event_type":"last_trade_price","asset_id":"t1","market":"0x1234abcd"},{"event_type":"last_trade_price","asset_id":"t1","market":"0x1234abcd","price":"0.55"}]"#;
+        let msg = Message::new("polymarket-elements-test", array_json.to_vec());
+
+        writer.write(&msg).await.unwrap();
+
+        let received = sub.next().await.unwrap();
+        assert_eq!(received.subject, "dev.polymarket.json.trade.0x1234abcd");
+        assert_eq!(writer.message_count(), 1);
+
+        let output = metrics::encode_metrics().unwrap();
+        assert!(output.contains(
+            r#"ssmd_connector_writer_dropped_frames_total{feed="polymarket-elements-test",reason="malformed"} 1"#
+        ));
+        assert!(output.contains(
+            r#"ssmd_connector_writer_dropped_frames_total{feed="polymarket-elements-test",reason="unknown_variant"} 1"#
+        ));
     }
 
     #[tokio::test]
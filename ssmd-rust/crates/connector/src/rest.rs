@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::error::ConnectorError;
+use crate::traits::{Connector, TimestampedMsg};
+use ssmd_middleware::now_tsc;
+
+/// Poll interval used when the feed version doesn't set one
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Per-request HTTP timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Decides which polled snapshots are emitted
+#[derive(Debug, Default)]
+struct SnapshotFilter {
+    last: Option<Vec<u8>>,
+    emit_unchanged: bool,
+}
+
+impl SnapshotFilter {
+    /// Returns true if `body` should be emitted: always in full-snapshot mode,
+    /// otherwise only when it differs from the previous snapshot.
+    fn accept(&mut self, body: &[u8]) -> bool {
+        if !self.emit_unchanged && self.last.as_deref() == Some(body) {
+            return false;
+        }
+        self.last = Some(body.to_vec());
+        true
+    }
+}
+
+/// REST polling connector for snapshot-only sources.
+///
+/// GETs the endpoint every `poll_interval` and emits the raw response body as
+/// one message. By default a snapshot is only emitted when it differs from the
+/// previous one; `with_emit_unchanged(true)` emits every poll. Reconnectable:
+/// each `connect()` starts a fresh poller and message channel.
+pub struct RestConnector {
+    url: String,
+    poll_interval: Duration,
+    headers: Vec<(String, String)>,
+    emit_unchanged: bool,
+    rx: Option<mpsc::Receiver<TimestampedMsg>>,
+    poller: Option<tokio::task::JoinHandle<()>>,
+    /// Epoch seconds of the last successful poll (changed or not)
+    last_poll_epoch_secs: Arc<AtomicU64>,
+}
+
+impl RestConnector {
+    pub fn new(url: impl Into<String>, poll_interval: Duration) -> Self {
+        Self {
+            url: url.into(),
+            poll_interval,
+            headers: Vec::new(),
+            emit_unchanged: false,
+            rx: None,
+            poller: None,
+            last_poll_epoch_secs: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Add a request header (e.g. an API key resolved via `EnvResolver`)
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Emit every polled snapshot instead of only changed ones
+    pub fn with_emit_unchanged(mut self, emit_unchanged: bool) -> Self {
+        self.emit_unchanged = emit_unchanged;
+        self
+    }
+}
+
+/// GET `url` and return the body, treating non-2xx as an error
+async fn fetch(
+    client: &Client,
+    url: &str,
+    headers: &[(String, String)],
+) -> Result<Vec<u8>, String> {
+    let mut request = client.get(url);
+    for (name, value) in headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status));
+    }
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    Ok(body.to_vec())
+}
+
+fn now_epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl Connector for RestConnector {
+    async fn connect(&mut self) -> Result<(), ConnectorError> {
+        if let Some(poller) = self.poller.take() {
+            poller.abort();
+        }
+
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ConnectorError::ConnectionFailed(e.to_string()))?;
+
+        // Fail fast on a bad endpoint or credentials
+        let first = fetch(&client, &self.url, &self.headers)
+            .await
+            .map_err(|e| ConnectorError::ConnectionFailed(format!("{}: {}", self.url, e)))?;
+        self.last_poll_epoch_secs
+            .store(now_epoch_secs(), Ordering::SeqCst);
+
+        let (tx, rx) = mpsc::channel(1024);
+        self.rx = Some(rx);
+
+        let url = self.url.clone();
+        let headers = self.headers.clone();
+        let poll_interval = self.poll_interval;
+        let last_poll = Arc::clone(&self.last_poll_epoch_secs);
+        let mut filter = SnapshotFilter {
+            last: None,
+            emit_unchanged: self.emit_unchanged,
+        };
+
+        info!(url = %url, poll_interval_ms = poll_interval.as_millis() as u64, "REST poller started");
+
+        self.poller = Some(tokio::spawn(async move {
+            if filter.accept(&first) && tx.send((now_tsc(), first)).await.is_err() {
+                return;
+            }
+
+            let mut interval = tokio::time::interval(poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // Skip the immediate first tick — the initial snapshot was just sent
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                match fetch(&client, &url, &headers).await {
+                    Ok(body) => {
+                        last_poll.store(now_epoch_secs(), Ordering::SeqCst);
+                        if filter.accept(&body) && tx.send((now_tsc(), body)).await.is_err() {
+                            warn!("REST poller: message channel closed, exiting");
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        // Transient failures are retried on the next tick; a
+                        // feed that stays down is caught by the idle watchdog
+                        warn!(url = %url, error = %e, "REST poll failed");
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    fn messages(&mut self) -> mpsc::Receiver<TimestampedMsg> {
+        self.rx.take().expect("messages() called before connect() or twice")
+    }
+
+    async fn close(&mut self) -> Result<(), ConnectorError> {
+        if let Some(poller) = self.poller.take() {
+            poller.abort();
+        }
+        Ok(())
+    }
+
+    fn activity_handle(&self) -> Option<Arc<AtomicU64>> {
+        Some(Arc::clone(&self.last_poll_epoch_secs))
+    }
+
+    fn reconnectable(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::HeaderMap, routing::get, Router};
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_snapshot_filter_diff_mode() {
+        let mut filter = SnapshotFilter::default();
+        assert!(filter.accept(b"{\"a\":1}"));
+        assert!(!filter.accept(b"{\"a\":1}"));
+        assert!(filter.accept(b"{\"a\":2}"));
+        assert!(filter.accept(b"{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_snapshot_filter_full_mode() {
+        let mut filter = SnapshotFilter {
+            last: None,
+            emit_unchanged: true,
+        };
+        assert!(filter.accept(b"{\"a\":1}"));
+        assert!(filter.accept(b"{\"a\":1}"));
+    }
+
+    /// Serves `{"n":<polls / 2>}` so every other poll repeats the last snapshot,
+    /// and rejects requests without the expected API key.
+    async fn serve_snapshots() -> (String, Arc<AtomicUsize>) {
+        async fn snapshot(
+            State(polls): State<Arc<AtomicUsize>>,
+            headers: HeaderMap,
+        ) -> Result<String, axum::http::StatusCode> {
+            if headers.get("x-api-key").map(|v| v.as_bytes()) != Some(b"secret") {
+                return Err(axum::http::StatusCode::UNAUTHORIZED);
+            }
+            let n = polls.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("{{\"n\":{}}}", n / 2))
+        }
+
+        let polls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route("/snapshot", get(snapshot))
+            .with_state(Arc::clone(&polls));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/snapshot", addr), polls)
+    }
+
+    #[tokio::test]
+    async fn test_rest_connector_emits_changed_snapshots() {
+        let (url, polls) = serve_snapshots().await;
+        let mut connector = RestConnector::new(url, Duration::from_millis(10))
+            .with_header("x-api-key", "secret");
+        connector.connect().await.unwrap();
+        let mut rx = connector.messages();
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            let (_, body) = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            seen.push(String::from_utf8(body).unwrap());
+        }
+        connector.close().await.unwrap();
+
+        // Duplicate snapshots are suppressed
+        assert_eq!(seen, vec!["{\"n\":0}", "{\"n\":1}", "{\"n\":2}"]);
+        assert!(polls.load(Ordering::SeqCst) >= 5);
+        assert!(connector.activity_handle().unwrap().load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_rest_connector_connect_fails_without_auth() {
+        let (url, _) = serve_snapshots().await;
+        let mut connector = RestConnector::new(url, Duration::from_millis(10));
+        let err = connector.connect().await.unwrap_err();
+        assert!(matches!(err, ConnectorError::ConnectionFailed(_)));
+    }
+}
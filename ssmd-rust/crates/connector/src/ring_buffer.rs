@@ -30,6 +30,15 @@ pub struct SlotHeader {
     pub flags: u32,
 }
 
+/// Why a push was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError {
+    /// Consumer has not freed a slot; nothing was overwritten
+    Full,
+    /// Payload does not fit in a slot
+    TooLarge,
+}
+
 /// SPSC ring buffer backed by memory-mapped file
 pub struct RingBuffer {
     #[allow(dead_code)]
@@ -37,6 +46,10 @@ pub struct RingBuffer {
     mmap: MmapMut,
     write_pos: CachePaddedAtomic,
     read_pos: CachePaddedAtomic,
+    /// Pushes refused because the ring was full
+    overflows: CachePaddedAtomic,
+    /// Peak number of occupied slots
+    high_watermark: CachePaddedAtomic,
 }
 
 #[repr(align(64))]
@@ -56,6 +69,11 @@ impl CachePaddedAtomic {
     fn store(&self, v: u64, order: Ordering) {
         self.0.store(v, order)
     }
+
+    #[inline]
+    fn fetch_add(&self, v: u64, order: Ordering) -> u64 {
+        self.0.fetch_add(v, order)
+    }
 }
 
 impl RingBuffer {
@@ -77,6 +95,8 @@ impl RingBuffer {
             mmap,
             write_pos: CachePaddedAtomic::new(0),
             read_pos: CachePaddedAtomic::new(0),
+            overflows: CachePaddedAtomic::new(0),
+            high_watermark: CachePaddedAtomic::new(0),
         })
     }
 
@@ -90,6 +110,16 @@ impl RingBuffer {
         self.read_pos.load(Ordering::Acquire)
     }
 
+    /// Number of pushes refused because the ring was full
+    pub fn overflow_count(&self) -> u64 {
+        self.overflows.load(Ordering::Relaxed)
+    }
+
+    /// Peak number of occupied slots since creation
+    pub fn high_watermark(&self) -> u64 {
+        self.high_watermark.load(Ordering::Relaxed)
+    }
+
     /// Check if ring buffer is full
     pub fn is_full(&self) -> bool {
         let write = self.write_pos.load(Ordering::Acquire);
@@ -108,9 +138,16 @@ impl RingBuffer {
     /// Returns false if ring is full (backpressure)
     #[inline]
     pub fn try_write(&self, data: &[u8]) -> bool {
+        self.try_push(data).is_ok()
+    }
+
+    /// Producer: write message to ring buffer, reporting why it was refused.
+    /// Never overwrites unread slots; the caller decides whether to retry or drop.
+    #[inline]
+    pub fn try_push(&self, data: &[u8]) -> Result<(), PushError> {
         let max_payload = SLOT_SIZE - std::mem::size_of::<SlotHeader>();
         if data.len() > max_payload {
-            return false; // Message too large
+            return Err(PushError::TooLarge);
         }
 
         let write = self.write_pos.load(Ordering::Acquire);
@@ -118,7 +155,8 @@ impl RingBuffer {
 
         // Check if full (writer caught up to reader)
         if write.wrapping_sub(read) >= RING_SLOTS as u64 {
-            return false;
+            self.overflows.fetch_add(1, Ordering::Relaxed);
+            return Err(PushError::Full);
         }
 
         let slot_idx = (write as usize) % RING_SLOTS;
@@ -145,7 +183,13 @@ impl RingBuffer {
 
         // Release write position
         self.write_pos.store(write + 1, Ordering::Release);
-        true
+
+        // Single producer, so a plain load/store is enough for the peak
+        let occupied = (write + 1).wrapping_sub(read);
+        if occupied > self.high_watermark.load(Ordering::Relaxed) {
+            self.high_watermark.store(occupied, Ordering::Relaxed);
+        }
+        Ok(())
     }
 
     /// Consumer: read next message from ring buffer
@@ -260,6 +304,40 @@ mod tests {
         assert!(ring.try_write(b"new msg"));
     }
 
+    #[test]
+    fn test_try_push_reports_full_and_counts_overflow() {
+        let (ring, _tmp) = create_test_ring();
+
+        for i in 0..RING_SLOTS {
+            ring.try_push(format!("msg{}", i).as_bytes()).unwrap();
+        }
+        assert_eq!(ring.try_push(b"overflow"), Err(PushError::Full));
+        assert_eq!(ring.try_push(b"overflow"), Err(PushError::Full));
+        assert_eq!(ring.overflow_count(), 2);
+
+        // Oldest message was not overwritten
+        assert_eq!(ring.try_read().unwrap(), b"msg0");
+
+        let too_big = vec![0u8; SLOT_SIZE];
+        assert_eq!(ring.try_push(&too_big), Err(PushError::TooLarge));
+        assert_eq!(ring.overflow_count(), 2);
+    }
+
+    #[test]
+    fn test_high_watermark_tracks_peak_occupancy() {
+        let (ring, _tmp) = create_test_ring();
+
+        for _ in 0..10 {
+            ring.try_write(b"x");
+        }
+        for _ in 0..10 {
+            ring.try_read();
+        }
+        ring.try_write(b"y");
+
+        assert_eq!(ring.high_watermark(), 10);
+    }
+
     #[test]
     fn test_peek_does_not_advance() {
         let (ring, _tmp) = create_test_ring();
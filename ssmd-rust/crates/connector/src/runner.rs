@@ -1,16 +1,47 @@
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::select;
+use tokio::sync::mpsc;
 use tokio::task::JoinSet;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::error::ConnectorError;
 use crate::message::Message;
 use crate::metrics;
-use crate::traits::{Connector, Writer};
+use crate::snapshot::LastSeen;
+use crate::traits::{Connector, TimestampedMsg, Writer};
 use ssmd_middleware::{now_tsc, CLOCK};
 
+/// Exponential backoff for reconnecting a dropped connector
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay before the first reconnect attempt; doubles on each failure
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+    /// Consecutive failed attempts before giving up
+    pub max_retries: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_retries: 10,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before attempt `attempt` (1-based)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
 /// Runner orchestrates the data collection pipeline
 pub struct Runner<C: Connector, W: Writer> {
     feed_name: Arc<str>,
@@ -19,6 +50,13 @@ pub struct Runner<C: Connector, W: Writer> {
     connected: Arc<AtomicBool>,
     /// Unix timestamp (seconds) of last message received
     last_message_epoch_secs: Arc<AtomicU64>,
+    /// Successful reconnects since start
+    reconnects: Arc<AtomicU64>,
+    /// Last message per ticker, for the health server's `/snapshot`
+    last_seen: Arc<LastSeen>,
+    reconnect: ReconnectPolicy,
+    /// Treat the connection as dead if no message arrives within this window
+    max_idle: Option<Duration>,
 }
 
 impl<C: Connector, W: Writer> Runner<C, W> {
@@ -29,9 +67,26 @@ impl<C: Connector, W: Writer> Runner<C, W> {
             writer,
             connected: Arc::new(AtomicBool::new(false)),
             last_message_epoch_secs: Arc::new(AtomicU64::new(0)),
+            reconnects: Arc::new(AtomicU64::new(0)),
+            last_seen: Arc::new(LastSeen::new()),
+            reconnect: ReconnectPolicy::default(),
+            max_idle: None,
         }
     }
 
+    /// Treat the connection as dead after `secs` without a message: reconnect
+    /// if the connector supports it, otherwise exit with an error. 0 disables.
+    pub fn with_max_idle_secs(mut self, secs: u64) -> Self {
+        self.max_idle = (secs > 0).then(|| Duration::from_secs(secs));
+        self
+    }
+
+    /// Override the reconnect backoff (only used for reconnectable connectors)
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
     /// Returns whether the connector is currently connected
     pub fn is_connected(&self) -> bool {
         self.connected.load(Ordering::SeqCst)
@@ -47,6 +102,16 @@ impl<C: Connector, W: Writer> Runner<C, W> {
         Arc::clone(&self.last_message_epoch_secs)
     }
 
+    /// Returns a handle to the count of successful reconnects
+    pub fn reconnects_handle(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.reconnects)
+    }
+
+    /// Returns a handle to the per-ticker last-seen map
+    pub fn last_seen_handle(&self) -> Arc<LastSeen> {
+        Arc::clone(&self.last_seen)
+    }
+
     /// Returns the connector's activity handle if available.
     /// This tracks WebSocket activity (ping/pong + data messages) for health checks.
     /// Falls back to Runner's last_message_epoch_secs if connector doesn't track activity.
@@ -130,7 +195,10 @@ impl<C: Connector, W: Writer> Runner<C, W> {
             });
         }
 
+        let mut last_message_at = tokio::time::Instant::now();
+
         loop {
+            let max_idle = self.max_idle;
             select! {
                 _ = shutdown.changed() => {
                     if *shutdown.borrow() {
@@ -138,6 +206,30 @@ impl<C: Connector, W: Writer> Runner<C, W> {
                         break;
                     }
                 }
+                _ = async {
+                    match max_idle {
+                        Some(idle) => tokio::time::sleep_until(last_message_at + idle).await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    // Socket may still be open but the exchange has stopped sending
+                    self.connected.store(false, Ordering::SeqCst);
+                    let idle_secs = max_idle.map(|d| d.as_secs()).unwrap_or_default();
+                    if !self.connector.reconnectable() {
+                        error!(feed = %self.feed_name, idle_secs, "No messages within idle window - exiting to trigger restart");
+                        return Err(ConnectorError::Disconnected(format!("idle for {}s", idle_secs)));
+                    }
+                    warn!(feed = %self.feed_name, idle_secs, "No messages within idle window - reconnecting");
+                    self.connector.close().await.ok();
+                    match self.reconnect(&mut shutdown).await? {
+                        Some(new_rx) => rx = new_rx,
+                        None => {
+                            info!("Shutdown signal received during reconnect");
+                            break;
+                        }
+                    }
+                    last_message_at = tokio::time::Instant::now();
+                }
                 msg = rx.recv() => {
                     match msg {
                         Some((ws_tsc, data)) => {
@@ -172,12 +264,25 @@ impl<C: Connector, W: Writer> Runner<C, W> {
                             );
                             // Update last message time on successful write
                             self.update_last_message_time();
+                            self.last_seen.observe(&message.data, message.capture_ts_micros);
+                            last_message_at = tokio::time::Instant::now();
                         }
                         None => {
                             // Channel closed - connector disconnected unexpectedly
                             self.connected.store(false, Ordering::SeqCst);
-                            error!("Connector disconnected unexpectedly - exiting to trigger restart");
-                            return Err(ConnectorError::Disconnected("channel closed".to_string()));
+                            if !self.connector.reconnectable() {
+                                error!("Connector disconnected unexpectedly - exiting to trigger restart");
+                                return Err(ConnectorError::Disconnected("channel closed".to_string()));
+                            }
+                            warn!(feed = %self.feed_name, "Connector disconnected - reconnecting");
+                            match self.reconnect(&mut shutdown).await? {
+                                Some(new_rx) => rx = new_rx,
+                                None => {
+                                    info!("Shutdown signal received during reconnect");
+                                    break;
+                                }
+                            }
+                            last_message_at = tokio::time::Instant::now();
                         }
                     }
                 }
@@ -213,6 +318,57 @@ impl<C: Connector, W: Writer> Runner<C, W> {
 
         Ok(())
     }
+
+    /// Reconnect with exponential backoff and return the new message receiver.
+    ///
+    /// Returns `Ok(None)` if shutdown is signalled while waiting, and an error
+    /// once `max_retries` consecutive attempts have failed so the process exits
+    /// non-zero and K8s restarts the pod.
+    async fn reconnect(
+        &mut self,
+        shutdown: &mut tokio::sync::watch::Receiver<bool>,
+    ) -> Result<Option<mpsc::Receiver<TimestampedMsg>>, ConnectorError> {
+        for attempt in 1..=self.reconnect.max_retries {
+            let delay = self.reconnect.delay(attempt);
+            select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.changed() => {
+                    if *shutdown.borrow() {
+                        return Ok(None);
+                    }
+                }
+            }
+
+            match self.connector.connect().await {
+                Ok(()) => {
+                    self.connected.store(true, Ordering::SeqCst);
+                    self.reconnects.fetch_add(1, Ordering::SeqCst);
+                    info!(feed = %self.feed_name, attempt, "Reconnected to data source");
+                    return Ok(Some(self.connector.messages()));
+                }
+                Err(e) => {
+                    warn!(
+                        feed = %self.feed_name,
+                        attempt,
+                        max_retries = self.reconnect.max_retries,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Reconnect attempt failed"
+                    );
+                }
+            }
+        }
+
+        error!(
+            feed = %self.feed_name,
+            max_retries = self.reconnect.max_retries,
+            "Reconnect retries exhausted - exiting to trigger restart"
+        );
+        Err(ConnectorError::Disconnected(format!(
+            "reconnect failed after {} attempts",
+            self.reconnect.max_retries
+        )))
+    }
 }
 
 #[cfg(test)]
@@ -283,12 +439,177 @@ mod tests {
         }
     }
 
+    /// Hands out a fresh channel on every connect; fails `fail_connects` times
+    /// after the first successful connect.
+    struct ReconnectingConnector {
+        senders: mpsc::UnboundedSender<mpsc::Sender<TimestampedMsg>>,
+        rx: Option<mpsc::Receiver<TimestampedMsg>>,
+        connects: Arc<AtomicUsize>,
+        fail_connects: usize,
+    }
+
+    #[async_trait]
+    impl Connector for ReconnectingConnector {
+        async fn connect(&mut self) -> Result<(), ConnectorError> {
+            let n = self.connects.fetch_add(1, Ordering::SeqCst);
+            if n > 0 && n <= self.fail_connects {
+                return Err(ConnectorError::ConnectionFailed("refused".to_string()));
+            }
+            let (tx, rx) = mpsc::channel(10);
+            self.rx = Some(rx);
+            self.senders.send(tx).unwrap();
+            Ok(())
+        }
+        fn messages(&mut self) -> mpsc::Receiver<TimestampedMsg> {
+            self.rx.take().unwrap()
+        }
+        async fn close(&mut self) -> Result<(), ConnectorError> {
+            Ok(())
+        }
+        fn reconnectable(&self) -> bool {
+            true
+        }
+    }
+
+    fn fast_policy(max_retries: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            max_retries,
+        }
+    }
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            max_retries: 10,
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(4), Duration::from_millis(800));
+        assert_eq!(policy.delay(5), Duration::from_secs(1));
+        assert_eq!(policy.delay(40), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_runner_reconnects_after_disconnect() {
+        let (senders_tx, mut senders_rx) = mpsc::unbounded_channel();
+        let connects = Arc::new(AtomicUsize::new(0));
+        let connector = ReconnectingConnector {
+            senders: senders_tx,
+            rx: None,
+            connects: Arc::clone(&connects),
+            fail_connects: 2,
+        };
+        let (writer, write_count) = MockWriter::new();
+
+        let mut runner =
+            Runner::new("test-feed", connector, writer).with_reconnect_policy(fast_policy(5));
+        let connected = runner.connected_handle();
+        let reconnects = runner.reconnects_handle();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = tokio::spawn(async move { runner.run(shutdown_rx).await });
+
+        // Drop the first connection's sender to simulate a closed socket
+        let first = senders_rx.recv().await.unwrap();
+        first.send((now_tsc(), b"{\"n\":1}".to_vec())).await.unwrap();
+        drop(first);
+
+        // Two refused attempts, then a fresh connection
+        let second = senders_rx.recv().await.unwrap();
+        second.send((now_tsc(), b"{\"n\":2}".to_vec())).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(connected.load(Ordering::SeqCst));
+        assert_eq!(reconnects.load(Ordering::SeqCst), 1);
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap().unwrap();
+
+        assert_eq!(connects.load(Ordering::SeqCst), 4);
+        assert_eq!(write_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_runner_exits_after_reconnect_cap() {
+        let (senders_tx, mut senders_rx) = mpsc::unbounded_channel();
+        let connects = Arc::new(AtomicUsize::new(0));
+        let connector = ReconnectingConnector {
+            senders: senders_tx,
+            rx: None,
+            connects: Arc::clone(&connects),
+            fail_connects: usize::MAX,
+        };
+        let (writer, _) = MockWriter::new();
+
+        let mut runner =
+            Runner::new("test-feed", connector, writer).with_reconnect_policy(fast_policy(3));
+        let connected = runner.connected_handle();
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = tokio::spawn(async move { runner.run(shutdown_rx).await });
+
+        drop(senders_rx.recv().await.unwrap());
+
+        let err = handle.await.unwrap().unwrap_err();
+        assert!(matches!(err, ConnectorError::Disconnected(_)));
+        assert!(!connected.load(Ordering::SeqCst));
+        // Initial connect + 3 retries
+        assert_eq!(connects.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_runner_reconnects_when_idle() {
+        let (senders_tx, mut senders_rx) = mpsc::unbounded_channel();
+        let connects = Arc::new(AtomicUsize::new(0));
+        let connector = ReconnectingConnector {
+            senders: senders_tx,
+            rx: None,
+            connects: Arc::clone(&connects),
+            fail_connects: 0,
+        };
+        let (writer, _) = MockWriter::new();
+
+        let mut runner = Runner::new("test-feed", connector, writer)
+            .with_reconnect_policy(fast_policy(3))
+            .with_max_idle_secs(1);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = tokio::spawn(async move { runner.run(shutdown_rx).await });
+
+        // Socket stays open (sender held) but never delivers a message
+        let _silent = senders_rx.recv().await.unwrap();
+        let _fresh = tokio::time::timeout(Duration::from_secs(3), senders_rx.recv())
+            .await
+            .expect("idle watchdog should reconnect")
+            .unwrap();
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap().unwrap();
+        assert_eq!(connects.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_runner_exits_when_idle_and_not_reconnectable() {
+        let (connector, _msg_tx) = MockConnector::new();
+        let (writer, _) = MockWriter::new();
+
+        let mut runner = Runner::new("test-feed", connector, writer).with_max_idle_secs(1);
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+        let err = tokio::time::timeout(Duration::from_secs(3), runner.run(shutdown_rx))
+            .await
+            .expect("idle watchdog should fire")
+            .unwrap_err();
+        assert!(matches!(err, ConnectorError::Disconnected(_)));
+    }
+
     #[tokio::test]
     async fn test_runner_processes_messages() {
         let (connector, msg_tx) = MockConnector::new();
         let (writer, write_count) = MockWriter::new();
 
         let mut runner = Runner::new("test-feed", connector, writer);
+        let last_seen = runner.last_seen_handle();
         let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
 
         // Spawn runner
@@ -307,5 +628,33 @@ mod tests {
         handle.await.unwrap().unwrap();
 
         assert!(write_count.load(Ordering::SeqCst) >= 1);
+        // No ticker in the payload, so nothing to snapshot
+        assert!(last_seen.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_runner_records_last_seen() {
+        let (connector, msg_tx) = MockConnector::new();
+        let (writer, _) = MockWriter::new();
+
+        let mut runner = Runner::new("test-feed", connector, writer);
+        let last_seen = runner.last_seen_handle();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = tokio::spawn(async move { runner.run(shutdown_rx).await });
+
+        msg_tx
+            .send((now_tsc(), br#"{"feed":"ticker","product_id":"PF_XBTUSD"}"#.to_vec()))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap().unwrap();
+
+        let snap = last_seen.snapshot();
+        assert_eq!(snap.len(), 1);
+        assert_eq!(snap[0].ticker, "PF_XBTUSD");
+        assert_eq!(snap[0].msg_type, "ticker");
+        assert!(snap[0].last_message_epoch_micros > 0);
     }
 }
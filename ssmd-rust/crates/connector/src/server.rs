@@ -12,7 +12,8 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::net::TcpListener;
 
-use crate::metrics::encode_metrics;
+use crate::metrics::{encode_metrics, set_last_message_age};
+use crate::snapshot::{LastSeen, LastSeenEntry};
 
 /// Default staleness threshold in seconds (5 minutes)
 /// If no messages received for this duration, health check reports stale
@@ -32,6 +33,26 @@ pub struct HealthResponse {
     pub stale: bool,
 }
 
+/// Detailed connector status for `/status`
+#[derive(Serialize)]
+pub struct StatusResponse {
+    pub feed: String,
+    pub connected: bool,
+    pub ready: bool,
+    /// Unix timestamp (seconds) of last message received (None if no messages yet)
+    pub last_message_epoch_secs: Option<u64>,
+    pub last_message_secs_ago: Option<u64>,
+    /// Successful reconnects since the process started
+    pub reconnects: u64,
+}
+
+/// Per-ticker view of the connector's latest messages for `/snapshot`
+#[derive(Serialize)]
+pub struct SnapshotResponse {
+    pub feed: String,
+    pub tickers: Vec<LastSeenEntry>,
+}
+
 /// Shared state for health endpoints
 #[derive(Clone)]
 pub struct ServerState {
@@ -41,6 +62,13 @@ pub struct ServerState {
     pub last_message_epoch_secs: Arc<AtomicU64>,
     /// Staleness threshold in seconds
     pub stale_threshold_secs: u64,
+    /// Successful reconnects since start (shared from `Runner::reconnects_handle`)
+    pub reconnects: Arc<AtomicU64>,
+    /// `/readyz` requires a message within this window; 0 falls back to
+    /// `stale_threshold_secs`
+    pub max_idle_secs: u64,
+    /// Last message per ticker (shared from `Runner::last_seen_handle`)
+    pub last_seen: Arc<LastSeen>,
 }
 
 impl ServerState {
@@ -50,6 +78,9 @@ impl ServerState {
             connected,
             last_message_epoch_secs: Arc::new(AtomicU64::new(0)),
             stale_threshold_secs: DEFAULT_STALE_THRESHOLD_SECS,
+            reconnects: Arc::new(AtomicU64::new(0)),
+            max_idle_secs: 0,
+            last_seen: Arc::new(LastSeen::new()),
         }
     }
 
@@ -65,9 +96,42 @@ impl ServerState {
             connected,
             last_message_epoch_secs,
             stale_threshold_secs,
+            reconnects: Arc::new(AtomicU64::new(0)),
+            max_idle_secs: 0,
+            last_seen: Arc::new(LastSeen::new()),
         }
     }
 
+    /// Share the runner's reconnect counter for `/status`
+    pub fn with_reconnects(mut self, reconnects: Arc<AtomicU64>) -> Self {
+        self.reconnects = reconnects;
+        self
+    }
+
+    /// Share the runner's last-seen map for `/snapshot`
+    pub fn with_last_seen(mut self, last_seen: Arc<LastSeen>) -> Self {
+        self.last_seen = last_seen;
+        self
+    }
+
+    /// Set the `/readyz` freshness window (0 = use the staleness threshold)
+    pub fn with_max_idle_secs(mut self, secs: u64) -> Self {
+        self.max_idle_secs = secs;
+        self
+    }
+
+    /// Connected and a message arrived within the readiness window.
+    /// Unlike `/ready`, a connector that has not received anything yet is not ready.
+    fn is_ready(&self) -> bool {
+        let window = if self.max_idle_secs > 0 {
+            self.max_idle_secs
+        } else {
+            self.stale_threshold_secs
+        };
+        let connected = self.connected.load(Ordering::SeqCst);
+        matches!(self.staleness_info().0, Some(secs_ago) if connected && secs_ago <= window)
+    }
+
     /// Calculate staleness info from current time
     fn staleness_info(&self) -> (Option<u64>, bool) {
         let last_msg = self.last_message_epoch_secs.load(Ordering::SeqCst);
@@ -146,8 +210,51 @@ async fn ready(State(state): State<ServerState>) -> (StatusCode, Json<HealthResp
     )
 }
 
+/// Liveness endpoint - 200 whenever the process can serve HTTP
+async fn livez() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Readiness endpoint - 200 only if connected and a message arrived within
+/// `max_idle_secs`
+async fn readyz(State(state): State<ServerState>) -> StatusCode {
+    if state.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// Status endpoint - last message timestamp, reconnect count and readiness
+async fn status(State(state): State<ServerState>) -> Json<StatusResponse> {
+    let last_msg = state.last_message_epoch_secs.load(Ordering::SeqCst);
+    let (last_message_secs_ago, _) = state.staleness_info();
+    Json(StatusResponse {
+        feed: state.feed_name.clone(),
+        connected: state.connected.load(Ordering::SeqCst),
+        ready: state.is_ready(),
+        last_message_epoch_secs: (last_msg != 0).then_some(last_msg),
+        last_message_secs_ago,
+        reconnects: state.reconnects.load(Ordering::SeqCst),
+    })
+}
+
+/// Snapshot endpoint - per ticker, the type and capture time of the last
+/// message written (no payloads)
+async fn snapshot(State(state): State<ServerState>) -> Json<SnapshotResponse> {
+    Json(SnapshotResponse {
+        feed: state.feed_name.clone(),
+        tickers: state.last_seen.snapshot(),
+    })
+}
+
 /// Metrics endpoint - returns Prometheus text format
-async fn metrics() -> impl IntoResponse {
+async fn metrics(State(state): State<ServerState>) -> impl IntoResponse {
+    // Age is computed at scrape time so a stalled feed keeps climbing
+    if let (Some(secs_ago), _) = state.staleness_info() {
+        set_last_message_age(&state.feed_name, secs_ago as f64);
+    }
+
     match encode_metrics() {
         Ok(body) => (
             StatusCode::OK,
@@ -167,6 +274,10 @@ pub fn create_router(state: ServerState) -> Router {
     Router::new()
         .route("/health", get(health))
         .route("/ready", get(ready))
+        .route("/livez", get(livez))
+        .route("/readyz", get(readyz))
+        .route("/status", get(status))
+        .route("/snapshot", get(snapshot))
         .route("/metrics", get(metrics))
         .with_state(state)
 }
@@ -191,6 +302,9 @@ mod tests {
             connected: Arc::new(AtomicBool::new(connected)),
             last_message_epoch_secs: Arc::new(AtomicU64::new(0)),
             stale_threshold_secs: DEFAULT_STALE_THRESHOLD_SECS,
+            reconnects: Arc::new(AtomicU64::new(0)),
+            max_idle_secs: 0,
+            last_seen: Arc::new(LastSeen::new()),
         }
     }
 
@@ -200,6 +314,9 @@ mod tests {
             connected: Arc::new(AtomicBool::new(connected)),
             last_message_epoch_secs: Arc::new(AtomicU64::new(last_msg_epoch)),
             stale_threshold_secs: threshold,
+            reconnects: Arc::new(AtomicU64::new(0)),
+            max_idle_secs: 0,
+            last_seen: Arc::new(LastSeen::new()),
         }
     }
 
@@ -300,6 +417,96 @@ mod tests {
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[tokio::test]
+    async fn test_livez_ok_when_disconnected() {
+        let state = create_test_state(false);
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/livez").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_requires_message_within_max_idle() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // Connected but nothing received yet
+        let app = create_router(create_test_state(true));
+        let response = app
+            .oneshot(Request::builder().uri("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Fresh under the staleness threshold but older than max_idle_secs
+        let state = create_test_state_with_last_message(true, now - 30, 300).with_max_idle_secs(10);
+        let response = create_router(state)
+            .oneshot(Request::builder().uri("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let state = create_test_state_with_last_message(true, now, 300).with_max_idle_secs(10);
+        let response = create_router(state)
+            .oneshot(Request::builder().uri("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_reconnects_and_last_message() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let state = create_test_state_with_last_message(true, now, 60)
+            .with_reconnects(Arc::new(AtomicU64::new(3)));
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["reconnects"], 3);
+        assert_eq!(json["last_message_epoch_secs"], now);
+        assert_eq!(json["ready"], true);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_endpoint() {
+        let last_seen = Arc::new(LastSeen::new());
+        last_seen.observe(br#"{"feed":"ticker","product_id":"PF_XBTUSD","bid":1.0}"#, 1_000);
+        let app = create_router(create_test_state(true).with_last_seen(last_seen));
+
+        let response = app
+            .oneshot(Request::builder().uri("/snapshot").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["feed"], "test-feed");
+        let tickers = json["tickers"].as_array().unwrap();
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0]["ticker"], "PF_XBTUSD");
+        assert_eq!(tickers[0]["type"], "ticker");
+        assert_eq!(tickers[0]["last_message_epoch_micros"], 1_000);
+        assert!(tickers[0].get("bid").is_none());
+    }
+
     #[tokio::test]
     async fn test_metrics_endpoint() {
         let state = create_test_state(true);
@@ -316,6 +523,31 @@ mod tests {
         assert!(content_type.to_str().unwrap().contains("text/plain"));
     }
 
+    #[tokio::test]
+    async fn test_metrics_reports_last_message_age() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut state = create_test_state_with_last_message(true, now - 120, 300);
+        state.feed_name = "age-test-feed".to_string();
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        let line = text
+            .lines()
+            .find(|l| l.starts_with("ssmd_connector_last_message_age_seconds{feed=\"age-test-feed\"}"))
+            .expect("age gauge present");
+        let age: f64 = line.rsplit(' ').next().unwrap().parse().unwrap();
+        assert!((120.0..125.0).contains(&age));
+    }
+
     #[test]
     fn test_staleness_info_no_messages() {
         let state = create_test_state(true);
@@ -0,0 +1,232 @@
+//! Last-seen message per ticker, for the health server's `/snapshot`.
+//!
+//! The runner records the ticker, message type and capture time of every
+//! message it writes. Only those few fields are parsed out of the raw bytes;
+//! payloads are never stored.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Tickers beyond this many are not tracked, bounding memory on huge feeds
+pub const MAX_TRACKED_TICKERS: usize = 50_000;
+
+/// Last message seen for one ticker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LastSeenEntry {
+    pub ticker: String,
+    /// Feed-specific message type (e.g. `ticker`, `trade`, `book`)
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    /// Capture time in Unix epoch microseconds
+    pub last_message_epoch_micros: i64,
+}
+
+/// Shared map of ticker -> last message, written by the runner and read by
+/// the health server
+#[derive(Debug, Default)]
+pub struct LastSeen {
+    entries: Mutex<HashMap<String, LastSeenEntry>>,
+}
+
+impl LastSeen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `data` if a ticker and message type can be found in it.
+    /// Control frames and unrecognised shapes are ignored.
+    pub fn observe(&self, data: &[u8], capture_ts_micros: i64) {
+        let Some((ticker, msg_type)) = probe(data) else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get_mut(ticker.as_ref()) {
+            Some(entry) => {
+                if entry.msg_type != msg_type {
+                    entry.msg_type = msg_type.into_owned();
+                }
+                entry.last_message_epoch_micros = capture_ts_micros;
+            }
+            None if entries.len() < MAX_TRACKED_TICKERS => {
+                let ticker = ticker.into_owned();
+                entries.insert(
+                    ticker.clone(),
+                    LastSeenEntry {
+                        ticker,
+                        msg_type: msg_type.into_owned(),
+                        last_message_epoch_micros: capture_ts_micros,
+                    },
+                );
+            }
+            None => {}
+        }
+    }
+
+    /// All tracked tickers, sorted by ticker
+    pub fn snapshot(&self) -> Vec<LastSeenEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out: Vec<LastSeenEntry> = entries.values().cloned().collect();
+        out.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+        out
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Fields that carry a ticker or message type across the supported feeds:
+/// Kraken Futures (`feed`, `product_id`), Kraken Spot (`channel`,
+/// `data[].symbol`), Polymarket (`event_type`, `asset_id`), Massive (`ev`,
+/// `sym`), Kalshi (`type`, `msg.market_ticker`) and Binance (`data.e`,
+/// `data.s`). `type` is checked late because Kraken uses it for sub-types
+/// (`fill`, `update`).
+#[derive(Deserialize)]
+struct Probe<'a> {
+    #[serde(rename = "type", borrow)]
+    kind: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    feed: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    channel: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    event_type: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    ev: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    product_id: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    asset_id: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    sym: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    msg: Option<ProbeInner<'a>>,
+    #[serde(borrow)]
+    data: Option<ProbeData<'a>>,
+}
+
+#[derive(Deserialize)]
+struct ProbeInner<'a> {
+    #[serde(borrow)]
+    market_ticker: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    symbol: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    s: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    e: Option<Cow<'a, str>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProbeData<'a> {
+    #[serde(borrow)]
+    One(ProbeInner<'a>),
+    #[serde(borrow)]
+    Many(Vec<ProbeInner<'a>>),
+    #[allow(dead_code)]
+    Other(serde::de::IgnoredAny),
+}
+
+/// Extract (ticker, message type) from a raw message
+fn probe(data: &[u8]) -> Option<(Cow<'_, str>, Cow<'_, str>)> {
+    let p: Probe = serde_json::from_slice(data).ok()?;
+
+    let inner = match p.data {
+        Some(ProbeData::One(inner)) => Some(inner),
+        Some(ProbeData::Many(mut items)) if !items.is_empty() => Some(items.swap_remove(0)),
+        _ => None,
+    };
+    let (data_ticker, data_type) = match inner {
+        Some(i) => (i.symbol.or(i.s), i.e),
+        None => (None, None),
+    };
+
+    let ticker = p
+        .msg
+        .and_then(|m| m.market_ticker)
+        .or(p.product_id)
+        .or(p.asset_id)
+        .or(p.sym)
+        .or(data_ticker)?;
+    let msg_type = p
+        .feed
+        .or(p.channel)
+        .or(p.event_type)
+        .or(p.ev)
+        .or(p.kind)
+        .or(data_type)?;
+    Some((ticker, msg_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_feeds() {
+        let cases: &[(&[u8], &str, &str)] = &[
+            (
+                br#"{"type":"ticker","sid":1,"msg":{"market_ticker":"KXBTC-26","price":50}}"#,
+                "KXBTC-26",
+                "ticker",
+            ),
+            (
+                br#"{"feed":"trade","product_id":"PF_XBTUSD","type":"fill","qty":1.0}"#,
+                "PF_XBTUSD",
+                "trade",
+            ),
+            (
+                br#"{"channel":"ticker","type":"update","data":[{"symbol":"BTC/USD","bid":1.0}]}"#,
+                "BTC/USD",
+                "ticker",
+            ),
+            (
+                br#"{"event_type":"book","asset_id":"1234","market":"0xabc"}"#,
+                "1234",
+                "book",
+            ),
+            (
+                br#"{"stream":"btcusdt@trade","data":{"e":"trade","s":"BTCUSDT","p":"1"}}"#,
+                "BTCUSDT",
+                "trade",
+            ),
+            (br#"{"ev":"AM","sym":"AAPL","o":1.0}"#, "AAPL", "AM"),
+        ];
+        for (data, ticker, msg_type) in cases {
+            let (t, m) = probe(data).expect("probe");
+            assert_eq!(t, *ticker);
+            assert_eq!(m, *msg_type);
+        }
+    }
+
+    #[test]
+    fn test_probe_skips_control_frames() {
+        assert!(probe(br#"{"event":"heartbeat"}"#).is_none());
+        assert!(probe(br#"{"type":"subscribed","id":1}"#).is_none());
+        assert!(probe(b"not json").is_none());
+    }
+
+    #[test]
+    fn test_observe_keeps_latest_per_ticker() {
+        let seen = LastSeen::new();
+        seen.observe(br#"{"feed":"ticker","product_id":"PF_ETHUSD"}"#, 100);
+        seen.observe(br#"{"feed":"trade","product_id":"PF_ETHUSD"}"#, 200);
+        seen.observe(br#"{"feed":"ticker","product_id":"PF_XBTUSD"}"#, 150);
+        seen.observe(br#"{"event":"heartbeat"}"#, 300);
+
+        let snap = seen.snapshot();
+        assert_eq!(snap.len(), 2);
+        assert_eq!(snap[0].ticker, "PF_ETHUSD");
+        assert_eq!(snap[0].msg_type, "trade");
+        assert_eq!(snap[0].last_message_epoch_micros, 200);
+        assert_eq!(snap[1].ticker, "PF_XBTUSD");
+        assert_eq!(snap[1].last_message_epoch_micros, 150);
+    }
+}
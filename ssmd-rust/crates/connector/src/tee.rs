@@ -0,0 +1,148 @@
+//! Fan-out writer for bringing up a new feed.
+//!
+//! [`TeeWriter`] hands every message to a primary writer (normally the NATS
+//! writer serving live consumers) and then to a secondary one (normally a
+//! local [`FileWriter`](crate::writer::FileWriter) kept for verification).
+//! A write stops at the first failing sink, and the error names that sink so
+//! a broken local disk is not mistaken for a NATS outage.
+
+use async_trait::async_trait;
+
+use crate::error::WriterError;
+use crate::message::Message;
+use crate::traits::Writer;
+
+/// Writer that sends each message to two writers in order
+pub struct TeeWriter<A: Writer, B: Writer> {
+    primary: A,
+    secondary: B,
+    primary_name: &'static str,
+    secondary_name: &'static str,
+}
+
+impl<A: Writer, B: Writer> TeeWriter<A, B> {
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self {
+            primary,
+            secondary,
+            primary_name: "primary",
+            secondary_name: "secondary",
+        }
+    }
+
+    /// Names used for each sink in errors (e.g. `"nats"`, `"file"`)
+    pub fn with_names(mut self, primary: &'static str, secondary: &'static str) -> Self {
+        self.primary_name = primary;
+        self.secondary_name = secondary;
+        self
+    }
+}
+
+fn sink_error(sink: &'static str, source: WriterError) -> WriterError {
+    WriterError::Sink {
+        sink,
+        source: Box::new(source),
+    }
+}
+
+#[async_trait]
+impl<A: Writer, B: Writer> Writer for TeeWriter<A, B> {
+    async fn write(&mut self, msg: &Message) -> Result<(), WriterError> {
+        self.primary
+            .write(msg)
+            .await
+            .map_err(|e| sink_error(self.primary_name, e))?;
+        self.secondary
+            .write(msg)
+            .await
+            .map_err(|e| sink_error(self.secondary_name, e))
+    }
+
+    /// Close both sinks even if the first fails, returning the first error
+    async fn close(&mut self) -> Result<(), WriterError> {
+        let primary = self.primary.close().await;
+        let secondary = self.secondary.close().await;
+        primary.map_err(|e| sink_error(self.primary_name, e))?;
+        secondary.map_err(|e| sink_error(self.secondary_name, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockWriter {
+        written: Arc<Mutex<Vec<Bytes>>>,
+        fail: Arc<AtomicBool>,
+        closes: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Writer for MockWriter {
+        async fn write(&mut self, msg: &Message) -> Result<(), WriterError> {
+            if self.fail.load(Ordering::Relaxed) {
+                return Err(WriterError::WriteFailed("down".into()));
+            }
+            self.written.lock().unwrap().push(msg.data.clone());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), WriterError> {
+            self.closes.fetch_add(1, Ordering::Relaxed);
+            if self.fail.load(Ordering::Relaxed) {
+                return Err(WriterError::WriteFailed("down".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tee_writes_both_sinks() {
+        let nats = MockWriter::default();
+        let file = MockWriter::default();
+        let mut tee = TeeWriter::new(nats.clone(), file.clone());
+
+        tee.write(&Message::new("kraken", b"a".to_vec())).await.unwrap();
+        tee.write(&Message::new("kraken", b"b".to_vec())).await.unwrap();
+
+        let expected = vec![Bytes::from("a"), Bytes::from("b")];
+        assert_eq!(*nats.written.lock().unwrap(), expected);
+        assert_eq!(*file.written.lock().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_tee_short_circuits_and_names_sink() {
+        let nats = MockWriter::default();
+        let file = MockWriter::default();
+        let mut tee = TeeWriter::new(nats.clone(), file.clone()).with_names("nats", "file");
+
+        nats.fail.store(true, Ordering::Relaxed);
+        let err = tee.write(&Message::new("kraken", b"a".to_vec())).await.unwrap_err();
+        assert!(matches!(err, WriterError::Sink { sink: "nats", .. }));
+        assert!(file.written.lock().unwrap().is_empty());
+
+        nats.fail.store(false, Ordering::Relaxed);
+        file.fail.store(true, Ordering::Relaxed);
+        let err = tee.write(&Message::new("kraken", b"b".to_vec())).await.unwrap_err();
+        assert!(matches!(err, WriterError::Sink { sink: "file", .. }));
+        assert_eq!(err.to_string(), "file sink: write failed: down");
+        assert_eq!(nats.written.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tee_close_closes_both_on_error() {
+        let nats = MockWriter::default();
+        let file = MockWriter::default();
+        let mut tee = TeeWriter::new(nats.clone(), file.clone()).with_names("nats", "file");
+
+        nats.fail.store(true, Ordering::Relaxed);
+        let err = tee.close().await.unwrap_err();
+        assert!(matches!(err, WriterError::Sink { sink: "nats", .. }));
+        assert_eq!(nats.closes.load(Ordering::Relaxed), 1);
+        assert_eq!(file.closes.load(Ordering::Relaxed), 1);
+    }
+}
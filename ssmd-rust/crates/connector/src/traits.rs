@@ -37,6 +37,13 @@ pub trait Connector: Send + Sync {
     fn tasks(&mut self) -> Option<JoinSet<()>> {
         None
     }
+
+    /// Whether the runner may call `connect()` again after the message channel
+    /// closes. Connectors that return true must hand out a fresh receiver from
+    /// `messages()` after each successful `connect()`.
+    fn reconnectable(&self) -> bool {
+        false
+    }
 }
 
 /// Writer trait for output destinations (file, S3, NATS, etc.)
@@ -0,0 +1,396 @@
+//! Local write-ahead log in front of a `Writer`.
+//!
+//! Each frame is appended to `{dir}/{feed}.wal` and flushed before it is
+//! handed to the inner writer, and the log is truncated once the inner write
+//! returns Ok. A failed publish leaves the frame on disk; the runner exits on
+//! write errors, and the next process replays the log via [`WalWriter::replay`]
+//! before consuming new data. Delivery is at-least-once: a crash between the
+//! publish and the truncate re-sends that frame on restart.
+//!
+//! A file starts with [`WAL_MAGIC`] and a format version byte. Version 1
+//! records are a `u32` payload length, the capture time (`i64` Unix
+//! microseconds), a flag byte and `u64` for the source sequence, then the raw
+//! bytes, all little-endian; replay rebuilds the message with its original
+//! capture time and sequence. Files without the header hold the original
+//! format, bare length-prefixed frames, and still replay (stamped with the
+//! replay time).
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::error::WriterError;
+use crate::message::Message;
+use crate::metrics;
+use crate::traits::Writer;
+
+/// Leading bytes of a versioned WAL file
+const WAL_MAGIC: &[u8; 4] = b"SWAL";
+
+/// Record format written by this version
+const WAL_VERSION: u8 = 1;
+
+/// Bytes of a version 1 record before the payload
+const RECORD_HEADER_LEN: usize = 4 + 8 + 1 + 8;
+
+/// A frame read back from the WAL
+#[derive(Debug, Clone)]
+struct WalRecord {
+    /// `None` for frames from an unversioned file, which didn't store it
+    capture_ts_micros: Option<i64>,
+    source_seq: Option<u64>,
+    data: Bytes,
+}
+
+impl WalRecord {
+    fn from_message(msg: &Message) -> Self {
+        Self {
+            capture_ts_micros: Some(msg.capture_ts_micros),
+            source_seq: msg.source_seq,
+            data: msg.data.clone(),
+        }
+    }
+
+    fn to_message(&self, feed: &Arc<str>) -> Message {
+        match self.capture_ts_micros {
+            Some(ts) => Message::from_capture(feed.clone(), self.data.clone(), ts, self.source_seq),
+            None => Message::new(feed.clone(), self.data.clone()),
+        }
+    }
+}
+
+/// Writer wrapper that persists frames locally until the inner write succeeds.
+pub struct WalWriter<W: Writer> {
+    inner: W,
+    feed: Arc<str>,
+    path: PathBuf,
+    file: File,
+    /// Frames on disk that have not been delivered yet (loaded at open or
+    /// left by a failed write)
+    pending: Vec<WalRecord>,
+    /// The file is empty, so the next append writes the header first
+    empty: bool,
+}
+
+impl<W: Writer> WalWriter<W> {
+    /// Open (or create) the WAL for `feed` under `dir`, loading any frames
+    /// left behind by a previous process.
+    pub fn open(dir: impl AsRef<Path>, feed: &str, inner: W) -> Result<Self, WriterError> {
+        fs::create_dir_all(dir.as_ref())?;
+        let path = dir.as_ref().join(format!("{}.wal", feed));
+
+        let mut buf = Vec::new();
+        if path.exists() {
+            File::open(&path)?.read_to_end(&mut buf)?;
+        }
+        let (pending, torn) = decode_records(&buf)?;
+        if torn > 0 {
+            warn!(path = %path.display(), bytes = torn, "discarding torn WAL tail");
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        metrics::set_wal_pending(feed, pending.len() as i64);
+
+        let mut wal = Self {
+            inner,
+            feed: Arc::from(feed),
+            path,
+            file,
+            empty: buf.is_empty(),
+            pending,
+        };
+        if wal.pending.is_empty() && !wal.empty {
+            // Nothing to deliver; drop a torn tail (or an unversioned file)
+            // so new records aren't appended after it
+            wal.truncate()?;
+        }
+        Ok(wal)
+    }
+
+    /// Number of frames loaded from disk that still need replaying.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Publish frames left over from a previous run, in their original order,
+    /// then truncate the log. Call once before the runner starts.
+    pub async fn replay(&mut self) -> Result<usize, WriterError> {
+        let frames = std::mem::take(&mut self.pending);
+        let count = frames.len();
+        for (i, record) in frames.iter().enumerate() {
+            let msg = record.to_message(&self.feed);
+            if let Err(e) = self.inner.write(&msg).await {
+                // Keep what was not delivered so a later replay can retry
+                self.pending = frames[i..].to_vec();
+                metrics::set_wal_pending(&self.feed, self.pending.len() as i64);
+                return Err(e);
+            }
+        }
+        self.truncate()?;
+        if count > 0 {
+            info!(path = %self.path.display(), count, "replayed WAL frames");
+        }
+        Ok(count)
+    }
+
+    fn append(&mut self, msg: &Message) -> Result<(), WriterError> {
+        let len = u32::try_from(msg.data.len())
+            .map_err(|_| WriterError::WriteFailed("frame too large for WAL".to_string()))?;
+
+        let mut buf = Vec::with_capacity(WAL_MAGIC.len() + 1 + RECORD_HEADER_LEN + msg.data.len());
+        if self.empty {
+            buf.extend_from_slice(WAL_MAGIC);
+            buf.push(WAL_VERSION);
+        }
+        buf.extend_from_slice(&len.to_le_bytes());
+        buf.extend_from_slice(&msg.capture_ts_micros.to_le_bytes());
+        buf.push(u8::from(msg.source_seq.is_some()));
+        buf.extend_from_slice(&msg.source_seq.unwrap_or(0).to_le_bytes());
+        buf.extend_from_slice(&msg.data);
+
+        self.file.write_all(&buf)?;
+        self.file.flush()?;
+        self.empty = false;
+        Ok(())
+    }
+
+    fn truncate(&mut self) -> Result<(), WriterError> {
+        self.file.set_len(0)?;
+        self.empty = true;
+        metrics::set_wal_pending(&self.feed, 0);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<W: Writer> Writer for WalWriter<W> {
+    async fn write(&mut self, msg: &Message) -> Result<(), WriterError> {
+        if !self.pending.is_empty() {
+            // Unreplayed frames must go out first to preserve order
+            self.replay().await?;
+        }
+        self.append(msg)?;
+        metrics::set_wal_pending(&self.feed, 1);
+        if let Err(e) = self.inner.write(msg).await {
+            // Frame stays on disk; remember it so a later write replays it first
+            self.pending.push(WalRecord::from_message(msg));
+            return Err(e);
+        }
+        self.truncate()
+    }
+
+    async fn close(&mut self) -> Result<(), WriterError> {
+        self.inner.close().await
+    }
+}
+
+/// Split a WAL buffer into frames. Returns the frames and the number of
+/// trailing bytes that did not form a complete record.
+fn decode_records(buf: &[u8]) -> Result<(Vec<WalRecord>, usize), WriterError> {
+    match buf.strip_prefix(WAL_MAGIC.as_slice()) {
+        Some([WAL_VERSION, rest @ ..]) => Ok(decode_v1_records(rest)),
+        Some([version, ..]) => Err(WriterError::WriteFailed(format!(
+            "unsupported WAL version {}",
+            version
+        ))),
+        // Torn while writing the header of the first record
+        Some([]) => Ok((Vec::new(), buf.len())),
+        None => Ok(decode_legacy_records(buf)),
+    }
+}
+
+/// Version 1 records: length, capture time, source sequence, payload.
+fn decode_v1_records(mut buf: &[u8]) -> (Vec<WalRecord>, usize) {
+    let mut frames = Vec::new();
+    while buf.len() >= RECORD_HEADER_LEN {
+        let len = u32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize;
+        if buf.len() - RECORD_HEADER_LEN < len {
+            break;
+        }
+        let capture_ts_micros = i64::from_le_bytes(buf[4..12].try_into().unwrap());
+        let source_seq = (buf[12] != 0).then(|| u64::from_le_bytes(buf[13..21].try_into().unwrap()));
+        frames.push(WalRecord {
+            capture_ts_micros: Some(capture_ts_micros),
+            source_seq,
+            data: Bytes::copy_from_slice(&buf[RECORD_HEADER_LEN..RECORD_HEADER_LEN + len]),
+        });
+        buf = &buf[RECORD_HEADER_LEN + len..];
+    }
+    (frames, buf.len())
+}
+
+/// Unversioned files: bare `u32` length-prefixed frames.
+fn decode_legacy_records(mut buf: &[u8]) -> (Vec<WalRecord>, usize) {
+    let mut frames = Vec::new();
+    while buf.len() >= 4 {
+        let len = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        if buf.len() - 4 < len {
+            break;
+        }
+        frames.push(WalRecord {
+            capture_ts_micros: None,
+            source_seq: None,
+            data: Bytes::copy_from_slice(&buf[4..4 + len]),
+        });
+        buf = &buf[4 + len..];
+    }
+    (frames, buf.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    #[derive(Clone, Default)]
+    struct MockWriter {
+        written: Arc<Mutex<Vec<Message>>>,
+        fail: Arc<AtomicBool>,
+    }
+
+    impl MockWriter {
+        fn data(&self) -> Vec<Bytes> {
+            self.written
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|m| m.data.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl Writer for MockWriter {
+        async fn write(&mut self, msg: &Message) -> Result<(), WriterError> {
+            if self.fail.load(Ordering::Relaxed) {
+                return Err(WriterError::WriteFailed("NATS publish failed: down".into()));
+            }
+            self.written.lock().unwrap().push(msg.clone());
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), WriterError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wal_truncated_after_successful_write() {
+        let tmp = TempDir::new().unwrap();
+        let mock = MockWriter::default();
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+
+        wal.write(&Message::new("kalshi", b"a".to_vec()))
+            .await
+            .unwrap();
+        wal.write(&Message::new("kalshi", b"b".to_vec()))
+            .await
+            .unwrap();
+
+        assert_eq!(mock.data(), vec![Bytes::from("a"), Bytes::from("b")]);
+        assert_eq!(
+            fs::metadata(tmp.path().join("kalshi.wal")).unwrap().len(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wal_replays_unacked_frame_on_reopen() {
+        let tmp = TempDir::new().unwrap();
+        let mock = MockWriter::default();
+        mock.fail.store(true, Ordering::Relaxed);
+
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+        assert!(wal
+            .write(&Message::new("kalshi", b"lost".to_vec()))
+            .await
+            .is_err());
+        drop(wal);
+
+        mock.fail.store(false, Ordering::Relaxed);
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+        assert_eq!(wal.pending(), 1);
+        assert_eq!(wal.replay().await.unwrap(), 1);
+        assert_eq!(wal.pending(), 0);
+
+        assert_eq!(mock.data(), vec![Bytes::from("lost")]);
+        assert_eq!(
+            fs::metadata(tmp.path().join("kalshi.wal")).unwrap().len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_decode_records_discards_torn_tail() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(b"abc");
+        buf.extend_from_slice(&10u32.to_le_bytes());
+        buf.extend_from_slice(b"xy");
+
+        let (frames, torn) = decode_records(&buf).unwrap();
+        let data: Vec<_> = frames.into_iter().map(|f| f.data).collect();
+        assert_eq!(data, vec![Bytes::from("abc")]);
+        assert_eq!(torn, 6);
+    }
+
+    #[tokio::test]
+    async fn test_wal_replay_keeps_capture_time_and_sequence() {
+        let tmp = TempDir::new().unwrap();
+        let mock = MockWriter::default();
+        mock.fail.store(true, Ordering::Relaxed);
+
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+        let with_seq = Message::from_capture("kalshi", b"seq".to_vec(), 1_700_000_000_123_456, Some(42));
+        let without_seq = Message::from_capture("kalshi", b"noseq".to_vec(), 1_700_000_000_654_321, None);
+        assert!(wal.write(&with_seq).await.is_err());
+        assert!(wal.write(&without_seq).await.is_err());
+        drop(wal);
+
+        mock.fail.store(false, Ordering::Relaxed);
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+        assert_eq!(wal.replay().await.unwrap(), 2);
+
+        let written = mock.written.lock().unwrap();
+        let replayed: Vec<_> = written
+            .iter()
+            .map(|m| (m.data.clone(), m.capture_ts_micros, m.source_seq))
+            .collect();
+        assert_eq!(
+            replayed,
+            vec![
+                (Bytes::from("seq"), 1_700_000_000_123_456, Some(42)),
+                (Bytes::from("noseq"), 1_700_000_000_654_321, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wal_replays_unversioned_file() {
+        let tmp = TempDir::new().unwrap();
+        let mut legacy = Vec::new();
+        legacy.extend_from_slice(&3u32.to_le_bytes());
+        legacy.extend_from_slice(b"old");
+        fs::write(tmp.path().join("kalshi.wal"), &legacy).unwrap();
+
+        let mock = MockWriter::default();
+        let mut wal = WalWriter::open(tmp.path(), "kalshi", mock.clone()).unwrap();
+        assert_eq!(wal.pending(), 1);
+        assert_eq!(wal.replay().await.unwrap(), 1);
+        assert_eq!(mock.data(), vec![Bytes::from("old")]);
+        assert_eq!(mock.written.lock().unwrap()[0].source_seq, None);
+
+        // New records go into a versioned file
+        mock.fail.store(true, Ordering::Relaxed);
+        assert!(wal.write(&Message::new("kalshi", b"new".to_vec())).await.is_err());
+        let buf = fs::read(tmp.path().join("kalshi.wal")).unwrap();
+        assert!(buf.starts_with(WAL_MAGIC));
+        assert_eq!(buf[WAL_MAGIC.len()], WAL_VERSION);
+    }
+}
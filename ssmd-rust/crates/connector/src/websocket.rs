@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 use url::Url;
@@ -8,15 +9,31 @@ use url::Url;
 use tracing::warn;
 
 use crate::error::ConnectorError;
+use crate::keepalive::{Keepalive, PongTracker};
 use crate::traits::{Connector, TimestampedMsg};
 use ssmd_middleware::now_tsc;
 
+/// Default WS ping interval for the generic connector
+pub const DEFAULT_PING_INTERVAL_SECS: u64 = 30;
+
 /// WebSocket connector for Kalshi
+///
+/// Reconnectable: each `connect()` opens a fresh socket and message channel,
+/// then re-sends auth and any subscribe messages. The reader sends WS ping
+/// frames and drops the socket if a pong does not come back in time, which
+/// closes the message channel so the runner reconnects.
 pub struct WebSocketConnector {
     url: String,
     creds: Option<HashMap<String, String>>,
+    subscribe_messages: Vec<String>,
+    /// Ping schedule (None = never ping)
+    keepalive: Option<Keepalive>,
+    /// Feed label for the pong latency metric
+    feed_name: String,
     tx: Option<mpsc::Sender<TimestampedMsg>>,
     rx: Option<mpsc::Receiver<TimestampedMsg>>,
+    /// Reader task for the current socket, aborted on close/reconnect
+    reader: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl WebSocketConnector {
@@ -25,10 +42,32 @@ impl WebSocketConnector {
         Self {
             url: url.into(),
             creds,
+            subscribe_messages: Vec::new(),
+            keepalive: Some(Keepalive::every(Duration::from_secs(DEFAULT_PING_INTERVAL_SECS))),
+            feed_name: "websocket".to_string(),
             tx: Some(tx),
             rx: Some(rx),
+            reader: None,
         }
     }
+
+    /// Text frames sent after auth on every connect (including reconnects)
+    pub fn with_subscribe_messages(mut self, messages: Vec<String>) -> Self {
+        self.subscribe_messages = messages;
+        self
+    }
+
+    /// Override the ping schedule (`None` disables pings)
+    pub fn with_keepalive(mut self, keepalive: Option<Keepalive>) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
+    /// Feed name used as the metric label
+    pub fn with_feed_name(mut self, feed_name: impl Into<String>) -> Self {
+        self.feed_name = feed_name.into();
+        self
+    }
 }
 
 #[async_trait]
@@ -37,12 +76,25 @@ impl Connector for WebSocketConnector {
         let url = Url::parse(&self.url)
             .map_err(|e| ConnectorError::ConnectionFailed(e.to_string()))?;
 
+        // Drop any previous socket (e.g. reconnecting after an idle timeout)
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+
         let (ws_stream, _) = connect_async(url)
             .await
             .map_err(|e| ConnectorError::ConnectionFailed(e.to_string()))?;
 
         let (mut write, mut read) = ws_stream.split();
-        let tx = self.tx.take().unwrap();
+        // First connect uses the channel from `new()`; reconnects get a fresh one
+        let tx = match self.tx.take() {
+            Some(tx) => tx,
+            None => {
+                let (tx, rx) = mpsc::channel(1024);
+                self.rx = Some(rx);
+                tx
+            }
+        };
 
         // Handle authentication if credentials provided
         if let Some(ref creds) = self.creds {
@@ -62,9 +114,53 @@ impl Connector for WebSocketConnector {
             }
         }
 
-        // Spawn reader task
-        tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
+        for msg in &self.subscribe_messages {
+            write
+                .send(WsMessage::Text(msg.clone()))
+                .await
+                .map_err(|e| ConnectorError::ConnectionFailed(format!("subscribe: {}", e)))?;
+        }
+
+        let keepalive = self.keepalive;
+        let pong_timeout = keepalive.map(|k| k.pong_timeout);
+        let mut pongs = PongTracker::new(self.feed_name.clone());
+
+        // Spawn reader task; it also owns the write half to send pings
+        self.reader = Some(tokio::spawn(async move {
+            let mut ping_timer = keepalive.map(|k| {
+                let start = tokio::time::Instant::now() + k.ping_interval;
+                let mut timer = tokio::time::interval_at(start, k.ping_interval);
+                timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                timer
+            });
+
+            loop {
+                let msg = tokio::select! {
+                    _ = async {
+                        match ping_timer.as_mut() {
+                            Some(timer) => timer.tick().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        if pong_timeout.is_some_and(|t| pongs.timed_out(t)) {
+                            warn!(
+                                waited_secs = pongs.awaiting().unwrap_or_default().as_secs(),
+                                "WS reader: no pong within timeout, dropping connection"
+                            );
+                            break;
+                        }
+                        if let Err(e) = write.send(WsMessage::Ping(Vec::new())).await {
+                            warn!(error = %e, "WS reader: ping failed, exiting");
+                            break;
+                        }
+                        pongs.ping_sent();
+                        continue;
+                    }
+                    msg = read.next() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                };
                 match msg {
                     Ok(WsMessage::Text(text)) => {
                         if tx.send((now_tsc(), text.into_bytes())).await.is_err() {
@@ -82,6 +178,9 @@ impl Connector for WebSocketConnector {
                         warn!(?frame, "WS reader: received close frame, exiting");
                         break;
                     }
+                    Ok(WsMessage::Pong(_)) => {
+                        pongs.pong_received();
+                    }
                     Err(e) => {
                         warn!(error = %e, "WS reader: WebSocket error, exiting");
                         break;
@@ -90,7 +189,7 @@ impl Connector for WebSocketConnector {
                 }
             }
             warn!("WS reader: stream ended");
-        });
+        }));
 
         Ok(())
     }
@@ -100,10 +199,17 @@ impl Connector for WebSocketConnector {
     }
 
     async fn close(&mut self) -> Result<(), ConnectorError> {
-        // Drop sender to signal reader task to stop
+        // Drop sender and stop the reader task (closes the socket)
         self.tx = None;
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
         Ok(())
     }
+
+    fn reconnectable(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +227,19 @@ mod tests {
         assert_eq!(connector.url, "wss://example.com/ws");
     }
 
+    #[test]
+    fn test_keepalive_builders() {
+        let connector = WebSocketConnector::new("wss://example.com/ws", None);
+        assert_eq!(
+            connector.keepalive,
+            Some(Keepalive::every(Duration::from_secs(DEFAULT_PING_INTERVAL_SECS)))
+        );
+
+        let connector = connector.with_keepalive(None).with_feed_name("test-feed");
+        assert!(connector.keepalive.is_none());
+        assert_eq!(connector.feed_name, "test-feed");
+    }
+
     #[test]
     fn test_messages_channel() {
         let mut connector = WebSocketConnector::new("wss://example.com/ws", None);
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     routing::{delete, get, post},
@@ -12,7 +13,7 @@ mod routes;
 mod state;
 
 use routes::AppState;
-use state::ExchangeState;
+use state::{ExchangeState, FillMode};
 
 #[derive(Parser)]
 #[command(name = "harman-test-exchange")]
@@ -25,6 +26,14 @@ struct Args {
     /// Starting balance in cents (1_000_000 = $10,000)
     #[arg(long, env = "STARTING_BALANCE", default_value = "1000000")]
     starting_balance: i64,
+
+    /// How much of each order fills automatically: full, partial (half), or none
+    #[arg(long, env = "FILL_MODE", value_enum, default_value = "full")]
+    fill_mode: FillMode,
+
+    /// Delay in milliseconds between order submission and its automatic fill
+    #[arg(long, env = "FILL_LATENCY_MS", default_value = "0")]
+    fill_latency_ms: u64,
 }
 
 #[tokio::main]
@@ -34,7 +43,11 @@ async fn main() {
         .init();
 
     let args = Args::parse();
-    let state: AppState = Arc::new(Mutex::new(ExchangeState::new(args.starting_balance)));
+    let state: AppState = Arc::new(Mutex::new(ExchangeState::new(
+        args.starting_balance,
+        args.fill_mode,
+        Duration::from_millis(args.fill_latency_ms),
+    )));
 
     let app = Router::new()
         .route(
@@ -73,6 +86,8 @@ async fn main() {
             "/trade-api/v2/portfolio/settlements",
             get(routes::list_settlements),
         )
+        .route("/test/inject", post(routes::inject))
+        .route("/test/orders/:id/fill", post(routes::inject_fill))
         .route("/health", get(routes::health))
         .with_state(state);
 
@@ -83,6 +98,8 @@ async fn main() {
     tracing::info!(
         addr = %args.listen_addr,
         balance_cents = args.starting_balance,
+        fill_mode = ?args.fill_mode,
+        fill_latency_ms = args.fill_latency_ms,
         "harman-test-exchange started"
     );
 
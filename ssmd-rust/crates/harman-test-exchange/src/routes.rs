@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     extract::{Path, Query, State},
@@ -8,10 +9,17 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
-use crate::state::{AmendRequest, DecreaseRequest, ExchangeState, Fill, Order, OrderRequest, Position};
+use crate::state::{
+    AmendRequest, DecreaseRequest, ExchangeState, Fill, InjectFillRequest, InjectRequest,
+    InjectedFault, Order, OrderRequest, Position, SubmitFault,
+};
 
 pub type AppState = Arc<Mutex<ExchangeState>>;
 
+/// How long an injected `timeout` holds the request open. Longer than
+/// harman's Kalshi client timeout (10s), so the client gives up first.
+const INJECTED_TIMEOUT: Duration = Duration::from_secs(30);
+
 // --- Response types (match Kalshi JSON shapes) ---
 
 #[derive(Serialize)]
@@ -60,6 +68,11 @@ pub struct AmendResponse {
     pub order: Order,
 }
 
+#[derive(Serialize)]
+pub struct InjectResponse {
+    pub injected: Option<InjectedFault>,
+}
+
 #[derive(Serialize)]
 pub struct HealthResponse {
     pub status: String,
@@ -81,22 +94,115 @@ pub async fn health() -> Json<HealthResponse> {
     })
 }
 
+/// Kalshi-shaped error body: `{"error": {"code", "message"}}`.
+fn error_body(code: &str, message: &str) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "error": { "code": code, "message": message } }))
+}
+
 pub async fn submit_order(
-    State(state): State<AppState>,
+    State(app): State<AppState>,
     Json(req): Json<OrderRequest>,
-) -> (StatusCode, Json<OrderResponse>) {
-    let mut state = state.lock().await;
+) -> Result<Json<OrderResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let mut state = app.lock().await;
+    if let Some((fault, reason)) = state.take_submit_fault() {
+        drop(state);
+        tracing::info!(
+            client_order_id = %req.client_order_id,
+            fault = ?fault,
+            reason = %reason,
+            "order submit failed — injected fault"
+        );
+        return Err(match fault {
+            SubmitFault::Reject => (
+                StatusCode::BAD_REQUEST,
+                error_body("order_rejected", &reason),
+            ),
+            SubmitFault::ServerError => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                error_body("internal_server_error", &reason),
+            ),
+            SubmitFault::Timeout => {
+                tokio::time::sleep(INJECTED_TIMEOUT).await;
+                (StatusCode::GATEWAY_TIMEOUT, error_body("timeout", &reason))
+            }
+        });
+    }
+
     tracing::info!(
         ticker = %req.ticker,
         side = %req.side,
         action = %req.action,
         count_fp = %req.count_fp,
-        yes_price = req.yes_price,
+        yes_price_dollars = %req.yes_price_dollars,
         client_order_id = %req.client_order_id,
-        "order submitted — immediate fill"
+        fill_mode = ?state.fill_mode,
+        fill_latency_ms = state.fill_latency.as_millis() as u64,
+        "order submitted"
     );
     let order = state.submit_order(&req);
-    (StatusCode::OK, Json(OrderResponse { order }))
+
+    let fill_count = state.fill_mode.fill_count(order.count.unwrap_or(0));
+    if !state.fill_latency.is_zero() && fill_count > 0 {
+        let delay = state.fill_latency;
+        let order_id = order.order_id.clone();
+        let app = app.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let mut state = app.lock().await;
+            match state.apply_fill(&order_id, fill_count) {
+                Some(order) => tracing::info!(
+                    order_id = %order_id,
+                    count = fill_count,
+                    status = %order.status,
+                    "delayed fill applied"
+                ),
+                None => tracing::info!(
+                    order_id = %order_id,
+                    "delayed fill skipped — order no longer resting"
+                ),
+            }
+        });
+    }
+
+    Ok(Json(OrderResponse { order }))
+}
+
+/// Test-only control endpoint: make the next `count` submits fail.
+pub async fn inject(
+    State(state): State<AppState>,
+    Json(req): Json<InjectRequest>,
+) -> Json<InjectResponse> {
+    let mut state = state.lock().await;
+    let reason = req
+        .reason
+        .unwrap_or_else(|| format!("injected {:?}", req.next_submit).to_lowercase());
+    tracing::info!(
+        next_submit = ?req.next_submit,
+        reason = %reason,
+        count = req.count,
+        "submit fault injected"
+    );
+    state.inject_submit_fault(req.next_submit, reason, req.count);
+    Json(InjectResponse {
+        injected: state.injected_fault.clone(),
+    })
+}
+
+/// Test-only control endpoint: fill `count` contracts of a resting order.
+pub async fn inject_fill(
+    State(state): State<AppState>,
+    Path(order_id): Path<String>,
+    Json(req): Json<InjectFillRequest>,
+) -> Result<Json<OrderResponse>, StatusCode> {
+    let mut state = state.lock().await;
+    if !state.orders.contains_key(&order_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    tracing::info!(order_id = %order_id, count = req.count, "manual fill injected");
+    match state.apply_fill(&order_id, req.count) {
+        Some(order) => Ok(Json(OrderResponse { order })),
+        None => Err(StatusCode::UNPROCESSABLE_ENTITY),
+    }
 }
 
 pub async fn cancel_order(
@@ -178,7 +284,7 @@ pub async fn amend_order(
     tracing::info!(
         order_id = %order_id,
         ticker = %req.ticker,
-        yes_price = ?req.yes_price,
+        yes_price_dollars = ?req.yes_price_dollars,
         count_fp = ?req.count_fp,
         "amend order"
     );
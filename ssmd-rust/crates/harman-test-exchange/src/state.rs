@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -66,7 +67,7 @@ pub struct OrderRequest {
     #[serde(rename = "type")]
     pub order_type: String,
     pub count_fp: String,
-    pub yes_price: i32,
+    pub yes_price_dollars: String,
     #[serde(default = "default_tif")]
     pub time_in_force: String,
     #[serde(default)]
@@ -84,7 +85,7 @@ pub struct AmendRequest {
     pub ticker: String,
     pub side: String,
     pub action: String,
-    pub yes_price: Option<i32>,
+    pub yes_price_dollars: Option<String>,
     pub count_fp: Option<String>,
     #[serde(default)]
     pub subaccount: i32,
@@ -99,21 +100,93 @@ pub struct DecreaseRequest {
     pub subaccount: i32,
 }
 
+/// Incoming manual fill injection (test-only control endpoint).
+#[derive(Debug, Deserialize)]
+pub struct InjectFillRequest {
+    pub count: i64,
+}
+
+/// Failure to inject into upcoming `submit_order` calls (test-only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmitFault {
+    /// Return 400 with a Kalshi-shaped error body.
+    Reject,
+    /// Hold the request open past the client's timeout.
+    Timeout,
+    /// Return 500.
+    #[serde(rename = "500")]
+    ServerError,
+}
+
+/// Incoming fault injection request (test-only control endpoint).
+#[derive(Debug, Deserialize)]
+pub struct InjectRequest {
+    pub next_submit: SubmitFault,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Number of upcoming submits to fail. Zero clears any pending injection.
+    #[serde(default = "default_inject_count")]
+    pub count: u32,
+}
+
+fn default_inject_count() -> u32 {
+    1
+}
+
+/// A pending injected failure and how many more submits it applies to.
+#[derive(Debug, Clone, Serialize)]
+pub struct InjectedFault {
+    pub next_submit: SubmitFault,
+    pub reason: String,
+    pub remaining: u32,
+}
+
+/// How much of a submitted order the exchange fills on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FillMode {
+    /// Fill the whole order.
+    Full,
+    /// Fill half the order (rounded down); the rest stays resting.
+    Partial,
+    /// Never fill automatically; orders rest until filled manually or cancelled.
+    None,
+}
+
+impl FillMode {
+    /// Number of contracts to fill automatically for an order of `count`.
+    pub fn fill_count(self, count: i64) -> i64 {
+        match self {
+            FillMode::Full => count,
+            FillMode::Partial => count / 2,
+            FillMode::None => 0,
+        }
+    }
+}
+
 /// In-memory exchange state. All mutations go through methods.
 pub struct ExchangeState {
     pub orders: HashMap<String, Order>,
     pub fills: Vec<Fill>,
     pub balance: i64,
+    pub fill_mode: FillMode,
+    /// Delay between submission and the automatic fill. Zero fills inline.
+    pub fill_latency: Duration,
+    /// Failure to apply to the next `remaining` submits, if any.
+    pub injected_fault: Option<InjectedFault>,
     next_order_id: u64,
     next_trade_id: u64,
 }
 
 impl ExchangeState {
-    pub fn new(starting_balance: i64) -> Self {
+    pub fn new(starting_balance: i64, fill_mode: FillMode, fill_latency: Duration) -> Self {
         Self {
             orders: HashMap::new(),
             fills: Vec::new(),
             balance: starting_balance,
+            fill_mode,
+            fill_latency,
+            injected_fault: None,
             next_order_id: 1,
             next_trade_id: 1,
         }
@@ -136,58 +209,113 @@ impl ExchangeState {
         s.parse::<f64>().unwrap_or(0.0).round() as i64
     }
 
-    /// Accept an order, immediately fill it, update balance.
+    /// Parse a dollar price string to whole cents (the test exchange's unit)
+    fn parse_price_dollars(s: &str) -> i64 {
+        (s.parse::<f64>().unwrap_or(0.0) * 100.0).round() as i64
+    }
+
+    /// Make the next `count` submits fail with `fault`. A count of zero clears
+    /// any pending injection.
+    pub fn inject_submit_fault(&mut self, fault: SubmitFault, reason: String, count: u32) {
+        self.injected_fault = (count > 0).then_some(InjectedFault {
+            next_submit: fault,
+            reason,
+            remaining: count,
+        });
+    }
+
+    /// Consume one injected failure, if any is pending.
+    pub fn take_submit_fault(&mut self) -> Option<(SubmitFault, String)> {
+        let pending = self.injected_fault.as_mut()?;
+        let taken = (pending.next_submit, pending.reason.clone());
+        pending.remaining -= 1;
+        if pending.remaining == 0 {
+            self.injected_fault = None;
+        }
+        Some(taken)
+    }
+
+    /// Accept an order as resting. With zero fill latency, the automatic fill
+    /// for the configured `fill_mode` is applied before returning; otherwise the
+    /// caller schedules it via `apply_fill` after `fill_latency`.
     pub fn submit_order(&mut self, req: &OrderRequest) -> Order {
         let order_id = self.next_order_id();
-        let trade_id = self.next_trade_id();
         let now = Utc::now().to_rfc3339();
         let count = Self::parse_count_fp(&req.count_fp);
-        let yes_price = req.yes_price as i64;
+        let yes_price = Self::parse_price_dollars(&req.yes_price_dollars);
         let no_price = 100 - yes_price;
 
         let order = Order {
             order_id: order_id.clone(),
             client_order_id: Some(req.client_order_id.clone()),
             ticker: req.ticker.clone(),
-            status: "executed".to_string(),
+            status: "resting".to_string(),
             side: req.side.clone(),
             action: req.action.clone(),
             yes_price,
             no_price,
             count_fp: Some(req.count_fp.clone()),
-            remaining_count_fp: Some("0".to_string()),
+            remaining_count_fp: Some(count.to_string()),
             count: Some(count),
-            remaining_count: Some(0),
-            created_time: Some(now.clone()),
+            remaining_count: Some(count),
+            created_time: Some(now),
             close_cancel_count: None,
         };
+        self.orders.insert(order_id.clone(), order.clone());
 
-        let fill = Fill {
-            trade_id,
-            order_id: order_id.clone(),
-            ticker: req.ticker.clone(),
-            side: req.side.clone(),
-            action: req.action.clone(),
-            yes_price,
-            count,
-            is_taker: true,
-            created_time: now,
-        };
+        let fill_count = self.fill_mode.fill_count(count);
+        if self.fill_latency.is_zero() && fill_count > 0 {
+            if let Some(filled) = self.apply_fill(&order_id, fill_count) {
+                return filled;
+            }
+        }
+
+        order
+    }
+
+    /// Fill `count` contracts of a resting order: record the fill, update the
+    /// balance, and mark the order executed once nothing remains.
+    /// Returns None if the order is unknown, not resting, or `count` is not in
+    /// `1..=remaining`.
+    pub fn apply_fill(&mut self, order_id: &str, count: i64) -> Option<Order> {
+        let order = self.orders.get(order_id)?.clone();
+        let remaining = order.remaining_count.unwrap_or(0);
+        if order.status != "resting" || count <= 0 || count > remaining {
+            return None;
+        }
 
         // Deduct cost (buy) or credit proceeds (sell), in cents.
-        let cost = match (req.action.as_str(), req.side.as_str()) {
-            ("buy", "yes") => yes_price * count,
-            ("buy", "no") => no_price * count,
-            ("sell", "yes") => -(yes_price * count),
-            ("sell", "no") => -(no_price * count),
+        let cost = match (order.action.as_str(), order.side.as_str()) {
+            ("buy", "yes") => order.yes_price * count,
+            ("buy", "no") => order.no_price * count,
+            ("sell", "yes") => -(order.yes_price * count),
+            ("sell", "no") => -(order.no_price * count),
             _ => 0,
         };
         self.balance -= cost;
 
-        self.orders.insert(order_id, order.clone());
-        self.fills.push(fill);
+        let trade_id = self.next_trade_id();
+        self.fills.push(Fill {
+            trade_id,
+            order_id: order_id.to_string(),
+            ticker: order.ticker,
+            side: order.side,
+            action: order.action,
+            yes_price: order.yes_price,
+            count,
+            is_taker: true,
+            created_time: Utc::now().to_rfc3339(),
+        });
 
-        order
+        let order = self.orders.get_mut(order_id)?;
+        let new_remaining = remaining - count;
+        order.remaining_count = Some(new_remaining);
+        order.remaining_count_fp = Some(new_remaining.to_string());
+        if new_remaining == 0 {
+            order.status = "executed".to_string();
+        }
+
+        Some(order.clone())
     }
 
     /// Cancel a resting order. Returns None if not found or already executed.
@@ -227,7 +355,11 @@ impl ExchangeState {
 
         // Create new order with amended values
         let new_order_id = self.next_order_id();
-        let yes_price = req.yes_price.map(|p| p as i64).unwrap_or(old_order.yes_price);
+        let yes_price = req
+            .yes_price_dollars
+            .as_ref()
+            .map(|s| Self::parse_price_dollars(s))
+            .unwrap_or(old_order.yes_price);
         let count = req
             .count_fp
             .as_ref()
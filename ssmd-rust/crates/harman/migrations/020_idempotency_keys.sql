@@ -0,0 +1,19 @@
+-- Migration 020: Idempotency-Key support for mutating API endpoints
+-- Stores the first response for each (session, key) so client retries replay it
+-- instead of re-applying the mutation.
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    session_id BIGINT NOT NULL REFERENCES sessions(id),
+    idempotency_key TEXT NOT NULL,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    status_code INTEGER NOT NULL,
+    response_body TEXT NOT NULL,
+    response_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys (created_at);
+
+INSERT INTO schema_migrations (version) VALUES ('020_idempotency_keys') ON CONFLICT DO NOTHING;
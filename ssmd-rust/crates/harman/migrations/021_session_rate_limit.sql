@@ -0,0 +1,7 @@
+-- Per-session order rate limit override (requests per second).
+-- NULL = use global default from --order-rate-limit / ORDER_RATE_LIMIT env var.
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS order_rate_limit INTEGER
+    CHECK (order_rate_limit > 0);
+
+INSERT INTO schema_migrations (version) VALUES ('021_session_rate_limit')
+    ON CONFLICT DO NOTHING;
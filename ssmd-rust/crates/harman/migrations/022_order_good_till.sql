@@ -0,0 +1,10 @@
+-- Migration 022: Good-till time for GTC orders.
+-- NULL = no time policy. Resting orders past good_till are cancelled by the
+-- OMS expiry sweeper with cancel_reason = 'expired'.
+ALTER TABLE prediction_orders ADD COLUMN IF NOT EXISTS good_till TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_prediction_orders_good_till
+    ON prediction_orders (good_till)
+    WHERE good_till IS NOT NULL AND state IN ('acknowledged', 'partially_filled');
+
+INSERT INTO schema_migrations (version) VALUES ('022_order_good_till') ON CONFLICT DO NOTHING;
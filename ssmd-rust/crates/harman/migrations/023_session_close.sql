@@ -0,0 +1,16 @@
+-- Migration 023: Explicit session close/rotate.
+-- A closed session keeps its orders and fills for audit; the next API request
+-- for the same (exchange, environment) creates a fresh session.
+
+BEGIN;
+
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS closed_at TIMESTAMPTZ;
+
+-- Natural key only applies to open sessions
+DROP INDEX IF EXISTS sessions_natural_key;
+CREATE UNIQUE INDEX sessions_natural_key ON sessions (exchange, environment)
+    WHERE closed_at IS NULL;
+
+INSERT INTO schema_migrations (version) VALUES ('023_session_close') ON CONFLICT DO NOTHING;
+
+COMMIT;
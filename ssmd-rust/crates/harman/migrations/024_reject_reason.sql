@@ -0,0 +1,5 @@
+-- Migration 024: Exchange reject reason on orders.
+-- Populated by the pump when the exchange rejects a submit; NULL otherwise.
+ALTER TABLE prediction_orders ADD COLUMN IF NOT EXISTS reject_reason TEXT;
+
+INSERT INTO schema_migrations (version) VALUES ('024_reject_reason') ON CONFLICT DO NOTHING;
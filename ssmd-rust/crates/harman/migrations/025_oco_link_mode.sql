@@ -0,0 +1,9 @@
+-- Migration 025: OCO link mode.
+-- 'peg' groups reprice leg2 to keep peg_spread_dollars from leg1 while both
+-- legs rest; drift smaller than peg_min_reprice_dollars is ignored.
+ALTER TABLE order_groups ADD COLUMN IF NOT EXISTS link_mode TEXT NOT NULL DEFAULT 'none'
+    CHECK (link_mode IN ('none', 'peg'));
+ALTER TABLE order_groups ADD COLUMN IF NOT EXISTS peg_spread_dollars NUMERIC(18,8);
+ALTER TABLE order_groups ADD COLUMN IF NOT EXISTS peg_min_reprice_dollars NUMERIC(18,8);
+
+INSERT INTO schema_migrations (version) VALUES ('025_oco_link_mode') ON CONFLICT DO NOTHING;
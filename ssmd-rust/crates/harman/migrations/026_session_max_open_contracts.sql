@@ -0,0 +1,6 @@
+-- Migration 026: per-session open contract limit.
+-- NULL = use global default from --max-open-contracts / MAX_OPEN_CONTRACTS
+-- (itself optional: no cap when unset).
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS max_open_contracts NUMERIC(20,8);
+
+INSERT INTO schema_migrations (version) VALUES ('026_session_max_open_contracts') ON CONFLICT DO NOTHING;
@@ -0,0 +1,14 @@
+-- Migration 027: cancel_reason for orders cancelled by a forced session close.
+
+BEGIN;
+
+ALTER TABLE prediction_orders DROP CONSTRAINT IF EXISTS prediction_orders_cancel_reason_check;
+ALTER TABLE prediction_orders ADD CONSTRAINT prediction_orders_cancel_reason_check
+    CHECK (cancel_reason IN (
+        'user_requested', 'risk_limit_breached', 'shutdown', 'expired', 'exchange_cancel',
+        'session_closed'
+    ));
+
+INSERT INTO schema_migrations (version) VALUES ('027_cancel_reason_session_closed') ON CONFLICT DO NOTHING;
+
+COMMIT;
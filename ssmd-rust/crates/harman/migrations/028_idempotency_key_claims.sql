@@ -0,0 +1,14 @@
+-- Migration 028: Claim Idempotency-Keys before running the request
+-- A row with a NULL status_code is a pending claim held by an in-flight
+-- request; the response columns are filled in when it completes.
+
+BEGIN;
+
+ALTER TABLE idempotency_keys
+    ALTER COLUMN status_code DROP NOT NULL,
+    ALTER COLUMN response_body DROP NOT NULL,
+    ALTER COLUMN response_hash DROP NOT NULL;
+
+INSERT INTO schema_migrations (version) VALUES ('028_idempotency_key_claims') ON CONFLICT DO NOTHING;
+
+COMMIT;
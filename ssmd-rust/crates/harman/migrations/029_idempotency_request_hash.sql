@@ -0,0 +1,12 @@
+-- Migration 029: Remember which request body claimed an Idempotency-Key
+-- A key reused with a different body is rejected instead of replaying the
+-- first request's response. NULL for entries stored before this migration.
+
+BEGIN;
+
+ALTER TABLE idempotency_keys
+    ADD COLUMN IF NOT EXISTS request_hash TEXT;
+
+INSERT INTO schema_migrations (version) VALUES ('029_idempotency_request_hash') ON CONFLICT DO NOTHING;
+
+COMMIT;
@@ -0,0 +1,12 @@
+-- Migration 030: Persist the reduce-only flag on orders
+-- Open reduce-only orders count against the position a new reduce-only
+-- order may close, so the flag has to outlive the enqueue request.
+
+BEGIN;
+
+ALTER TABLE prediction_orders
+    ADD COLUMN IF NOT EXISTS reduce_only BOOLEAN NOT NULL DEFAULT FALSE;
+
+INSERT INTO schema_migrations (version) VALUES ('030_order_reduce_only') ON CONFLICT DO NOTHING;
+
+COMMIT;
@@ -8,15 +8,30 @@ use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::error::EnqueueError;
-use crate::risk::{RiskLimits, RiskState};
+use crate::risk::{
+    ContractRiskState, PositionNetting, RestingSell, RiskLimits, RiskState, TickerRiskState,
+};
 use crate::state::{apply_event, OrderEvent, OrderState};
 use crate::types::{
-    Action, CancelReason, GroupState, GroupType, LegRole, MarketResult, Order, OrderGroup,
-    OrderRequest, OrderType, QueueAction, Settlement, Side, TimeInForce,
+    Action, CancelReason, GroupState, GroupType, LegRole, LinkMode, MarketResult, Order,
+    OrderGroup, OrderRequest, OrderType, PegConfig, QueueAction, Settlement, Side, TimeInForce,
 };
 
 /// Create a connection pool from a database URL
 pub fn create_pool(database_url: &str) -> Result<Pool, String> {
+    create_pool_with_limits(database_url, None, None)
+}
+
+/// Create a connection pool with an explicit max size and checkout timeout.
+///
+/// `None` keeps deadpool's defaults (max size scales with CPU count; `get()`
+/// waits indefinitely). A `wait_timeout` makes `pool.get()` fail instead of
+/// hanging when the pool is exhausted.
+pub fn create_pool_with_limits(
+    database_url: &str,
+    max_size: Option<usize>,
+    wait_timeout: Option<std::time::Duration>,
+) -> Result<Pool, String> {
     // Parse the URL into deadpool config
     let pg_config: tokio_postgres::Config = database_url
         .parse()
@@ -45,6 +60,13 @@ pub fn create_pool(database_url: &str) -> Result<Pool, String> {
         cfg.dbname = Some(dbname.to_string());
     }
 
+    let mut pool_cfg = deadpool_postgres::PoolConfig::default();
+    if let Some(max_size) = max_size {
+        pool_cfg.max_size = max_size;
+    }
+    pool_cfg.timeouts.wait = wait_timeout;
+    cfg.pool = Some(pool_cfg);
+
     // NoTls is acceptable here: harman connects to ssmd-postgres within the same
     // K8s namespace. Network policies restrict access to port 5432. For external
     // Postgres connections, replace with tokio-postgres-rustls and sslmode=require.
@@ -407,6 +429,204 @@ pub async fn run_migrations(pool: &Pool) -> Result<(), String> {
         info!("migration 019_drop_exchange_check applied");
     }
 
+    // Check if 020 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '020_idempotency_keys'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 020: {}", e))?;
+
+    if row.is_none() {
+        let migration_020 = include_str!("../migrations/020_idempotency_keys.sql");
+        client
+            .batch_execute(migration_020)
+            .await
+            .map_err(|e| format!("migration 020 failed: {}", e))?;
+        info!("migration 020_idempotency_keys applied");
+    }
+
+    // Check if 021 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '021_session_rate_limit'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 021: {}", e))?;
+
+    if row.is_none() {
+        let migration_021 = include_str!("../migrations/021_session_rate_limit.sql");
+        client
+            .batch_execute(migration_021)
+            .await
+            .map_err(|e| format!("migration 021 failed: {}", e))?;
+        info!("migration 021_session_rate_limit applied");
+    }
+
+    // Check if 022 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '022_order_good_till'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 022: {}", e))?;
+
+    if row.is_none() {
+        let migration_022 = include_str!("../migrations/022_order_good_till.sql");
+        client
+            .batch_execute(migration_022)
+            .await
+            .map_err(|e| format!("migration 022 failed: {}", e))?;
+        info!("migration 022_order_good_till applied");
+    }
+
+    // Check if 023 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '023_session_close'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 023: {}", e))?;
+
+    if row.is_none() {
+        let migration_023 = include_str!("../migrations/023_session_close.sql");
+        client
+            .batch_execute(migration_023)
+            .await
+            .map_err(|e| format!("migration 023 failed: {}", e))?;
+        info!("migration 023_session_close applied");
+    }
+
+    // Check if 024 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '024_reject_reason'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 024: {}", e))?;
+
+    if row.is_none() {
+        let migration_024 = include_str!("../migrations/024_reject_reason.sql");
+        client
+            .batch_execute(migration_024)
+            .await
+            .map_err(|e| format!("migration 024 failed: {}", e))?;
+        info!("migration 024_reject_reason applied");
+    }
+
+    // Check if 025 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '025_oco_link_mode'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 025: {}", e))?;
+
+    if row.is_none() {
+        let migration_025 = include_str!("../migrations/025_oco_link_mode.sql");
+        client
+            .batch_execute(migration_025)
+            .await
+            .map_err(|e| format!("migration 025 failed: {}", e))?;
+        info!("migration 025_oco_link_mode applied");
+    }
+
+    // Check if 026 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '026_session_max_open_contracts'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 026: {}", e))?;
+
+    if row.is_none() {
+        let migration_026 = include_str!("../migrations/026_session_max_open_contracts.sql");
+        client
+            .batch_execute(migration_026)
+            .await
+            .map_err(|e| format!("migration 026 failed: {}", e))?;
+        info!("migration 026_session_max_open_contracts applied");
+    }
+
+    // Check if 027 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '027_cancel_reason_session_closed'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 027: {}", e))?;
+
+    if row.is_none() {
+        let migration_027 = include_str!("../migrations/027_cancel_reason_session_closed.sql");
+        client
+            .batch_execute(migration_027)
+            .await
+            .map_err(|e| format!("migration 027 failed: {}", e))?;
+        info!("migration 027_cancel_reason_session_closed applied");
+    }
+
+    // Check if 028 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '028_idempotency_key_claims'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 028: {}", e))?;
+
+    if row.is_none() {
+        let migration_028 = include_str!("../migrations/028_idempotency_key_claims.sql");
+        client
+            .batch_execute(migration_028)
+            .await
+            .map_err(|e| format!("migration 028 failed: {}", e))?;
+        info!("migration 028_idempotency_key_claims applied");
+    }
+
+    // Check if 029 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '029_idempotency_request_hash'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 029: {}", e))?;
+
+    if row.is_none() {
+        let migration_029 = include_str!("../migrations/029_idempotency_request_hash.sql");
+        client
+            .batch_execute(migration_029)
+            .await
+            .map_err(|e| format!("migration 029 failed: {}", e))?;
+        info!("migration 029_idempotency_request_hash applied");
+    }
+
+    // Check if 030 is applied
+    let row = client
+        .query_opt(
+            "SELECT version FROM schema_migrations WHERE version = '030_order_reduce_only'",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("check migration 030: {}", e))?;
+
+    if row.is_none() {
+        let migration_030 = include_str!("../migrations/030_order_reduce_only.sql");
+        client
+            .batch_execute(migration_030)
+            .await
+            .map_err(|e| format!("migration 030 failed: {}", e))?;
+        info!("migration 030_order_reduce_only applied");
+    }
+
     info!("database migrations applied successfully");
     Ok(())
 }
@@ -535,28 +755,188 @@ fn validate_transition(from: OrderState, to: OrderState) -> Result<OrderState, S
     apply_event(from, &event).map_err(|e| format!("{}", e))
 }
 
-/// The core transactional enqueue operation.
+/// Aggregate open notional and open order count per ticker for a session.
 ///
-/// Single transaction: SELECT FOR UPDATE (risk state) → risk check → INSERT order → INSERT queue → COMMIT
-pub async fn enqueue_order(
-    pool: &Pool,
-    request: &OrderRequest,
+/// Must run inside the enqueue transaction after the open order rows are
+/// locked so the per-ticker totals are consistent with the session totals.
+async fn load_ticker_risk(
+    tx: &deadpool_postgres::Transaction<'_>,
     session_id: i64,
-    limits: &RiskLimits,
-) -> Result<Order, EnqueueError> {
-    let mut client = pool
-        .get()
+    tickers: &[String],
+) -> Result<std::collections::HashMap<String, TickerRiskState>, EnqueueError> {
+    let rows = tx
+        .query(
+            "SELECT ticker, \
+                    COALESCE(SUM(price_dollars * (quantity - filled_qty(id))), 0) as open_notional, \
+                    COUNT(*) as open_orders \
+             FROM prediction_orders \
+             WHERE session_id = $1 AND ticker = ANY($2) \
+               AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
+             GROUP BY ticker",
+            &[&session_id, &tickers],
+        )
         .await
-        .map_err(|e| EnqueueError::Database(format!("pool error: {}", e)))?;
+        .map_err(|e| EnqueueError::Database(format!("ticker risk query: {}", e)))?;
 
-    let tx = client
-        .transaction()
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let ticker: String = row.get("ticker");
+            let open_orders: i64 = row.get("open_orders");
+            (
+                ticker,
+                TickerRiskState {
+                    open_notional: row.get::<_, Decimal>("open_notional"),
+                    open_orders: u32::try_from(open_orders).unwrap_or(u32::MAX),
+                },
+            )
+        })
+        .collect())
+}
+
+/// Load filled long positions and resting sells for a session and net them.
+///
+/// Must run inside the enqueue transaction after the open order rows are
+/// locked. A concurrent fill on a resting sell lowers both the position and
+/// that sell's remaining quantity, so the netting is stable under fills.
+/// Settled tickers have no position.
+async fn load_position_netting(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+) -> Result<PositionNetting, EnqueueError> {
+    let position_rows = tx
+        .query(
+            "SELECT o.ticker, o.side, \
+                    SUM(CASE WHEN o.action = 'buy' THEN f.quantity ELSE -f.quantity END) AS net_filled \
+             FROM prediction_orders o \
+             JOIN fills f ON f.order_id = o.id \
+             WHERE o.session_id = $1 \
+               AND o.ticker NOT IN (SELECT ticker FROM settlements WHERE session_id = $1) \
+             GROUP BY o.ticker, o.side",
+            &[&session_id],
+        )
         .await
-        .map_err(|e| EnqueueError::Database(format!("begin tx: {}", e)))?;
+        .map_err(|e| EnqueueError::Database(format!("position netting query: {}", e)))?;
+
+    let sell_rows = tx
+        .query(
+            "SELECT ticker, side, quantity - filled_qty(id) AS remaining, price_dollars \
+             FROM prediction_orders \
+             WHERE session_id = $1 AND action = 'sell' \
+               AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
+             ORDER BY id",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| EnqueueError::Database(format!("resting sells query: {}", e)))?;
+
+    let longs = position_rows
+        .iter()
+        .map(|row| {
+            (
+                (row.get::<_, String>("ticker"), parse_side(row.get("side"))),
+                row.get::<_, Decimal>("net_filled"),
+            )
+        })
+        .collect();
+
+    let sells: Vec<RestingSell> = sell_rows
+        .iter()
+        .map(|row| RestingSell {
+            ticker: row.get("ticker"),
+            side: parse_side(row.get("side")),
+            remaining: row.get("remaining"),
+            price_dollars: row.get("price_dollars"),
+        })
+        .collect();
+
+    Ok(PositionNetting::new(longs, &sells))
+}
+
+/// Net position per (ticker, side) a reduce-only order may close: filled buys
+/// minus sells, plus the signed remaining quantity of the session's open
+/// reduce-only orders, as if they had filled. `exclude_order_id` leaves one
+/// order out (the order being amended). Settled tickers have no position.
+async fn load_reduce_only_positions(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    exclude_order_id: Option<i64>,
+) -> Result<std::collections::HashMap<(String, Side), Decimal>, String> {
+    let filled_rows = tx
+        .query(
+            "SELECT o.ticker, o.side, \
+                    SUM(CASE WHEN o.action = 'buy' THEN f.quantity ELSE -f.quantity END) AS net \
+             FROM prediction_orders o \
+             JOIN fills f ON f.order_id = o.id \
+             WHERE o.session_id = $1 \
+               AND o.ticker NOT IN (SELECT ticker FROM settlements WHERE session_id = $1) \
+             GROUP BY o.ticker, o.side",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("reduce-only position query: {}", e))?;
+
+    let open_rows = tx
+        .query(
+            "SELECT ticker, side, \
+                    SUM(CASE WHEN action = 'buy' THEN quantity - filled_qty(id) \
+                             ELSE filled_qty(id) - quantity END) AS net \
+             FROM prediction_orders \
+             WHERE session_id = $1 AND reduce_only \
+               AND ($2::BIGINT IS NULL OR id <> $2) \
+               AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
+               AND ticker NOT IN (SELECT ticker FROM settlements WHERE session_id = $1) \
+             GROUP BY ticker, side",
+            &[&session_id, &exclude_order_id],
+        )
+        .await
+        .map_err(|e| format!("open reduce-only orders query: {}", e))?;
+
+    let mut positions = std::collections::HashMap::new();
+    for row in filled_rows.iter().chain(open_rows.iter()) {
+        *positions
+            .entry((row.get::<_, String>("ticker"), parse_side(row.get("side"))))
+            .or_insert(Decimal::ZERO) += row.get::<_, Decimal>("net");
+    }
+    Ok(positions)
+}
+
+/// Check reduce-only orders against the session's positions.
+///
+/// Runs inside the enqueue transaction after the open order rows are locked.
+/// Orders are checked in turn, each against the position left once the open
+/// reduce-only orders and the earlier reduce-only orders of the request on
+/// its ticker and side fill, so neither a batch nor two separate requests can
+/// close the same contracts twice. No-op unless some order is reduce-only.
+async fn check_reduce_only_orders(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    requests: &[&OrderRequest],
+) -> Result<(), EnqueueError> {
+    if !requests.iter().any(|r| r.reduce_only) {
+        return Ok(());
+    }
+
+    let mut net = load_reduce_only_positions(tx, session_id, None)
+        .await
+        .map_err(EnqueueError::Database)?;
+
+    for request in requests.iter().filter(|r| r.reduce_only) {
+        let position = net.entry((request.ticker.clone(), request.side)).or_default();
+        crate::risk::check_reduce_only(request, *position).map_err(EnqueueError::RiskCheck)?;
+        *position += crate::risk::signed_quantity(request);
+    }
+    Ok(())
+}
 
-    // Lock open order rows to serialize concurrent enqueues, then compute risk.
-    // FOR UPDATE cannot be combined with aggregate functions in PostgreSQL,
-    // so we lock first, then aggregate in a separate query within the same tx.
+/// Lock the session's open order rows to serialize concurrent enqueues.
+///
+/// FOR UPDATE cannot be combined with aggregate functions in PostgreSQL, so
+/// callers lock first and then aggregate in separate queries within the same tx.
+async fn lock_open_orders(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+) -> Result<(), EnqueueError> {
     tx.query(
         "SELECT id FROM prediction_orders \
          WHERE session_id = $1 AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
@@ -565,10 +945,18 @@ pub async fn enqueue_order(
     )
     .await
     .map_err(|e| EnqueueError::Database(format!("risk lock: {}", e)))?;
+    Ok(())
+}
 
+/// Unfilled notional and contracts across the session's open orders.
+async fn load_open_exposure(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+) -> Result<(RiskState, ContractRiskState), EnqueueError> {
     let risk_row = tx
         .query_one(
-            "SELECT COALESCE(SUM(price_dollars * (quantity - filled_qty(id))), 0) as open_notional \
+            "SELECT COALESCE(SUM(price_dollars * (quantity - filled_qty(id))), 0) as open_notional, \
+                    COALESCE(SUM(quantity - filled_qty(id)), 0) as open_contracts \
              FROM prediction_orders \
              WHERE session_id = $1 AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease')",
             &[&session_id],
@@ -576,38 +964,51 @@ pub async fn enqueue_order(
         .await
         .map_err(|e| EnqueueError::Database(format!("risk query: {}", e)))?;
 
-    let open_notional: Decimal = risk_row.get::<_, Decimal>("open_notional");
-
-    let risk_state = RiskState { open_notional };
+    Ok((
+        RiskState {
+            open_notional: risk_row.get::<_, Decimal>("open_notional"),
+        },
+        ContractRiskState {
+            open_contracts: risk_row.get::<_, Decimal>("open_contracts"),
+        },
+    ))
+}
 
-    // Query per-session risk limits; fall back to global
+/// Global `limits` with the session's overrides (max notional, max open
+/// contracts, daily loss) applied.
+async fn load_effective_limits(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    limits: &RiskLimits,
+) -> Result<RiskLimits, EnqueueError> {
     let session_row = tx
         .query_one(
-            "SELECT max_notional, daily_loss_limit FROM sessions WHERE id = $1",
+            "SELECT max_notional, max_open_contracts, daily_loss_limit FROM sessions WHERE id = $1",
             &[&session_id],
         )
         .await
         .map_err(|e| EnqueueError::Database(format!("session risk query: {}", e)))?;
 
-    let effective_limits = RiskLimits {
+    Ok(RiskLimits {
         max_notional: session_row
             .get::<_, Option<Decimal>>("max_notional")
             .unwrap_or(limits.max_notional),
-        max_order_notional: limits.max_order_notional,
-        daily_loss_limit: limits.daily_loss_limit,
-    };
-
-    // Risk check (fat-finger + aggregate notional)
-    risk_state
-        .check_order(request, &effective_limits)
-        .map_err(EnqueueError::RiskCheck)?;
-
-    // Daily loss check — query realized P&L from today's settlements
-    let daily_loss_limit = match session_row.get::<_, Option<Decimal>>("daily_loss_limit") {
-        Some(session_limit) => session_limit,
-        None => limits.daily_loss_limit,
-    };
+        max_open_contracts: session_row
+            .get::<_, Option<Decimal>>("max_open_contracts")
+            .or(limits.max_open_contracts),
+        daily_loss_limit: session_row
+            .get::<_, Option<Decimal>>("daily_loss_limit")
+            .unwrap_or(limits.daily_loss_limit),
+        ..limits.clone()
+    })
+}
 
+/// Reject once today's realized P&L from settlements is below `-daily_loss_limit`.
+async fn check_daily_loss(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    daily_loss_limit: Decimal,
+) -> Result<(), EnqueueError> {
     let pnl_row = tx
         .query_one(
             "SELECT COALESCE(SUM(revenue_dollars - COALESCE(value_dollars, 0)), 0) AS daily_pnl \
@@ -627,12 +1028,22 @@ pub async fn enqueue_order(
             },
         ));
     }
+    Ok(())
+}
 
-    // Insert order
+/// Insert a pending order with its submit queue item and `created` audit row.
+///
+/// A `client_order_id` that already exists maps to `DuplicateClientOrderId`.
+async fn insert_pending_order(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    request: &OrderRequest,
+    actor: &str,
+) -> Result<Order, EnqueueError> {
     let row = tx
         .query_one(
-            "INSERT INTO prediction_orders (session_id, client_order_id, ticker, side, action, quantity, price_dollars, time_in_force, state, order_type, trigger_price) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'pending', $9, $10) \
+            "INSERT INTO prediction_orders (session_id, client_order_id, ticker, side, action, quantity, price_dollars, time_in_force, state, order_type, trigger_price, good_till, reduce_only) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'pending', $9, $10, $11, $12) \
              RETURNING id, created_at, updated_at",
             &[
                 &session_id,
@@ -645,6 +1056,8 @@ pub async fn enqueue_order(
                 &request.time_in_force.to_string(),
                 &request.order_type.to_string(),
                 &request.trigger_price,
+                &request.good_till,
+                &request.reduce_only,
             ],
         )
         .await
@@ -659,31 +1072,23 @@ pub async fn enqueue_order(
         })?;
 
     let order_id: i64 = row.get("id");
-    let created_at: DateTime<Utc> = row.get("created_at");
-    let updated_at: DateTime<Utc> = row.get("updated_at");
 
     // Insert into order queue
     tx.execute(
-        "INSERT INTO order_queue (order_id, action, actor) VALUES ($1, 'submit', 'api')",
-        &[&order_id],
+        "INSERT INTO order_queue (order_id, action, actor) VALUES ($1, 'submit', $2)",
+        &[&order_id, &actor],
     )
     .await
     .map_err(|e| EnqueueError::Database(format!("insert queue: {}", e)))?;
 
     // Insert audit log
     tx.execute(
-        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, 'none', 'pending', 'created', 'api')",
-        &[&order_id],
+        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, 'none', 'pending', 'created', $2)",
+        &[&order_id, &actor],
     )
     .await
     .map_err(|e| EnqueueError::Database(format!("insert audit: {}", e)))?;
 
-    tx.commit()
-        .await
-        .map_err(|e| EnqueueError::Database(format!("commit: {}", e)))?;
-
-    debug!(order_id, client_order_id = %request.client_order_id, "order enqueued");
-
     Ok(Order {
         id: order_id,
         session_id,
@@ -702,21 +1107,213 @@ pub async fn enqueue_order(
         trigger_price: request.trigger_price,
         group_id: None,
         leg_role: None,
-        created_at,
-        updated_at,
+        good_till: request.good_till,
+        reduce_only: request.reduce_only,
+        reject_reason: None,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
     })
 }
 
-/// Queued order item for the pump
-#[derive(Debug)]
-pub struct QueueItem {
-    pub queue_id: i64,
-    pub order_id: i64,
-    pub action: QueueAction,
-    pub order: Order,
-    pub metadata: Option<serde_json::Value>,
+/// Projected session risk if an order were enqueued
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskProjection {
+    /// Open notional before the order
+    #[serde(with = "rust_decimal::serde::str")]
+    pub open_notional: Decimal,
+    /// Open notional including the order
+    #[serde(with = "rust_decimal::serde::str")]
+    pub projected_open_notional: Decimal,
+    /// Effective session limit (per-session override or global)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub max_notional: Decimal,
+    /// Remaining notional after the order
+    #[serde(with = "rust_decimal::serde::str")]
+    pub headroom: Decimal,
+}
+
+/// Lock the session's open orders and run every enqueue-time risk check for
+/// `request`.
+async fn check_enqueue_risk(
+    tx: &deadpool_postgres::Transaction<'_>,
+    request: &OrderRequest,
+    session_id: i64,
+    limits: &RiskLimits,
+) -> Result<RiskProjection, EnqueueError> {
+    lock_open_orders(tx, session_id).await?;
+    check_orders_risk(tx, session_id, &[request], limits).await
+}
+
+/// Run every enqueue-time risk check for `requests` as if they were enqueued
+/// in order: reduce-only, fat-finger, aggregate notional and open contracts
+/// (netted against the position), per-ticker, and daily loss.
+///
+/// Each order is checked against the exposure of the orders ahead of it, and
+/// a long position closed by one sell can't be closed again by a later one.
+/// Enqueues hold the open-order lock; `validate_order` runs it on a read-only
+/// snapshot so a dry run sees exactly what a real enqueue would.
+async fn check_orders_risk(
+    tx: &deadpool_postgres::Transaction<'_>,
+    session_id: i64,
+    requests: &[&OrderRequest],
+    limits: &RiskLimits,
+) -> Result<RiskProjection, EnqueueError> {
+    check_reduce_only_orders(tx, session_id, requests).await?;
+
+    let (risk_state, mut contract_state) = load_open_exposure(tx, session_id).await?;
+
+    // Resting sells that close an existing long don't count as exposure, and
+    // neither does the part of a new sell that closes what remains of it
+    let mut netting = load_position_netting(tx, session_id).await?;
+    let open_notional = risk_state.open_notional - netting.total_closing_notional();
+    let mut risk_state = RiskState { open_notional };
+
+    let effective_limits = load_effective_limits(tx, session_id, limits).await?;
+
+    let mut requested_by_ticker: std::collections::BTreeMap<&str, (Decimal, u32)> =
+        std::collections::BTreeMap::new();
+    for request in requests {
+        let closeable = netting.take_closeable(request);
+
+        // Risk check (fat-finger + aggregate notional)
+        risk_state
+            .check_order_netted(request, &effective_limits, closeable)
+            .map_err(EnqueueError::RiskCheck)?;
+
+        // Open contract count, netted like notional
+        let contracts = crate::risk::opening_contracts(request, closeable);
+        contract_state
+            .check(contracts, &effective_limits)
+            .map_err(EnqueueError::RiskCheck)?;
+
+        let requested = crate::risk::opening_notional(request, closeable);
+        risk_state.open_notional += requested;
+        contract_state.open_contracts += contracts;
+
+        let entry = requested_by_ticker
+            .entry(request.ticker.as_str())
+            .or_insert((Decimal::ZERO, 0));
+        entry.0 += requested;
+        entry.1 += 1;
+    }
+
+    // Per-ticker check (notional + open order count)
+    if effective_limits.max_ticker_notional.is_some()
+        || effective_limits.max_open_orders_per_ticker.is_some()
+    {
+        let tickers: Vec<String> = requested_by_ticker.keys().map(|t| t.to_string()).collect();
+        let ticker_risk = load_ticker_risk(tx, session_id, &tickers).await?;
+        for (ticker, (requested, count)) in &requested_by_ticker {
+            let mut ticker_state = ticker_risk.get(*ticker).cloned().unwrap_or_default();
+            ticker_state.open_notional -= netting.closing_notional(ticker);
+            ticker_state
+                .check(ticker, *requested, *count, &effective_limits)
+                .map_err(EnqueueError::RiskCheck)?;
+        }
+    }
+
+    check_daily_loss(tx, session_id, effective_limits.daily_loss_limit).await?;
+
+    let projected_open_notional = risk_state.open_notional;
+    Ok(RiskProjection {
+        open_notional,
+        projected_open_notional,
+        max_notional: effective_limits.max_notional,
+        headroom: effective_limits.max_notional - projected_open_notional,
+    })
+}
+
+/// Dry-run an enqueue: duplicate check and risk checks inside a read-only
+/// snapshot transaction. Nothing is persisted and no row locks are taken, so a
+/// dry run never blocks real enqueues on the session.
+pub async fn validate_order(
+    pool: &Pool,
+    request: &OrderRequest,
+    session_id: i64,
+    limits: &RiskLimits,
+) -> Result<RiskProjection, EnqueueError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("pool error: {}", e)))?;
+
+    let tx = client
+        .build_transaction()
+        .isolation_level(tokio_postgres::IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("begin tx: {}", e)))?;
+
+    let duplicate = tx
+        .query_opt(
+            "SELECT 1 FROM prediction_orders WHERE client_order_id = $1",
+            &[&request.client_order_id],
+        )
+        .await
+        .map_err(|e| EnqueueError::Database(format!("duplicate check: {}", e)))?;
+    if duplicate.is_some() {
+        return Err(EnqueueError::DuplicateClientOrderId(request.client_order_id));
+    }
+
+    let projection = check_orders_risk(&tx, session_id, &[request], limits).await;
+
+    tx.rollback()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("rollback: {}", e)))?;
+
+    projection
+}
+
+/// The core transactional enqueue operation.
+///
+/// Single transaction: SELECT FOR UPDATE (risk state) → risk check → INSERT order → INSERT queue → COMMIT
+pub async fn enqueue_order(
+    pool: &Pool,
+    request: &OrderRequest,
+    session_id: i64,
+    limits: &RiskLimits,
+    actor: &str,
+) -> Result<Order, EnqueueError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("pool error: {}", e)))?;
+
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("begin tx: {}", e)))?;
+
+    check_enqueue_risk(&tx, request, session_id, limits).await?;
+    let order = insert_pending_order(&tx, session_id, request, actor).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("commit: {}", e)))?;
+
+    debug!(order_id = order.id, client_order_id = %request.client_order_id, "order enqueued");
+
+    Ok(order)
 }
 
+/// Queued order item for the pump
+#[derive(Debug)]
+pub struct QueueItem {
+    pub queue_id: i64,
+    pub order_id: i64,
+    pub action: QueueAction,
+    pub order: Order,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Columns selected by the dequeue queries, as read by [`claim_queue_row`].
+const DEQUEUE_COLUMNS: &str = "q.id as queue_id, q.order_id, q.action, q.metadata, \
+    o.id, o.session_id, o.client_order_id, o.exchange_order_id, \
+    o.ticker, o.side, o.action as order_action, o.quantity, o.price_dollars, \
+    filled_qty(o.id) as filled_quantity, o.time_in_force, o.state, o.cancel_reason, \
+    o.order_type, o.trigger_price, o.group_id, o.leg_role, o.good_till, o.reduce_only, o.reject_reason, o.created_at, o.updated_at";
+
 /// Dequeue the next order for processing, scoped to a session.
 ///
 /// Uses SELECT FOR UPDATE SKIP LOCKED for concurrent safety.
@@ -735,17 +1332,16 @@ pub async fn dequeue_order(pool: &Pool, session_id: i64) -> Result<Option<QueueI
     // Dequeue with SKIP LOCKED, filtered by session
     let row = tx
         .query_opt(
-            "SELECT q.id as queue_id, q.order_id, q.action, q.metadata, \
-                    o.id, o.session_id, o.client_order_id, o.exchange_order_id, \
-                    o.ticker, o.side, o.action as order_action, o.quantity, o.price_dollars, \
-                    filled_qty(o.id) as filled_quantity, o.time_in_force, o.state, o.cancel_reason, \
-                    o.order_type, o.trigger_price, o.group_id, o.leg_role, o.created_at, o.updated_at \
-             FROM order_queue q \
-             JOIN prediction_orders o ON o.id = q.order_id \
-             WHERE NOT q.processing AND o.session_id = $1 \
-             ORDER BY q.id \
-             LIMIT 1 \
-             FOR UPDATE OF q SKIP LOCKED",
+            &format!(
+                "SELECT {} \
+                 FROM order_queue q \
+                 JOIN prediction_orders o ON o.id = q.order_id \
+                 WHERE NOT q.processing AND o.session_id = $1 \
+                 ORDER BY q.id \
+                 LIMIT 1 \
+                 FOR UPDATE OF q SKIP LOCKED",
+                DEQUEUE_COLUMNS
+            ),
             &[&session_id],
         )
         .await
@@ -756,6 +1352,74 @@ pub async fn dequeue_order(pool: &Pool, session_id: i64) -> Result<Option<QueueI
         None => return Ok(None),
     };
 
+    let item = claim_queue_row(&tx, &row).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("commit: {}", e))?;
+
+    Ok(Some(item))
+}
+
+/// Dequeue up to `limit` submit items from the head of a session's queue,
+/// for batch submission.
+///
+/// Stops at the first non-submit item so batching never reorders a submit
+/// ahead of an earlier cancel/amend/decrease. Each item is claimed exactly as
+/// [`dequeue_order`] would claim it.
+pub async fn dequeue_submit_run(
+    pool: &Pool,
+    session_id: i64,
+    limit: i64,
+) -> Result<Vec<QueueItem>, String> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| format!("begin tx: {}", e))?;
+
+    let rows = tx
+        .query(
+            &format!(
+                "SELECT {} \
+                 FROM order_queue q \
+                 JOIN prediction_orders o ON o.id = q.order_id \
+                 WHERE NOT q.processing AND o.session_id = $1 \
+                 ORDER BY q.id \
+                 LIMIT $2 \
+                 FOR UPDATE OF q SKIP LOCKED",
+                DEQUEUE_COLUMNS
+            ),
+            &[&session_id, &limit],
+        )
+        .await
+        .map_err(|e| format!("dequeue query: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in &rows {
+        if row.get::<_, String>("action").parse() != Ok(QueueAction::Submit) {
+            break;
+        }
+        items.push(claim_queue_row(&tx, row).await?);
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("commit: {}", e))?;
+
+    Ok(items)
+}
+
+/// Mark a selected queue row as processing and, for submits, move the order
+/// to submitted. Runs inside the caller's dequeue transaction.
+async fn claim_queue_row(
+    tx: &deadpool_postgres::Transaction<'_>,
+    row: &tokio_postgres::Row,
+) -> Result<QueueItem, String> {
     let queue_id: i64 = row.get("queue_id");
     let order_id: i64 = row.get("order_id");
     let action_str: String = row.get("action");
@@ -792,10 +1456,6 @@ pub async fn dequeue_order(pool: &Pool, session_id: i64) -> Result<Option<QueueI
         .map_err(|e| format!("insert audit: {}", e))?;
     }
 
-    tx.commit()
-        .await
-        .map_err(|e| format!("commit: {}", e))?;
-
     let order = Order {
         id: order_id,
         session_id: row.get("session_id"),
@@ -822,19 +1482,22 @@ pub async fn dequeue_order(pool: &Pool, session_id: i64) -> Result<Option<QueueI
         leg_role: row
             .get::<_, Option<String>>("leg_role")
             .map(|s| parse_leg_role(&s)),
+        good_till: row.get("good_till"),
+        reduce_only: row.get("reduce_only"),
+        reject_reason: row.get("reject_reason"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     };
 
     let metadata: Option<serde_json::Value> = row.get("metadata");
 
-    Ok(Some(QueueItem {
+    Ok(QueueItem {
         queue_id,
         order_id,
         action,
         order,
         metadata,
-    }))
+    })
 }
 
 /// Update an order's state after exchange interaction.
@@ -1031,6 +1694,24 @@ pub async fn update_decreased_order(
     Ok(())
 }
 
+/// Record the exchange's reason for rejecting an order.
+pub async fn set_reject_reason(pool: &Pool, order_id: i64, reason: &str) -> Result<(), String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    client
+        .execute(
+            "UPDATE prediction_orders SET reject_reason = $2 WHERE id = $1",
+            &[&order_id, &reason],
+        )
+        .await
+        .map_err(|e| format!("set reject reason: {}", e))?;
+
+    Ok(())
+}
+
 /// Remove a processed queue item
 pub async fn remove_queue_item(pool: &Pool, queue_id: i64) -> Result<(), String> {
     let client = pool
@@ -1064,6 +1745,39 @@ pub async fn requeue_item(pool: &Pool, queue_id: i64) -> Result<(), String> {
     Ok(())
 }
 
+/// Queue backlog snapshot for a session
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueueDepth {
+    pub depth: i64,
+    /// Age of the oldest queue item in seconds (0 when the queue is empty)
+    pub oldest_age_secs: f64,
+}
+
+/// Count queued items (pending and in-flight) for a session and the age of the oldest.
+pub async fn queue_depth(pool: &Pool, session_id: i64) -> Result<QueueDepth, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let row = client
+        .query_one(
+            "SELECT count(*) AS depth, \
+                    COALESCE(EXTRACT(EPOCH FROM NOW() - min(q.created_at))::float8, 0) AS oldest_age \
+             FROM order_queue q \
+             JOIN prediction_orders o ON o.id = q.order_id \
+             WHERE o.session_id = $1",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("queue depth: {}", e))?;
+
+    Ok(QueueDepth {
+        depth: row.get("depth"),
+        oldest_age_secs: row.get("oldest_age"),
+    })
+}
+
 /// Record a fill (trade execution), validating the order belongs to the session.
 ///
 /// Uses INSERT ... SELECT to atomically verify order ownership.
@@ -1105,8 +1819,8 @@ pub async fn record_fill(
     Ok(result > 0)
 }
 
-/// Find the session for (exchange, environment) at startup.
-/// Returns None if no session exists (first boot).
+/// Find the open session for (exchange, environment) at startup.
+/// Returns None if no open session exists (first boot, or the last one was closed).
 pub async fn find_startup_session(
     pool: &Pool,
     exchange: &str,
@@ -1119,7 +1833,7 @@ pub async fn find_startup_session(
 
     let row = client
         .query_opt(
-            "SELECT id FROM sessions WHERE exchange = $1 AND environment = $2",
+            "SELECT id FROM sessions WHERE exchange = $1 AND environment = $2 AND closed_at IS NULL",
             &[&exchange, &environment],
         )
         .await
@@ -1135,7 +1849,7 @@ pub async fn find_startup_session(
     }
 }
 
-/// Get or create the single session for (exchange, environment).
+/// Get or create the single open session for (exchange, environment).
 ///
 /// If the session exists, updates api_key_prefix if a new one is provided.
 /// Returns the session ID. Idempotent — safe to call on every startup and API request.
@@ -1154,7 +1868,7 @@ pub async fn get_or_create_session(
         .query_one(
             "INSERT INTO sessions (exchange, environment, api_key_prefix) \
              VALUES ($1, $2, $3) \
-             ON CONFLICT (exchange, environment) \
+             ON CONFLICT (exchange, environment) WHERE closed_at IS NULL \
              DO UPDATE SET api_key_prefix = COALESCE(EXCLUDED.api_key_prefix, sessions.api_key_prefix), \
                            updated_at = NOW() \
              RETURNING id",
@@ -1213,6 +1927,32 @@ pub async fn find_filled_orders_without_fills(
     Ok(rows.iter().map(|r| r.get::<_, i64>("id")).collect())
 }
 
+/// Largest page size accepted by the paginated list queries.
+pub const MAX_PAGE_LIMIT: i64 = 1000;
+
+/// One page of a keyset-paginated listing, highest id first.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass back as `cursor` to fetch the next page; None on the last page.
+    pub next_cursor: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from up to `limit + 1` rows; the extra row only signals
+    /// that another page exists and is dropped.
+    fn from_rows(mut items: Vec<T>, limit: i64, id: impl Fn(&T) -> i64) -> Self {
+        let limit = limit as usize;
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(id)
+        } else {
+            None
+        };
+        Self { items, next_cursor }
+    }
+}
+
 /// A fill record returned by list_fills.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Fill {
@@ -1244,7 +1984,16 @@ pub struct AuditEntry {
 }
 
 /// List fills for a session, joining with prediction_orders to get ticker/side/action.
-pub async fn list_fills(pool: &Pool, session_id: i64, limit: i64) -> Result<Vec<Fill>, String> {
+///
+/// Keyset-paginated on fill id, newest first: pass the previous page's
+/// `next_cursor` as `cursor` to continue. `limit` is clamped to 1..=MAX_PAGE_LIMIT.
+pub async fn list_fills(
+    pool: &Pool,
+    session_id: i64,
+    cursor: Option<i64>,
+    limit: i64,
+) -> Result<Page<Fill>, String> {
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
     let client = pool
         .get()
         .await
@@ -1256,14 +2005,99 @@ pub async fn list_fills(pool: &Pool, session_id: i64, limit: i64) -> Result<Vec<
              f.price_dollars, f.quantity, f.is_taker, f.filled_at \
              FROM fills f \
              JOIN prediction_orders o ON f.order_id = o.id \
-             WHERE o.session_id = $1 \
-             ORDER BY f.filled_at DESC \
-             LIMIT $2",
-            &[&session_id, &limit],
+             WHERE o.session_id = $1 AND ($2::BIGINT IS NULL OR f.id < $2) \
+             ORDER BY f.id DESC \
+             LIMIT $3",
+            &[&session_id, &cursor, &(limit + 1)],
         )
         .await
         .map_err(|e| format!("list fills: {}", e))?;
 
+    let fills = rows
+        .iter()
+        .map(|row| Fill {
+            id: row.get("id"),
+            order_id: row.get("order_id"),
+            ticker: row.get("ticker"),
+            side: row.get("side"),
+            action: row.get("action"),
+            trade_id: row.get("trade_id"),
+            price_dollars: row.get("price_dollars"),
+            quantity: row.get("quantity"),
+            is_taker: row.get("is_taker"),
+            filled_at: row.get("filled_at"),
+        })
+        .collect();
+    Ok(Page::from_rows(fills, limit, |f: &Fill| f.id))
+}
+
+/// List a session's fills up to and including `until`, oldest first, for
+/// replaying position history.
+pub async fn list_fills_until(
+    pool: &Pool,
+    session_id: i64,
+    until: DateTime<Utc>,
+) -> Result<Vec<Fill>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let rows = client
+        .query(
+            "SELECT f.id, f.order_id, o.ticker, o.side, o.action, f.trade_id, \
+             f.price_dollars, f.quantity, f.is_taker, f.filled_at \
+             FROM fills f \
+             JOIN prediction_orders o ON f.order_id = o.id \
+             WHERE o.session_id = $1 AND f.filled_at <= $2 \
+             ORDER BY f.filled_at, f.id",
+            &[&session_id, &until],
+        )
+        .await
+        .map_err(|e| format!("list fills until: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| Fill {
+            id: row.get("id"),
+            order_id: row.get("order_id"),
+            ticker: row.get("ticker"),
+            side: row.get("side"),
+            action: row.get("action"),
+            trade_id: row.get("trade_id"),
+            price_dollars: row.get("price_dollars"),
+            quantity: row.get("quantity"),
+            is_taker: row.get("is_taker"),
+            filled_at: row.get("filled_at"),
+        })
+        .collect())
+}
+
+/// List the fills for one order, oldest first. Scoped to the session, so an
+/// order belonging to another session yields no rows.
+pub async fn list_fills_for_order(
+    pool: &Pool,
+    order_id: i64,
+    session_id: i64,
+) -> Result<Vec<Fill>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let rows = client
+        .query(
+            "SELECT f.id, f.order_id, o.ticker, o.side, o.action, f.trade_id, \
+             f.price_dollars, f.quantity, f.is_taker, f.filled_at \
+             FROM fills f \
+             JOIN prediction_orders o ON f.order_id = o.id \
+             WHERE f.order_id = $1 AND o.session_id = $2 \
+             ORDER BY f.filled_at, f.id",
+            &[&order_id, &session_id],
+        )
+        .await
+        .map_err(|e| format!("list fills for order: {}", e))?;
+
     Ok(rows
         .iter()
         .map(|row| Fill {
@@ -1282,11 +2116,15 @@ pub async fn list_fills(pool: &Pool, session_id: i64, limit: i64) -> Result<Vec<
 }
 
 /// List audit log entries for a session, joining with prediction_orders to get ticker.
+///
+/// Keyset-paginated on audit id, newest first (see `list_fills`).
 pub async fn list_audit_log(
     pool: &Pool,
     session_id: i64,
+    cursor: Option<i64>,
     limit: i64,
-) -> Result<Vec<AuditEntry>, String> {
+) -> Result<Page<AuditEntry>, String> {
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
     let client = pool
         .get()
         .await
@@ -1298,15 +2136,15 @@ pub async fn list_audit_log(
              a.event, a.actor, a.created_at \
              FROM audit_log a \
              JOIN prediction_orders o ON a.order_id = o.id \
-             WHERE o.session_id = $1 \
-             ORDER BY a.created_at DESC \
-             LIMIT $2",
-            &[&session_id, &limit],
+             WHERE o.session_id = $1 AND ($2::BIGINT IS NULL OR a.id < $2) \
+             ORDER BY a.id DESC \
+             LIMIT $3",
+            &[&session_id, &cursor, &(limit + 1)],
         )
         .await
         .map_err(|e| format!("list audit log: {}", e))?;
 
-    Ok(rows
+    let entries = rows
         .iter()
         .map(|row| AuditEntry {
             id: row.get("id"),
@@ -1318,7 +2156,8 @@ pub async fn list_audit_log(
             actor: row.get("actor"),
             created_at: row.get("created_at"),
         })
-        .collect())
+        .collect();
+    Ok(Page::from_rows(entries, limit, |e: &AuditEntry| e.id))
 }
 
 /// Find orders in ambiguous states (for recovery and reconciliation).
@@ -1337,7 +2176,7 @@ pub async fn get_ambiguous_orders(pool: &Pool, session_id: i64) -> Result<Vec<Or
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders \
              WHERE session_id = $1 AND state IN ('submitted', 'acknowledged', 'pending_cancel', 'pending_amend', 'pending_decrease') \
              ORDER BY id",
@@ -1361,7 +2200,7 @@ pub async fn get_order(pool: &Pool, order_id: i64, session_id: i64) -> Result<Op
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders WHERE id = $1 AND session_id = $2",
             &[&order_id, &session_id],
         )
@@ -1387,7 +2226,7 @@ pub async fn get_order_by_client_id(
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders WHERE client_order_id = $1 AND session_id = $2",
             &[&client_order_id, &session_id],
         )
@@ -1409,7 +2248,7 @@ pub async fn find_order_by_id(pool: &Pool, order_id: i64) -> Result<Option<Order
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders WHERE id = $1",
             &[&order_id],
         )
@@ -1437,7 +2276,7 @@ pub async fn find_order_by_exchange_id(
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders WHERE exchange_order_id = $1",
             &[&exchange_order_id],
         )
@@ -1464,7 +2303,7 @@ pub async fn list_orders(
                 "SELECT id, session_id, client_order_id, exchange_order_id, \
                         ticker, side, action, quantity, price_dollars, \
                         filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                        order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                        order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
                  FROM prediction_orders WHERE session_id = $1 AND state = $2 ORDER BY id",
                 &[&session_id, &state.to_string()],
             )
@@ -1475,7 +2314,7 @@ pub async fn list_orders(
                 "SELECT id, session_id, client_order_id, exchange_order_id, \
                         ticker, side, action, quantity, price_dollars, \
                         filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                        order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                        order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
                  FROM prediction_orders WHERE session_id = $1 ORDER BY id",
                 &[&session_id],
             )
@@ -1486,6 +2325,220 @@ pub async fn list_orders(
     Ok(rows.iter().map(row_to_order).collect())
 }
 
+/// List a page of orders for a session, newest first, optionally filtered by state.
+///
+/// Keyset-paginated on order id (see `list_fills`). Unlike `list_orders`, which
+/// internal callers use to see every order, this is bounded by MAX_PAGE_LIMIT.
+pub async fn list_orders_page(
+    pool: &Pool,
+    session_id: i64,
+    state_filter: Option<OrderState>,
+    cursor: Option<i64>,
+    limit: i64,
+) -> Result<Page<Order>, String> {
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let state_str = state_filter.map(|s| s.to_string());
+    let rows = client
+        .query(
+            "SELECT id, session_id, client_order_id, exchange_order_id, \
+                    ticker, side, action, quantity, price_dollars, \
+                    filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
+             FROM prediction_orders \
+             WHERE session_id = $1 AND ($2::TEXT IS NULL OR state = $2) \
+               AND ($3::BIGINT IS NULL OR id < $3) \
+             ORDER BY id DESC \
+             LIMIT $4",
+            &[&session_id, &state_str, &cursor, &(limit + 1)],
+        )
+        .await
+        .map_err(|e| format!("list orders page: {}", e))?;
+
+    let orders = rows.iter().map(row_to_order).collect();
+    Ok(Page::from_rows(orders, limit, |o: &Order| o.id))
+}
+
+/// A stored response for an `Idempotency-Key` replay
+#[derive(Debug, Clone)]
+pub struct IdempotentResponse {
+    pub method: String,
+    pub path: String,
+    pub status_code: u16,
+    pub response_body: String,
+    pub response_hash: String,
+}
+
+/// The request an `Idempotency-Key` is claimed for
+#[derive(Debug, Clone)]
+pub struct IdempotencyRequest {
+    pub method: String,
+    pub path: String,
+    /// Hex SHA-256 of the request body
+    pub request_hash: String,
+}
+
+/// Result of claiming an `Idempotency-Key`
+#[derive(Debug, Clone)]
+pub enum IdempotencyClaim {
+    /// The key was free (or expired); the caller runs the request and then
+    /// completes or releases the claim
+    Claimed,
+    /// The key is held by a request with a different method, path or body
+    Mismatch,
+    /// Another request holds the key and hasn't finished yet
+    InProgress,
+    /// The stored response of the request that held the key
+    Completed(IdempotentResponse),
+}
+
+/// Claim `(session_id, key)` for a request before running it.
+///
+/// The claim is a pending row inserted with `ON CONFLICT DO NOTHING`, so of two
+/// concurrent requests with the same key exactly one gets `Claimed`. An entry
+/// older than `ttl` is replaced, and so is a pending claim older than `lease`:
+/// its request crashed or was dropped before completing or releasing it.
+pub async fn claim_idempotency_key(
+    pool: &Pool,
+    session_id: i64,
+    key: &str,
+    request: &IdempotencyRequest,
+    ttl: std::time::Duration,
+    lease: std::time::Duration,
+) -> Result<IdempotencyClaim, String> {
+    let mut client = pool.get().await.map_err(|e| format!("pool error: {}", e))?;
+    let ttl_secs = ttl.as_secs_f64();
+    let lease_secs = lease.as_secs_f64();
+
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| format!("begin tx: {}", e))?;
+
+    tx.execute(
+        "DELETE FROM idempotency_keys \
+         WHERE session_id = $1 AND idempotency_key = $2 \
+           AND (created_at <= NOW() - make_interval(secs => $3) \
+                OR (status_code IS NULL AND created_at <= NOW() - make_interval(secs => $4)))",
+        &[&session_id, &key, &ttl_secs, &lease_secs],
+    )
+    .await
+    .map_err(|e| format!("expire idempotency key: {}", e))?;
+
+    let inserted = tx
+        .execute(
+            "INSERT INTO idempotency_keys (session_id, idempotency_key, method, path, request_hash) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (session_id, idempotency_key) DO NOTHING",
+            &[&session_id, &key, &request.method, &request.path, &request.request_hash],
+        )
+        .await
+        .map_err(|e| format!("claim idempotency key: {}", e))?;
+
+    let claim = if inserted == 1 {
+        IdempotencyClaim::Claimed
+    } else {
+        let row = tx
+            .query_one(
+                "SELECT method, path, request_hash, status_code, response_body, response_hash \
+                 FROM idempotency_keys \
+                 WHERE session_id = $1 AND idempotency_key = $2",
+                &[&session_id, &key],
+            )
+            .await
+            .map_err(|e| format!("get idempotency key: {}", e))?;
+
+        let method: String = row.get("method");
+        let path: String = row.get("path");
+        // Entries stored before request hashing have no hash to compare
+        let request_hash: Option<String> = row.get("request_hash");
+        if method != request.method
+            || path != request.path
+            || request_hash.is_some_and(|h| h != request.request_hash)
+        {
+            IdempotencyClaim::Mismatch
+        } else {
+            match row.get::<_, Option<i32>>("status_code") {
+                Some(status_code) => IdempotencyClaim::Completed(IdempotentResponse {
+                    method,
+                    path,
+                    status_code: status_code as u16,
+                    response_body: row.get::<_, Option<String>>("response_body").unwrap_or_default(),
+                    response_hash: row.get::<_, Option<String>>("response_hash").unwrap_or_default(),
+                }),
+                None => IdempotencyClaim::InProgress,
+            }
+        }
+    };
+
+    tx.commit().await.map_err(|e| format!("commit: {}", e))?;
+    Ok(claim)
+}
+
+/// Store the response for a claimed `(session_id, key)`. A completed entry is
+/// never overwritten.
+pub async fn complete_idempotency_key(
+    pool: &Pool,
+    session_id: i64,
+    key: &str,
+    response: &IdempotentResponse,
+) -> Result<(), String> {
+    let client = pool.get().await.map_err(|e| format!("pool error: {}", e))?;
+    let status_code = response.status_code as i32;
+
+    client
+        .execute(
+            "UPDATE idempotency_keys \
+             SET status_code = $3, response_body = $4, response_hash = $5 \
+             WHERE session_id = $1 AND idempotency_key = $2 AND status_code IS NULL",
+            &[
+                &session_id,
+                &key,
+                &status_code,
+                &response.response_body,
+                &response.response_hash,
+            ],
+        )
+        .await
+        .map_err(|e| format!("store idempotency key: {}", e))?;
+
+    Ok(())
+}
+
+/// Drop a pending claim so a retry with the same key runs the request again.
+pub async fn release_idempotency_key(pool: &Pool, session_id: i64, key: &str) -> Result<(), String> {
+    let client = pool.get().await.map_err(|e| format!("pool error: {}", e))?;
+
+    client
+        .execute(
+            "DELETE FROM idempotency_keys \
+             WHERE session_id = $1 AND idempotency_key = $2 AND status_code IS NULL",
+            &[&session_id, &key],
+        )
+        .await
+        .map_err(|e| format!("release idempotency key: {}", e))?;
+
+    Ok(())
+}
+
+/// Delete idempotency entries older than `ttl`. Returns the number removed.
+pub async fn purge_idempotency_keys(pool: &Pool, ttl: std::time::Duration) -> Result<u64, String> {
+    let client = pool.get().await.map_err(|e| format!("pool error: {}", e))?;
+    let ttl_secs = ttl.as_secs_f64();
+
+    client
+        .execute(
+            "DELETE FROM idempotency_keys WHERE created_at <= NOW() - make_interval(secs => $1)",
+            &[&ttl_secs],
+        )
+        .await
+        .map_err(|e| format!("purge idempotency keys: {}", e))
+}
+
 /// Compute risk state from database
 pub async fn compute_risk_state(pool: &Pool, session_id: i64) -> Result<RiskState, String> {
     let client = pool
@@ -1503,30 +2556,94 @@ pub async fn compute_risk_state(pool: &Pool, session_id: i64) -> Result<RiskStat
         .await
         .map_err(|e| format!("compute risk: {}", e))?;
 
-    Ok(RiskState {
-        open_notional: row.get("open_notional"),
-    })
+    Ok(RiskState {
+        open_notional: row.get("open_notional"),
+    })
+}
+
+/// Sum of unfilled contracts across the session's open orders
+pub async fn compute_open_contracts(pool: &Pool, session_id: i64) -> Result<Decimal, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(quantity - filled_qty(id)), 0) as open_contracts \
+             FROM prediction_orders \
+             WHERE session_id = $1 AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease')",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("compute open contracts: {}", e))?;
+
+    Ok(row.get("open_contracts"))
+}
+
+/// Get the per-session max_notional override (NULL = use global)
+pub async fn get_session_max_notional(
+    pool: &Pool,
+    session_id: i64,
+) -> Result<Option<Decimal>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let row = client
+        .query_one(
+            "SELECT max_notional FROM sessions WHERE id = $1",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("get session risk: {}", e))?;
+
+    Ok(row.get("max_notional"))
+}
+
+/// Get the per-session max_open_contracts override (NULL = use global)
+pub async fn get_session_max_open_contracts(
+    pool: &Pool,
+    session_id: i64,
+) -> Result<Option<Decimal>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let row = client
+        .query_one(
+            "SELECT max_open_contracts FROM sessions WHERE id = $1",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("get session risk: {}", e))?;
+
+    Ok(row.get("max_open_contracts"))
 }
 
-/// Get the per-session max_notional override (NULL = use global)
-pub async fn get_session_max_notional(
+/// Get the per-session order rate limit override (NULL = use global)
+pub async fn get_session_order_rate_limit(
     pool: &Pool,
     session_id: i64,
-) -> Result<Option<Decimal>, String> {
+) -> Result<Option<u32>, String> {
     let client = pool
         .get()
         .await
         .map_err(|e| format!("pool error: {}", e))?;
 
     let row = client
-        .query_one(
-            "SELECT max_notional FROM sessions WHERE id = $1",
+        .query_opt(
+            "SELECT order_rate_limit FROM sessions WHERE id = $1",
             &[&session_id],
         )
         .await
-        .map_err(|e| format!("get session risk: {}", e))?;
+        .map_err(|e| format!("get session rate limit: {}", e))?;
 
-    Ok(row.get("max_notional"))
+    Ok(row
+        .and_then(|r| r.get::<_, Option<i32>>("order_rate_limit"))
+        .and_then(|v| u32::try_from(v).ok()))
 }
 
 /// Session info returned by list_sessions
@@ -1536,13 +2653,15 @@ pub struct SessionInfo {
     pub api_key_prefix: Option<String>,
     pub display_name: Option<String>,
     pub max_notional: Option<String>,
+    pub max_open_contracts: Option<String>,
     pub suspended: bool,
     pub open_notional: String,
     pub created_at: String,
+    pub closed_at: Option<String>,
 }
 
 /// List all sessions for an exchange+environment, with open_notional for each.
-/// Sessions are permanent (stable sessions) — all sessions are always active.
+/// Includes closed sessions (closed_at set) so their history stays visible.
 pub async fn list_sessions(
     pool: &Pool,
     exchange: &str,
@@ -1556,8 +2675,8 @@ pub async fn list_sessions(
 
     let rows = client
         .query(
-            "SELECT id, api_key_prefix, display_name, max_notional, \
-                    created_at::text \
+            "SELECT id, api_key_prefix, display_name, max_notional, max_open_contracts, \
+                    created_at::text, closed_at::text \
              FROM sessions \
              WHERE exchange = $1 AND environment = $2 \
              ORDER BY id",
@@ -1570,6 +2689,7 @@ pub async fn list_sessions(
     for row in &rows {
         let id: i64 = row.get("id");
         let max_notional: Option<Decimal> = row.get("max_notional");
+        let max_open_contracts: Option<Decimal> = row.get("max_open_contracts");
 
         let open_notional = match compute_risk_state(pool, id).await {
             Ok(rs) => rs.open_notional,
@@ -1581,16 +2701,48 @@ pub async fn list_sessions(
             api_key_prefix: row.get("api_key_prefix"),
             display_name: row.get("display_name"),
             max_notional: max_notional.map(|d| d.to_string()),
+            max_open_contracts: max_open_contracts.map(|d| d.to_string()),
             suspended: is_suspended(id),
             open_notional: open_notional.to_string(),
             created_at: row.get("created_at"),
+            closed_at: row.get("closed_at"),
         });
     }
 
     Ok(sessions)
 }
 
-/// Update the per-session risk limit (NULL = reset to global).
+/// Sessions of this exchange+environment that currently have open
+/// (non-terminal) orders. Closed sessions are included until their orders
+/// settle, so cancels requested by a forced close still get reconciled.
+/// Scoped like `list_session_ids` so an instance never reconciles another
+/// instance's sessions.
+pub async fn list_active_session_ids(
+    pool: &Pool,
+    exchange: &str,
+    environment: &str,
+) -> Result<Vec<i64>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let rows = client
+        .query(
+            "SELECT DISTINCT o.session_id FROM prediction_orders o \
+             JOIN sessions s ON s.id = o.session_id \
+             WHERE s.exchange = $1 AND s.environment = $2 \
+               AND o.state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
+             ORDER BY o.session_id",
+            &[&exchange, &environment],
+        )
+        .await
+        .map_err(|e| format!("list active sessions: {}", e))?;
+
+    Ok(rows.iter().map(|r| r.get("session_id")).collect())
+}
+
+/// Update the per-session risk limits (NULL = reset to global).
 /// Scoped to exchange+environment so an admin cannot modify sessions belonging to another instance.
 pub async fn update_session_risk(
     pool: &Pool,
@@ -1598,6 +2750,7 @@ pub async fn update_session_risk(
     exchange: &str,
     environment: &str,
     max_notional: Option<Decimal>,
+    max_open_contracts: Option<Decimal>,
 ) -> Result<bool, String> {
     let client = pool
         .get()
@@ -1606,8 +2759,9 @@ pub async fn update_session_risk(
 
     let count = client
         .execute(
-            "UPDATE sessions SET max_notional = $2 WHERE id = $1 AND exchange = $3 AND environment = $4",
-            &[&session_id, &max_notional, &exchange, &environment],
+            "UPDATE sessions SET max_notional = $2, max_open_contracts = $5 \
+             WHERE id = $1 AND exchange = $3 AND environment = $4",
+            &[&session_id, &max_notional, &exchange, &environment, &max_open_contracts],
         )
         .await
         .map_err(|e| format!("update session risk: {}", e))?;
@@ -1615,8 +2769,86 @@ pub async fn update_session_risk(
     Ok(count > 0)
 }
 
-/// List all session IDs for an exchange+environment.
-/// Sessions are permanent (stable sessions) — no closed_at filter needed.
+/// Close a session. Its key prefix then resolves to a fresh session.
+/// Scoped to exchange+environment like `update_session_risk`.
+///
+/// Returns false if the session does not exist or is already closed.
+pub async fn close_session(
+    pool: &Pool,
+    session_id: i64,
+    exchange: &str,
+    environment: &str,
+) -> Result<bool, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let count = client
+        .execute(
+            "UPDATE sessions SET closed_at = NOW(), updated_at = NOW() \
+             WHERE id = $1 AND exchange = $2 AND environment = $3 AND closed_at IS NULL",
+            &[&session_id, &exchange, &environment],
+        )
+        .await
+        .map_err(|e| format!("close session: {}", e))?;
+
+    if count > 0 {
+        info!(session_id, exchange, environment, "session closed");
+    }
+    Ok(count > 0)
+}
+
+/// Whether `session_id` is an open session of this exchange+environment.
+/// Admin handlers check this before acting on a session's orders.
+pub async fn is_open_session(
+    pool: &Pool,
+    session_id: i64,
+    exchange: &str,
+    environment: &str,
+) -> Result<bool, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let row = client
+        .query_opt(
+            "SELECT 1 FROM sessions \
+             WHERE id = $1 AND exchange = $2 AND environment = $3 AND closed_at IS NULL",
+            &[&session_id, &exchange, &environment],
+        )
+        .await
+        .map_err(|e| format!("session lookup: {}", e))?;
+
+    Ok(row.is_some())
+}
+
+/// Non-terminal orders for a session, as (order_id, state).
+pub async fn list_open_orders(pool: &Pool, session_id: i64) -> Result<Vec<(i64, OrderState)>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let rows = client
+        .query(
+            "SELECT id, state FROM prediction_orders \
+             WHERE session_id = $1 \
+               AND state NOT IN ('filled', 'cancelled', 'rejected', 'expired') \
+             ORDER BY id",
+            &[&session_id],
+        )
+        .await
+        .map_err(|e| format!("list open orders: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| (r.get("id"), parse_state(&r.get::<_, String>("state"))))
+        .collect())
+}
+
+/// List open (not closed) session IDs for an exchange+environment.
 pub async fn list_session_ids(
     pool: &Pool,
     exchange: &str,
@@ -1629,7 +2861,7 @@ pub async fn list_session_ids(
 
     let rows = client
         .query(
-            "SELECT id FROM sessions WHERE exchange = $1 AND environment = $2 ORDER BY id",
+            "SELECT id FROM sessions WHERE exchange = $1 AND environment = $2 AND closed_at IS NULL ORDER BY id",
             &[&exchange, &environment],
         )
         .await
@@ -1665,6 +2897,7 @@ pub async fn atomic_cancel_order(
     order_id: i64,
     session_id: i64,
     cancel_reason: &CancelReason,
+    actor: &str,
 ) -> Result<(), String> {
     let mut client = pool
         .get()
@@ -1720,8 +2953,8 @@ pub async fn atomic_cancel_order(
     // Pre-exchange orders (Cancelled directly) have nothing to cancel.
     if target == OrderState::PendingCancel {
         tx.execute(
-            "INSERT INTO order_queue (order_id, action, actor) VALUES ($1, 'cancel', 'api')",
-            &[&order_id],
+            "INSERT INTO order_queue (order_id, action, actor) VALUES ($1, 'cancel', $2)",
+            &[&order_id, &actor],
         )
         .await
         .map_err(|e| format!("enqueue cancel: {}", e))?;
@@ -1734,7 +2967,7 @@ pub async fn atomic_cancel_order(
     // Audit log
     tx.execute(
         "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, $3, $4, $5)",
-        &[&order_id, &current_state_str, &target_str, &event_name, &"api"],
+        &[&order_id, &current_state_str, &target_str, &event_name, &actor],
     )
     .await
     .map_err(|e| format!("insert audit: {}", e))?;
@@ -1748,6 +2981,98 @@ pub async fn atomic_cancel_order(
     Ok(())
 }
 
+/// Order moved to PendingCancel by the expiry sweeper
+#[derive(Debug, Clone)]
+pub struct ExpiredOrder {
+    pub order_id: i64,
+    pub session_id: i64,
+}
+
+/// Expire resting orders whose `good_till` is at or before `now`.
+///
+/// Acknowledged and partially filled orders move to PendingCancel with
+/// cancel_reason `expired` and get a cancel enqueued, all in one transaction.
+/// Rows locked by another transaction (e.g. a concurrent user cancel) are
+/// skipped and picked up on the next sweep. Only sessions of this
+/// exchange+environment are swept, so an instance never cancels another
+/// instance's orders.
+pub async fn expire_orders(
+    pool: &Pool,
+    now: DateTime<Utc>,
+    exchange: &str,
+    environment: &str,
+) -> Result<Vec<ExpiredOrder>, String> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| format!("begin tx: {}", e))?;
+
+    let rows = tx
+        .query(
+            "SELECT o.id, o.session_id, o.state FROM prediction_orders o \
+             JOIN sessions s ON s.id = o.session_id \
+             WHERE o.good_till IS NOT NULL AND o.good_till <= $1 \
+               AND o.state IN ('acknowledged', 'partially_filled') \
+               AND s.exchange = $2 AND s.environment = $3 \
+             ORDER BY o.id \
+             FOR UPDATE OF o SKIP LOCKED",
+            &[&now, &exchange, &environment],
+        )
+        .await
+        .map_err(|e| format!("select expired orders: {}", e))?;
+
+    let cancel_str = CancelReason::Expired.to_string();
+    let target_str = OrderState::PendingCancel.to_string();
+    let mut expired = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let order_id: i64 = row.get("id");
+        let session_id: i64 = row.get("session_id");
+        let current_state_str: String = row.get("state");
+        let current_state = parse_state(&current_state_str);
+
+        validate_transition(current_state, OrderState::PendingCancel)
+            .map_err(|e| format!("order {} cannot expire from {}: {}", order_id, current_state, e))?;
+
+        tx.execute(
+            "UPDATE prediction_orders SET state = $1, cancel_reason = $2 WHERE id = $3",
+            &[&target_str, &cancel_str, &order_id],
+        )
+        .await
+        .map_err(|e| format!("update state: {}", e))?;
+
+        tx.execute(
+            "INSERT INTO order_queue (order_id, action, actor) VALUES ($1, 'cancel', 'expiry')",
+            &[&order_id],
+        )
+        .await
+        .map_err(|e| format!("enqueue cancel: {}", e))?;
+
+        tx.execute(
+            "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, $3, 'cancel_request', 'expiry')",
+            &[&order_id, &current_state_str, &target_str],
+        )
+        .await
+        .map_err(|e| format!("insert audit: {}", e))?;
+
+        expired.push(ExpiredOrder { order_id, session_id });
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("commit: {}", e))?;
+
+    if !expired.is_empty() {
+        info!(count = expired.len(), "expired orders past good_till");
+    }
+
+    Ok(expired)
+}
+
 /// Atomically amend an order: lock row, verify amendable state, update to PendingAmend,
 /// enqueue amend with metadata — all in one transaction.
 ///
@@ -1758,6 +3083,7 @@ pub async fn atomic_amend_order(
     session_id: i64,
     new_price_dollars: Option<Decimal>,
     new_quantity: Option<Decimal>,
+    actor: &str,
 ) -> Result<(), String> {
     if new_price_dollars.is_none() && new_quantity.is_none() {
         return Err("at least one of new_price_dollars or new_quantity required".to_string());
@@ -1776,7 +3102,8 @@ pub async fn atomic_amend_order(
     // Lock the order row and get current state (scoped to session)
     let row = tx
         .query_opt(
-            "SELECT state FROM prediction_orders WHERE id = $1 AND session_id = $2 FOR UPDATE",
+            "SELECT state, ticker, side, action, quantity, filled_qty(id) as filled_quantity, reduce_only \
+             FROM prediction_orders WHERE id = $1 AND session_id = $2 FOR UPDATE",
             &[&order_id, &session_id],
         )
         .await
@@ -1790,6 +3117,26 @@ pub async fn atomic_amend_order(
     let _ = validate_transition(current_state, OrderState::PendingAmend)
         .map_err(|e| format!("cannot amend order in {} state: {}", current_state, e))?;
 
+    // A reduce-only order may only grow within the position the session's
+    // other reduce-only orders leave to close
+    let quantity: Decimal = row.get("quantity");
+    if let Some(new_qty) = new_quantity.filter(|q| row.get::<_, bool>("reduce_only") && *q > quantity) {
+        lock_open_orders(&tx, session_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let ticker: String = row.get("ticker");
+        let side = parse_side(row.get("side"));
+        let positions = load_reduce_only_positions(&tx, session_id, Some(order_id)).await?;
+        let net = positions.get(&(ticker.clone(), side)).copied().unwrap_or_default();
+        let remaining = new_qty - row.get::<_, Decimal>("filled_quantity");
+        let requested = match parse_action(row.get("action")) {
+            Action::Buy => remaining,
+            Action::Sell => -remaining,
+        };
+        crate::risk::check_reduce_only_quantity(&ticker, requested, net)
+            .map_err(|e| format!("cannot amend reduce-only order: {}", e))?;
+    }
+
     // Build metadata
     let mut metadata = serde_json::Map::new();
     if let Some(price) = new_price_dollars {
@@ -1810,16 +3157,16 @@ pub async fn atomic_amend_order(
 
     // Enqueue amend with metadata
     tx.execute(
-        "INSERT INTO order_queue (order_id, action, actor, metadata) VALUES ($1, 'amend', 'api', $2)",
-        &[&order_id, &metadata_json],
+        "INSERT INTO order_queue (order_id, action, actor, metadata) VALUES ($1, 'amend', $2, $3)",
+        &[&order_id, &actor, &metadata_json],
     )
     .await
     .map_err(|e| format!("enqueue amend: {}", e))?;
 
     // Audit log
     tx.execute(
-        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, 'pending_amend', 'amend_request', 'api')",
-        &[&order_id, &current_state_str],
+        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, 'pending_amend', 'amend_request', $3)",
+        &[&order_id, &current_state_str, &actor],
     )
     .await
     .map_err(|e| format!("insert audit: {}", e))?;
@@ -1840,6 +3187,7 @@ pub async fn atomic_decrease_order(
     order_id: i64,
     session_id: i64,
     reduce_by: Decimal,
+    actor: &str,
 ) -> Result<(), String> {
     if reduce_by <= Decimal::ZERO {
         return Err("reduce_by must be positive".to_string());
@@ -1896,16 +3244,16 @@ pub async fn atomic_decrease_order(
 
     // Enqueue decrease with metadata
     tx.execute(
-        "INSERT INTO order_queue (order_id, action, actor, metadata) VALUES ($1, 'decrease', 'api', $2)",
-        &[&order_id, &metadata_json],
+        "INSERT INTO order_queue (order_id, action, actor, metadata) VALUES ($1, 'decrease', $2, $3)",
+        &[&order_id, &actor, &metadata_json],
     )
     .await
     .map_err(|e| format!("enqueue decrease: {}", e))?;
 
     // Audit log
     tx.execute(
-        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, 'pending_decrease', 'decrease_request', 'api')",
-        &[&order_id, &current_state_str],
+        "INSERT INTO audit_log (order_id, from_state, to_state, event, actor) VALUES ($1, $2, 'pending_decrease', 'decrease_request', $3)",
+        &[&order_id, &current_state_str, &actor],
     )
     .await
     .map_err(|e| format!("insert audit: {}", e))?;
@@ -2049,6 +3397,41 @@ pub async fn drain_queue_for_shutdown_all(pool: &Pool) -> Result<u64, String> {
     Ok(count)
 }
 
+/// Resting orders across all sessions of this exchange+environment, as
+/// (order_id, session_id, exchange_order_id).
+///
+/// Used by `cancel_resting` shutdown to cancel live exchange orders one by one
+/// while leaving the queue intact for the next pod. Scoped so a pod never
+/// cancels (or marks cancelled) another instance's orders.
+pub async fn list_resting_orders_all(
+    pool: &Pool,
+    exchange: &str,
+    environment: &str,
+) -> Result<Vec<(i64, i64, String)>, String> {
+    let client = pool
+        .get()
+        .await
+        .map_err(|e| format!("pool error: {}", e))?;
+
+    let rows = client
+        .query(
+            "SELECT o.id, o.session_id, o.exchange_order_id FROM prediction_orders o \
+             JOIN sessions s ON s.id = o.session_id \
+             WHERE s.exchange = $1 AND s.environment = $2 \
+               AND o.state IN ('acknowledged', 'partially_filled') \
+               AND o.exchange_order_id IS NOT NULL \
+             ORDER BY o.id",
+            &[&exchange, &environment],
+        )
+        .await
+        .map_err(|e| format!("list resting orders: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| (r.get("id"), r.get("session_id"), r.get("exchange_order_id")))
+        .collect())
+}
+
 /// Local position computed from filled orders in a session.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LocalPosition {
@@ -2322,6 +3705,84 @@ pub async fn create_external_resting_order(
 
 // --- Helper parsers ---
 
+/// Per-order outcome of a batch enqueue
+#[derive(Debug, Clone)]
+pub enum BatchEnqueueOutcome {
+    /// Order inserted and queued for submission
+    Enqueued(Order),
+    /// client_order_id already exists (or repeats earlier in the batch); skipped
+    Duplicate(Uuid),
+}
+
+/// Enqueue several orders in one transaction.
+///
+/// Duplicate client_order_ids are skipped and reported per item. The remaining
+/// orders go through the same risk check as `enqueue_order`, in request order,
+/// each on top of the ones before it. Any risk failure rejects the entire
+/// batch. Outcomes are returned in request order.
+pub async fn enqueue_order_batch(
+    pool: &Pool,
+    requests: &[OrderRequest],
+    session_id: i64,
+    limits: &RiskLimits,
+    actor: &str,
+) -> Result<Vec<BatchEnqueueOutcome>, EnqueueError> {
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("pool error: {}", e)))?;
+
+    let tx = client
+        .transaction()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("begin tx: {}", e)))?;
+
+    lock_open_orders(&tx, session_id).await?;
+
+    // Find client_order_ids that already exist so they can be reported per item
+    let client_ids: Vec<Uuid> = requests.iter().map(|r| r.client_order_id).collect();
+    let existing_rows = tx
+        .query(
+            "SELECT client_order_id FROM prediction_orders WHERE client_order_id = ANY($1)",
+            &[&client_ids],
+        )
+        .await
+        .map_err(|e| EnqueueError::Database(format!("duplicate query: {}", e)))?;
+    let mut seen: std::collections::HashSet<Uuid> = existing_rows
+        .iter()
+        .map(|row| row.get::<_, Uuid>("client_order_id"))
+        .collect();
+    let is_new: Vec<bool> = requests
+        .iter()
+        .map(|r| seen.insert(r.client_order_id))
+        .collect();
+
+    let new_requests: Vec<&OrderRequest> = requests
+        .iter()
+        .zip(&is_new)
+        .filter(|(_, new)| **new)
+        .map(|(request, _)| request)
+        .collect();
+    check_orders_risk(&tx, session_id, &new_requests, limits).await?;
+
+    let mut outcomes = Vec::with_capacity(requests.len());
+    for (request, new) in requests.iter().zip(&is_new) {
+        if !*new {
+            outcomes.push(BatchEnqueueOutcome::Duplicate(request.client_order_id));
+            continue;
+        }
+        let order = insert_pending_order(&tx, session_id, request, actor).await?;
+        outcomes.push(BatchEnqueueOutcome::Enqueued(order));
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| EnqueueError::Database(format!("commit: {}", e)))?;
+
+    debug!(count = outcomes.len(), "order batch enqueued");
+    Ok(outcomes)
+}
+
 /// Create an order group with its legs atomically.
 ///
 /// Each leg is an `(OrderRequest, LegRole, OrderState)` tuple. Pending legs are
@@ -2333,7 +3794,10 @@ pub async fn create_order_group(
     group_type: GroupType,
     legs: &[(OrderRequest, LegRole, OrderState)],
     risk_limits: &RiskLimits,
+    peg: Option<PegConfig>,
 ) -> Result<(OrderGroup, Vec<Order>), EnqueueError> {
+    let link_mode = if peg.is_some() { LinkMode::Peg } else { LinkMode::None };
+
     let mut client = pool
         .get()
         .await
@@ -2344,19 +3808,12 @@ pub async fn create_order_group(
         .await
         .map_err(|e| EnqueueError::Database(format!("begin tx: {}", e)))?;
 
-    // Lock open order rows to serialize concurrent enqueues, then compute risk.
-    tx.query(
-        "SELECT id FROM prediction_orders \
-         WHERE session_id = $1 AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease') \
-         FOR UPDATE",
-        &[&session_id],
-    )
-    .await
-    .map_err(|e| EnqueueError::Database(format!("risk lock: {}", e)))?;
+    lock_open_orders(&tx, session_id).await?;
 
     let risk_row = tx
         .query_one(
-            "SELECT COALESCE(SUM(price_dollars * (quantity - filled_qty(id))), 0) as open_notional \
+            "SELECT COALESCE(SUM(price_dollars * (quantity - filled_qty(id))), 0) as open_notional, \
+                    COALESCE(SUM(quantity - filled_qty(id)), 0) as open_contracts \
              FROM prediction_orders \
              WHERE session_id = $1 AND state IN ('staged', 'monitoring', 'pending', 'submitted', 'acknowledged', 'partially_filled', 'pending_cancel', 'pending_amend', 'pending_decrease')",
             &[&session_id],
@@ -2365,11 +3822,14 @@ pub async fn create_order_group(
         .map_err(|e| EnqueueError::Database(format!("risk query: {}", e)))?;
 
     let open_notional: Decimal = risk_row.get::<_, Decimal>("open_notional");
+    let contract_state = ContractRiskState {
+        open_contracts: risk_row.get::<_, Decimal>("open_contracts"),
+    };
 
-    // Query per-session risk limit; fall back to global
+    // Query per-session risk limits; fall back to global
     let session_row = tx
         .query_one(
-            "SELECT max_notional FROM sessions WHERE id = $1",
+            "SELECT max_notional, max_open_contracts FROM sessions WHERE id = $1",
             &[&session_id],
         )
         .await
@@ -2379,9 +3839,16 @@ pub async fn create_order_group(
         Some(session_max) => session_max,
         None => risk_limits.max_notional,
     };
+    let contract_limits = RiskLimits {
+        max_open_contracts: session_row
+            .get::<_, Option<Decimal>>("max_open_contracts")
+            .or(risk_limits.max_open_contracts),
+        ..risk_limits.clone()
+    };
 
-    // Fat-finger check per leg + compute total notional for non-terminal legs
+    // Fat-finger check per leg + compute totals for non-terminal legs
     let mut legs_notional = Decimal::ZERO;
+    let mut legs_contracts = Decimal::ZERO;
     for (req, _role, state) in legs {
         let leg_notional = req.notional();
         if leg_notional > risk_limits.max_order_notional {
@@ -2394,6 +3861,7 @@ pub async fn create_order_group(
         }
         if state.is_open() {
             legs_notional += leg_notional;
+            legs_contracts += req.quantity;
         }
     }
 
@@ -2406,26 +3874,60 @@ pub async fn create_order_group(
             },
         ));
     }
+    contract_state
+        .check(legs_contracts, &contract_limits)
+        .map_err(EnqueueError::RiskCheck)?;
+
+    // Per-ticker check: group open legs by ticker (BTreeMap for a stable
+    // rejection order), then compare against existing per-ticker totals
+    if risk_limits.max_ticker_notional.is_some()
+        || risk_limits.max_open_orders_per_ticker.is_some()
+    {
+        let mut requested_by_ticker: std::collections::BTreeMap<&str, (Decimal, u32)> =
+            std::collections::BTreeMap::new();
+        for (req, _role, state) in legs {
+            if state.is_open() {
+                let entry = requested_by_ticker
+                    .entry(req.ticker.as_str())
+                    .or_insert((Decimal::ZERO, 0));
+                entry.0 += req.notional();
+                entry.1 += 1;
+            }
+        }
+
+        let tickers: Vec<String> = requested_by_ticker.keys().map(|t| t.to_string()).collect();
+        let ticker_risk = load_ticker_risk(&tx, session_id, &tickers).await?;
+        for (ticker, (requested, count)) in &requested_by_ticker {
+            ticker_risk
+                .get(*ticker)
+                .cloned()
+                .unwrap_or_default()
+                .check(ticker, *requested, *count, risk_limits)
+                .map_err(EnqueueError::RiskCheck)?;
+        }
+    }
 
     // Create the group
     let group_row = tx
         .query_one(
-            "INSERT INTO order_groups (session_id, group_type) VALUES ($1, $2) \
-             RETURNING id, session_id, group_type, state, created_at, updated_at",
-            &[&session_id, &group_type.to_string()],
+            "INSERT INTO order_groups \
+               (session_id, group_type, link_mode, peg_spread_dollars, peg_min_reprice_dollars) \
+             VALUES ($1, $2, $3, $4, $5) \
+             RETURNING id, session_id, group_type, state, link_mode, peg_spread_dollars, \
+                       peg_min_reprice_dollars, created_at, updated_at",
+            &[
+                &session_id,
+                &group_type.to_string(),
+                &link_mode.to_string(),
+                &peg.map(|p| p.spread_dollars),
+                &peg.map(|p| p.min_reprice_dollars),
+            ],
         )
         .await
         .map_err(|e| EnqueueError::Database(format!("insert group: {}", e)))?;
 
     let group_id: i64 = group_row.get("id");
-    let group = OrderGroup {
-        id: group_id,
-        session_id: group_row.get("session_id"),
-        group_type: parse_group_type(group_row.get("group_type")),
-        state: parse_group_state(group_row.get("state")),
-        created_at: group_row.get("created_at"),
-        updated_at: group_row.get("updated_at"),
-    };
+    let group = row_to_group(&group_row);
 
     let mut orders = Vec::with_capacity(legs.len());
     for (req, role, initial_state) in legs {
@@ -2436,8 +3938,8 @@ pub async fn create_order_group(
             .query_one(
                 "INSERT INTO prediction_orders \
                  (session_id, client_order_id, ticker, side, action, quantity, price_dollars, \
-                  time_in_force, state, group_id, leg_role, order_type, trigger_price) \
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) \
+                  time_in_force, state, group_id, leg_role, order_type, trigger_price, good_till) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) \
                  RETURNING id, created_at, updated_at",
                 &[
                     &session_id,
@@ -2453,6 +3955,7 @@ pub async fn create_order_group(
                     &role_str,
                     &req.order_type.to_string(),
                     &req.trigger_price,
+                    &req.good_till,
                 ],
             )
             .await
@@ -2506,6 +4009,9 @@ pub async fn create_order_group(
             trigger_price: req.trigger_price,
             group_id: Some(group_id),
             leg_role: Some(*role),
+            good_till: req.good_till,
+            reduce_only: false,
+            reject_reason: None,
             created_at,
             updated_at,
         });
@@ -2602,7 +4108,7 @@ pub async fn get_group_orders(
             "SELECT id, session_id, client_order_id, exchange_order_id, \
                     ticker, side, action, quantity, price_dollars, \
                     filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                    order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                    order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
              FROM prediction_orders \
              WHERE group_id = $1 AND session_id = $2 \
              ORDER BY id",
@@ -2615,6 +4121,8 @@ pub async fn get_group_orders(
 }
 
 /// Get active groups with at least one terminal leg for trigger evaluation.
+///
+/// Active peg groups are always included so leg2 can follow leg1's price.
 pub async fn get_groups_needing_evaluation(
     pool: &Pool,
     session_id: i64,
@@ -2627,11 +4135,12 @@ pub async fn get_groups_needing_evaluation(
     // Find active groups that have at least one terminal order
     let group_rows = client
         .query(
-            "SELECT DISTINCT g.id, g.session_id, g.group_type, g.state, g.created_at, g.updated_at \
+            "SELECT DISTINCT g.id, g.session_id, g.group_type, g.state, g.link_mode, \
+                    g.peg_spread_dollars, g.peg_min_reprice_dollars, g.created_at, g.updated_at \
              FROM order_groups g \
              JOIN prediction_orders o ON o.group_id = g.id \
              WHERE g.state = 'active' AND g.session_id = $1 \
-               AND o.state IN ('filled', 'cancelled', 'rejected', 'expired') \
+               AND (o.state IN ('filled', 'cancelled', 'rejected', 'expired') OR g.link_mode = 'peg') \
              ORDER BY g.id",
             &[&session_id],
         )
@@ -2652,7 +4161,7 @@ pub async fn get_groups_needing_evaluation(
                 "SELECT id, session_id, client_order_id, exchange_order_id, \
                         ticker, side, action, quantity, price_dollars, \
                         filled_qty(id) as filled_quantity, time_in_force, state, cancel_reason, \
-                        order_type, trigger_price, group_id, leg_role, created_at, updated_at \
+                        order_type, trigger_price, group_id, leg_role, good_till, reduce_only, reject_reason, created_at, updated_at \
                  FROM prediction_orders \
                  WHERE group_id = $1 AND session_id = $2 \
                  ORDER BY id",
@@ -2726,7 +4235,7 @@ pub async fn list_groups(
     let rows = if let Some(state) = state_filter {
         client
             .query(
-                "SELECT id, session_id, group_type, state, created_at, updated_at \
+                "SELECT id, session_id, group_type, state, link_mode, peg_spread_dollars, peg_min_reprice_dollars, created_at, updated_at \
                  FROM order_groups WHERE session_id = $1 AND state = $2 ORDER BY id",
                 &[&session_id, &state.to_string()],
             )
@@ -2734,7 +4243,7 @@ pub async fn list_groups(
     } else {
         client
             .query(
-                "SELECT id, session_id, group_type, state, created_at, updated_at \
+                "SELECT id, session_id, group_type, state, link_mode, peg_spread_dollars, peg_min_reprice_dollars, created_at, updated_at \
                  FROM order_groups WHERE session_id = $1 ORDER BY id",
                 &[&session_id],
             )
@@ -2758,7 +4267,7 @@ pub async fn get_group(
 
     let row = client
         .query_opt(
-            "SELECT id, session_id, group_type, state, created_at, updated_at \
+            "SELECT id, session_id, group_type, state, link_mode, peg_spread_dollars, peg_min_reprice_dollars, created_at, updated_at \
              FROM order_groups WHERE id = $1 AND session_id = $2",
             &[&group_id, &session_id],
         )
@@ -3010,6 +4519,9 @@ fn row_to_order(row: &tokio_postgres::Row) -> Order {
         leg_role: row
             .get::<_, Option<String>>("leg_role")
             .map(|s| parse_leg_role(&s)),
+        good_till: row.get("good_till"),
+        reduce_only: row.get("reduce_only"),
+        reject_reason: row.get("reject_reason"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }
@@ -3021,6 +4533,17 @@ fn row_to_group(row: &tokio_postgres::Row) -> OrderGroup {
         session_id: row.get("session_id"),
         group_type: parse_group_type(row.get("group_type")),
         state: parse_group_state(row.get("state")),
+        link_mode: parse_link_mode(row.get("link_mode")),
+        peg: match (
+            row.get::<_, Option<Decimal>>("peg_spread_dollars"),
+            row.get::<_, Option<Decimal>>("peg_min_reprice_dollars"),
+        ) {
+            (Some(spread_dollars), Some(min_reprice_dollars)) => Some(PegConfig {
+                spread_dollars,
+                min_reprice_dollars,
+            }),
+            _ => None,
+        },
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }
@@ -3128,6 +4651,17 @@ fn parse_group_state(s: &str) -> GroupState {
     }
 }
 
+fn parse_link_mode(s: &str) -> LinkMode {
+    match s {
+        "none" => LinkMode::None,
+        "peg" => LinkMode::Peg,
+        _ => {
+            warn!(value = s, "unknown link_mode in DB, defaulting to None");
+            LinkMode::None
+        }
+    }
+}
+
 fn parse_cancel_reason(s: &str) -> CancelReason {
     match s {
         "user_requested" => CancelReason::UserRequested,
@@ -3135,6 +4669,7 @@ fn parse_cancel_reason(s: &str) -> CancelReason {
         "shutdown" => CancelReason::Shutdown,
         "expired" => CancelReason::Expired,
         "exchange_cancel" => CancelReason::ExchangeCancel,
+        "session_closed" => CancelReason::SessionClosed,
         _ => {
             warn!(value = s, "unknown cancel_reason in DB, defaulting to ExchangeCancel");
             CancelReason::ExchangeCancel
@@ -3523,6 +5058,25 @@ async fn activate_staged_siblings(
     })
 }
 
+#[cfg(test)]
+mod page_tests {
+    use super::*;
+
+    #[test]
+    fn test_page_from_rows_with_more() {
+        let page = Page::from_rows(vec![9, 8, 7], 2, |id: &i64| *id);
+        assert_eq!(page.items, vec![9, 8]);
+        assert_eq!(page.next_cursor, Some(8));
+    }
+
+    #[test]
+    fn test_page_from_rows_last_page() {
+        let page = Page::from_rows(vec![3, 2], 2, |id: &i64| *id);
+        assert_eq!(page.items, vec![3, 2]);
+        assert_eq!(page.next_cursor, None);
+    }
+}
+
 #[cfg(test)]
 mod transition_tests {
     use super::*;
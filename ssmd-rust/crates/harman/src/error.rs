@@ -1,3 +1,4 @@
+use serde::Serialize;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -24,6 +25,13 @@ pub enum RiskCheckError {
         limit: rust_decimal::Decimal,
     },
 
+    #[error("max open contracts exceeded: current={current}, requested={requested}, limit={limit}")]
+    MaxOpenContractsExceeded {
+        current: rust_decimal::Decimal,
+        requested: rust_decimal::Decimal,
+        limit: rust_decimal::Decimal,
+    },
+
     #[error("max order notional exceeded: order_notional={order_notional}, limit={limit}")]
     MaxOrderNotionalExceeded {
         order_notional: rust_decimal::Decimal,
@@ -35,6 +43,50 @@ pub enum RiskCheckError {
         daily_pnl: rust_decimal::Decimal,
         limit: rust_decimal::Decimal,
     },
+
+    #[error("ticker limit exceeded for {ticker}: {limit}")]
+    TickerLimitExceeded { ticker: String, limit: TickerLimit },
+
+    #[error("reduce-only order would open or increase exposure on {ticker}: net_position={net_position}, requested={requested}")]
+    ReduceOnlyViolation {
+        ticker: String,
+        /// Filled net position (buys minus sells) before the order
+        net_position: rust_decimal::Decimal,
+        /// Signed order quantity: positive buys, negative sells
+        requested: rust_decimal::Decimal,
+    },
+}
+
+/// Which per-ticker risk limit was hit
+#[derive(Debug, Clone, PartialEq)]
+pub enum TickerLimit {
+    Notional {
+        current: rust_decimal::Decimal,
+        requested: rust_decimal::Decimal,
+        limit: rust_decimal::Decimal,
+    },
+    OpenOrders { current: u32, limit: u32 },
+}
+
+impl std::fmt::Display for TickerLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TickerLimit::Notional {
+                current,
+                requested,
+                limit,
+            } => write!(
+                f,
+                "max ticker notional: current={}, requested={}, limit={}",
+                current, requested, limit
+            ),
+            TickerLimit::OpenOrders { current, limit } => write!(
+                f,
+                "max open orders per ticker: current={}, limit={}",
+                current, limit
+            ),
+        }
+    }
 }
 
 /// Errors from order enqueue operations
@@ -50,11 +102,44 @@ pub enum EnqueueError {
     Database(String),
 }
 
+/// Why the exchange rejected an order, normalized across exchanges.
+/// Used as the `reason` label on `harman_orders_rejected_total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectCategory {
+    InsufficientBalance,
+    MarketClosed,
+    PriceOutOfBand,
+    InvalidOrder,
+    Other,
+}
+
+impl RejectCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectCategory::InsufficientBalance => "insufficient_balance",
+            RejectCategory::MarketClosed => "market_closed",
+            RejectCategory::PriceOutOfBand => "price_out_of_band",
+            RejectCategory::InvalidOrder => "invalid_order",
+            RejectCategory::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for RejectCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Errors from exchange operations
 #[derive(Error, Debug)]
 pub enum ExchangeError {
-    #[error("order rejected by exchange: {reason}")]
-    Rejected { reason: String },
+    #[error("order rejected by exchange ({category}): {reason}")]
+    Rejected {
+        category: RejectCategory,
+        reason: String,
+    },
 
     #[error("rate limited, retry after {retry_after_ms}ms")]
     RateLimited { retry_after_ms: u64 },
@@ -71,9 +156,19 @@ pub enum ExchangeError {
     #[error("exchange connection error: {0}")]
     Connection(String),
 
+    /// 5xx from the exchange: the request may succeed if retried
+    #[error("exchange server error: HTTP {status}")]
+    ServerError { status: u16 },
+
     #[error("exchange returned unexpected response: {0}")]
     Unexpected(String),
 
+    /// A batch call refused as a whole before any order in it was placed
+    /// (over the batch size limit, or a 4xx for the request); each order can
+    /// still be sent on its own
+    #[error("batch refused: {0}")]
+    BatchRefused(String),
+
     #[error("authentication error: {0}")]
     Auth(String),
 
@@ -85,6 +180,22 @@ pub enum ExchangeError {
 }
 
 impl ExchangeError {
+    /// An uncategorized rejection.
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        ExchangeError::Rejected {
+            category: RejectCategory::Other,
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns true for failures worth retrying as-is (5xx, connection errors).
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ExchangeError::ServerError { .. } | ExchangeError::Connection(_)
+        )
+    }
+
     /// Returns true if this is any variant of order-not-found.
     pub fn is_not_found(&self) -> bool {
         matches!(
@@ -0,0 +1,119 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::state::OrderState;
+use crate::types::GroupState;
+
+/// Default capacity of the live update channel. Slow subscribers that fall
+/// further behind than this see a `Lagged` error and skip ahead.
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// Live order/fill/group update pushed to streaming clients.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    OrderStateChanged {
+        session_id: i64,
+        order_id: i64,
+        state: OrderState,
+        actor: String,
+    },
+    FillRecorded {
+        session_id: i64,
+        count: u64,
+    },
+    GroupCompleted {
+        session_id: i64,
+        group_id: i64,
+        state: GroupState,
+    },
+}
+
+impl StreamEvent {
+    pub fn session_id(&self) -> i64 {
+        match self {
+            StreamEvent::OrderStateChanged { session_id, .. }
+            | StreamEvent::FillRecorded { session_id, .. }
+            | StreamEvent::GroupCompleted { session_id, .. } => *session_id,
+        }
+    }
+}
+
+/// Broadcast fan-out for `StreamEvent`s.
+///
+/// Publishing never blocks and never fails: with no subscribers the event is
+/// simply dropped.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<StreamEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    pub fn publish(&self, event: StreamEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StreamEvent> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_with_type_tag() {
+        let event = StreamEvent::OrderStateChanged {
+            session_id: 1,
+            order_id: 42,
+            state: OrderState::Acknowledged,
+            actor: "pump".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "order_state_changed");
+        assert_eq!(json["order_id"], 42);
+        assert_eq!(json["state"], "acknowledged");
+
+        let event = StreamEvent::GroupCompleted {
+            session_id: 1,
+            group_id: 7,
+            state: GroupState::Completed,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "group_completed");
+        assert_eq!(json["state"], "completed");
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_noop() {
+        let bus = EventBus::default();
+        bus.publish(StreamEvent::FillRecorded {
+            session_id: 1,
+            count: 1,
+        });
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_receives_events() {
+        let bus = EventBus::new(8);
+        let mut rx = bus.subscribe();
+        bus.publish(StreamEvent::FillRecorded {
+            session_id: 3,
+            count: 2,
+        });
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.session_id(), 3);
+    }
+}
@@ -6,7 +6,7 @@ use uuid::Uuid;
 use crate::error::ExchangeError;
 use crate::state::OrderState;
 use crate::types::{
-    Action, AmendRequest, AmendResult, Balance, ExchangeFill, ExchangeOrder,
+    Action, AmendRequest, AmendResult, Balance, ExchangeFill, ExchangeOrder, ExchangeOrderState,
     ExchangeOrderStatus, ExchangeSettlement, MarketResult, OrderRequest, Position, Side,
 };
 
@@ -21,6 +21,39 @@ pub trait ExchangeAdapter: Send + Sync {
     /// Returns the exchange-assigned order ID on success.
     async fn submit_order(&self, order: &OrderRequest) -> Result<String, ExchangeError>;
 
+    /// Most orders `submit_batch` sends in one exchange call.
+    ///
+    /// Adapters without native batch creation leave this at 1, and the pump
+    /// submits their orders one at a time.
+    fn max_submit_batch(&self) -> usize {
+        1
+    }
+
+    /// Submit several orders, returning one result per order in input order.
+    ///
+    /// The outer error means the batch call as a whole failed and no
+    /// per-order outcome is known (e.g. a timeout, where some orders may
+    /// have been placed). The default submits each order in turn; once one
+    /// is rate limited the rest are reported rate limited without being sent.
+    async fn submit_batch(
+        &self,
+        orders: &[OrderRequest],
+    ) -> Result<Vec<Result<String, ExchangeError>>, ExchangeError> {
+        let mut results = Vec::with_capacity(orders.len());
+        let mut retry_after = None;
+        for order in orders {
+            let result = match retry_after {
+                Some(retry_after_ms) => Err(ExchangeError::RateLimited { retry_after_ms }),
+                None => self.submit_order(order).await,
+            };
+            if let Err(ExchangeError::RateLimited { retry_after_ms }) = &result {
+                retry_after = Some(*retry_after_ms);
+            }
+            results.push(result);
+        }
+        Ok(results)
+    }
+
     /// Cancel an order by its exchange-assigned ID.
     async fn cancel_order(&self, exchange_order_id: &str) -> Result<(), ExchangeError>;
 
@@ -42,12 +75,52 @@ pub trait ExchangeAdapter: Send + Sync {
     /// Get order status by the exchange-assigned order ID.
     ///
     /// Preferred over `get_order_by_client_id` when the exchange order ID
-    /// is known. Uses the direct single-order endpoint which is more reliable
-    /// than list-based lookups.
+    /// is known. Adapters with a direct single-order endpoint should override
+    /// this; it is more reliable than list-based lookups.
+    ///
+    /// The default scans `get_orders` and then `get_fills`. An order that is
+    /// no longer resting but has fills is reported `Executed` with the summed
+    /// fill quantity, since a list lookup can't tell a full fill from a
+    /// partial fill that was later cancelled.
     async fn get_order_by_exchange_id(
         &self,
         exchange_order_id: &str,
-    ) -> Result<ExchangeOrderStatus, ExchangeError>;
+    ) -> Result<ExchangeOrderStatus, ExchangeError> {
+        if let Some(order) = self
+            .get_orders()
+            .await?
+            .into_iter()
+            .find(|o| o.exchange_order_id == exchange_order_id)
+        {
+            return Ok(ExchangeOrderStatus {
+                exchange_order_id: order.exchange_order_id,
+                status: order.status,
+                filled_quantity: order.filled_quantity,
+                remaining_quantity: order.remaining_quantity,
+                close_cancel_count: None,
+            });
+        }
+
+        let filled: Decimal = self
+            .get_fills(None)
+            .await?
+            .iter()
+            .filter(|f| f.order_id == exchange_order_id)
+            .map(|f| f.quantity)
+            .sum();
+        if filled.is_zero() {
+            return Err(ExchangeError::OrderNotFoundByExchangeId(
+                exchange_order_id.to_string(),
+            ));
+        }
+        Ok(ExchangeOrderStatus {
+            exchange_order_id: exchange_order_id.to_string(),
+            status: ExchangeOrderState::Executed,
+            filled_quantity: filled,
+            remaining_quantity: Decimal::ZERO,
+            close_cancel_count: None,
+        })
+    }
 
     /// Get current portfolio positions.
     async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError>;
@@ -161,3 +234,115 @@ pub trait EventStream: Send + Sync {
     /// The broadcast channel has a bounded buffer — slow consumers will lag.
     fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ExchangeEvent>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adapter with list endpoints only, exercising the default lookup.
+    struct ListOnly {
+        orders: Vec<ExchangeOrder>,
+        fills: Vec<ExchangeFill>,
+    }
+
+    #[async_trait]
+    impl ExchangeAdapter for ListOnly {
+        async fn submit_order(&self, _: &OrderRequest) -> Result<String, ExchangeError> {
+            unimplemented!()
+        }
+        async fn cancel_order(&self, _: &str) -> Result<(), ExchangeError> {
+            unimplemented!()
+        }
+        async fn cancel_all_orders(&self) -> Result<i32, ExchangeError> {
+            unimplemented!()
+        }
+        async fn get_order_by_client_id(
+            &self,
+            _: Uuid,
+        ) -> Result<ExchangeOrderStatus, ExchangeError> {
+            unimplemented!()
+        }
+        async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError> {
+            unimplemented!()
+        }
+        async fn get_orders(&self) -> Result<Vec<ExchangeOrder>, ExchangeError> {
+            Ok(self.orders.clone())
+        }
+        async fn get_fills(
+            &self,
+            _: Option<DateTime<Utc>>,
+        ) -> Result<Vec<ExchangeFill>, ExchangeError> {
+            Ok(self.fills.clone())
+        }
+        async fn get_balance(&self) -> Result<Balance, ExchangeError> {
+            unimplemented!()
+        }
+        async fn amend_order(&self, _: &AmendRequest) -> Result<AmendResult, ExchangeError> {
+            unimplemented!()
+        }
+        async fn decrease_order(&self, _: &str, _: Decimal) -> Result<(), ExchangeError> {
+            unimplemented!()
+        }
+        async fn is_market_active(&self, _: &str) -> Result<bool, ExchangeError> {
+            unimplemented!()
+        }
+        async fn get_settlements(
+            &self,
+            _: Option<DateTime<Utc>>,
+            _: Option<&str>,
+        ) -> Result<Vec<ExchangeSettlement>, ExchangeError> {
+            unimplemented!()
+        }
+    }
+
+    fn fill(order_id: &str, quantity: Decimal) -> ExchangeFill {
+        ExchangeFill {
+            trade_id: format!("t-{}", quantity),
+            order_id: order_id.to_string(),
+            ticker: "KXTEST".to_string(),
+            side: Side::Yes,
+            action: Action::Buy,
+            price_dollars: Decimal::new(50, 2),
+            quantity,
+            is_taker: true,
+            filled_at: Utc::now(),
+            client_order_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_get_order_by_exchange_id() {
+        let adapter = ListOnly {
+            orders: vec![ExchangeOrder {
+                exchange_order_id: "resting-1".to_string(),
+                client_order_id: None,
+                ticker: "KXTEST".to_string(),
+                side: Side::Yes,
+                action: Action::Buy,
+                price_dollars: Decimal::new(50, 2),
+                quantity: Decimal::from(10),
+                filled_quantity: Decimal::from(4),
+                remaining_quantity: Decimal::from(6),
+                status: ExchangeOrderState::Resting,
+            }],
+            fills: vec![
+                fill("done-1", Decimal::from(3)),
+                fill("done-1", Decimal::from(2)),
+            ],
+        };
+
+        let resting = adapter.get_order_by_exchange_id("resting-1").await.unwrap();
+        assert_eq!(resting.status, ExchangeOrderState::Resting);
+        assert_eq!(resting.remaining_quantity, Decimal::from(6));
+
+        let done = adapter.get_order_by_exchange_id("done-1").await.unwrap();
+        assert_eq!(done.status, ExchangeOrderState::Executed);
+        assert_eq!(done.filled_quantity, Decimal::from(5));
+
+        let missing = adapter.get_order_by_exchange_id("nope").await.unwrap_err();
+        assert!(matches!(
+            missing,
+            ExchangeError::OrderNotFoundByExchangeId(_)
+        ));
+    }
+}
@@ -1,9 +1,12 @@
 pub mod audit;
 pub mod db;
 pub mod error;
+pub mod events;
 pub mod exchange;
 pub mod fill_processor;
 pub mod order_importer;
+pub mod position_history;
+pub mod rate_limit;
 pub mod risk;
 pub mod settlement_compute;
 pub mod settlement_recorder;
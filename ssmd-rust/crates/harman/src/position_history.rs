@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::db::Fill;
+
+/// Longest time range a single history request may cover (24h).
+pub const MAX_HISTORY_RANGE_SECS: i64 = 24 * 60 * 60;
+
+/// Smallest bucket interval a history request may use.
+pub const MIN_INTERVAL_SECS: i64 = 60;
+
+/// Position state for one ticker/side at the end of a bucket.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PositionPoint {
+    pub bucket_end: DateTime<Utc>,
+    /// Net quantity: positive = long, negative = short
+    pub net_quantity: Decimal,
+    /// Average cost of the open quantity; zero when flat.
+    pub avg_cost_dollars: Decimal,
+    /// Cumulative realized PnL since the first fill in the session.
+    pub realized_pnl_dollars: Decimal,
+}
+
+/// Bucketed position curve for one ticker/side.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionSeries {
+    pub ticker: String,
+    pub side: String,
+    pub points: Vec<PositionPoint>,
+}
+
+/// Validate a history request range. Returns a message suitable for a 400.
+pub fn validate_range(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    interval_secs: i64,
+) -> Result<(), String> {
+    if from >= to {
+        return Err("from must be before to".to_string());
+    }
+    if (to - from).num_seconds() > MAX_HISTORY_RANGE_SECS {
+        return Err(format!("range exceeds {}s maximum", MAX_HISTORY_RANGE_SECS));
+    }
+    if interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!(
+            "interval_secs must be at least {}",
+            MIN_INTERVAL_SECS
+        ));
+    }
+    Ok(())
+}
+
+/// Running average-cost book for one ticker/side.
+#[derive(Debug, Default)]
+struct Book {
+    qty: Decimal,
+    avg_cost: Decimal,
+    realized: Decimal,
+}
+
+impl Book {
+    /// Apply a signed fill (buy positive, sell negative) at `price`.
+    ///
+    /// Fills that grow the position re-average the cost. Fills that reduce it
+    /// realize `(price - avg_cost) * closed` for longs (inverted for shorts)
+    /// and leave the average unchanged; a fill that crosses zero opens the
+    /// remainder at `price`.
+    fn apply(&mut self, delta: Decimal, price: Decimal) {
+        if delta.is_zero() {
+            return;
+        }
+        let new_qty = self.qty + delta;
+        if self.qty.is_zero() || self.qty.is_sign_positive() == delta.is_sign_positive() {
+            self.avg_cost = (self.avg_cost * self.qty.abs() + price * delta.abs()) / new_qty.abs();
+        } else {
+            let closed = delta.abs().min(self.qty.abs());
+            let pnl_per_contract = if self.qty.is_sign_positive() {
+                price - self.avg_cost
+            } else {
+                self.avg_cost - price
+            };
+            self.realized += closed * pnl_per_contract;
+            if new_qty.is_zero() {
+                self.avg_cost = Decimal::ZERO;
+            } else if new_qty.is_sign_positive() != self.qty.is_sign_positive() {
+                self.avg_cost = price;
+            }
+        }
+        self.qty = new_qty;
+    }
+}
+
+/// Replay fills (oldest first) into bucketed position curves per ticker/side.
+///
+/// Buckets are `[from + k*interval, from + (k+1)*interval]`, with the last
+/// bucket ending at `to`. A fill at exactly a bucket end counts toward that
+/// bucket. Fills before `from` seed the opening position. A series starts at
+/// the first bucket by whose end the ticker/side has any fill.
+///
+/// Yes and No contracts are separate instruments, so each side gets its own
+/// series. Net quantity is buys minus sells. PnL uses average-cost basis on
+/// the recorded fill price and excludes fees.
+pub fn replay_fills(
+    fills: &[Fill],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    interval: Duration,
+) -> Vec<PositionSeries> {
+    let mut bucket_ends = Vec::new();
+    let mut end = from + interval;
+    while end < to {
+        bucket_ends.push(end);
+        end += interval;
+    }
+    bucket_ends.push(to);
+
+    let mut grouped: BTreeMap<(&str, &str), Vec<&Fill>> = BTreeMap::new();
+    for fill in fills.iter().filter(|f| f.filled_at <= to) {
+        grouped
+            .entry((fill.ticker.as_str(), fill.side.as_str()))
+            .or_default()
+            .push(fill);
+    }
+
+    grouped
+        .into_iter()
+        .map(|((ticker, side), fills)| {
+            let mut book = Book::default();
+            let mut next = 0;
+            let mut points = Vec::new();
+            for &bucket_end in &bucket_ends {
+                while next < fills.len() && fills[next].filled_at <= bucket_end {
+                    let fill = fills[next];
+                    let delta = match fill.action.as_str() {
+                        "sell" => -fill.quantity,
+                        _ => fill.quantity,
+                    };
+                    book.apply(delta, fill.price_dollars);
+                    next += 1;
+                }
+                if next > 0 {
+                    points.push(PositionPoint {
+                        bucket_end,
+                        net_quantity: book.qty,
+                        avg_cost_dollars: book.avg_cost,
+                        realized_pnl_dollars: book.realized,
+                    });
+                }
+            }
+            PositionSeries {
+                ticker: ticker.to_string(),
+                side: side.to_string(),
+                points,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(mins: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, 14, 0, 0).unwrap() + Duration::minutes(mins)
+    }
+
+    fn fill(ticker: &str, side: &str, action: &str, qty: i64, price_cents: i64, mins: i64) -> Fill {
+        Fill {
+            id: 0,
+            order_id: 0,
+            ticker: ticker.to_string(),
+            side: side.to_string(),
+            action: action.to_string(),
+            trade_id: String::new(),
+            price_dollars: Decimal::new(price_cents, 2),
+            quantity: Decimal::new(qty, 0),
+            is_taker: true,
+            filled_at: at(mins),
+        }
+    }
+
+    #[test]
+    fn test_book_average_cost_and_realized() {
+        let mut book = Book::default();
+        book.apply(Decimal::new(10, 0), Decimal::new(40, 2));
+        book.apply(Decimal::new(10, 0), Decimal::new(60, 2));
+        assert_eq!(book.avg_cost, Decimal::new(50, 2));
+
+        // Sell 5 at $0.70: realize 5 * ($0.70 - $0.50) = $1.00
+        book.apply(Decimal::new(-5, 0), Decimal::new(70, 2));
+        assert_eq!(book.qty, Decimal::new(15, 0));
+        assert_eq!(book.avg_cost, Decimal::new(50, 2));
+        assert_eq!(book.realized, Decimal::new(100, 2));
+
+        // Close out at $0.40: realize 15 * -$0.10 = -$1.50
+        book.apply(Decimal::new(-15, 0), Decimal::new(40, 2));
+        assert_eq!(book.qty, Decimal::ZERO);
+        assert_eq!(book.avg_cost, Decimal::ZERO);
+        assert_eq!(book.realized, Decimal::new(-50, 2));
+    }
+
+    #[test]
+    fn test_book_flip_opens_remainder_at_fill_price() {
+        let mut book = Book::default();
+        book.apply(Decimal::new(5, 0), Decimal::new(30, 2));
+        // Sell 8 at $0.50: close 5 (+$1.00), open short 3 at $0.50
+        book.apply(Decimal::new(-8, 0), Decimal::new(50, 2));
+        assert_eq!(book.qty, Decimal::new(-3, 0));
+        assert_eq!(book.avg_cost, Decimal::new(50, 2));
+        assert_eq!(book.realized, Decimal::new(100, 2));
+
+        // Buy back 3 at $0.45: short profits 3 * $0.05 = $0.15
+        book.apply(Decimal::new(3, 0), Decimal::new(45, 2));
+        assert_eq!(book.qty, Decimal::ZERO);
+        assert_eq!(book.realized, Decimal::new(115, 2));
+    }
+
+    #[test]
+    fn test_replay_buckets_per_ticker_side() {
+        let fills = vec![
+            fill("KXA", "yes", "buy", 10, 40, -30), // before range: seeds opening position
+            fill("KXA", "yes", "sell", 4, 55, 7),
+            fill("KXB", "no", "buy", 2, 20, 15), // exactly on a bucket end
+            fill("KXA", "yes", "buy", 1, 10, 25), // after `to`: ignored
+        ];
+
+        let series = replay_fills(&fills, at(0), at(20), Duration::minutes(5));
+        assert_eq!(series.len(), 2);
+
+        let a = &series[0];
+        assert_eq!((a.ticker.as_str(), a.side.as_str()), ("KXA", "yes"));
+        let ends: Vec<_> = a.points.iter().map(|p| p.bucket_end).collect();
+        assert_eq!(ends, vec![at(5), at(10), at(15), at(20)]);
+        assert_eq!(a.points[0].net_quantity, Decimal::new(10, 0));
+        assert_eq!(a.points[1].net_quantity, Decimal::new(6, 0));
+        assert_eq!(a.points[1].realized_pnl_dollars, Decimal::new(60, 2));
+        assert_eq!(a.points[3].net_quantity, Decimal::new(6, 0));
+
+        let b = &series[1];
+        assert_eq!((b.ticker.as_str(), b.side.as_str()), ("KXB", "no"));
+        let ends: Vec<_> = b.points.iter().map(|p| p.bucket_end).collect();
+        assert_eq!(ends, vec![at(15), at(20)]);
+        assert_eq!(b.points[0].avg_cost_dollars, Decimal::new(20, 2));
+    }
+
+    #[test]
+    fn test_replay_last_bucket_clipped_to_range_end() {
+        let fills = vec![fill("KXA", "yes", "buy", 1, 50, 1)];
+        let series = replay_fills(&fills, at(0), at(12), Duration::minutes(5));
+        let ends: Vec<_> = series[0].points.iter().map(|p| p.bucket_end).collect();
+        assert_eq!(ends, vec![at(5), at(10), at(12)]);
+    }
+
+    #[test]
+    fn test_validate_range() {
+        assert!(validate_range(at(0), at(60), 300).is_ok());
+        assert!(validate_range(at(60), at(0), 300).is_err());
+        assert!(validate_range(at(0), at(60), 30).is_err());
+        assert!(validate_range(at(0), at(24 * 60 + 1), 300).is_err());
+    }
+}
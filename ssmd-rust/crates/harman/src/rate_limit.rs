@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+
+/// Token bucket for per-session API rate limiting.
+///
+/// Holds up to `rate_per_sec` tokens (one second of burst) and refills
+/// continuously at `rate_per_sec` tokens per second.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    rate_per_sec: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a full bucket. `rate_per_sec` must be non-zero.
+    pub fn new(rate_per_sec: u32) -> Self {
+        Self::new_at(rate_per_sec, Instant::now())
+    }
+
+    fn new_at(rate_per_sec: u32, now: Instant) -> Self {
+        let rate_per_sec = f64::from(rate_per_sec.max(1));
+        Self {
+            rate_per_sec,
+            tokens: rate_per_sec,
+            last_refill: now,
+        }
+    }
+
+    /// Take one token. On failure returns how long until a token is available.
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.try_acquire_n(1)
+    }
+
+    /// Take `n` tokens at once, e.g. one per order in a batch.
+    ///
+    /// A request costing more than the burst only needs a full bucket and
+    /// leaves it in debt, so later requests wait until the excess is repaid.
+    pub fn try_acquire_n(&mut self, n: u32) -> Result<(), Duration> {
+        self.try_acquire_n_at(n, Instant::now())
+    }
+
+    #[cfg(test)]
+    fn try_acquire_at(&mut self, now: Instant) -> Result<(), Duration> {
+        self.try_acquire_n_at(1, now)
+    }
+
+    fn try_acquire_n_at(&mut self, n: u32, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate_per_sec).min(self.rate_per_sec);
+        self.last_refill = now;
+
+        let cost = f64::from(n);
+        let required = cost.min(self.rate_per_sec);
+        if self.tokens >= required {
+            self.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((required - self.tokens) / self.rate_per_sec))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_burst_up_to_rate() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new_at(3, start);
+        for _ in 0..3 {
+            assert!(limiter.try_acquire_at(start).is_ok());
+        }
+        let retry_after = limiter.try_acquire_at(start).unwrap_err();
+        assert!(retry_after > Duration::ZERO);
+        assert!(retry_after <= Duration::from_millis(334));
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new_at(2, start);
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_err());
+
+        // Half a second at 2/s refills one token
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+
+    #[test]
+    fn test_refill_caps_at_burst() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new_at(2, start);
+        let much_later = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(much_later).is_ok());
+        assert!(limiter.try_acquire_at(much_later).is_ok());
+        assert!(limiter.try_acquire_at(much_later).is_err());
+    }
+
+    #[test]
+    fn test_acquire_n_charges_per_token() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new_at(5, start);
+        assert!(limiter.try_acquire_n_at(3, start).is_ok());
+        assert!(limiter.try_acquire_n_at(3, start).is_err());
+        assert!(limiter.try_acquire_n_at(2, start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_err());
+    }
+
+    #[test]
+    fn test_acquire_n_over_burst_goes_into_debt() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new_at(10, start);
+        // 50 at 10/s: allowed from a full bucket, then 4s of debt before the next token
+        assert!(limiter.try_acquire_n_at(50, start).is_ok());
+        let retry_after = limiter.try_acquire_at(start).unwrap_err();
+        assert!(retry_after >= Duration::from_secs(4));
+        assert!(limiter.try_acquire_at(start + Duration::from_millis(4200)).is_ok());
+    }
+}
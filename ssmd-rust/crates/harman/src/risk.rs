@@ -1,25 +1,64 @@
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
 
-use crate::error::RiskCheckError;
-use crate::types::OrderRequest;
+use crate::error::{RiskCheckError, TickerLimit};
+use crate::types::{Action, OrderRequest, Side};
 
 /// Risk limits configuration
 #[derive(Debug, Clone)]
 pub struct RiskLimits {
     /// Maximum total notional exposure in dollars
     pub max_notional: Decimal,
+    /// Maximum total unfilled contracts across open orders (None = no cap)
+    pub max_open_contracts: Option<Decimal>,
     /// Maximum notional for a single order (fat-finger protection)
     pub max_order_notional: Decimal,
     /// Maximum daily realized loss in dollars (positive number, e.g., 50 = -$50 threshold)
     pub daily_loss_limit: Decimal,
+    /// Maximum open notional on any single ticker in dollars (None = no cap)
+    pub max_ticker_notional: Option<Decimal>,
+    /// Maximum number of open orders on any single ticker (None = no cap)
+    pub max_open_orders_per_ticker: Option<u32>,
+    /// Worst-case buy price for market orders in dollars (e.g., 0.99). Market
+    /// orders are submitted as IOC limits at this price (sells at its
+    /// complement), and risk notional is computed from it.
+    pub market_order_worst_price: Decimal,
+    /// Minimum price increment in dollars (e.g., 0.01 for penny ticks, 0.001
+    /// for sub-cent markets). Limit and amend prices must be a multiple of it.
+    pub tick_size: Decimal,
+}
+
+impl RiskLimits {
+    /// IOC limit price used for a market order: crosses any resting quote up
+    /// to the configured worst case.
+    pub fn market_order_price(&self, action: Action) -> Decimal {
+        match action {
+            Action::Buy => self.market_order_worst_price,
+            Action::Sell => Decimal::ONE - self.market_order_worst_price,
+        }
+    }
+
+    /// Reject prices that are not a whole number of ticks.
+    pub fn check_tick(&self, price_dollars: Decimal) -> Result<(), String> {
+        if self.tick_size > Decimal::ZERO && !(price_dollars % self.tick_size).is_zero() {
+            return Err(format!("price must be a multiple of {}", self.tick_size));
+        }
+        Ok(())
+    }
 }
 
 impl Default for RiskLimits {
     fn default() -> Self {
         Self {
             max_notional: Decimal::new(100, 0),       // $100 default
+            max_open_contracts: None,
             max_order_notional: Decimal::new(25, 0),   // $25 default
             daily_loss_limit: Decimal::new(50, 0),     // $50 default
+            max_ticker_notional: None,
+            max_open_orders_per_ticker: None,
+            market_order_worst_price: Decimal::new(99, 2), // $0.99 default
+            tick_size: Decimal::new(1, 2),                 // $0.01 default
         }
     }
 }
@@ -46,17 +85,34 @@ impl RiskState {
         order: &OrderRequest,
         limits: &RiskLimits,
     ) -> Result<(), RiskCheckError> {
-        let requested = order.notional();
+        self.check_order_netted(order, limits, Decimal::ZERO)
+    }
+
+    /// Check a new order, netting it against an existing long position.
+    ///
+    /// `closeable` is the number of contracts held long on the order's ticker
+    /// and side that are not already committed to resting sells. A sell of up
+    /// to that many contracts reduces exposure, so only the remainder counts
+    /// toward aggregate notional. The fat-finger cap still applies to the
+    /// full order.
+    pub fn check_order_netted(
+        &self,
+        order: &OrderRequest,
+        limits: &RiskLimits,
+        closeable: Decimal,
+    ) -> Result<(), RiskCheckError> {
+        let order_notional = order.notional();
 
         // Fat-finger: single order notional cap
-        if requested > limits.max_order_notional {
+        if order_notional > limits.max_order_notional {
             return Err(RiskCheckError::MaxOrderNotionalExceeded {
-                order_notional: requested,
+                order_notional,
                 limit: limits.max_order_notional,
             });
         }
 
         // Aggregate: total open notional cap
+        let requested = opening_notional(order, closeable);
         let total = self.open_notional + requested;
         if total > limits.max_notional {
             return Err(RiskCheckError::MaxNotionalExceeded {
@@ -70,10 +126,228 @@ impl RiskState {
     }
 }
 
+/// Notional of `order` that opens new exposure.
+///
+/// Buys always open. A sell closes up to `closeable` contracts of an existing
+/// long; a partial close is charged only for the contracts beyond it.
+pub fn opening_notional(order: &OrderRequest, closeable: Decimal) -> Decimal {
+    order.price_dollars * opening_contracts(order, closeable)
+}
+
+/// Contracts of `order` that open new exposure, netted like [`opening_notional`]
+pub fn opening_contracts(order: &OrderRequest, closeable: Decimal) -> Decimal {
+    match order.action {
+        Action::Buy => order.quantity,
+        Action::Sell => order.quantity - order.quantity.min(closeable.max(Decimal::ZERO)),
+    }
+}
+
+/// A resting sell order considered for position netting
+#[derive(Debug, Clone)]
+pub struct RestingSell {
+    pub ticker: String,
+    pub side: Side,
+    /// Unfilled quantity
+    pub remaining: Decimal,
+    pub price_dollars: Decimal,
+}
+
+/// Long positions netted against resting sells.
+///
+/// Each long is allocated to the session's resting sells on the same ticker
+/// and side in enqueue order, mirroring how each sell was exempted when it
+/// was enqueued. Covered sell notional closes exposure rather than opening it.
+#[derive(Debug, Clone, Default)]
+pub struct PositionNetting {
+    /// Contracts per (ticker, side) still available to close after resting sells
+    closeable: HashMap<(String, Side), Decimal>,
+    /// Resting sell notional covered by a long, per ticker
+    closing_notional: HashMap<String, Decimal>,
+}
+
+impl PositionNetting {
+    /// `longs` is the filled net position per (ticker, side); `sells` must be
+    /// in enqueue order.
+    pub fn new(longs: HashMap<(String, Side), Decimal>, sells: &[RestingSell]) -> Self {
+        let mut closeable: HashMap<(String, Side), Decimal> = longs
+            .into_iter()
+            .filter(|(_, qty)| *qty > Decimal::ZERO)
+            .collect();
+        let mut closing_notional: HashMap<String, Decimal> = HashMap::new();
+
+        for sell in sells {
+            let Some(available) = closeable.get_mut(&(sell.ticker.clone(), sell.side)) else {
+                continue;
+            };
+            let covered = sell.remaining.min(*available);
+            *available -= covered;
+            *closing_notional.entry(sell.ticker.clone()).or_default() +=
+                covered * sell.price_dollars;
+        }
+
+        Self {
+            closeable,
+            closing_notional,
+        }
+    }
+
+    /// Contracts a new sell on `ticker`/`side` can close
+    pub fn closeable(&self, ticker: &str, side: Side) -> Decimal {
+        self.closeable
+            .get(&(ticker.to_string(), side))
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Contracts `order` closes, consumed so later orders in the same batch
+    /// can't close them again. Buys close nothing.
+    pub fn take_closeable(&mut self, order: &OrderRequest) -> Decimal {
+        if order.action != Action::Sell {
+            return Decimal::ZERO;
+        }
+        let Some(available) = self
+            .closeable
+            .get_mut(&(order.ticker.clone(), order.side))
+        else {
+            return Decimal::ZERO;
+        };
+        let closeable = *available;
+        *available -= order.quantity.min(closeable);
+        closeable
+    }
+
+    /// Resting sell notional on `ticker` that closes rather than opens
+    pub fn closing_notional(&self, ticker: &str) -> Decimal {
+        self.closing_notional
+            .get(ticker)
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Resting sell notional across all tickers that closes rather than opens
+    pub fn total_closing_notional(&self) -> Decimal {
+        self.closing_notional.values().copied().sum()
+    }
+}
+
+/// Open contract count for a session, computed from open orders
+#[derive(Debug, Clone, Default)]
+pub struct ContractRiskState {
+    /// Sum of unfilled quantity across open orders
+    pub open_contracts: Decimal,
+}
+
+impl ContractRiskState {
+    /// Check whether adding `requested` contracts passes `max_open_contracts`
+    pub fn check(&self, requested: Decimal, limits: &RiskLimits) -> Result<(), RiskCheckError> {
+        if let Some(limit) = limits.max_open_contracts {
+            if self.open_contracts + requested > limit {
+                return Err(RiskCheckError::MaxOpenContractsExceeded {
+                    current: self.open_contracts,
+                    requested,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Current risk state for a single ticker, computed from open orders
+#[derive(Debug, Clone, Default)]
+pub struct TickerRiskState {
+    /// Sum of notional for open orders on this ticker
+    pub open_notional: Decimal,
+    /// Number of open orders on this ticker
+    pub open_orders: u32,
+}
+
+impl TickerRiskState {
+    /// Check whether adding `new_orders` orders totalling `requested` notional
+    /// on `ticker` passes the per-ticker limits.
+    pub fn check(
+        &self,
+        ticker: &str,
+        requested: Decimal,
+        new_orders: u32,
+        limits: &RiskLimits,
+    ) -> Result<(), RiskCheckError> {
+        if let Some(limit) = limits.max_ticker_notional {
+            if self.open_notional + requested > limit {
+                return Err(RiskCheckError::TickerLimitExceeded {
+                    ticker: ticker.to_string(),
+                    limit: TickerLimit::Notional {
+                        current: self.open_notional,
+                        requested,
+                        limit,
+                    },
+                });
+            }
+        }
+
+        if let Some(limit) = limits.max_open_orders_per_ticker {
+            if self.open_orders.saturating_add(new_orders) > limit {
+                return Err(RiskCheckError::TickerLimitExceeded {
+                    ticker: ticker.to_string(),
+                    limit: TickerLimit::OpenOrders {
+                        current: self.open_orders,
+                        limit,
+                    },
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Signed quantity of `order`: positive for buys, negative for sells
+pub fn signed_quantity(order: &OrderRequest) -> Decimal {
+    match order.action {
+        Action::Buy => order.quantity,
+        Action::Sell => -order.quantity,
+    }
+}
+
+/// Check a reduce-only order against the net position on its ticker and side:
+/// filled buys minus sells, plus the signed remaining quantity of the
+/// session's other open reduce-only orders there (see
+/// `db::check_reduce_only_orders`).
+///
+/// The order must trade against the position and be no larger than it, so
+/// it can bring the position to flat but never open or flip it. Open
+/// reduce-only orders count as if filled, so two of them can't both close the
+/// same contracts. Other resting orders are not counted, and the check runs at
+/// enqueue and amend only; a fill that lands afterwards can still take the
+/// order past flat.
+pub fn check_reduce_only(order: &OrderRequest, net_position: Decimal) -> Result<(), RiskCheckError> {
+    check_reduce_only_quantity(&order.ticker, signed_quantity(order), net_position)
+}
+
+/// [`check_reduce_only`] for a signed quantity: positive buys, negative sells
+pub fn check_reduce_only_quantity(
+    ticker: &str,
+    requested: Decimal,
+    net_position: Decimal,
+) -> Result<(), RiskCheckError> {
+    let reduces = net_position != Decimal::ZERO
+        && requested.is_sign_positive() != net_position.is_sign_positive()
+        && requested.abs() <= net_position.abs();
+    if reduces {
+        Ok(())
+    } else {
+        Err(RiskCheckError::ReduceOnlyViolation {
+            ticker: ticker.to_string(),
+            net_position,
+            requested,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Action, OrderType, Side, TimeInForce};
+    use crate::types::{OrderType, Side, TimeInForce};
     use uuid::Uuid;
 
     fn make_order(quantity: Decimal, price_dollars: Decimal) -> OrderRequest {
@@ -96,6 +370,8 @@ mod tests {
             time_in_force: TimeInForce::Gtc,
             order_type: OrderType::default(),
             trigger_price: None,
+            good_till: None,
+            reduce_only: false,
         }
     }
 
@@ -140,6 +416,25 @@ mod tests {
     // Boundary / edge cases
     // ======================================================================
 
+    #[test]
+    fn test_check_tick_default_penny() {
+        let limits = RiskLimits::default();
+        assert!(limits.check_tick(Decimal::new(50, 2)).is_ok());
+        assert!(limits.check_tick(Decimal::new(500, 3)).is_ok()); // 0.500
+        let err = limits.check_tick(Decimal::new(505, 3)).unwrap_err();
+        assert_eq!(err, "price must be a multiple of 0.01");
+    }
+
+    #[test]
+    fn test_check_tick_sub_cent() {
+        let limits = RiskLimits {
+            tick_size: Decimal::new(1, 3), // $0.001
+            ..RiskLimits::default()
+        };
+        assert!(limits.check_tick(Decimal::new(505, 3)).is_ok());
+        assert!(limits.check_tick(Decimal::new(5055, 4)).is_err());
+    }
+
     #[test]
     fn test_minimum_notional_one_contract_one_cent() {
         // Smallest possible order: 1 contract at 1 cent = $0.01
@@ -383,6 +678,115 @@ mod tests {
         assert!(matches!(err, RiskCheckError::MaxNotionalExceeded { .. }));
     }
 
+    // ======================================================================
+    // Position netting
+    // ======================================================================
+
+    #[test]
+    fn test_sell_closing_long_is_exempt() {
+        let state = RiskState {
+            open_notional: Decimal::new(100, 0), // at limit
+        };
+        let limits = RiskLimits::default();
+        let order = make_order_with_side_action(Decimal::from(10), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert!(state.check_order(&order, &limits).is_err());
+        assert!(state.check_order_netted(&order, &limits, Decimal::from(10)).is_ok());
+    }
+
+    #[test]
+    fn test_partial_close_charges_remainder() {
+        let state = RiskState {
+            open_notional: Decimal::new(98, 0), // $98
+        };
+        let limits = RiskLimits::default();
+        // Sell 10 @ $0.50 with 6 closeable → 4 open = $2 → $100 passes
+        let order = make_order_with_side_action(Decimal::from(10), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert_eq!(opening_notional(&order, Decimal::from(6)), Decimal::new(200, 2));
+        assert!(state.check_order_netted(&order, &limits, Decimal::from(6)).is_ok());
+
+        // Only 5 closeable → $2.50 opens → $100.50 fails
+        let err = state.check_order_netted(&order, &limits, Decimal::from(5)).unwrap_err();
+        assert!(matches!(
+            err,
+            RiskCheckError::MaxNotionalExceeded { requested, .. } if requested == Decimal::new(250, 2)
+        ));
+    }
+
+    #[test]
+    fn test_buy_is_never_netted() {
+        let order = make_order(Decimal::from(10), Decimal::new(50, 2));
+        assert_eq!(opening_notional(&order, Decimal::from(10)), order.notional());
+    }
+
+    #[test]
+    fn test_netting_ignores_negative_closeable() {
+        // Resting sells exceed the long: nothing left to close
+        let order = make_order_with_side_action(Decimal::from(10), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert_eq!(opening_notional(&order, Decimal::from(-3)), order.notional());
+    }
+
+    #[test]
+    fn test_position_netting_allocates_in_enqueue_order() {
+        let longs = HashMap::from([
+            (("KXA".to_string(), Side::Yes), Decimal::from(10)),
+            (("KXB".to_string(), Side::No), Decimal::from(-5)), // short: nothing to close
+        ]);
+        let sells = vec![
+            RestingSell {
+                ticker: "KXA".to_string(),
+                side: Side::Yes,
+                remaining: Decimal::from(6),
+                price_dollars: Decimal::new(50, 2),
+            },
+            RestingSell {
+                ticker: "KXA".to_string(),
+                side: Side::Yes,
+                remaining: Decimal::from(6),
+                price_dollars: Decimal::new(40, 2),
+            },
+            // Other side of the same ticker is a separate position
+            RestingSell {
+                ticker: "KXA".to_string(),
+                side: Side::No,
+                remaining: Decimal::from(3),
+                price_dollars: Decimal::new(50, 2),
+            },
+        ];
+        let netting = PositionNetting::new(longs, &sells);
+
+        // 6 @ $0.50 + 4 @ $0.40 covered; the last 2 contracts open exposure
+        assert_eq!(netting.closing_notional("KXA"), Decimal::new(460, 2));
+        assert_eq!(netting.total_closing_notional(), Decimal::new(460, 2));
+        assert_eq!(netting.closeable("KXA", Side::Yes), Decimal::ZERO);
+        assert_eq!(netting.closeable("KXA", Side::No), Decimal::ZERO);
+        assert_eq!(netting.closeable("KXB", Side::No), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_take_closeable_consumes_position() {
+        let longs = HashMap::from([(("KXTEST-123".to_string(), Side::Yes), Decimal::from(10))]);
+        let mut netting = PositionNetting::new(longs, &[]);
+
+        let sell = make_order_with_side_action(Decimal::from(6), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        let buy = make_order_with_side_action(Decimal::from(6), Decimal::new(50, 2), Side::Yes, Action::Buy);
+
+        assert_eq!(netting.take_closeable(&buy), Decimal::ZERO);
+        assert_eq!(netting.take_closeable(&sell), Decimal::from(10));
+        // Only 4 left for the second sell: 2 of its 6 contracts open exposure
+        assert_eq!(netting.take_closeable(&sell), Decimal::from(4));
+        assert_eq!(opening_contracts(&sell, Decimal::from(4)), Decimal::from(2));
+        assert_eq!(netting.take_closeable(&sell), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_netting_keeps_fat_finger() {
+        let state = RiskState::default();
+        let limits = RiskLimits::default(); // $25 max_order_notional
+        let order = make_order_with_side_action(Decimal::from(60), Decimal::new(50, 2), Side::Yes, Action::Sell); // $30
+        let err = state.check_order_netted(&order, &limits, Decimal::from(60)).unwrap_err();
+        assert!(matches!(err, RiskCheckError::MaxOrderNotionalExceeded { .. }));
+    }
+
     // ======================================================================
     // Fat-finger (max order notional) checks
     // ======================================================================
@@ -425,4 +829,199 @@ mod tests {
         let err = state.check_order(&order, &limits).unwrap_err();
         assert!(matches!(err, RiskCheckError::MaxOrderNotionalExceeded { .. }));
     }
+
+    // ======================================================================
+    // Open contract limit
+    // ======================================================================
+
+    #[test]
+    fn test_open_contracts_unset_allows_anything() {
+        let state = ContractRiskState {
+            open_contracts: Decimal::from(1_000_000),
+        };
+        assert!(state.check(Decimal::from(1000), &RiskLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_open_contracts_limit() {
+        let limits = RiskLimits {
+            max_open_contracts: Some(Decimal::from(100)),
+            ..RiskLimits::default()
+        };
+        let state = ContractRiskState {
+            open_contracts: Decimal::from(90),
+        };
+        assert!(state.check(Decimal::from(10), &limits).is_ok());
+        let err = state.check(Decimal::from(11), &limits).unwrap_err();
+        assert!(matches!(
+            err,
+            RiskCheckError::MaxOpenContractsExceeded { requested, .. } if requested == Decimal::from(11)
+        ));
+    }
+
+    #[test]
+    fn test_opening_contracts_nets_sells() {
+        let buy = make_order(Decimal::from(10), Decimal::new(50, 2));
+        assert_eq!(opening_contracts(&buy, Decimal::from(10)), Decimal::from(10));
+
+        let sell = make_order_with_side_action(Decimal::from(10), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert_eq!(opening_contracts(&sell, Decimal::from(6)), Decimal::from(4));
+        assert_eq!(opening_contracts(&sell, Decimal::from(20)), Decimal::ZERO);
+        assert_eq!(opening_contracts(&sell, Decimal::from(-3)), Decimal::from(10));
+    }
+
+    // ======================================================================
+    // Per-ticker limits
+    // ======================================================================
+
+    fn ticker_limits() -> RiskLimits {
+        RiskLimits {
+            max_ticker_notional: Some(Decimal::new(20, 0)), // $20
+            max_open_orders_per_ticker: Some(3),
+            ..RiskLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_ticker_limits_unset_allow_anything() {
+        let state = TickerRiskState {
+            open_notional: Decimal::new(1000, 0),
+            open_orders: 1000,
+        };
+        assert!(state
+            .check("KXTEST-123", Decimal::new(1000, 0), 1, &RiskLimits::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_ticker_notional_at_limit() {
+        let state = TickerRiskState {
+            open_notional: Decimal::new(15, 0),
+            open_orders: 1,
+        };
+        assert!(state
+            .check("KXTEST-123", Decimal::new(5, 0), 1, &ticker_limits())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_ticker_notional_exceeded() {
+        let state = TickerRiskState {
+            open_notional: Decimal::new(15, 0),
+            open_orders: 1,
+        };
+        let err = state
+            .check("KXTEST-123", Decimal::new(6, 0), 1, &ticker_limits())
+            .unwrap_err();
+        match err {
+            RiskCheckError::TickerLimitExceeded { ticker, limit } => {
+                assert_eq!(ticker, "KXTEST-123");
+                assert!(matches!(limit, TickerLimit::Notional { .. }));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ticker_open_orders_exceeded() {
+        let state = TickerRiskState {
+            open_notional: Decimal::ZERO,
+            open_orders: 3,
+        };
+        let err = state
+            .check("KXTEST-123", Decimal::new(1, 0), 1, &ticker_limits())
+            .unwrap_err();
+        match err {
+            RiskCheckError::TickerLimitExceeded { ticker, limit } => {
+                assert_eq!(ticker, "KXTEST-123");
+                assert!(matches!(
+                    limit,
+                    TickerLimit::OpenOrders { current: 3, limit: 3 }
+                ));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ticker_open_orders_counts_new_orders() {
+        // Two existing + two new legs on the same ticker > 3
+        let state = TickerRiskState {
+            open_notional: Decimal::ZERO,
+            open_orders: 2,
+        };
+        assert!(state
+            .check("KXTEST-123", Decimal::new(1, 0), 1, &ticker_limits())
+            .is_ok());
+        assert!(state
+            .check("KXTEST-123", Decimal::new(1, 0), 2, &ticker_limits())
+            .is_err());
+    }
+
+    // ======================================================================
+    // Market orders
+    // ======================================================================
+
+    #[test]
+    fn test_market_order_price_buy_uses_worst_case() {
+        let limits = RiskLimits::default();
+        assert_eq!(limits.market_order_price(Action::Buy), Decimal::new(99, 2));
+    }
+
+    #[test]
+    fn test_market_order_price_sell_uses_complement() {
+        let limits = RiskLimits {
+            market_order_worst_price: Decimal::new(95, 2),
+            ..RiskLimits::default()
+        };
+        assert_eq!(limits.market_order_price(Action::Sell), Decimal::new(5, 2));
+    }
+
+    #[test]
+    fn test_market_order_notional_uses_worst_case() {
+        let limits = RiskLimits::default();
+        let mut order = make_order(Decimal::from(30), limits.market_order_price(Action::Buy));
+        order.order_type = OrderType::Market;
+        order.time_in_force = TimeInForce::Ioc;
+        // 30 × $0.99 = $29.70 > $25 fat-finger cap
+        let err = RiskState::default().check_order(&order, &limits).unwrap_err();
+        assert!(matches!(err, RiskCheckError::MaxOrderNotionalExceeded { .. }));
+    }
+
+    // ======================================================================
+    // Reduce-only
+    // ======================================================================
+
+    #[test]
+    fn test_reduce_only_sell_against_long() {
+        let sell = make_order_with_side_action(Decimal::from(5), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert!(check_reduce_only(&sell, Decimal::from(10)).is_ok());
+        // Closing the whole position is allowed, going past flat is not
+        assert!(check_reduce_only(&sell, Decimal::from(5)).is_ok());
+        let err = check_reduce_only(&sell, Decimal::from(4)).unwrap_err();
+        match err {
+            RiskCheckError::ReduceOnlyViolation {
+                net_position,
+                requested,
+                ..
+            } => {
+                assert_eq!(net_position, Decimal::from(4));
+                assert_eq!(requested, Decimal::from(-5));
+            }
+            other => panic!("expected ReduceOnlyViolation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reduce_only_rejects_increase_or_open() {
+        let buy = make_order_with_side_action(Decimal::from(1), Decimal::new(50, 2), Side::Yes, Action::Buy);
+        assert!(check_reduce_only(&buy, Decimal::from(10)).is_err());
+        assert!(check_reduce_only(&buy, Decimal::ZERO).is_err());
+        // Buying back a net short reduces it
+        assert!(check_reduce_only(&buy, Decimal::from(-3)).is_ok());
+
+        let sell = make_order_with_side_action(Decimal::from(1), Decimal::new(50, 2), Side::Yes, Action::Sell);
+        assert!(check_reduce_only(&sell, Decimal::from(-3)).is_err());
+        assert!(check_reduce_only(&sell, Decimal::ZERO).is_err());
+    }
 }
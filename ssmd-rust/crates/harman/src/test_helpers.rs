@@ -12,7 +12,7 @@ use rust_decimal::Decimal;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::error::ExchangeError;
+use crate::error::{ExchangeError, RejectCategory};
 use crate::exchange::ExchangeAdapter;
 use crate::types::{
     Action, AmendRequest, AmendResult, Balance, ExchangeFill, ExchangeOrder, ExchangeOrderState,
@@ -28,10 +28,16 @@ pub enum SubmitBehavior {
     AcceptWithId(String),
     /// Return Err(Rejected).
     Reject(String),
+    /// Return Err(Rejected) with a specific category.
+    RejectWith(RejectCategory, String),
     /// Return Err(Timeout).
     Timeout,
     /// Return Err(RateLimited).
     RateLimited(u64),
+    /// Return Err(ServerError) with the given HTTP status.
+    ServerError(u16),
+    /// Never respond (exercises the caller's own timeout).
+    Hang,
 }
 
 /// Configurable response for `cancel_order`.
@@ -92,6 +98,11 @@ pub struct MockExchangeState {
     next_id: u64,
     /// Log of submitted orders (for assertions).
     pub submitted_orders: Vec<OrderRequest>,
+    /// Log of submit_batch calls (number of orders in each).
+    pub batch_calls: Vec<usize>,
+    /// Place every order in a submit_batch call, then fail the call with
+    /// Err(Unexpected) as if the 2xx response body couldn't be decoded.
+    pub batch_response_undecodable: bool,
     /// Log of cancel calls (exchange_order_id).
     pub cancel_calls: Vec<String>,
     /// How many times cancel_all_orders was called.
@@ -126,6 +137,8 @@ impl Default for MockExchangeState {
             },
             next_id: 1,
             submitted_orders: Vec::new(),
+            batch_calls: Vec::new(),
+            batch_response_undecodable: false,
             cancel_calls: Vec::new(),
             cancel_all_calls: 0,
             amend_behavior: AmendBehavior::Accept,
@@ -151,6 +164,8 @@ impl MockExchangeState {
 /// the inner state to configure exchange responses mid-test.
 pub struct MockExchange {
     pub state: Arc<Mutex<MockExchangeState>>,
+    /// Value reported by `max_submit_batch` (1 = no batching).
+    pub submit_batch_size: usize,
 }
 
 impl Default for MockExchange {
@@ -161,13 +176,20 @@ impl Default for MockExchange {
 
 impl MockExchange {
     pub fn new() -> Self {
+        Self::with_state(Arc::new(Mutex::new(MockExchangeState::default())))
+    }
+
+    pub fn with_state(state: Arc<Mutex<MockExchangeState>>) -> Self {
         Self {
-            state: Arc::new(Mutex::new(MockExchangeState::default())),
+            state,
+            submit_batch_size: 1,
         }
     }
 
-    pub fn with_state(state: Arc<Mutex<MockExchangeState>>) -> Self {
-        Self { state }
+    /// Advertise batch submission of up to `size` orders per call.
+    pub fn with_submit_batch(mut self, size: usize) -> Self {
+        self.submit_batch_size = size;
+        self
     }
 }
 
@@ -189,14 +211,46 @@ impl ExchangeAdapter for MockExchange {
                 Ok(id)
             }
             SubmitBehavior::AcceptWithId(id) => Ok(id),
-            SubmitBehavior::Reject(reason) => Err(ExchangeError::Rejected { reason }),
+            SubmitBehavior::Reject(reason) => Err(ExchangeError::rejected(reason)),
+            SubmitBehavior::RejectWith(category, reason) => {
+                Err(ExchangeError::Rejected { category, reason })
+            }
             SubmitBehavior::Timeout => Err(ExchangeError::Timeout { timeout_ms: 5000 }),
             SubmitBehavior::RateLimited(ms) => {
                 Err(ExchangeError::RateLimited { retry_after_ms: ms })
             }
+            SubmitBehavior::ServerError(status) => Err(ExchangeError::ServerError { status }),
+            SubmitBehavior::Hang => {
+                drop(state);
+                std::future::pending().await
+            }
         }
     }
 
+    fn max_submit_batch(&self) -> usize {
+        self.submit_batch_size
+    }
+
+    /// Logs the batch size, then applies each order's submit behavior.
+    async fn submit_batch(
+        &self,
+        orders: &[OrderRequest],
+    ) -> Result<Vec<Result<String, ExchangeError>>, ExchangeError> {
+        let undecodable = {
+            let mut state = self.state.lock().await;
+            state.batch_calls.push(orders.len());
+            state.batch_response_undecodable
+        };
+        let mut results = Vec::with_capacity(orders.len());
+        for order in orders {
+            results.push(self.submit_order(order).await);
+        }
+        if undecodable {
+            return Err(ExchangeError::Unexpected("error decoding response body".into()));
+        }
+        Ok(results)
+    }
+
     async fn cancel_order(&self, exchange_order_id: &str) -> Result<(), ExchangeError> {
         let mut state = self.state.lock().await;
         state.cancel_calls.push(exchange_order_id.to_string());
@@ -275,9 +329,7 @@ impl ExchangeAdapter for MockExchange {
                 filled_quantity: Decimal::ZERO,
                 remaining_quantity: request.new_quantity.unwrap_or(Decimal::ZERO),
             }),
-            AmendBehavior::Reject(reason) => Err(ExchangeError::Rejected {
-                reason: reason.clone(),
-            }),
+            AmendBehavior::Reject(reason) => Err(ExchangeError::rejected(reason.clone())),
             AmendBehavior::NotFound => Err(ExchangeError::OrderNotFoundByExchangeId(request.exchange_order_id.clone())),
         }
     }
@@ -294,9 +346,7 @@ impl ExchangeAdapter for MockExchange {
 
         match &state.decrease_behavior {
             DecreaseBehavior::Accept => Ok(()),
-            DecreaseBehavior::Reject(reason) => Err(ExchangeError::Rejected {
-                reason: reason.clone(),
-            }),
+            DecreaseBehavior::Reject(reason) => Err(ExchangeError::rejected(reason.clone())),
             DecreaseBehavior::NotFound => Err(ExchangeError::OrderNotFoundByExchangeId(exchange_order_id.to_string())),
         }
     }
@@ -676,6 +726,7 @@ pub async fn clean_session_data(pool: &Pool, session_id: i64) -> Result<(), Stri
         "DELETE FROM exchange_audit_log WHERE session_id = $1".to_string(),
         "DELETE FROM prediction_orders WHERE session_id = $1".to_string(),
         "DELETE FROM order_groups WHERE session_id = $1".to_string(),
+        "DELETE FROM idempotency_keys WHERE session_id = $1".to_string(),
     ] {
         client.execute(stmt.as_str(), &[&session_id]).await
             .map_err(|e| format!("clean {}: {}", stmt.split_whitespace().nth(2).unwrap_or("?"), e))?;
@@ -6,7 +6,7 @@ use uuid::Uuid;
 use crate::state::OrderState;
 
 /// Side of an order
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     Yes,
@@ -156,6 +156,51 @@ impl std::fmt::Display for LegRole {
     }
 }
 
+/// How the legs of an OCO group track each other while both rest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkMode {
+    /// Legs are independent until one fills
+    #[default]
+    None,
+    /// Amending leg1's price reprices leg2 to keep the spread
+    Peg,
+}
+
+impl std::fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkMode::None => write!(f, "none"),
+            LinkMode::Peg => write!(f, "peg"),
+        }
+    }
+}
+
+/// Peg settings for an OCO group in `LinkMode::Peg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PegConfig {
+    /// leg2 price minus leg1 price, fixed at group creation
+    pub spread_dollars: Decimal,
+    /// Smallest drift from the pegged price that triggers a reprice of leg2
+    pub min_reprice_dollars: Decimal,
+}
+
+impl PegConfig {
+    /// Price leg2 should be amended to, if it has drifted at least
+    /// `min_reprice_dollars` from `leg1_price + spread_dollars` and that
+    /// price is still tradeable (strictly between 0 and 1).
+    pub fn reprice_target(&self, leg1_price: Decimal, leg2_price: Decimal) -> Option<Decimal> {
+        let target = leg1_price + self.spread_dollars;
+        if (target - leg2_price).abs() < self.min_reprice_dollars {
+            return None;
+        }
+        if target <= Decimal::ZERO || target >= Decimal::ONE {
+            return None;
+        }
+        Some(target)
+    }
+}
+
 /// An order group (bracket or OCO)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderGroup {
@@ -163,6 +208,11 @@ pub struct OrderGroup {
     pub session_id: i64,
     pub group_type: GroupType,
     pub state: GroupState,
+    #[serde(default)]
+    pub link_mode: LinkMode,
+    /// Set when `link_mode` is `Peg`
+    #[serde(default)]
+    pub peg: Option<PegConfig>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -209,6 +259,8 @@ pub enum CancelReason {
     Shutdown,
     Expired,
     ExchangeCancel,
+    /// Cancelled by a forced admin session close
+    SessionClosed,
 }
 
 impl std::fmt::Display for CancelReason {
@@ -219,6 +271,7 @@ impl std::fmt::Display for CancelReason {
             CancelReason::Shutdown => write!(f, "shutdown"),
             CancelReason::Expired => write!(f, "expired"),
             CancelReason::ExchangeCancel => write!(f, "exchange_cancel"),
+            CancelReason::SessionClosed => write!(f, "session_closed"),
         }
     }
 }
@@ -239,6 +292,14 @@ pub struct OrderRequest {
     pub order_type: OrderType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trigger_price: Option<Decimal>,
+    /// GTC orders still resting at this time are cancelled by the expiry sweeper
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub good_till: Option<DateTime<Utc>>,
+    /// Only allowed to shrink the session's filled net position on the
+    /// ticker and side; checked at enqueue and amend (see
+    /// [`crate::risk::check_reduce_only`])
+    #[serde(default)]
+    pub reduce_only: bool,
 }
 
 impl OrderRequest {
@@ -272,6 +333,15 @@ pub struct Order {
     pub trigger_price: Option<Decimal>,
     pub group_id: Option<i64>,
     pub leg_role: Option<LegRole>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub good_till: Option<DateTime<Utc>>,
+    /// Accepted as reduce-only; counts against the position later
+    /// reduce-only orders on the same ticker and side may close
+    #[serde(default)]
+    pub reduce_only: bool,
+    /// Exchange-provided reason when the order was rejected on submit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -469,6 +539,8 @@ mod tests {
             time_in_force: TimeInForce::Gtc,
             order_type: OrderType::default(),
             trigger_price: None,
+            good_till: None,
+            reduce_only: false,
         };
         // 10 contracts at $0.50 each = $5.00
         assert_eq!(req.notional(), Decimal::new(500, 2));
@@ -486,6 +558,8 @@ mod tests {
             time_in_force: TimeInForce::Gtc,
             order_type: OrderType::default(),
             trigger_price: None,
+            good_till: None,
+            reduce_only: false,
         };
         // 100 contracts at $0.99 each = $99.00
         assert_eq!(req.notional(), Decimal::new(9900, 2));
@@ -514,4 +588,32 @@ mod tests {
         assert_eq!(TimeInForce::Gtc.to_kalshi_str(), "good_till_canceled");
         assert_eq!(TimeInForce::Ioc.to_kalshi_str(), "immediate_or_cancel");
     }
+
+    #[test]
+    fn test_peg_reprice_target() {
+        let peg = PegConfig {
+            spread_dollars: Decimal::new(10, 2),     // leg2 = leg1 + $0.10
+            min_reprice_dollars: Decimal::new(2, 2), // $0.02
+        };
+        // In line with the spread: nothing to do
+        assert_eq!(
+            peg.reprice_target(Decimal::new(40, 2), Decimal::new(50, 2)),
+            None
+        );
+        // Drift below the minimum delta is ignored
+        assert_eq!(
+            peg.reprice_target(Decimal::new(41, 2), Decimal::new(50, 2)),
+            None
+        );
+        // Drift at the minimum delta reprices
+        assert_eq!(
+            peg.reprice_target(Decimal::new(42, 2), Decimal::new(50, 2)),
+            Some(Decimal::new(52, 2))
+        );
+        // Target outside (0, 1) is never produced
+        assert_eq!(
+            peg.reprice_target(Decimal::new(95, 2), Decimal::new(50, 2)),
+            None
+        );
+    }
 }
@@ -234,9 +234,44 @@ impl Environment {
     pub fn load(path: &Path) -> Result<Self, MetadataError> {
         let content = std::fs::read_to_string(path)?;
         let env: Environment = serde_yaml::from_str(&content)?;
+        env.validate()?;
         Ok(env)
     }
 
+    /// Check invariants serde can't express, reporting every violation.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        let mut violations = Vec::new();
+        if let Some(keys) = &self.keys {
+            let mut names: Vec<_> = keys.keys().collect();
+            names.sort();
+            for name in names {
+                let spec = &keys[name];
+                let has_source = spec.source.as_deref().is_some_and(|s| !s.trim().is_empty());
+                if spec.key_type == KeyType::ApiKey && !has_source {
+                    violations.push(format!("key {}: source is required for api_key keys", name));
+                }
+            }
+        }
+        if let Some(subscription) = &self.subscription {
+            if !(MIN_BATCH_SIZE..=MAX_BATCH_SIZE).contains(&subscription.batch_size) {
+                violations.push(format!(
+                    "subscription.batch_size {} outside {}..={}",
+                    subscription.batch_size, MIN_BATCH_SIZE, MAX_BATCH_SIZE
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(MetadataError::Invalid {
+                kind: "environment",
+                name: self.name.clone(),
+                violations,
+            })
+        }
+    }
+
     /// Get the schema name (before the colon)
     pub fn get_schema_name(&self) -> &str {
         self.schema.split(':').next().unwrap_or(&self.schema)
@@ -364,4 +399,52 @@ storage:
         assert_eq!(validated.batch_size, 200);
         assert!(!clamped);
     }
+
+    #[test]
+    fn test_validate_reports_all_violations() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"
+name: kalshi-broken
+feed: kalshi
+schema: trade:v1
+keys:
+  kalshi:
+    type: api_key
+    fields:
+      - api_key
+  nats:
+    type: transport
+    fields:
+      - token
+subscription:
+  batch_size: 1000
+transport:
+  type: memory
+storage:
+  type: local
+"#
+        )
+        .unwrap();
+
+        match Environment::load(file.path()).unwrap_err() {
+            MetadataError::Invalid {
+                kind,
+                name,
+                violations,
+            } => {
+                assert_eq!(kind, "environment");
+                assert_eq!(name, "kalshi-broken");
+                assert_eq!(
+                    violations,
+                    vec![
+                        "key kalshi: source is required for api_key keys".to_string(),
+                        format!("subscription.batch_size 1000 outside {}..={}", MIN_BATCH_SIZE, MAX_BATCH_SIZE),
+                    ]
+                );
+            }
+            other => panic!("expected Invalid, got {:?}", other),
+        }
+    }
 }
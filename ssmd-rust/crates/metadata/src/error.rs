@@ -8,4 +8,10 @@ pub enum MetadataError {
     Yaml(#[from] serde_yaml::Error),
     #[error("validation error: {0}")]
     Validation(String),
+    #[error("invalid {kind} '{name}': {}", .violations.join("; "))]
+    Invalid {
+        kind: &'static str,
+        name: String,
+        violations: Vec<String>,
+    },
 }
@@ -86,7 +86,9 @@ pub struct Calendar {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedVersion {
     pub version: String,
+    /// First day the version is active (YYYY-MM-DD, inclusive)
     pub effective_from: String,
+    /// Last day the version is active (YYYY-MM-DD, inclusive); open-ended when absent
     pub effective_to: Option<String>,
     pub protocol: Protocol,
     pub endpoint: String,
@@ -97,6 +99,41 @@ pub struct FeedVersion {
     pub supports_trades: Option<bool>,
     pub supports_historical: Option<bool>,
     pub parser_config: Option<HashMap<String, String>>,
+    /// Poll interval for REST feeds
+    pub poll_interval_secs: Option<u64>,
+    /// Channels to subscribe to (connector default set when absent)
+    pub channels: Option<Vec<String>>,
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+impl FeedVersion {
+    /// `effective_from` as a date, if it parses
+    pub fn valid_from(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.effective_from, DATE_FORMAT).ok()
+    }
+
+    /// `effective_to` as a date; `None` when open-ended or unparseable
+    pub fn valid_to(&self) -> Option<NaiveDate> {
+        self.effective_to
+            .as_deref()
+            .and_then(|to| NaiveDate::parse_from_str(to, DATE_FORMAT).ok())
+    }
+
+    /// Whether `date` falls in the version's inclusive date range.
+    /// A version whose dates don't parse is never active.
+    pub fn is_active_on(&self, date: NaiveDate) -> bool {
+        let Some(from) = self.valid_from() else {
+            return false;
+        };
+        if date < from {
+            return false;
+        }
+        match &self.effective_to {
+            None => true,
+            Some(_) => self.valid_to().is_some_and(|to| date <= to),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,29 +153,72 @@ impl Feed {
     pub fn load(path: &Path) -> Result<Self, MetadataError> {
         let content = std::fs::read_to_string(path)?;
         let feed: Feed = serde_yaml::from_str(&content)?;
+        feed.validate()?;
         Ok(feed)
     }
 
-    /// Get the version effective for a given date
-    pub fn get_version_for_date(&self, date: NaiveDate) -> Option<&FeedVersion> {
-        let date_str = date.format("%Y-%m-%d").to_string();
-
-        // Sort by effective_from descending
-        let mut versions: Vec<_> = self.versions.iter().collect();
-        versions.sort_by(|a, b| b.effective_from.cmp(&a.effective_from));
-
-        for v in versions {
-            if v.effective_from <= date_str {
-                if let Some(ref to) = v.effective_to {
-                    if to >= &date_str {
-                        return Some(v);
-                    }
-                } else {
-                    return Some(v);
+    /// Check invariants serde can't express, reporting every violation.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        let mut violations = Vec::new();
+        if self.versions.is_empty() {
+            violations.push("at least one version is required".to_string());
+        }
+        for v in &self.versions {
+            match v.valid_from() {
+                None => violations.push(format!(
+                    "version {}: effective_from {:?} is not a YYYY-MM-DD date",
+                    v.version, v.effective_from
+                )),
+                Some(from) => match (&v.effective_to, v.valid_to()) {
+                    (Some(to), None) => violations.push(format!(
+                        "version {}: effective_to {:?} is not a YYYY-MM-DD date",
+                        v.version, to
+                    )),
+                    (Some(_), Some(to)) if to < from => violations.push(format!(
+                        "version {}: effective_to is before effective_from",
+                        v.version
+                    )),
+                    _ => {}
+                },
+            }
+        }
+        if self.feed_type == FeedType::Websocket {
+            for v in &self.versions {
+                if v.endpoint.trim().is_empty() {
+                    violations.push(format!(
+                        "version {}: endpoint is required for websocket feeds",
+                        v.version
+                    ));
                 }
             }
         }
-        None
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(MetadataError::Invalid {
+                kind: "feed",
+                name: self.name.clone(),
+                violations,
+            })
+        }
+    }
+
+    /// Get the version active on `date`, for processing data from that day.
+    ///
+    /// Ranges are inclusive at both ends. If ranges overlap, the version with
+    /// the latest `effective_from` wins; a date in a gap between versions, or
+    /// before the first one, has no version.
+    pub fn version_for_date(&self, date: NaiveDate) -> Option<&FeedVersion> {
+        self.versions
+            .iter()
+            .filter(|v| v.is_active_on(date))
+            .max_by_key(|v| v.valid_from())
+    }
+
+    /// Get the version effective for a given date
+    pub fn get_version_for_date(&self, date: NaiveDate) -> Option<&FeedVersion> {
+        self.version_for_date(date)
     }
 
     /// Get the most recent version
@@ -208,6 +288,8 @@ versions:
                     supports_trades: None,
                     supports_historical: None,
                     parser_config: None,
+                    poll_interval_secs: None,
+                    channels: None,
                 },
                 FeedVersion {
                     version: "v2".to_string(),
@@ -226,6 +308,8 @@ versions:
                     supports_trades: None,
                     supports_historical: None,
                     parser_config: None,
+                    poll_interval_secs: None,
+                    channels: None,
                 },
             ],
             calendar: None,
@@ -237,4 +321,132 @@ versions:
         assert_eq!(feed.get_version_for_date(march).unwrap().version, "v1");
         assert_eq!(feed.get_version_for_date(aug).unwrap().version, "v2");
     }
+
+    #[test]
+    fn test_validate_reports_all_violations() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"
+name: broken
+type: websocket
+versions:
+  - version: v1
+    effective_from: "2025-01-01"
+    protocol:
+      transport: wss
+      message: json
+    endpoint: ""
+  - version: v2
+    effective_from: "2025-07-01"
+    protocol:
+      transport: wss
+      message: json
+    endpoint: " "
+"#
+        )
+        .unwrap();
+
+        match Feed::load(file.path()).unwrap_err() {
+            MetadataError::Invalid {
+                kind,
+                name,
+                violations,
+            } => {
+                assert_eq!(kind, "feed");
+                assert_eq!(name, "broken");
+                assert_eq!(violations.len(), 2);
+                assert!(violations[0].contains("v1"));
+            }
+            other => panic!("expected Invalid, got {:?}", other),
+        }
+
+        let feed = Feed {
+            name: "empty".to_string(),
+            display_name: None,
+            feed_type: FeedType::Rest,
+            status: None,
+            capture_locations: None,
+            versions: vec![],
+            calendar: None,
+        };
+        let err = feed.validate().unwrap_err();
+        assert!(err.to_string().contains("at least one version is required"));
+    }
+
+    fn version(name: &str, from: &str, to: Option<&str>) -> FeedVersion {
+        FeedVersion {
+            version: name.to_string(),
+            effective_from: from.to_string(),
+            effective_to: to.map(str::to_string),
+            protocol: Protocol {
+                transport: TransportProtocol::Wss,
+                message: MessageProtocol::Json,
+                version: None,
+            },
+            endpoint: format!("wss://{}", name),
+            auth_method: None,
+            rate_limit_per_second: None,
+            max_symbols_per_connection: None,
+            supports_orderbook: None,
+            supports_trades: None,
+            supports_historical: None,
+            parser_config: None,
+            poll_interval_secs: None,
+            channels: None,
+        }
+    }
+
+    fn feed_with(versions: Vec<FeedVersion>) -> Feed {
+        Feed {
+            name: "test".to_string(),
+            display_name: None,
+            feed_type: FeedType::Websocket,
+            status: None,
+            capture_locations: None,
+            versions,
+            calendar: None,
+        }
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_version_for_date_boundaries_and_gaps() {
+        // v1 ends in March, nothing in April, v2 from May; v3 overlaps v2 from September
+        let feed = feed_with(vec![
+            version("v2", "2025-05-01", None),
+            version("v1", "2025-01-01", Some("2025-03-31")),
+            version("v3", "2025-09-01", None),
+        ]);
+        let active = |date| feed.version_for_date(date).map(|v| v.version.as_str());
+
+        assert_eq!(active(ymd(2024, 12, 31)), None);
+        assert_eq!(active(ymd(2025, 1, 1)), Some("v1"));
+        assert_eq!(active(ymd(2025, 3, 31)), Some("v1"));
+        assert_eq!(active(ymd(2025, 4, 1)), None);
+        assert_eq!(active(ymd(2025, 4, 30)), None);
+        assert_eq!(active(ymd(2025, 5, 1)), Some("v2"));
+        assert_eq!(active(ymd(2025, 8, 31)), Some("v2"));
+        assert_eq!(active(ymd(2025, 9, 1)), Some("v3"));
+        assert_eq!(active(ymd(2030, 1, 1)), Some("v3"));
+    }
+
+    #[test]
+    fn test_unparseable_dates_are_never_active() {
+        let feed = feed_with(vec![
+            version("bad-from", "2025/01/01", None),
+            version("bad-to", "2025-01-01", Some("soon")),
+        ]);
+        assert!(feed.version_for_date(ymd(2025, 6, 1)).is_none());
+
+        let err = feed.validate().unwrap_err().to_string();
+        assert!(err.contains("bad-from: effective_from"));
+        assert!(err.contains("bad-to: effective_to"));
+
+        let reversed = feed_with(vec![version("v1", "2025-06-01", Some("2025-05-31"))]);
+        assert!(reversed.validate().is_err());
+    }
 }
@@ -1,10 +1,26 @@
 use async_trait::async_trait;
 use bytes::Bytes;
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::error::CacheError;
 
+/// Result of a `Cache::lookup`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheLookup {
+    /// Live value
+    Hit(Bytes),
+    /// Negative entry from `set_missing`: the backend is known not to have the key
+    Missing,
+    /// Nothing cached (never set, deleted, or expired)
+    Miss,
+}
+
 /// Cache abstraction for fast key-value lookups
+///
+/// Negative entries written by `set_missing` read as absent everywhere except
+/// `lookup`: `get`, `exists`, `scan` and friends skip them, and `set_nx`/`cas`
+/// treat them as no value.
 #[async_trait]
 pub trait Cache: Send + Sync {
     /// Get a value
@@ -13,6 +29,21 @@ pub trait Cache: Send + Sync {
     /// Set a value with optional TTL
     async fn set(&self, key: &str, value: Bytes, ttl: Option<Duration>) -> Result<(), CacheError>;
 
+    /// Set a value that expires after `ttl`
+    async fn set_with_ttl(&self, key: &str, value: Bytes, ttl: Duration) -> Result<(), CacheError> {
+        self.set(key, value, Some(ttl)).await
+    }
+
+    /// Record that `key` is absent from the backing store for `ttl`, so
+    /// repeated lookups don't all fall through to it.
+    ///
+    /// Redis-backed implementations should store a reserved sentinel value
+    /// with `SET ... PX` and map it to `CacheLookup::Missing`.
+    async fn set_missing(&self, key: &str, ttl: Duration) -> Result<(), CacheError>;
+
+    /// Get a value, distinguishing negative entries from plain misses
+    async fn lookup(&self, key: &str) -> Result<CacheLookup, CacheError>;
+
     /// Delete a key
     async fn delete(&self, key: &str) -> Result<(), CacheError>;
 
@@ -32,6 +63,48 @@ pub trait Cache: Send + Sync {
 
     /// Set multiple keys
     async fn mset(&self, pairs: &[(&str, Bytes)]) -> Result<(), CacheError>;
+
+    /// Get multiple keys, returning only the keys that are present
+    async fn get_many(&self, keys: &[&str]) -> Result<HashMap<String, Bytes>, CacheError>;
+
+    /// Set multiple keys, each with its own optional TTL
+    async fn put_many(&self, entries: &[(&str, Bytes, Option<Duration>)]) -> Result<(), CacheError>;
+
+    /// Compare-and-swap: set `key` to `new` only if its current value equals
+    /// `expected` (`None` = key must be absent or expired). Returns whether the
+    /// write happened.
+    ///
+    /// Redis-backed implementations must run the compare and the write in a
+    /// single Lua script (`GET` + `SET ... PX`) so no other writer can
+    /// interleave.
+    async fn cas(
+        &self,
+        key: &str,
+        expected: Option<&[u8]>,
+        new: Bytes,
+        ttl: Option<Duration>,
+    ) -> Result<bool, CacheError>;
+
+    /// List live keys starting with `prefix`, in no particular order.
+    ///
+    /// Redis-backed implementations must use incremental `SCAN ... MATCH`,
+    /// never `KEYS`, so large keyspaces don't block the server.
+    async fn scan(&self, prefix: &str) -> Result<Vec<String>, CacheError>;
+
+    /// Check that the backend is reachable. In-process backends are always
+    /// healthy; Redis-backed implementations should `PING`.
+    async fn health_check(&self) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Delete every key starting with `prefix`, returning how many were removed
+    async fn delete_prefix(&self, prefix: &str) -> Result<u64, CacheError> {
+        let keys = self.scan(prefix).await?;
+        for key in &keys {
+            self.delete(key).await?;
+        }
+        Ok(keys.len() as u64)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use crate::error::JournalError;
+
+/// Durable store for journal consumer checkpoints.
+///
+/// A checkpoint is the sequence of the last entry a consumer finished
+/// processing. On restart the consumer resumes at the entry after it.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Load the last committed sequence for a consumer (None if never committed)
+    async fn load(&self, consumer_id: &str) -> Result<Option<u64>, JournalError>;
+
+    /// Persist the last processed sequence for a consumer
+    async fn save(&self, consumer_id: &str, sequence: u64) -> Result<(), JournalError>;
+}
+
+/// File-backed checkpoint store: one file per consumer under `dir`.
+///
+/// Writes go to a temp file followed by a rename so a crash never leaves a
+/// half-written checkpoint behind.
+pub struct FsCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FsCheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path_for(&self, consumer_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.checkpoint", consumer_id))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FsCheckpointStore {
+    async fn load(&self, consumer_id: &str) -> Result<Option<u64>, JournalError> {
+        let path = self.path_for(consumer_id);
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(JournalError::CheckpointFailed(e.to_string())),
+        };
+        contents
+            .trim()
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|e| {
+                JournalError::CheckpointFailed(format!(
+                    "invalid checkpoint in {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    async fn save(&self, consumer_id: &str, sequence: u64) -> Result<(), JournalError> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| JournalError::CheckpointFailed(e.to_string()))?;
+
+        let path = self.path_for(consumer_id);
+        let tmp = path.with_extension("checkpoint.tmp");
+        tokio::fs::write(&tmp, sequence.to_string())
+            .await
+            .map_err(|e| JournalError::CheckpointFailed(e.to_string()))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| JournalError::CheckpointFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fs_checkpoint_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsCheckpointStore::new(dir.path());
+        store.save("archiver", 42).await.unwrap();
+        assert_eq!(store.load("archiver").await.unwrap(), Some(42));
+
+        store.save("archiver", 43).await.unwrap();
+        let reopened = FsCheckpointStore::new(dir.path());
+        assert_eq!(reopened.load("archiver").await.unwrap(), Some(43));
+    }
+
+    #[tokio::test]
+    async fn test_fs_checkpoint_missing_consumer() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsCheckpointStore::new(dir.path().join("nested"));
+        assert_eq!(store.load("unknown").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! Object compression codecs used by `Storage` implementations
+
+use std::io::{Read, Write};
+
+use bytes::Bytes;
+
+use crate::error::StorageError;
+
+/// zstd level: the library default, a good speed/ratio balance for JSON
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to a stored object
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Compress `data` with this codec
+    pub fn compress(self, data: &[u8]) -> Result<Bytes, StorageError> {
+        match self {
+            Compression::None => Ok(Bytes::copy_from_slice(data)),
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                Ok(Bytes::from(encoder.finish()?))
+            }
+            Compression::Zstd => Ok(Bytes::from(zstd::stream::encode_all(data, ZSTD_LEVEL)?)),
+        }
+    }
+
+    /// Decompress `data` that was compressed with this codec
+    pub fn decompress(self, data: &[u8]) -> Result<Bytes, StorageError> {
+        match self {
+            Compression::None => Ok(Bytes::copy_from_slice(data)),
+            Compression::Gzip => {
+                let mut out = Vec::new();
+                flate2::read::GzDecoder::new(data)
+                    .read_to_end(&mut out)
+                    .map_err(|e| StorageError::ReadFailed(format!("gzip: {}", e)))?;
+                Ok(Bytes::from(out))
+            }
+            Compression::Zstd => zstd::stream::decode_all(data)
+                .map(Bytes::from)
+                .map_err(|e| StorageError::ReadFailed(format!("zstd: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_all_codecs() {
+        let data = br#"{"ticker":"KXBTC","price":55}"#.repeat(100);
+        for codec in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let compressed = codec.compress(&data).unwrap();
+            if codec != Compression::None {
+                assert!(compressed.len() < data.len(), "{:?} should shrink repetitive JSON", codec);
+            }
+            assert_eq!(codec.decompress(&compressed).unwrap().as_ref(), data.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_decompress_garbage_fails() {
+        assert!(matches!(
+            Compression::Gzip.decompress(b"not gzip"),
+            Err(StorageError::ReadFailed(_))
+        ));
+        assert!(matches!(
+            Compression::Zstd.decompress(b"not zstd"),
+            Err(StorageError::ReadFailed(_))
+        ));
+    }
+}
@@ -24,6 +24,12 @@ pub enum StorageError {
     WriteFailed(String),
     #[error("read failed: {0}")]
     ReadFailed(String),
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
+    #[error("already exists: {0}")]
+    AlreadyExists(String),
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -44,4 +50,6 @@ pub enum JournalError {
     ReadFailed(String),
     #[error("topic not found: {0}")]
     TopicNotFound(String),
+    #[error("checkpoint failed: {0}")]
+    CheckpointFailed(String),
 }
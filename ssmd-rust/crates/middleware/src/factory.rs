@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
+use serde::Deserialize;
 use ssmd_metadata::{CacheType, Environment, StorageType, TransportType};
 
 use crate::cache::Cache;
+use crate::fs::FsStorage;
 use crate::journal::Journal;
 use crate::memory::{InMemoryCache, InMemoryJournal, InMemoryStorage, InMemoryTransport};
 use crate::nats::NatsTransport;
@@ -12,14 +14,93 @@ use crate::transport::Transport;
 /// Error creating middleware
 #[derive(Debug, thiserror::Error)]
 pub enum FactoryError {
-    #[error("unsupported transport type: {0:?}")]
+    #[error("unsupported transport type: {0:?} (supported: memory, nats)")]
     UnsupportedTransport(TransportType),
-    #[error("unsupported storage type: {0:?}")]
+    #[error("unsupported storage type: {0:?} (supported: local)")]
     UnsupportedStorage(StorageType),
-    #[error("unsupported cache type: {0:?}")]
+    #[error("unsupported cache type: {0:?} (supported: memory)")]
     UnsupportedCache(CacheType),
     #[error("configuration error: {0}")]
     ConfigError(String),
+    #[error("unknown {component} backend {backend:?} (expected one of: {expected})")]
+    UnknownBackend {
+        component: &'static str,
+        backend: String,
+        expected: &'static str,
+    },
+    #[error("failed to create {component}: {source}")]
+    Component {
+        component: &'static str,
+        #[source]
+        source: Box<FactoryError>,
+    },
+}
+
+impl FactoryError {
+    fn in_component(self, component: &'static str) -> Self {
+        match self {
+            // Already names its component
+            FactoryError::UnknownBackend { .. } | FactoryError::Component { .. } => self,
+            other => FactoryError::Component {
+                component,
+                source: Box::new(other),
+            },
+        }
+    }
+}
+
+/// Backend selection for a single middleware component
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackendConfig {
+    /// Backend name, e.g. "memory", "nats", "fs", "redis"
+    pub backend: String,
+    /// Connection URL (nats, redis; comma-separated bootstrap servers for kafka)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Filesystem root (fs) or key namespace (redis storage)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl BackendConfig {
+    pub fn memory() -> Self {
+        Self {
+            backend: "memory".to_string(),
+            url: None,
+            path: None,
+        }
+    }
+}
+
+/// Complete middleware stack configuration, one backend per component
+#[derive(Debug, Clone, Deserialize)]
+pub struct MiddlewareConfig {
+    pub transport: BackendConfig,
+    pub storage: BackendConfig,
+    pub cache: BackendConfig,
+    pub journal: BackendConfig,
+}
+
+/// A fully wired middleware stack
+#[derive(Clone)]
+pub struct MiddlewareStack {
+    pub transport: Arc<dyn Transport>,
+    pub storage: Arc<dyn Storage>,
+    pub cache: Arc<dyn Cache>,
+    pub journal: Arc<dyn Journal>,
+}
+
+/// Aggregate health of a middleware stack
+#[derive(Debug, Default)]
+pub struct HealthReport {
+    /// Failing components and their errors; empty when all are healthy
+    pub unhealthy: Vec<(&'static str, String)>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.unhealthy.is_empty()
+    }
 }
 
 /// Factory for creating middleware instances based on environment config
@@ -92,11 +173,11 @@ impl MiddlewareFactory {
     /// Create a storage based on environment configuration
     pub fn create_storage(env: &Environment) -> Result<Arc<dyn Storage>, FactoryError> {
         match env.storage.storage_type {
-            StorageType::Local => {
-                // Local storage maps to in-memory for now
-                // Real LocalStorage (file-based) will come later
-                Ok(Arc::new(InMemoryStorage::new()))
-            }
+            StorageType::Local => match &env.storage.path {
+                Some(path) => Ok(Arc::new(FsStorage::new(path))),
+                // No path configured: keep objects in memory
+                None => Ok(Arc::new(InMemoryStorage::new())),
+            },
             StorageType::S3 => {
                 Err(FactoryError::UnsupportedStorage(StorageType::S3))
             }
@@ -123,6 +204,153 @@ impl MiddlewareFactory {
     pub fn create_journal() -> Arc<dyn Journal> {
         Arc::new(InMemoryJournal::new())
     }
+
+    /// Build transport, storage, cache and journal from an environment file.
+    ///
+    /// Uses the `create_*` mappings; the journal is always in-memory. Errors
+    /// name the component that failed.
+    pub async fn from_environment(env: &Environment) -> Result<MiddlewareStack, FactoryError> {
+        let storage = Self::create_storage(env).map_err(|e| e.in_component("storage"))?;
+        let cache = Self::create_cache(env).map_err(|e| e.in_component("cache"))?;
+        let journal = Self::create_journal();
+        // Transport last: it opens a connection
+        let transport = Self::create_transport(env)
+            .await
+            .map_err(|e| e.in_component("transport"))?;
+
+        Ok(MiddlewareStack {
+            transport,
+            storage,
+            cache,
+            journal,
+        })
+    }
+
+    /// Build transport, storage, cache and journal from a single config.
+    ///
+    /// Errors name the component that failed.
+    pub async fn from_config(config: &MiddlewareConfig) -> Result<MiddlewareStack, FactoryError> {
+        let storage = Self::storage_from_config(&config.storage)
+            .await
+            .map_err(|e| e.in_component("storage"))?;
+        let cache = Self::cache_from_config(&config.cache)
+            .map_err(|e| e.in_component("cache"))?;
+        let journal = Self::journal_from_config(&config.journal)
+            .map_err(|e| e.in_component("journal"))?;
+        // Transport last: it opens a connection (as does redis storage)
+        let transport = Self::transport_from_config(&config.transport)
+            .await
+            .map_err(|e| e.in_component("transport"))?;
+
+        Ok(MiddlewareStack {
+            transport,
+            storage,
+            cache,
+            journal,
+        })
+    }
+
+    /// Run every component's health check, collecting all failures.
+    pub async fn health_check(stack: &MiddlewareStack) -> HealthReport {
+        let (transport, storage, cache, journal) = tokio::join!(
+            stack.transport.health_check(),
+            stack.storage.health_check(),
+            stack.cache.health_check(),
+            stack.journal.health_check(),
+        );
+
+        let mut report = HealthReport::default();
+        if let Err(e) = transport {
+            report.unhealthy.push(("transport", e.to_string()));
+        }
+        if let Err(e) = storage {
+            report.unhealthy.push(("storage", e.to_string()));
+        }
+        if let Err(e) = cache {
+            report.unhealthy.push(("cache", e.to_string()));
+        }
+        if let Err(e) = journal {
+            report.unhealthy.push(("journal", e.to_string()));
+        }
+        report
+    }
+
+    async fn transport_from_config(config: &BackendConfig) -> Result<Arc<dyn Transport>, FactoryError> {
+        match config.backend.as_str() {
+            "memory" => Ok(Arc::new(InMemoryTransport::new())),
+            "nats" => {
+                let url = config.url.as_ref()
+                    .ok_or_else(|| FactoryError::ConfigError("NATS URL required".to_string()))?;
+                let transport = NatsTransport::connect(url)
+                    .await
+                    .map_err(|e| FactoryError::ConfigError(e.to_string()))?;
+                Ok(Arc::new(transport))
+            }
+            #[cfg(feature = "kafka")]
+            "kafka" => {
+                let brokers = config.url.as_ref()
+                    .ok_or_else(|| FactoryError::ConfigError("Kafka bootstrap servers required".to_string()))?;
+                let transport = crate::kafka::KafkaTransport::connect(brokers)
+                    .map_err(|e| FactoryError::ConfigError(e.to_string()))?;
+                Ok(Arc::new(transport))
+            }
+            other => Err(FactoryError::UnknownBackend {
+                component: "transport",
+                backend: other.to_string(),
+                expected: if cfg!(feature = "kafka") { "memory, nats, kafka" } else { "memory, nats" },
+            }),
+        }
+    }
+
+    async fn storage_from_config(config: &BackendConfig) -> Result<Arc<dyn Storage>, FactoryError> {
+        match config.backend.as_str() {
+            "memory" => Ok(Arc::new(InMemoryStorage::new())),
+            "fs" => {
+                let path = config.path.as_ref()
+                    .ok_or_else(|| FactoryError::ConfigError("fs storage path required".to_string()))?;
+                Ok(Arc::new(FsStorage::new(path)))
+            }
+            #[cfg(feature = "redis-storage")]
+            "redis" => {
+                let url = config.url.as_ref()
+                    .ok_or_else(|| FactoryError::ConfigError("Redis URL required".to_string()))?;
+                let namespace = config.path.as_deref()
+                    .unwrap_or(crate::redis_storage::DEFAULT_NAMESPACE);
+                let storage = crate::redis_storage::RedisStorage::connect(url, namespace)
+                    .await
+                    .map_err(|e| FactoryError::ConfigError(e.to_string()))?;
+                Ok(Arc::new(storage))
+            }
+            other => Err(FactoryError::UnknownBackend {
+                component: "storage",
+                backend: other.to_string(),
+                expected: if cfg!(feature = "redis-storage") { "memory, fs, redis" } else { "memory, fs" },
+            }),
+        }
+    }
+
+    fn cache_from_config(config: &BackendConfig) -> Result<Arc<dyn Cache>, FactoryError> {
+        match config.backend.as_str() {
+            "memory" => Ok(Arc::new(InMemoryCache::new())),
+            "redis" => Err(FactoryError::UnsupportedCache(CacheType::Redis)),
+            other => Err(FactoryError::UnknownBackend {
+                component: "cache",
+                backend: other.to_string(),
+                expected: "memory, redis",
+            }),
+        }
+    }
+
+    fn journal_from_config(config: &BackendConfig) -> Result<Arc<dyn Journal>, FactoryError> {
+        match config.backend.as_str() {
+            "memory" => Ok(Self::create_journal()),
+            other => Err(FactoryError::UnknownBackend {
+                component: "journal",
+                backend: other.to_string(),
+                expected: "memory",
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -203,4 +431,153 @@ mod tests {
         let transport = MiddlewareFactory::create_transport(&env).await.unwrap();
         drop(transport);
     }
+
+    fn load_env(yaml: &str) -> Environment {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "name: test\nfeed: kalshi\nschema: trade:v1\n{}",
+            yaml
+        )
+        .unwrap();
+        Environment::load(file.path()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_from_environment_supported_combinations() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path().to_string_lossy();
+        let samples = [
+            // Local storage with a path is on disk, without one in memory;
+            // cache defaults to memory when omitted
+            format!("transport:\n  type: memory\nstorage:\n  type: local\n  path: {}\n", data),
+            "transport:\n  type: memory\nstorage:\n  type: local\n".to_string(),
+            format!(
+                "transport:\n  type: memory\nstorage:\n  type: local\n  path: {}\ncache:\n  type: memory\n",
+                data
+            ),
+        ];
+
+        for yaml in &samples {
+            let env = load_env(yaml);
+            let stack = MiddlewareFactory::from_environment(&env).await.unwrap();
+            stack.storage.put("bucket", "key", bytes::Bytes::from("data")).await.unwrap();
+            assert!(stack.storage.exists("bucket", "key").await.unwrap(), "{}", yaml);
+            stack.cache.set("k", bytes::Bytes::from("v"), None).await.unwrap();
+            assert!(stack.cache.exists("k").await.unwrap(), "{}", yaml);
+            assert!(MiddlewareFactory::health_check(&stack).await.is_healthy(), "{}", yaml);
+        }
+        // The on-disk sample wrote through to the directory
+        assert!(dir.path().join("bucket").exists());
+    }
+
+    #[tokio::test]
+    async fn test_from_environment_unsupported_types() {
+        let env = load_env("transport:\n  type: mqtt\nstorage:\n  type: local\n");
+        let err = MiddlewareFactory::from_environment(&env).await.err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "failed to create transport: unsupported transport type: Mqtt (supported: memory, nats)"
+        );
+
+        let env = load_env("transport:\n  type: memory\nstorage:\n  type: s3\n  bucket: b\n");
+        let err = MiddlewareFactory::from_environment(&env).await.err().unwrap();
+        assert!(matches!(err, FactoryError::Component { component: "storage", .. }));
+
+        let env = load_env(
+            "transport:\n  type: memory\nstorage:\n  type: local\ncache:\n  type: redis\n",
+        );
+        let err = MiddlewareFactory::from_environment(&env).await.err().unwrap();
+        assert!(matches!(err, FactoryError::Component { component: "cache", .. }));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NATS server
+    async fn test_from_environment_nats() {
+        let env = load_env(
+            "transport:\n  type: nats\n  url: nats://localhost:4222\nstorage:\n  type: local\n",
+        );
+        let stack = MiddlewareFactory::from_environment(&env).await.unwrap();
+        assert!(MiddlewareFactory::health_check(&stack).await.is_healthy());
+    }
+
+    fn memory_config() -> MiddlewareConfig {
+        MiddlewareConfig {
+            transport: BackendConfig::memory(),
+            storage: BackendConfig::memory(),
+            cache: BackendConfig::memory(),
+            journal: BackendConfig::memory(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_config_all_memory() {
+        let config: MiddlewareConfig = serde_json::from_str(
+            r#"{
+                "transport": {"backend": "memory"},
+                "storage": {"backend": "memory"},
+                "cache": {"backend": "memory"},
+                "journal": {"backend": "memory"}
+            }"#,
+        )
+        .unwrap();
+
+        let stack = MiddlewareFactory::from_config(&config).await.unwrap();
+        stack.cache.set("k", bytes::Bytes::from("v"), None).await.unwrap();
+        assert!(stack.cache.exists("k").await.unwrap());
+        stack.storage.put("bucket", "key", bytes::Bytes::from("data")).await.unwrap();
+        assert!(stack.storage.exists("bucket", "key").await.unwrap());
+        drop(stack.transport);
+        drop(stack.journal);
+    }
+
+    #[tokio::test]
+    async fn test_from_config_fs_storage_requires_path() {
+        let mut config = memory_config();
+        config.storage.backend = "fs".to_string();
+
+        let err = MiddlewareFactory::from_config(&config).await.err().unwrap();
+        assert!(matches!(err, FactoryError::Component { component: "storage", .. }));
+    }
+
+    #[tokio::test]
+    async fn test_from_config_invalid_backend() {
+        let mut config = memory_config();
+        config.cache.backend = "memcached".to_string();
+
+        let err = MiddlewareFactory::from_config(&config).await.err().unwrap();
+        assert!(matches!(err, FactoryError::UnknownBackend { component: "cache", .. }));
+        assert_eq!(
+            err.to_string(),
+            "unknown cache backend \"memcached\" (expected one of: memory, redis)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_check_all_memory() {
+        let stack = MiddlewareFactory::from_config(&memory_config()).await.unwrap();
+        let report = MiddlewareFactory::health_check(&stack).await;
+        assert!(report.is_healthy(), "{:?}", report);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_broken_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+        std::fs::write(&file, b"x").unwrap();
+
+        let mut config = memory_config();
+        config.storage = BackendConfig {
+            backend: "fs".to_string(),
+            url: None,
+            path: Some(file.to_string_lossy().into_owned()),
+        };
+        let stack = MiddlewareFactory::from_config(&config).await.unwrap();
+
+        let report = MiddlewareFactory::health_check(&stack).await;
+        assert!(!report.is_healthy());
+        assert_eq!(report.unhealthy.len(), 1);
+        assert_eq!(report.unhealthy[0].0, "storage");
+    }
 }
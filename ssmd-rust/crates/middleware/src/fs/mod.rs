@@ -0,0 +1,4 @@
+//! Local filesystem implementations
+pub mod storage;
+
+pub use storage::FsStorage;
@@ -0,0 +1,548 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use crate::compression::Compression;
+use crate::error::StorageError;
+use crate::storage::{paginate, ListPage, ObjectMeta, Storage};
+
+/// Prefix for in-flight temp files; excluded from listings.
+const TMP_PREFIX: &str = ".ssmd-tmp-";
+
+/// Chunk size for streaming writes.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// File-backed storage: objects live at `{root}/{bucket}/{key}`.
+///
+/// Writes go to a temp file in the destination directory and are renamed
+/// into place, so readers never observe a partially written object. Etags are
+/// the MD5 of the content, like the memory and Redis backends.
+/// `put_if_not_exists` hard-links the temp file into place, which fails
+/// atomically if the key exists (safe across processes). `put_if_match`
+/// checks the etag, renames, then re-reads the etag to detect a concurrent
+/// writer in another process; the in-process lock only avoids that race
+/// between writers sharing this `FsStorage`.
+pub struct FsStorage {
+    root: PathBuf,
+    tmp_counter: AtomicU64,
+    /// Serializes check-then-write in `put_if_match` within this process.
+    conditional_lock: Mutex<()>,
+}
+
+impl FsStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            tmp_counter: AtomicU64::new(0),
+            conditional_lock: Mutex::new(()),
+        }
+    }
+
+    fn bucket_dir(&self, bucket: &str) -> PathBuf {
+        self.root.join(bucket)
+    }
+
+    fn object_path(&self, bucket: &str, key: &str) -> Result<PathBuf, StorageError> {
+        let rel = Path::new(key);
+        let valid = !key.is_empty()
+            && rel
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !valid {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        Ok(self.bucket_dir(bucket).join(rel))
+    }
+
+    /// Temp path next to `path` so the final rename stays on one filesystem.
+    fn tmp_path(&self, path: &Path) -> PathBuf {
+        let n = self.tmp_counter.fetch_add(1, Ordering::Relaxed);
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        path.with_file_name(format!("{}{}-{}-{}", TMP_PREFIX, std::process::id(), n, name))
+    }
+
+    async fn stat(key: &str, path: &Path) -> Result<std::fs::Metadata, StorageError> {
+        match tokio::fs::metadata(path).await {
+            Ok(md) if md.is_file() => Ok(md),
+            Ok(_) => Err(StorageError::NotFound(key.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(key.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// MD5 of the file's content, read in chunks.
+    async fn file_etag(key: &str, path: &Path) -> Result<String, StorageError> {
+        let mut file = match tokio::fs::File::open(path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(key.to_string()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut ctx = md5::Context::new();
+        let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+        loop {
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            ctx.consume(&buf[..n]);
+        }
+        Ok(format!("{:x}", ctx.finalize()))
+    }
+
+    fn meta_from(key: &str, md: &std::fs::Metadata, etag: String) -> ObjectMeta {
+        let modified = md
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        ObjectMeta {
+            key: key.to_string(),
+            size: md.len(),
+            stored_size: md.len(),
+            compression: Compression::None,
+            last_modified: modified.as_millis() as u64,
+            etag: Some(etag),
+            content_type: None,
+        }
+    }
+
+    async fn meta_for(key: &str, path: &Path) -> Result<ObjectMeta, StorageError> {
+        let md = Self::stat(key, path).await?;
+        let etag = Self::file_etag(key, path).await?;
+        Ok(Self::meta_from(key, &md, etag))
+    }
+
+    /// Write `reader` to a temp file next to `path`, returning the temp path
+    /// and the content's etag.
+    async fn write_tmp(
+        &self,
+        path: &Path,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<(PathBuf, String), StorageError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = self.tmp_path(path);
+        let result = async {
+            let mut file = tokio::fs::File::create(&tmp).await?;
+            let mut ctx = md5::Context::new();
+            let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+            loop {
+                let n = reader.read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                ctx.consume(&buf[..n]);
+                file.write_all(&buf[..n]).await?;
+            }
+            file.sync_all().await?;
+            Ok::<_, std::io::Error>(format!("{:x}", ctx.finalize()))
+        }
+        .await;
+        match result {
+            Ok(etag) => Ok((tmp, etag)),
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Rename a temp file from `write_tmp` over `path`.
+    async fn commit_tmp(tmp: &Path, path: &Path) -> Result<(), StorageError> {
+        if let Err(e) = tokio::fs::rename(tmp, path).await {
+            let _ = tokio::fs::remove_file(tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Storage for FsStorage {
+    async fn put(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError> {
+        let len = data.len() as u64;
+        let mut reader: &[u8] = &data;
+        self.put_stream(bucket, key, &mut reader, Some(len)).await
+    }
+
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        _size_hint: Option<u64>,
+    ) -> Result<ObjectMeta, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        let (tmp, etag) = self.write_tmp(&path, reader).await?;
+        Self::commit_tmp(&tmp, &path).await?;
+        let md = Self::stat(key, &path).await?;
+        Ok(Self::meta_from(key, &md, etag))
+    }
+
+    async fn put_if_not_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+    ) -> Result<ObjectMeta, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        let mut reader: &[u8] = &data;
+        let (tmp, etag) = self.write_tmp(&path, &mut reader).await?;
+        let linked = tokio::fs::hard_link(&tmp, &path).await;
+        let _ = tokio::fs::remove_file(&tmp).await;
+        match linked {
+            Ok(()) => {
+                let md = Self::stat(key, &path).await?;
+                Ok(Self::meta_from(key, &md, etag))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                Err(StorageError::AlreadyExists(format!("{}/{}", bucket, key)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn put_if_match(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        etag: &str,
+    ) -> Result<ObjectMeta, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        // Stage the new content first so the check-to-rename window is short
+        let mut reader: &[u8] = &data;
+        let (tmp, new_etag) = self.write_tmp(&path, &mut reader).await?;
+
+        let _guard = self.conditional_lock.lock().await;
+        let current = match Self::file_etag(key, &path).await {
+            Ok(current) => Some(current),
+            Err(StorageError::NotFound(_)) => None,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                return Err(e);
+            }
+        };
+        if current.as_deref() != Some(etag) {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(StorageError::PreconditionFailed(format!(
+                "{}/{}: etag {:?} does not match {}",
+                bucket, key, current, etag
+            )));
+        }
+        Self::commit_tmp(&tmp, &path).await?;
+
+        // Another process may have renamed over the key between our check and
+        // rename; if the content on disk isn't ours, our write lost
+        let md = Self::stat(key, &path).await?;
+        let written = Self::file_etag(key, &path).await?;
+        if written != new_etag {
+            return Err(StorageError::PreconditionFailed(format!(
+                "{}/{}: concurrent write replaced etag {}",
+                bucket, key, etag
+            )));
+        }
+        Ok(Self::meta_from(key, &md, new_etag))
+    }
+
+    async fn get(&self, bucket: &str, key: &str) -> Result<Bytes, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Bytes::from(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(format!("{}/{}", bucket, key)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn exists(&self, bucket: &str, key: &str) -> Result<bool, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(md) => Ok(md.is_file()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn head(&self, bucket: &str, key: &str) -> Result<ObjectMeta, StorageError> {
+        let path = self.object_path(bucket, key)?;
+        Self::meta_for(key, &path).await
+    }
+
+    async fn delete(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        let path = self.object_path(bucket, key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError> {
+        let base = self.bucket_dir(bucket);
+        let mut objects = Vec::new();
+        let mut dirs = vec![base.clone()];
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if entry.file_name().to_string_lossy().starts_with(TMP_PREFIX) {
+                    continue;
+                }
+                let Ok(rel) = path.strip_prefix(&base) else {
+                    continue;
+                };
+                let key = rel
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if !key.starts_with(prefix) {
+                    continue;
+                }
+                match Self::meta_for(&key, &path).await {
+                    Ok(meta) => objects.push(meta),
+                    // Deleted between read_dir and stat
+                    Err(StorageError::NotFound(_)) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(objects)
+    }
+
+    async fn list_page(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        continuation_token: Option<&str>,
+        max_keys: usize,
+    ) -> Result<ListPage, StorageError> {
+        // The directory walk has no native cursor; list() returns keys sorted
+        let objects = self.list(bucket, prefix).await?;
+        Ok(paginate(objects, continuation_token, max_keys))
+    }
+
+    async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        tokio::fs::create_dir_all(self.bucket_dir(bucket)).await?;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), StorageError> {
+        // Write and remove a probe file so an unwritable root is reported
+        tokio::fs::create_dir_all(&self.root).await?;
+        let probe = self.tmp_path(&self.root.join("health"));
+        tokio::fs::write(&probe, b"ok").await?;
+        tokio::fs::remove_file(&probe).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_and_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        let data = Bytes::from("hello world");
+        let meta = storage.put("bucket", "2025/12/23/file.txt", data.clone()).await.unwrap();
+        assert_eq!(meta.size, data.len() as u64);
+        assert_eq!(storage.get("bucket", "2025/12/23/file.txt").await.unwrap(), data);
+        assert!(storage.exists("bucket", "2025/12/23/file.txt").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_put_stream_large_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        // Several chunks' worth, not a multiple of the chunk size
+        let data: Vec<u8> = (0..(5 * STREAM_CHUNK_SIZE + 123)).map(|i| (i % 251) as u8).collect();
+
+        let mut reader: &[u8] = &data;
+        let meta = storage
+            .put_stream("bucket", "big.parquet", &mut reader, Some(data.len() as u64))
+            .await
+            .unwrap();
+        assert_eq!(meta.size, data.len() as u64);
+
+        let read_back = storage.get("bucket", "big.parquet").await.unwrap();
+        assert_eq!(read_back.as_ref(), data.as_slice());
+        assert_eq!(storage.head("bucket", "big.parquet").await.unwrap().size, data.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        storage.put("bucket", "a/1.json", Bytes::from("1")).await.unwrap();
+        storage.put("bucket", "a/2.json", Bytes::from("2")).await.unwrap();
+        storage.put("bucket", "b/3.json", Bytes::from("3")).await.unwrap();
+
+        let keys: Vec<String> = storage
+            .list("bucket", "a/")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.key)
+            .collect();
+        assert_eq!(keys, vec!["a/1.json", "a/2.json"]);
+
+        storage.delete("bucket", "a/1.json").await.unwrap();
+        assert!(!storage.exists("bucket", "a/1.json").await.unwrap());
+        assert!(matches!(
+            storage.get("bucket", "a/1.json").await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_put_if_not_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        storage
+            .put_if_not_exists("bucket", "out.parquet", Bytes::from("first"))
+            .await
+            .unwrap();
+        let result = storage
+            .put_if_not_exists("bucket", "out.parquet", Bytes::from("second"))
+            .await;
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+        assert_eq!(
+            storage.get("bucket", "out.parquet").await.unwrap(),
+            Bytes::from("first")
+        );
+        // No temp files left behind
+        assert_eq!(storage.list("bucket", "").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_put_if_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        let meta = storage.put("bucket", "k", Bytes::from("v1")).await.unwrap();
+        let etag = meta.etag.unwrap();
+
+        let result = storage
+            .put_if_match("bucket", "k", Bytes::from("v2"), "stale-etag")
+            .await;
+        assert!(matches!(result, Err(StorageError::PreconditionFailed(_))));
+        assert_eq!(storage.get("bucket", "k").await.unwrap(), Bytes::from("v1"));
+
+        storage
+            .put_if_match("bucket", "k", Bytes::from("v2"), &etag)
+            .await
+            .unwrap();
+        assert_eq!(storage.get("bucket", "k").await.unwrap(), Bytes::from("v2"));
+
+        let missing = storage
+            .put_if_match("bucket", "missing", Bytes::from("x"), &etag)
+            .await;
+        assert!(matches!(missing, Err(StorageError::PreconditionFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_etag_tracks_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        let first = storage.put("bucket", "k", Bytes::from("aaaa")).await.unwrap();
+        assert_eq!(first.etag.as_deref(), Some(format!("{:x}", md5::compute("aaaa")).as_str()));
+
+        // Same size, possibly the same mtime: the etag must still change
+        let second = storage.put("bucket", "k", Bytes::from("bbbb")).await.unwrap();
+        assert_ne!(first.etag, second.etag);
+        assert_eq!(storage.head("bucket", "k").await.unwrap().etag, second.etag);
+
+        let stale = storage
+            .put_if_match("bucket", "k", Bytes::from("cccc"), first.etag.as_deref().unwrap())
+            .await;
+        assert!(matches!(stale, Err(StorageError::PreconditionFailed(_))));
+        // The staged temp file is cleaned up
+        assert_eq!(std::fs::read_dir(dir.path().join("bucket")).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_page_two_pages_no_overlap() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        for i in 0..5 {
+            storage
+                .put("bucket", &format!("d/{}.json", i), Bytes::from("x"))
+                .await
+                .unwrap();
+        }
+
+        let first = storage.list_page("bucket", "d/", None, 3).await.unwrap();
+        let token = first.next_token.clone().expect("more pages");
+        let second = storage
+            .list_page("bucket", "d/", Some(&token), 3)
+            .await
+            .unwrap();
+        assert!(second.next_token.is_none());
+
+        let keys: Vec<String> = first
+            .objects
+            .iter()
+            .chain(second.objects.iter())
+            .map(|m| m.key.clone())
+            .collect();
+        assert_eq!(
+            keys,
+            (0..5).map(|i| format!("d/{}.json", i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_escaping_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FsStorage::new(dir.path());
+        let result = storage.put("bucket", "../escape", Bytes::from("x")).await;
+        assert!(matches!(result, Err(StorageError::InvalidKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("root");
+        let storage = FsStorage::new(&root);
+        storage.health_check().await.unwrap();
+        // Probe file is cleaned up
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_unwritable_root() {
+        // A regular file can never be used as a directory, regardless of privileges
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+        std::fs::write(&file, b"x").unwrap();
+
+        let storage = FsStorage::new(&file);
+        assert!(storage.health_check().await.is_err());
+    }
+}
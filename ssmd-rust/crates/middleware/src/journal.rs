@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::checkpoint::CheckpointStore;
 use crate::error::JournalError;
 
 /// Journal entry for audit trail
@@ -10,6 +12,8 @@ use crate::error::JournalError;
 pub struct JournalEntry {
     pub sequence: u64,
     pub timestamp: u64,
+    /// Wall-clock time the journal recorded the entry
+    pub recorded_at: DateTime<Utc>,
     pub topic: String,
     pub key: Option<Bytes>,
     pub payload: Bytes,
@@ -38,6 +42,37 @@ pub struct TopicConfig {
 pub trait JournalReader: Send + Sync {
     async fn next(&mut self) -> Result<Option<JournalEntry>, JournalError>;
     async fn seek(&mut self, position: JournalPosition) -> Result<(), JournalError>;
+
+    /// Position the reader at the first entry with `recorded_at >= ts`, or at
+    /// the end if there is none.
+    ///
+    /// Backends must assign `recorded_at` monotonically with sequence within a
+    /// topic so the seek can rely on ordering (binary search, or a broker-side
+    /// offset-for-timestamp lookup).
+    async fn seek_to_timestamp(&mut self, ts: DateTime<Utc>) -> Result<(), JournalError>;
+
+    /// Seek to the entry after the consumer's last committed checkpoint,
+    /// or to the beginning if the consumer has never committed.
+    async fn resume_from_checkpoint(
+        &mut self,
+        store: &dyn CheckpointStore,
+        consumer_id: &str,
+    ) -> Result<(), JournalError> {
+        match store.load(consumer_id).await? {
+            Some(seq) => self.seek(JournalPosition::Sequence(seq.saturating_add(1))).await,
+            None => self.seek(JournalPosition::Beginning).await,
+        }
+    }
+
+    /// Record `sequence` as the consumer's last processed entry
+    async fn commit(
+        &self,
+        store: &dyn CheckpointStore,
+        consumer_id: &str,
+        sequence: u64,
+    ) -> Result<(), JournalError> {
+        store.save(consumer_id, sequence).await
+    }
 }
 
 /// Journal abstraction for append-only audit log
@@ -58,6 +93,13 @@ pub trait Journal: Send + Sync {
         headers: HashMap<String, String>,
     ) -> Result<u64, JournalError>;
 
+    /// Append a batch of entries atomically: readers observe all of them or none.
+    ///
+    /// Sequence, timestamp and `recorded_at` on the input entries are ignored and assigned by
+    /// the journal. Returns the position of the last appended entry
+    /// (`JournalPosition::End` for an empty batch).
+    async fn append_batch(&self, entries: &[JournalEntry]) -> Result<JournalPosition, JournalError>;
+
     async fn reader(
         &self,
         topic: &str,
@@ -67,6 +109,11 @@ pub trait Journal: Send + Sync {
     async fn end_position(&self, topic: &str) -> Result<u64, JournalError>;
 
     async fn create_topic(&self, config: TopicConfig) -> Result<(), JournalError>;
+
+    /// Check that the backend is usable. In-process backends are always healthy.
+    async fn health_check(&self) -> Result<(), JournalError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -78,6 +125,7 @@ mod tests {
         let entry = JournalEntry {
             sequence: 1,
             timestamp: 1703318400000,
+            recorded_at: Utc::now(),
             topic: "ssmd.audit".to_string(),
             key: Some(Bytes::from("user:123")),
             payload: Bytes::from(r#"{"action":"login"}"#),
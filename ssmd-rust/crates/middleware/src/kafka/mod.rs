@@ -0,0 +1,5 @@
+pub mod topics;
+mod transport;
+
+pub use topics::{subject_pattern_to_topic_regex, subject_to_topic};
+pub use transport::KafkaTransport;
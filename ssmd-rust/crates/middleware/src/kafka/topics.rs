@@ -0,0 +1,117 @@
+//! Mapping from NATS-style subjects to Kafka topic names
+
+use crate::error::TransportError;
+use crate::nats::sanitize_subject_token;
+
+/// Kafka's maximum topic name length
+const MAX_TOPIC_LEN: usize = 249;
+
+/// Sanitize each `.`-separated token with the `SubjectBuilder` rules.
+fn sanitize_tokens(subject: &str) -> Result<Vec<String>, TransportError> {
+    subject
+        .split('.')
+        .map(|token| {
+            let sanitized = sanitize_subject_token(token);
+            if sanitized.is_empty() {
+                Err(TransportError::ValidationFailed(format!(
+                    "subject '{}' has an empty token after sanitization",
+                    subject
+                )))
+            } else {
+                Ok(sanitized)
+            }
+        })
+        .collect()
+}
+
+/// Convert a concrete subject to a Kafka topic name.
+///
+/// Tokens are sanitized with the same rules as `SubjectBuilder` and joined
+/// with `.`, which Kafka allows in topic names:
+/// ```
+/// use ssmd_middleware::kafka::subject_to_topic;
+/// assert_eq!(subject_to_topic("prod.kraken.json.trade.BTC/USD").unwrap(), "prod.kraken.json.trade.BTC-USD");
+/// ```
+pub fn subject_to_topic(subject: &str) -> Result<String, TransportError> {
+    if subject.contains('*') || subject.contains('>') {
+        return Err(TransportError::ValidationFailed(format!(
+            "cannot publish to wildcard subject '{}'",
+            subject
+        )));
+    }
+    let topic = sanitize_tokens(subject)?.join(".");
+    if topic.len() > MAX_TOPIC_LEN {
+        return Err(TransportError::ValidationFailed(format!(
+            "topic for subject '{}' exceeds {} characters",
+            subject, MAX_TOPIC_LEN
+        )));
+    }
+    Ok(topic)
+}
+
+/// Convert a subject pattern to a Kafka regex subscription.
+///
+/// `*` matches one token and a trailing `>` one or more tokens, as in NATS.
+/// Returns `None` for patterns without wildcards (subscribe to the plain topic).
+pub fn subject_pattern_to_topic_regex(pattern: &str) -> Result<Option<String>, TransportError> {
+    if !pattern.contains('*') && !pattern.contains('>') {
+        return Ok(None);
+    }
+
+    let tokens: Vec<&str> = pattern.split('.').collect();
+    let mut parts = Vec::with_capacity(tokens.len());
+    for (i, token) in tokens.iter().enumerate() {
+        match *token {
+            "*" => parts.push("[^.]+".to_string()),
+            ">" if i == tokens.len() - 1 => parts.push("[^.]+(\\.[^.]+)*".to_string()),
+            ">" => {
+                return Err(TransportError::ValidationFailed(format!(
+                    "'>' must be the last token in '{}'",
+                    pattern
+                )))
+            }
+            token => parts.extend(sanitize_tokens(token)?),
+        }
+    }
+    // Kafka treats subscriptions starting with '^' as regular expressions
+    Ok(Some(format!("^{}$", parts.join("\\."))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_to_topic_sanitizes_tokens() {
+        assert_eq!(
+            subject_to_topic("prod.kalshi.json.trade.KXBTC-25").unwrap(),
+            "prod.kalshi.json.trade.KXBTC-25"
+        );
+        assert_eq!(
+            subject_to_topic("dev.kraken.json.ticker.BTC/USD").unwrap(),
+            "dev.kraken.json.ticker.BTC-USD"
+        );
+    }
+
+    #[test]
+    fn test_subject_to_topic_rejects_wildcards_and_empty_tokens() {
+        assert!(subject_to_topic("prod.kalshi.>").is_err());
+        assert!(subject_to_topic("prod.*.trade").is_err());
+        assert!(subject_to_topic("prod..trade").is_err());
+        assert!(subject_to_topic(&"a".repeat(250)).is_err());
+    }
+
+    #[test]
+    fn test_pattern_without_wildcards_is_plain_topic() {
+        assert_eq!(subject_pattern_to_topic_regex("prod.kalshi.trade").unwrap(), None);
+    }
+
+    #[test]
+    fn test_pattern_wildcards_map_to_regex() {
+        assert_eq!(
+            subject_pattern_to_topic_regex("prod.*.json.trade.>").unwrap().unwrap(),
+            "^prod\\.[^.]+\\.json\\.trade\\.[^.]+(\\.[^.]+)*$"
+        );
+        assert!(subject_pattern_to_topic_regex("prod.>.trade").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::message::{Header, Headers, Message, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{Offset, TopicPartitionList};
+
+use super::topics::{subject_pattern_to_topic_regex, subject_to_topic};
+use crate::error::TransportError;
+use crate::latency::now_tsc;
+use crate::transport::{MessageFilter, Subscription, Transport, TransportMessage};
+
+/// Header carrying the original (unsanitized) subject
+const SUBJECT_HEADER: &str = "Ssmd-Subject";
+
+/// Delivery timeout for a single publish
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Metadata timeout for health checks
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Kafka subscription wrapper.
+///
+/// Durable subscriptions hand out sequences for received records; `ack`
+/// commits every record up to and including that sequence.
+struct KafkaSubscription {
+    consumer: StreamConsumer,
+    filter: Option<MessageFilter>,
+    durable: bool,
+    next_sequence: u64,
+    /// sequence -> (topic, partition, offset) for records not yet acked
+    pending: Mutex<BTreeMap<u64, (String, i32, i64)>>,
+}
+
+impl KafkaSubscription {
+    fn new(consumer: StreamConsumer, filter: Option<MessageFilter>, durable: bool) -> Self {
+        Self {
+            consumer,
+            filter,
+            durable,
+            next_sequence: 1,
+            pending: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Subscription for KafkaSubscription {
+    async fn next(&mut self) -> Result<TransportMessage, TransportError> {
+        loop {
+            let record = self.consumer
+                .recv()
+                .await
+                .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+
+            let mut subject = record.topic().to_string();
+            let mut headers = HashMap::new();
+            if let Some(record_headers) = record.headers() {
+                for header in record_headers.iter() {
+                    let value = header.value.map(|v| String::from_utf8_lossy(v).into_owned()).unwrap_or_default();
+                    if header.key == SUBJECT_HEADER {
+                        subject = value;
+                    } else {
+                        headers.insert(header.key.to_string(), value);
+                    }
+                }
+            }
+
+            let mut msg = TransportMessage {
+                subject,
+                payload: Bytes::copy_from_slice(record.payload().unwrap_or_default()),
+                headers,
+                timestamp: now_tsc(),
+                sequence: None,
+            };
+
+            if self.durable {
+                let sequence = self.next_sequence;
+                self.next_sequence += 1;
+                self.pending.lock().unwrap().insert(
+                    sequence,
+                    (record.topic().to_string(), record.partition(), record.offset()),
+                );
+                msg.sequence = Some(sequence);
+            }
+
+            if self.filter.as_ref().is_none_or(|f| f(&msg)) {
+                return Ok(msg);
+            }
+        }
+    }
+
+    async fn ack(&self, sequence: u64) -> Result<(), TransportError> {
+        if !self.durable {
+            // Ephemeral subscriptions never commit offsets
+            return Ok(());
+        }
+
+        let acked = {
+            let mut pending = self.pending.lock().unwrap();
+            let rest = pending.split_off(&(sequence + 1));
+            std::mem::replace(&mut *pending, rest)
+        };
+        if acked.is_empty() {
+            return Ok(());
+        }
+
+        // Commit the next offset to read for each partition
+        let mut next_offsets: HashMap<(String, i32), i64> = HashMap::new();
+        for (topic, partition, offset) in acked.into_values() {
+            let next = next_offsets.entry((topic, partition)).or_insert(offset + 1);
+            *next = (*next).max(offset + 1);
+        }
+        let mut tpl = TopicPartitionList::new();
+        for ((topic, partition), offset) in next_offsets {
+            tpl.add_partition_offset(&topic, partition, Offset::Offset(offset))
+                .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+        }
+
+        self.consumer
+            .commit(&tpl, CommitMode::Async)
+            .map_err(|e| TransportError::SubscribeFailed(format!("commit failed: {}", e)))
+    }
+
+    async fn unsubscribe(self: Box<Self>) -> Result<(), TransportError> {
+        self.consumer.unsubscribe();
+        Ok(())
+    }
+}
+
+/// Kafka transport implementation.
+///
+/// Subjects map to topics via `subject_to_topic`; the original subject travels
+/// in the `Ssmd-Subject` header so subscribers see it unchanged. `subscribe`
+/// is ephemeral (fresh consumer group, starts at the latest offset, never
+/// commits), matching core NATS. `subscribe_durable` joins a named consumer
+/// group that resumes from its committed offsets, committed via `ack`.
+pub struct KafkaTransport {
+    brokers: String,
+    producer: FutureProducer,
+}
+
+impl KafkaTransport {
+    /// Connect a producer to the given bootstrap servers (comma-separated)
+    pub fn connect(brokers: &str) -> Result<Self, TransportError> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", PUBLISH_TIMEOUT.as_millis().to_string())
+            .create()
+            .map_err(|e| TransportError::ConnectionFailed(e.to_string()))?;
+        Ok(Self {
+            brokers: brokers.to_string(),
+            producer,
+        })
+    }
+
+    /// Subscribe as a member of consumer group `group_id`.
+    ///
+    /// Starts from the group's committed offsets (earliest when none), and
+    /// load-balances across subscribers sharing the group.
+    pub async fn subscribe_durable(
+        &self,
+        subject: &str,
+        group_id: &str,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        let consumer = self.consumer(subject, group_id, "earliest")?;
+        Ok(Box::new(KafkaSubscription::new(consumer, None, true)))
+    }
+
+    fn ephemeral_group_id() -> String {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        format!(
+            "ssmd-ephemeral-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    fn consumer(
+        &self,
+        subject: &str,
+        group_id: &str,
+        offset_reset: &str,
+    ) -> Result<StreamConsumer, TransportError> {
+        let topic = match subject_pattern_to_topic_regex(subject)? {
+            Some(regex) => regex,
+            None => subject_to_topic(subject)?,
+        };
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &self.brokers)
+            .set("group.id", group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", offset_reset)
+            // Pick up topics created after a pattern subscription starts
+            .set("topic.metadata.refresh.interval.ms", "5000")
+            .create()
+            .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+
+        consumer
+            .subscribe(&[topic.as_str()])
+            .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+        Ok(consumer)
+    }
+}
+
+#[async_trait]
+impl Transport for KafkaTransport {
+    async fn publish(&self, subject: &str, payload: Bytes) -> Result<(), TransportError> {
+        self.publish_with_headers(subject, payload, HashMap::new()).await
+    }
+
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        payload: Bytes,
+        headers: HashMap<String, String>,
+    ) -> Result<(), TransportError> {
+        let topic = subject_to_topic(subject)?;
+
+        let mut kafka_headers = OwnedHeaders::new().insert(Header {
+            key: SUBJECT_HEADER,
+            value: Some(subject),
+        });
+        for (k, v) in &headers {
+            kafka_headers = kafka_headers.insert(Header {
+                key: k.as_str(),
+                value: Some(v.as_str()),
+            });
+        }
+
+        let record: FutureRecord<'_, (), [u8]> = FutureRecord::to(&topic)
+            .payload(payload.as_ref())
+            .headers(kafka_headers);
+
+        self.producer
+            .send(record, PUBLISH_TIMEOUT)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| TransportError::PublishFailed(e.to_string()))
+    }
+
+    async fn subscribe(&self, subject: &str) -> Result<Box<dyn Subscription>, TransportError> {
+        let consumer = self.consumer(subject, &Self::ephemeral_group_id(), "latest")?;
+        Ok(Box::new(KafkaSubscription::new(consumer, None, false)))
+    }
+
+    async fn subscribe_filtered(
+        &self,
+        subject: &str,
+        filter: MessageFilter,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        let consumer = self.consumer(subject, &Self::ephemeral_group_id(), "latest")?;
+        Ok(Box::new(KafkaSubscription::new(consumer, Some(filter), false)))
+    }
+
+    async fn request(
+        &self,
+        _subject: &str,
+        _payload: Bytes,
+        _timeout: Duration,
+    ) -> Result<TransportMessage, TransportError> {
+        Err(TransportError::RequestFailed(
+            "request/reply is not supported by the Kafka transport".to_string(),
+        ))
+    }
+
+    async fn health_check(&self) -> Result<(), TransportError> {
+        // Metadata fetch round-trips to a broker; it blocks, so keep it off the runtime
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || {
+            producer
+                .client()
+                .fetch_metadata(None, HEALTH_TIMEOUT)
+                .map(|_| ())
+                .map_err(|e| TransportError::ConnectionFailed(e.to_string()))
+        })
+        .await
+        .map_err(|e| TransportError::ConnectionFailed(e.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ephemeral_group_ids_are_unique() {
+        assert_ne!(
+            KafkaTransport::ephemeral_group_id(),
+            KafkaTransport::ephemeral_group_id()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_is_unsupported() {
+        // Producer creation doesn't contact the broker
+        let transport = KafkaTransport::connect("localhost:9092").unwrap();
+        let err = transport
+            .request("a.b", Bytes::new(), Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TransportError::RequestFailed(_)));
+    }
+}
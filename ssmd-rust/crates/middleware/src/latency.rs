@@ -5,10 +5,22 @@
 use lasso::{Spur, ThreadedRodeo};
 use once_cell::sync::Lazy;
 use quanta::Clock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Global TSC clock - zero syscall timestamp reads
 pub static CLOCK: Lazy<Clock> = Lazy::new(Clock::new);
 
+/// Wall-clock anchor (unix micros, raw TSC) taken once, so TSC readings can be
+/// converted to epoch time without a syscall per message
+static WALL_ANCHOR: Lazy<(i64, u64)> = Lazy::new(|| {
+    let raw = CLOCK.raw();
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as i64)
+        .unwrap_or(0);
+    (micros, raw)
+});
+
 /// Global string interner - lock-free reads after interning
 pub static INTERNER: Lazy<ThreadedRodeo> = Lazy::new(ThreadedRodeo::new);
 
@@ -18,6 +30,18 @@ pub fn now_tsc() -> u64 {
     CLOCK.raw()
 }
 
+/// Convert a raw TSC reading to Unix epoch microseconds (zero syscalls after
+/// the first call)
+#[inline]
+pub fn tsc_to_epoch_micros(tsc: u64) -> i64 {
+    let (anchor_micros, anchor_raw) = *WALL_ANCHOR;
+    if tsc >= anchor_raw {
+        anchor_micros + CLOCK.delta(anchor_raw, tsc).as_micros() as i64
+    } else {
+        anchor_micros - CLOCK.delta(tsc, anchor_raw).as_micros() as i64
+    }
+}
+
 /// Intern a string, returning a Spur handle
 #[inline]
 pub fn intern(s: &str) -> Spur {
@@ -42,6 +66,21 @@ mod tests {
         assert!(t2 >= t1, "TSC should be monotonic");
     }
 
+    #[test]
+    fn test_tsc_to_epoch_micros_tracks_wall_clock() {
+        let wall = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_micros() as i64;
+        let converted = tsc_to_epoch_micros(now_tsc());
+        assert!(
+            (converted - wall).abs() < 1_000_000,
+            "{} vs {}",
+            converted,
+            wall
+        );
+    }
+
     #[test]
     fn test_intern_and_resolve() {
         let spur = intern("BTCUSD");
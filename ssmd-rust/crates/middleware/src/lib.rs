@@ -4,9 +4,14 @@
 //! with in-memory implementations for testing.
 
 pub mod cache;
+pub mod checkpoint;
+pub mod compression;
 pub mod error;
 pub mod factory;
+pub mod fs;
 pub mod journal;
+#[cfg(feature = "kafka")]
+pub mod kafka;
 pub mod latency;
 pub mod lsn;
 pub mod memory;
@@ -15,16 +20,31 @@ pub mod nats;
 pub mod postgres_health;
 #[cfg(feature = "redis-health")]
 pub mod redis_health;
+#[cfg(feature = "redis-storage")]
+pub mod redis_storage;
 pub mod storage;
 pub mod transport;
 
-pub use cache::Cache;
+pub use cache::{Cache, CacheLookup};
+pub use compression::Compression;
+pub use checkpoint::{CheckpointStore, FsCheckpointStore};
 pub use error::{CacheError, JournalError, StorageError, TransportError};
-pub use factory::{FactoryError, MiddlewareFactory};
+pub use factory::{
+    BackendConfig, FactoryError, HealthReport, MiddlewareConfig, MiddlewareFactory, MiddlewareStack,
+};
+pub use fs::FsStorage;
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaTransport;
 pub use journal::{Journal, JournalEntry, JournalPosition, JournalReader, TopicConfig};
-pub use latency::{intern, now_tsc, resolve, CLOCK, INTERNER};
+pub use latency::{intern, now_tsc, resolve, tsc_to_epoch_micros, CLOCK, INTERNER};
 pub use lsn::lsn_gte;
-pub use memory::{InMemoryCache, InMemoryJournal, InMemoryStorage, InMemoryTransport};
+pub use memory::{
+    InMemoryCache, InMemoryCheckpointStore, InMemoryJournal, InMemoryStorage, InMemoryTransport,
+};
+#[cfg(feature = "redis-storage")]
+pub use redis_storage::RedisStorage;
 pub use nats::{sanitize_subject_token, NatsTransport, SubjectBuilder};
-pub use storage::{ObjectMeta, Storage};
-pub use transport::{Subscription, Transport, TransportMessage};
+pub use storage::{ListPage, ObjectMeta, Storage};
+pub use transport::{
+    MessageFilter, Subscription, Transport, TransportMessage, CAPTURE_TS_HEADER, SOURCE_SEQ_HEADER,
+};
@@ -4,19 +4,37 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
-use crate::cache::Cache;
+use crate::cache::{Cache, CacheLookup};
 use crate::error::CacheError;
 
 struct CacheEntry {
-    value: Bytes,
+    /// `None` marks a negative entry from `set_missing`
+    value: Option<Bytes>,
     expires_at: Option<Instant>,
 }
 
 impl CacheEntry {
+    fn new(value: Bytes, ttl: Option<Duration>) -> Self {
+        Self {
+            value: Some(value),
+            expires_at: ttl.map(|d| Instant::now() + d),
+        }
+    }
+
     fn is_expired(&self) -> bool {
         self.expires_at.map(|e| Instant::now() > e).unwrap_or(false)
     }
+
+    /// The value, unless expired or negative
+    fn live(&self) -> Option<&Bytes> {
+        if self.is_expired() {
+            None
+        } else {
+            self.value.as_ref()
+        }
+    }
 }
 
 pub struct InMemoryCache {
@@ -27,6 +45,31 @@ impl InMemoryCache {
     pub fn new() -> Self {
         Self { data: Arc::new(RwLock::new(HashMap::new())) }
     }
+
+    /// Periodically drop expired entries that are never read again.
+    ///
+    /// Reads already evict lazily; the sweep bounds memory for write-mostly
+    /// keys. The task stops once the cache is dropped.
+    pub fn spawn_sweeper(&self, interval: Duration) -> JoinHandle<()> {
+        let data = Arc::downgrade(&self.data);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(data) = data.upgrade() else { return };
+                data.write().await.retain(|_, e| !e.is_expired());
+            }
+        })
+    }
+
+    /// Remove `key` if it has expired (lazy eviction after a read)
+    async fn evict_if_expired(&self, key: &str) {
+        let mut data = self.data.write().await;
+        if data.get(key).is_some_and(|e| e.is_expired()) {
+            data.remove(key);
+        }
+    }
 }
 
 impl Default for InMemoryCache {
@@ -36,17 +79,46 @@ impl Default for InMemoryCache {
 #[async_trait]
 impl Cache for InMemoryCache {
     async fn get(&self, key: &str) -> Result<Option<Bytes>, CacheError> {
-        let data = self.data.read().await;
-        Ok(data.get(key).and_then(|e| if e.is_expired() { None } else { Some(e.value.clone()) }))
+        match self.lookup(key).await? {
+            CacheLookup::Hit(value) => Ok(Some(value)),
+            CacheLookup::Missing | CacheLookup::Miss => Ok(None),
+        }
     }
 
     async fn set(&self, key: &str, value: Bytes, ttl: Option<Duration>) -> Result<(), CacheError> {
-        let expires_at = ttl.map(|d| Instant::now() + d);
         let mut data = self.data.write().await;
-        data.insert(key.to_string(), CacheEntry { value, expires_at });
+        data.insert(key.to_string(), CacheEntry::new(value, ttl));
+        Ok(())
+    }
+
+    async fn set_missing(&self, key: &str, ttl: Duration) -> Result<(), CacheError> {
+        let mut data = self.data.write().await;
+        data.insert(
+            key.to_string(),
+            CacheEntry { value: None, expires_at: Some(Instant::now() + ttl) },
+        );
         Ok(())
     }
 
+    async fn lookup(&self, key: &str) -> Result<CacheLookup, CacheError> {
+        let lookup = {
+            let data = self.data.read().await;
+            match data.get(key) {
+                None => return Ok(CacheLookup::Miss),
+                Some(e) if e.is_expired() => None,
+                Some(CacheEntry { value: Some(value), .. }) => Some(CacheLookup::Hit(value.clone())),
+                Some(CacheEntry { value: None, .. }) => Some(CacheLookup::Missing),
+            }
+        };
+        match lookup {
+            Some(lookup) => Ok(lookup),
+            None => {
+                self.evict_if_expired(key).await;
+                Ok(CacheLookup::Miss)
+            }
+        }
+    }
+
     async fn delete(&self, key: &str) -> Result<(), CacheError> {
         let mut data = self.data.write().await;
         data.remove(key);
@@ -55,28 +127,74 @@ impl Cache for InMemoryCache {
 
     async fn exists(&self, key: &str) -> Result<bool, CacheError> {
         let data = self.data.read().await;
-        Ok(data.get(key).map(|e| !e.is_expired()).unwrap_or(false))
+        Ok(data.get(key).is_some_and(|e| e.live().is_some()))
     }
 
     async fn set_nx(&self, key: &str, value: Bytes, ttl: Option<Duration>) -> Result<bool, CacheError> {
         let mut data = self.data.write().await;
-        if let Some(entry) = data.get(key) {
-            if !entry.is_expired() { return Ok(false); }
+        if data.get(key).is_some_and(|e| e.live().is_some()) {
+            return Ok(false);
         }
-        let expires_at = ttl.map(|d| Instant::now() + d);
-        data.insert(key.to_string(), CacheEntry { value, expires_at });
+        data.insert(key.to_string(), CacheEntry::new(value, ttl));
         Ok(true)
     }
 
     async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Bytes>>, CacheError> {
         let data = self.data.read().await;
-        Ok(keys.iter().map(|k| data.get(*k).and_then(|e| if e.is_expired() { None } else { Some(e.value.clone()) })).collect())
+        Ok(keys.iter().map(|k| data.get(*k).and_then(|e| e.live().cloned())).collect())
     }
 
     async fn mset(&self, pairs: &[(&str, Bytes)]) -> Result<(), CacheError> {
         let mut data = self.data.write().await;
         for (key, value) in pairs {
-            data.insert(key.to_string(), CacheEntry { value: value.clone(), expires_at: None });
+            data.insert(key.to_string(), CacheEntry::new(value.clone(), None));
+        }
+        Ok(())
+    }
+
+    async fn get_many(&self, keys: &[&str]) -> Result<HashMap<String, Bytes>, CacheError> {
+        let data = self.data.read().await;
+        Ok(keys
+            .iter()
+            .filter_map(|k| {
+                data.get(*k)
+                    .and_then(|e| e.live())
+                    .map(|v| (k.to_string(), v.clone()))
+            })
+            .collect())
+    }
+
+    async fn cas(
+        &self,
+        key: &str,
+        expected: Option<&[u8]>,
+        new: Bytes,
+        ttl: Option<Duration>,
+    ) -> Result<bool, CacheError> {
+        let mut data = self.data.write().await;
+        let current = data.get(key).and_then(|e| e.live()).map(|v| &v[..]);
+        if current != expected {
+            return Ok(false);
+        }
+        data.insert(key.to_string(), CacheEntry::new(new, ttl));
+        Ok(true)
+    }
+
+    async fn scan(&self, prefix: &str) -> Result<Vec<String>, CacheError> {
+        let data = self.data.read().await;
+        Ok(data
+            .iter()
+            .filter(|(k, e)| k.starts_with(prefix) && e.live().is_some())
+            .map(|(k, _)| k.clone())
+            .collect())
+    }
+
+    async fn put_many(&self, entries: &[(&str, Bytes, Option<Duration>)]) -> Result<(), CacheError> {
+        let now = Instant::now();
+        let mut data = self.data.write().await;
+        for (key, value, ttl) in entries {
+            let expires_at = ttl.map(|d| now + d);
+            data.insert(key.to_string(), CacheEntry { value: Some(value.clone()), expires_at });
         }
         Ok(())
     }
@@ -108,4 +226,145 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(10)).await;
         assert!(cache.get("key").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_set_with_ttl_evicts_on_read() {
+        let cache = InMemoryCache::new();
+        cache.set_with_ttl("key", Bytes::from("value"), Duration::from_millis(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(cache.get("key").await.unwrap().is_none());
+        assert!(cache.data.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_removes_unread_expired_entries() {
+        let cache = InMemoryCache::new();
+        cache.set_with_ttl("short", Bytes::from("s"), Duration::from_millis(1)).await.unwrap();
+        cache.set("forever", Bytes::from("f"), None).await.unwrap();
+        let sweeper = cache.spawn_sweeper(Duration::from_millis(5));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let data = cache.data.read().await;
+        assert!(!data.contains_key("short"));
+        assert!(data.contains_key("forever"));
+        drop(data);
+        sweeper.abort();
+    }
+
+    #[tokio::test]
+    async fn test_negative_caching() {
+        let cache = InMemoryCache::new();
+        assert_eq!(cache.lookup("key").await.unwrap(), CacheLookup::Miss);
+
+        cache.set_missing("key", Duration::from_millis(20)).await.unwrap();
+        assert_eq!(cache.lookup("key").await.unwrap(), CacheLookup::Missing);
+        // Reads as absent everywhere else
+        assert!(cache.get("key").await.unwrap().is_none());
+        assert!(!cache.exists("key").await.unwrap());
+        assert!(cache.scan("k").await.unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(cache.lookup("key").await.unwrap(), CacheLookup::Miss);
+
+        // A real value replaces the negative entry
+        cache.set_missing("key", Duration::from_secs(60)).await.unwrap();
+        assert!(cache.set_nx("key", Bytes::from("v"), None).await.unwrap());
+        assert_eq!(cache.lookup("key").await.unwrap(), CacheLookup::Hit(Bytes::from("v")));
+    }
+
+    #[tokio::test]
+    async fn test_get_many_skips_missing_keys() {
+        let cache = InMemoryCache::new();
+        cache.set("a", Bytes::from("1"), None).await.unwrap();
+        cache.set("c", Bytes::from("3"), None).await.unwrap();
+
+        let found = cache.get_many(&["a", "b", "c"]).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found.get("a"), Some(&Bytes::from("1")));
+        assert_eq!(found.get("c"), Some(&Bytes::from("3")));
+        assert!(!found.contains_key("b"));
+    }
+
+    #[tokio::test]
+    async fn test_get_many_empty() {
+        let cache = InMemoryCache::new();
+        assert!(cache.get_many(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_put_many_mixed_ttls() {
+        let cache = InMemoryCache::new();
+        cache
+            .put_many(&[
+                ("short", Bytes::from("s"), Some(Duration::from_millis(1))),
+                ("long", Bytes::from("l"), Some(Duration::from_secs(60))),
+                ("forever", Bytes::from("f"), None),
+            ])
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let found = cache.get_many(&["short", "long", "forever"]).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(!found.contains_key("short"));
+        assert_eq!(found.get("long"), Some(&Bytes::from("l")));
+        assert_eq!(found.get("forever"), Some(&Bytes::from("f")));
+    }
+
+    #[tokio::test]
+    async fn test_scan_by_prefix() {
+        let cache = InMemoryCache::new();
+        for key in ["monitor:a", "monitor:b", "monitor:c:d", "secmaster:a", "mon"] {
+            cache.set(key, Bytes::from("v"), None).await.unwrap();
+        }
+        cache.set("monitor:expired", Bytes::from("v"), Some(Duration::from_millis(1))).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let mut keys = cache.scan("monitor:").await.unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["monitor:a", "monitor:b", "monitor:c:d"]);
+        assert!(cache.scan("nothing:").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_prefix() {
+        let cache = InMemoryCache::new();
+        for key in ["monitor:a", "monitor:b", "secmaster:a"] {
+            cache.set(key, Bytes::from("v"), None).await.unwrap();
+        }
+
+        assert_eq!(cache.delete_prefix("monitor:").await.unwrap(), 2);
+        assert!(!cache.exists("monitor:a").await.unwrap());
+        assert!(!cache.exists("monitor:b").await.unwrap());
+        assert!(cache.exists("secmaster:a").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cas_success() {
+        let cache = InMemoryCache::new();
+        cache.set("key", Bytes::from("v1"), None).await.unwrap();
+        assert!(cache.cas("key", Some(b"v1"), Bytes::from("v2"), None).await.unwrap());
+        assert_eq!(cache.get("key").await.unwrap(), Some(Bytes::from("v2")));
+    }
+
+    #[tokio::test]
+    async fn test_cas_fails_when_value_changed() {
+        let cache = InMemoryCache::new();
+        cache.set("key", Bytes::from("v1"), None).await.unwrap();
+        cache.set("key", Bytes::from("other"), None).await.unwrap();
+        assert!(!cache.cas("key", Some(b"v1"), Bytes::from("v2"), None).await.unwrap());
+        assert_eq!(cache.get("key").await.unwrap(), Some(Bytes::from("other")));
+    }
+
+    #[tokio::test]
+    async fn test_cas_missing_key() {
+        let cache = InMemoryCache::new();
+        // Expecting a value that isn't there fails
+        assert!(!cache.cas("key", Some(b"v1"), Bytes::from("v2"), None).await.unwrap());
+        assert!(cache.get("key").await.unwrap().is_none());
+        // Expecting absence succeeds, once
+        assert!(cache.cas("key", None, Bytes::from("v1"), None).await.unwrap());
+        assert!(!cache.cas("key", None, Bytes::from("v2"), None).await.unwrap());
+        assert_eq!(cache.get("key").await.unwrap(), Some(Bytes::from("v1")));
+    }
 }
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::checkpoint::CheckpointStore;
+use crate::error::JournalError;
+
+pub struct InMemoryCheckpointStore {
+    checkpoints: Arc<RwLock<HashMap<String, u64>>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self { checkpoints: Arc::new(RwLock::new(HashMap::new())) }
+    }
+}
+
+impl Default for InMemoryCheckpointStore {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, consumer_id: &str) -> Result<Option<u64>, JournalError> {
+        let checkpoints = self.checkpoints.read().await;
+        Ok(checkpoints.get(consumer_id).copied())
+    }
+
+    async fn save(&self, consumer_id: &str, sequence: u64) -> Result<(), JournalError> {
+        let mut checkpoints = self.checkpoints.write().await;
+        checkpoints.insert(consumer_id.to_string(), sequence);
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 
 use crate::error::JournalError;
@@ -31,6 +32,13 @@ impl InMemoryJournal {
     fn now_millis() -> u64 {
         now_tsc()
     }
+
+    /// Wall-clock time for the next entry in `entries`, never earlier than the
+    /// last one so `seek_to_timestamp` can binary search.
+    fn recorded_at(entries: &[JournalEntry]) -> DateTime<Utc> {
+        let now = Utc::now();
+        entries.last().map_or(now, |last| last.recorded_at.max(now))
+    }
 }
 
 impl Default for InMemoryJournal {
@@ -77,6 +85,11 @@ impl JournalReader for InMemoryJournalReader {
         }
         Ok(())
     }
+
+    async fn seek_to_timestamp(&mut self, ts: DateTime<Utc>) -> Result<(), JournalError> {
+        self.position = self.entries.partition_point(|e| e.recorded_at < ts);
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -94,19 +107,46 @@ impl Journal for InMemoryJournal {
         headers: HashMap<String, String>,
     ) -> Result<u64, JournalError> {
         let seq = self.next_sequence();
+        let mut topics = self.topics.write().await;
+        let entries = topics.entry(topic.to_string()).or_default();
         let entry = JournalEntry {
             sequence: seq,
             timestamp: Self::now_millis(),
+            recorded_at: Self::recorded_at(entries),
             topic: topic.to_string(),
             key,
             payload,
             headers,
         };
-        let mut topics = self.topics.write().await;
-        topics.entry(topic.to_string()).or_default().push(entry);
+        entries.push(entry);
         Ok(seq)
     }
 
+    async fn append_batch(&self, entries: &[JournalEntry]) -> Result<JournalPosition, JournalError> {
+        if entries.is_empty() {
+            return Ok(JournalPosition::End);
+        }
+
+        // Hold the write lock across sequence allocation and insertion so the
+        // batch gets contiguous sequences and readers never see a partial batch.
+        let mut topics = self.topics.write().await;
+        let first = self
+            .sequence
+            .fetch_add(entries.len() as u64, Ordering::Relaxed);
+        let timestamp = Self::now_millis();
+        for (i, entry) in entries.iter().enumerate() {
+            let topic_entries = topics.entry(entry.topic.clone()).or_default();
+            let recorded_at = Self::recorded_at(topic_entries);
+            topic_entries.push(JournalEntry {
+                sequence: first + i as u64,
+                timestamp,
+                recorded_at,
+                ..entry.clone()
+            });
+        }
+        Ok(JournalPosition::Sequence(first + entries.len() as u64 - 1))
+    }
+
     async fn reader(
         &self,
         topic: &str,
@@ -140,6 +180,7 @@ impl Journal for InMemoryJournal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::InMemoryCheckpointStore;
 
     #[tokio::test]
     async fn test_append_and_read() {
@@ -174,6 +215,161 @@ mod tests {
         assert_eq!(journal.end_position("topic").await.unwrap(), 0);
     }
 
+    fn batch_entry(topic: &str, payload: &str) -> JournalEntry {
+        JournalEntry {
+            sequence: 0,
+            timestamp: 0,
+            recorded_at: DateTime::UNIX_EPOCH,
+            topic: topic.to_string(),
+            key: None,
+            payload: Bytes::from(payload.to_string()),
+            headers: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_batch_reads_back_in_order() {
+        let journal = InMemoryJournal::new();
+        journal
+            .append("topic", None, Bytes::from("single"))
+            .await
+            .unwrap();
+        let batch: Vec<JournalEntry> = ["a", "b", "c"]
+            .iter()
+            .map(|p| batch_entry("topic", p))
+            .collect();
+        let pos = journal.append_batch(&batch).await.unwrap();
+        assert!(matches!(pos, JournalPosition::Sequence(3)));
+
+        let mut reader = journal
+            .reader("topic", JournalPosition::Sequence(1))
+            .await
+            .unwrap();
+        for (expected_seq, expected_payload) in [(1, "a"), (2, "b"), (3, "c")] {
+            let entry = reader.next().await.unwrap().unwrap();
+            assert_eq!(entry.sequence, expected_seq);
+            assert_eq!(entry.payload, Bytes::from(expected_payload));
+        }
+        assert!(reader.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_append_batch_empty() {
+        let journal = InMemoryJournal::new();
+        let pos = journal.append_batch(&[]).await.unwrap();
+        assert!(matches!(pos, JournalPosition::End));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_reader_never_sees_partial_batch() {
+        const BATCH_SIZE: usize = 10;
+        let journal = Arc::new(InMemoryJournal::new());
+
+        let writer = {
+            let journal = journal.clone();
+            tokio::spawn(async move {
+                for _ in 0..100 {
+                    let batch: Vec<JournalEntry> = (0..BATCH_SIZE)
+                        .map(|i| batch_entry("topic", &i.to_string()))
+                        .collect();
+                    journal.append_batch(&batch).await.unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        while !writer.is_finished() {
+            let mut reader = journal
+                .reader("topic", JournalPosition::Beginning)
+                .await
+                .unwrap();
+            let mut count = 0;
+            while reader.next().await.unwrap().is_some() {
+                count += 1;
+            }
+            assert_eq!(count % BATCH_SIZE, 0, "reader observed a partial batch");
+            tokio::task::yield_now().await;
+        }
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_commit_and_resume() {
+        let journal = InMemoryJournal::new();
+        let store = InMemoryCheckpointStore::new();
+        for payload in ["a", "b", "c"] {
+            journal
+                .append("topic", None, Bytes::from(payload))
+                .await
+                .unwrap();
+        }
+
+        let mut reader = journal
+            .reader("topic", JournalPosition::Beginning)
+            .await
+            .unwrap();
+        let first = reader.next().await.unwrap().unwrap();
+        reader.commit(&store, "consumer", first.sequence).await.unwrap();
+
+        // Simulate restart: a fresh reader resumes after the committed entry
+        let mut reader = journal
+            .reader("topic", JournalPosition::Beginning)
+            .await
+            .unwrap();
+        reader.resume_from_checkpoint(&store, "consumer").await.unwrap();
+        let next = reader.next().await.unwrap().unwrap();
+        assert_eq!(next.payload, Bytes::from("b"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_empty_checkpoint_store() {
+        let journal = InMemoryJournal::new();
+        let store = InMemoryCheckpointStore::new();
+        journal
+            .append("topic", None, Bytes::from("a"))
+            .await
+            .unwrap();
+
+        let mut reader = journal
+            .reader("topic", JournalPosition::End)
+            .await
+            .unwrap();
+        reader.resume_from_checkpoint(&store, "new-consumer").await.unwrap();
+        let entry = reader.next().await.unwrap().unwrap();
+        assert_eq!(entry.payload, Bytes::from("a"));
+    }
+
+    #[tokio::test]
+    async fn test_seek_to_timestamp() {
+        let journal = InMemoryJournal::new();
+        for payload in ["a", "b", "c"] {
+            journal
+                .append("topic", None, Bytes::from(payload))
+                .await
+                .unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        let mut reader = journal
+            .reader("topic", JournalPosition::Beginning)
+            .await
+            .unwrap();
+        let _a = reader.next().await.unwrap().unwrap();
+        let b = reader.next().await.unwrap().unwrap();
+
+        reader.seek_to_timestamp(b.recorded_at).await.unwrap();
+        assert_eq!(reader.next().await.unwrap().unwrap().payload, Bytes::from("b"));
+
+        reader.seek_to_timestamp(DateTime::UNIX_EPOCH).await.unwrap();
+        assert_eq!(reader.next().await.unwrap().unwrap().payload, Bytes::from("a"));
+
+        reader
+            .seek_to_timestamp(Utc::now() + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert!(reader.next().await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_sequence_is_atomic() {
         let journal = InMemoryJournal::new();
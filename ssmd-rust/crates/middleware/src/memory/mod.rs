@@ -1,10 +1,12 @@
 //! In-memory implementations for testing
 pub mod cache;
+pub mod checkpoint;
 pub mod journal;
 pub mod storage;
 pub mod transport;
 
 pub use cache::InMemoryCache;
+pub use checkpoint::InMemoryCheckpointStore;
 pub use journal::InMemoryJournal;
 pub use storage::InMemoryStorage;
 pub use transport::InMemoryTransport;
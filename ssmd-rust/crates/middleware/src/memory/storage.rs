@@ -2,11 +2,14 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::sync::RwLock;
 
+use crate::compression::Compression;
 use crate::error::StorageError;
-use crate::storage::{ObjectMeta, Storage};
+use crate::storage::{paginate, ListPage, ObjectMeta, Storage};
 
+/// Stored (possibly compressed) bytes and their metadata
 type BucketData = HashMap<String, (Bytes, ObjectMeta)>;
 type StorageData = HashMap<String, BucketData>;
 
@@ -19,6 +22,27 @@ impl InMemoryStorage {
         Self { data: Arc::new(RwLock::new(HashMap::new())) }
     }
 
+    /// Compress `data` for storage. The etag is over the uncompressed bytes,
+    /// so it doesn't depend on the codec.
+    fn encode(key: &str, data: Bytes, compression: Compression) -> Result<(Bytes, ObjectMeta), StorageError> {
+        let etag = format!("{:x}", md5::compute(&data));
+        let size = data.len() as u64;
+        let stored = match compression {
+            Compression::None => data,
+            codec => codec.compress(&data)?,
+        };
+        let meta = ObjectMeta {
+            key: key.to_string(),
+            size,
+            stored_size: stored.len() as u64,
+            compression,
+            last_modified: Self::now_millis(),
+            etag: Some(etag),
+            content_type: None,
+        };
+        Ok((stored, meta))
+    }
+
     fn now_millis() -> u64 {
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -34,23 +58,69 @@ impl Default for InMemoryStorage {
 #[async_trait]
 impl Storage for InMemoryStorage {
     async fn put(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError> {
-        let meta = ObjectMeta {
-            key: key.to_string(),
-            size: data.len() as u64,
-            last_modified: Self::now_millis(),
-            etag: Some(format!("{:x}", md5::compute(&data))),
-            content_type: None,
-        };
+        self.put_compressed(bucket, key, data, Compression::None).await
+    }
+
+    async fn put_compressed(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        compression: Compression,
+    ) -> Result<ObjectMeta, StorageError> {
+        let (stored, meta) = Self::encode(key, data, compression)?;
+        let mut store = self.data.write().await;
+        let bucket_data = store.entry(bucket.to_string()).or_default();
+        bucket_data.insert(key.to_string(), (stored, meta.clone()));
+        Ok(meta)
+    }
+
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        size_hint: Option<u64>,
+    ) -> Result<ObjectMeta, StorageError> {
+        // Objects live in memory anyway, so buffer the stream
+        let mut buf = Vec::with_capacity(size_hint.unwrap_or(0) as usize);
+        reader.read_to_end(&mut buf).await?;
+        self.put(bucket, key, Bytes::from(buf)).await
+    }
+
+    async fn put_if_not_exists(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError> {
         let mut store = self.data.write().await;
         let bucket_data = store.entry(bucket.to_string()).or_default();
-        bucket_data.insert(key.to_string(), (data, meta.clone()));
+        if bucket_data.contains_key(key) {
+            return Err(StorageError::AlreadyExists(format!("{}/{}", bucket, key)));
+        }
+        let (stored, meta) = Self::encode(key, data, Compression::None)?;
+        bucket_data.insert(key.to_string(), (stored, meta.clone()));
+        Ok(meta)
+    }
+
+    async fn put_if_match(&self, bucket: &str, key: &str, data: Bytes, etag: &str) -> Result<ObjectMeta, StorageError> {
+        let mut store = self.data.write().await;
+        let bucket_data = store.entry(bucket.to_string()).or_default();
+        let current = bucket_data.get(key).and_then(|(_, meta)| meta.etag.as_deref());
+        if current != Some(etag) {
+            return Err(StorageError::PreconditionFailed(format!(
+                "{}/{}: etag {:?} does not match {}", bucket, key, current, etag
+            )));
+        }
+        let (stored, meta) = Self::encode(key, data, Compression::None)?;
+        bucket_data.insert(key.to_string(), (stored, meta.clone()));
         Ok(meta)
     }
 
     async fn get(&self, bucket: &str, key: &str) -> Result<Bytes, StorageError> {
         let store = self.data.read().await;
-        store.get(bucket).and_then(|b| b.get(key)).map(|(data, _)| data.clone())
-            .ok_or_else(|| StorageError::NotFound(format!("{}/{}", bucket, key)))
+        let (stored, meta) = store.get(bucket).and_then(|b| b.get(key))
+            .ok_or_else(|| StorageError::NotFound(format!("{}/{}", bucket, key)))?;
+        match meta.compression {
+            Compression::None => Ok(stored.clone()),
+            codec => codec.decompress(stored),
+        }
     }
 
     async fn exists(&self, bucket: &str, key: &str) -> Result<bool, StorageError> {
@@ -75,6 +145,12 @@ impl Storage for InMemoryStorage {
         Ok(store.get(bucket).map(|b| b.iter().filter(|(k, _)| k.starts_with(prefix)).map(|(_, (_, meta))| meta.clone()).collect()).unwrap_or_default())
     }
 
+    async fn list_page(&self, bucket: &str, prefix: &str, continuation_token: Option<&str>, max_keys: usize) -> Result<ListPage, StorageError> {
+        let mut objects = self.list(bucket, prefix).await?;
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(paginate(objects, continuation_token, max_keys))
+    }
+
     async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
         let mut store = self.data.write().await;
         store.entry(bucket.to_string()).or_default();
@@ -95,6 +171,67 @@ mod tests {
         assert_eq!(retrieved, data);
     }
 
+    #[tokio::test]
+    async fn test_put_if_not_exists() {
+        let storage = InMemoryStorage::new();
+        storage.put_if_not_exists("bucket", "k", Bytes::from("v1")).await.unwrap();
+        let result = storage.put_if_not_exists("bucket", "k", Bytes::from("v2")).await;
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+        assert_eq!(storage.get("bucket", "k").await.unwrap(), Bytes::from("v1"));
+    }
+
+    #[tokio::test]
+    async fn test_put_if_match() {
+        let storage = InMemoryStorage::new();
+        let meta = storage.put("bucket", "k", Bytes::from("v1")).await.unwrap();
+        let result = storage.put_if_match("bucket", "k", Bytes::from("v2"), "stale").await;
+        assert!(matches!(result, Err(StorageError::PreconditionFailed(_))));
+
+        storage.put_if_match("bucket", "k", Bytes::from("v2"), meta.etag.as_deref().unwrap()).await.unwrap();
+        assert_eq!(storage.get("bucket", "k").await.unwrap(), Bytes::from("v2"));
+    }
+
+    #[tokio::test]
+    async fn test_list_page_two_pages_no_overlap() {
+        let storage = InMemoryStorage::new();
+        for i in 0..5 {
+            storage.put("bucket", &format!("manifests/{}.json", i), Bytes::from("x")).await.unwrap();
+        }
+        storage.put("bucket", "other/skip.json", Bytes::from("x")).await.unwrap();
+
+        let first = storage.list_page("bucket", "manifests/", None, 3).await.unwrap();
+        assert_eq!(first.objects.len(), 3);
+        let token = first.next_token.clone().expect("more pages");
+
+        let second = storage.list_page("bucket", "manifests/", Some(&token), 3).await.unwrap();
+        assert_eq!(second.objects.len(), 2);
+        assert!(second.next_token.is_none());
+
+        let mut keys: Vec<String> = first.objects.iter().chain(second.objects.iter()).map(|m| m.key.clone()).collect();
+        let total = keys.len();
+        keys.dedup();
+        assert_eq!(keys.len(), total);
+        assert_eq!(keys, (0..5).map(|i| format!("manifests/{}.json", i)).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_put_compressed_is_transparent() {
+        let storage = InMemoryStorage::new();
+        let data = Bytes::from(r#"{"ticker":"KXBTC","yes_bid":55}"#.repeat(50));
+
+        for (key, codec) in [("gz", Compression::Gzip), ("zst", Compression::Zstd)] {
+            let meta = storage.put_compressed("bucket", key, data.clone(), codec).await.unwrap();
+            assert_eq!(meta.compression, codec);
+            assert_eq!(meta.size, data.len() as u64);
+            assert!(meta.stored_size < meta.size);
+            assert_eq!(storage.get("bucket", key).await.unwrap(), data);
+
+            let head = storage.head("bucket", key).await.unwrap();
+            assert_eq!(head.stored_size, meta.stored_size);
+            assert_eq!(head.etag, storage.put("bucket", "plain", data.clone()).await.unwrap().etag);
+        }
+    }
+
     #[tokio::test]
     async fn test_not_found() {
         let storage = InMemoryStorage::new();
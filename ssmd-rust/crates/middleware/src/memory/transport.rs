@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -9,13 +10,16 @@ use tokio::sync::broadcast;
 
 use crate::error::TransportError;
 use crate::latency::now_tsc;
-use crate::transport::{Subscription, Transport, TransportMessage};
+use crate::transport::{MessageFilter, Subscription, Transport, TransportMessage};
 
 const CHANNEL_BUFFER_SIZE: usize = 1024;
 
 pub struct InMemoryTransport {
     channels: DashMap<String, broadcast::Sender<TransportMessage>>,
     sequence: AtomicU64,
+    /// Held for write while a batch is sent; single publishes and receivers
+    /// take it for read, so nothing interleaves with or observes half a batch
+    batch_lock: Arc<RwLock<()>>,
 }
 
 impl InMemoryTransport {
@@ -23,6 +27,7 @@ impl InMemoryTransport {
         Self {
             channels: DashMap::new(),
             sequence: AtomicU64::new(0),
+            batch_lock: Arc::new(RwLock::new(())),
         }
     }
 
@@ -37,6 +42,14 @@ impl InMemoryTransport {
             .or_insert_with(|| broadcast::channel(CHANNEL_BUFFER_SIZE).0)
             .clone()
     }
+
+    fn subscription(&self, subject: &str, filter: Option<MessageFilter>) -> InMemorySubscription {
+        InMemorySubscription {
+            rx: self.get_or_create_channel(subject).subscribe(),
+            filter,
+            batch_lock: self.batch_lock.clone(),
+        }
+    }
 }
 
 impl Default for InMemoryTransport {
@@ -47,15 +60,26 @@ impl Default for InMemoryTransport {
 
 struct InMemorySubscription {
     rx: broadcast::Receiver<TransportMessage>,
+    filter: Option<MessageFilter>,
+    batch_lock: Arc<RwLock<()>>,
 }
 
 #[async_trait]
 impl Subscription for InMemorySubscription {
     async fn next(&mut self) -> Result<TransportMessage, TransportError> {
-        self.rx
-            .recv()
-            .await
-            .map_err(|e| TransportError::SubscribeFailed(e.to_string()))
+        loop {
+            let msg = self
+                .rx
+                .recv()
+                .await
+                .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+            // A message from an in-flight batch is released only once the
+            // whole batch has been sent
+            drop(self.batch_lock.read().unwrap());
+            if self.filter.as_ref().is_none_or(|f| f(&msg)) {
+                return Ok(msg);
+            }
+        }
     }
 
     async fn ack(&self, _sequence: u64) -> Result<(), TransportError> {
@@ -81,6 +105,7 @@ impl Transport for InMemoryTransport {
         headers: HashMap<String, String>,
     ) -> Result<(), TransportError> {
         let tx = self.get_or_create_channel(subject);
+        let _guard = self.batch_lock.read().unwrap();
         let seq = self.next_sequence();
         let msg = TransportMessage {
             subject: subject.to_string(),
@@ -93,10 +118,34 @@ impl Transport for InMemoryTransport {
         Ok(())
     }
 
+    async fn publish_batch(&self, messages: &[TransportMessage]) -> Result<(), TransportError> {
+        let senders: Vec<_> = messages
+            .iter()
+            .map(|msg| self.get_or_create_channel(&msg.subject))
+            .collect();
+
+        // Contiguous sequences in slice order, released to receivers together
+        let _guard = self.batch_lock.write().unwrap();
+        for (msg, tx) in messages.iter().zip(senders) {
+            let _ = tx.send(TransportMessage {
+                timestamp: now_tsc(),
+                sequence: Some(self.next_sequence()),
+                ..msg.clone()
+            });
+        }
+        Ok(())
+    }
+
     async fn subscribe(&self, subject: &str) -> Result<Box<dyn Subscription>, TransportError> {
-        let tx = self.get_or_create_channel(subject);
-        let rx = tx.subscribe();
-        Ok(Box::new(InMemorySubscription { rx }))
+        Ok(Box::new(self.subscription(subject, None)))
+    }
+
+    async fn subscribe_filtered(
+        &self,
+        subject: &str,
+        filter: MessageFilter,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        Ok(Box::new(self.subscription(subject, Some(filter))))
     }
 
     async fn request(
@@ -144,6 +193,110 @@ mod tests {
         assert_eq!(msg2.sequence, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_subscribe_filtered_drops_non_matching() {
+        let transport = InMemoryTransport::new();
+        let filter: MessageFilter = std::sync::Arc::new(|msg: &TransportMessage| {
+            msg.payload.as_ref() != b"skip"
+        });
+        let mut sub = transport
+            .subscribe_filtered("test.filter", filter)
+            .await
+            .unwrap();
+        transport
+            .publish("test.filter", Bytes::from("skip"))
+            .await
+            .unwrap();
+        transport
+            .publish("test.filter", Bytes::from("keep"))
+            .await
+            .unwrap();
+        let msg = sub.next().await.unwrap();
+        assert_eq!(msg.payload, Bytes::from("keep"));
+        assert_eq!(msg.sequence, Some(1));
+    }
+
+    fn batch_message(subject: &str, payload: &str) -> TransportMessage {
+        TransportMessage {
+            subject: subject.to_string(),
+            payload: Bytes::from(payload.to_string()),
+            headers: HashMap::new(),
+            timestamp: 0,
+            sequence: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_in_order() {
+        let transport = InMemoryTransport::new();
+        let mut sub_a = transport.subscribe("test.batch.a").await.unwrap();
+        let mut sub_b = transport.subscribe("test.batch.b").await.unwrap();
+        transport
+            .publish_batch(&[
+                batch_message("test.batch.a", "1"),
+                batch_message("test.batch.b", "2"),
+                batch_message("test.batch.a", "3"),
+            ])
+            .await
+            .unwrap();
+
+        let m1 = sub_a.next().await.unwrap();
+        let m2 = sub_b.next().await.unwrap();
+        let m3 = sub_a.next().await.unwrap();
+        assert_eq!(m1.payload, Bytes::from("1"));
+        assert_eq!(m3.payload, Bytes::from("3"));
+        assert_eq!(
+            (m1.sequence, m2.sequence, m3.sequence),
+            (Some(0), Some(1), Some(2))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_publish_batch_not_interleaved() {
+        const BATCH_SIZE: usize = 10;
+        let transport = std::sync::Arc::new(InMemoryTransport::new());
+        let mut sub = transport.subscribe("test.atomic").await.unwrap();
+
+        let batcher = {
+            let transport = transport.clone();
+            tokio::spawn(async move {
+                for _ in 0..50 {
+                    let batch: Vec<_> = (0..BATCH_SIZE)
+                        .map(|_| batch_message("test.atomic", "batch"))
+                        .collect();
+                    transport.publish_batch(&batch).await.unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+        let single = {
+            let transport = transport.clone();
+            tokio::spawn(async move {
+                for _ in 0..50 {
+                    transport
+                        .publish("test.atomic", Bytes::from("single"))
+                        .await
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+        batcher.await.unwrap();
+        single.await.unwrap();
+
+        let mut run = 0;
+        for _ in 0..50 * BATCH_SIZE + 50 {
+            let msg = sub.next().await.unwrap();
+            if msg.payload.as_ref() == b"batch" {
+                run += 1;
+            } else {
+                assert_eq!(run % BATCH_SIZE, 0, "single publish interleaved with a batch");
+                run = 0;
+            }
+        }
+        assert_eq!(run % BATCH_SIZE, 0);
+    }
+
     #[tokio::test]
     async fn test_timestamp_is_tsc() {
         let transport = InMemoryTransport::new();
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_nats::jetstream::{self, Context};
 use async_nats::jetstream::stream::{Config, RetentionPolicy, StorageType};
@@ -10,34 +11,49 @@ use futures_util::StreamExt;
 
 use crate::error::TransportError;
 use crate::latency::now_tsc;
-use crate::transport::{Subscription, Transport, TransportMessage};
+use crate::transport::{MessageFilter, Subscription, Transport, TransportMessage};
+
+/// JetStream atomic batch headers (server 2.12+, stream needs `allow_atomic`)
+const BATCH_ID_HEADER: &str = "Nats-Batch-Id";
+const BATCH_SEQUENCE_HEADER: &str = "Nats-Batch-Sequence";
+const BATCH_COMMIT_HEADER: &str = "Nats-Batch-Commit";
+
+/// Server limit on messages per atomic batch
+const MAX_ATOMIC_BATCH: usize = 1000;
 
 /// NATS subscription wrapper
 struct NatsSubscription {
     subscriber: async_nats::Subscriber,
+    filter: Option<MessageFilter>,
 }
 
 impl NatsSubscription {
-    fn new(subscriber: async_nats::Subscriber) -> Self {
-        Self { subscriber }
+    fn new(subscriber: async_nats::Subscriber, filter: Option<MessageFilter>) -> Self {
+        Self { subscriber, filter }
     }
 }
 
 #[async_trait]
 impl Subscription for NatsSubscription {
     async fn next(&mut self) -> Result<TransportMessage, TransportError> {
-        let msg = self.subscriber
-            .next()
-            .await
-            .ok_or_else(|| TransportError::SubscribeFailed("subscription closed".to_string()))?;
-
-        Ok(TransportMessage {
-            subject: msg.subject.to_string(),
-            payload: msg.payload,
-            headers: HashMap::new(),
-            timestamp: now_tsc(),
-            sequence: None,
-        })
+        loop {
+            let msg = self.subscriber
+                .next()
+                .await
+                .ok_or_else(|| TransportError::SubscribeFailed("subscription closed".to_string()))?;
+
+            let msg = TransportMessage {
+                subject: msg.subject.to_string(),
+                payload: msg.payload,
+                headers: HashMap::new(),
+                timestamp: now_tsc(),
+                sequence: None,
+            };
+
+            if self.filter.as_ref().is_none_or(|f| f(&msg)) {
+                return Ok(msg);
+            }
+        }
     }
 
     async fn ack(&self, _sequence: u64) -> Result<(), TransportError> {
@@ -102,6 +118,38 @@ impl NatsTransport {
         Ok(())
     }
 
+    fn batch_id() -> String {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        format!(
+            "{}-{}-{}",
+            std::process::id(),
+            nanos,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    fn batch_headers(
+        msg: &TransportMessage,
+        batch_id: &str,
+        batch_sequence: usize,
+        commit: bool,
+    ) -> async_nats::HeaderMap {
+        let mut headers = async_nats::HeaderMap::new();
+        for (k, v) in &msg.headers {
+            headers.insert(k.as_str(), v.as_str());
+        }
+        headers.insert(BATCH_ID_HEADER, batch_id);
+        headers.insert(BATCH_SEQUENCE_HEADER, batch_sequence.to_string());
+        if commit {
+            headers.insert(BATCH_COMMIT_HEADER, "1");
+        }
+        headers
+    }
+
     /// Validate that a subject prefix will be captured by a stream
     pub async fn validate_stream_subjects(
         &self,
@@ -173,12 +221,80 @@ impl Transport for NatsTransport {
             .map_err(|e| TransportError::PublishFailed(e.to_string()))
     }
 
+    /// Publish as a JetStream atomic batch.
+    ///
+    /// All subjects must belong to one stream created with atomic publish
+    /// enabled. The stream stores the messages contiguously in slice order,
+    /// or not at all, once it sees the commit header on the last one; only
+    /// JetStream consumers reading that stream get the all-or-nothing
+    /// guarantee. The staged messages go out as core publishes, so a core
+    /// subscription on these subjects receives each one immediately, even if
+    /// the commit later fails. Only the commit message is acked; a rejected
+    /// message earlier in the batch fails the commit, so its ack covers the
+    /// batch.
+    async fn publish_batch(&self, messages: &[TransportMessage]) -> Result<(), TransportError> {
+        let Some((last, rest)) = messages.split_last() else {
+            return Ok(());
+        };
+        if messages.len() > MAX_ATOMIC_BATCH {
+            return Err(TransportError::ValidationFailed(format!(
+                "batch of {} messages exceeds the atomic batch limit of {}",
+                messages.len(),
+                MAX_ATOMIC_BATCH
+            )));
+        }
+
+        let batch_id = Self::batch_id();
+        for (i, msg) in rest.iter().enumerate() {
+            self.client
+                .publish_with_headers(
+                    msg.subject.clone(),
+                    Self::batch_headers(msg, &batch_id, i + 1, false),
+                    msg.payload.clone(),
+                )
+                .await
+                .map_err(|e| TransportError::PublishFailed(e.to_string()))?;
+        }
+
+        self.jetstream
+            .publish_with_headers(
+                last.subject.clone(),
+                Self::batch_headers(last, &batch_id, messages.len(), true),
+                last.payload.clone(),
+            )
+            .await
+            .map_err(|e| TransportError::PublishFailed(e.to_string()))?
+            .await
+            .map_err(|e| TransportError::PublishFailed(format!("batch commit failed: {}", e)))?;
+        Ok(())
+    }
+
     async fn subscribe(&self, subject: &str) -> Result<Box<dyn Subscription>, TransportError> {
         let subscriber = self.client
             .subscribe(subject.to_string())
             .await
             .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
-        Ok(Box::new(NatsSubscription::new(subscriber)))
+        Ok(Box::new(NatsSubscription::new(subscriber, None)))
+    }
+
+    async fn subscribe_filtered(
+        &self,
+        subject: &str,
+        filter: MessageFilter,
+    ) -> Result<Box<dyn Subscription>, TransportError> {
+        let subscriber = self.client
+            .subscribe(subject.to_string())
+            .await
+            .map_err(|e| TransportError::SubscribeFailed(e.to_string()))?;
+        Ok(Box::new(NatsSubscription::new(subscriber, Some(filter))))
+    }
+
+    async fn health_check(&self) -> Result<(), TransportError> {
+        // Flush round-trips to the server, so it fails if the connection is down
+        self.client
+            .flush()
+            .await
+            .map_err(|e| TransportError::ConnectionFailed(e.to_string()))
     }
 
     async fn request(
@@ -251,6 +367,30 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_batch_headers_mark_commit() {
+        let msg = TransportMessage {
+            subject: "test.batch".to_string(),
+            payload: Bytes::from("x"),
+            headers: HashMap::from([("Nats-Msg-Id".to_string(), "abc".to_string())]),
+            timestamp: 0,
+            sequence: None,
+        };
+        let headers = NatsTransport::batch_headers(&msg, "b1", 1, false);
+        assert_eq!(headers.get(BATCH_ID_HEADER).map(|v| v.as_str()), Some("b1"));
+        assert_eq!(headers.get(BATCH_SEQUENCE_HEADER).map(|v| v.as_str()), Some("1"));
+        assert_eq!(headers.get("Nats-Msg-Id").map(|v| v.as_str()), Some("abc"));
+        assert!(headers.get(BATCH_COMMIT_HEADER).is_none());
+
+        let headers = NatsTransport::batch_headers(&msg, "b1", 2, true);
+        assert_eq!(headers.get(BATCH_COMMIT_HEADER).map(|v| v.as_str()), Some("1"));
+    }
+
+    #[test]
+    fn test_batch_ids_are_unique() {
+        assert_ne!(NatsTransport::batch_id(), NatsTransport::batch_id());
+    }
+
     // Tests for subject_matches_pattern helper function
     #[test]
     fn test_subject_matches_pattern_with_multi_level_wildcard() {
@@ -0,0 +1,372 @@
+//! Redis-backed object storage for small hot objects (latest snapshots etc.)
+//!
+//! Each object is stored as two keys:
+//! - `{ns}:data:{bucket}:{key}` — the object bytes
+//! - `{ns}:meta:{bucket}:{key}` — a hash with `size`, `last_modified`, `etag`
+//!   and optionally `content_type`
+//!
+//! Writes go through a Lua script so data and metadata change atomically.
+//! Listing scans the metadata keys with `SCAN MATCH`.
+//!
+//! Gated behind the `redis-storage` feature flag.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::compression::Compression;
+use crate::error::StorageError;
+use crate::storage::{paginate, ListPage, ObjectMeta, Storage};
+
+/// Key namespace used when none is configured
+pub const DEFAULT_NAMESPACE: &str = "ssmd";
+
+/// Keys fetched per `SCAN` round-trip
+const SCAN_COUNT: usize = 1000;
+
+/// KEYS: data, meta. ARGV: mode (always|absent|match), expected etag, data,
+/// size, last_modified, etag. Returns 1 if written, 0 if the precondition failed.
+const PUT_SCRIPT: &str = r#"
+local mode = ARGV[1]
+if mode == 'absent' then
+  if redis.call('EXISTS', KEYS[1]) == 1 then return 0 end
+elseif mode == 'match' then
+  if redis.call('HGET', KEYS[2], 'etag') ~= ARGV[2] then return 0 end
+end
+redis.call('SET', KEYS[1], ARGV[3])
+redis.call('DEL', KEYS[2])
+redis.call('HSET', KEYS[2], 'size', ARGV[4], 'last_modified', ARGV[5], 'etag', ARGV[6])
+return 1
+"#;
+
+#[derive(Clone, Copy)]
+enum PutMode<'a> {
+    Always,
+    IfAbsent,
+    IfMatch(&'a str),
+}
+
+/// Escape glob metacharacters for use in a `SCAN MATCH` pattern
+fn escape_glob(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn parse_meta(key: &str, fields: &HashMap<String, String>) -> Option<ObjectMeta> {
+    if fields.is_empty() {
+        return None;
+    }
+    Some(ObjectMeta {
+        key: key.to_string(),
+        size: fields.get("size").and_then(|v| v.parse().ok()).unwrap_or(0),
+        stored_size: fields.get("size").and_then(|v| v.parse().ok()).unwrap_or(0),
+        compression: Compression::None,
+        last_modified: fields.get("last_modified").and_then(|v| v.parse().ok()).unwrap_or(0),
+        etag: fields.get("etag").cloned(),
+        content_type: fields.get("content_type").cloned(),
+    })
+}
+
+fn redis_err(e: redis::RedisError) -> StorageError {
+    StorageError::ReadFailed(e.to_string())
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system time should be after UNIX epoch")
+        .as_millis() as u64
+}
+
+pub struct RedisStorage {
+    conn: ConnectionManager,
+    namespace: String,
+}
+
+impl RedisStorage {
+    pub fn new(conn: ConnectionManager, namespace: impl Into<String>) -> Self {
+        Self { conn, namespace: namespace.into() }
+    }
+
+    /// Connect to Redis at `url`, storing keys under `namespace`
+    pub async fn connect(url: &str, namespace: impl Into<String>) -> Result<Self, StorageError> {
+        let client = redis::Client::open(url).map_err(redis_err)?;
+        let conn = ConnectionManager::new(client).await.map_err(redis_err)?;
+        Ok(Self::new(conn, namespace))
+    }
+
+    fn check_bucket(bucket: &str) -> Result<(), StorageError> {
+        // ':' separates bucket from key, so a bucket containing it would
+        // make prefix scans ambiguous
+        if bucket.is_empty() || bucket.contains(':') {
+            return Err(StorageError::InvalidKey(format!("invalid bucket name: {:?}", bucket)));
+        }
+        Ok(())
+    }
+
+    fn data_key(&self, bucket: &str, key: &str) -> String {
+        format!("{}:data:{}:{}", self.namespace, bucket, key)
+    }
+
+    fn meta_prefix(&self, bucket: &str) -> String {
+        format!("{}:meta:{}:", self.namespace, bucket)
+    }
+
+    fn meta_key(&self, bucket: &str, key: &str) -> String {
+        format!("{}{}", self.meta_prefix(bucket), key)
+    }
+
+    async fn put_with_mode(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        mode: PutMode<'_>,
+    ) -> Result<ObjectMeta, StorageError> {
+        Self::check_bucket(bucket)?;
+        let meta = ObjectMeta {
+            key: key.to_string(),
+            size: data.len() as u64,
+            stored_size: data.len() as u64,
+            compression: Compression::None,
+            last_modified: now_millis(),
+            etag: Some(format!("{:x}", md5::compute(&data))),
+            content_type: None,
+        };
+        let (mode_arg, expected) = match mode {
+            PutMode::Always => ("always", ""),
+            PutMode::IfAbsent => ("absent", ""),
+            PutMode::IfMatch(etag) => ("match", etag),
+        };
+
+        let mut conn = self.conn.clone();
+        let written: i64 = redis::Script::new(PUT_SCRIPT)
+            .key(self.data_key(bucket, key))
+            .key(self.meta_key(bucket, key))
+            .arg(mode_arg)
+            .arg(expected)
+            .arg(data.as_ref())
+            .arg(meta.size)
+            .arg(meta.last_modified)
+            .arg(meta.etag.as_deref().unwrap_or_default())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| StorageError::WriteFailed(e.to_string()))?;
+
+        if written == 0 {
+            return Err(match mode {
+                PutMode::IfAbsent => StorageError::AlreadyExists(format!("{}/{}", bucket, key)),
+                _ => StorageError::PreconditionFailed(format!(
+                    "{}/{}: etag does not match {}",
+                    bucket, key, expected
+                )),
+            });
+        }
+        Ok(meta)
+    }
+}
+
+#[async_trait]
+impl Storage for RedisStorage {
+    async fn put(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError> {
+        self.put_with_mode(bucket, key, data, PutMode::Always).await
+    }
+
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        size_hint: Option<u64>,
+    ) -> Result<ObjectMeta, StorageError> {
+        // Redis values are written whole, so buffer the stream
+        let mut buf = Vec::with_capacity(size_hint.unwrap_or(0) as usize);
+        reader.read_to_end(&mut buf).await?;
+        self.put(bucket, key, Bytes::from(buf)).await
+    }
+
+    async fn put_if_not_exists(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError> {
+        self.put_with_mode(bucket, key, data, PutMode::IfAbsent).await
+    }
+
+    async fn put_if_match(&self, bucket: &str, key: &str, data: Bytes, etag: &str) -> Result<ObjectMeta, StorageError> {
+        self.put_with_mode(bucket, key, data, PutMode::IfMatch(etag)).await
+    }
+
+    async fn get(&self, bucket: &str, key: &str) -> Result<Bytes, StorageError> {
+        Self::check_bucket(bucket)?;
+        let mut conn = self.conn.clone();
+        let data: Option<Vec<u8>> = conn.get(self.data_key(bucket, key)).await.map_err(redis_err)?;
+        data.map(Bytes::from)
+            .ok_or_else(|| StorageError::NotFound(format!("{}/{}", bucket, key)))
+    }
+
+    async fn exists(&self, bucket: &str, key: &str) -> Result<bool, StorageError> {
+        Self::check_bucket(bucket)?;
+        let mut conn = self.conn.clone();
+        conn.exists(self.data_key(bucket, key)).await.map_err(redis_err)
+    }
+
+    async fn head(&self, bucket: &str, key: &str) -> Result<ObjectMeta, StorageError> {
+        Self::check_bucket(bucket)?;
+        let mut conn = self.conn.clone();
+        let fields: HashMap<String, String> =
+            conn.hgetall(self.meta_key(bucket, key)).await.map_err(redis_err)?;
+        parse_meta(key, &fields).ok_or_else(|| StorageError::NotFound(format!("{}/{}", bucket, key)))
+    }
+
+    async fn delete(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        Self::check_bucket(bucket)?;
+        let mut conn = self.conn.clone();
+        conn.del::<_, ()>(vec![self.data_key(bucket, key), self.meta_key(bucket, key)])
+            .await
+            .map_err(|e| StorageError::WriteFailed(e.to_string()))
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError> {
+        Self::check_bucket(bucket)?;
+        let meta_prefix = self.meta_prefix(bucket);
+        let pattern = format!("{}{}*", escape_glob(&meta_prefix), escape_glob(prefix));
+        let mut conn = self.conn.clone();
+
+        let mut objects = Vec::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT)
+                .query_async(&mut conn)
+                .await
+                .map_err(redis_err)?;
+
+            if !keys.is_empty() {
+                let mut pipe = redis::pipe();
+                for key in &keys {
+                    pipe.hgetall(key);
+                }
+                let metas: Vec<HashMap<String, String>> =
+                    pipe.query_async(&mut conn).await.map_err(redis_err)?;
+                for (full_key, fields) in keys.iter().zip(metas) {
+                    // Deleted between SCAN and HGETALL: empty hash, skip
+                    if let Some(meta) = full_key
+                        .strip_prefix(&meta_prefix)
+                        .and_then(|key| parse_meta(key, &fields))
+                    {
+                        objects.push(meta);
+                    }
+                }
+            }
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        // SCAN may return a key more than once
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        objects.dedup_by(|a, b| a.key == b.key);
+        Ok(objects)
+    }
+
+    async fn list_page(&self, bucket: &str, prefix: &str, continuation_token: Option<&str>, max_keys: usize) -> Result<ListPage, StorageError> {
+        let objects = self.list(bucket, prefix).await?;
+        Ok(paginate(objects, continuation_token, max_keys))
+    }
+
+    async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        // Buckets are just key prefixes
+        Self::check_bucket(bucket)
+    }
+
+    async fn health_check(&self) -> Result<(), StorageError> {
+        let mut conn = self.conn.clone();
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map(|_| ())
+            .map_err(redis_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_glob() {
+        assert_eq!(escape_glob("snap/latest"), "snap/latest");
+        assert_eq!(escape_glob("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+
+    #[test]
+    fn test_parse_meta() {
+        assert!(parse_meta("k", &HashMap::new()).is_none());
+
+        let fields = HashMap::from([
+            ("size".to_string(), "11".to_string()),
+            ("last_modified".to_string(), "1703318400000".to_string()),
+            ("etag".to_string(), "abc".to_string()),
+        ]);
+        let meta = parse_meta("snap/latest.json", &fields).unwrap();
+        assert_eq!(meta.key, "snap/latest.json");
+        assert_eq!(meta.size, 11);
+        assert_eq!(meta.last_modified, 1703318400000);
+        assert_eq!(meta.etag.as_deref(), Some("abc"));
+        assert!(meta.content_type.is_none());
+    }
+
+    #[test]
+    fn test_check_bucket() {
+        assert!(RedisStorage::check_bucket("snapshots").is_ok());
+        assert!(RedisStorage::check_bucket("").is_err());
+        assert!(RedisStorage::check_bucket("a:b").is_err());
+    }
+
+    // Requires a running Redis server
+    // Run: docker run -p 6379:6379 redis:latest
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_storage_roundtrip() {
+        let namespace = format!("ssmd-test-{}", now_millis());
+        let storage = RedisStorage::connect("redis://localhost:6379", namespace)
+            .await
+            .unwrap();
+
+        let meta = storage.put("snap", "kalshi/latest.json", Bytes::from("v1")).await.unwrap();
+        storage.put("snap", "kraken/latest.json", Bytes::from("v2")).await.unwrap();
+        assert_eq!(storage.get("snap", "kalshi/latest.json").await.unwrap(), Bytes::from("v1"));
+        assert_eq!(storage.head("snap", "kalshi/latest.json").await.unwrap().size, 2);
+
+        let result = storage.put_if_not_exists("snap", "kalshi/latest.json", Bytes::from("x")).await;
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+        let result = storage.put_if_match("snap", "kalshi/latest.json", Bytes::from("x"), "stale").await;
+        assert!(matches!(result, Err(StorageError::PreconditionFailed(_))));
+        storage
+            .put_if_match("snap", "kalshi/latest.json", Bytes::from("v3"), meta.etag.as_deref().unwrap())
+            .await
+            .unwrap();
+
+        let listed = storage.list("snap", "kalshi/").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "kalshi/latest.json");
+
+        storage.delete("snap", "kalshi/latest.json").await.unwrap();
+        assert!(!storage.exists("snap", "kalshi/latest.json").await.unwrap());
+        assert_eq!(storage.list("snap", "").await.unwrap().len(), 1);
+        storage.delete("snap", "kraken/latest.json").await.unwrap();
+    }
+}
@@ -1,24 +1,104 @@
 use async_trait::async_trait;
 use bytes::Bytes;
+use tokio::io::AsyncRead;
 
+use crate::compression::Compression;
 use crate::error::StorageError;
 
 /// Object metadata
 #[derive(Debug, Clone)]
 pub struct ObjectMeta {
     pub key: String,
+    /// Uncompressed size (what `get` returns)
     pub size: u64,
+    /// Size as stored, after compression
+    pub stored_size: u64,
+    pub compression: Compression,
     pub last_modified: u64,
     pub etag: Option<String>,
     pub content_type: Option<String>,
 }
 
+/// One page of a paginated listing
+#[derive(Debug, Clone)]
+pub struct ListPage {
+    pub objects: Vec<ObjectMeta>,
+    /// Pass back as `continuation_token` to fetch the next page; None when done
+    pub next_token: Option<String>,
+}
+
+/// Paginate a listing sorted by key. The token is the last key of the
+/// previous page, so pages never overlap even if objects are added between calls.
+pub(crate) fn paginate(
+    sorted: impl IntoIterator<Item = ObjectMeta>,
+    continuation_token: Option<&str>,
+    max_keys: usize,
+) -> ListPage {
+    let mut iter = sorted
+        .into_iter()
+        .filter(|meta| continuation_token.is_none_or(|token| meta.key.as_str() > token));
+    let objects: Vec<ObjectMeta> = iter.by_ref().take(max_keys).collect();
+    let next_token = match iter.next() {
+        Some(_) => objects.last().map(|meta| meta.key.clone()),
+        None => None,
+    };
+    ListPage { objects, next_token }
+}
+
 /// Storage abstraction for object storage (S3, local, etc.)
 #[async_trait]
 pub trait Storage: Send + Sync {
     /// Put an object
     async fn put(&self, bucket: &str, key: &str, data: Bytes) -> Result<ObjectMeta, StorageError>;
 
+    /// Put an object stored with `compression`; `get` transparently
+    /// decompresses it. Backends without compression support accept only
+    /// `Compression::None`.
+    async fn put_compressed(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        compression: Compression,
+    ) -> Result<ObjectMeta, StorageError> {
+        match compression {
+            Compression::None => self.put(bucket, key, data).await,
+            other => Err(StorageError::WriteFailed(format!(
+                "{:?} compression not supported by this backend",
+                other
+            ))),
+        }
+    }
+
+    /// Put an object by streaming from `reader` without buffering it whole.
+    /// `size_hint` lets backends pre-size buffers or pick multipart upload.
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        size_hint: Option<u64>,
+    ) -> Result<ObjectMeta, StorageError>;
+
+    /// Put an object only if nothing exists at `key`.
+    /// Fails with `StorageError::AlreadyExists` otherwise.
+    async fn put_if_not_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+    ) -> Result<ObjectMeta, StorageError>;
+
+    /// Put an object only if the current object's etag equals `etag`.
+    /// Fails with `StorageError::PreconditionFailed` on mismatch or if missing.
+    async fn put_if_match(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        etag: &str,
+    ) -> Result<ObjectMeta, StorageError>;
+
     /// Get an object
     async fn get(&self, bucket: &str, key: &str) -> Result<Bytes, StorageError>;
 
@@ -34,8 +114,23 @@ pub trait Storage: Send + Sync {
     /// List objects with prefix
     async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<ObjectMeta>, StorageError>;
 
+    /// List up to `max_keys` objects with prefix in key order, starting after
+    /// `continuation_token` (the `next_token` of the previous page)
+    async fn list_page(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        continuation_token: Option<&str>,
+        max_keys: usize,
+    ) -> Result<ListPage, StorageError>;
+
     /// Create a bucket
     async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError>;
+
+    /// Check that the backend is usable. In-process backends are always healthy.
+    async fn health_check(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +142,8 @@ mod tests {
         let meta = ObjectMeta {
             key: "2025/12/23/kalshi.jsonl".to_string(),
             size: 1024,
+            stored_size: 1024,
+            compression: Compression::None,
             last_modified: 1703318400,
             etag: Some("abc123".to_string()),
             content_type: Some("application/jsonl".to_string()),
@@ -55,4 +152,28 @@ mod tests {
         assert_eq!(meta.key, "2025/12/23/kalshi.jsonl");
         assert_eq!(meta.size, 1024);
     }
+
+    fn meta(key: &str) -> ObjectMeta {
+        ObjectMeta {
+            key: key.to_string(),
+            size: 0,
+            stored_size: 0,
+            compression: Compression::None,
+            last_modified: 0,
+            etag: None,
+            content_type: None,
+        }
+    }
+
+    #[test]
+    fn test_paginate_last_page_has_no_token() {
+        let page = paginate(vec![meta("a"), meta("b")], None, 2);
+        assert_eq!(page.objects.len(), 2);
+        assert!(page.next_token.is_none());
+
+        let page = paginate(vec![meta("a"), meta("b"), meta("c")], Some("b"), 2);
+        assert_eq!(page.objects.len(), 1);
+        assert_eq!(page.objects[0].key, "c");
+        assert!(page.next_token.is_none());
+    }
 }
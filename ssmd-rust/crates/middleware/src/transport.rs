@@ -1,10 +1,17 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::error::TransportError;
 
+/// Header carrying the connector's capture time (Unix epoch microseconds)
+pub const CAPTURE_TS_HEADER: &str = "Ssmd-Capture-Ts";
+
+/// Header carrying the exchange-provided sequence number, when there is one
+pub const SOURCE_SEQ_HEADER: &str = "Ssmd-Source-Seq";
+
 /// Message envelope with metadata
 #[derive(Debug, Clone)]
 pub struct TransportMessage {
@@ -15,6 +22,10 @@ pub struct TransportMessage {
     pub sequence: Option<u64>,
 }
 
+/// Client-side predicate applied to each message before delivery.
+/// Messages for which the filter returns `false` are dropped.
+pub type MessageFilter = Arc<dyn Fn(&TransportMessage) -> bool + Send + Sync>;
+
 /// Subscription handle for receiving messages
 #[async_trait]
 pub trait Subscription: Send + Sync {
@@ -45,6 +56,31 @@ pub trait Transport: Send + Sync {
     /// Subscribe to a subject pattern
     async fn subscribe(&self, subject: &str) -> Result<Box<dyn Subscription>, TransportError>;
 
+    /// Subscribe to a subject pattern, delivering only messages accepted by `filter`
+    async fn subscribe_filtered(
+        &self,
+        subject: &str,
+        filter: MessageFilter,
+    ) -> Result<Box<dyn Subscription>, TransportError>;
+
+    /// Publish a batch of messages.
+    ///
+    /// Messages are delivered in slice order; `timestamp` and `sequence` on the
+    /// inputs are ignored. Backends that override this make the batch
+    /// all-or-nothing for the readers they document: those observe every
+    /// message or none, with no other publish interleaved. For NATS that is
+    /// only JetStream consumers of the batch's stream; core subscribers see
+    /// each message as it is sent (see `NatsTransport::publish_batch`). The
+    /// default publishes sequentially and is NOT atomic — a failure part-way
+    /// leaves the earlier messages published.
+    async fn publish_batch(&self, messages: &[TransportMessage]) -> Result<(), TransportError> {
+        for msg in messages {
+            self.publish_with_headers(&msg.subject, msg.payload.clone(), msg.headers.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Request/reply pattern with timeout
     async fn request(
         &self,
@@ -52,6 +88,11 @@ pub trait Transport: Send + Sync {
         payload: Bytes,
         timeout: Duration,
     ) -> Result<TransportMessage, TransportError>;
+
+    /// Check that the backend is reachable. In-process backends are always healthy.
+    async fn health_check(&self) -> Result<(), TransportError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,121 @@
+//! Integration tests for Kafka transport
+//!
+//! Run with: cargo test -p ssmd-middleware --features kafka --test kafka_integration -- --ignored
+//! Uses `KAFKA_BROKERS` if set, otherwise starts a Kafka container (requires Docker).
+#![cfg(feature = "kafka")]
+
+use bytes::Bytes;
+use ssmd_middleware::{KafkaTransport, Subscription, Transport, TransportMessage};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+static BROKERS: OnceCell<String> = OnceCell::const_new();
+
+async fn brokers() -> String {
+    BROKERS
+        .get_or_init(|| async {
+            if let Ok(brokers) = std::env::var("KAFKA_BROKERS") {
+                return brokers;
+            }
+
+            use testcontainers::runners::AsyncRunner;
+            use testcontainers_modules::kafka::{Kafka, KAFKA_PORT};
+
+            let container = Kafka::default()
+                .start()
+                .await
+                .expect("failed to start Kafka container (is Docker running?)");
+            let host = container.get_host().await.unwrap();
+            let port = container.get_host_port_ipv4(KAFKA_PORT).await.unwrap();
+
+            // Leak the container so it lives for the process lifetime
+            Box::leak(Box::new(container));
+            format!("{}:{}", host, port)
+        })
+        .await
+        .clone()
+}
+
+/// Publish until the subscriber sees a message: ephemeral consumers start at
+/// the latest offset, so anything sent before partition assignment is missed.
+async fn publish_until_received(
+    transport: &KafkaTransport,
+    sub: &mut Box<dyn Subscription>,
+    subject: &str,
+    headers: HashMap<String, String>,
+) -> TransportMessage {
+    for _ in 0..30 {
+        transport
+            .publish_with_headers(subject, Bytes::from("test message"), headers.clone())
+            .await
+            .expect("Failed to publish");
+        if let Ok(msg) = tokio::time::timeout(Duration::from_secs(1), sub.next()).await {
+            return msg.expect("Failed to receive");
+        }
+    }
+    panic!("no message received on {}", subject);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_kafka_publish_subscribe_roundtrip() {
+    let transport = KafkaTransport::connect(&brokers().await).expect("Failed to connect to Kafka");
+    transport.health_check().await.expect("Kafka unhealthy");
+
+    // Topic is test-env.kraken.json.trade.BTC-USD; the original subject round-trips
+    let subject = "test-env.kraken.json.trade.BTC/USD";
+
+    let mut sub = transport.subscribe(subject).await.expect("Failed to subscribe");
+    let headers = HashMap::from([("Nats-Msg-Id".to_string(), "abc".to_string())]);
+    let msg = publish_until_received(&transport, &mut sub, subject, headers).await;
+
+    assert_eq!(msg.subject, subject);
+    assert_eq!(msg.payload, Bytes::from("test message"));
+    assert_eq!(msg.headers.get("Nats-Msg-Id").map(String::as_str), Some("abc"));
+    assert_eq!(msg.sequence, None);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_kafka_durable_subscription_resumes_after_ack() {
+    let transport = KafkaTransport::connect(&brokers().await).expect("Failed to connect to Kafka");
+    // Unique per run so a long-lived broker doesn't carry over committed offsets
+    let run = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let subject = format!("test-env.kafka.durable.resume{}", run);
+    let subject = subject.as_str();
+    let group = format!("ssmd-test-durable-{}", run);
+    let group = group.as_str();
+
+    for n in 0..3 {
+        transport
+            .publish(subject, Bytes::from(format!("{}", n)))
+            .await
+            .expect("Failed to publish");
+    }
+
+    // Durable subscriptions start from the earliest offset for a new group
+    let mut sub = transport.subscribe_durable(subject, group).await.unwrap();
+    let first = tokio::time::timeout(Duration::from_secs(30), sub.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.payload, Bytes::from("0"));
+    sub.ack(first.sequence.expect("durable messages carry a sequence"))
+        .await
+        .unwrap();
+    // Async commit: give it a moment before leaving the group
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    sub.unsubscribe().await.unwrap();
+
+    // A new member of the same group resumes after the acked record
+    let mut sub = transport.subscribe_durable(subject, group).await.unwrap();
+    let next = tokio::time::timeout(Duration::from_secs(30), sub.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(next.payload, Bytes::from("1"));
+}
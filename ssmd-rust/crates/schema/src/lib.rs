@@ -55,6 +55,28 @@ mod tests {
         assert_eq!(reader.get_ask_price(), 0.46);
     }
 
+    #[test]
+    fn test_build_quote() {
+        let mut message = Builder::new_default();
+        {
+            let mut quote = message.init_root::<quote::Builder>();
+            quote.set_timestamp(1703318400000000000);
+            quote.set_ticker("KXTEST-123");
+            quote.set_bid_price(0.45);
+            quote.set_bid_size(120);
+            quote.set_ask_price(0.46);
+            quote.set_ask_size(80);
+        }
+
+        let reader = message.get_root_as_reader::<quote::Reader>().unwrap();
+        assert_eq!(reader.get_timestamp(), 1703318400000000000);
+        assert_eq!(reader.get_ticker().unwrap(), "KXTEST-123");
+        assert_eq!(reader.get_bid_price(), 0.45);
+        assert_eq!(reader.get_bid_size(), 120);
+        assert_eq!(reader.get_ask_price(), 0.46);
+        assert_eq!(reader.get_ask_size(), 80);
+    }
+
     #[test]
     fn test_build_order_book_update() {
         let mut message = Builder::new_default();
@@ -62,6 +84,8 @@ mod tests {
             let mut update = message.init_root::<order_book_update::Builder>();
             update.set_timestamp(1703318400000000000);
             update.set_ticker("BTCUSD");
+            update.set_depth(10);
+            update.set_checksum(3_310_070_434);
 
             {
                 let mut bids = update.reborrow().init_bids(2);
@@ -89,9 +113,25 @@ mod tests {
             .get_root_as_reader::<order_book_update::Reader>()
             .unwrap();
         assert_eq!(reader.get_ticker().unwrap(), "BTCUSD");
+        assert_eq!(reader.get_depth(), 10);
+        assert_eq!(reader.get_checksum(), 3_310_070_434);
 
         let bids = reader.get_bids().unwrap();
         assert_eq!(bids.len(), 2);
         assert_eq!(bids.get(0).get_price(), 100.0);
     }
+
+    #[test]
+    fn test_order_book_update_depth_and_checksum_default_to_zero() {
+        let mut message = Builder::new_default();
+        message
+            .init_root::<order_book_update::Builder>()
+            .set_ticker("BTCUSD");
+
+        let reader = message
+            .get_root_as_reader::<order_book_update::Reader>()
+            .unwrap();
+        assert_eq!(reader.get_depth(), 0);
+        assert_eq!(reader.get_checksum(), 0);
+    }
 }
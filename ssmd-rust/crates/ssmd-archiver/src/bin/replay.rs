@@ -0,0 +1,124 @@
+//! ssmd-replay: publish a day's archived JSONL.gz files back into NATS
+//!
+//! Reads the day's manifest, replays each file's records in NATS sequence
+//! order, and publishes them under `--subject-prefix` either paced to the
+//! original inter-arrival gaps (scaled by `--speed`) or as fast as possible.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::Parser;
+use tracing::{error, info, warn};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+use ssmd_archiver::replay::{load_manifest, read_file, replay_order, replay_subject, Pacer};
+use ssmd_middleware::{CAPTURE_TS_HEADER, SOURCE_SEQ_HEADER};
+
+#[derive(Parser, Debug)]
+#[command(name = "ssmd-replay")]
+#[command(about = "Replay archived SSMD market data into NATS")]
+struct Args {
+    /// Path to the day's manifest.json; files are read from its directory
+    #[arg(short, long)]
+    manifest: PathBuf,
+
+    /// NATS server URL
+    #[arg(long, env = "NATS_URL", default_value = "nats://localhost:4222")]
+    nats_url: String,
+
+    /// Subject prefix; records go to {prefix}.json.{type}.{ticker}
+    #[arg(long)]
+    subject_prefix: String,
+
+    /// Replay speed relative to the original arrival rate (0 = as fast as possible)
+    #[arg(long, default_value = "1.0")]
+    speed: f64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let args = Args::parse();
+    if !args.speed.is_finite() || args.speed < 0.0 {
+        return Err(format!("--speed must be >= 0, got {}", args.speed).into());
+    }
+
+    let manifest = load_manifest(&args.manifest).map_err(|e| {
+        error!(error = %e, path = ?args.manifest, "Failed to load manifest");
+        e
+    })?;
+    let dir = args
+        .manifest
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+
+    info!(
+        feed = %manifest.feed,
+        date = %manifest.date,
+        files = manifest.files.len(),
+        subject_prefix = %args.subject_prefix,
+        speed = args.speed,
+        "Starting replay"
+    );
+
+    let client = async_nats::connect(&args.nats_url).await?;
+    let mut pacer = Pacer::new(args.speed);
+    let mut last_seq: Option<u64> = None;
+    let mut published: u64 = 0;
+
+    for entry in replay_order(&manifest.files) {
+        let path = dir.join(&entry.name);
+        let records = match read_file(&path) {
+            Ok(records) => records,
+            Err(e) => {
+                warn!(file = %entry.name, error = %e, "Skipping unreadable archive file");
+                continue;
+            }
+        };
+
+        let mut file_published: u64 = 0;
+        for record in records {
+            // Files can overlap after a redelivery; each sequence goes out once
+            if let (Some(seq), Some(last)) = (record.nats_seq, last_seq) {
+                if seq <= last {
+                    continue;
+                }
+            }
+            if record.nats_seq.is_some() {
+                last_seq = record.nats_seq;
+            }
+
+            if let Some(delay) = pacer.delay(record.received_at_micros, Instant::now()) {
+                tokio::time::sleep(delay).await;
+            }
+
+            let subject = replay_subject(&args.subject_prefix, &manifest.feed, &record.payload);
+            let mut headers = async_nats::HeaderMap::new();
+            if let Some(ts) = record.capture.capture_ts_micros {
+                headers.insert(CAPTURE_TS_HEADER, ts.to_string().as_str());
+            }
+            if let Some(source_seq) = record.capture.source_seq {
+                headers.insert(SOURCE_SEQ_HEADER, source_seq.to_string().as_str());
+            }
+            client
+                .publish_with_headers(subject, headers, record.payload.into())
+                .await?;
+            file_published += 1;
+        }
+
+        client.flush().await?;
+        published += file_published;
+        info!(file = %entry.name, records = file_published, "Replayed file");
+    }
+
+    info!(published, "Replay complete");
+    Ok(())
+}
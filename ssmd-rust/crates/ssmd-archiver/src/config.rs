@@ -1,6 +1,15 @@
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
+use thiserror::Error;
+
+/// Every problem found by [`Config::validate`], reported together so a bad
+/// deploy can be fixed in one pass.
+#[derive(Error, Debug)]
+#[error("invalid config: {}", .problems.join("; "))]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
@@ -13,6 +22,43 @@ pub struct Config {
 pub struct NatsConfig {
     pub url: String,
     pub streams: Vec<StreamConfig>,
+    /// How long the server waits for an ack before redelivering (e.g. "30s");
+    /// server default if unset
+    #[serde(default)]
+    pub ack_wait: Option<String>,
+    /// Deliveries before the server stops redelivering a message; unlimited
+    /// if unset
+    #[serde(default)]
+    pub max_deliver: Option<i64>,
+    /// Where messages that fail to write on their last delivery are
+    /// republished instead of being left unacked
+    #[serde(default)]
+    pub dead_letter: Option<DeadLetterConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DeadLetterConfig {
+    /// Literal subject (no wildcards); a stream should capture it
+    pub subject: String,
+}
+
+/// Redelivery settings applied to every stream's consumer
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryPolicy {
+    pub ack_wait: Option<Duration>,
+    pub max_deliver: Option<i64>,
+    pub dead_letter_subject: Option<String>,
+}
+
+impl NatsConfig {
+    /// Parsed redelivery settings. Call after [`Config::validate`].
+    pub fn delivery_policy(&self) -> Result<DeliveryPolicy, crate::ArchiverError> {
+        Ok(DeliveryPolicy {
+            ack_wait: self.ack_wait.as_deref().map(parse_duration).transpose()?,
+            max_deliver: self.max_deliver,
+            dead_letter_subject: self.dead_letter.as_ref().map(|d| d.subject.clone()),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -25,15 +71,19 @@ pub struct StreamConfig {
     pub consumer: String,
     /// Subject filter pattern
     pub filter: String,
-    /// Per-stream feed name (falls back to storage.feed if empty)
+    /// Per-stream feed name (derived from the filter if empty)
     #[serde(default)]
     pub feed: String,
+    /// Per-stream rotation (falls back to the top-level rotation if unset)
+    #[serde(default)]
+    pub rotation: Option<RotationConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct StorageConfig {
     pub path: PathBuf,
-    /// Global feed name (used as fallback when per-stream feed is not set)
+    /// Global feed name (used when a stream sets no feed and its filter has
+    /// no literal feed token)
     #[serde(default)]
     pub feed: String,
 }
@@ -46,49 +96,197 @@ pub struct RotationConfig {
 impl Config {
     pub fn load(path: &std::path::Path) -> Result<Self, crate::ArchiverError> {
         let content = std::fs::read_to_string(path)?;
-        serde_yaml::from_str(&content).map_err(|e| crate::ArchiverError::Config(e.to_string()))
+        let config: Self = serde_yaml::from_str(&content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check values that deserialize fine but would fail (or silently
+    /// misbehave) once the archiver is running.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if let Err(reason) = check_rotation(&self.rotation) {
+            problems.push(format!("rotation.interval {}", reason));
+        }
+
+        if let Err(reason) = check_storage_path(&self.storage.path) {
+            problems.push(format!("storage.path {:?}: {}", self.storage.path, reason));
+        }
+
+        if let Err(reason) = check_delivery(&self.nats) {
+            problems.push(reason);
+        }
+
+        if self.nats.streams.is_empty() {
+            problems.push("nats.streams: at least one stream is required".to_string());
+        }
+        for stream in &self.nats.streams {
+            if let Some(rotation) = &stream.rotation {
+                if let Err(reason) = check_rotation(rotation) {
+                    problems.push(format!(
+                        "nats.streams[{}].rotation.interval {}",
+                        stream.name, reason
+                    ));
+                }
+            }
+            if let Err(reason) = check_filter(&stream.filter) {
+                problems.push(format!(
+                    "nats.streams[{}].filter {:?}: {}",
+                    stream.name, stream.filter, reason
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { problems })
+        }
     }
 
-    /// Fill empty per-stream feed names from the global storage.feed value.
+    /// Fill empty per-stream feed names from each stream's filter
+    /// (`{env}.{feed}...`), falling back to the global storage.feed value.
     pub fn resolve_feeds(&mut self) {
         let global_feed = &self.storage.feed;
         for stream in &mut self.nats.streams {
             if stream.feed.is_empty() {
-                stream.feed = global_feed.clone();
+                stream.feed = feed_from_filter(&stream.filter)
+                    .unwrap_or(global_feed)
+                    .to_string();
             }
         }
     }
 }
 
+fn check_rotation(rotation: &RotationConfig) -> Result<(), String> {
+    rotation.parse_interval().map(|_| ()).map_err(|e| {
+        let reason = match e {
+            crate::ArchiverError::Config(msg) => msg,
+            other => other.to_string(),
+        };
+        format!("{:?}: {}", rotation.interval, reason)
+    })
+}
+
+fn check_delivery(nats: &NatsConfig) -> Result<(), String> {
+    if let Some(ack_wait) = &nats.ack_wait {
+        parse_duration(ack_wait).map_err(|e| {
+            let reason = match e {
+                crate::ArchiverError::Config(msg) => msg,
+                other => other.to_string(),
+            };
+            format!("nats.ack_wait {:?}: {}", ack_wait, reason)
+        })?;
+    }
+    if let Some(max_deliver) = nats.max_deliver {
+        if max_deliver < 1 {
+            return Err(format!("nats.max_deliver {}: must be at least 1", max_deliver));
+        }
+    }
+    if let Some(dead_letter) = &nats.dead_letter {
+        let subject = &dead_letter.subject;
+        if subject.is_empty()
+            || subject
+                .split('.')
+                .any(|t| t.is_empty() || t == "*" || t == ">")
+        {
+            return Err(format!(
+                "nats.dead_letter.subject {:?}: must be a literal subject",
+                subject
+            ));
+        }
+        if nats.max_deliver.is_none() {
+            return Err(
+                "nats.dead_letter requires nats.max_deliver (messages are never exhausted otherwise)"
+                    .to_string(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The storage path need not exist yet (the writer creates it), but its
+/// nearest existing ancestor must be a directory without its write bits
+/// cleared. A read-only mount still only shows up on the first write.
+fn check_storage_path(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("must not be empty".to_string());
+    }
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+        return Err("no existing parent directory".to_string());
+    };
+    let meta = std::fs::metadata(existing).map_err(|e| format!("{}: {}", existing.display(), e))?;
+    if !meta.is_dir() {
+        return Err(format!("{} is not a directory", existing.display()));
+    }
+    if meta.permissions().readonly() {
+        return Err(format!("{} is not writable", existing.display()));
+    }
+    Ok(())
+}
+
+/// Filters must look like `{env}.{feed}[.{shard}].json.>` (optionally
+/// narrowed after `json`, e.g. `prod.kalshi.json.lifecycle.>`), the subject
+/// layout connectors publish with `SubjectBuilder`. Anything else archives
+/// nothing, or the wrong feed.
+fn check_filter(filter: &str) -> Result<(), String> {
+    const EXPECTED: &str = "expected {env}.{feed}[.{shard}].json.>";
+    let tokens: Vec<&str> = filter.split('.').collect();
+    if tokens.iter().any(|t| t.is_empty()) {
+        return Err(format!("empty subject token; {}", EXPECTED));
+    }
+    if tokens.len() < 4 || tokens[..2].iter().any(|t| *t == "*" || *t == ">") {
+        return Err(EXPECTED.to_string());
+    }
+    if !matches!(tokens.iter().position(|t| *t == "json"), Some(2) | Some(3)) {
+        return Err(format!("missing json token; {}", EXPECTED));
+    }
+    Ok(())
+}
+
+/// Feed token of a `{env}.{feed}...` filter, if it is a literal.
+fn feed_from_filter(filter: &str) -> Option<&str> {
+    filter
+        .split('.')
+        .nth(1)
+        .filter(|t| !t.is_empty() && *t != "*" && *t != ">")
+}
+
 impl RotationConfig {
     /// Parse interval string like "15m", "1h", "1d" to Duration
     pub fn parse_interval(&self) -> Result<Duration, crate::ArchiverError> {
-        let s = self.interval.trim();
-        if s.is_empty() {
-            return Err(crate::ArchiverError::Config("Empty interval".to_string()));
-        }
+        parse_duration(&self.interval)
+    }
+}
 
-        let (num_str, unit) = s.split_at(s.len() - 1);
-        let num: u64 = num_str
-            .parse()
-            .map_err(|_| crate::ArchiverError::Config(format!("Invalid interval: {}", s)))?;
+/// Parse a duration string like "30s", "15m", "1h", "1d"
+fn parse_duration(s: &str) -> Result<Duration, crate::ArchiverError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(crate::ArchiverError::Config("Empty interval".to_string()));
+    }
 
-        if num == 0 {
-            return Err(crate::ArchiverError::Config(
-                "Rotation interval must be greater than zero".to_string(),
-            ));
-        }
+    let (num_str, unit) = s.split_at(s.len() - 1);
+    let num: u64 = num_str
+        .parse()
+        .map_err(|_| crate::ArchiverError::Config(format!("Invalid interval: {}", s)))?;
 
-        match unit {
-            "s" => Ok(Duration::from_secs(num)),
-            "m" => Ok(Duration::from_secs(num * 60)),
-            "h" => Ok(Duration::from_secs(num * 60 * 60)),
-            "d" => Ok(Duration::from_secs(num * 60 * 60 * 24)),
-            _ => Err(crate::ArchiverError::Config(format!(
-                "Unknown unit: {}",
-                unit
-            ))),
-        }
+    if num == 0 {
+        return Err(crate::ArchiverError::Config(
+            "Interval must be greater than zero".to_string(),
+        ));
+    }
+
+    match unit {
+        "s" => Ok(Duration::from_secs(num)),
+        "m" => Ok(Duration::from_secs(num * 60)),
+        "h" => Ok(Duration::from_secs(num * 60 * 60)),
+        "d" => Ok(Duration::from_secs(num * 60 * 60 * 24)),
+        _ => Err(crate::ArchiverError::Config(format!(
+            "Unknown unit: {}",
+            unit
+        ))),
     }
 }
 
@@ -318,4 +516,149 @@ rotation:
         assert_eq!(config.nats.streams[0].feed, "kalshi");
         assert_eq!(config.nats.streams[1].feed, "kraken-futures");
     }
+
+    #[test]
+    fn test_per_stream_feed_from_filter_and_rotation() {
+        let yaml = r#"
+nats:
+  url: nats://localhost:4222
+  streams:
+    - name: crypto
+      stream: PROD_KALSHI_CRYPTO
+      consumer: archiver-kalshi
+      filter: "prod.kalshi.json.>"
+    - name: futures
+      stream: PROD_KRAKEN_FUTURES
+      consumer: archiver-kraken
+      filter: "prod.kraken-futures.json.>"
+      rotation:
+        interval: 1h
+
+storage:
+  path: /data/ssmd
+  feed: fallback
+
+rotation:
+  interval: 15m
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let mut config = Config::load(file.path()).unwrap();
+        config.resolve_feeds();
+        assert_eq!(config.nats.streams[0].feed, "kalshi");
+        assert_eq!(config.nats.streams[1].feed, "kraken-futures");
+        assert!(config.nats.streams[0].rotation.is_none());
+        assert_eq!(
+            config.nats.streams[1]
+                .rotation
+                .as_ref()
+                .unwrap()
+                .parse_interval()
+                .unwrap(),
+            Duration::from_secs(60 * 60)
+        );
+    }
+
+    #[test]
+    fn test_load_reports_all_problems() {
+        let storage = NamedTempFile::new().unwrap();
+        let yaml = format!(
+            r#"
+nats:
+  url: nats://localhost:4222
+  streams:
+    - name: main
+      stream: PROD_KALSHI
+      consumer: archiver-kalshi
+      filter: "prod.kalshi.>"
+
+storage:
+  path: {}/ssmd
+
+rotation:
+  interval: 15x
+"#,
+            storage.path().display()
+        );
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let err = Config::load(file.path()).unwrap_err();
+        let crate::ArchiverError::Invalid(ConfigError { problems }) = err else {
+            panic!("expected validation error, got {err:?}");
+        };
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("rotation.interval"));
+        assert!(problems[1].contains("is not a directory"));
+        assert!(problems[2].starts_with("nats.streams[main].filter"));
+    }
+
+    #[test]
+    fn test_delivery_policy() {
+        let yaml = r#"
+nats:
+  url: nats://localhost:4222
+  ack_wait: 45s
+  max_deliver: 5
+  dead_letter:
+    subject: prod.archiver.dead-letter
+  streams:
+    - name: main
+      stream: PROD_KALSHI
+      consumer: archiver-kalshi
+      filter: "prod.kalshi.json.>"
+
+storage:
+  path: /tmp
+
+rotation:
+  interval: 15m
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let config = Config::load(file.path()).unwrap();
+        let policy = config.nats.delivery_policy().unwrap();
+        assert_eq!(policy.ack_wait, Some(Duration::from_secs(45)));
+        assert_eq!(policy.max_deliver, Some(5));
+        assert_eq!(
+            policy.dead_letter_subject.as_deref(),
+            Some("prod.archiver.dead-letter")
+        );
+    }
+
+    #[test]
+    fn test_check_delivery() {
+        let nats = |ack_wait: Option<&str>, max_deliver: Option<i64>, dead_letter: Option<&str>| {
+            NatsConfig {
+                url: "nats://localhost:4222".to_string(),
+                streams: vec![],
+                ack_wait: ack_wait.map(str::to_string),
+                max_deliver,
+                dead_letter: dead_letter.map(|subject| DeadLetterConfig {
+                    subject: subject.to_string(),
+                }),
+            }
+        };
+
+        assert!(check_delivery(&nats(None, None, None)).is_ok());
+        assert!(check_delivery(&nats(Some("30s"), Some(3), Some("prod.dlq"))).is_ok());
+        assert!(check_delivery(&nats(Some("30x"), None, None)).is_err());
+        assert!(check_delivery(&nats(None, Some(0), None)).is_err());
+        assert!(check_delivery(&nats(None, Some(3), Some("prod.dlq.>"))).is_err());
+        // Without max_deliver nothing is ever dead-lettered
+        assert!(check_delivery(&nats(None, None, Some("prod.dlq"))).is_err());
+    }
+
+    #[test]
+    fn test_check_filter() {
+        assert!(check_filter("prod.kalshi.json.>").is_ok());
+        assert!(check_filter("prod.kalshi.politics.json.>").is_ok());
+        assert!(check_filter("prod.kalshi.json.lifecycle.>").is_ok());
+        assert!(check_filter("prod.kalshi.>").is_err());
+        assert!(check_filter("prod.*.json.>").is_err());
+        assert!(check_filter("prod..json.>").is_err());
+        assert!(check_filter("prod.kalshi.a.b.json.>").is_err());
+    }
 }
@@ -1,16 +1,81 @@
 use thiserror::Error;
 
+/// Underlying NATS client error; async-nats uses a distinct type per operation
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Display strings include the source so `error = %e` logs the whole chain.
 #[derive(Error, Debug)]
 pub enum ArchiverError {
+    /// Invalid configuration value or stream setup that would drop data
     #[error("Configuration error: {0}")]
     Config(String),
 
-    #[error("NATS error: {0}")]
-    Nats(String),
+    /// Config file is not valid YAML for `Config`
+    #[error("Configuration error: {0}")]
+    ConfigParse(#[from] serde_yaml::Error),
+
+    /// NATS rejected our credentials or permissions
+    #[error("NATS authentication failed: {0}")]
+    Auth(#[source] async_nats::error::Error<async_nats::ConnectErrorKind>),
+
+    /// Any other NATS / JetStream failure; `context` names the operation
+    #[error("NATS {context}: {source}")]
+    Nats {
+        context: &'static str,
+        #[source]
+        source: BoxError,
+    },
+
+    /// Config parsed but failed [`crate::Config::validate`]
+    #[error(transparent)]
+    Invalid(#[from] crate::config::ConfigError),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
+    Serialize(#[from] serde_json::Error),
+}
+
+impl ArchiverError {
+    pub(crate) fn nats(context: &'static str, source: impl Into<BoxError>) -> Self {
+        Self::Nats {
+            context,
+            source: source.into(),
+        }
+    }
+}
+
+impl From<async_nats::error::Error<async_nats::ConnectErrorKind>> for ArchiverError {
+    fn from(e: async_nats::error::Error<async_nats::ConnectErrorKind>) -> Self {
+        use async_nats::ConnectErrorKind;
+        match e.kind() {
+            ConnectErrorKind::Authentication | ConnectErrorKind::AuthorizationViolation => {
+                Self::Auth(e)
+            }
+            _ => Self::nats("connect failed", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_nats_error_keeps_source() {
+        let io = std::io::Error::other("connection reset");
+        let e = ArchiverError::nats("fetch failed", io);
+        assert_eq!(e.to_string(), "NATS fetch failed: connection reset");
+        assert_eq!(e.source().unwrap().to_string(), "connection reset");
+    }
+
+    #[test]
+    fn test_config_parse_error_keeps_source() {
+        let yaml_err = serde_yaml::from_str::<u32>("not a number").unwrap_err();
+        let e = ArchiverError::from(yaml_err);
+        assert!(matches!(e, ArchiverError::ConfigParse(_)));
+        assert!(e.source().is_some());
+    }
 }
@@ -8,10 +8,11 @@ pub mod error;
 pub mod manifest;
 pub mod manifest_io;
 pub mod metrics;
+pub mod replay;
 pub mod server;
 pub mod subscriber;
 pub mod validation;
 pub mod writer;
 
-pub use config::Config;
+pub use config::{Config, ConfigError};
 pub use error::ArchiverError;
@@ -10,17 +10,18 @@ use std::time::Duration;
 use chrono::Utc;
 use clap::Parser;
 use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc;
 use tokio::task::JoinSet;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use ssmd_archiver::config::StreamConfig;
+use ssmd_archiver::config::{DeliveryPolicy, StreamConfig};
 use ssmd_archiver::manifest::{FileEntry, Gap};
 use ssmd_archiver::manifest_io::update_manifest;
 use ssmd_archiver::metrics::{ArchiverMetrics, StreamMetrics};
-use ssmd_archiver::server::{run_server, ServerState};
+use ssmd_archiver::server::{run_server, FlushOutcome, FlushRequest, ServerState};
 use ssmd_archiver::subscriber::Subscriber;
 use ssmd_archiver::validation::{extract_manifest_fields, MessageValidator};
 use ssmd_archiver::writer::{ArchiveOutput, ArchiveWriter};
@@ -59,8 +60,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     })?;
     config.resolve_feeds();
 
-    let rotation_duration = config.rotation.parse_interval()?;
-
     info!(
         nats_url = %config.nats.url,
         streams = config.nats.streams.len(),
@@ -73,7 +72,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let shutdown = CancellationToken::new();
     let nats_url = Arc::new(config.nats.url.clone());
     let base_path = Arc::new(config.storage.path.clone());
-    let rotation_interval = Arc::new(config.rotation.interval.clone());
+    let delivery_policy = config.nats.delivery_policy()?;
     let connected = Arc::new(AtomicBool::new(false));
     let last_message_epoch_secs = Arc::new(AtomicU64::new(0));
 
@@ -88,42 +87,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         feed_names.join(",")
     };
 
-    // Spawn HTTP health/metrics server
-    let server_state = ServerState::new(
-        &server_feed,
-        connected.clone(),
-        last_message_epoch_secs.clone(),
-    );
-    let health_addr = args.health_addr;
-
     // Spawn a task per stream
     let mut tasks: JoinSet<Result<(), Box<dyn std::error::Error + Send + Sync>>> = JoinSet::new();
-
-    // Health server runs in the JoinSet — if it exits, the archiver shuts down
-    tasks.spawn(async move {
-        info!(%health_addr, "Starting health/metrics server");
-        if let Err(e) = run_server(health_addr, server_state).await {
-            error!(error = %e, "Health server failed");
-        }
-        Err("Health server exited unexpectedly".into())
-    });
+    let mut flush_channels = Vec::with_capacity(config.nats.streams.len());
 
     for stream_config in config.nats.streams {
         let shutdown = shutdown.clone();
         let nats_url = Arc::clone(&nats_url);
         let base_path = Arc::clone(&base_path);
+        let delivery_policy = delivery_policy.clone();
         let feed = stream_config.feed.clone();
-        let rotation_interval = Arc::clone(&rotation_interval);
+        let rotation = stream_config
+            .rotation
+            .clone()
+            .unwrap_or_else(|| config.rotation.clone());
+        let rotation_duration = rotation.parse_interval()?;
+        let rotation_interval = rotation.interval;
         let connected = connected.clone();
         let last_message_epoch_secs = last_message_epoch_secs.clone();
         let archiver_metrics = ArchiverMetrics::new(&feed);
         let metrics = archiver_metrics.for_stream(&stream_config.name);
         metrics.init(&["ticker", "trade"]);
+        let (flush_tx, flush_rx) = mpsc::channel(1);
+        flush_channels.push((stream_config.name.clone(), flush_tx));
 
         info!(
             stream = %stream_config.stream,
             name = %stream_config.name,
             filter = %stream_config.filter,
+            feed = %feed,
+            rotation = %rotation_interval,
             "Spawning archive task"
         );
 
@@ -131,6 +124,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             archive_stream(
                 &nats_url,
                 stream_config,
+                delivery_policy,
                 &base_path,
                 &feed,
                 &rotation_interval,
@@ -139,11 +133,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 metrics,
                 connected,
                 last_message_epoch_secs,
+                flush_rx,
             )
             .await
         });
     }
 
+    // Spawn HTTP health/metrics server
+    let server_state = ServerState::new(
+        &server_feed,
+        connected.clone(),
+        last_message_epoch_secs.clone(),
+    )
+    .with_flush_channels(flush_channels);
+    let health_addr = args.health_addr;
+
+    // Health server runs in the JoinSet — if it exits, the archiver shuts down
+    tasks.spawn(async move {
+        info!(%health_addr, "Starting health/metrics server");
+        if let Err(e) = run_server(health_addr, server_state).await {
+            error!(error = %e, "Health server failed");
+        }
+        Err("Health server exited unexpectedly".into())
+    });
+
     // Set up signal handlers for graceful shutdown
     let mut sigterm =
         signal(SignalKind::terminate()).expect("Failed to create SIGTERM handler");
@@ -206,6 +219,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 async fn archive_stream(
     nats_url: &str,
     stream_config: StreamConfig,
+    delivery_policy: DeliveryPolicy,
     base_path: &Path,
     feed: &str,
     rotation_interval: &str,
@@ -214,6 +228,7 @@ async fn archive_stream(
     metrics: StreamMetrics,
     connected: Arc<AtomicBool>,
     last_message_epoch_secs: Arc<AtomicU64>,
+    mut flush_rx: mpsc::Receiver<FlushRequest>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let stream_name = stream_config.name.clone();
     let rotation_minutes = (rotation_duration.as_secs() / 60) as u32;
@@ -225,7 +240,7 @@ async fn archive_stream(
     );
 
     // Connect to NATS
-    let mut subscriber = Subscriber::connect(nats_url, &stream_config).await?;
+    let mut subscriber = Subscriber::connect(nats_url, &stream_config, delivery_policy).await?;
     connected.store(true, Ordering::SeqCst);
 
     // Create JSONL.gz writer
@@ -264,6 +279,36 @@ async fn archive_stream(
                 info!(stream_name = %stream_name, "Shutdown signal received");
                 break;
             }
+            Some(req) = flush_rx.recv() => {
+                let mut outcome = FlushOutcome {
+                    stream: stream_name.clone(),
+                    ..Default::default()
+                };
+                if writer.has_records() {
+                    match writer.close() {
+                        Ok(entries) => {
+                            for mut entry in entries {
+                                metrics.inc_files_rotated();
+                                entry.records_by_type = Some(std::mem::take(&mut current_file_type_counts));
+                                info!(stream_name = %stream_name, file = %entry.name, records = entry.records, "File flushed on request");
+                                outcome.flushed = true;
+                                outcome.file = Some(entry.name.clone());
+                                outcome.records = Some(entry.records);
+                                completed_files.push(entry);
+                            }
+                            if let Err(e) = update_manifest(base_path, feed, &stream_name, &current_date, rotation_interval, &tickers, &message_types, &gaps, &completed_files) {
+                                error!(stream_name = %stream_name, error = %e, "Failed to update manifest after flush");
+                                outcome.error = Some(format!("manifest update failed: {}", e));
+                            }
+                        }
+                        Err(e) => {
+                            error!(stream_name = %stream_name, error = %e, "Failed to close file on flush request");
+                            outcome.error = Some(e.to_string());
+                        }
+                    }
+                }
+                let _ = req.reply.send(outcome);
+            }
             _ = fetch_interval.tick() => {
                 let now = Utc::now();
                 let date = now.format("%Y-%m-%d").to_string();
@@ -352,7 +397,7 @@ async fn archive_stream(
 
                             // Write to archive (returns rotated FileEntries on rotation)
                             let seq = msg.seq;
-                            match writer.write(msg.payload(), seq, now) {
+                            match writer.write(msg.payload(), seq, msg.capture(), now) {
                                 Ok(rotated_entries) => {
                                     pending_acks.push(msg);
                                     if !rotated_entries.is_empty() {
@@ -379,8 +424,26 @@ async fn archive_stream(
                                     }
                                 }
                                 Err(e) => {
-                                    // Don't ack - message will be redelivered by NATS
-                                    warn!(stream_name = %stream_name, error = %e, seq = seq, "Failed to write message, will be redelivered");
+                                    // The writer rolled its file back to the last flush, so
+                                    // earlier messages in this batch are no longer on disk
+                                    if !pending_acks.is_empty() {
+                                        warn!(stream_name = %stream_name, discarded = pending_acks.len(), "Write failure dropped unflushed messages, leaving them unacked for redelivery");
+                                        pending_acks.clear();
+                                    }
+                                    if subscriber.should_dead_letter(&msg) {
+                                        // Last delivery: move it aside instead of leaving it unacked forever
+                                        let deliveries = msg.deliveries;
+                                        error!(stream_name = %stream_name, error = %e, seq = seq, deliveries = deliveries, "Failed to write message on final delivery, dead-lettering");
+                                        match subscriber.dead_letter(msg, &e.to_string()).await {
+                                            Ok(()) => metrics.inc_dead_letter(),
+                                            Err(dle) => {
+                                                error!(stream_name = %stream_name, error = %dle, seq = seq, "Failed to dead-letter message");
+                                            }
+                                        }
+                                    } else {
+                                        // Don't ack - message will be redelivered by NATS
+                                        warn!(stream_name = %stream_name, error = %e, seq = seq, deliveries = msg.deliveries, "Failed to write message, will be redelivered");
+                                    }
                                 }
                             }
                         }
@@ -14,6 +14,12 @@ pub struct Manifest {
     pub tickers: Vec<String>,
     pub message_types: Vec<String>,
     pub has_gaps: bool,
+    /// Earliest event timestamp across all files
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub start_ts: Option<DateTime<Utc>>,
+    /// Latest event timestamp across all files
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub end_ts: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,6 +37,12 @@ pub struct FileEntry {
     pub nats_end_seq: u64,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub records_by_type: Option<HashMap<String, u64>>,
+    /// Earliest event timestamp in the file (write time for messages without one)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub start_ts: Option<DateTime<Utc>>,
+    /// Latest event timestamp in the file
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub end_ts: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -52,6 +64,14 @@ impl Manifest {
             tickers: Vec::new(),
             message_types: Vec::new(),
             has_gaps: false,
+            start_ts: None,
+            end_ts: None,
         }
     }
+
+    /// Set the day's time coverage from the files' event timestamp bounds.
+    pub fn set_time_coverage(&mut self) {
+        self.start_ts = self.files.iter().filter_map(|f| f.start_ts).min();
+        self.end_ts = self.files.iter().filter_map(|f| f.end_ts).max();
+    }
 }
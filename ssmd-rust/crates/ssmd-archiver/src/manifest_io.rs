@@ -25,6 +25,7 @@ pub fn update_manifest(
     manifest.message_types.sort_unstable();
     manifest.gaps = gaps.to_vec();
     manifest.has_gaps = !gaps.is_empty();
+    manifest.set_time_coverage();
 
     let manifest_path = base_path
         .join(feed)
@@ -93,6 +94,7 @@ mod tests {
             &mut self,
             _data: &[u8],
             _seq: u64,
+            _capture: crate::subscriber::CaptureMeta,
             _now: chrono::DateTime<Utc>,
         ) -> Result<Vec<FileEntry>, ArchiverError> {
             Ok(Vec::new())
@@ -129,6 +131,8 @@ mod tests {
             nats_start_seq: 1,
             nats_end_seq: 10,
             records_by_type: None,
+            start_ts: None,
+            end_ts: None,
         }];
 
         update_manifest(
@@ -178,6 +182,8 @@ mod tests {
                 nats_start_seq: 11,
                 nats_end_seq: 15,
                 records_by_type: None,
+                start_ts: None,
+                end_ts: None,
             }],
         };
 
@@ -192,6 +198,8 @@ mod tests {
             nats_start_seq: 1,
             nats_end_seq: 10,
             records_by_type: None,
+            start_ts: None,
+            end_ts: None,
         }];
 
         let mut tickers = HashSet::new();
@@ -74,6 +74,16 @@ static GAPS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
     .expect("Failed to register gaps_total metric")
 });
 
+/// Messages republished to the dead-letter subject per stream
+static DEAD_LETTERS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "ssmd_archiver_dead_letters_total",
+        "Total messages republished to the dead-letter subject after exhausting max_deliver",
+        &[LABEL_FEED, LABEL_STREAM]
+    )
+    .expect("Failed to register dead_letters_total metric")
+});
+
 /// Number of active stream subscriptions
 static ACTIVE_STREAMS: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
@@ -181,6 +191,13 @@ impl StreamMetrics {
             .inc();
     }
 
+    /// Increment dead-letter counter
+    pub fn inc_dead_letter(&self) {
+        DEAD_LETTERS_TOTAL
+            .with_label_values(&[&self.feed, &self.stream])
+            .inc();
+    }
+
     /// Update last message timestamp
     pub fn set_last_message_timestamp(&self, epoch_secs: f64) {
         LAST_MESSAGE_TIMESTAMP
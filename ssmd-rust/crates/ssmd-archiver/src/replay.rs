@@ -0,0 +1,254 @@
+//! Read archived JSONL.gz files back for replay into NATS (`ssmd-replay`).
+//!
+//! Records come back as the connector published them: the fields the
+//! archiver appended (`_received_at`, `_nats_seq`, `_capture_ts`,
+//! `_source_seq`) are split off the payload and returned alongside it.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use flate2::read::GzDecoder;
+
+use crate::error::ArchiverError;
+use crate::manifest::{FileEntry, Manifest};
+use crate::subscriber::CaptureMeta;
+use crate::validation::extract_manifest_fields;
+
+/// Start of the suffix `ArchiveWriter` appends to each JSON record
+const ARCHIVE_FIELDS_MARKER: &[u8] = b",\"_received_at\":";
+
+/// One archived record with the archiver's fields split off
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedRecord {
+    /// Payload as originally published
+    pub payload: Vec<u8>,
+    pub nats_seq: Option<u64>,
+    /// Archiver receive time (Unix micros)
+    pub received_at_micros: Option<i64>,
+    pub capture: CaptureMeta,
+}
+
+/// Load a day's `manifest.json`.
+pub fn load_manifest(path: &Path) -> Result<Manifest, ArchiverError> {
+    let data = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Files in replay order (by first NATS sequence, then start time).
+pub fn replay_order(files: &[FileEntry]) -> Vec<&FileEntry> {
+    let mut ordered: Vec<&FileEntry> = files.iter().collect();
+    ordered.sort_by_key(|f| (f.nats_start_seq, f.start));
+    ordered
+}
+
+/// Split the archiver's appended fields off a JSONL line.
+///
+/// Lines without them (non-JSON payloads the archiver wrote raw) are
+/// returned unchanged with no metadata.
+pub fn split_archive_fields(line: &[u8]) -> ArchivedRecord {
+    let marker = line
+        .windows(ARCHIVE_FIELDS_MARKER.len())
+        .rposition(|w| w == ARCHIVE_FIELDS_MARKER);
+    let Some(pos) = marker.filter(|_| line.ends_with(b"}")) else {
+        return ArchivedRecord {
+            payload: line.to_vec(),
+            nats_seq: None,
+            received_at_micros: None,
+            capture: CaptureMeta::default(),
+        };
+    };
+
+    let mut payload = Vec::with_capacity(pos + 1);
+    payload.extend_from_slice(&line[..pos]);
+    payload.push(b'}');
+
+    let mut record = ArchivedRecord {
+        payload,
+        nats_seq: None,
+        received_at_micros: None,
+        capture: CaptureMeta::default(),
+    };
+    let suffix = String::from_utf8_lossy(&line[pos + 1..line.len() - 1]);
+    for field in suffix.split(',') {
+        let Some((key, value)) = field.split_once(':') else {
+            continue;
+        };
+        match key {
+            "\"_received_at\"" => record.received_at_micros = value.parse().ok(),
+            "\"_nats_seq\"" => record.nats_seq = value.parse().ok(),
+            "\"_capture_ts\"" => record.capture.capture_ts_micros = value.parse().ok(),
+            "\"_source_seq\"" => record.capture.source_seq = value.parse().ok(),
+            _ => {}
+        }
+    }
+    record
+}
+
+/// Read one archived file, sorted by NATS sequence with redelivered
+/// duplicates removed. Records without a sequence keep their file position
+/// relative to each other and sort first.
+pub fn read_file(path: &Path) -> Result<Vec<ArchivedRecord>, ArchiverError> {
+    let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+    let mut records = Vec::new();
+    for line in reader.split(b'\n') {
+        let line = line?;
+        if !line.is_empty() {
+            records.push(split_archive_fields(&line));
+        }
+    }
+    records.sort_by_key(|r| r.nats_seq);
+    records.dedup_by(|a, b| a.nats_seq.is_some() && a.nats_seq == b.nats_seq);
+    Ok(records)
+}
+
+/// Subject a record is replayed on: `{prefix}.json.{type}.{ticker}`, the
+/// connector's layout, with `unknown` for fields the feed does not carry.
+pub fn replay_subject(prefix: &str, feed: &str, payload: &[u8]) -> String {
+    let fields = extract_manifest_fields(feed, payload);
+    let msg_type = fields.as_ref().and_then(|f| f.msg_type.as_deref());
+    let ticker = fields.as_ref().and_then(|f| f.ticker.as_deref());
+    format!(
+        "{}.json.{}.{}",
+        prefix,
+        subject_token(msg_type.unwrap_or("unknown")),
+        subject_token(ticker.unwrap_or("unknown")),
+    )
+}
+
+/// Replace characters that would split or wildcard a subject token
+fn subject_token(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '.' | '*' | '>' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Paces replay to the original inter-arrival gaps.
+///
+/// Each record is due `(received_at - first received_at) / speed` after the
+/// first record was replayed. A speed of zero or less replays as fast as
+/// possible.
+pub struct Pacer {
+    speed: f64,
+    origin: Option<(i64, Instant)>,
+}
+
+impl Pacer {
+    pub fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            origin: None,
+        }
+    }
+
+    /// How long to wait before publishing a record received at
+    /// `received_at_micros`, or `None` if it is already due.
+    pub fn delay(&mut self, received_at_micros: Option<i64>, now: Instant) -> Option<Duration> {
+        if self.speed <= 0.0 {
+            return None;
+        }
+        let ts = received_at_micros?;
+        let (origin_ts, origin_at) = *self.origin.get_or_insert((ts, now));
+        let offset_micros = ts - origin_ts;
+        if offset_micros <= 0 {
+            return None;
+        }
+        let due = origin_at + Duration::from_secs_f64(offset_micros as f64 / 1e6 / self.speed);
+        due.checked_duration_since(now).filter(|d| !d.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{ArchiveOutput, ArchiveWriter};
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_archive_fields() {
+        let line = br#"{"type":"trade","ticker":"INXD","_received_at":1700000000000000,"_nats_seq":42,"_capture_ts":1699999999999000,"_source_seq":7}"#;
+        let record = split_archive_fields(line);
+        assert_eq!(record.payload, br#"{"type":"trade","ticker":"INXD"}"#);
+        assert_eq!(record.nats_seq, Some(42));
+        assert_eq!(record.received_at_micros, Some(1_700_000_000_000_000));
+        assert_eq!(record.capture.capture_ts_micros, Some(1_699_999_999_999_000));
+        assert_eq!(record.capture.source_seq, Some(7));
+
+        let raw = split_archive_fields(b"not json");
+        assert_eq!(raw.payload, b"not json");
+        assert_eq!(raw.nats_seq, None);
+    }
+
+    #[test]
+    fn test_read_file_sorts_and_dedups_writer_output() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+        let now = Utc::now();
+        // Redelivery: seq 2 arrives after 3, and 3 arrives twice
+        for (seq, ticker) in [(1, "A"), (3, "C"), (2, "B"), (3, "C")] {
+            let data = format!(r#"{{"type":"trade","msg":{{"market_ticker":"{}"}}}}"#, ticker);
+            writer.write(data.as_bytes(), seq, CaptureMeta::default(), now).unwrap();
+        }
+        let entries = writer.close().unwrap();
+
+        let date_str = now.format("%Y-%m-%d").to_string();
+        let path = tmp
+            .path()
+            .join("kalshi")
+            .join("politics")
+            .join(&date_str)
+            .join(&entries[0].name);
+        let records = read_file(&path).unwrap();
+
+        let seqs: Vec<_> = records.iter().map(|r| r.nats_seq).collect();
+        assert_eq!(seqs, vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(records[1].payload, br#"{"type":"trade","msg":{"market_ticker":"B"}}"#);
+        assert_eq!(records[0].received_at_micros, Some(now.timestamp_micros()));
+    }
+
+    #[test]
+    fn test_replay_subject() {
+        let payload = br#"{"type":"trade","msg":{"market_ticker":"KXBTC-25.5"}}"#;
+        assert_eq!(
+            replay_subject("replay.kalshi", "kalshi", payload),
+            "replay.kalshi.json.trade.KXBTC-25_5"
+        );
+        assert_eq!(
+            replay_subject("replay.kalshi", "kalshi", b"not json"),
+            "replay.kalshi.json.unknown.unknown"
+        );
+    }
+
+    #[test]
+    fn test_pacer() {
+        let start = Instant::now();
+        let mut pacer = Pacer::new(2.0);
+        assert_eq!(pacer.delay(Some(1_000_000), start), None);
+        // 1s of original gap at 2x speed is due 500ms after the first record
+        assert_eq!(
+            pacer.delay(Some(2_000_000), start),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            pacer.delay(Some(2_000_000), start + Duration::from_millis(200)),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(pacer.delay(Some(2_000_000), start + Duration::from_secs(1)), None);
+        assert_eq!(pacer.delay(None, start), None);
+
+        let mut unpaced = Pacer::new(0.0);
+        assert_eq!(unpaced.delay(Some(1), start), None);
+        assert_eq!(unpaced.delay(Some(10_000_000), start), None);
+    }
+}
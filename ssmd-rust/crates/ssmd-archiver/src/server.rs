@@ -4,15 +4,16 @@ use axum::{
     extract::State,
     http::StatusCode,
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
 use serde::Serialize;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
 
 use crate::metrics::encode_metrics;
 
@@ -31,6 +32,28 @@ pub struct HealthResponse {
     pub stale: bool,
 }
 
+/// How long `/admin/flush` waits for each archive task to answer
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Ask an archive task to close its current file and rewrite the manifest
+pub struct FlushRequest {
+    pub reply: oneshot::Sender<FlushOutcome>,
+}
+
+/// One stream's answer to a [`FlushRequest`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FlushOutcome {
+    pub stream: String,
+    /// False when nothing was written since the last rotation
+    pub flushed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub records: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Shared state for health endpoints
 #[derive(Clone)]
 pub struct ServerState {
@@ -40,6 +63,8 @@ pub struct ServerState {
     pub last_message_epoch_secs: Arc<AtomicU64>,
     /// Staleness threshold in seconds
     pub stale_threshold_secs: u64,
+    /// (stream name, channel) per archive task, for `/admin/flush`
+    pub flush_channels: Vec<(String, mpsc::Sender<FlushRequest>)>,
 }
 
 impl ServerState {
@@ -53,9 +78,18 @@ impl ServerState {
             connected,
             last_message_epoch_secs,
             stale_threshold_secs: DEFAULT_STALE_THRESHOLD_SECS,
+            flush_channels: Vec::new(),
         }
     }
 
+    pub fn with_flush_channels(
+        mut self,
+        flush_channels: Vec<(String, mpsc::Sender<FlushRequest>)>,
+    ) -> Self {
+        self.flush_channels = flush_channels;
+        self
+    }
+
     fn staleness_info(&self) -> (Option<u64>, bool) {
         let last_msg = self.last_message_epoch_secs.load(Ordering::SeqCst);
         if last_msg == 0 {
@@ -145,12 +179,48 @@ async fn metrics() -> impl IntoResponse {
     }
 }
 
+/// Admin flush endpoint - closes every stream's partial file and rewrites its
+/// manifest. Streams with nothing written since their last rotation report
+/// `flushed: false`. Returns 500 if any stream failed or did not answer.
+async fn admin_flush(State(state): State<ServerState>) -> (StatusCode, Json<serde_json::Value>) {
+    let mut outcomes = Vec::with_capacity(state.flush_channels.len());
+    for (stream, tx) in &state.flush_channels {
+        outcomes.push(request_flush(stream, tx).await);
+    }
+
+    let status = if outcomes.iter().any(|o| o.error.is_some()) {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(serde_json::json!({ "streams": outcomes })))
+}
+
+async fn request_flush(stream: &str, tx: &mpsc::Sender<FlushRequest>) -> FlushOutcome {
+    let failed = |error: &str| FlushOutcome {
+        stream: stream.to_string(),
+        error: Some(error.to_string()),
+        ..Default::default()
+    };
+
+    let (reply, rx) = oneshot::channel();
+    if tx.send(FlushRequest { reply }).await.is_err() {
+        return failed("archive task not running");
+    }
+    match tokio::time::timeout(FLUSH_TIMEOUT, rx).await {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(_)) => failed("archive task dropped the request"),
+        Err(_) => failed("timed out waiting for archive task"),
+    }
+}
+
 /// Create the health server router
 pub fn create_router(state: ServerState) -> Router {
     Router::new()
         .route("/health", get(health))
         .route("/ready", get(ready))
         .route("/metrics", get(metrics))
+        .route("/admin/flush", post(admin_flush))
         .with_state(state)
 }
 
@@ -174,6 +244,7 @@ mod tests {
             connected: Arc::new(AtomicBool::new(connected)),
             last_message_epoch_secs: Arc::new(AtomicU64::new(0)),
             stale_threshold_secs: DEFAULT_STALE_THRESHOLD_SECS,
+            flush_channels: Vec::new(),
         }
     }
 
@@ -187,6 +258,7 @@ mod tests {
             connected: Arc::new(AtomicBool::new(connected)),
             last_message_epoch_secs: Arc::new(AtomicU64::new(last_msg_epoch)),
             stale_threshold_secs: threshold,
+            flush_channels: Vec::new(),
         }
     }
 
@@ -312,4 +384,75 @@ mod tests {
         let content_type = response.headers().get("content-type").unwrap();
         assert!(content_type.to_str().unwrap().contains("text/plain"));
     }
+
+    #[tokio::test]
+    async fn test_admin_flush_collects_stream_outcomes() {
+        let (tx, mut rx) = mpsc::channel::<FlushRequest>(1);
+        tokio::spawn(async move {
+            while let Some(req) = rx.recv().await {
+                let _ = req.reply.send(FlushOutcome {
+                    stream: "politics".to_string(),
+                    flushed: true,
+                    file: Some("1215.jsonl.gz".to_string()),
+                    records: Some(3),
+                    error: None,
+                });
+            }
+        });
+        let (idle_tx, mut idle_rx) = mpsc::channel::<FlushRequest>(1);
+        tokio::spawn(async move {
+            while let Some(req) = idle_rx.recv().await {
+                let _ = req.reply.send(FlushOutcome {
+                    stream: "economics".to_string(),
+                    ..Default::default()
+                });
+            }
+        });
+
+        let state = create_test_state(true).with_flush_channels(vec![
+            ("politics".to_string(), tx),
+            ("economics".to_string(), idle_tx),
+        ]);
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/flush")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["streams"][0]["flushed"], true);
+        assert_eq!(json["streams"][0]["records"], 3);
+        assert_eq!(json["streams"][1]["flushed"], false);
+        assert!(json["streams"][1].get("file").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_admin_flush_reports_dead_task() {
+        let (tx, rx) = mpsc::channel::<FlushRequest>(1);
+        drop(rx);
+
+        let state =
+            create_test_state(true).with_flush_channels(vec![("politics".to_string(), tx)]);
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/flush")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
 }
@@ -1,20 +1,42 @@
 use async_nats::jetstream::{self, consumer::PullConsumer, message::Message};
 use futures_util::StreamExt;
+use ssmd_middleware::{CAPTURE_TS_HEADER, SOURCE_SEQ_HEADER};
 use std::time::Duration;
 use tracing::{error, info, trace, warn};
 
-use crate::config::StreamConfig;
+use crate::config::{DeliveryPolicy, StreamConfig};
 use crate::error::ArchiverError;
 
+/// Headers added to a dead-lettered message (original headers are kept)
+pub const DEAD_LETTER_STREAM_HEADER: &str = "Ssmd-Dead-Letter-Stream";
+pub const DEAD_LETTER_SUBJECT_HEADER: &str = "Ssmd-Dead-Letter-Subject";
+pub const DEAD_LETTER_SEQ_HEADER: &str = "Ssmd-Dead-Letter-Seq";
+pub const DEAD_LETTER_DELIVERIES_HEADER: &str = "Ssmd-Dead-Letter-Deliveries";
+pub const DEAD_LETTER_ERROR_HEADER: &str = "Ssmd-Dead-Letter-Error";
+
 pub struct Subscriber {
     consumer: PullConsumer,
+    jetstream: jetstream::Context,
+    stream: String,
+    policy: DeliveryPolicy,
     expected_seq: Option<u64>,
 }
 
+/// Connector-side capture metadata carried in NATS headers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CaptureMeta {
+    /// Capture time at the connector (Unix epoch microseconds)
+    pub capture_ts_micros: Option<i64>,
+    /// Exchange-provided sequence number
+    pub source_seq: Option<u64>,
+}
+
 /// A received message that must be explicitly acked after processing
 pub struct ReceivedMessage {
     pub seq: u64,
     pub gap: Option<(u64, u64)>,
+    /// Times the server has delivered this message, including this one
+    pub deliveries: i64,
     message: Message,
 }
 
@@ -24,21 +46,38 @@ impl ReceivedMessage {
         &self.message.payload
     }
 
+    /// Capture metadata from the connector's headers, if present.
+    pub fn capture(&self) -> CaptureMeta {
+        let header = |name: &str| {
+            self.message
+                .headers
+                .as_ref()
+                .and_then(|h| h.get(name))
+                .map(|v| v.as_str())
+        };
+        CaptureMeta {
+            capture_ts_micros: header(CAPTURE_TS_HEADER).and_then(|v| v.parse().ok()),
+            source_seq: header(SOURCE_SEQ_HEADER).and_then(|v| v.parse().ok()),
+        }
+    }
+
     /// Acknowledge the message after successful processing
     pub async fn ack(self) -> Result<(), ArchiverError> {
         self.message
             .ack()
             .await
-            .map_err(|e| ArchiverError::Nats(format!("Failed to ack: {}", e)))
+            .map_err(|e| ArchiverError::nats("ack failed", e))
     }
 }
 
 impl Subscriber {
     /// Connect to NATS and create a subscriber for a specific stream
-    pub async fn connect(nats_url: &str, stream_config: &StreamConfig) -> Result<Self, ArchiverError> {
-        let client = async_nats::connect(nats_url)
-            .await
-            .map_err(|e| ArchiverError::Nats(e.to_string()))?;
+    pub async fn connect(
+        nats_url: &str,
+        stream_config: &StreamConfig,
+        policy: DeliveryPolicy,
+    ) -> Result<Self, ArchiverError> {
+        let client = async_nats::connect(nats_url).await?;
 
         let jetstream = jetstream::new(client);
 
@@ -46,7 +85,7 @@ impl Subscriber {
         let mut stream = jetstream
             .get_stream(&stream_config.stream)
             .await
-            .map_err(|e| ArchiverError::Nats(format!("Stream not found: {}", e)))?;
+            .map_err(|e| ArchiverError::nats("stream lookup failed", e))?;
 
         // Validate filter subject matches stream subjects — crash on mismatch
         // to prevent silent data loss (lifecycle stream incident: 1,856 messages
@@ -54,13 +93,13 @@ impl Subscriber {
         let stream_info = stream
             .info()
             .await
-            .map_err(|e| ArchiverError::Nats(format!("Failed to get stream info: {}", e)))?;
+            .map_err(|e| ArchiverError::nats("stream info failed", e))?;
 
         let stream_subjects = &stream_info.config.subjects;
         let filter = &stream_config.filter;
 
         if !filter_matches_stream_subjects(filter, stream_subjects) {
-            return Err(ArchiverError::Nats(format!(
+            return Err(ArchiverError::Config(format!(
                 "Filter subject '{}' does not match any stream '{}' subjects: {:?}. \
                  This will result in zero messages delivered.",
                 filter, stream_config.stream, stream_subjects
@@ -74,27 +113,40 @@ impl Subscriber {
             "Stream subject validation passed"
         );
 
+        let mut consumer_config = jetstream::consumer::pull::Config {
+            durable_name: Some(stream_config.consumer.clone()),
+            filter_subject: stream_config.filter.clone(),
+            ..Default::default()
+        };
+        if let Some(ack_wait) = policy.ack_wait {
+            consumer_config.ack_wait = ack_wait;
+        }
+        if let Some(max_deliver) = policy.max_deliver {
+            consumer_config.max_deliver = max_deliver;
+        }
+
+        // Create-or-update, so ack_wait / max_deliver changes reach an
+        // existing durable consumer
         let consumer = stream
-            .get_or_create_consumer(
-                &stream_config.consumer,
-                jetstream::consumer::pull::Config {
-                    durable_name: Some(stream_config.consumer.clone()),
-                    filter_subject: stream_config.filter.clone(),
-                    ..Default::default()
-                },
-            )
+            .create_consumer(consumer_config)
             .await
-            .map_err(|e| ArchiverError::Nats(e.to_string()))?;
+            .map_err(|e| ArchiverError::nats("consumer setup failed", e))?;
 
         info!(
             stream = %stream_config.stream,
             consumer = %stream_config.consumer,
             filter = %stream_config.filter,
+            ack_wait = ?policy.ack_wait,
+            max_deliver = ?policy.max_deliver,
+            dead_letter = ?policy.dead_letter_subject,
             "Connected to NATS JetStream"
         );
 
         Ok(Self {
             consumer,
+            jetstream,
+            stream: stream_config.stream.clone(),
+            policy,
             expected_seq: None,
         })
     }
@@ -109,7 +161,7 @@ impl Subscriber {
             .expires(Duration::from_secs(30)) // Timeout to prevent indefinite hangs
             .messages()
             .await
-            .map_err(|e| ArchiverError::Nats(e.to_string()))?;
+            .map_err(|e| ArchiverError::nats("fetch failed", e))?;
 
         let mut result = Vec::new();
 
@@ -117,7 +169,10 @@ impl Subscriber {
         while let Some(msg_result) = messages.next().await {
             match msg_result {
                 Ok(msg) => {
-                    let seq = msg.info().map(|i| i.stream_sequence).unwrap_or(0);
+                    let (seq, deliveries) = msg
+                        .info()
+                        .map(|i| (i.stream_sequence, i.delivered))
+                        .unwrap_or((0, 1));
                     let (gap, next_expected) = compute_gap_and_next(self.expected_seq, seq);
 
                     if let Some((after_seq, missing_count)) = gap {
@@ -135,6 +190,7 @@ impl Subscriber {
                     result.push(ReceivedMessage {
                         seq,
                         gap,
+                        deliveries,
                         message: msg,
                     });
                     // Note: ack deferred until after successful write
@@ -149,6 +205,47 @@ impl Subscriber {
         Ok(result)
     }
 
+    /// True if `msg` failed on its last allowed delivery and should go to
+    /// the dead-letter subject rather than being left unacked
+    pub fn should_dead_letter(&self, msg: &ReceivedMessage) -> bool {
+        self.policy.dead_letter_subject.is_some()
+            && is_final_delivery(msg.deliveries, self.policy.max_deliver)
+    }
+
+    /// Republish `msg` to the dead-letter subject with headers describing
+    /// the failure, then ack the original so the consumer moves past it.
+    pub async fn dead_letter(&self, msg: ReceivedMessage, reason: &str) -> Result<(), ArchiverError> {
+        let subject = self
+            .policy
+            .dead_letter_subject
+            .clone()
+            .ok_or_else(|| ArchiverError::Config("no dead-letter subject configured".to_string()))?;
+
+        let mut headers = msg.message.headers.clone().unwrap_or_default();
+        headers.insert(DEAD_LETTER_STREAM_HEADER, self.stream.as_str());
+        headers.insert(DEAD_LETTER_SUBJECT_HEADER, msg.message.subject.as_str());
+        headers.insert(DEAD_LETTER_SEQ_HEADER, msg.seq.to_string().as_str());
+        headers.insert(DEAD_LETTER_DELIVERIES_HEADER, msg.deliveries.to_string().as_str());
+        headers.insert(DEAD_LETTER_ERROR_HEADER, header_safe(reason).as_str());
+
+        self.jetstream
+            .publish_with_headers(subject, headers, msg.message.payload.clone())
+            .await
+            .map_err(|e| ArchiverError::nats("dead-letter publish failed", e))?
+            .await
+            .map_err(|e| ArchiverError::nats("dead-letter publish not acked", e))?;
+
+        msg.ack().await
+    }
+}
+
+/// Header values cannot span lines
+fn header_safe(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+fn is_final_delivery(deliveries: i64, max_deliver: Option<i64>) -> bool {
+    max_deliver.is_some_and(|max| deliveries >= max)
 }
 
 /// Check if a NATS filter subject is compatible with any of the stream's subjects.
@@ -187,7 +284,23 @@ fn compute_gap_and_next(expected_seq: Option<u64>, seq: u64) -> (Option<(u64, u6
 
 #[cfg(test)]
 mod tests {
-    use super::{compute_gap_and_next, filter_matches_stream_subjects};
+    use super::{
+        compute_gap_and_next, filter_matches_stream_subjects, header_safe, is_final_delivery,
+    };
+
+    #[test]
+    fn test_is_final_delivery() {
+        assert!(!is_final_delivery(1, None));
+        assert!(!is_final_delivery(100, None));
+        assert!(!is_final_delivery(2, Some(3)));
+        assert!(is_final_delivery(3, Some(3)));
+        assert!(is_final_delivery(1, Some(1)));
+    }
+
+    #[test]
+    fn test_header_safe_strips_newlines() {
+        assert_eq!(header_safe("io error:\ndisk full\r"), "io error: disk full ");
+    }
 
     #[test]
     fn test_compute_gap_and_next_detects_gap() {
@@ -39,6 +39,53 @@ struct KrakenFuturesEnvelope<'a> {
     product_id: Option<&'a str>,
 }
 
+#[derive(Deserialize)]
+struct KalshiTsEnvelope {
+    msg: Option<KalshiTsMsg>,
+}
+
+#[derive(Deserialize)]
+struct KalshiTsMsg {
+    /// Unix seconds
+    ts: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct PolymarketTsEnvelope {
+    /// Unix millis, sent as a string
+    timestamp: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct KrakenFuturesTsEnvelope {
+    /// Unix millis
+    time: Option<i64>,
+}
+
+/// Extract the exchange event timestamp (Unix micros) from the feed-specific
+/// time field. Returns None for unknown feeds or messages without one.
+pub fn extract_event_ts_micros(feed: &str, data: &[u8]) -> Option<i64> {
+    match feed {
+        "kalshi" => {
+            let env: KalshiTsEnvelope = serde_json::from_slice(data).ok()?;
+            env.msg?.ts?.checked_mul(1_000_000)
+        }
+        "polymarket" => {
+            let env: PolymarketTsEnvelope = serde_json::from_slice(data).ok()?;
+            let millis = match env.timestamp? {
+                serde_json::Value::String(s) => s.parse::<i64>().ok()?,
+                v => v.as_i64()?,
+            };
+            millis.checked_mul(1_000)
+        }
+        "kraken-futures" => {
+            let env: KrakenFuturesTsEnvelope = serde_json::from_slice(data).ok()?;
+            env.time?.checked_mul(1_000)
+        }
+        _ => None,
+    }
+}
+
 /// Extract manifest-relevant fields using minimal serde structs.
 /// Returns None only on parse failure (malformed JSON).
 pub fn extract_manifest_fields(feed: &str, data: &[u8]) -> Option<ManifestFields> {
@@ -695,6 +742,21 @@ mod tests {
         assert!(extract_manifest_fields("kalshi", b"not json").is_none());
     }
 
+    #[test]
+    fn test_extract_event_ts_per_feed() {
+        let kalshi = br#"{"type":"ticker","msg":{"market_ticker":"KXBTC","ts":1707667200}}"#;
+        assert_eq!(extract_event_ts_micros("kalshi", kalshi), Some(1_707_667_200_000_000));
+
+        let poly = br#"{"event_type":"book","market":"0xabc","timestamp":"1757908892351"}"#;
+        assert_eq!(extract_event_ts_micros("polymarket", poly), Some(1_757_908_892_351_000));
+
+        let kf = br#"{"feed":"ticker","product_id":"PF_XBTUSD","time":1770920339237}"#;
+        assert_eq!(extract_event_ts_micros("kraken-futures", kf), Some(1_770_920_339_237_000));
+
+        assert_eq!(extract_event_ts_micros("kalshi", br#"{"type":"subscribed"}"#), None);
+        assert_eq!(extract_event_ts_micros("unknown", br#"{"ts":1}"#), None);
+    }
+
     #[test]
     fn test_extract_unknown_feed() {
         let data = br#"{"foo":"bar"}"#;
@@ -1,13 +1,16 @@
-use std::fs::{self, File};
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
 use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
 use flate2::write::GzEncoder;
-use flate2::Compression;
+use flate2::{Compression, Crc};
+use tracing::{error, warn};
 
 use crate::error::ArchiverError;
 use crate::manifest::FileEntry;
+use crate::subscriber::CaptureMeta;
+use crate::validation::extract_event_ts_micros;
 
 /// Trait for archive output formats.
 pub trait ArchiveOutput: Send {
@@ -16,6 +19,7 @@ pub trait ArchiveOutput: Send {
         &mut self,
         data: &[u8],
         seq: u64,
+        capture: CaptureMeta,
         now: DateTime<Utc>,
     ) -> Result<Vec<FileEntry>, ArchiverError>;
 
@@ -26,24 +30,89 @@ pub trait ArchiveOutput: Send {
     fn flush(&mut self) -> Result<(), ArchiverError>;
 }
 
+/// Final empty stored block of a deflate stream (BFINAL=1, LEN=0, NLEN=!0)
+const DEFLATE_FINAL_EMPTY_BLOCK: [u8; 5] = [0x01, 0x00, 0x00, 0xff, 0xff];
+
 /// Writes JSONL.gz files with rotation.
+///
+/// If a record fails to encode part-way through (or a flush fails), the file
+/// is cut back to the last successful [`flush`](ArchiveOutput::flush) and
+/// closed as valid gzip; the call returns the error and the next write
+/// starts a new file. Records written since that flush are dropped from
+/// disk, so callers must not ack them.
 pub struct ArchiveWriter {
     base_path: PathBuf,
     feed: String,
     stream_name: String,
     current_file: Option<CurrentFile>,
     rotation_minutes: u32,
+    /// Files closed by a rollback, returned from the next write or close
+    salvaged: Vec<FileEntry>,
 }
 
 struct CurrentFile {
     path: PathBuf,
     final_name: String,
-    encoder: GzEncoder<File>,
+    encoder: GzEncoder<ArchiveFile>,
     start_time: DateTime<Utc>,
+    /// CRC of the uncompressed bytes handed to the encoder
+    crc: Crc,
+    stats: FileStats,
+    /// State as of the last successful flush
+    checkpoint: Checkpoint,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FileStats {
     records: u64,
     bytes_written: u64,
     first_seq: Option<u64>,
     last_seq: Option<u64>,
+    /// Event timestamp bounds (Unix micros)
+    min_event_ts: Option<i64>,
+    max_event_ts: Option<i64>,
+}
+
+/// Point after a sync flush, where the compressed stream ends on a byte
+/// boundary and can be cut and terminated as a valid gzip member.
+#[derive(Debug, Clone, Copy, Default)]
+struct Checkpoint {
+    /// Compressed file length
+    offset: u64,
+    crc: u32,
+    /// Uncompressed length mod 2^32 (the gzip ISIZE field)
+    amount: u32,
+    stats: FileStats,
+}
+
+/// File under the gzip encoder; tests can make its writes fail to simulate
+/// a disk error in the middle of a record.
+struct ArchiveFile {
+    file: File,
+    #[cfg(test)]
+    fail_writes: bool,
+}
+
+impl Write for ArchiveFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        #[cfg(test)]
+        if self.fail_writes {
+            return Err(std::io::Error::other("injected write failure"));
+        }
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl CurrentFile {
+    fn write_part(&mut self, part: &[u8]) -> std::io::Result<()> {
+        self.encoder.write_all(part)?;
+        self.crc.update(part);
+        Ok(())
+    }
 }
 
 impl ArchiveWriter {
@@ -59,9 +128,16 @@ impl ArchiveWriter {
             stream_name,
             current_file: None,
             rotation_minutes,
+            salvaged: Vec::new(),
         }
     }
 
+    /// True if there are records not yet returned in a closed file's entry
+    pub fn has_records(&self) -> bool {
+        !self.salvaged.is_empty()
+            || self.current_file.as_ref().is_some_and(|f| f.stats.records > 0)
+    }
+
     fn should_rotate(&self, now: DateTime<Utc>) -> bool {
         if let Some(ref file) = self.current_file {
             let elapsed = now.signed_duration_since(file.start_time);
@@ -102,7 +178,11 @@ impl ArchiveWriter {
             suffix += 1;
         }
 
-        let file = File::create(&path)?;
+        let file = ArchiveFile {
+            file: File::create(&path)?,
+            #[cfg(test)]
+            fail_writes: false,
+        };
         let encoder = GzEncoder::new(file, Compression::default());
 
         self.current_file = Some(CurrentFile {
@@ -110,10 +190,9 @@ impl ArchiveWriter {
             final_name: filename,
             encoder,
             start_time: now,
-            records: 0,
-            bytes_written: 0,
-            first_seq: None,
-            last_seq: None,
+            crc: Crc::new(),
+            stats: FileStats::default(),
+            checkpoint: Checkpoint::default(),
         });
 
         Ok(())
@@ -126,20 +205,63 @@ impl ArchiveWriter {
         let final_path = file.path.with_file_name(&file.final_name);
         fs::rename(&file.path, &final_path)?;
 
-        let end_time = Utc::now();
+        Ok(Self::file_entry(file.final_name, file.start_time, &file.stats))
+    }
 
-        Ok(FileEntry {
-            name: file.final_name,
-            start: file.start_time,
-            end: end_time,
-            records: file.records,
-            bytes: file.bytes_written,
+    fn file_entry(name: String, start: DateTime<Utc>, stats: &FileStats) -> FileEntry {
+        FileEntry {
+            name,
+            start,
+            end: Utc::now(),
+            records: stats.records,
+            bytes: stats.bytes_written,
             raw_bytes: None,
             compression_ratio: None,
-            nats_start_seq: file.first_seq.unwrap_or(0),
-            nats_end_seq: file.last_seq.unwrap_or(0),
+            nats_start_seq: stats.first_seq.unwrap_or(0),
+            nats_end_seq: stats.last_seq.unwrap_or(0),
             records_by_type: None,
-        })
+            start_ts: stats.min_event_ts.and_then(DateTime::from_timestamp_micros),
+            end_ts: stats.max_event_ts.and_then(DateTime::from_timestamp_micros),
+        }
+    }
+
+    /// Drop the current file back to its last checkpoint after a failed
+    /// write. A file with flushed records is truncated there, terminated,
+    /// and renamed into place; one with none is deleted.
+    fn roll_back(&mut self) -> Result<(), ArchiverError> {
+        let Some(file) = self.current_file.take() else {
+            return Ok(());
+        };
+        let CurrentFile {
+            path,
+            final_name,
+            encoder,
+            start_time,
+            checkpoint,
+            ..
+        } = file;
+        // Dropping the encoder may append a trailer after the bad bytes;
+        // everything past the checkpoint is truncated below anyway
+        drop(encoder);
+
+        if checkpoint.stats.records == 0 {
+            fs::remove_file(&path)?;
+            return Ok(());
+        }
+
+        let mut out = OpenOptions::new().write(true).open(&path)?;
+        out.set_len(checkpoint.offset)?;
+        out.seek(SeekFrom::Start(checkpoint.offset))?;
+        out.write_all(&DEFLATE_FINAL_EMPTY_BLOCK)?;
+        out.write_all(&checkpoint.crc.to_le_bytes())?;
+        out.write_all(&checkpoint.amount.to_le_bytes())?;
+        out.sync_data()?;
+
+        let final_path = path.with_file_name(&final_name);
+        fs::rename(&path, &final_path)?;
+        self.salvaged
+            .push(Self::file_entry(final_name, start_time, &checkpoint.stats));
+        Ok(())
     }
 }
 
@@ -148,6 +270,7 @@ impl ArchiveOutput for ArchiveWriter {
         &mut self,
         data: &[u8],
         seq: u64,
+        capture: CaptureMeta,
         now: DateTime<Utc>,
     ) -> Result<Vec<FileEntry>, ArchiverError> {
         // Check if we need to rotate
@@ -168,42 +291,74 @@ impl ArchiveOutput for ArchiveWriter {
             )));
         };
 
-        // Track sequence
-        if file.first_seq.is_none() {
-            file.first_seq = Some(seq);
-        }
-        file.last_seq = Some(seq);
-
-        // Inject _received_at and _nats_seq into JSON payload via byte-level
-        // manipulation (no serde round-trip — this is the hot path).
         let received_at_micros = now.timestamp_micros();
-        if let Some(pos) = data.iter().rposition(|&b| b == b'}') {
-            file.encoder.write_all(&data[..pos])?;
-            let suffix = format!(
-                ",\"_received_at\":{},\"_nats_seq\":{}}}",
+
+        // Inject _received_at and _nats_seq (plus the connector's _capture_ts
+        // and _source_seq when its headers carried them) into JSON payload via
+        // byte-level manipulation (no serde round-trip — this is the hot path).
+        let written = if let Some(pos) = data.iter().rposition(|&b| b == b'}') {
+            let mut suffix = format!(
+                ",\"_received_at\":{},\"_nats_seq\":{}",
                 received_at_micros, seq
             );
-            file.encoder.write_all(suffix.as_bytes())?;
-            file.encoder.write_all(b"\n")?;
-            file.records += 1;
-            file.bytes_written += pos as u64 + suffix.len() as u64 + 1;
+            if let Some(ts) = capture.capture_ts_micros {
+                suffix.push_str(&format!(",\"_capture_ts\":{}", ts));
+            }
+            if let Some(source_seq) = capture.source_seq {
+                suffix.push_str(&format!(",\"_source_seq\":{}", source_seq));
+            }
+            suffix.push_str("}\n");
+            file.write_part(&data[..pos])
+                .and_then(|()| file.write_part(suffix.as_bytes()))
+                .map(|()| pos as u64 + suffix.len() as u64)
         } else {
             // No closing brace — write raw (shouldn't happen for well-formed JSON)
-            file.encoder.write_all(data)?;
-            file.encoder.write_all(b"\n")?;
-            file.records += 1;
-            file.bytes_written += data.len() as u64 + 1;
+            file.write_part(data)
+                .and_then(|()| file.write_part(b"\n"))
+                .map(|()| data.len() as u64 + 1)
+        };
+
+        let bytes = match written {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let discarded = file.stats.records - file.checkpoint.stats.records;
+                warn!(
+                    stream_name = %self.stream_name,
+                    error = %e,
+                    discarded,
+                    "Archive write failed mid-record, rolling file back to last flush"
+                );
+                if let Err(rollback) = self.roll_back() {
+                    error!(stream_name = %self.stream_name, error = %rollback, "Failed to roll back archive file");
+                }
+                return Err(e.into());
+            }
+        };
+
+        let stats = &mut file.stats;
+        if stats.first_seq.is_none() {
+            stats.first_seq = Some(seq);
         }
+        stats.last_seq = Some(seq);
+
+        let event_ts = extract_event_ts_micros(&self.feed, data).unwrap_or(received_at_micros);
+        stats.min_event_ts = Some(stats.min_event_ts.map_or(event_ts, |t| t.min(event_ts)));
+        stats.max_event_ts = Some(stats.max_event_ts.map_or(event_ts, |t| t.max(event_ts)));
+
+        stats.records += 1;
+        stats.bytes_written += bytes;
 
-        Ok(rotated.into_iter().collect())
+        let mut entries = std::mem::take(&mut self.salvaged);
+        entries.extend(rotated);
+        Ok(entries)
     }
 
     fn close(&mut self) -> Result<Vec<FileEntry>, ArchiverError> {
+        let mut entries = std::mem::take(&mut self.salvaged);
         if let Some(file) = self.current_file.take() {
-            let entry = self.finish_file(file)?;
-            return Ok(vec![entry]);
+            entries.push(self.finish_file(file)?);
         }
-        Ok(Vec::new())
+        Ok(entries)
     }
 
     fn flush(&mut self) -> Result<(), ArchiverError> {
@@ -214,7 +369,26 @@ impl ArchiveOutput for ArchiveWriter {
         // Flush gzip internal buffer to the OS page cache. We intentionally
         // skip fdatasync here — it runs every 100ms and the cost would hurt
         // throughput. Data reaches disk on rotation (finish_file) or OS writeback.
-        file.encoder.flush()?;
+        if let Err(e) = file.encoder.flush() {
+            warn!(
+                stream_name = %self.stream_name,
+                error = %e,
+                "Archive flush failed, rolling file back to last flush"
+            );
+            if let Err(rollback) = self.roll_back() {
+                error!(stream_name = %self.stream_name, error = %rollback, "Failed to roll back archive file");
+            }
+            return Err(e.into());
+        }
+
+        // The sync flush leaves the stream on a byte boundary: remember it
+        // as the point a failed write rolls back to
+        file.checkpoint = Checkpoint {
+            offset: file.encoder.get_ref().file.metadata()?.len(),
+            crc: file.crc.sum(),
+            amount: file.crc.amount(),
+            stats: file.stats,
+        };
         Ok(())
     }
 }
@@ -246,11 +420,11 @@ mod tests {
 
         let now = Utc::now();
         assert!(writer
-            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, now)
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, CaptureMeta::default(), now)
             .unwrap()
             .is_empty());
         assert!(writer
-            .write(br#"{"type":"trade","ticker":"KXBTC"}"#, 2, now)
+            .write(br#"{"type":"trade","ticker":"KXBTC"}"#, 2, CaptureMeta::default(), now)
             .unwrap()
             .is_empty());
 
@@ -281,6 +455,35 @@ mod tests {
         assert!(lines[1].as_ref().unwrap().contains("KXBTC"));
     }
 
+    #[test]
+    fn test_has_records_cleared_by_close() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+        assert!(!writer.has_records());
+
+        let now = Utc::now();
+        writer
+            .write(br#"{"type":"trade"}"#, 1, CaptureMeta::default(), now)
+            .unwrap();
+        assert!(writer.has_records());
+
+        assert_eq!(writer.close().unwrap().len(), 1);
+        assert!(!writer.has_records());
+
+        // The next write opens a fresh file
+        writer
+            .write(br#"{"type":"trade"}"#, 2, CaptureMeta::default(), now)
+            .unwrap();
+        let entries = writer.close().unwrap();
+        assert_eq!(entries[0].nats_start_seq, 2);
+        assert_eq!(entries[0].records, 1);
+    }
+
     #[test]
     fn test_tmp_file_during_write() {
         let tmp = TempDir::new().unwrap();
@@ -293,7 +496,7 @@ mod tests {
 
         let now = Utc::now();
         writer
-            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, now)
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, CaptureMeta::default(), now)
             .unwrap();
 
         // During active write, .tmp should exist and final should not
@@ -339,7 +542,7 @@ mod tests {
         std::fs::write(&existing_final, b"existing").unwrap();
 
         writer
-            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, now)
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, CaptureMeta::default(), now)
             .unwrap();
         let entries = writer.close().unwrap();
         assert_eq!(entries.len(), 1);
@@ -360,7 +563,7 @@ mod tests {
 
         let now = Utc::now();
         writer
-            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, now)
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, CaptureMeta::default(), now)
             .unwrap();
 
         writer.flush().unwrap();
@@ -381,10 +584,10 @@ mod tests {
 
         let now = Utc::now();
         writer
-            .write(br#"{"type":"trade","ticker":"INXD"}"#, 42, now)
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 42, CaptureMeta::default(), now)
             .unwrap();
         writer
-            .write(br#"{"type":"ticker","msg":{"market_ticker":"KXBTC"}}"#, 43, now)
+            .write(br#"{"type":"ticker","msg":{"market_ticker":"KXBTC"}}"#, 43, CaptureMeta::default(), now)
             .unwrap();
 
         let entries = writer.close().unwrap();
@@ -422,6 +625,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_injects_capture_fields_when_present() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+
+        let now = Utc::now();
+        let capture = CaptureMeta {
+            capture_ts_micros: Some(1_707_667_200_123_456),
+            source_seq: Some(7),
+        };
+        writer
+            .write(br#"{"type":"orderbook_delta","seq":7}"#, 1, capture, now)
+            .unwrap();
+
+        let entries = writer.close().unwrap();
+        let date_str = now.format("%Y-%m-%d").to_string();
+        let dir = tmp.path().join("kalshi").join("politics").join(&date_str);
+        let lines = read_gz_lines(&dir.join(&entries[0].name));
+
+        let json: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(json["_capture_ts"].as_i64(), Some(1_707_667_200_123_456));
+        assert_eq!(json["_source_seq"].as_u64(), Some(7));
+        assert_eq!(json["_received_at"].as_i64(), Some(now.timestamp_micros()));
+    }
+
+    #[test]
+    fn test_file_entry_records_event_time_bounds() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+
+        let now = DateTime::from_timestamp(1_707_667_300, 0).unwrap();
+        for data in [
+            br#"{"type":"ticker","msg":{"market_ticker":"KXBTC","ts":1707667250}}"#.as_slice(),
+            br#"{"type":"ticker","msg":{"market_ticker":"KXBTC","ts":1707667200}}"#.as_slice(),
+            // No event time: falls back to the write wall-clock
+            br#"{"type":"ok","id":1}"#.as_slice(),
+        ] {
+            writer.write(data, 1, CaptureMeta::default(), now).unwrap();
+        }
+
+        let entries = writer.close().unwrap();
+        assert_eq!(entries[0].start_ts, DateTime::from_timestamp(1_707_667_200, 0));
+        assert_eq!(entries[0].end_ts, Some(now));
+    }
+
     #[test]
     fn test_bytes_written_accounts_for_injected_fields() {
         let tmp = TempDir::new().unwrap();
@@ -434,11 +692,100 @@ mod tests {
 
         let now = Utc::now();
         let data = br#"{"type":"trade"}"#;
-        writer.write(data, 1, now).unwrap();
+        writer.write(data, 1, CaptureMeta::default(), now).unwrap();
 
         let entries = writer.close().unwrap();
         // bytes_written should be greater than just data + newline
         // because we injected ,"_received_at":...,"_nats_seq":...}
         assert!(entries[0].bytes > data.len() as u64 + 1);
     }
+
+    #[test]
+    fn test_encoder_error_mid_record_rolls_back_to_flush() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+
+        let now = Utc::now();
+        writer
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 1, CaptureMeta::default(), now)
+            .unwrap();
+        writer.flush().unwrap();
+        // Written but never flushed, so it goes with the failed record
+        writer
+            .write(br#"{"type":"trade","ticker":"KXBTC"}"#, 2, CaptureMeta::default(), now)
+            .unwrap();
+
+        // A record too large and too random for the encoder to buffer, so the
+        // disk write fails after part of it has been handed to the encoder
+        let mut state: u32 = 0x9e37_79b9;
+        let noise: String = (0..256 * 1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (b'a' + (state % 26) as u8) as char
+            })
+            .collect();
+        let big = format!(r#"{{"type":"trade","noise":"{}"}}"#, noise);
+        writer.current_file.as_mut().unwrap().encoder.get_mut().fail_writes = true;
+        assert!(writer
+            .write(big.as_bytes(), 3, CaptureMeta::default(), now)
+            .is_err());
+        assert!(writer.has_records(), "salvaged file is still to be reported");
+
+        // The next write starts a fresh file and reports the salvaged one
+        let entries = writer
+            .write(br#"{"type":"trade","ticker":"INXD"}"#, 4, CaptureMeta::default(), now)
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        let salvaged = &entries[0];
+        assert_eq!(salvaged.records, 1);
+        assert_eq!(salvaged.nats_start_seq, 1);
+        assert_eq!(salvaged.nats_end_seq, 1);
+
+        let date_str = now.format("%Y-%m-%d").to_string();
+        let dir = tmp.path().join("kalshi").join("politics").join(&date_str);
+        assert!(!dir.join(format!("{}.tmp", salvaged.name)).exists());
+
+        // Valid gzip (trailer CRC and length check out) with only the flushed record
+        let lines = read_gz_lines(&dir.join(&salvaged.name));
+        assert_eq!(lines.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(json["_nats_seq"].as_u64(), Some(1));
+
+        let entries = writer.close().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_ne!(entries[0].name, salvaged.name);
+        assert_eq!(entries[0].nats_start_seq, 4);
+        assert_eq!(read_gz_lines(&dir.join(&entries[0].name)).len(), 1);
+    }
+
+    #[test]
+    fn test_flush_error_before_any_checkpoint_discards_file() {
+        let tmp = TempDir::new().unwrap();
+        let mut writer = ArchiveWriter::new(
+            tmp.path().to_path_buf(),
+            "kalshi".to_string(),
+            "politics".to_string(),
+            15,
+        );
+
+        let now = Utc::now();
+        writer
+            .write(br#"{"type":"trade"}"#, 1, CaptureMeta::default(), now)
+            .unwrap();
+        let file = writer.current_file.as_mut().unwrap();
+        let tmp_path = file.path.clone();
+        file.encoder.get_mut().fail_writes = true;
+
+        assert!(writer.flush().is_err());
+        assert!(!tmp_path.exists(), "file with no flushed records is removed");
+        assert!(!writer.has_records());
+        assert!(writer.close().unwrap().is_empty());
+    }
 }
@@ -111,14 +111,37 @@ impl RedisCache {
         Ok(keys.len() as u64)
     }
 
-    /// DEL all keys matching a pattern.
-    /// Uses KEYS command — safe for small keyspaces (monitor:* has ~200 keys).
+    /// Get all keys matching a pattern using incremental SCAN (non-blocking,
+    /// unlike KEYS).
+    pub async fn scan(&self, pattern: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        let mut cursor: u64 = 0;
+        let mut keys = Vec::new();
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await?;
+            keys.extend(batch);
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        // SCAN may return a key more than once across iterations
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+
+    /// DEL all keys matching a pattern (enumerated via SCAN).
     pub async fn del_pattern(&self, pattern: &str) -> Result<u64> {
+        let keys = self.scan(pattern).await?;
         let mut conn = self.conn.clone();
-        let keys: Vec<String> = redis::cmd("KEYS")
-            .arg(pattern)
-            .query_async(&mut conn)
-            .await?;
 
         if keys.is_empty() {
             return Ok(0);
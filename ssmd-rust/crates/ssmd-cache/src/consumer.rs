@@ -222,6 +222,7 @@ impl CdcConsumer {
                     }
 
                     processed += 1;
+                    self.metrics.last_applied_timestamp.set(Utc::now().timestamp() as f64);
                     self.metrics.cdc_events.with_label_values(&[&event.table, &event.op]).inc();
                     if processed % 100 == 0 {
                         tracing::info!(
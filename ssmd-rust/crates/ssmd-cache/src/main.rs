@@ -1,5 +1,5 @@
-use axum::{routing::get, Router, response::IntoResponse};
-use prometheus::{Registry, TextEncoder, Encoder};
+use axum::{routing::get, Json, Router, response::IntoResponse};
+use prometheus::{Gauge, Registry, TextEncoder, Encoder};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use ssmd_cache::{
@@ -26,6 +26,19 @@ async fn metrics_handler(
     )
 }
 
+/// Liveness plus CDC freshness: when the last CDC event was applied to Redis
+/// (null until the first one), so staleness can be alerted on
+async fn healthz_handler(last_applied: Gauge) -> impl IntoResponse {
+    let last_applied = Some(last_applied.get() as i64)
+        .filter(|ts| *ts > 0)
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+    Json(serde_json::json!({
+        "status": "ok",
+        "last_applied_cdc": last_applied.map(|t| t.to_rfc3339()),
+        "last_applied_age_secs": last_applied.map(|t| (chrono::Utc::now() - t).num_seconds()),
+    }))
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::registry()
@@ -49,10 +62,12 @@ async fn main() -> anyhow::Result<()> {
 
     // Spawn metrics HTTP server on port 9090
     let metrics_registry = registry.clone();
+    let last_applied = cache_metrics.last_applied_timestamp.clone();
     tokio::spawn(async move {
         let app = Router::new()
             .route("/metrics", get(metrics_handler))
             .route("/health", get(|| async { "ok" }))
+            .route("/healthz", get(move || healthz_handler(last_applied.clone())))
             .with_state(metrics_registry);
 
         let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await.unwrap();
@@ -4,6 +4,7 @@ use prometheus::{IntCounterVec, IntCounter, Gauge, Opts, Registry};
 pub struct CacheMetrics {
     pub cdc_events: IntCounterVec,
     pub last_event_timestamp: Gauge,
+    pub last_applied_timestamp: Gauge,
     pub gaps: IntCounter,
     pub skipped: IntCounter,
     pub redis_writes: IntCounterVec,
@@ -23,6 +24,9 @@ impl CacheMetrics {
         let last_event_timestamp = Gauge::with_opts(
             Opts::new("ssmd_cache_cdc_last_event_timestamp", "Unix epoch of last CDC event"),
         )?;
+        let last_applied_timestamp = Gauge::with_opts(
+            Opts::new("ssmd_cache_cdc_last_applied_timestamp", "Unix epoch of last CDC event applied to Redis"),
+        )?;
         let gaps = IntCounter::with_opts(
             Opts::new("ssmd_cache_cdc_gaps_total", "LSN gaps detected"),
         )?;
@@ -49,6 +53,7 @@ impl CacheMetrics {
 
         registry.register(Box::new(cdc_events.clone()))?;
         registry.register(Box::new(last_event_timestamp.clone()))?;
+        registry.register(Box::new(last_applied_timestamp.clone()))?;
         registry.register(Box::new(gaps.clone()))?;
         registry.register(Box::new(skipped.clone()))?;
         registry.register(Box::new(redis_writes.clone()))?;
@@ -60,6 +65,7 @@ impl CacheMetrics {
         Ok(Self {
             cdc_events,
             last_event_timestamp,
+            last_applied_timestamp,
             gaps,
             skipped,
             redis_writes,
@@ -2,6 +2,12 @@ use futures_util::TryStreamExt;
 use deadpool_postgres::Pool;
 use crate::{Result, Error, cache::RedisCache};
 
+/// Exchanges that get a precomputed `monitor:categories:{exchange}` index.
+pub const MONITOR_EXCHANGES: &[&str] = &["kalshi", "polymarket", "kraken"];
+
+/// Per-exchange category → matching series count, accumulated while warming.
+type CategoryIndex = std::collections::HashMap<&'static str, std::collections::HashMap<String, i64>>;
+
 pub struct CacheWarmer {
     pool: Pool,
 }
@@ -28,6 +34,7 @@ impl CacheWarmer {
     ///
     /// Creates:
     ///   monitor:categories          → { cat: {"event_count":N,"series_count":N} }
+    ///   monitor:categories:{exchange} → { cat: {"series_count":N} }  (series matching the exchange)
     ///   monitor:series:{category}   → { series: {"title":"...","active_events":N,"active_markets":N} }
     ///   monitor:events:{series}     → { event: {"title":"...","status":"...","strike_date":"...","market_count":N} }
     ///   monitor:markets:{event}     → { market: {"title":"...","status":"...","close_time":"..."} }
@@ -41,6 +48,7 @@ impl CacheWarmer {
         let mut tmp_keys: Vec<String> = Vec::new();
         let mut final_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
         let mut total_keys: u64 = 0;
+        let mut category_index = CategoryIndex::new();
 
         // 1. Categories: only categories that have events with live markets
         let tmp_cat_key = "monitor:categories:_tmp".to_string();
@@ -121,6 +129,7 @@ impl CacheWarmer {
                     "active_events": active_events,
                     "active_markets": active_markets,
                 });
+                index_series(&mut category_index, &category, &ticker, &val);
                 let tmp_key = format!("monitor:series:{}:_tmp", category);
                 let final_key = format!("monitor:series:{}", category);
                 cache.hset(&tmp_key, &ticker, &val.to_string()).await?;
@@ -287,7 +296,26 @@ impl CacheWarmer {
         }
 
         // 5. Kraken Futures pairs → merged into monitor hierarchy
-        total_keys += self.warm_pairs_monitor(cache, &mut tmp_keys, &mut final_keys).await?;
+        total_keys += self
+            .warm_pairs_monitor(cache, &mut tmp_keys, &mut final_keys, &mut category_index)
+            .await?;
+
+        // 6. Per-exchange category index, so readers need one HGETALL instead of
+        //    scanning every monitor:series:{category} hash.
+        for (exchange, categories) in &category_index {
+            let tmp_key = format!("monitor:categories:{}:_tmp", exchange);
+            for (category, series_count) in categories {
+                let val = serde_json::json!({ "series_count": series_count });
+                cache.hset(&tmp_key, category, &val.to_string()).await?;
+            }
+            final_keys.insert(format!("monitor:categories:{}", exchange));
+            tmp_keys.push(tmp_key);
+            total_keys += categories.len() as u64;
+        }
+        tracing::info!(
+            exchanges = category_index.len(),
+            "Warmed monitor:categories:{{exchange}}:_tmp"
+        );
 
         // Atomic swap: RENAME each :_tmp key to its final name.
         let mut renamed = 0u64;
@@ -336,7 +364,7 @@ impl CacheWarmer {
     ///   Series:   base currency group (BTC, ETH, etc.)
     ///   Event:    "{base}-perps" synthetic event for perpetuals
     ///   Market:   pair_id (e.g., "PF_XBTUSD")
-    async fn warm_pairs_monitor(&self, cache: &RedisCache, tmp_keys: &mut Vec<String>, final_keys: &mut std::collections::HashSet<String>) -> Result<u64> {
+    async fn warm_pairs_monitor(&self, cache: &RedisCache, tmp_keys: &mut Vec<String>, final_keys: &mut std::collections::HashSet<String>, category_index: &mut CategoryIndex) -> Result<u64> {
         let client = self.pool.get().await?;
         // Kraken pairs are few (~20-50), collect to group by base currency
         let rows = client
@@ -381,6 +409,7 @@ impl CacheWarmer {
                 "title": format!("{} Perpetuals", base),
                 "active_pairs": active_pairs,
             });
+            index_series(category_index, "Kraken Futures", base, &series_val);
             let tmp_series = "monitor:series:Kraken Futures:_tmp".to_string();
             let final_series = "monitor:series:Kraken Futures".to_string();
             cache.hset(&tmp_series, base, &series_val.to_string()).await?;
@@ -465,6 +494,33 @@ impl CacheWarmer {
     }
 }
 
+/// Whether a `monitor:series:{category}` entry belongs to `exchange`.
+///
+/// Kalshi entries carry active_events/active_markets, Polymarket entries
+/// active_conditions or a `PM:` ticker, Kraken entries active_pairs. ssmd-harman
+/// applies the same rule when it falls back to scanning series hashes.
+pub fn series_matches_exchange(exchange: &str, ticker: &str, val: &serde_json::Value) -> bool {
+    match exchange {
+        "kalshi" => val.get("active_events").is_some() || val.get("active_markets").is_some(),
+        "polymarket" => val.get("active_conditions").is_some() || ticker.starts_with("PM:"),
+        "kraken" => val.get("active_pairs").is_some(),
+        _ => false,
+    }
+}
+
+/// Count a series entry toward every exchange it matches.
+fn index_series(index: &mut CategoryIndex, category: &str, ticker: &str, val: &serde_json::Value) {
+    for &exchange in MONITOR_EXCHANGES {
+        if series_matches_exchange(exchange, ticker, val) {
+            *index
+                .entry(exchange)
+                .or_default()
+                .entry(category.to_string())
+                .or_default() += 1;
+        }
+    }
+}
+
 /// Flush a batch of lifecycle events into the existing market hash entry in Redis.
 async fn flush_lifecycle(
     cache: &RedisCache,
@@ -507,3 +563,30 @@ fn extract_event_ticker(market_ticker: &str) -> &str {
     }
     market_ticker
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_series_counts_per_exchange() {
+        let mut index = CategoryIndex::new();
+        let kalshi = serde_json::json!({"title": "BTC", "active_events": 2, "active_markets": 10});
+        let kraken = serde_json::json!({"title": "BTC Perpetuals", "active_pairs": 3});
+        index_series(&mut index, "Crypto", "KXBTCD", &kalshi);
+        index_series(&mut index, "Crypto", "KXETHD", &kalshi);
+        index_series(&mut index, "Crypto", "PM:btc-up", &kalshi);
+        index_series(&mut index, "Kraken Futures", "BTC", &kraken);
+
+        assert_eq!(index["kalshi"]["Crypto"], 3);
+        assert_eq!(index["polymarket"]["Crypto"], 1);
+        assert_eq!(index["kraken"]["Kraken Futures"], 1);
+        assert!(!index["kalshi"].contains_key("Kraken Futures"));
+    }
+
+    #[test]
+    fn test_series_matches_exchange_unknown() {
+        let val = serde_json::json!({"active_events": 1});
+        assert!(!series_matches_exchange("binance", "X", &val));
+    }
+}
@@ -8,6 +8,9 @@ pub struct Config {
     pub nats_url: String,
     pub slot_name: String,
     pub tables: Vec<String>,
+    /// Events publish to `{subject_prefix}.{table}.{op}`, e.g. `cdc.harman`
+    /// for the harman database's `prediction_orders`/`fills`/`sessions`
+    pub subject_prefix: String,
 }
 
 impl Config {
@@ -32,11 +35,16 @@ impl Config {
                 "polymarket_tokens".into(),
             ]);
 
+        let subject_prefix = std::env::var("CDC_SUBJECT_PREFIX")
+            .map(|s| s.trim_end_matches('.').to_string())
+            .unwrap_or_else(|_| "cdc".into());
+
         Ok(Self {
             database_url,
             nats_url,
             slot_name,
             tables,
+            subject_prefix,
         })
     }
 }
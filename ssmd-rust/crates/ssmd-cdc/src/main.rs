@@ -62,6 +62,7 @@ async fn main() -> anyhow::Result<()> {
         database_url = %config.database_url.split('@').next_back().unwrap_or("***"),
         nats_url = %config.nats_url,
         slot = %config.slot_name,
+        subject_prefix = %config.subject_prefix,
         health_addr = %args.health_addr,
         "Starting ssmd-cdc"
     );
@@ -81,7 +82,7 @@ async fn main() -> anyhow::Result<()> {
     metrics::CDC_PUBLISH_ERRORS.with_label_values(&["_init"]);
 
     // Connect to NATS and ensure stream exists
-    let publisher = Publisher::new(&config.nats_url, &args.stream_name).await?;
+    let publisher = Publisher::new(&config.nats_url, &args.stream_name, &config.subject_prefix).await?;
     publisher.ensure_stream().await?;
 
     // Connect to PostgreSQL and ensure replication slot exists
@@ -2,13 +2,28 @@
 use serde::{Deserialize, Serialize};
 
 /// Published CDC event (to NATS)
+///
+/// `before`/`after` are the row images: INSERT has only `after`, DELETE only
+/// `before`, UPDATE both. Watched tables need `REPLICA IDENTITY FULL`
+/// (`ALTER TABLE ... REPLICA IDENTITY FULL`) for UPDATE before-images and
+/// full DELETE rows; with the default identity an UPDATE has no `before` and
+/// a DELETE's `before` holds only the primary key columns.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CdcEvent {
+    /// LSN of the change itself
     pub lsn: String,
+    /// LSN of the transaction's commit, when the commit was decoded in the same batch
+    #[serde(default)]
+    pub commit_lsn: Option<String>,
     pub table: String,
     pub op: CdcOperation,
     pub key: serde_json::Value,
+    /// `after`, or `before` for DELETEs (kept for existing consumers)
     pub data: Option<serde_json::Value>,
+    #[serde(default)]
+    pub before: Option<serde_json::Value>,
+    #[serde(default)]
+    pub after: Option<serde_json::Value>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
@@ -46,18 +61,24 @@ mod tests {
     fn test_dedup_id_unique_per_table_and_op() {
         let event1 = CdcEvent {
             lsn: "22/1D4AE960".into(),
+            commit_lsn: None,
             table: "events".into(),
             op: CdcOperation::Update,
             key: serde_json::json!({"event_ticker": "KXBTCD-26MAR1413"}),
             data: None,
+            before: None,
+            after: None,
             timestamp: chrono::Utc::now(),
         };
         let event2 = CdcEvent {
             lsn: "22/1D4AE960".into(),
+            commit_lsn: None,
             table: "markets".into(),
             op: CdcOperation::Update,
             key: serde_json::json!({"ticker": "KXBTCD-26MAR1413-T70000"}),
             data: None,
+            before: None,
+            after: None,
             timestamp: chrono::Utc::now(),
         };
         assert_ne!(event1.dedup_id(), event2.dedup_id());
@@ -68,14 +89,26 @@ mod tests {
     fn test_cdc_operation_serialization() {
         let event = CdcEvent {
             lsn: "0/16B3748".into(),
+            commit_lsn: None,
             table: "markets".into(),
             op: CdcOperation::Update,
             key: serde_json::json!({"ticker": "INXD-25-B4000"}),
             data: Some(serde_json::json!({"status": "active"})),
+            before: None,
+            after: Some(serde_json::json!({"status": "active"})),
             timestamp: chrono::Utc::now(),
         };
 
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"op\":\"update\""));
+        assert!(json.contains("\"before\":null"));
+    }
+
+    #[test]
+    fn test_deserialize_event_without_images() {
+        // Events published before before/after images were added
+        let json = r#"{"lsn":"0/1","table":"markets","op":"insert","key":{"ticker":"T"},"data":{"ticker":"T"},"timestamp":"2026-01-01T00:00:00Z"}"#;
+        let event: CdcEvent = serde_json::from_str(json).unwrap();
+        assert!(event.before.is_none() && event.after.is_none() && event.commit_lsn.is_none());
     }
 }
@@ -1,15 +1,21 @@
 //! NATS JetStream publisher for CDC events
 
+use std::collections::HashSet;
+use std::sync::Mutex;
+
 use async_nats::jetstream::{self, Context};
-use crate::{Error, Result, messages::CdcEvent};
+use crate::{Error, Result, messages::{CdcEvent, CdcOperation}};
 
 pub struct Publisher {
     js: Context,
     stream_name: String,
+    subject_prefix: String,
+    /// Tables already warned about for missing UPDATE before-images
+    warned_tables: Mutex<HashSet<String>>,
 }
 
 impl Publisher {
-    pub async fn new(nats_url: &str, stream_name: &str) -> Result<Self> {
+    pub async fn new(nats_url: &str, stream_name: &str, subject_prefix: &str) -> Result<Self> {
         let client = async_nats::connect(nats_url).await
             .map_err(|e| Error::Config(format!("NATS connection failed: {}", e)))?;
         let js = jetstream::new(client);
@@ -17,14 +23,20 @@ impl Publisher {
         Ok(Self {
             js,
             stream_name: stream_name.to_string(),
+            subject_prefix: subject_prefix.to_string(),
+            warned_tables: Mutex::new(HashSet::new()),
         })
     }
 
-    /// Ensure the CDC stream exists
+    /// Ensure the CDC stream exists.
+    ///
+    /// A new stream captures `{subject_prefix}.>`. An existing stream is used
+    /// as-is, so a prefix like `cdc.harman` can publish into a stream that
+    /// already captures `cdc.>` (NATS rejects overlapping stream subjects).
     pub async fn ensure_stream(&self) -> Result<()> {
         let config = jetstream::stream::Config {
             name: self.stream_name.clone(),
-            subjects: vec!["cdc.>".into()],
+            subjects: vec![format!("{}.>", self.subject_prefix)],
             max_messages: 100_000,
             max_age: std::time::Duration::from_secs(7 * 24 * 60 * 60), // 7 days
             storage: jetstream::stream::StorageType::File,
@@ -49,7 +61,17 @@ impl Publisher {
     /// JetStream deduplicates by Nats-Msg-Id, making replayed peeks safe.
     /// LSN alone is NOT unique — a single transaction can touch multiple tables.
     pub async fn publish(&self, event: &CdcEvent) -> Result<()> {
-        let subject = format!("cdc.{}.{}", event.table, event.op.as_str());
+        if event.op == CdcOperation::Update
+            && event.before.is_none()
+            && self.warned_tables.lock().unwrap().insert(event.table.clone())
+        {
+            tracing::warn!(
+                table = %event.table,
+                "UPDATE has no before-image — set REPLICA IDENTITY FULL on the table"
+            );
+        }
+
+        let subject = format!("{}.{}.{}", self.subject_prefix, event.table, event.op.as_str());
         let payload = serde_json::to_vec(event)?;
 
         let dedup_id = event.dedup_id();
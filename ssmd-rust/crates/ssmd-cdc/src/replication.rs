@@ -170,92 +170,9 @@ impl ReplicationSlot {
 
     /// Parse test_decoding output rows into CdcEvents
     fn parse_test_decoding_rows(rows: Vec<tokio_postgres::Row>) -> Result<Vec<CdcEvent>> {
-        let mut events = Vec::new();
-
-        // Parse test_decoding output format:
-        // table schema.table: INSERT: col1[type]:value1 col2[type]:value2 ...
-        // table schema.table: UPDATE: old-key: col1[type]:value1 col1[type]:value1 ...
-        // table schema.table: DELETE: col1[type]:value1
-
-        for row in rows {
-            let lsn: String = row.get(0);
-            let data: String = row.get(1);
-
-            if let Some(caps) = TABLE_RE.captures(&data) {
-                let full_table = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                let table = full_table.split('.').next_back().unwrap_or(full_table).to_string();
-                let op_str = caps.get(2).map(|m| m.as_str()).unwrap_or("");
-                let cols_str = caps.get(3).map(|m| m.as_str()).unwrap_or("");
-
-                let op = match op_str {
-                    "INSERT" => CdcOperation::Insert,
-                    "UPDATE" => CdcOperation::Update,
-                    "DELETE" => CdcOperation::Delete,
-                    _ => continue,
-                };
-
-                // Parse columns
-                let mut columns: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
-                let mut first_col_name = String::new();
-                let mut first_col_value = serde_json::Value::Null;
-
-                for cap in COL_RE.captures_iter(cols_str) {
-                    let col_name = cap.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
-                    let _col_type = cap.get(2).map(|m| m.as_str()).unwrap_or("");
-                    let col_value_str = cap.get(3).map(|m| m.as_str()).unwrap_or("");
-
-                    let value = if col_value_str.starts_with('\'') && col_value_str.ends_with('\'') {
-                        let unquoted = &col_value_str[1..col_value_str.len()-1];
-                        let unescaped = unquoted.replace("''", "'");
-                        serde_json::Value::String(unescaped)
-                    } else if col_value_str == "null" {
-                        serde_json::Value::Null
-                    } else if let Ok(n) = col_value_str.parse::<i64>() {
-                        serde_json::Value::Number(n.into())
-                    } else if let Ok(f) = col_value_str.parse::<f64>() {
-                        serde_json::Number::from_f64(f)
-                            .map(serde_json::Value::Number)
-                            .unwrap_or(serde_json::Value::String(col_value_str.to_string()))
-                    } else if col_value_str == "true" {
-                        serde_json::Value::Bool(true)
-                    } else if col_value_str == "false" {
-                        serde_json::Value::Bool(false)
-                    } else {
-                        serde_json::Value::String(col_value_str.to_string())
-                    };
-
-                    if first_col_name.is_empty() {
-                        first_col_name = col_name.clone();
-                        first_col_value = value.clone();
-                    }
-
-                    columns.insert(col_name, value);
-                }
-
-                let key = if !first_col_name.is_empty() {
-                    serde_json::json!({ first_col_name: first_col_value })
-                } else {
-                    serde_json::Value::Null
-                };
-
-                let data = if columns.is_empty() {
-                    None
-                } else {
-                    Some(serde_json::Value::Object(columns))
-                };
-
-                events.push(CdcEvent {
-                    lsn: lsn.clone(),
-                    table,
-                    op,
-                    key,
-                    data,
-                    timestamp: chrono::Utc::now(),
-                });
-            }
-        }
-
-        Ok(events)
+        Ok(parse_test_decoding(
+            rows.into_iter().map(|row| (row.get(0), row.get(1))),
+        ))
     }
 
     /// Advance the replication slot past the given LSN, consuming all changes up to it.
@@ -273,6 +190,131 @@ impl ReplicationSlot {
     }
 }
 
+/// Parse `(lsn, data)` rows of test_decoding output into CdcEvents.
+///
+/// Output format:
+///   BEGIN 1234
+///   table schema.table: INSERT: col1[type]:value1 col2[type]:value2 ...
+///   table schema.table: UPDATE: old-key: col1[type]:value1 ... new-tuple: col1[type]:value1 ...
+///   table schema.table: DELETE: col1[type]:value1
+///   COMMIT 1234
+///
+/// `old-key:` (the UPDATE before-image) and full DELETE rows are only emitted
+/// with `REPLICA IDENTITY FULL`; otherwise UPDATEs carry just the new tuple and
+/// DELETEs just the key columns. Events get the LSN of their transaction's
+/// COMMIT row when it's in the same batch.
+fn parse_test_decoding(rows: impl IntoIterator<Item = (String, String)>) -> Vec<CdcEvent> {
+    let mut events = Vec::new();
+    let mut txn_start = 0;
+
+    for (lsn, data) in rows {
+        if data.starts_with("BEGIN") {
+            txn_start = events.len();
+            continue;
+        }
+        if data.starts_with("COMMIT") {
+            for event in &mut events[txn_start..] {
+                event.commit_lsn = Some(lsn.clone());
+            }
+            txn_start = events.len();
+            continue;
+        }
+
+        let Some(caps) = TABLE_RE.captures(&data) else { continue };
+        let full_table = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+        let table = full_table.split('.').next_back().unwrap_or(full_table).to_string();
+        let op_str = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+        let cols_str = caps.get(3).map(|m| m.as_str()).unwrap_or("").trim();
+
+        let op = match op_str {
+            "INSERT" => CdcOperation::Insert,
+            "UPDATE" => CdcOperation::Update,
+            "DELETE" => CdcOperation::Delete,
+            _ => continue,
+        };
+
+        let (before_str, after_str) = match op {
+            CdcOperation::Insert => (None, Some(cols_str)),
+            CdcOperation::Update => match cols_str
+                .strip_prefix("old-key:")
+                .and_then(|rest| rest.split_once("new-tuple:"))
+            {
+                Some((old, new)) => (Some(old), Some(new)),
+                None => (None, Some(cols_str)),
+            },
+            CdcOperation::Delete => (Some(cols_str), None),
+        };
+
+        // Key is the first column of the first image test_decoding printed
+        let key = before_str
+            .or(after_str)
+            .and_then(|cols| COL_RE.captures(cols))
+            .map(|cap| {
+                let name = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+                serde_json::json!({ name: parse_value(cap.get(3).map(|m| m.as_str()).unwrap_or("")) })
+            })
+            .unwrap_or(serde_json::Value::Null);
+
+        let before = before_str.and_then(parse_columns);
+        let after = after_str.and_then(parse_columns);
+
+        events.push(CdcEvent {
+            lsn,
+            commit_lsn: None,
+            table,
+            op,
+            key,
+            data: after.clone().or_else(|| before.clone()),
+            before,
+            after,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    events
+}
+
+/// Parse `col[type]:value ...` into a JSON object.
+/// Returns `None` when there are no columns (e.g. `(no-tuple-data)`).
+fn parse_columns(cols_str: &str) -> Option<serde_json::Value> {
+    let columns: serde_json::Map<String, serde_json::Value> = COL_RE
+        .captures_iter(cols_str)
+        .map(|cap| {
+            let col_name = cap.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
+            (col_name, parse_value(cap.get(3).map(|m| m.as_str()).unwrap_or("")))
+        })
+        .collect();
+
+    if columns.is_empty() {
+        None
+    } else {
+        Some(serde_json::Value::Object(columns))
+    }
+}
+
+/// Convert a test_decoding column value to JSON
+fn parse_value(col_value_str: &str) -> serde_json::Value {
+    if col_value_str.starts_with('\'') && col_value_str.ends_with('\'') {
+        let unquoted = &col_value_str[1..col_value_str.len()-1];
+        let unescaped = unquoted.replace("''", "'");
+        serde_json::Value::String(unescaped)
+    } else if col_value_str == "null" {
+        serde_json::Value::Null
+    } else if let Ok(n) = col_value_str.parse::<i64>() {
+        serde_json::Value::Number(n.into())
+    } else if let Ok(f) = col_value_str.parse::<f64>() {
+        serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::String(col_value_str.to_string()))
+    } else if col_value_str == "true" {
+        serde_json::Value::Bool(true)
+    } else if col_value_str == "false" {
+        serde_json::Value::Bool(false)
+    } else {
+        serde_json::Value::String(col_value_str.to_string())
+    }
+}
+
 /// Build the SQL for advancing a replication slot.
 /// Uses format! with string literal — tokio-postgres cannot bind &str to pg_lsn.
 /// The LSN comes from pg_logical_slot_peek_changes output, not user input.
@@ -287,6 +329,56 @@ fn build_advance_sql(lsn: &str) -> String {
 mod tests {
     use super::*;
 
+    fn rows(rows: &[(&str, &str)]) -> Vec<(String, String)> {
+        rows.iter().map(|(l, d)| (l.to_string(), d.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_update_with_replica_identity_full() {
+        let events = parse_test_decoding(rows(&[
+            ("0/10", "BEGIN 700"),
+            ("0/18", "table public.markets: UPDATE: old-key: ticker[text]:'KX-1' status[text]:'active' new-tuple: ticker[text]:'KX-1' status[text]:'settled'"),
+            ("0/20", "COMMIT 700"),
+        ]));
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.op, CdcOperation::Update);
+        assert_eq!(event.lsn, "0/18");
+        assert_eq!(event.commit_lsn.as_deref(), Some("0/20"));
+        assert_eq!(event.key, serde_json::json!({"ticker": "KX-1"}));
+        assert_eq!(event.before, Some(serde_json::json!({"ticker": "KX-1", "status": "active"})));
+        assert_eq!(event.after, Some(serde_json::json!({"ticker": "KX-1", "status": "settled"})));
+        assert_eq!(event.data, event.after);
+    }
+
+    #[test]
+    fn test_parse_update_without_before_image() {
+        let events = parse_test_decoding(rows(&[
+            ("0/18", "table public.markets: UPDATE: ticker[text]:'KX-1' status[text]:'settled'"),
+        ]));
+        assert!(events[0].before.is_none());
+        assert_eq!(events[0].after, Some(serde_json::json!({"ticker": "KX-1", "status": "settled"})));
+        // No COMMIT row in the batch
+        assert!(events[0].commit_lsn.is_none());
+    }
+
+    #[test]
+    fn test_parse_insert_and_delete_images() {
+        let events = parse_test_decoding(rows(&[
+            ("0/10", "BEGIN 701"),
+            ("0/18", "table public.fills: INSERT: id[bigint]:7 qty[integer]:3"),
+            ("0/20", "table public.fills: DELETE: id[bigint]:7"),
+            ("0/28", "table public.fills: DELETE: (no-tuple-data)"),
+            ("0/30", "COMMIT 701"),
+        ]));
+        assert_eq!(events.len(), 3);
+        assert_eq!((events[0].before.clone(), events[0].after.clone()), (None, Some(serde_json::json!({"id": 7, "qty": 3}))));
+        assert_eq!((events[1].before.clone(), events[1].after.clone()), (Some(serde_json::json!({"id": 7})), None));
+        assert_eq!(events[1].data, events[1].before);
+        assert!(events[2].before.is_none() && events[2].data.is_none());
+        assert!(events.iter().all(|e| e.commit_lsn.as_deref() == Some("0/30")));
+    }
+
     #[test]
     fn test_build_advance_sql_normal_lsn() {
         let sql = build_advance_sql("22/1D4AE960");
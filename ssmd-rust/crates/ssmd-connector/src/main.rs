@@ -6,7 +6,7 @@
 use clap::Parser;
 use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::watch;
 use tracing::{error, info, warn};
@@ -16,9 +16,11 @@ use ssmd_connector_lib::{
     binance::{BinanceConnector, BinanceNatsWriter},
     kalshi::{KalshiConfig, KalshiConnector, KalshiCredentials},
     massive::{MassiveConnector, MassiveNatsWriter},
-    EnvResolver, KeyResolver, NatsWriter, Runner, ServerState, WebSocketConnector,
+    CoalescingTransport, EnvResolver, FileWriter, Keepalive, KeyResolver, NatsWriter,
+    ReconnectPolicy, RestConnector, Runner, ServerState, TeeWriter, WalWriter,
+    WebSocketConnector,
 };
-use ssmd_metadata::{Environment, Feed, FeedType, KeyType, TransportType};
+use ssmd_metadata::{Environment, Feed, FeedType, FeedVersion, KeyType, TransportType};
 use ssmd_middleware::MiddlewareFactory;
 
 #[derive(Parser, Debug)]
@@ -36,6 +38,11 @@ struct Args {
     /// Health server bind address
     #[arg(long, default_value = "0.0.0.0:8080")]
     health_addr: String,
+
+    /// Also write every message as date-partitioned JSONL under this directory
+    /// (in addition to NATS), for verifying a new feed
+    #[arg(long)]
+    tee_dir: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -72,29 +79,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Parse health server address
     let health_addr: SocketAddr = args.health_addr.parse()?;
+    let tee_dir = args.tee_dir.as_deref();
 
     // Create and run connector based on feed name
     match feed.name.as_str() {
         "kalshi" => {
-            run_kalshi_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_kalshi_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         "kraken" | "kraken-spot" => {
-            run_kraken_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_kraken_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         "kraken-futures" => {
-            run_kraken_futures_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_kraken_futures_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         "polymarket" => {
-            run_polymarket_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_polymarket_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         "massive" => {
-            run_massive_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_massive_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         "binance" => {
-            run_binance_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_binance_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
-            run_generic_connector(&feed, &env_config, health_addr, shutdown_rx).await
+            run_generic_connector(&feed, &env_config, health_addr, tee_dir, shutdown_rx).await
         }
     }
 }
@@ -104,6 +112,7 @@ async fn run_kalshi_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Resolve credentials from env config keys (preferred) or fall back to hardcoded env vars
@@ -255,6 +264,9 @@ async fn run_kalshi_connector(
         info!(use_demo = use_demo, "Creating Kalshi connector (global mode)");
         KalshiConnector::new(credentials, use_demo, ws_url)
     };
+    let connector = connector.with_keepalive(keepalive_from_env(
+        ssmd_connector_lib::kalshi::connector::PING_INTERVAL_SECS,
+    ));
 
     // NATS transport required
     match env_config.transport.transport_type {
@@ -262,7 +274,7 @@ async fn run_kalshi_connector(
             info!(transport = "nats", "Using NATS writer (raw JSON)");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
             let writer = create_nats_writer(transport, env_config, feed, series_filter);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         TransportType::Memory => {
             error!("Memory transport not supported - use NATS transport");
@@ -281,6 +293,7 @@ async fn run_kraken_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Parse symbols from environment or use defaults
@@ -300,18 +313,23 @@ async fn run_kraken_connector(
         .map(|v| v.endpoint.clone())
         .filter(|url| !url.contains("MISSING_FEED_CONFIGMAP"));
 
-    info!(symbols = ?symbols, ?ws_url, "Creating Kraken connector");
+    let channels = feed.get_latest_version().and_then(|v| v.channels.clone());
+
+    info!(symbols = ?symbols, ?ws_url, ?channels, "Creating Kraken connector");
 
     let connector = ssmd_connector_lib::kraken::KrakenConnector::with_feed_name(
         symbols, ws_url, feed.name.clone(),
-    );
+    )
+    .with_channels(channels)?
+    .with_keepalive(keepalive_from_env(ssmd_connector_lib::kraken::connector::PING_INTERVAL_SECS));
 
     match env_config.transport.transport_type {
         TransportType::Nats => {
             info!(transport = "nats", "Using Kraken NATS writer");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
+            let transport = maybe_coalesce(transport, feed);
             let writer = create_kraken_nats_writer(transport, env_config, feed);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
             error!("Only NATS transport is supported for Kraken connector");
@@ -330,6 +348,7 @@ async fn run_binance_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Parse symbols from environment or use defaults
@@ -366,7 +385,7 @@ async fn run_binance_connector(
             info!(transport = "nats", "Using Binance NATS writer");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
             let writer = create_binance_nats_writer(transport, env_config, feed);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
             error!("Only NATS transport is supported for Binance connector");
@@ -405,6 +424,7 @@ async fn run_kraken_futures_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Parse product IDs from environment or use defaults
@@ -426,16 +446,22 @@ async fn run_kraken_futures_connector(
         .map(|v| v.endpoint.clone())
         .filter(|url| !url.contains("MISSING_FEED_CONFIGMAP"));
 
-    info!(product_ids = ?product_ids, "Creating Kraken Futures connector");
+    let channels = feed.get_latest_version().and_then(|v| v.channels.clone());
 
-    let connector = ssmd_connector_lib::kraken_futures::KrakenFuturesConnector::new(product_ids, ws_url);
+    info!(product_ids = ?product_ids, ?channels, "Creating Kraken Futures connector");
+
+    let connector = ssmd_connector_lib::kraken_futures::KrakenFuturesConnector::new(product_ids, ws_url)
+        .with_channels(channels)?
+        .with_keepalive(keepalive_from_env(
+            ssmd_connector_lib::kraken_futures::websocket::PING_INTERVAL_SECS,
+        ));
 
     match env_config.transport.transport_type {
         TransportType::Nats => {
             info!(transport = "nats", "Using Kraken Futures NATS writer");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
             let writer = create_kraken_futures_nats_writer(transport, env_config, feed);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
             error!("Only NATS transport is supported for Kraken Futures connector");
@@ -478,6 +504,7 @@ async fn run_polymarket_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Check for static token IDs from environment (for testing/manual override)
@@ -564,13 +591,31 @@ async fn run_polymarket_connector(
 
         ssmd_connector_lib::polymarket::PolymarketConnector::with_discovery(discovery, ws_url)
     };
+    let connector = connector
+        .with_channels(feed.get_latest_version().and_then(|v| v.channels.clone()))?
+        .with_keepalive(keepalive_from_env(
+            ssmd_connector_lib::polymarket::connector::PING_INTERVAL_SECS,
+        ));
+
+    // Discovery refresh interval (0 disables; default: discovery poll interval)
+    let connector = match std::env::var("POLYMARKET_REFRESH_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        Some(secs) => {
+            info!(refresh_secs = secs, "Polymarket discovery refresh interval override");
+            connector.with_refresh_interval(std::time::Duration::from_secs(secs))
+        }
+        None => connector,
+    };
 
     match env_config.transport.transport_type {
         TransportType::Nats => {
             info!(transport = "nats", "Using Polymarket NATS writer");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
+            let transport = maybe_coalesce(transport, feed);
             let writer = create_polymarket_nats_writer(transport, env_config, feed);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
             error!("Only NATS transport is supported for Polymarket connector");
@@ -683,8 +728,120 @@ fn create_nats_writer(
 /// Staleness threshold in seconds - if no messages for this long, health check fails
 const STALE_THRESHOLD_SECS: u64 = 300; // 5 minutes
 
-/// Run connector with a specific writer implementation
+/// Wrap `transport` in a per-subject coalescing layer when `PUBLISH_COALESCE_MS`
+/// is set to a non-zero window. Unset or 0 leaves publishing unchanged.
+fn maybe_coalesce(
+    transport: Arc<dyn ssmd_middleware::Transport>,
+    feed: &Feed,
+) -> Arc<dyn ssmd_middleware::Transport> {
+    let window_ms: u64 = std::env::var("PUBLISH_COALESCE_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    if window_ms == 0 {
+        return transport;
+    }
+    info!(window_ms, "Coalescing NATS publishes per subject");
+    Arc::new(CoalescingTransport::new(
+        transport,
+        feed.name.as_str(),
+        std::time::Duration::from_millis(window_ms),
+    ))
+}
+
+/// Reconnect backoff for reconnectable connectors, overridable via
+/// `RECONNECT_BASE_DELAY_MS`, `RECONNECT_MAX_DELAY_MS` and `RECONNECT_MAX_RETRIES`.
+fn reconnect_policy_from_env() -> ReconnectPolicy {
+    fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+        std::env::var(name).ok().and_then(|v| v.parse().ok())
+    }
+
+    let default = ReconnectPolicy::default();
+    ReconnectPolicy {
+        base_delay: env_parse("RECONNECT_BASE_DELAY_MS")
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(default.base_delay),
+        max_delay: env_parse("RECONNECT_MAX_DELAY_MS")
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(default.max_delay),
+        max_retries: env_parse("RECONNECT_MAX_RETRIES").unwrap_or(default.max_retries),
+    }
+}
+
+/// WebSocket keepalive with the connector's default ping interval,
+/// overridable via `WS_PING_INTERVAL_SECS` and `WS_PONG_TIMEOUT_SECS`.
+fn keepalive_from_env(default_ping_interval_secs: u64) -> Keepalive {
+    fn env_secs(name: &str) -> Option<std::time::Duration> {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    let ping_interval = env_secs("WS_PING_INTERVAL_SECS")
+        .unwrap_or(std::time::Duration::from_secs(default_ping_interval_secs));
+    let keepalive = Keepalive::every(ping_interval);
+    match env_secs("WS_PONG_TIMEOUT_SECS") {
+        Some(timeout) => keepalive.with_pong_timeout(timeout),
+        None => keepalive,
+    }
+}
+
+/// Run connector with a specific writer implementation.
+///
+/// When `CONNECTOR_WAL_DIR` is set, the writer is wrapped in a local
+/// write-ahead log and frames left over from a previous run are replayed
+/// before the connector starts.
 async fn run_with_writer<C, W>(
+    feed: &Feed,
+    connector: C,
+    writer: W,
+    health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
+    shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    C: ssmd_connector_lib::traits::Connector,
+    W: ssmd_connector_lib::traits::Writer,
+{
+    match std::env::var("CONNECTOR_WAL_DIR") {
+        Ok(dir) if !dir.is_empty() => {
+            let mut wal = WalWriter::open(&dir, &feed.name, writer)?;
+            let replayed = wal.replay().await?;
+            info!(dir = %dir, replayed, "Write-ahead log enabled");
+            run_with_tee(feed, connector, wal, health_addr, tee_dir, shutdown_rx).await
+        }
+        _ => run_with_tee(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await,
+    }
+}
+
+/// With `--tee-dir`, also write every message to local JSONL files. The file
+/// sink sits outside the WAL so a replayed frame is not written twice.
+async fn run_with_tee<C, W>(
+    feed: &Feed,
+    connector: C,
+    writer: W,
+    health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
+    shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    C: ssmd_connector_lib::traits::Connector,
+    W: ssmd_connector_lib::traits::Writer,
+{
+    match tee_dir {
+        Some(dir) => {
+            info!(dir = %dir.display(), "Tee to local JSONL enabled");
+            let tee = TeeWriter::new(writer, FileWriter::new(dir, feed.name.as_str()))
+                .with_names("nats", "file");
+            run_runner(feed, connector, tee, health_addr, shutdown_rx).await
+        }
+        None => run_runner(feed, connector, writer, health_addr, shutdown_rx).await,
+    }
+}
+
+async fn run_runner<C, W>(
     feed: &Feed,
     connector: C,
     writer: W,
@@ -695,7 +852,14 @@ where
     C: ssmd_connector_lib::traits::Connector,
     W: ssmd_connector_lib::traits::Writer,
 {
-    let mut runner = Runner::new(feed.name.as_str(), connector, writer);
+    // MAX_IDLE_SECS: treat the feed as dead after this long without a message (0 = off)
+    let max_idle_secs: u64 = std::env::var("MAX_IDLE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let mut runner = Runner::new(feed.name.as_str(), connector, writer)
+        .with_reconnect_policy(reconnect_policy_from_env())
+        .with_max_idle_secs(max_idle_secs);
     let connected_handle = runner.connected_handle();
     // Use activity handle (tracks WebSocket ping/pong + data messages) for health checks
     // This prevents false staleness during quiet market periods when pings are succeeding
@@ -707,7 +871,10 @@ where
         Arc::clone(&connected_handle),
         Arc::clone(&activity_handle),
         STALE_THRESHOLD_SECS,
-    );
+    )
+    .with_reconnects(runner.reconnects_handle())
+    .with_max_idle_secs(max_idle_secs)
+    .with_last_seen(runner.last_seen_handle());
     let health_handle = tokio::spawn(async move {
         if let Err(e) = ssmd_connector_lib::run_server(health_addr, server_state).await {
             error!(error = %e, "Health server error");
@@ -782,6 +949,7 @@ async fn run_massive_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let api_key = std::env::var("MASSIVE_API_KEY")
@@ -813,7 +981,7 @@ async fn run_massive_connector(
             let transport =
                 MiddlewareFactory::create_nats_transport_validated(env_config).await?;
             let writer = create_massive_nats_writer(transport, env_config, feed);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
         }
         _ => {
             error!("Only NATS transport is supported for Massive connector");
@@ -837,28 +1005,63 @@ mod massive_tests {
     }
 }
 
-/// Run generic WebSocket connector
+/// Build a REST polling connector from the feed version.
+///
+/// A resolved API key is sent in the header named by `parser_config.auth_header`,
+/// or as `Authorization: Bearer <key>` if unset. Set `parser_config.emit_unchanged`
+/// to `"true"` to publish every poll instead of only changed snapshots.
+fn build_rest_connector(
+    version: &FeedVersion,
+    creds: Option<HashMap<String, String>>,
+) -> Result<RestConnector, Box<dyn std::error::Error>> {
+    let poll_interval = version
+        .poll_interval_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(ssmd_connector_lib::rest::DEFAULT_POLL_INTERVAL);
+    let parser_config = version.parser_config.clone().unwrap_or_default();
+
+    let mut connector = RestConnector::new(&version.endpoint, poll_interval).with_emit_unchanged(
+        parser_config.get("emit_unchanged").map(|v| v == "true").unwrap_or(false),
+    );
+
+    if let Some(creds) = creds {
+        if creds.len() != 1 {
+            return Err(format!(
+                "REST feeds support a single API key credential, got {}",
+                creds.len()
+            )
+            .into());
+        }
+        let key = creds.into_values().next().unwrap_or_default();
+        connector = match parser_config.get("auth_header") {
+            Some(header) => connector.with_header(header.as_str(), key),
+            None => connector.with_header("Authorization", format!("Bearer {}", key)),
+        };
+    }
+
+    info!(
+        endpoint = %version.endpoint,
+        poll_interval_secs = poll_interval.as_secs(),
+        "Using REST polling connector"
+    );
+    Ok(connector)
+}
+
+/// Run generic WebSocket or REST polling connector
 async fn run_generic_connector(
     feed: &Feed,
     env_config: &Environment,
     health_addr: SocketAddr,
+    tee_dir: Option<&Path>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Get latest version
     let version = feed.get_latest_version().ok_or("No feed versions defined")?;
 
-    // Determine connection URL based on feed type
-    let url = match feed.feed_type {
-        FeedType::Websocket => version.endpoint.clone(),
-        FeedType::Rest => {
-            error!("REST feeds not yet supported");
-            return Err("REST feeds not yet supported".into());
-        }
-        FeedType::Multicast => {
-            error!("Multicast feeds not yet supported");
-            return Err("Multicast feeds not yet supported".into());
-        }
-    };
+    if feed.feed_type == FeedType::Multicast {
+        error!("Multicast feeds not yet supported");
+        return Err("Multicast feeds not yet supported".into());
+    }
 
     // Resolve credentials from environment config
     let creds: Option<HashMap<String, String>> = if let Some(ref keys) = env_config.keys {
@@ -889,9 +1092,18 @@ async fn run_generic_connector(
         TransportType::Nats => {
             info!(transport = "nats", "Using NATS writer (raw JSON)");
             let transport = MiddlewareFactory::create_nats_transport_validated(env_config).await?;
-            let connector = WebSocketConnector::new(&url, creds);
             let writer = create_nats_writer(transport, env_config, feed, None);
-            run_with_writer(feed, connector, writer, health_addr, shutdown_rx).await
+            if feed.feed_type == FeedType::Rest {
+                let connector = build_rest_connector(version, creds)?;
+                run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
+            } else {
+                let connector = WebSocketConnector::new(&version.endpoint, creds)
+                    .with_feed_name(feed.name.clone())
+                    .with_keepalive(Some(keepalive_from_env(
+                        ssmd_connector_lib::websocket::DEFAULT_PING_INTERVAL_SECS,
+                    )));
+                run_with_writer(feed, connector, writer, health_addr, tee_dir, shutdown_rx).await
+            }
         }
         TransportType::Memory => {
             error!("Memory transport not supported - use NATS transport");
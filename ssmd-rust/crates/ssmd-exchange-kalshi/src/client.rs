@@ -8,7 +8,7 @@ use std::time::Duration;
 use tracing::{debug, error, warn};
 use uuid::Uuid;
 
-use harman::error::ExchangeError;
+use harman::error::{ExchangeError, RejectCategory};
 use harman::exchange::ExchangeAdapter;
 use harman::types::{
     Action, AmendRequest, AmendResult, Balance, ExchangeFill, ExchangeOrder,
@@ -22,6 +22,8 @@ use crate::types::*;
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 const MIN_REQUEST_GAP: Duration = Duration::from_millis(200);
 const DEFAULT_PATH_PREFIX: &str = "/trade-api/v2";
+/// Kalshi caps batched create/cancel requests at 20 orders
+const MAX_BATCH_ORDERS: usize = 20;
 
 /// Kalshi REST trading client
 pub struct KalshiRestClient {
@@ -102,7 +104,7 @@ impl KalshiRestClient {
                 }
             })?;
 
-        self.check_rate_limit(&resp)?;
+        self.check_status(&resp)?;
         Ok(resp)
     }
 
@@ -142,7 +144,7 @@ impl KalshiRestClient {
                 }
             })?;
 
-        self.check_rate_limit(&resp)?;
+        self.check_status(&resp)?;
         Ok(resp)
     }
 
@@ -192,12 +194,18 @@ impl KalshiRestClient {
                 }
             })?;
 
-        self.check_rate_limit(&resp)?;
+        self.check_status(&resp)?;
         Ok(resp)
     }
 
-    /// Check if response is rate limited
-    fn check_rate_limit(&self, resp: &reqwest::Response) -> Result<(), ExchangeError> {
+    /// Map rate limiting and 5xx responses to their own errors, so callers
+    /// don't treat them as rejections
+    fn check_status(&self, resp: &reqwest::Response) -> Result<(), ExchangeError> {
+        if resp.status().is_server_error() {
+            return Err(ExchangeError::ServerError {
+                status: resp.status().as_u16(),
+            });
+        }
         if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
             let retry_after = resp
                 .headers()
@@ -255,6 +263,37 @@ impl KalshiRestClient {
         })
     }
 
+    /// Build the Kalshi create-order body for one of our order requests.
+    fn order_body(order: &OrderRequest) -> KalshiOrderRequest {
+        // Kalshi API always uses the yes price for both Yes and No side orders.
+        // For No-side orders, the exchange interprets it as the complement
+        // (i.e., no_price = 1 - yes_price). We pass price_dollars as a dollar
+        // string so sub-cent prices survive instead of truncating to cents.
+        // Kalshi has no native "market" order type — all orders are limit.
+        // For market orders and triggered SL (OrderType::Market), we submit as limit
+        // with IOC TIF; price_dollars is the worst-case cap, so it crosses at best.
+        let effective_type = match order.order_type {
+            harman::types::OrderType::Market => "limit".to_string(),
+            harman::types::OrderType::Limit => "limit".to_string(),
+        };
+        let effective_tif = match order.order_type {
+            harman::types::OrderType::Market => "immediate_or_cancel".to_string(),
+            harman::types::OrderType::Limit => order.time_in_force.to_kalshi_str().to_string(),
+        };
+
+        KalshiOrderRequest {
+            ticker: order.ticker.clone(),
+            client_order_id: order.client_order_id.to_string(),
+            side: order.side.to_string(),
+            action: order.action.to_string(),
+            order_type: effective_type,
+            count_fp: order.quantity.normalize().to_string(),
+            yes_price_dollars: order.price_dollars.normalize().to_string(),
+            time_in_force: effective_tif,
+            subaccount: 0,
+        }
+    }
+
     /// Map a Kalshi order status string to our ExchangeOrderState
     fn map_order_status(order: &KalshiOrder) -> ExchangeOrderState {
         match order.status.as_str() {
@@ -288,33 +327,7 @@ impl KalshiRestClient {
 #[async_trait]
 impl ExchangeAdapter for KalshiRestClient {
     async fn submit_order(&self, order: &OrderRequest) -> Result<String, ExchangeError> {
-        // Kalshi API always uses yes_price for both Yes and No side orders.
-        // For No-side orders, the exchange interprets yes_price as the complement
-        // (i.e., no_price = 100 - yes_price). We pass our price_dollars converted to cents.
-        // Kalshi has no native "market" order type — all orders are limit.
-        // For triggered SL (OrderType::Market), we submit as limit with IOC TIF.
-        let effective_type = match order.order_type {
-            harman::types::OrderType::Market => "limit".to_string(),
-            harman::types::OrderType::Limit => "limit".to_string(),
-        };
-        let effective_tif = match order.order_type {
-            harman::types::OrderType::Market => "immediate_or_cancel".to_string(),
-            harman::types::OrderType::Limit => order.time_in_force.to_kalshi_str().to_string(),
-        };
-
-        let body = KalshiOrderRequest {
-            ticker: order.ticker.clone(),
-            client_order_id: order.client_order_id.to_string(),
-            side: order.side.to_string(),
-            action: order.action.to_string(),
-            order_type: effective_type,
-            count_fp: order.quantity.normalize().to_string(),
-            yes_price: (order.price_dollars * Decimal::from(100))
-                .to_i32()
-                .unwrap_or(0),
-            time_in_force: effective_tif,
-            subaccount: 0,
-        };
+        let body = Self::order_body(order);
 
         let path = format!("{}/portfolio/orders", self.path_prefix);
         let resp = self.post(&path, &body).await?;
@@ -329,11 +342,74 @@ impl ExchangeAdapter for KalshiRestClient {
         } else {
             let error_body = resp.text().await.unwrap_or_default();
             Err(ExchangeError::Rejected {
+                category: classify_reject(&error_body),
                 reason: format!("HTTP {}: {}", status, error_body),
             })
         }
     }
 
+    fn max_submit_batch(&self) -> usize {
+        MAX_BATCH_ORDERS
+    }
+
+    async fn submit_batch(
+        &self,
+        orders: &[OrderRequest],
+    ) -> Result<Vec<Result<String, ExchangeError>>, ExchangeError> {
+        if orders.len() > MAX_BATCH_ORDERS {
+            return Err(ExchangeError::BatchRefused(format!(
+                "batch of {} orders exceeds Kalshi limit of {}",
+                orders.len(),
+                MAX_BATCH_ORDERS
+            )));
+        }
+
+        let body = KalshiBatchCreateRequest {
+            orders: orders.iter().map(Self::order_body).collect(),
+        };
+        let path = format!("{}/portfolio/orders/batched", self.path_prefix);
+        let resp = self.post(&path, &body).await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let error_body = resp.text().await.unwrap_or_default();
+            return Err(ExchangeError::BatchRefused(format!(
+                "batch create HTTP {}: {}",
+                status, error_body
+            )));
+        }
+
+        // The batch was accepted; if the body can't be read, the orders are
+        // live but their ids are unknown
+        let batch_resp: KalshiBatchCreateResponse = resp
+            .json()
+            .await
+            .map_err(|e| ExchangeError::Unexpected(e.to_string()))?;
+
+        // Results come back in request order
+        Ok(batch_resp
+            .orders
+            .into_iter()
+            .map(|r| match (r.order, r.error) {
+                (Some(order), None) => Ok(order.order_id),
+                (_, Some(err)) => {
+                    let detail = format!(
+                        "{} {}",
+                        err.code.unwrap_or_default(),
+                        err.message.unwrap_or_default()
+                    );
+                    Err(ExchangeError::Rejected {
+                        category: classify_reject(&detail),
+                        reason: format!("batch create: {}", detail.trim()),
+                    })
+                }
+                (None, None) => Err(ExchangeError::Unexpected(
+                    "batch create result has neither order nor error".into(),
+                )),
+            })
+            .collect())
+    }
+
     async fn cancel_order(&self, exchange_order_id: &str) -> Result<(), ExchangeError> {
         let path = format!("{}/portfolio/orders/{}", self.path_prefix, exchange_order_id);
         let resp = self.delete(&path).await?;
@@ -345,9 +421,7 @@ impl ExchangeAdapter for KalshiRestClient {
             Err(ExchangeError::OrderNotFoundByExchangeId(exchange_order_id.to_string()))
         } else {
             let error_body = resp.text().await.unwrap_or_default();
-            Err(ExchangeError::Rejected {
-                reason: format!("cancel failed HTTP {}: {}", status, error_body),
-            })
+            Err(ExchangeError::rejected(format!("cancel failed HTTP {}: {}", status, error_body)))
         }
     }
 
@@ -359,9 +433,10 @@ impl ExchangeAdapter for KalshiRestClient {
         let status = resp.status();
         if !status.is_success() {
             let error_body = resp.text().await.unwrap_or_default();
-            return Err(ExchangeError::Rejected {
-                reason: format!("list orders failed HTTP {}: {}", status, error_body),
-            });
+            return Err(ExchangeError::rejected(format!(
+                "list orders failed HTTP {}: {}",
+                status, error_body
+            )));
         }
 
         let orders_resp: KalshiOrdersResponse = resp
@@ -395,9 +470,7 @@ impl ExchangeAdapter for KalshiRestClient {
             Ok(cancel_resp.orders.len() as i32)
         } else {
             let error_body = resp.text().await.unwrap_or_default();
-            Err(ExchangeError::Rejected {
-                reason: format!("mass cancel failed HTTP {}: {}", status, error_body),
-            })
+            Err(ExchangeError::rejected(format!("mass cancel failed HTTP {}: {}", status, error_body)))
         }
     }
 
@@ -775,19 +848,19 @@ impl ExchangeAdapter for KalshiRestClient {
     }
 
     async fn amend_order(&self, request: &AmendRequest) -> Result<AmendResult, ExchangeError> {
-        // Kalshi requires both yes_price and count_fp in every amend request.
-        let price = request.new_price_dollars.ok_or(ExchangeError::Rejected {
-            reason: "Kalshi amend requires new_price_dollars".to_string(),
-        })?;
-        let quantity = request.new_quantity.ok_or(ExchangeError::Rejected {
-            reason: "Kalshi amend requires new_quantity".to_string(),
+        // Kalshi requires both the yes price and count_fp in every amend request.
+        let price = request.new_price_dollars.ok_or_else(|| {
+            ExchangeError::rejected("Kalshi amend requires new_price_dollars")
         })?;
+        let quantity = request
+            .new_quantity
+            .ok_or_else(|| ExchangeError::rejected("Kalshi amend requires new_quantity"))?;
 
         let body = KalshiAmendRequest {
             ticker: request.ticker.clone(),
             side: request.side.to_string(),
             action: request.action.to_string(),
-            yes_price: (price * Decimal::from(100)).to_i32().unwrap_or(0),
+            yes_price_dollars: price.normalize().to_string(),
             count_fp: quantity.normalize().to_string(),
             subaccount: 0,
         };
@@ -832,6 +905,7 @@ impl ExchangeAdapter for KalshiRestClient {
         } else {
             let error_body = resp.text().await.unwrap_or_default();
             Err(ExchangeError::Rejected {
+                category: classify_reject(&error_body),
                 reason: format!("amend failed HTTP {}: {}", status, error_body),
             })
         }
@@ -861,6 +935,7 @@ impl ExchangeAdapter for KalshiRestClient {
         } else {
             let error_body = resp.text().await.unwrap_or_default();
             Err(ExchangeError::Rejected {
+                category: classify_reject(&error_body),
                 reason: format!("decrease failed HTTP {}: {}", status, error_body),
             })
         }
@@ -997,6 +1072,46 @@ impl ExchangeAdapter for KalshiRestClient {
     }
 }
 
+/// Map a Kalshi error body to a `RejectCategory`.
+///
+/// Kalshi returns `{"code": ..., "message": ...}`, sometimes nested under
+/// `"error"`. Matching is on the code and message text so new codes with
+/// familiar wording still land in the right bucket.
+fn classify_reject(error_body: &str) -> RejectCategory {
+    let parsed: Option<serde_json::Value> = serde_json::from_str(error_body).ok();
+    let detail = parsed
+        .as_ref()
+        .map(|v| v.get("error").unwrap_or(v))
+        .map(|v| {
+            format!(
+                "{} {}",
+                v.get("code").and_then(|c| c.as_str()).unwrap_or_default(),
+                v.get("message").and_then(|m| m.as_str()).unwrap_or_default()
+            )
+        })
+        .unwrap_or_else(|| error_body.to_string())
+        .to_lowercase();
+
+    if detail.contains("insufficient") {
+        RejectCategory::InsufficientBalance
+    } else if detail.contains("market_closed")
+        || detail.contains("market closed")
+        || detail.contains("not open")
+        || detail.contains("trading_halted")
+        || detail.contains("paused")
+    {
+        RejectCategory::MarketClosed
+    } else if detail.contains("price")
+        && (detail.contains("band") || detail.contains("range") || detail.contains("bound"))
+    {
+        RejectCategory::PriceOutOfBand
+    } else if detail.contains("invalid") || detail.contains("not found") {
+        RejectCategory::InvalidOrder
+    } else {
+        RejectCategory::Other
+    }
+}
+
 impl std::fmt::Debug for KalshiRestClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KalshiRestClient")
@@ -1008,7 +1123,7 @@ impl std::fmt::Debug for KalshiRestClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wiremock::matchers::{header, method, path};
+    use wiremock::matchers::{body_partial_json, header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn setup() -> (MockServer, KalshiRestClient) {
@@ -1039,6 +1154,8 @@ mod tests {
             time_in_force: harman::types::TimeInForce::Gtc,
             order_type: harman::types::OrderType::default(),
             trigger_price: None,
+            good_till: None,
+            reduce_only: false,
         }
     }
 
@@ -1070,6 +1187,39 @@ mod tests {
         assert_eq!(result.unwrap(), "exch-order-123");
     }
 
+    #[tokio::test]
+    async fn test_submit_order_sub_cent_price() {
+        let (server, client) = setup().await;
+
+        // 0.505 goes out as dollars, not truncated to 50 cents
+        Mock::given(method("POST"))
+            .and(path("/trade-api/v2/portfolio/orders"))
+            .and(body_partial_json(serde_json::json!({
+                "yes_price_dollars": "0.505"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "order": {
+                    "order_id": "exch-order-505",
+                    "client_order_id": "550e8400-e29b-41d4-a716-446655440000",
+                    "ticker": "KXBTCD-26FEB-T100000",
+                    "status": "resting",
+                    "side": "yes",
+                    "action": "buy",
+                    "yes_price_dollars": "0.5050",
+                    "count_fp": "10.00",
+                    "remaining_count_fp": "10.00"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut order = test_order_request();
+        order.price_dollars = Decimal::new(505, 3);
+        let result = client.submit_order(&order).await;
+        assert_eq!(result.unwrap(), "exch-order-505");
+    }
+
     #[tokio::test]
     async fn test_submit_order_rejected() {
         let (server, client) = setup().await;
@@ -1088,13 +1238,31 @@ mod tests {
         let result = client.submit_order(&test_order_request()).await;
         assert!(result.is_err());
         match result.unwrap_err() {
-            ExchangeError::Rejected { reason } => {
+            ExchangeError::Rejected { category, reason } => {
                 assert!(reason.contains("400"));
+                assert_eq!(category, RejectCategory::InvalidOrder);
             }
             e => panic!("expected Rejected, got: {:?}", e),
         }
     }
 
+    #[test]
+    fn test_classify_reject() {
+        assert_eq!(
+            classify_reject(r#"{"code":"insufficient_balance","message":"Insufficient balance"}"#),
+            RejectCategory::InsufficientBalance
+        );
+        assert_eq!(
+            classify_reject(r#"{"error":{"code":"market_closed","message":"market is closed"}}"#),
+            RejectCategory::MarketClosed
+        );
+        assert_eq!(
+            classify_reject(r#"{"code":"invalid_parameters","message":"price out of range"}"#),
+            RejectCategory::PriceOutOfBand
+        );
+        assert_eq!(classify_reject("upstream connect error"), RejectCategory::Other);
+    }
+
     #[tokio::test]
     async fn test_submit_order_rate_limited() {
         let (server, client) = setup().await;
@@ -1117,6 +1285,97 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_submit_order_server_error_is_transient() {
+        let (server, client) = setup().await;
+
+        Mock::given(method("POST"))
+            .and(path("/trade-api/v2/portfolio/orders"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let err = client.submit_order(&test_order_request()).await.unwrap_err();
+        assert!(matches!(err, ExchangeError::ServerError { status: 503 }));
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn test_submit_batch_undecodable_success_body_is_ambiguous() {
+        let (server, client) = setup().await;
+
+        Mock::given(method("POST"))
+            .and(path("/trade-api/v2/portfolio/orders/batched"))
+            .respond_with(ResponseTemplate::new(201).set_body_string("<html>gateway</html>"))
+            .mount(&server)
+            .await;
+
+        // The orders may be live: this must not look like a refused batch
+        let err = client.submit_batch(&[test_order_request()]).await.unwrap_err();
+        assert!(matches!(err, ExchangeError::Unexpected(_)), "got: {:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_submit_batch_http_error_is_refused() {
+        let (server, client) = setup().await;
+
+        Mock::given(method("POST"))
+            .and(path("/trade-api/v2/portfolio/orders/batched"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("bad request"))
+            .mount(&server)
+            .await;
+
+        let err = client.submit_batch(&[test_order_request()]).await.unwrap_err();
+        assert!(matches!(err, ExchangeError::BatchRefused(_)), "got: {:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_submit_batch_partial_failure() {
+        let (server, client) = setup().await;
+
+        Mock::given(method("POST"))
+            .and(path("/trade-api/v2/portfolio/orders/batched"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "orders": [
+                    {
+                        "order": {
+                            "order_id": "exch-batch-1",
+                            "ticker": "KXBTCD-26FEB-T100000",
+                            "status": "resting",
+                            "side": "yes",
+                            "action": "buy",
+                            "count_fp": "10.00",
+                            "remaining_count_fp": "10.00"
+                        },
+                        "error": null
+                    },
+                    {
+                        "order": null,
+                        "error": {"code": "insufficient_balance", "message": "Insufficient balance"}
+                    }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        assert_eq!(client.max_submit_batch(), 20);
+        let mut second = test_order_request();
+        second.client_order_id = Uuid::new_v4();
+        let results = client
+            .submit_batch(&[test_order_request(), second])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "exch-batch-1");
+        match &results[1] {
+            Err(ExchangeError::Rejected { category, .. }) => {
+                assert_eq!(*category, RejectCategory::InsufficientBalance);
+            }
+            other => panic!("expected Rejected, got: {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_cancel_order_success() {
         let (server, client) = setup().await;
@@ -1381,7 +1640,7 @@ mod tests {
 
         let result = client.amend_order(&request).await;
         match result.unwrap_err() {
-            ExchangeError::Rejected { reason } => {
+            ExchangeError::Rejected { reason, .. } => {
                 assert!(reason.contains("400"));
             }
             e => panic!("expected Rejected, got: {:?}", e),
@@ -10,11 +10,33 @@ pub struct KalshiOrderRequest {
     #[serde(rename = "type")]
     pub order_type: String,
     pub count_fp: String,
-    pub yes_price: i32,
+    pub yes_price_dollars: String,
     pub time_in_force: String,
     pub subaccount: i32,
 }
 
+/// Request body for POST /portfolio/orders/batched
+#[derive(Debug, Serialize)]
+pub struct KalshiBatchCreateRequest {
+    pub orders: Vec<KalshiOrderRequest>,
+}
+
+/// Individual result from batch create; exactly one of `order`/`error` is set
+#[derive(Debug, Deserialize)]
+pub struct KalshiBatchCreateResult {
+    #[serde(default)]
+    pub order: Option<KalshiOrder>,
+    #[serde(default)]
+    pub error: Option<KalshiError>,
+}
+
+/// Response from POST /portfolio/orders/batched
+#[derive(Debug, Deserialize)]
+pub struct KalshiBatchCreateResponse {
+    #[serde(default)]
+    pub orders: Vec<KalshiBatchCreateResult>,
+}
+
 /// Response from placing an order
 #[derive(Debug, Deserialize)]
 pub struct KalshiOrderResponse {
@@ -191,7 +213,7 @@ pub struct KalshiAmendRequest {
     pub ticker: String,
     pub side: String,
     pub action: String,
-    pub yes_price: i32,
+    pub yes_price_dollars: String,
     pub count_fp: String,
     pub subaccount: i32,
 }
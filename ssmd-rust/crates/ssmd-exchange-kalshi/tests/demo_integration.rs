@@ -185,6 +185,8 @@ async fn test_demo_submit_and_cancel() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -234,6 +236,8 @@ async fn test_demo_mass_cancel() {
             time_in_force: TimeInForce::Gtc,
             order_type: harman::types::OrderType::default(),
             trigger_price: None,
+            good_till: None,
+            reduce_only: false,
         };
         let eid = client
             .submit_order(&order)
@@ -289,6 +293,8 @@ async fn test_demo_get_order_by_client_id() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -349,6 +355,8 @@ async fn test_demo_double_cancel() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -372,7 +380,7 @@ async fn test_demo_double_cancel() {
     );
     match result.unwrap_err() {
         e if e.is_not_found() => println!("Got expected NotFound"),
-        ExchangeError::Rejected { reason } => println!("Got Rejected (acceptable): {}", reason),
+        ExchangeError::Rejected { reason, .. } => println!("Got Rejected (acceptable): {}", reason),
         e => panic!("unexpected error type: {:?}", e),
     }
 }
@@ -400,7 +408,7 @@ async fn test_demo_cancel_nonexistent_order() {
     );
     match result.unwrap_err() {
         e if e.is_not_found() => println!("Got expected NotFound"),
-        ExchangeError::Rejected { reason } => println!("Got Rejected (acceptable): {}", reason),
+        ExchangeError::Rejected { reason, .. } => println!("Got Rejected (acceptable): {}", reason),
         e => panic!("unexpected error type: {:?}", e),
     }
 }
@@ -427,13 +435,15 @@ async fn test_demo_submit_invalid_ticker() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let result = client.submit_order(&order).await;
     println!("Invalid ticker result: {:?}", result);
     assert!(result.is_err(), "expected invalid ticker to be rejected");
     match result.unwrap_err() {
-        ExchangeError::Rejected { reason } => {
+        ExchangeError::Rejected { reason, .. } => {
             println!("Got expected Rejected: {}", reason);
         }
         e => panic!("expected Rejected, got: {:?}", e),
@@ -470,6 +480,8 @@ async fn test_demo_submit_zero_quantity() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let result = client.submit_order(&order).await;
@@ -507,6 +519,8 @@ async fn test_demo_submit_invalid_price() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let result = client.submit_order(&order).await;
@@ -546,6 +560,8 @@ async fn test_demo_submit_duplicate_client_order_id() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -565,6 +581,8 @@ async fn test_demo_submit_duplicate_client_order_id() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let result = client.submit_order(&order2).await;
@@ -614,6 +632,8 @@ async fn test_demo_amend_price() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -675,6 +695,8 @@ async fn test_demo_amend_quantity() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -734,6 +756,8 @@ async fn test_demo_amend_price_and_quantity() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -794,6 +818,8 @@ async fn test_demo_decrease_order() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -857,6 +883,8 @@ async fn test_demo_amend_cancelled_order() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -882,7 +910,7 @@ async fn test_demo_amend_cancelled_order() {
     println!("Amend cancelled order result: {:?}", result);
     assert!(result.is_err(), "expected amend of cancelled order to fail");
     match result.unwrap_err() {
-        ExchangeError::Rejected { reason } => println!("Got expected Rejected: {}", reason),
+        ExchangeError::Rejected { reason, .. } => println!("Got expected Rejected: {}", reason),
         e if e.is_not_found() => println!("Got NotFound (acceptable)"),
         e => panic!("unexpected error type: {:?}", e),
     }
@@ -914,7 +942,7 @@ async fn test_demo_amend_nonexistent_order() {
     assert!(result.is_err(), "expected amend of non-existent order to fail");
     match result.unwrap_err() {
         e if e.is_not_found() => println!("Got expected NotFound"),
-        ExchangeError::Rejected { reason } => println!("Got Rejected (acceptable): {}", reason),
+        ExchangeError::Rejected { reason, .. } => println!("Got Rejected (acceptable): {}", reason),
         e => panic!("unexpected error type: {:?}", e),
     }
 }
@@ -950,6 +978,8 @@ async fn test_demo_amend_invalid_price() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -1006,6 +1036,8 @@ async fn test_demo_amend_zero_quantity() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -1061,6 +1093,8 @@ async fn test_demo_decrease_more_than_remaining() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -1109,6 +1143,8 @@ async fn test_demo_decrease_cancelled_order() {
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     };
 
     let exchange_id = client
@@ -1152,7 +1188,7 @@ async fn test_demo_decrease_nonexistent_order() {
     );
     match result.unwrap_err() {
         e if e.is_not_found() => println!("Got expected NotFound"),
-        ExchangeError::Rejected { reason } => println!("Got Rejected (acceptable): {}", reason),
+        ExchangeError::Rejected { reason, .. } => println!("Got Rejected (acceptable): {}", reason),
         e => panic!("unexpected error type: {:?}", e),
     }
 }
@@ -0,0 +1,135 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use harman::error::ExchangeError;
+
+use crate::Ems;
+
+/// Limits on a single pump call to the exchange.
+///
+/// Each attempt is cut off after `timeout` so a hung exchange can't block the
+/// session queue; the outcome is then `ExchangeError::Timeout`. Transient
+/// failures (5xx, connection errors) are retried up to `max_retries` times,
+/// waiting `retry_backoff` and doubling after each attempt.
+#[derive(Debug, Clone)]
+pub struct ExchangeCallPolicy {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub retry_backoff: Duration,
+}
+
+impl Default for ExchangeCallPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(15),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+impl ExchangeCallPolicy {
+    /// Wait before retry number `retry` (1-based)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(1u32 << retry.saturating_sub(1).min(16))
+    }
+}
+
+/// Run an exchange call under the EMS call policy, retrying transient
+/// failures and recording duration and timeouts by `operation`.
+pub(crate) async fn call<T, F, Fut>(
+    ems: &Ems,
+    operation: &'static str,
+    f: F,
+) -> Result<T, ExchangeError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ExchangeError>>,
+{
+    call_with_retries(ems, operation, ems.call_policy.max_retries, f).await
+}
+
+/// Like [`call`], but never retries; for calls that are unsafe to repeat
+/// (submits and decreases).
+pub(crate) async fn call_once<T, F, Fut>(
+    ems: &Ems,
+    operation: &'static str,
+    f: F,
+) -> Result<T, ExchangeError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ExchangeError>>,
+{
+    call_with_retries(ems, operation, 0, f).await
+}
+
+async fn call_with_retries<T, F, Fut>(
+    ems: &Ems,
+    operation: &'static str,
+    max_retries: u32,
+    mut f: F,
+) -> Result<T, ExchangeError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ExchangeError>>,
+{
+    let policy = &ems.call_policy;
+    let mut retries = 0;
+    loop {
+        let start = Instant::now();
+        let result = match tokio::time::timeout(policy.timeout, f()).await {
+            Ok(result) => result,
+            Err(_) => Err(ExchangeError::Timeout {
+                timeout_ms: policy.timeout.as_millis() as u64,
+            }),
+        };
+        ems.metrics
+            .exchange_call_duration
+            .with_label_values(&[operation])
+            .observe(start.elapsed().as_secs_f64());
+
+        match result {
+            Err(ExchangeError::Timeout { timeout_ms }) => {
+                ems.metrics
+                    .exchange_timeouts
+                    .with_label_values(&[operation])
+                    .inc();
+                return Err(ExchangeError::Timeout { timeout_ms });
+            }
+            Err(e) if e.is_transient() && retries < max_retries => {
+                retries += 1;
+                let backoff = policy.backoff(retries);
+                warn!(
+                    operation,
+                    error = %e,
+                    retry = retries,
+                    backoff_ms = backoff.as_millis() as u64,
+                    "transient exchange error, retrying"
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            other => return other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles() {
+        let policy = ExchangeCallPolicy {
+            retry_backoff: Duration::from_millis(100),
+            ..ExchangeCallPolicy::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        // Large retry counts saturate instead of overflowing
+        assert!(policy.backoff(100) >= policy.backoff(17));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod exchange_call;
 pub mod pump;
 pub mod queue;
 pub mod risk;
@@ -9,25 +10,42 @@ use std::sync::Arc;
 use deadpool_postgres::Pool;
 
 use harman::audit::AuditSender;
+use harman::db;
+use harman::events::{EventBus, StreamEvent};
 use harman::exchange::ExchangeAdapter;
 use harman::risk::RiskLimits;
+use harman::state::OrderState;
+use harman::types::CancelReason;
 
+use crate::exchange_call::ExchangeCallPolicy;
 use crate::pump::PumpResult;
+pub use crate::shutdown::ShutdownMode;
 
 /// EMS metrics -- execution-layer counters only.
 /// Reconciliation metrics stay in the binary (will move to OMS later).
 pub struct EmsMetrics {
+    pub orders_enqueued: prometheus::IntCounter,
     pub orders_dequeued: prometheus::IntCounter,
     pub orders_submitted: prometheus::IntCounter,
-    pub orders_rejected: prometheus::IntCounter,
+    /// Labeled by `reason` (a `RejectCategory`)
+    pub orders_rejected: prometheus::IntCounterVec,
     pub orders_cancelled: prometheus::IntCounter,
     pub fills_recorded: prometheus::IntCounter,
     pub orders_amended: prometheus::IntCounter,
     pub orders_decreased: prometheus::IntCounter,
+    pub queue_depth: prometheus::IntGaugeVec,
+    pub queue_oldest_age: prometheus::GaugeVec,
+    /// Pump exchange call latency, labeled by `operation`
+    pub exchange_call_duration: prometheus::HistogramVec,
+    /// Pump exchange calls that timed out, labeled by `operation`
+    pub exchange_timeouts: prometheus::IntCounterVec,
 }
 
 impl EmsMetrics {
     pub fn new(registry: &prometheus::Registry) -> Self {
+        let orders_enqueued =
+            prometheus::IntCounter::new("harman_orders_enqueued_total", "Orders enqueued for submission")
+                .unwrap();
         let orders_dequeued =
             prometheus::IntCounter::new("harman_orders_dequeued_total", "Orders dequeued from queue")
                 .unwrap();
@@ -36,9 +54,9 @@ impl EmsMetrics {
             "Orders submitted to exchange",
         )
         .unwrap();
-        let orders_rejected = prometheus::IntCounter::new(
-            "harman_orders_rejected_total",
-            "Orders rejected by exchange",
+        let orders_rejected = prometheus::IntCounterVec::new(
+            prometheus::Opts::new("harman_orders_rejected_total", "Orders rejected by exchange"),
+            &["reason"],
         )
         .unwrap();
         let orders_cancelled =
@@ -56,7 +74,40 @@ impl EmsMetrics {
             "Orders decreased on exchange",
         )
         .unwrap();
+        let queue_depth = prometheus::IntGaugeVec::new(
+            prometheus::Opts::new("harman_order_queue_depth", "Items in the order queue"),
+            &["session_id"],
+        )
+        .unwrap();
+        let queue_oldest_age = prometheus::GaugeVec::new(
+            prometheus::Opts::new(
+                "harman_order_queue_oldest_age_seconds",
+                "Age of the oldest item in the order queue",
+            ),
+            &["session_id"],
+        )
+        .unwrap();
+        let exchange_call_duration = prometheus::HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "harman_exchange_call_duration_seconds",
+                "Duration of exchange calls made by the pump",
+            )
+            .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0]),
+            &["operation"],
+        )
+        .unwrap();
+        let exchange_timeouts = prometheus::IntCounterVec::new(
+            prometheus::Opts::new(
+                "harman_exchange_timeouts_total",
+                "Exchange calls made by the pump that timed out",
+            ),
+            &["operation"],
+        )
+        .unwrap();
 
+        registry
+            .register(Box::new(orders_enqueued.clone()))
+            .unwrap();
         registry
             .register(Box::new(orders_dequeued.clone()))
             .unwrap();
@@ -78,8 +129,19 @@ impl EmsMetrics {
         registry
             .register(Box::new(orders_decreased.clone()))
             .unwrap();
+        registry.register(Box::new(queue_depth.clone())).unwrap();
+        registry
+            .register(Box::new(queue_oldest_age.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(exchange_call_duration.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(exchange_timeouts.clone()))
+            .unwrap();
 
         Self {
+            orders_enqueued,
             orders_dequeued,
             orders_submitted,
             orders_rejected,
@@ -87,6 +149,10 @@ impl EmsMetrics {
             fills_recorded,
             orders_amended,
             orders_decreased,
+            queue_depth,
+            queue_oldest_age,
+            exchange_call_duration,
+            exchange_timeouts,
         }
     }
 }
@@ -94,7 +160,7 @@ impl EmsMetrics {
 /// The Execution Management System.
 ///
 /// Owns: queue processing (pump), order enqueue, execution-level risk checks,
-/// graceful shutdown (mass cancel + drain). Does NOT own reconciliation,
+/// graceful shutdown (drain, cancel resting, or hold). Does NOT own reconciliation,
 /// recovery, positions, or auth -- those stay in the binary (future OMS).
 pub struct Ems {
     pub pool: Pool,
@@ -103,6 +169,10 @@ pub struct Ems {
     pub metrics: EmsMetrics,
     pub audit: AuditSender,
     pub shutting_down: AtomicBool,
+    /// Live order/fill/group updates for streaming clients
+    pub events: EventBus,
+    /// Timeout and retry limits for pump exchange calls
+    pub call_policy: ExchangeCallPolicy,
 }
 
 impl Ems {
@@ -120,9 +190,16 @@ impl Ems {
             metrics,
             audit,
             shutting_down: AtomicBool::new(false),
+            events: EventBus::default(),
+            call_policy: ExchangeCallPolicy::default(),
         }
     }
 
+    pub fn with_call_policy(mut self, policy: ExchangeCallPolicy) -> Self {
+        self.call_policy = policy;
+        self
+    }
+
     pub fn is_shutting_down(&self) -> bool {
         self.shutting_down.load(Ordering::Relaxed)
     }
@@ -131,7 +208,51 @@ impl Ems {
         pump::pump(self, session_id).await
     }
 
-    pub async fn shutdown(&self) {
-        shutdown::shutdown(self).await
+    pub async fn shutdown(&self, mode: ShutdownMode, exchange: &str, environment: &str) {
+        shutdown::shutdown(self, mode, exchange, environment).await
+    }
+
+    /// Refresh the queue depth/age gauges for a session from the database.
+    pub async fn observe_queue_depth(&self, session_id: i64) -> Result<db::QueueDepth, String> {
+        let snapshot = db::queue_depth(&self.pool, session_id).await?;
+        let label = session_id.to_string();
+        self.metrics
+            .queue_depth
+            .with_label_values(&[&label])
+            .set(snapshot.depth);
+        self.metrics
+            .queue_oldest_age
+            .with_label_values(&[&label])
+            .set(snapshot.oldest_age_secs);
+        Ok(snapshot)
+    }
+
+    /// Transition an order's state and publish `order_state_changed` on success.
+    pub async fn update_order_state(
+        &self,
+        order_id: i64,
+        session_id: i64,
+        new_state: OrderState,
+        exchange_order_id: Option<&str>,
+        cancel_reason: Option<&CancelReason>,
+        actor: &str,
+    ) -> Result<(), String> {
+        db::update_order_state(
+            &self.pool,
+            order_id,
+            session_id,
+            new_state,
+            exchange_order_id,
+            cancel_reason,
+            actor,
+        )
+        .await?;
+        self.events.publish(StreamEvent::OrderStateChanged {
+            session_id,
+            order_id,
+            state: new_state,
+            actor: actor.to_string(),
+        });
+        Ok(())
     }
 }
@@ -9,6 +9,7 @@ use harman::error::ExchangeError;
 use harman::state::OrderState;
 use harman::types::{AmendRequest, CancelReason, QueueAction};
 
+use crate::exchange_call;
 use crate::Ems;
 
 #[derive(Debug, Serialize)]
@@ -25,7 +26,8 @@ pub struct PumpResult {
 
 /// Drain all pending queue items, submit/cancel to exchange, return results.
 ///
-/// Processes items until the queue is empty or a rate limit is hit.
+/// Processes items until the queue is empty, a rate limit is hit, or the
+/// exchange is unavailable (a call timed out or kept failing transiently).
 /// Called explicitly via `POST /v1/admin/pump` -- no background polling.
 pub async fn pump(ems: &Ems, session_id: i64) -> PumpResult {
     let mut result = PumpResult {
@@ -58,28 +60,67 @@ pub async fn pump(ems: &Ems, session_id: i64) -> PumpResult {
 
                 match item.action {
                     QueueAction::Submit => {
-                        let outcome = handle_submit(ems, session_id, &item).await;
-                        match outcome {
-                            SubmitOutcome::Submitted => result.submitted += 1,
-                            SubmitOutcome::Rejected => result.rejected += 1,
-                            SubmitOutcome::Timeout => {
-                                result.errors.push(format!(
-                                    "order {} timed out, left for reconciliation",
-                                    item.order_id
-                                ));
-                            }
-                            SubmitOutcome::Requeued(reason) => {
-                                result.requeued += 1;
-                                result.errors.push(reason);
+                        let mut items = vec![item];
+                        let max_batch = ems.exchange.max_submit_batch();
+                        if max_batch > 1 {
+                            match db::dequeue_submit_run(
+                                &ems.pool,
+                                session_id,
+                                (max_batch - 1) as i64,
+                            )
+                            .await
+                            {
+                                Ok(more) => {
+                                    ems.metrics.orders_dequeued.inc_by(more.len() as u64);
+                                    result.processed += more.len() as u64;
+                                    items.extend(more);
+                                }
+                                Err(e) => {
+                                    warn!(error = %e, "batch dequeue failed, submitting one order");
+                                }
                             }
-                            SubmitOutcome::RateLimited => {
-                                result.requeued += 1;
-                                result
-                                    .errors
-                                    .push("rate limited, stopping early".into());
-                                break;
+                        }
+
+                        let outcomes = if items.len() == 1 {
+                            vec![handle_submit(ems, session_id, &items[0]).await]
+                        } else {
+                            handle_submit_batch(ems, session_id, &items).await
+                        };
+
+                        let mut rate_limited = false;
+                        let mut unavailable = false;
+                        for (item, outcome) in items.iter().zip(outcomes) {
+                            match outcome {
+                                SubmitOutcome::Submitted => result.submitted += 1,
+                                SubmitOutcome::Rejected => result.rejected += 1,
+                                SubmitOutcome::Timeout => {
+                                    result.errors.push(format!(
+                                        "order {} timed out, left for reconciliation",
+                                        item.order_id
+                                    ));
+                                }
+                                SubmitOutcome::Requeued(reason) => {
+                                    result.requeued += 1;
+                                    result.errors.push(reason);
+                                }
+                                SubmitOutcome::RateLimited => {
+                                    result.requeued += 1;
+                                    rate_limited = true;
+                                }
+                                SubmitOutcome::Unavailable(reason) => {
+                                    result.errors.push(reason);
+                                    unavailable = true;
+                                }
                             }
                         }
+                        if rate_limited {
+                            result.errors.push("rate limited, stopping early".into());
+                            break;
+                        }
+                        if unavailable {
+                            result.errors.push("exchange unavailable, stopping early".into());
+                            break;
+                        }
                     }
                     QueueAction::Cancel => {
                         let outcome = handle_cancel(ems, session_id, &item).await;
@@ -98,6 +139,14 @@ pub async fn pump(ems: &Ems, session_id: i64) -> PumpResult {
                                     .push("rate limited on cancel, stopping early".into());
                                 break;
                             }
+                            CancelOutcome::Unavailable(reason) => {
+                                result.requeued += 1;
+                                result.errors.push(reason);
+                                result
+                                    .errors
+                                    .push("exchange unavailable on cancel, stopping early".into());
+                                break;
+                            }
                         }
                     }
                     QueueAction::Amend => {
@@ -115,6 +164,14 @@ pub async fn pump(ems: &Ems, session_id: i64) -> PumpResult {
                                     .push("rate limited on amend, stopping early".into());
                                 break;
                             }
+                            AmendOutcome::Unavailable(reason) => {
+                                result.requeued += 1;
+                                result.errors.push(reason);
+                                result
+                                    .errors
+                                    .push("exchange unavailable on amend, stopping early".into());
+                                break;
+                            }
                         }
                     }
                     QueueAction::Decrease => {
@@ -146,6 +203,12 @@ pub async fn pump(ems: &Ems, session_id: i64) -> PumpResult {
         }
     }
 
+    // Whatever is left (rate-limited, requeued, or enqueued mid-pump) feeds
+    // the backlog gauges; a pump that can't drain shows a growing oldest age.
+    if let Err(e) = ems.observe_queue_depth(session_id).await {
+        warn!(error = %e, "failed to refresh queue depth metrics");
+    }
+
     info!(
         processed = result.processed,
         submitted = result.submitted,
@@ -167,10 +230,16 @@ enum SubmitOutcome {
     Timeout,
     Requeued(String),
     RateLimited,
+    /// Transient exchange error or unreadable response; like `Timeout`, the
+    /// order may have been placed, so it is left for reconciliation
+    Unavailable(String),
 }
 
-async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> SubmitOutcome {
-    let request = harman::types::OrderRequest {
+const SUBMIT_ENDPOINT: &str = "POST /trade-api/v2/portfolio/orders";
+const SUBMIT_BATCH_ENDPOINT: &str = "POST /trade-api/v2/portfolio/orders/batched";
+
+fn submit_request(item: &db::QueueItem) -> harman::types::OrderRequest {
+    harman::types::OrderRequest {
         client_order_id: item.order.client_order_id,
         ticker: item.order.ticker.clone(),
         side: item.order.side,
@@ -180,19 +249,140 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
         time_in_force: item.order.time_in_force,
         order_type: item.order.order_type,
         trigger_price: item.order.trigger_price,
-    };
+        good_till: item.order.good_till,
+        reduce_only: item.order.reduce_only,
+    }
+}
+
+/// Submit one order. Submits are never retried: after a timeout or transient
+/// error the exchange may already hold the order, and a resend would be
+/// rejected as a duplicate `client_order_id` while the original is live.
+async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> SubmitOutcome {
+    let request = submit_request(item);
+    let start = std::time::Instant::now();
+    let submitted =
+        exchange_call::call_once(ems, "submit_order", || ems.exchange.submit_order(&request)).await;
+    let duration_ms = start.elapsed().as_millis() as i32;
+    record_submit(
+        ems,
+        session_id,
+        item,
+        &request,
+        SUBMIT_ENDPOINT,
+        duration_ms,
+        submitted,
+    )
+    .await
+}
+
+/// Submit several dequeued orders in one exchange call and record each
+/// order's outcome individually.
+///
+/// If the batch call itself fails: a timeout or transient error leaves every
+/// order submitted for reconciliation (any of them may have been placed), a
+/// rate limit requeues them all, and any other error falls back to
+/// submitting one at a time. Like single submits, the call is never retried.
+async fn handle_submit_batch(
+    ems: &Ems,
+    session_id: i64,
+    items: &[db::QueueItem],
+) -> Vec<SubmitOutcome> {
+    let requests: Vec<_> = items.iter().map(submit_request).collect();
     let start = std::time::Instant::now();
-    match ems.exchange.submit_order(&request).await {
+    let batch =
+        exchange_call::call_once(ems, "submit_batch", || ems.exchange.submit_batch(&requests))
+            .await;
+    let duration_ms = start.elapsed().as_millis() as i32;
+
+    let results: Vec<Result<String, ExchangeError>> = match batch {
+        Ok(mut results) => {
+            if results.len() != items.len() {
+                warn!(
+                    expected = items.len(),
+                    got = results.len(),
+                    "batch submit returned wrong number of results"
+                );
+            }
+            results.truncate(items.len());
+            while results.len() < items.len() {
+                results.push(Err(ExchangeError::Unexpected(
+                    "no result for order in batch response".into(),
+                )));
+            }
+            results
+        }
+        Err(ExchangeError::Timeout { timeout_ms }) => items
+            .iter()
+            .map(|_| Err(ExchangeError::Timeout { timeout_ms }))
+            .collect(),
+        Err(ExchangeError::RateLimited { retry_after_ms }) => items
+            .iter()
+            .map(|_| Err(ExchangeError::RateLimited { retry_after_ms }))
+            .collect(),
+        Err(ExchangeError::ServerError { status }) => items
+            .iter()
+            .map(|_| Err(ExchangeError::ServerError { status }))
+            .collect(),
+        Err(ExchangeError::Connection(reason)) => items
+            .iter()
+            .map(|_| Err(ExchangeError::Connection(reason.clone())))
+            .collect(),
+        Err(ExchangeError::BatchRefused(reason)) => {
+            warn!(reason = %reason, count = items.len(), "batch submit refused, submitting individually");
+            let mut outcomes = Vec::with_capacity(items.len());
+            for item in items {
+                outcomes.push(handle_submit(ems, session_id, item).await);
+            }
+            return outcomes;
+        }
+        // The request left and its outcome is unknown (e.g. a 2xx body that
+        // failed to decode). Resubmitting would duplicate live orders, so each
+        // order is left for reconciliation like a timeout.
+        Err(e) => items
+            .iter()
+            .map(|_| Err(ExchangeError::Unexpected(e.to_string())))
+            .collect(),
+    };
+
+    let mut outcomes = Vec::with_capacity(items.len());
+    for ((item, request), submitted) in items.iter().zip(&requests).zip(results) {
+        outcomes.push(
+            record_submit(
+                ems,
+                session_id,
+                item,
+                request,
+                SUBMIT_BATCH_ENDPOINT,
+                duration_ms,
+                submitted,
+            )
+            .await,
+        );
+    }
+    outcomes
+}
+
+/// Apply one order's submit result: audit the call, update order state and
+/// the queue item.
+async fn record_submit(
+    ems: &Ems,
+    session_id: i64,
+    item: &db::QueueItem,
+    request: &harman::types::OrderRequest,
+    endpoint: &str,
+    duration_ms: i32,
+    submitted: Result<String, ExchangeError>,
+) -> SubmitOutcome {
+    match submitted {
         Ok(exchange_order_id) => {
-            let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
                 session_id,
                 Some(item.order_id),
                 "submit_order",
-                "POST /trade-api/v2/portfolio/orders",
+                endpoint,
                 Some(200),
                 Some(duration_ms),
-                serde_json::to_value(&request).ok(),
+                serde_json::to_value(request).ok(),
                 Some(serde_json::json!({"exchange_order_id": exchange_order_id})),
                 "success",
                 None,
@@ -204,8 +394,7 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
             );
             ems.metrics.orders_submitted.inc();
 
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -221,29 +410,35 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
             let _ = db::remove_queue_item(&ems.pool, item.queue_id).await;
             SubmitOutcome::Submitted
         }
-        Err(ExchangeError::Rejected { reason }) => {
-            let duration_ms = start.elapsed().as_millis() as i32;
+        Err(ExchangeError::Rejected { category, reason }) => {
             ems.audit.rest_call(
                 session_id,
                 Some(item.order_id),
                 "submit_order",
-                "POST /trade-api/v2/portfolio/orders",
+                endpoint,
                 Some(400),
                 Some(duration_ms),
-                serde_json::to_value(&request).ok(),
+                serde_json::to_value(request).ok(),
                 None,
                 "error",
                 Some(reason.clone()),
             );
             warn!(
                 order_id = item.order_id,
+                %category,
                 reason = %reason,
                 "order rejected by exchange"
             );
-            ems.metrics.orders_rejected.inc();
+            ems.metrics
+                .orders_rejected
+                .with_label_values(&[category.as_str()])
+                .inc();
 
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = db::set_reject_reason(&ems.pool, item.order_id, &reason).await {
+                error!(error = %e, "failed to record reject reason");
+            }
+
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Rejected,
@@ -260,15 +455,14 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
             SubmitOutcome::Rejected
         }
         Err(ExchangeError::RateLimited { retry_after_ms: _ }) => {
-            let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
                 session_id,
                 Some(item.order_id),
                 "submit_order",
-                "POST /trade-api/v2/portfolio/orders",
+                endpoint,
                 Some(429),
                 Some(duration_ms),
-                serde_json::to_value(&request).ok(),
+                serde_json::to_value(request).ok(),
                 None,
                 "rate_limited",
                 None,
@@ -282,15 +476,14 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
             SubmitOutcome::RateLimited
         }
         Err(ExchangeError::Timeout { .. }) => {
-            let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
                 session_id,
                 Some(item.order_id),
                 "submit_order",
-                "POST /trade-api/v2/portfolio/orders",
+                endpoint,
                 None,
                 Some(duration_ms),
-                serde_json::to_value(&request).ok(),
+                serde_json::to_value(request).ok(),
                 None,
                 "timeout",
                 None,
@@ -302,16 +495,41 @@ async fn handle_submit(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Subm
             let _ = db::remove_queue_item(&ems.pool, item.queue_id).await;
             SubmitOutcome::Timeout
         }
+        // Transient errors and unreadable responses: the order may have been
+        // placed, so it is left for reconciliation rather than resubmitted
+        Err(e) if e.is_transient() || matches!(e, ExchangeError::Unexpected(_)) => {
+            ems.audit.rest_call(
+                session_id,
+                Some(item.order_id),
+                "submit_order",
+                endpoint,
+                None,
+                Some(duration_ms),
+                serde_json::to_value(request).ok(),
+                None,
+                if e.is_transient() { "unavailable" } else { "unknown" },
+                Some(e.to_string()),
+            );
+            warn!(
+                error = %e,
+                order_id = item.order_id,
+                "submit outcome unknown, leaving as submitted for reconciliation"
+            );
+            let _ = db::remove_queue_item(&ems.pool, item.queue_id).await;
+            SubmitOutcome::Unavailable(format!(
+                "order {}: {}, left for reconciliation",
+                item.order_id, e
+            ))
+        }
         Err(e) => {
-            let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
                 session_id,
                 Some(item.order_id),
                 "submit_order",
-                "POST /trade-api/v2/portfolio/orders",
+                endpoint,
                 None,
                 Some(duration_ms),
-                serde_json::to_value(&request).ok(),
+                serde_json::to_value(request).ok(),
                 None,
                 "error",
                 Some(e.to_string()),
@@ -336,6 +554,8 @@ enum CancelOutcome {
     NotFound,
     Requeued(String),
     RateLimited,
+    /// Requeued after a timeout or exhausted transient retries
+    Unavailable(String),
 }
 
 async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> CancelOutcome {
@@ -346,8 +566,7 @@ async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Canc
                 order_id = item.order_id,
                 "cancel requested but never sent to exchange, cancelling locally"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Cancelled,
@@ -365,7 +584,11 @@ async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Canc
     };
 
     let start = std::time::Instant::now();
-    match ems.exchange.cancel_order(&exchange_order_id).await {
+    let cancelled = exchange_call::call(ems, "cancel_order", || {
+        ems.exchange.cancel_order(&exchange_order_id)
+    })
+    .await;
+    match cancelled {
         Ok(()) => {
             let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
@@ -383,8 +606,7 @@ async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Canc
             info!(order_id = item.order_id, "cancel confirmed");
             ems.metrics.orders_cancelled.inc();
 
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Cancelled,
@@ -418,8 +640,7 @@ async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Canc
                 order_id = item.order_id,
                 "cancel target not found on exchange, marking cancelled"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Cancelled,
@@ -453,6 +674,30 @@ async fn handle_cancel(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Canc
             }
             CancelOutcome::RateLimited
         }
+        Err(e) if e.is_transient() || matches!(e, ExchangeError::Timeout { .. }) => {
+            let duration_ms = start.elapsed().as_millis() as i32;
+            ems.audit.rest_call(
+                session_id,
+                Some(item.order_id),
+                "cancel_order",
+                "DELETE /trade-api/v2/portfolio/orders",
+                None,
+                Some(duration_ms),
+                Some(serde_json::json!({"exchange_order_id": exchange_order_id})),
+                None,
+                "unavailable",
+                Some(e.to_string()),
+            );
+            warn!(
+                error = %e,
+                order_id = item.order_id,
+                "cancel: exchange unavailable, requeueing"
+            );
+            if let Err(e) = db::requeue_item(&ems.pool, item.queue_id).await {
+                error!(error = %e, "failed to requeue cancel");
+            }
+            CancelOutcome::Unavailable(format!("cancel order {}: {}", item.order_id, e))
+        }
         Err(e) => {
             let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
@@ -484,6 +729,8 @@ enum AmendOutcome {
     Amended,
     Requeued(String),
     RateLimited,
+    /// Requeued after a timeout or exhausted transient retries
+    Unavailable(String),
 }
 
 async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> AmendOutcome {
@@ -494,8 +741,7 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
                 order_id = item.order_id,
                 "amend requested but no exchange_order_id, reverting state"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -520,8 +766,7 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
         Some(m) => m,
         None => {
             error!(order_id = item.order_id, "amend queue item missing metadata");
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -561,7 +806,9 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
     };
 
     let start = std::time::Instant::now();
-    match ems.exchange.amend_order(&request).await {
+    let amended =
+        exchange_call::call(ems, "amend_order", || ems.exchange.amend_order(&request)).await;
+    match amended {
         Ok(result) => {
             let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
@@ -617,8 +864,7 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
                 order_id = item.order_id,
                 "amend target not found on exchange, marking cancelled"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Cancelled,
@@ -650,6 +896,25 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
             }
             AmendOutcome::RateLimited
         }
+        Err(e) if e.is_transient() || matches!(e, ExchangeError::Timeout { .. }) => {
+            let duration_ms = start.elapsed().as_millis() as i32;
+            ems.audit.rest_call(
+                session_id, Some(item.order_id), "amend_order",
+                "POST /trade-api/v2/portfolio/orders/amend",
+                None, Some(duration_ms),
+                serde_json::to_value(&request).ok(), None, "unavailable", Some(e.to_string()),
+            );
+            // Left in its pending-amend state; retrying the same amend is safe
+            warn!(
+                error = %e,
+                order_id = item.order_id,
+                "amend: exchange unavailable, requeueing"
+            );
+            if let Err(e) = db::requeue_item(&ems.pool, item.queue_id).await {
+                error!(error = %e, "failed to requeue amend");
+            }
+            AmendOutcome::Unavailable(format!("amend order {}: {}", item.order_id, e))
+        }
         Err(e) => {
             let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
@@ -664,8 +929,7 @@ async fn handle_amend(ems: &Ems, session_id: i64, item: &db::QueueItem) -> Amend
                 "amend exchange error, reverting state"
             );
             // Revert to acknowledged on failure
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -697,8 +961,7 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
                 order_id = item.order_id,
                 "decrease requested but no exchange_order_id, reverting state"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -724,8 +987,7 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
             Some(d) => d,
             None => {
                 error!(order_id = item.order_id, "decrease metadata missing reduce_by");
-                if let Err(e) = db::update_order_state(
-                    &ems.pool,
+                if let Err(e) = ems.update_order_state(
                     item.order_id,
                     session_id,
                     OrderState::Acknowledged,
@@ -746,8 +1008,7 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
         },
         None => {
             error!(order_id = item.order_id, "decrease queue item missing metadata");
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -768,11 +1029,13 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
     };
 
     let start = std::time::Instant::now();
-    match ems
-        .exchange
-        .decrease_order(&exchange_order_id, reduce_by)
-        .await
-    {
+    // Not retried or requeued: a decrease that landed but timed out would
+    // be applied twice
+    let decreased = exchange_call::call_once(ems, "decrease_order", || {
+        ems.exchange.decrease_order(&exchange_order_id, reduce_by)
+    })
+    .await;
+    match decreased {
         Ok(()) => {
             let duration_ms = start.elapsed().as_millis() as i32;
             ems.audit.rest_call(
@@ -816,8 +1079,7 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
                 order_id = item.order_id,
                 "decrease target not found on exchange, marking cancelled"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Cancelled,
@@ -864,8 +1126,7 @@ async fn handle_decrease(ems: &Ems, session_id: i64, item: &db::QueueItem) -> De
                 order_id = item.order_id,
                 "decrease exchange error, reverting state"
             );
-            if let Err(e) = db::update_order_state(
-                &ems.pool,
+            if let Err(e) = ems.update_order_state(
                 item.order_id,
                 session_id,
                 OrderState::Acknowledged,
@@ -1,4 +1,4 @@
-use harman::db;
+use harman::db::{self, BatchEnqueueOutcome};
 use harman::error::EnqueueError;
 use harman::types::{CancelReason, Order, OrderRequest};
 use rust_decimal::Decimal;
@@ -7,12 +7,46 @@ use crate::Ems;
 
 impl Ems {
     /// Enqueue a new order (risk check + atomic DB insert + queue item).
+    ///
+    /// `actor` is recorded on the queue item and audit row, e.g. `api:<key_prefix>`.
     pub async fn enqueue(
         &self,
         session_id: i64,
         request: &OrderRequest,
+        actor: &str,
     ) -> Result<Order, EnqueueError> {
-        db::enqueue_order(&self.pool, request, session_id, &self.risk_limits).await
+        let order =
+            db::enqueue_order(&self.pool, request, session_id, &self.risk_limits, actor).await?;
+        self.metrics.orders_enqueued.inc();
+        Ok(order)
+    }
+
+    /// Run the same risk checks as `enqueue` without persisting anything.
+    pub async fn validate(
+        &self,
+        session_id: i64,
+        request: &OrderRequest,
+    ) -> Result<db::RiskProjection, EnqueueError> {
+        db::validate_order(&self.pool, request, session_id, &self.risk_limits).await
+    }
+
+    /// Enqueue a batch of orders in one transaction (all-or-nothing on risk;
+    /// duplicates reported per item).
+    pub async fn enqueue_batch(
+        &self,
+        session_id: i64,
+        requests: &[OrderRequest],
+        actor: &str,
+    ) -> Result<Vec<BatchEnqueueOutcome>, EnqueueError> {
+        let outcomes =
+            db::enqueue_order_batch(&self.pool, requests, session_id, &self.risk_limits, actor)
+                .await?;
+        let enqueued = outcomes
+            .iter()
+            .filter(|o| matches!(o, BatchEnqueueOutcome::Enqueued(_)))
+            .count();
+        self.metrics.orders_enqueued.inc_by(enqueued as u64);
+        Ok(outcomes)
     }
 
     /// Enqueue a cancel action for an existing order.
@@ -21,8 +55,9 @@ impl Ems {
         order_id: i64,
         session_id: i64,
         cancel_reason: &CancelReason,
+        actor: &str,
     ) -> Result<(), String> {
-        db::atomic_cancel_order(&self.pool, order_id, session_id, cancel_reason).await
+        db::atomic_cancel_order(&self.pool, order_id, session_id, cancel_reason, actor).await
     }
 
     /// Enqueue an amend action for an existing order.
@@ -32,9 +67,17 @@ impl Ems {
         session_id: i64,
         new_price_dollars: Option<Decimal>,
         new_quantity: Option<Decimal>,
+        actor: &str,
     ) -> Result<(), String> {
-        db::atomic_amend_order(&self.pool, order_id, session_id, new_price_dollars, new_quantity)
-            .await
+        db::atomic_amend_order(
+            &self.pool,
+            order_id,
+            session_id,
+            new_price_dollars,
+            new_quantity,
+            actor,
+        )
+        .await
     }
 
     /// Enqueue a decrease action for an existing order.
@@ -43,7 +86,8 @@ impl Ems {
         order_id: i64,
         session_id: i64,
         reduce_by: Decimal,
+        actor: &str,
     ) -> Result<(), String> {
-        db::atomic_decrease_order(&self.pool, order_id, session_id, reduce_by).await
+        db::atomic_decrease_order(&self.pool, order_id, session_id, reduce_by, actor).await
     }
 }
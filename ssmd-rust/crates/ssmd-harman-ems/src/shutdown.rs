@@ -1,16 +1,72 @@
 use std::sync::atomic::Ordering;
 use tracing::{error, info, warn};
 
+use harman::state::OrderState;
+use harman::types::CancelReason;
+
 use crate::Ems;
 
-/// Execute shutdown sequence: set flag, mass cancel, drain queue.
+/// What the EMS does with live work when the pod is asked to stop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Mass cancel on the exchange and reject everything still queued.
+    #[default]
+    Drain,
+    /// Cancel resting (acknowledged) orders on the exchange; leave the queue
+    /// for the next pod to pick up.
+    CancelResting,
+    /// Stop accepting new work; leave exchange orders and the queue untouched.
+    Hold,
+}
+
+impl std::fmt::Display for ShutdownMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownMode::Drain => write!(f, "drain"),
+            ShutdownMode::CancelResting => write!(f, "cancel_resting"),
+            ShutdownMode::Hold => write!(f, "hold"),
+        }
+    }
+}
+
+impl std::str::FromStr for ShutdownMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drain" => Ok(ShutdownMode::Drain),
+            "cancel_resting" => Ok(ShutdownMode::CancelResting),
+            "hold" => Ok(ShutdownMode::Hold),
+            other => Err(format!(
+                "unknown shutdown mode '{}' (expected drain, cancel_resting or hold)",
+                other
+            )),
+        }
+    }
+}
+
+/// Execute shutdown sequence: set flag, then act according to `mode`.
+///
+/// `exchange` and `environment` identify this instance's sessions; orders of
+/// other instances sharing the database are never touched.
 ///
 /// Does NOT listen for signals -- that stays in the binary.
 /// This just executes the shutdown actions.
-pub async fn shutdown(ems: &Ems) {
-    info!("EMS shutdown initiated");
+pub async fn shutdown(ems: &Ems, mode: ShutdownMode, exchange: &str, environment: &str) {
+    info!(%mode, "EMS shutdown initiated");
     ems.shutting_down.store(true, Ordering::Relaxed);
 
+    match mode {
+        ShutdownMode::Drain => drain(ems).await,
+        ShutdownMode::CancelResting => cancel_resting(ems, exchange, environment).await,
+        ShutdownMode::Hold => info!("holding exchange orders and queue for next pod"),
+    }
+
+    info!("EMS shutdown complete");
+}
+
+/// Mass cancel on the exchange and reject all queued orders.
+async fn drain(ems: &Ems) {
     // Mass cancel on exchange
     match ems.exchange.cancel_all_orders().await {
         Ok(count) => info!(count, "mass cancel completed"),
@@ -26,6 +82,47 @@ pub async fn shutdown(ems: &Ems) {
         }
         Err(e) => error!(error = %e, "drain queue failed"),
     }
+}
 
-    info!("EMS shutdown complete");
+/// Cancel each resting order on the exchange, leaving the queue untouched.
+///
+/// Only confirmed cancels are marked cancelled locally. Anything the exchange
+/// no longer knows about (filled or cancelled elsewhere) is left for the next
+/// pod's reconciliation to resolve.
+async fn cancel_resting(ems: &Ems, exchange: &str, environment: &str) {
+    let resting = match harman::db::list_resting_orders_all(&ems.pool, exchange, environment).await {
+        Ok(orders) => orders,
+        Err(e) => {
+            error!(error = %e, "list resting orders failed during shutdown");
+            return;
+        }
+    };
+
+    let mut cancelled = 0u64;
+    for (order_id, session_id, exchange_order_id) in resting {
+        match ems.exchange.cancel_order(&exchange_order_id).await {
+            Ok(()) => {
+                ems.metrics.orders_cancelled.inc();
+                match ems
+                    .update_order_state(
+                        order_id,
+                        session_id,
+                        OrderState::Cancelled,
+                        None,
+                        Some(&CancelReason::Shutdown),
+                        "shutdown",
+                    )
+                    .await
+                {
+                    Ok(()) => cancelled += 1,
+                    Err(e) => error!(order_id, error = %e, "failed to update cancelled state"),
+                }
+            }
+            Err(e) => {
+                warn!(order_id, exchange_order_id = %exchange_order_id, error = %e, "shutdown cancel failed")
+            }
+        }
+    }
+
+    info!(cancelled, "resting order cancel completed");
 }
@@ -17,7 +17,8 @@ use harman::test_helpers::*;
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use ssmd_harman_ems::{Ems, EmsMetrics};
+use ssmd_harman_ems::exchange_call::ExchangeCallPolicy;
+use ssmd_harman_ems::{Ems, EmsMetrics, ShutdownMode};
 
 /// Build an Ems instance with MockExchange and a test DB pool.
 async fn build_test_ems(mock: MockExchange, pool: deadpool_postgres::Pool) -> Ems {
@@ -51,6 +52,18 @@ async fn setup() -> Option<(deadpool_postgres::Pool, i64)> {
     Some((pool, session_id))
 }
 
+/// The (exchange, environment) of a session, i.e. the instance it belongs to.
+async fn session_scope(pool: &deadpool_postgres::Pool, session_id: i64) -> (String, String) {
+    let row = pool
+        .get()
+        .await
+        .unwrap()
+        .query_one("SELECT exchange, environment FROM sessions WHERE id = $1", &[&session_id])
+        .await
+        .unwrap();
+    (row.get("exchange"), row.get("environment"))
+}
+
 /// Unwrap setup or return early (skip the test).
 macro_rules! setup_or_skip {
     () => {
@@ -86,7 +99,10 @@ async fn test_ems_pump_submit_success() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .expect("enqueue should succeed");
@@ -113,7 +129,10 @@ async fn test_ems_pump_submit_rejected() {
     let mock = MockExchange::new();
     {
         let mut state = mock.state.lock().await;
-        state.submit_behavior = SubmitBehavior::Reject("insufficient balance".to_string());
+        state.submit_behavior = SubmitBehavior::RejectWith(
+            harman::error::RejectCategory::InsufficientBalance,
+            "insufficient balance".to_string(),
+        );
     }
     let ems = build_test_ems(mock, pool.clone()).await;
 
@@ -130,7 +149,10 @@ async fn test_ems_pump_submit_rejected() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -145,8 +167,18 @@ async fn test_ems_pump_submit_rejected() {
         .await
         .unwrap();
 
-    // Metric incremented
-    assert_eq!(ems.metrics.orders_rejected.get(), 1);
+    // Exchange reason is kept on the order
+    let stored = db::get_order(&pool, order.id, session_id).await.unwrap().unwrap();
+    assert_eq!(stored.reject_reason.as_deref(), Some("insufficient balance"));
+
+    // Metric incremented under the categorized reason
+    assert_eq!(
+        ems.metrics
+            .orders_rejected
+            .with_label_values(&["insufficient_balance"])
+            .get(),
+        1
+    );
 }
 
 #[tokio::test]
@@ -173,7 +205,10 @@ async fn test_ems_pump_submit_rate_limited() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -211,7 +246,10 @@ async fn test_ems_pump_submit_timeout() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -226,6 +264,190 @@ async fn test_ems_pump_submit_timeout() {
         .unwrap();
 }
 
+fn test_order_request(ticker: &str) -> harman::types::OrderRequest {
+    harman::types::OrderRequest {
+        client_order_id: Uuid::new_v4(),
+        ticker: ticker.to_string(),
+        side: harman::types::Side::Yes,
+        action: harman::types::Action::Buy,
+        quantity: Decimal::from(1),
+        price_dollars: Decimal::new(50, 2),
+        time_in_force: harman::types::TimeInForce::Gtc,
+        order_type: harman::types::OrderType::default(),
+        trigger_price: None,
+        good_till: None,
+        reduce_only: false,
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_pump_submit_server_error_left_for_reconciliation() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new();
+    let state = mock.state.clone();
+    state.lock().await.submit_behavior = SubmitBehavior::ServerError(503);
+    let ems = build_test_ems(mock, pool.clone())
+        .await
+        .with_call_policy(ExchangeCallPolicy {
+            max_retries: 2,
+            retry_backoff: std::time::Duration::from_millis(1),
+            ..ExchangeCallPolicy::default()
+        });
+
+    let order = ems
+        .enqueue(session_id, &test_order_request("KXTEST-EMS-5XX"), "test")
+        .await
+        .unwrap();
+
+    let result = ems.pump(session_id).await;
+    assert_eq!(result.processed, 1);
+    assert_eq!(result.requeued, 0);
+    assert!(result.errors.iter().any(|e| e.contains("exchange unavailable")));
+
+    // The exchange may have accepted the order before failing, so it is sent
+    // once and not requeued; reconciliation resolves it by client_order_id
+    assert_eq!(state.lock().await.submitted_orders.len(), 1);
+    assert_eq!(queue_count(&pool, session_id).await.unwrap(), 0);
+    assert_order_state(&pool, order.id, OrderState::Submitted)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_pump_submit_hang_times_out() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new();
+    mock.state.lock().await.submit_behavior = SubmitBehavior::Hang;
+    let ems = build_test_ems(mock, pool.clone())
+        .await
+        .with_call_policy(ExchangeCallPolicy {
+            timeout: std::time::Duration::from_millis(50),
+            ..ExchangeCallPolicy::default()
+        });
+
+    let order = ems
+        .enqueue(session_id, &test_order_request("KXTEST-EMS-HANG"), "test")
+        .await
+        .unwrap();
+
+    let result = ems.pump(session_id).await;
+    assert_eq!(result.processed, 1);
+    assert!(!result.errors.is_empty());
+    assert_eq!(
+        ems.metrics
+            .exchange_timeouts
+            .with_label_values(&["submit_order"])
+            .get(),
+        1
+    );
+
+    // Same as an exchange-reported timeout: left for reconciliation
+    assert_order_state(&pool, order.id, OrderState::Submitted)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_pump_submit_batch_partial_reject() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new().with_submit_batch(10);
+    let state = mock.state.clone();
+    let client_ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    state.lock().await.submit_overrides.insert(
+        client_ids[1],
+        SubmitBehavior::Reject("price out of range".to_string()),
+    );
+    let ems = build_test_ems(mock, pool.clone()).await;
+
+    let mut orders = Vec::new();
+    for (i, client_order_id) in client_ids.iter().enumerate() {
+        let order = ems
+            .enqueue(
+                session_id,
+                &harman::types::OrderRequest {
+                    client_order_id: *client_order_id,
+                    ticker: format!("KXTEST-EMS-BATCH-{}", i),
+                    side: harman::types::Side::Yes,
+                    action: harman::types::Action::Buy,
+                    quantity: Decimal::from(1),
+                    price_dollars: Decimal::new(50, 2),
+                    time_in_force: harman::types::TimeInForce::Gtc,
+                    order_type: harman::types::OrderType::default(),
+                    trigger_price: None,
+                    good_till: None,
+                    reduce_only: false,
+                },
+                "test",
+            )
+            .await
+            .unwrap();
+        orders.push(order);
+    }
+
+    let result = ems.pump(session_id).await;
+    assert_eq!(result.processed, 3);
+    assert_eq!(result.submitted, 2);
+    assert_eq!(result.rejected, 1);
+
+    // All three went out in one exchange call
+    assert_eq!(state.lock().await.batch_calls, vec![3]);
+
+    // Each order's state reflects its own result
+    assert_order_state(&pool, orders[0].id, OrderState::Acknowledged)
+        .await
+        .unwrap();
+    assert_order_state(&pool, orders[1].id, OrderState::Rejected)
+        .await
+        .unwrap();
+    assert_order_state(&pool, orders[2].id, OrderState::Acknowledged)
+        .await
+        .unwrap();
+    assert_eq!(queue_count(&pool, session_id).await.unwrap(), 0);
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_pump_submit_batch_undecodable_response_left_for_reconciliation() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new().with_submit_batch(10);
+    let state = mock.state.clone();
+    state.lock().await.batch_response_undecodable = true;
+    let ems = build_test_ems(mock, pool.clone()).await;
+
+    let mut orders = Vec::new();
+    for i in 0..2 {
+        let order = ems
+            .enqueue(
+                session_id,
+                &batch_order(&format!("KXTEST-EMS-UNDECODABLE-{}", i), Decimal::from(1), Decimal::new(50, 2)),
+                "test",
+            )
+            .await
+            .unwrap();
+        orders.push(order);
+    }
+
+    let result = ems.pump(session_id).await;
+    assert_eq!(result.processed, 2);
+    assert_eq!(result.rejected, 0);
+
+    // One batch call and no individual resubmits of the already-live orders
+    let state = state.lock().await;
+    assert_eq!(state.batch_calls, vec![2]);
+    assert_eq!(state.submitted_orders.len(), 2);
+    drop(state);
+
+    for order in &orders {
+        assert_order_state(&pool, order.id, OrderState::Submitted)
+            .await
+            .unwrap();
+    }
+    assert_eq!(queue_count(&pool, session_id).await.unwrap(), 0);
+}
+
 // =============================================================================
 // Pump: cancel
 // =============================================================================
@@ -251,7 +473,10 @@ async fn test_ems_pump_cancel_success() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -262,6 +487,7 @@ async fn test_ems_pump_cancel_success() {
         order.id,
         session_id,
         &harman::types::CancelReason::UserRequested,
+        "test",
     )
     .await
     .unwrap();
@@ -297,6 +523,7 @@ async fn test_ems_pump_cancel_without_exchange_id() {
         order_id,
         session_id,
         &harman::types::CancelReason::UserRequested,
+        "test",
     )
     .await
     .unwrap();
@@ -336,6 +563,7 @@ async fn test_ems_pump_cancel_not_found() {
         order_id,
         session_id,
         &harman::types::CancelReason::UserRequested,
+        "test",
     )
     .await
     .unwrap();
@@ -371,7 +599,7 @@ async fn test_ems_pump_amend_success() {
     .unwrap();
 
     // Enqueue amend with new price
-    ems.enqueue_amend(order_id, session_id, Some(Decimal::new(60, 2)), None)
+    ems.enqueue_amend(order_id, session_id, Some(Decimal::new(60, 2)), None, "test")
         .await
         .unwrap();
 
@@ -407,7 +635,7 @@ async fn test_ems_pump_amend_not_found() {
     .await
     .unwrap();
 
-    ems.enqueue_amend(order_id, session_id, Some(Decimal::new(60, 2)), None)
+    ems.enqueue_amend(order_id, session_id, Some(Decimal::new(60, 2)), None, "test")
         .await
         .unwrap();
 
@@ -440,7 +668,7 @@ async fn test_ems_pump_decrease_success() {
     .await
     .unwrap();
 
-    ems.enqueue_decrease(order_id, session_id, Decimal::from(3))
+    ems.enqueue_decrease(order_id, session_id, Decimal::from(3), "test")
         .await
         .unwrap();
 
@@ -475,7 +703,7 @@ async fn test_ems_pump_decrease_not_found() {
     .await
     .unwrap();
 
-    ems.enqueue_decrease(order_id, session_id, Decimal::from(3))
+    ems.enqueue_decrease(order_id, session_id, Decimal::from(3), "test")
         .await
         .unwrap();
 
@@ -511,7 +739,10 @@ async fn test_ems_pump_respects_shutdown() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -549,7 +780,10 @@ async fn test_ems_shutdown_mass_cancels_and_drains() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -557,7 +791,8 @@ async fn test_ems_shutdown_mass_cancels_and_drains() {
     assert!(queue_count(&pool, session_id).await.unwrap() > 0);
 
     // Shutdown
-    ems.shutdown().await;
+    let (exchange, environment) = session_scope(&pool, session_id).await;
+    ems.shutdown(ShutdownMode::Drain, &exchange, &environment).await;
 
     assert!(ems.is_shutting_down());
     // Queue should be drained
@@ -569,6 +804,110 @@ async fn test_ems_shutdown_mass_cancels_and_drains() {
     assert_eq!(state, 5);
 }
 
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_shutdown_cancel_resting_keeps_queue() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new();
+    let mock_state = mock.state.clone();
+    let ems = build_test_ems(mock, pool.clone()).await;
+
+    let resting_id = insert_test_order(
+        &pool,
+        session_id,
+        OrderState::Acknowledged,
+        "KXTEST-EMS-RESTING",
+        Some("exch-shutdown-resting"),
+    )
+    .await
+    .unwrap();
+
+    let queued = ems
+        .enqueue(
+            session_id,
+            &harman::types::OrderRequest {
+                client_order_id: Uuid::new_v4(),
+                ticker: "KXTEST-EMS-HOLDQ".to_string(),
+                side: harman::types::Side::Yes,
+                action: harman::types::Action::Buy,
+                quantity: Decimal::from(1),
+                price_dollars: Decimal::new(50, 2),
+                time_in_force: harman::types::TimeInForce::Gtc,
+                order_type: harman::types::OrderType::default(),
+                trigger_price: None,
+                good_till: None,
+                reduce_only: false,
+            },
+            "test",
+        )
+        .await
+        .unwrap();
+
+    let (exchange, environment) = session_scope(&pool, session_id).await;
+    ems.shutdown(ShutdownMode::CancelResting, &exchange, &environment).await;
+
+    assert!(ems.is_shutting_down());
+    assert_order_state(&pool, resting_id, OrderState::Cancelled)
+        .await
+        .unwrap();
+    // Queued order is left for the next pod
+    assert_order_state(&pool, queued.id, OrderState::Pending)
+        .await
+        .unwrap();
+    assert!(queue_count(&pool, session_id).await.unwrap() > 0);
+
+    let state = mock_state.lock().await;
+    assert!(state.cancel_calls.contains(&"exch-shutdown-resting".to_string()));
+    assert_eq!(state.cancel_all_calls, 0);
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_shutdown_cancel_resting_scoped_to_environment() {
+    let (pool, session_id) = setup_or_skip!();
+    let mock = MockExchange::new();
+    let mock_state = mock.state.clone();
+    let ems = build_test_ems(mock, pool.clone()).await;
+
+    // Another instance on the same exchange, different environment
+    let (exchange, environment) = session_scope(&pool, session_id).await;
+    let other_session = db::get_or_create_session(&pool, &exchange, "demo", None)
+        .await
+        .expect("create session");
+
+    let own_id = insert_test_order(
+        &pool,
+        session_id,
+        OrderState::Acknowledged,
+        "KXTEST-EMS-SCOPE",
+        Some("exch-shutdown-own"),
+    )
+    .await
+    .unwrap();
+    let other_id = insert_test_order(
+        &pool,
+        other_session,
+        OrderState::Acknowledged,
+        "KXTEST-EMS-SCOPE",
+        Some("exch-shutdown-other"),
+    )
+    .await
+    .unwrap();
+
+    ems.shutdown(ShutdownMode::CancelResting, &exchange, &environment).await;
+
+    assert_order_state(&pool, own_id, OrderState::Cancelled)
+        .await
+        .unwrap();
+    assert_order_state(&pool, other_id, OrderState::Acknowledged)
+        .await
+        .unwrap();
+
+    let state = mock_state.lock().await;
+    assert!(state.cancel_calls.contains(&"exch-shutdown-own".to_string()));
+    assert!(!state.cancel_calls.contains(&"exch-shutdown-other".to_string()));
+}
+
 // =============================================================================
 // Edge cases
 // =============================================================================
@@ -613,7 +952,10 @@ async fn test_ems_metrics_increment() {
                 time_in_force: harman::types::TimeInForce::Gtc,
                 order_type: harman::types::OrderType::default(),
                 trigger_price: None,
+                good_till: None,
+                reduce_only: false,
             },
+            "test",
         )
         .await
         .unwrap();
@@ -623,5 +965,323 @@ async fn test_ems_metrics_increment() {
 
     assert_eq!(ems.metrics.orders_dequeued.get(), 2);
     assert_eq!(ems.metrics.orders_submitted.get(), 2);
-    assert_eq!(ems.metrics.orders_rejected.get(), 0);
+    assert_eq!(ems.metrics.orders_rejected.with_label_values(&["other"]).get(), 0);
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_queue_depth_metrics() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    for i in 0..3 {
+        let order = batch_order(&format!("KXTEST-DEPTH-{}", i), Decimal::from(1), Decimal::new(50, 2));
+        ems.enqueue(session_id, &order, "test").await.unwrap();
+    }
+
+    let snapshot = ems.observe_queue_depth(session_id).await.unwrap();
+    assert_eq!(snapshot.depth, 3);
+    assert!(snapshot.oldest_age_secs >= 0.0);
+    let label = session_id.to_string();
+    assert_eq!(ems.metrics.queue_depth.with_label_values(&[&label]).get(), 3);
+
+    // Pump drains the queue and refreshes the gauges on the way out
+    ems.pump(session_id).await;
+    assert_eq!(ems.metrics.queue_depth.with_label_values(&[&label]).get(), 0);
+    assert_eq!(ems.metrics.queue_oldest_age.with_label_values(&[&label]).get(), 0.0);
+}
+
+// =============================================================================
+// Batch enqueue
+// =============================================================================
+
+fn batch_order(ticker: &str, quantity: Decimal, price_dollars: Decimal) -> harman::types::OrderRequest {
+    harman::types::OrderRequest {
+        client_order_id: Uuid::new_v4(),
+        ticker: ticker.to_string(),
+        side: harman::types::Side::Yes,
+        action: harman::types::Action::Buy,
+        quantity,
+        price_dollars,
+        time_in_force: harman::types::TimeInForce::Gtc,
+        order_type: harman::types::OrderType::default(),
+        trigger_price: None,
+        good_till: None,
+        reduce_only: false,
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_enqueue_batch_reports_duplicates_per_item() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    let existing = batch_order("KXTEST-BATCH-1", Decimal::from(10), Decimal::new(10, 2));
+    ems.enqueue(session_id, &existing, "test").await.expect("enqueue should succeed");
+
+    let fresh = batch_order("KXTEST-BATCH-2", Decimal::from(10), Decimal::new(10, 2));
+    let outcomes = ems
+        .enqueue_batch(session_id, &[existing.clone(), fresh.clone(), fresh.clone()], "test")
+        .await
+        .expect("batch should succeed");
+
+    assert_eq!(outcomes.len(), 3);
+    assert!(matches!(&outcomes[0], db::BatchEnqueueOutcome::Duplicate(cid) if *cid == existing.client_order_id));
+    assert!(matches!(&outcomes[1], db::BatchEnqueueOutcome::Enqueued(o) if o.client_order_id == fresh.client_order_id));
+    assert!(matches!(&outcomes[2], db::BatchEnqueueOutcome::Duplicate(_)));
+    assert_eq!(ems.metrics.orders_enqueued.get(), 2);
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_enqueue_batch_risk_is_all_or_nothing() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    // Each order is $20 (under the $25 fat-finger cap); 5 × $20 = $100 fits the
+    // default $100 limit, the sixth pushes the batch over
+    let orders: Vec<_> = (0..6)
+        .map(|i| {
+            batch_order(
+                &format!("KXTEST-BATCH-RISK-{}", i),
+                Decimal::from(100),
+                Decimal::new(20, 2),
+            )
+        })
+        .collect();
+
+    let err = ems.enqueue_batch(session_id, &orders, "test").await.unwrap_err();
+    assert!(matches!(
+        err,
+        harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::MaxNotionalExceeded { .. })
+    ));
+
+    // Nothing from the batch was inserted
+    let listed = db::list_orders(&pool, session_id, None).await.unwrap();
+    assert!(listed.is_empty());
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_validate_persists_nothing() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    let resting = batch_order("KXTEST-VALIDATE-1", Decimal::from(100), Decimal::new(20, 2));
+    ems.enqueue(session_id, &resting, "test").await.expect("enqueue should succeed");
+
+    // $10 on top of $20 open against the default $100 limit
+    let probe = batch_order("KXTEST-VALIDATE-2", Decimal::from(50), Decimal::new(20, 2));
+    let projection = ems.validate(session_id, &probe).await.expect("validate should pass");
+    assert_eq!(projection.open_notional, Decimal::from(20));
+    assert_eq!(projection.projected_open_notional, Decimal::from(30));
+    assert_eq!(projection.headroom, Decimal::from(70));
+
+    // A dry run that fails risk surfaces the specific error
+    let too_big = batch_order("KXTEST-VALIDATE-3", Decimal::from(500), Decimal::new(20, 2));
+    let err = ems.validate(session_id, &too_big).await.unwrap_err();
+    assert!(matches!(err, harman::error::EnqueueError::RiskCheck(_)));
+
+    // Only the resting order exists; no extra order or queue item was written
+    let listed = db::list_orders(&pool, session_id, None).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(db::queue_depth(&pool, session_id).await.unwrap().depth, 1);
+    assert_eq!(ems.metrics.orders_enqueued.get(), 1);
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_sell_closing_long_nets_against_position() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    // Build a 40-contract YES long, fully filled so it adds no open notional
+    let long = batch_order("KXTEST-NET", Decimal::from(40), Decimal::new(50, 2));
+    let long = ems.enqueue(session_id, &long, "test").await.expect("enqueue long");
+    db::record_fill(&pool, long.id, session_id, "net-trade-1", Decimal::new(50, 2), Decimal::from(40), true, chrono::Utc::now())
+        .await
+        .expect("record fill");
+
+    // Use up the full $100 limit with resting buys on other tickers
+    for i in 0..4 {
+        let filler = batch_order(&format!("KXTEST-NET-FILL-{}", i), Decimal::from(50), Decimal::new(50, 2));
+        ems.enqueue(session_id, &filler, "test").await.expect("enqueue filler");
+    }
+
+    // Closing sell fits inside the long: no fresh notional
+    let mut close = batch_order("KXTEST-NET", Decimal::from(40), Decimal::new(50, 2));
+    close.action = harman::types::Action::Sell;
+    let projection = ems.validate(session_id, &close).await.expect("closing sell should pass");
+    assert_eq!(projection.projected_open_notional, projection.open_notional);
+    ems.enqueue(session_id, &close, "test").await.expect("enqueue closing sell");
+
+    // The resting sell is covered by the long, so the session is still at $100
+    // rather than $120 — but it uses up the long, so another sell opens new exposure
+    let mut extra = batch_order("KXTEST-NET", Decimal::from(10), Decimal::new(50, 2));
+    extra.action = harman::types::Action::Sell;
+    let err = ems.validate(session_id, &extra).await.unwrap_err();
+    assert!(matches!(
+        err,
+        harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::MaxNotionalExceeded { .. })
+    ));
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_batch_sells_net_against_position() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    let long = batch_order("KXTEST-BATCH-NET", Decimal::from(40), Decimal::new(50, 2));
+    let long = ems.enqueue(session_id, &long, "test").await.expect("enqueue long");
+    db::record_fill(&pool, long.id, session_id, "batch-net-trade-1", Decimal::new(50, 2), Decimal::from(40), true, chrono::Utc::now())
+        .await
+        .expect("record fill");
+
+    // Use up the full $100 limit with resting buys on other tickers
+    for i in 0..4 {
+        let filler = batch_order(&format!("KXTEST-BATCH-NET-FILL-{}", i), Decimal::from(50), Decimal::new(50, 2));
+        ems.enqueue(session_id, &filler, "test").await.expect("enqueue filler");
+    }
+
+    let sell = |quantity: i64| {
+        let mut order = batch_order("KXTEST-BATCH-NET", Decimal::from(quantity), Decimal::new(50, 2));
+        order.action = harman::types::Action::Sell;
+        order
+    };
+
+    // 30 + 20 closes only 40 of the long: the last 10 contracts open exposure
+    let err = ems
+        .enqueue_batch(session_id, &[sell(30), sell(20)], "test")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::MaxNotionalExceeded { .. })
+    ));
+
+    // 30 + 10 is fully covered by the long
+    let outcomes = ems
+        .enqueue_batch(session_id, &[sell(30), sell(10)], "test")
+        .await
+        .expect("closing sells should pass");
+    assert!(outcomes
+        .iter()
+        .all(|o| matches!(o, db::BatchEnqueueOutcome::Enqueued(_))));
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_reduce_only_checked_against_filled_position() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    let reduce = |quantity: i64| {
+        let mut order = batch_order("KXTEST-REDUCE", Decimal::from(quantity), Decimal::new(50, 2));
+        order.action = harman::types::Action::Sell;
+        order.reduce_only = true;
+        order
+    };
+    let is_violation = |err: &harman::error::EnqueueError| {
+        matches!(
+            err,
+            harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::ReduceOnlyViolation { .. })
+        )
+    };
+
+    // No position yet: a reduce-only sell would open a short
+    let err = ems.validate(session_id, &reduce(1)).await.unwrap_err();
+    assert!(is_violation(&err));
+
+    let long = batch_order("KXTEST-REDUCE", Decimal::from(10), Decimal::new(50, 2));
+    let long = ems.enqueue(session_id, &long, "test").await.expect("enqueue long");
+    db::record_fill(&pool, long.id, session_id, "reduce-trade-1", Decimal::new(50, 2), Decimal::from(10), true, chrono::Utc::now())
+        .await
+        .expect("record fill");
+
+    // A partial fill of a reduce-only sell already shrank the position to 6,
+    // and the 4 it still has open are spoken for: 2 are left to close
+    let first = ems.enqueue(session_id, &reduce(8), "test").await.expect("enqueue reduce-only");
+    db::record_fill(&pool, first.id, session_id, "reduce-trade-2", Decimal::new(50, 2), Decimal::from(4), true, chrono::Utc::now())
+        .await
+        .expect("record partial fill");
+    let err = ems.validate(session_id, &reduce(3)).await.unwrap_err();
+    assert!(is_violation(&err));
+    ems.validate(session_id, &reduce(2)).await.expect("closing the rest passes");
+
+    // The flag is stored with the order
+    let stored = db::get_order(&pool, first.id, session_id).await.unwrap().expect("order");
+    assert!(stored.reduce_only);
+
+    // Positions are per side: the long is on yes, so a reduce-only no sell opens
+    let mut no_side = reduce(1);
+    no_side.side = harman::types::Side::No;
+    let err = ems.validate(session_id, &no_side).await.unwrap_err();
+    assert!(is_violation(&err));
+
+    // Within a batch each reduce-only order sees the earlier ones as filled
+    let err = ems
+        .enqueue_batch(session_id, &[reduce(1), reduce(2)], "test")
+        .await
+        .unwrap_err();
+    assert!(is_violation(&err));
+
+    // Amending the open reduce-only order up may only take what is left:
+    // its 6 unfilled contracts close the position, 7 would flip it
+    pool.get()
+        .await
+        .unwrap()
+        .execute("UPDATE prediction_orders SET state = 'partially_filled' WHERE id = $1", &[&first.id])
+        .await
+        .unwrap();
+    let err = db::atomic_amend_order(&pool, first.id, session_id, None, Some(Decimal::from(11)), "test")
+        .await
+        .unwrap_err();
+    assert!(err.contains("cannot amend reduce-only order"), "got: {}", err);
+    db::atomic_amend_order(&pool, first.id, session_id, None, Some(Decimal::from(10)), "test")
+        .await
+        .expect("amend within the position");
+}
+
+#[tokio::test]
+#[ignore] // Requires PostgreSQL — runs in dedicated CI step with --ignored --test-threads=1
+async fn test_ems_enqueue_session_max_open_contracts() {
+    let (pool, session_id) = setup_or_skip!();
+    let ems = build_test_ems(MockExchange::new(), pool.clone()).await;
+
+    // Per-session contract cap; the global default has none
+    pool.get()
+        .await
+        .unwrap()
+        .execute(
+            "UPDATE sessions SET max_open_contracts = 50 WHERE id = $1",
+            &[&session_id],
+        )
+        .await
+        .expect("set session max_open_contracts");
+
+    // 40 contracts at $0.05 is only $2 of notional
+    let first = batch_order("KXTEST-CONTRACTS", Decimal::from(40), Decimal::new(5, 2));
+    ems.enqueue(session_id, &first, "test").await.expect("enqueue under cap");
+
+    let over = batch_order("KXTEST-CONTRACTS", Decimal::from(11), Decimal::new(5, 2));
+    let err = ems.enqueue(session_id, &over, "test").await.unwrap_err();
+    assert!(matches!(
+        err,
+        harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::MaxOpenContractsExceeded { .. })
+    ));
+
+    let batch = vec![
+        batch_order("KXTEST-CONTRACTS-B1", Decimal::from(6), Decimal::new(5, 2)),
+        batch_order("KXTEST-CONTRACTS-B2", Decimal::from(6), Decimal::new(5, 2)),
+    ];
+    let err = ems.enqueue_batch(session_id, &batch, "test").await.unwrap_err();
+    assert!(matches!(
+        err,
+        harman::error::EnqueueError::RiskCheck(harman::error::RiskCheckError::MaxOpenContractsExceeded { .. })
+    ));
+
+    let fits = batch_order("KXTEST-CONTRACTS", Decimal::from(10), Decimal::new(5, 2));
+    ems.enqueue(session_id, &fits, "test").await.expect("enqueue up to cap");
 }
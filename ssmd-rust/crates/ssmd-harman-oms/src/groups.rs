@@ -1,13 +1,60 @@
 use harman::db;
 use harman::error::EnqueueError;
+use harman::events::StreamEvent;
 use harman::state::OrderState;
 use harman::types::{
-    GroupState, GroupType, LegRole, Order, OrderGroup, OrderRequest,
+    GroupState, GroupType, LegRole, LinkMode, Order, OrderGroup, OrderRequest, PegConfig,
 };
 use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 use crate::Oms;
 
+/// Derive the `client_order_id` for leg `leg_index` of a group from the
+/// group's base id.
+///
+/// The id is a UUIDv5 in the base id's namespace named
+/// `"<group_type>:<leg_index>"` (e.g. `bracket:0` for the entry leg). The
+/// same base always yields the same leg ids, so a retried create with the
+/// same base hits the unique constraint instead of creating a second group.
+pub fn derive_leg_client_order_id(base: Uuid, group_type: GroupType, leg_index: usize) -> Uuid {
+    Uuid::new_v5(&base, format!("{}:{}", group_type, leg_index).as_bytes())
+}
+
+/// Fill in leg `client_order_id`s left as the nil UUID.
+///
+/// Either every leg supplies its own id (and `base` is absent) or every leg
+/// is nil and ids are derived from `base` with [`derive_leg_client_order_id`],
+/// in leg order.
+pub fn assign_leg_client_order_ids(
+    base: Option<Uuid>,
+    group_type: GroupType,
+    legs: &mut [OrderRequest],
+) -> Result<(), String> {
+    let nil_legs = legs.iter().filter(|l| l.client_order_id.is_nil()).count();
+    if nil_legs == 0 {
+        return match base {
+            Some(_) => Err("base_client_order_id requires nil leg client_order_ids".to_string()),
+            None => Ok(()),
+        };
+    }
+    if nil_legs != legs.len() {
+        return Err("either all legs supply client_order_id or all are nil".to_string());
+    }
+    let base = match base {
+        Some(base) if !base.is_nil() => base,
+        _ => {
+            return Err(
+                "base_client_order_id is required when leg client_order_ids are nil".to_string(),
+            )
+        }
+    };
+    for (i, leg) in legs.iter_mut().enumerate() {
+        leg.client_order_id = derive_leg_client_order_id(base, group_type, i);
+    }
+    Ok(())
+}
+
 impl Oms {
     /// Create a bracket order group: entry + take_profit + stop_loss.
     ///
@@ -32,6 +79,7 @@ impl Oms {
             GroupType::Bracket,
             &legs,
             &self.ems.risk_limits,
+            None,
         )
         .await
     }
@@ -39,12 +87,14 @@ impl Oms {
     /// Create an OCO (one-cancels-other) order group.
     ///
     /// Both legs are queued immediately (Pending). When one fills,
-    /// the other is cancelled by trigger evaluation.
+    /// the other is cancelled by trigger evaluation. With `peg`, leg2 is
+    /// repriced to follow leg1 while both rest.
     pub async fn create_oco(
         &self,
         session_id: i64,
         leg1: OrderRequest,
         leg2: OrderRequest,
+        peg: Option<PegConfig>,
     ) -> Result<(OrderGroup, Vec<Order>), EnqueueError> {
         let legs = vec![
             (leg1, LegRole::OcoLeg, OrderState::Pending),
@@ -57,13 +107,15 @@ impl Oms {
             GroupType::Oco,
             &legs,
             &self.ems.risk_limits,
+            peg,
         )
         .await
     }
 
     /// Evaluate group triggers after a pump cycle.
     ///
-    /// Returns the number of orders activated. Caller should re-pump if > 0.
+    /// Returns the number of orders activated or repriced. Caller should
+    /// re-pump if > 0.
     pub async fn evaluate_triggers(&self, session_id: i64) -> Result<u32, String> {
         let groups = db::get_groups_needing_evaluation(&self.pool, session_id).await?;
         let mut activated = 0u32;
@@ -92,8 +144,7 @@ impl Oms {
         for order in &orders {
             if order.state == OrderState::Staged {
                 // Staged → Cancelled directly (no exchange involvement)
-                db::update_order_state(
-                    &self.pool,
+                self.ems.update_order_state(
                     order.id,
                     session_id,
                     OrderState::Cancelled,
@@ -110,17 +161,37 @@ impl Oms {
                         order.id,
                         session_id,
                         &harman::types::CancelReason::UserRequested,
+                        "group_cancel",
                     )
                     .await;
             }
             // Terminal orders are left as-is
         }
 
-        db::update_group_state(&self.pool, group_id, GroupState::Cancelled).await?;
+        self.set_group_state(group_id, session_id, GroupState::Cancelled).await?;
         info!(group_id, "group cancelled");
         Ok(())
     }
 
+    /// Update a group's state, publishing `group_completed` once it reaches a
+    /// terminal state.
+    async fn set_group_state(
+        &self,
+        group_id: i64,
+        session_id: i64,
+        state: GroupState,
+    ) -> Result<(), String> {
+        db::update_group_state(&self.pool, group_id, state).await?;
+        if state != GroupState::Active {
+            self.ems.events.publish(StreamEvent::GroupCompleted {
+                session_id,
+                group_id,
+                state,
+            });
+        }
+        Ok(())
+    }
+
     /// Evaluate a bracket group's triggers.
     async fn evaluate_bracket(
         &self,
@@ -163,8 +234,7 @@ impl Oms {
                 // Cancel the other exit leg(s) and complete the group
                 for exit in &exits {
                     if exit.state == OrderState::Staged {
-                        db::update_order_state(
-                            &self.pool,
+                        self.ems.update_order_state(
                             exit.id,
                             session_id,
                             OrderState::Cancelled,
@@ -180,19 +250,19 @@ impl Oms {
                                 exit.id,
                                 session_id,
                                 &harman::types::CancelReason::UserRequested,
+                                "trigger",
                             )
                             .await;
                     }
                 }
-                db::update_group_state(&self.pool, group.id, GroupState::Completed).await?;
+                self.set_group_state(group.id, group.session_id, GroupState::Completed).await?;
                 info!(group_id = group.id, "bracket group completed (exit filled)");
             }
         } else if entry.state.is_terminal() {
             // Entry rejected/cancelled/expired → cancel staged exit legs, mark group cancelled
             for exit in &exits {
                 if exit.state == OrderState::Staged {
-                    db::update_order_state(
-                        &self.pool,
+                    self.ems.update_order_state(
                         exit.id,
                         session_id,
                         OrderState::Cancelled,
@@ -203,7 +273,7 @@ impl Oms {
                     .await?;
                 }
             }
-            db::update_group_state(&self.pool, group.id, GroupState::Cancelled).await?;
+            self.set_group_state(group.id, group.session_id, GroupState::Cancelled).await?;
             info!(group_id = group.id, "bracket group cancelled (entry terminal)");
         }
 
@@ -232,18 +302,68 @@ impl Oms {
                             order.id,
                             session_id,
                             &harman::types::CancelReason::UserRequested,
+                            "trigger",
                         )
                         .await;
                 }
             }
-            db::update_group_state(&self.pool, group.id, GroupState::Completed).await?;
+            self.set_group_state(group.id, group.session_id, GroupState::Completed).await?;
             info!(group_id = group.id, "OCO group completed (leg filled)");
         }
 
         // Check if all legs are terminal → finalize
         self.maybe_finalize_group(group, orders).await?;
 
-        Ok(0) // OCO never activates staged orders
+        // OCO never activates staged orders; peg groups may reprice leg2
+        if group.link_mode == LinkMode::Peg && filled_leg.is_none() {
+            return Ok(self.reprice_peg(group, orders, session_id).await);
+        }
+        Ok(0)
+    }
+
+    /// Keep a peg group's leg2 at leg1's price plus the configured spread.
+    ///
+    /// Only acts while both legs rest on the exchange with no amend, decrease
+    /// or cancel in flight, so each leg1 amend produces at most one leg2 amend
+    /// and drift under `min_reprice_dollars` is ignored. Returns 1 if an amend
+    /// was enqueued.
+    async fn reprice_peg(&self, group: &OrderGroup, orders: &[Order], session_id: i64) -> u32 {
+        let (Some(peg), [leg1, leg2]) = (group.peg, orders) else {
+            return 0;
+        };
+        let resting = |o: &Order| {
+            matches!(
+                o.state,
+                OrderState::Acknowledged | OrderState::PartiallyFilled
+            )
+        };
+        if !resting(leg1) || !resting(leg2) {
+            return 0;
+        }
+        let Some(target) = peg.reprice_target(leg1.price_dollars, leg2.price_dollars) else {
+            return 0;
+        };
+
+        match self
+            .ems
+            .enqueue_amend(leg2.id, session_id, Some(target), None, "peg")
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    group_id = group.id,
+                    order_id = leg2.id,
+                    from = %leg2.price_dollars,
+                    to = %target,
+                    "peg reprice enqueued"
+                );
+                1
+            }
+            Err(e) => {
+                warn!(group_id = group.id, order_id = leg2.id, error = %e, "peg reprice failed");
+                0
+            }
+        }
     }
 
     /// If all legs are terminal, finalize the group state.
@@ -269,8 +389,67 @@ impl Oms {
             GroupState::Cancelled
         };
 
-        db::update_group_state(&self.pool, group.id, final_state).await?;
+        self.set_group_state(group.id, group.session_id, final_state).await?;
         debug!(group_id = group.id, state = %final_state, "group finalized");
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harman::types::{Action, OrderType, Side, TimeInForce};
+    use rust_decimal::Decimal;
+
+    fn leg(client_order_id: Uuid) -> OrderRequest {
+        OrderRequest {
+            client_order_id,
+            ticker: "KXTEST-26".to_string(),
+            side: Side::Yes,
+            action: Action::Buy,
+            quantity: Decimal::ONE,
+            price_dollars: Decimal::new(50, 2),
+            time_in_force: TimeInForce::Gtc,
+            order_type: OrderType::Limit,
+            trigger_price: None,
+            good_till: None,
+            reduce_only: false,
+        }
+    }
+
+    #[test]
+    fn test_derive_leg_client_order_id_is_deterministic() {
+        let base = Uuid::new_v4();
+        let a = derive_leg_client_order_id(base, GroupType::Bracket, 0);
+        assert_eq!(a, derive_leg_client_order_id(base, GroupType::Bracket, 0));
+        assert_ne!(a, derive_leg_client_order_id(base, GroupType::Bracket, 1));
+        assert_ne!(a, derive_leg_client_order_id(base, GroupType::Oco, 0));
+        assert_ne!(a, derive_leg_client_order_id(Uuid::new_v4(), GroupType::Bracket, 0));
+    }
+
+    #[test]
+    fn test_assign_derives_nil_legs() {
+        let base = Uuid::new_v4();
+        let mut legs = [leg(Uuid::nil()), leg(Uuid::nil())];
+        assign_leg_client_order_ids(Some(base), GroupType::Oco, &mut legs).unwrap();
+        assert_eq!(legs[0].client_order_id, derive_leg_client_order_id(base, GroupType::Oco, 0));
+        assert_eq!(legs[1].client_order_id, derive_leg_client_order_id(base, GroupType::Oco, 1));
+    }
+
+    #[test]
+    fn test_assign_validation() {
+        let explicit = Uuid::new_v4();
+        let mut legs = [leg(explicit), leg(Uuid::new_v4())];
+        assign_leg_client_order_ids(None, GroupType::Oco, &mut legs).unwrap();
+        assert_eq!(legs[0].client_order_id, explicit);
+
+        let mut legs = [leg(explicit), leg(Uuid::new_v4())];
+        assert!(assign_leg_client_order_ids(Some(Uuid::new_v4()), GroupType::Oco, &mut legs).is_err());
+
+        let mut legs = [leg(explicit), leg(Uuid::nil())];
+        assert!(assign_leg_client_order_ids(Some(Uuid::new_v4()), GroupType::Oco, &mut legs).is_err());
+
+        let mut legs = [leg(Uuid::nil()), leg(Uuid::nil())];
+        assert!(assign_leg_client_order_ids(None, GroupType::Oco, &mut legs).is_err());
+    }
+}
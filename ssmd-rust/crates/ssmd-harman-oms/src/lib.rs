@@ -17,7 +17,7 @@ use harman::exchange::ExchangeAdapter;
 use ssmd_harman_ems::Ems;
 
 use crate::positions::PositionsView;
-use crate::reconciliation::ReconcileResult;
+use crate::reconciliation::{MismatchThresholds, ReconcileResult, ReconcileStatus};
 
 /// OMS metrics -- reconciliation and position-tracking counters.
 /// EMS metrics (orders_dequeued, orders_submitted, etc.) are in EmsMetrics.
@@ -29,6 +29,7 @@ pub struct OmsMetrics {
     pub reconciliation_fills_discovered: prometheus::IntCounter,
     pub reconciliation_settlements_discovered: prometheus::IntCounter,
     pub fills_external_imported: prometheus::IntCounter,
+    pub reconciliation_orphan_orders_imported: prometheus::IntCounter,
 }
 
 impl OmsMetrics {
@@ -69,6 +70,11 @@ impl OmsMetrics {
             "External fills imported as synthetic orders",
         )
         .unwrap();
+        let reconciliation_orphan_orders_imported = prometheus::IntCounter::new(
+            "harman_reconciliation_orphan_orders_imported_total",
+            "Resting exchange orders unknown to harman, imported during reconciliation",
+        )
+        .unwrap();
 
         registry.register(Box::new(reconciliation_ok.clone())).unwrap();
         registry.register(Box::new(reconciliation_mismatch.clone())).unwrap();
@@ -77,6 +83,7 @@ impl OmsMetrics {
         registry.register(Box::new(reconciliation_fills_discovered.clone())).unwrap();
         registry.register(Box::new(reconciliation_settlements_discovered.clone())).unwrap();
         registry.register(Box::new(fills_external_imported.clone())).unwrap();
+        registry.register(Box::new(reconciliation_orphan_orders_imported.clone())).unwrap();
 
         Self {
             reconciliation_ok,
@@ -86,6 +93,7 @@ impl OmsMetrics {
             reconciliation_fills_discovered,
             reconciliation_settlements_discovered,
             fills_external_imported,
+            reconciliation_orphan_orders_imported,
         }
     }
 }
@@ -101,6 +109,10 @@ pub struct Oms {
     pub metrics: Arc<OmsMetrics>,
     pub audit: AuditSender,
     pub suspended_sessions: DashMap<i64, ()>,
+    /// Latest reconcile outcome per session (manual or auto)
+    pub last_reconcile: DashMap<i64, ReconcileStatus>,
+    /// Position mismatch severity thresholds used by reconciliation
+    pub mismatch_thresholds: MismatchThresholds,
 }
 
 impl Oms {
@@ -118,9 +130,16 @@ impl Oms {
             metrics,
             audit,
             suspended_sessions: DashMap::new(),
+            last_reconcile: DashMap::new(),
+            mismatch_thresholds: MismatchThresholds::default(),
         }
     }
 
+    pub fn with_mismatch_thresholds(mut self, thresholds: MismatchThresholds) -> Self {
+        self.mismatch_thresholds = thresholds;
+        self
+    }
+
     pub fn is_suspended(&self, session_id: i64) -> bool {
         self.suspended_sessions.contains_key(&session_id)
     }
@@ -133,6 +152,10 @@ impl Oms {
         reconciliation::reconcile(self, session_id).await
     }
 
+    pub fn reconcile_status(&self, session_id: i64) -> Option<ReconcileStatus> {
+        self.last_reconcile.get(&session_id).map(|s| s.clone())
+    }
+
     pub async fn run_recovery(&self, session_id: i64) -> Result<(), String> {
         recovery::run(self, session_id).await
     }
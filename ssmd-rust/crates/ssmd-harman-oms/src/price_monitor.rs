@@ -256,6 +256,7 @@ impl PriceMonitor {
                             order.id,
                             session_id,
                             &CancelReason::UserRequested,
+                            "price_monitor",
                         )
                         .await
                         {
@@ -1,9 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 use harman::db;
+use harman::events::StreamEvent;
 use harman::fill_processor;
 use harman::order_importer;
 use harman::settlement_recorder;
@@ -15,14 +17,96 @@ use crate::Oms;
 
 const STALE_THRESHOLD: Duration = Duration::from_secs(30);
 
-/// Thresholds for position mismatch severity.
-const LARGE_MISMATCH_CONTRACTS: i64 = 1;
-const LARGE_MISMATCH_NOTIONAL: &str = "10"; // dollars
+/// Severity of a position mismatch, used as the `severity` metric label
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MismatchSeverity {
+    Minor,
+    Major,
+    Critical,
+}
+
+impl MismatchSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MismatchSeverity::Minor => "minor",
+            MismatchSeverity::Major => "major",
+            MismatchSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// Position delta thresholds that map a mismatch to a severity.
+///
+/// A mismatch is `critical` if its contract delta or its notional delta (in
+/// dollars) is above the critical threshold, otherwise `major` if either is
+/// above the major threshold, otherwise `minor`. When `suspend_on_critical`
+/// is set, a critical mismatch suspends the session until an admin resumes
+/// it; otherwise mismatches are only reported (they may be external orders).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MismatchThresholds {
+    pub major_contracts: Decimal,
+    pub major_notional: Decimal,
+    pub critical_contracts: Decimal,
+    pub critical_notional: Decimal,
+    pub suspend_on_critical: bool,
+}
+
+impl Default for MismatchThresholds {
+    fn default() -> Self {
+        Self {
+            major_contracts: Decimal::ONE,
+            major_notional: Decimal::from(10),
+            critical_contracts: Decimal::from(10),
+            critical_notional: Decimal::from(100),
+            suspend_on_critical: false,
+        }
+    }
+}
+
+impl MismatchThresholds {
+    /// Reject negative thresholds and critical thresholds below major ones
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("major_contracts", self.major_contracts),
+            ("major_notional", self.major_notional),
+            ("critical_contracts", self.critical_contracts),
+            ("critical_notional", self.critical_notional),
+        ] {
+            if value < Decimal::ZERO {
+                return Err(format!("{} must be >= 0, got {}", name, value));
+            }
+        }
+        if self.critical_contracts < self.major_contracts {
+            return Err(format!(
+                "critical_contracts ({}) must be >= major_contracts ({})",
+                self.critical_contracts, self.major_contracts
+            ));
+        }
+        if self.critical_notional < self.major_notional {
+            return Err(format!(
+                "critical_notional ({}) must be >= major_notional ({})",
+                self.critical_notional, self.major_notional
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn classify(&self, diff_contracts: Decimal, diff_notional: Decimal) -> MismatchSeverity {
+        if diff_contracts > self.critical_contracts || diff_notional > self.critical_notional {
+            MismatchSeverity::Critical
+        } else if diff_contracts > self.major_contracts || diff_notional > self.major_notional {
+            MismatchSeverity::Major
+        } else {
+            MismatchSeverity::Minor
+        }
+    }
+}
 
 #[derive(Debug, Serialize)]
 pub struct ReconcileResult {
     pub settlements_discovered: u64,
     pub fills_discovered: u64,
+    pub orphan_orders_imported: u64,
     pub orders_resolved: u64,
     pub position_mismatches: Vec<PositionMismatch>,
     pub suspended: bool,
@@ -37,6 +121,50 @@ pub struct PositionMismatch {
     pub severity: String,
 }
 
+/// Summary of the most recent reconciliation for a session, kept on `Oms`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileStatus {
+    pub session_id: i64,
+    pub completed_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub settlements_discovered: u64,
+    pub fills_discovered: u64,
+    pub orphan_orders_imported: u64,
+    pub orders_resolved: u64,
+    /// Position mismatch counts keyed by severity ("minor", "major", "critical")
+    pub mismatches_by_severity: BTreeMap<String, u64>,
+    pub suspended: bool,
+    pub errors: Vec<String>,
+}
+
+impl ReconcileStatus {
+    pub fn from_result(
+        session_id: i64,
+        result: &ReconcileResult,
+        completed_at: DateTime<Utc>,
+        duration: Duration,
+    ) -> Self {
+        let mut mismatches_by_severity = BTreeMap::new();
+        for mismatch in &result.position_mismatches {
+            *mismatches_by_severity
+                .entry(mismatch.severity.clone())
+                .or_insert(0) += 1;
+        }
+        Self {
+            session_id,
+            completed_at,
+            duration_ms: duration.as_millis() as u64,
+            settlements_discovered: result.settlements_discovered,
+            fills_discovered: result.fills_discovered,
+            orphan_orders_imported: result.orphan_orders_imported,
+            orders_resolved: result.orders_resolved,
+            mismatches_by_severity,
+            suspended: result.suspended,
+            errors: result.errors.clone(),
+        }
+    }
+}
+
 /// Run one full reconciliation cycle: discover fills, resolve stale orders, compare positions.
 pub async fn reconcile(oms: &Oms, session_id: i64) -> ReconcileResult {
     let start = Instant::now();
@@ -44,6 +172,7 @@ pub async fn reconcile(oms: &Oms, session_id: i64) -> ReconcileResult {
     let mut result = ReconcileResult {
         settlements_discovered: 0,
         fills_discovered: 0,
+        orphan_orders_imported: 0,
         orders_resolved: 0,
         position_mismatches: vec![],
         suspended: false,
@@ -72,8 +201,9 @@ pub async fn reconcile(oms: &Oms, session_id: i64) -> ReconcileResult {
 
     match discover_external_orders(oms, session_id).await {
         Ok(count) => {
+            result.orphan_orders_imported = count;
             if count > 0 {
-                info!(count, "imported external resting orders");
+                warn!(session_id, count, "imported orphaned exchange resting orders");
             }
         }
         Err(e) => {
@@ -128,6 +258,7 @@ pub async fn reconcile(oms: &Oms, session_id: i64) -> ReconcileResult {
     info!(
         settlements_discovered = result.settlements_discovered,
         fills_discovered = result.fills_discovered,
+        orphan_orders_imported = result.orphan_orders_imported,
         orders_resolved = result.orders_resolved,
         mismatches = result.position_mismatches.len(),
         suspended = result.suspended,
@@ -136,6 +267,12 @@ pub async fn reconcile(oms: &Oms, session_id: i64) -> ReconcileResult {
         "reconciliation complete"
     );
 
+    // Manual and auto-reconcile both land here, so the status is always the latest pass
+    oms.last_reconcile.insert(
+        session_id,
+        ReconcileStatus::from_result(session_id, &result, Utc::now(), start.elapsed()),
+    );
+
     result
 }
 
@@ -157,8 +294,9 @@ async fn discover_settlements(oms: &Oms, session_id: i64) -> Result<u64, String>
 
 /// Fetch resting orders from exchange and import any not tracked locally.
 ///
-/// External resting orders (placed via exchange website) are imported as
-/// synthetic orders in 'acknowledged' state into the user's session.
+/// Orphaned resting orders (placed via exchange website, matched on
+/// exchange_order_id) are imported as synthetic orders in 'acknowledged' state
+/// into the user's session; the importer logs each one's ticker/side/qty/price.
 /// With stable sessions, one harman instance = one exchange account.
 async fn discover_external_orders(oms: &Oms, session_id: i64) -> Result<u64, String> {
     let exchange_orders = oms
@@ -182,7 +320,7 @@ async fn discover_external_orders(oms: &Oms, session_id: i64) -> Result<u64, Str
     .await?;
 
     if count > 0 {
-        oms.metrics.fills_external_imported.inc_by(count);
+        oms.metrics.reconciliation_orphan_orders_imported.inc_by(count);
     }
 
     Ok(count)
@@ -217,6 +355,18 @@ async fn discover_fills(oms: &Oms, session_id: i64) -> Result<u64, String> {
     let total = import_result.recorded;
     if total > 0 {
         oms.ems.metrics.fills_recorded.inc_by(total);
+        oms.ems.events.publish(StreamEvent::FillRecorded {
+            session_id,
+            count: total,
+        });
+    }
+    for &order_id in &import_result.newly_filled_order_ids {
+        oms.ems.events.publish(StreamEvent::OrderStateChanged {
+            session_id,
+            order_id,
+            state: OrderState::Filled,
+            actor: "reconciliation".to_string(),
+        });
     }
     if import_result.external_imported > 0 {
         oms.metrics
@@ -254,9 +404,20 @@ async fn compare_positions(
         *exchange_map.entry(pos.ticker.clone()).or_default() += signed;
     }
 
-    // Compute local positions from filled orders in this session
+    // Per-contract value from the exchange, used to price the delta
+    let mut exchange_price: HashMap<String, Decimal> = HashMap::new();
+    for pos in &exchange_positions {
+        if !pos.quantity.is_zero() {
+            let per_contract = (pos.market_value_dollars / pos.quantity).abs();
+            exchange_price.insert(pos.ticker.clone(), per_contract);
+        }
+    }
+
+    // Compute local positions from filled orders in this session, plus the
+    // filled cost so tickers flat on the exchange can still be priced
     let orders = db::list_orders(&oms.pool, session_id, None).await?;
     let mut local_map: HashMap<String, Decimal> = HashMap::new();
+    let mut local_cost: HashMap<String, (Decimal, Decimal)> = HashMap::new();
     for order in &orders {
         if order.filled_quantity <= Decimal::ZERO {
             continue;
@@ -266,6 +427,9 @@ async fn compare_positions(
             Action::Sell => -order.filled_quantity,
         };
         *local_map.entry(order.ticker.clone()).or_default() += signed;
+        let (cost, qty) = local_cost.entry(order.ticker.clone()).or_default();
+        *cost += order.filled_quantity * order.price_dollars;
+        *qty += order.filled_quantity;
     }
 
     // Collect all tickers from both sides
@@ -277,13 +441,9 @@ async fn compare_positions(
     }
     all_tickers.sort();
 
-    let large_threshold_contracts = Decimal::from(LARGE_MISMATCH_CONTRACTS);
-    let large_threshold_notional: Decimal = LARGE_MISMATCH_NOTIONAL
-        .parse()
-        .unwrap_or(Decimal::from(10));
-
+    let thresholds = &oms.mismatch_thresholds;
     let mut mismatches = Vec::new();
-    let mut any_large = false;
+    let mut any_critical = false;
 
     for ticker in &all_tickers {
         // Skip settled tickers — exchange positions disappear after settlement
@@ -301,34 +461,43 @@ async fn compare_positions(
             continue;
         }
 
-        let is_large = diff > large_threshold_contracts || diff > large_threshold_notional;
-        let severity = if is_large { "large" } else { "small" };
+        // Unpriced tickers are classified on contracts alone
+        let price = exchange_price.get(ticker).copied().or_else(|| {
+            local_cost
+                .get(ticker)
+                .filter(|(_, qty)| !qty.is_zero())
+                .map(|(cost, qty)| cost / qty)
+        });
+        let diff_notional = price.map(|p| diff * p).unwrap_or(Decimal::ZERO);
+        let severity = thresholds.classify(diff, diff_notional);
 
         warn!(
             ticker = %ticker,
             local_qty = %local_qty,
             exchange_qty = %exchange_qty,
             diff = %diff,
-            severity,
+            diff_notional = %diff_notional,
+            severity = severity.as_str(),
             "position mismatch detected"
         );
 
         oms.metrics
             .reconciliation_mismatch
-            .with_label_values(&[severity])
+            .with_label_values(&[severity.as_str()])
             .inc();
 
-        if is_large {
-            any_large = true;
+        if severity == MismatchSeverity::Critical {
+            any_critical = true;
         }
 
         oms.audit.reconciliation(
-            session_id, None, "position_mismatch", severity,
+            session_id, None, "position_mismatch", severity.as_str(),
             Some(serde_json::json!({
                 "ticker": ticker,
                 "local_quantity": local_qty.to_string(),
                 "exchange_quantity": exchange_qty.to_string(),
                 "diff": diff.to_string(),
+                "diff_notional": diff_notional.to_string(),
             })),
         );
 
@@ -336,16 +505,29 @@ async fn compare_positions(
             ticker: ticker.clone(),
             local_quantity: local_qty.to_string(),
             exchange_quantity: exchange_qty.to_string(),
-            severity: severity.to_string(),
+            severity: severity.as_str().to_string(),
         });
     }
 
-    if any_large {
-        warn!(
-            session_id,
-            mismatches = mismatches.len(),
-            "position mismatch detected (may be external orders, not suspending)"
-        );
+    if any_critical {
+        if thresholds.suspend_on_critical {
+            oms.suspended_sessions.insert(session_id, ());
+            error!(
+                session_id,
+                mismatches = mismatches.len(),
+                "critical position mismatch, suspending session"
+            );
+            oms.audit.reconciliation(
+                session_id, None, "session_suspended", "critical",
+                Some(serde_json::json!({ "reason": "critical position mismatch" })),
+            );
+        } else {
+            warn!(
+                session_id,
+                mismatches = mismatches.len(),
+                "critical position mismatch (suspend_on_critical off, not suspending)"
+            );
+        }
     }
 
     Ok(mismatches)
@@ -451,8 +633,7 @@ async fn resolve_stale_orders(
                         "reconciliation resolved order"
                     );
 
-                    if let Err(e) = db::update_order_state(
-                        &oms.pool,
+                    if let Err(e) = oms.ems.update_order_state(
                         order.id,
                         session_id,
                         new_state,
@@ -497,8 +678,7 @@ async fn resolve_stale_orders(
                         event_settled,
                         "reconciliation: order not found but event settled, resolving as expired"
                     );
-                    if let Err(e) = db::update_order_state(
-                        &oms.pool,
+                    if let Err(e) = oms.ems.update_order_state(
                         order.id,
                         session_id,
                         OrderState::Cancelled,
@@ -528,8 +708,7 @@ async fn resolve_stale_orders(
                                 cancel_reason = ?cancel_reason,
                                 "reconciliation: order not found and market not active, resolving as expired"
                             );
-                            if let Err(e) = db::update_order_state(
-                                &oms.pool,
+                            if let Err(e) = oms.ems.update_order_state(
                                 order.id,
                                 session_id,
                                 OrderState::Cancelled,
@@ -584,3 +763,72 @@ async fn resolve_stale_orders(
 
     Ok(count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mismatch(severity: &str) -> PositionMismatch {
+        PositionMismatch {
+            ticker: "KXTEST".to_string(),
+            local_quantity: "0".to_string(),
+            exchange_quantity: "1".to_string(),
+            severity: severity.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_status_counts_mismatches_by_severity() {
+        let result = ReconcileResult {
+            settlements_discovered: 0,
+            fills_discovered: 3,
+            orphan_orders_imported: 0,
+            orders_resolved: 1,
+            position_mismatches: vec![mismatch("major"), mismatch("minor"), mismatch("major")],
+            suspended: true,
+            errors: vec![],
+        };
+        let status =
+            ReconcileStatus::from_result(7, &result, Utc::now(), Duration::from_millis(1500));
+        assert_eq!(status.session_id, 7);
+        assert_eq!(status.duration_ms, 1500);
+        assert_eq!(status.fills_discovered, 3);
+        assert_eq!(status.mismatches_by_severity.get("major"), Some(&2));
+        assert_eq!(status.mismatches_by_severity.get("minor"), Some(&1));
+        assert!(status.suspended);
+    }
+
+    #[test]
+    fn test_mismatch_thresholds_classify() {
+        let t = MismatchThresholds::default();
+        assert_eq!(t.classify(Decimal::ONE, Decimal::new(50, 2)), MismatchSeverity::Minor);
+        assert_eq!(t.classify(Decimal::from(2), Decimal::ONE), MismatchSeverity::Major);
+        // Notional alone can raise the severity
+        assert_eq!(t.classify(Decimal::ONE, Decimal::from(11)), MismatchSeverity::Major);
+        assert_eq!(t.classify(Decimal::ONE, Decimal::from(101)), MismatchSeverity::Critical);
+        assert_eq!(t.classify(Decimal::from(11), Decimal::ZERO), MismatchSeverity::Critical);
+
+        let strict = MismatchThresholds {
+            major_contracts: Decimal::ZERO,
+            critical_contracts: Decimal::ONE,
+            ..MismatchThresholds::default()
+        };
+        assert_eq!(strict.classify(Decimal::ONE, Decimal::ZERO), MismatchSeverity::Major);
+        assert_eq!(strict.classify(Decimal::from(2), Decimal::ZERO), MismatchSeverity::Critical);
+    }
+
+    #[test]
+    fn test_mismatch_thresholds_validate() {
+        assert!(MismatchThresholds::default().validate().is_ok());
+        let inverted = MismatchThresholds {
+            critical_notional: Decimal::from(5),
+            ..MismatchThresholds::default()
+        };
+        assert!(inverted.validate().is_err());
+        let negative = MismatchThresholds {
+            major_contracts: Decimal::from(-1),
+            ..MismatchThresholds::default()
+        };
+        assert!(negative.validate().is_err());
+    }
+}
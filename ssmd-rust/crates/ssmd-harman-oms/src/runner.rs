@@ -5,21 +5,39 @@ use dashmap::DashMap;
 use dashmap::DashSet;
 use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
+use chrono::Utc;
+use harman::db;
+use harman::events::StreamEvent;
 use harman::exchange::EventStream;
+use harman::state::OrderState;
 
 use crate::Oms;
 use crate::event_ingester::EventIngester;
 use crate::price_monitor::PriceMonitorHandle;
 
+/// Default number of sessions reconciled concurrently.
+pub const DEFAULT_RECONCILE_CONCURRENCY: usize = 4;
+
+/// How often resting orders are checked against their `good_till`.
+pub const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Background task coordinator for auto-pump, auto-reconcile, WS event ingestion,
-/// and price monitoring.
+/// order expiry, and price monitoring.
 pub struct OmsRunner {
     oms: Arc<Oms>,
     pump_trigger: PumpTrigger,
     reconcile_interval: Option<Duration>,
+    /// Max sessions reconciled at once
+    reconcile_concurrency: usize,
+    /// Sessions with a reconcile still in flight (from this or a prior pass)
+    reconciling: Arc<DashSet<i64>>,
     startup_session_id: i64,
+    /// Exchange and environment this instance serves; background loops only
+    /// touch sessions that match
+    exchange_type: String,
+    environment: String,
     shutdown: CancellationToken,
     /// Optional WS event stream for real-time events.
     event_stream: Option<Arc<dyn EventStream>>,
@@ -61,6 +79,8 @@ impl OmsRunner {
         oms: Arc<Oms>,
         reconcile_interval: Option<Duration>,
         startup_session_id: i64,
+        exchange_type: &str,
+        environment: &str,
         event_stream: Option<Arc<dyn EventStream>>,
         price_monitor_handle: Option<PriceMonitorHandle>,
     ) -> Self {
@@ -68,16 +88,21 @@ impl OmsRunner {
             oms,
             reconcile_interval,
             startup_session_id,
+            exchange_type,
+            environment,
             event_stream,
             price_monitor_handle,
             PumpTrigger::new(),
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_pump_trigger(
         oms: Arc<Oms>,
         reconcile_interval: Option<Duration>,
         startup_session_id: i64,
+        exchange_type: &str,
+        environment: &str,
         event_stream: Option<Arc<dyn EventStream>>,
         price_monitor_handle: Option<PriceMonitorHandle>,
         pump_trigger: PumpTrigger,
@@ -86,13 +111,23 @@ impl OmsRunner {
             oms,
             pump_trigger,
             reconcile_interval,
+            reconcile_concurrency: DEFAULT_RECONCILE_CONCURRENCY,
+            reconciling: Arc::new(DashSet::new()),
             startup_session_id,
+            exchange_type: exchange_type.to_string(),
+            environment: environment.to_string(),
             shutdown: CancellationToken::new(),
             event_stream,
             price_monitor_handle,
         }
     }
 
+    /// Set how many sessions may be reconciled concurrently (minimum 1).
+    pub fn with_reconcile_concurrency(mut self, concurrency: usize) -> Self {
+        self.reconcile_concurrency = concurrency.max(1);
+        self
+    }
+
     pub fn pump_trigger(&self) -> PumpTrigger {
         self.pump_trigger.clone()
     }
@@ -112,6 +147,7 @@ impl OmsRunner {
             () = self.auto_pump_loop(session_semaphores) => {}
             () = self.auto_reconcile_loop() => {}
             () = self.ws_event_loop() => {}
+            () = self.expiry_sweep_loop() => {}
             () = self.shutdown.cancelled() => {
                 info!("OMS runner shutting down");
             }
@@ -161,7 +197,13 @@ impl OmsRunner {
         }
     }
 
-    /// Reconcile the startup session on a configurable interval.
+    /// Reconcile active sessions on a configurable interval.
+    ///
+    /// Each pass reconciles the startup session plus every session with open
+    /// orders, at most `reconcile_concurrency` at a time. A session whose
+    /// previous reconcile is still running is skipped for this pass. Passes do
+    /// not wait for each other, and one session's failure does not affect the
+    /// rest.
     ///
     /// When WS is enabled, reconciliation is disabled entirely — the WS event
     /// ingester handles all live state updates. Recovery on startup handles
@@ -185,23 +227,90 @@ impl OmsRunner {
             }
         };
 
+        let semaphore = Arc::new(Semaphore::new(self.reconcile_concurrency));
         loop {
             tokio::time::sleep(interval).await;
 
+            let mut sessions = match db::list_active_session_ids(
+                &self.oms.pool,
+                &self.exchange_type,
+                &self.environment,
+            )
+            .await {
+                Ok(ids) => ids,
+                Err(e) => {
+                    error!(error = %e, "failed to list active sessions, reconciling startup session only");
+                    Vec::new()
+                }
+            };
+            if !sessions.contains(&self.startup_session_id) {
+                sessions.push(self.startup_session_id);
+            }
+
             info!(
-                session_id = self.startup_session_id,
+                sessions = sessions.len(),
+                concurrency = self.reconcile_concurrency,
                 interval_secs = interval.as_secs(),
                 "auto-reconcile starting"
             );
-            let result = self.oms.reconcile(self.startup_session_id).await;
-            if !result.errors.is_empty() {
-                warn!(
-                    session_id = self.startup_session_id,
-                    errors = ?result.errors,
-                    "reconciliation errors"
-                );
+            for session_id in sessions {
+                if !self.reconciling.insert(session_id) {
+                    warn!(session_id, "skipping reconcile: previous pass still running");
+                    continue;
+                }
+                let oms = self.oms.clone();
+                let semaphore = semaphore.clone();
+                let guard = InFlight {
+                    set: self.reconciling.clone(),
+                    session_id,
+                };
+                tokio::spawn(async move {
+                    let _guard = guard;
+                    let Ok(_permit) = semaphore.acquire().await else {
+                        return;
+                    };
+                    // reconcile() records its own duration in reconciliation_duration
+                    let result = oms.reconcile(session_id).await;
+                    if result.errors.is_empty() {
+                        info!(session_id, "auto-reconcile complete");
+                    } else {
+                        warn!(session_id, errors = ?result.errors, "reconciliation errors");
+                    }
+                });
+            }
+        }
+    }
+
+    /// Cancel resting orders whose `good_till` has passed, every
+    /// `EXPIRY_SWEEP_INTERVAL`. Expired orders move to PendingCancel and their
+    /// sessions are pumped so the cancels reach the exchange.
+    async fn expiry_sweep_loop(&self) {
+        loop {
+            tokio::time::sleep(EXPIRY_SWEEP_INTERVAL).await;
+
+            let expired = match db::expire_orders(
+                &self.oms.pool,
+                Utc::now(),
+                &self.exchange_type,
+                &self.environment,
+            )
+            .await
+            {
+                Ok(expired) => expired,
+                Err(e) => {
+                    error!(error = %e, "expiry sweep failed");
+                    continue;
+                }
+            };
+            for order in expired {
+                self.oms.ems.events.publish(StreamEvent::OrderStateChanged {
+                    session_id: order.session_id,
+                    order_id: order.order_id,
+                    state: OrderState::PendingCancel,
+                    actor: "expiry".to_string(),
+                });
+                self.pump_trigger.notify(order.session_id);
             }
-            info!(session_id = self.startup_session_id, "auto-reconcile complete");
         }
     }
 
@@ -239,3 +348,51 @@ impl OmsRunner {
         );
     }
 }
+
+/// Removes a session from the in-flight reconcile set when dropped, including
+/// when the reconcile task panics.
+struct InFlight {
+    set: Arc<DashSet<i64>>,
+    session_id: i64,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.set.remove(&self.session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_flight_guard_releases_session() {
+        let set = Arc::new(DashSet::new());
+        assert!(set.insert(7));
+        let guard = InFlight {
+            set: set.clone(),
+            session_id: 7,
+        };
+        // A second pass sees the session as busy
+        assert!(!set.insert(7));
+        drop(guard);
+        assert!(set.insert(7));
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_guard_releases_on_panic() {
+        let set = Arc::new(DashSet::new());
+        set.insert(9);
+        let guard = InFlight {
+            set: set.clone(),
+            session_id: 9,
+        };
+        let handle = tokio::spawn(async move {
+            let _guard = guard;
+            panic!("reconcile blew up");
+        });
+        assert!(handle.await.is_err());
+        assert!(!set.contains(&9));
+    }
+}
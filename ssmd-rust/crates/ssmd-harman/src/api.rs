@@ -1,6 +1,9 @@
 use axum::{
-    extract::{Path, Query, Request, State},
-    http::{HeaderMap, StatusCode},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, Request, State,
+    },
+    http::{HeaderMap, HeaderValue, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{delete, get, post, put},
@@ -13,12 +16,19 @@ use std::sync::Arc;
 use std::time::Duration;
 use subtle::ConstantTimeEq;
 use tokio::sync::Semaphore;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use harman::db;
+use harman::position_history;
 use harman::error::EnqueueError;
+use harman::rate_limit::RateLimiter;
 use harman::state::OrderState;
-use harman::types::{Action, GroupState, Order, OrderGroup, OrderRequest, OrderType, Side, TimeInForce};
+use harman::types::{
+    Action, GroupState, GroupType, LinkMode, Order, OrderGroup, OrderRequest, OrderType,
+    PegConfig, Side, TimeInForce,
+};
+use ssmd_harman_oms::groups::assign_leg_client_order_ids;
 
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
@@ -35,9 +45,12 @@ fn extract_bearer(req: &Request) -> Option<&str> {
         .and_then(|v| v.strip_prefix("Bearer "))
 }
 
-/// Compute a full SHA256 hex string for a token (used as auth cache key)
-fn token_cache_key(token: &str) -> String {
-    let hash = Sha256::digest(token.as_bytes());
+/// Compute a salted SHA256 hex string for a token (used as auth cache key)
+fn token_cache_key(salt: &[u8], token: &str) -> String {
+    let hash = Sha256::new()
+        .chain_update(salt)
+        .chain_update(token.as_bytes())
+        .finalize();
     hash.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
@@ -70,6 +83,49 @@ fn require_scope(ctx: &SessionContext, scope: &str) -> Result<(), StatusCode> {
     }
 }
 
+/// Enforce the per-session order rate limit, returning 429 with Retry-After if exceeded.
+///
+/// Each request costs `orders` tokens, so a batch is charged per order. The
+/// bucket for a session is created on first use from the session's
+/// `order_rate_limit` override (or the global default), so an override change
+/// takes effect after `POST /v1/admin/cache/invalidate` or a restart. A global
+/// default of 0 disables limiting only for sessions without an override.
+async fn check_rate_limit(state: &AppState, session_id: i64, orders: u32) -> Result<(), Response> {
+    if !state.rate_limiters.contains_key(&session_id) {
+        let rate = match db::get_session_order_rate_limit(&state.pool, session_id).await {
+            Ok(Some(rate)) => rate,
+            Ok(None) => state.order_rate_limit,
+            Err(e) => {
+                tracing::warn!(session_id, error = %e, "rate limit lookup failed, using global");
+                state.order_rate_limit
+            }
+        };
+        state
+            .rate_limiters
+            .entry(session_id)
+            .or_insert_with(|| (rate > 0).then(|| RateLimiter::new(rate)));
+    }
+
+    let result = match state.rate_limiters.get_mut(&session_id) {
+        Some(mut entry) => match entry.value_mut() {
+            Some(limiter) => limiter.try_acquire_n(orders),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    };
+
+    result.map_err(|retry_after| {
+        let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        tracing::warn!(session_id, retry_after_secs = secs, "order rate limit exceeded");
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, secs.to_string())],
+            Json(serde_json::json!({"error": "rate limit exceeded"})),
+        )
+            .into_response()
+    })
+}
+
 /// Response from data-ts /v1/auth/validate
 #[derive(Deserialize)]
 struct ValidateResponse {
@@ -177,15 +233,25 @@ async fn get_or_refresh_jwks(
         StatusCode::BAD_GATEWAY
     })?;
 
-    let keys: Vec<crate::CfJwk> = jwks
-        .keys
-        .into_iter()
-        .map(|k| crate::CfJwk {
-            kid: k.kid,
-            n: k.n,
-            e: k.e,
-        })
-        .collect();
+    let mut keys = std::collections::HashMap::with_capacity(jwks.keys.len());
+    for k in jwks.keys {
+        match build_decoding_key(&k.n, &k.e) {
+            Ok(decoding_key) => {
+                keys.insert(
+                    k.kid.clone(),
+                    crate::CfJwk {
+                        kid: k.kid,
+                        n: k.n,
+                        e: k.e,
+                        decoding_key,
+                    },
+                );
+            }
+            Err(e) => {
+                tracing::warn!(kid = %k.kid, error = %e, "skipping unusable JWKS key");
+            }
+        }
+    }
 
     tracing::info!(key_count = keys.len(), "JWKS refreshed");
     let mut cache = state.cf_jwks.write().await;
@@ -193,6 +259,20 @@ async fn get_or_refresh_jwks(
     Ok(())
 }
 
+/// Build an RSA decoding key from base64url-encoded JWK modulus/exponent.
+fn build_decoding_key(n: &str, e: &str) -> Result<DecodingKey, String> {
+    let n_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(n)
+        .map_err(|err| format!("invalid modulus encoding: {}", err))?;
+    let e_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(e)
+        .map_err(|err| format!("invalid exponent encoding: {}", err))?;
+    if n_bytes.is_empty() || e_bytes.is_empty() {
+        return Err("empty modulus or exponent".to_string());
+    }
+    Ok(DecodingKey::from_rsa_raw_components(&n_bytes, &e_bytes))
+}
+
 /// Validate CF Access JWT and return email
 async fn validate_cf_jwt(state: &AppState, token: &str) -> Result<String, StatusCode> {
     let cf_aud = state.cf_aud.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -215,15 +295,7 @@ async fn validate_cf_jwt(state: &AppState, token: &str) -> Result<String, Status
     let decoding_key = {
         let cache = state.cf_jwks.read().await;
         let keys = cache.as_ref().map(|(_, k)| k).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-        keys.iter().find(|k| k.kid == kid).map(|k| {
-            let n_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-                .decode(&k.n)
-                .unwrap_or_default();
-            let e_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-                .decode(&k.e)
-                .unwrap_or_default();
-            DecodingKey::from_rsa_raw_components(&n_bytes, &e_bytes)
-        })
+        keys.get(&kid).map(|k| k.decoding_key.clone())
     };
 
     let decoding_key = match decoding_key {
@@ -234,17 +306,8 @@ async fn validate_cf_jwt(state: &AppState, token: &str) -> Result<String, Status
             get_or_refresh_jwks(state, true).await?;
             let cache = state.cf_jwks.read().await;
             let keys = cache.as_ref().map(|(_, k)| k).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-            keys.iter()
-                .find(|k| k.kid == kid)
-                .map(|k| {
-                    let n_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-                        .decode(&k.n)
-                        .unwrap_or_default();
-                    let e_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-                        .decode(&k.e)
-                        .unwrap_or_default();
-                    DecodingKey::from_rsa_raw_components(&n_bytes, &e_bytes)
-                })
+            keys.get(&kid)
+                .map(|k| k.decoding_key.clone())
                 .ok_or_else(|| {
                     tracing::warn!(kid = %kid, "kid not found after JWKS refresh");
                     StatusCode::UNAUTHORIZED
@@ -272,11 +335,11 @@ async fn validate_cf_jwt(state: &AppState, token: &str) -> Result<String, Status
 async fn lookup_email(state: &AppState, email: &str) -> Result<(String, Vec<String>), StatusCode> {
     let cache_key = format!("cf:{}", email);
 
-    // Check cache (30s TTL)
+    // Check cache
     {
         let mut cache = state.auth_cache.write().await;
         if let Some(cached) = cache.get(&cache_key) {
-            if cached.cached_at.elapsed() < Duration::from_secs(30) {
+            if cached.is_fresh(state.auth_cache_ttl) {
                 return Ok((cached.key_prefix.clone(), cached.scopes.clone()));
             }
         }
@@ -395,12 +458,12 @@ async fn auth_middleware(
         .as_ref()
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // Check cache (30s TTL)
-    let cache_key = token_cache_key(token);
+    // Check cache
+    let cache_key = token_cache_key(&state.auth_cache_salt, token);
     {
         let mut cache = state.auth_cache.write().await;
         if let Some(cached) = cache.get(&cache_key) {
-            if cached.cached_at.elapsed() < Duration::from_secs(30) {
+            if cached.is_fresh(state.auth_cache_ttl) {
                 let session_id = resolve_session(&state, &cached.key_prefix)
                     .await
                     .map_err(|e| {
@@ -475,6 +538,197 @@ async fn auth_middleware(
     Ok(next.run(req).await)
 }
 
+/// How long a stored `Idempotency-Key` response can be replayed
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A pending `Idempotency-Key` claim older than this is treated as abandoned
+/// (the process crashed or the request was dropped) and can be taken over.
+/// Handlers only touch the database, so this is far above any real run time.
+pub const IDEMPOTENCY_LEASE: Duration = Duration::from_secs(60);
+
+/// Max body size buffered for idempotent requests and replay (both are small JSON)
+const IDEMPOTENCY_MAX_BODY: usize = 1024 * 1024;
+
+/// Extract and validate the `Idempotency-Key` header.
+///
+/// Returns `Ok(None)` when absent, `Err` when present but malformed.
+fn extract_idempotency_key(headers: &HeaderMap) -> Result<Option<String>, &'static str> {
+    let Some(value) = headers.get("idempotency-key") else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| "Idempotency-Key must be ASCII")?
+        .trim();
+    if key.is_empty() || key.len() > 255 {
+        return Err("Idempotency-Key must be 1-255 characters");
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// Replays the stored response for a repeated `Idempotency-Key` on mutating
+/// requests, and stores the first successful response. The key is claimed before
+/// the handler runs, so a concurrent duplicate gets 409 instead of applying the
+/// mutation twice; a key reused for a different method, path or body gets 422.
+/// Runs after auth so keys are scoped to the caller's session.
+async fn idempotency_middleware(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    use axum::http::Method;
+
+    if !matches!(*req.method(), Method::POST | Method::PUT | Method::DELETE) {
+        return next.run(req).await;
+    }
+    let key = match extract_idempotency_key(req.headers()) {
+        Ok(Some(key)) => key,
+        Ok(None) => return next.run(req).await,
+        Err(msg) => {
+            return (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": msg}))).into_response();
+        }
+    };
+    let Some(session_id) = req.extensions().get::<SessionContext>().map(|c| c.session_id) else {
+        return next.run(req).await;
+    };
+
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, IDEMPOTENCY_MAX_BODY).await {
+        Ok(body) => body,
+        Err(_) => {
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(serde_json::json!({"error": "request body too large"})),
+            )
+                .into_response();
+        }
+    };
+    let request = db::IdempotencyRequest {
+        method: parts.method.to_string(),
+        path: parts.uri.path().to_string(),
+        request_hash: Sha256::digest(&body).iter().map(|b| format!("{:02x}", b)).collect(),
+    };
+    let req = Request::from_parts(parts, axum::body::Body::from(body));
+
+    match db::claim_idempotency_key(
+        &state.pool,
+        session_id,
+        &key,
+        &request,
+        IDEMPOTENCY_TTL,
+        IDEMPOTENCY_LEASE,
+    )
+    .await
+    {
+        Ok(db::IdempotencyClaim::Claimed) => {}
+        Ok(db::IdempotencyClaim::Mismatch) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({"error": "Idempotency-Key was used for a different request"})),
+            )
+                .into_response();
+        }
+        Ok(db::IdempotencyClaim::Completed(stored)) => {
+            let status = StatusCode::from_u16(stored.status_code).unwrap_or(StatusCode::OK);
+            let mut headers = HeaderMap::new();
+            headers.insert("content-type", "application/json".parse().unwrap());
+            headers.insert("x-idempotent-replay", "true".parse().unwrap());
+            return (status, headers, stored.response_body).into_response();
+        }
+        Ok(db::IdempotencyClaim::InProgress) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "a request with this Idempotency-Key is still in progress"})),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "idempotency claim failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    }
+
+    let response = next.run(req).await;
+    if !response.status().is_success() {
+        // Handlers only refuse with 4xx/5xx before mutating anything (rate
+        // limit, suspended session, validation, conflicts, failures), so the
+        // response isn't stored: release the claim so a retry runs again
+        if let Err(e) = db::release_idempotency_key(&state.pool, session_id, &key).await {
+            tracing::error!(error = %e, "failed to release idempotency key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, IDEMPOTENCY_MAX_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to buffer response for idempotency");
+            if let Err(e) = db::release_idempotency_key(&state.pool, session_id, &key).await {
+                tracing::error!(error = %e, "failed to release idempotency key");
+            }
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    };
+
+    let stored = db::IdempotentResponse {
+        method: request.method,
+        path: request.path,
+        status_code: parts.status.as_u16(),
+        response_body: String::from_utf8_lossy(&bytes).into_owned(),
+        response_hash: Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect(),
+    };
+    if let Err(e) = db::complete_idempotency_key(&state.pool, session_id, &key, &stored).await {
+        // The mutation already happened; return it and let a retry re-apply
+        // rather than masking the result
+        tracing::error!(error = %e, "failed to store idempotency key");
+        if let Err(e) = db::release_idempotency_key(&state.pool, session_id, &key).await {
+            tracing::error!(error = %e, "failed to release idempotency key");
+        }
+    }
+
+    Response::from_parts(parts, axum::body::Body::from(bytes))
+}
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Caller-supplied request ids longer than this are replaced with a fresh one
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Correlate every log line for a request: take the caller's `x-request-id`
+/// (or generate one), run the request inside a span carrying it, and echo it
+/// back on the response.
+async fn request_id_middleware(req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty() && v.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+
+    let mut resp = next.run(req).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        resp.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    resp
+}
+
 /// Build the axum router with unified auth middleware
 pub fn router(state: Arc<AppState>) -> Router {
     let public = Router::new()
@@ -485,6 +739,8 @@ pub fn router(state: Arc<AppState>) -> Router {
     let authenticated = Router::new()
         // harman:write
         .route("/v1/orders", post(create_order))
+        .route("/v1/orders/batch", post(create_order_batch))
+        .route("/v1/orders/validate", post(validate_order_handler))
         .route("/v1/orders/:id", delete(cancel_order))
         .route("/v1/orders/:id/amend", post(amend_order))
         .route("/v1/orders/:id/decrease", post(decrease_order))
@@ -495,12 +751,15 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/v1/me", get(me_handler))
         .route("/v1/orders", get(list_orders))
         .route("/v1/orders/:id", get(get_order))
+        .route("/v1/orders/:id/fills", get(list_order_fills_handler))
         .route("/v1/groups", get(list_groups_handler))
         .route("/v1/groups/:id", get(get_group_handler))
         .route("/v1/fills", get(list_fills_handler))
+        .route("/v1/positions/history", get(position_history_handler))
         .route("/v1/audit", get(list_audit_handler))
         .route("/v1/tickers", get(list_tickers_handler))
         .route("/v1/snap", get(snap_handler))
+        .route("/v1/stream", get(stream_handler))
         // harman:read (monitor)
         .route("/v1/monitor/categories", get(monitor_categories_handler))
         .route("/v1/monitor/series", get(monitor_series_handler))
@@ -518,7 +777,14 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/v1/admin/settlements", get(settlements_handler))
         .route("/v1/admin/sessions/:id/risk", put(update_session_risk_handler))
         .route("/v1/admin/sessions/:id/resume", put(resume_session_handler))
+        .route("/v1/admin/sessions/:id/reconcile-status", get(reconcile_status_handler))
+        .route("/v1/admin/sessions/:id/close", post(close_session_handler))
         .route("/v1/admin/cache/invalidate", post(cache_invalidate_handler))
+        // Layers run bottom-up: auth first, then idempotency (needs SessionContext)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
@@ -526,7 +792,14 @@ pub fn router(state: Arc<AppState>) -> Router {
 
     use axum::http::{Method, header};
     let cors_methods = vec![Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS];
-    let cors_headers = vec![header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT];
+    let cors_headers = vec![
+        header::CONTENT_TYPE,
+        header::AUTHORIZATION,
+        header::ACCEPT,
+        header::HeaderName::from_static("idempotency-key"),
+        header::HeaderName::from_static(REQUEST_ID_HEADER),
+    ];
+    let cors_expose = vec![header::HeaderName::from_static(REQUEST_ID_HEADER)];
 
     let cors = match std::env::var("ALLOWED_ORIGINS") {
         Ok(origins) if !origins.is_empty() => {
@@ -538,16 +811,22 @@ pub fn router(state: Arc<AppState>) -> Router {
                 .allow_origin(AllowOrigin::list(allowed))
                 .allow_methods(cors_methods)
                 .allow_headers(cors_headers)
+                .expose_headers(cors_expose)
                 .allow_credentials(true)
         }
         _ => CorsLayer::new()
             .allow_origin(AllowOrigin::mirror_request())
             .allow_methods(cors_methods)
             .allow_headers(cors_headers)
+            .expose_headers(cors_expose)
             .allow_credentials(true),
     };
 
-    public.merge(authenticated).layer(cors).with_state(state)
+    public
+        .merge(authenticated)
+        .layer(cors)
+        .layer(middleware::from_fn(request_id_middleware))
+        .with_state(state)
 }
 
 /// POST /v1/orders
@@ -559,7 +838,8 @@ pub struct CreateOrderRequest {
     pub action: Action,
     #[serde(with = "rust_decimal::serde::str")]
     pub quantity: Decimal,
-    #[serde(with = "rust_decimal::serde::str")]
+    /// Limit price; ignored for market orders
+    #[serde(default, with = "rust_decimal::serde::str")]
     pub price_dollars: Decimal,
     #[serde(default = "default_tif")]
     pub time_in_force: TimeInForce,
@@ -571,6 +851,13 @@ pub struct CreateOrderRequest {
         with = "rust_decimal::serde::str_option"
     )]
     pub trigger_price: Option<Decimal>,
+    /// Cancel the order if still resting at this time (GTC only; not supported on group legs)
+    #[serde(default)]
+    pub good_till: Option<chrono::DateTime<chrono::Utc>>,
+    /// Reject unless the order only reduces the session's filled net position
+    /// on the ticker (not supported on group legs)
+    #[serde(default)]
+    pub reduce_only: bool,
 }
 
 fn default_tif() -> TimeInForce {
@@ -586,6 +873,10 @@ async fn create_order(
         return e.into_response();
     }
 
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, 1).await {
+        return resp;
+    }
+
     if state.ems.is_shutting_down() {
         return (
             StatusCode::SERVICE_UNAVAILABLE,
@@ -602,42 +893,18 @@ async fn create_order(
             .into_response();
     }
 
-    // Validate
-    if req.ticker.trim().is_empty() {
-        return (
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(serde_json::json!({"error": "ticker is required"})),
-        )
-            .into_response();
-    }
-    if req.quantity <= Decimal::ZERO {
-        return (
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(serde_json::json!({"error": "quantity must be positive"})),
-        )
-            .into_response();
-    }
-    if req.price_dollars <= Decimal::ZERO || req.price_dollars >= Decimal::ONE {
-        return (
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(serde_json::json!({"error": "price_dollars must be between 0 and 1 exclusive"})),
-        )
-            .into_response();
-    }
-
-    let order_req = OrderRequest {
-        client_order_id: req.client_order_id,
-        ticker: req.ticker,
-        side: req.side,
-        action: req.action,
-        quantity: req.quantity,
-        price_dollars: req.price_dollars,
-        time_in_force: req.time_in_force,
-        order_type: OrderType::default(),
-        trigger_price: None,
+    let order_req = match validate_create_order(req, &state.ems.risk_limits) {
+        Ok(order_req) => order_req,
+        Err(msg) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({"error": msg})),
+            )
+                .into_response();
+        }
     };
 
-    match state.ems.enqueue(ctx.session_id, &order_req).await {
+    match state.ems.enqueue(ctx.session_id, &order_req, &ctx.audit_actor()).await {
         Ok(order) => {
             if state.auto_pump {
                 state.pump_trigger.notify(ctx.session_id);
@@ -682,10 +949,246 @@ async fn create_order(
     }
 }
 
+/// Validate a single-order request and convert it to an `OrderRequest`.
+///
+/// Market orders must be IOC; their price is the configured worst case.
+fn validate_create_order(
+    req: CreateOrderRequest,
+    limits: &harman::risk::RiskLimits,
+) -> Result<OrderRequest, String> {
+    if req.ticker.trim().is_empty() {
+        return Err("ticker is required".to_string());
+    }
+    if req.quantity <= Decimal::ZERO {
+        return Err("quantity must be positive".to_string());
+    }
+    let order_type = req.order_type.unwrap_or_default();
+    let price_dollars = match order_type {
+        OrderType::Limit => {
+            if req.price_dollars <= Decimal::ZERO || req.price_dollars >= Decimal::ONE {
+                return Err("price_dollars must be between 0 and 1 exclusive".to_string());
+            }
+            limits.check_tick(req.price_dollars)?;
+            req.price_dollars
+        }
+        OrderType::Market => {
+            if req.time_in_force != TimeInForce::Ioc {
+                return Err("market orders require time_in_force ioc".to_string());
+            }
+            // Submitted as an IOC limit at the worst-case price, which also
+            // drives the risk notional
+            limits.market_order_price(req.action)
+        }
+    };
+    if let Some(good_till) = req.good_till {
+        if req.time_in_force != TimeInForce::Gtc {
+            return Err("good_till requires time_in_force gtc".to_string());
+        }
+        if good_till <= chrono::Utc::now() {
+            return Err("good_till must be in the future".to_string());
+        }
+    }
+
+    Ok(OrderRequest {
+        client_order_id: req.client_order_id,
+        ticker: req.ticker,
+        side: req.side,
+        action: req.action,
+        quantity: req.quantity,
+        price_dollars,
+        time_in_force: req.time_in_force,
+        order_type,
+        trigger_price: None,
+        good_till: req.good_till,
+        reduce_only: req.reduce_only,
+    })
+}
+
+/// POST /v1/orders/validate
+///
+/// Dry run of `POST /v1/orders`: same request validation and risk checks,
+/// evaluated in a transaction that is rolled back. Nothing is persisted and the
+/// order rate limit is not charged. Returns the projected open notional and
+/// remaining headroom, or the same error status `create_order` would return.
+async fn validate_order_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Json(req): Json<CreateOrderRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:write") {
+        return e.into_response();
+    }
+
+    if state.oms.is_suspended(ctx.session_id) {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "session suspended"})),
+        )
+            .into_response();
+    }
+
+    let order_req = match validate_create_order(req, &state.ems.risk_limits) {
+        Ok(order_req) => order_req,
+        Err(msg) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({"error": msg})),
+            )
+                .into_response();
+        }
+    };
+
+    match state.ems.validate(ctx.session_id, &order_req).await {
+        Ok(projection) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "valid": true,
+                "open_notional": projection.open_notional.to_string(),
+                "projected_open_notional": projection.projected_open_notional.to_string(),
+                "max_notional": projection.max_notional.to_string(),
+                "headroom": projection.headroom.to_string(),
+            })),
+        )
+            .into_response(),
+        Err(EnqueueError::DuplicateClientOrderId(_)) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "duplicate client_order_id"})),
+        )
+            .into_response(),
+        Err(EnqueueError::RiskCheck(e)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+        Err(EnqueueError::Database(e)) => {
+            tracing::error!(error = %e, "database error validating order");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Maximum number of orders accepted by `POST /v1/orders/batch`
+const MAX_BATCH_ORDERS: usize = 50;
+
+/// POST /v1/orders/batch
+#[derive(Debug, Deserialize)]
+pub struct CreateOrderBatchRequest {
+    pub orders: Vec<CreateOrderRequest>,
+}
+
+async fn create_order_batch(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Json(req): Json<CreateOrderBatchRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:write") {
+        return e.into_response();
+    }
+
+    if state.ems.is_shutting_down() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "shutting down"})),
+        )
+            .into_response();
+    }
+
+    if state.oms.is_suspended(ctx.session_id) {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "session suspended"})),
+        )
+            .into_response();
+    }
+
+    if req.orders.is_empty() || req.orders.len() > MAX_BATCH_ORDERS {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({
+                "error": format!("orders must contain 1 to {} items", MAX_BATCH_ORDERS)
+            })),
+        )
+            .into_response();
+    }
+
+    // One token per order, so a batch can't multiply the per-session rate
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, req.orders.len() as u32).await {
+        return resp;
+    }
+
+    let mut order_reqs = Vec::with_capacity(req.orders.len());
+    for (index, order) in req.orders.into_iter().enumerate() {
+        match validate_create_order(order, &state.ems.risk_limits) {
+            Ok(order_req) => order_reqs.push(order_req),
+            Err(msg) => {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(serde_json::json!({"error": msg, "index": index})),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    match state.ems.enqueue_batch(ctx.session_id, &order_reqs, &ctx.audit_actor()).await {
+        Ok(outcomes) => {
+            if state.auto_pump {
+                state.pump_trigger.notify(ctx.session_id);
+            }
+            let results: Vec<serde_json::Value> = outcomes
+                .iter()
+                .map(|outcome| match outcome {
+                    db::BatchEnqueueOutcome::Enqueued(order) => serde_json::json!({
+                        "id": order.id,
+                        "client_order_id": order.client_order_id,
+                        "status": "pending"
+                    }),
+                    db::BatchEnqueueOutcome::Duplicate(cid) => serde_json::json!({
+                        "client_order_id": cid,
+                        "status": "duplicate"
+                    }),
+                })
+                .collect();
+            (
+                StatusCode::CREATED,
+                Json(serde_json::json!({"orders": results})),
+            )
+                .into_response()
+        }
+        Err(EnqueueError::RiskCheck(e)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+        Err(EnqueueError::DuplicateClientOrderId(cid)) => (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "duplicate client_order_id", "client_order_id": cid})),
+        )
+            .into_response(),
+        Err(EnqueueError::Database(e)) => {
+            tracing::error!(error = %e, "database error creating order batch");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET /v1/orders
+///
+/// Without `cursor`/`limit`, returns every order (oldest first) as before.
+/// With either, returns one keyset page (newest first) plus `next_cursor`.
 #[derive(Debug, Deserialize)]
 pub struct ListOrdersQuery {
     pub state: Option<String>,
+    pub cursor: Option<i64>,
+    pub limit: Option<i64>,
 }
 
 async fn list_orders(
@@ -715,8 +1218,22 @@ async fn list_orders(
         _ => None,
     });
 
-    match db::list_orders(&state.pool, ctx.session_id, state_filter).await {
-        Ok(orders) => {
+    let paginated = query.cursor.is_some() || query.limit.is_some();
+    let result = if paginated {
+        let limit = query.limit.unwrap_or(100);
+        db::list_orders_page(&state.pool, ctx.session_id, state_filter, query.cursor, limit)
+            .await
+            .map(|page| (page.items, page.next_cursor))
+    } else {
+        db::list_orders(&state.pool, ctx.session_id, state_filter)
+            .await
+            .map(|orders| (orders, None))
+    };
+
+    // Group filters apply within the page, so a filtered page may hold fewer
+    // than `limit` orders; `next_cursor` still advances past the whole page.
+    match result {
+        Ok((orders, next_cursor)) => {
             let filtered: Vec<_> = match group_filter.as_deref() {
                 Some("open") => orders.into_iter().filter(|o| o.state.is_open()).collect(),
                 Some("terminal") => orders.into_iter().filter(|o| o.state.is_terminal()).collect(),
@@ -732,7 +1249,11 @@ async fn list_orders(
                 _ => orders,
             };
             let response: Vec<serde_json::Value> = filtered.iter().map(order_to_json).collect();
-            (StatusCode::OK, Json(serde_json::json!({"orders": response}))).into_response()
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({"orders": response, "next_cursor": next_cursor})),
+            )
+                .into_response()
         }
         Err(e) => {
             tracing::error!(error = %e, "list orders failed");
@@ -773,6 +1294,52 @@ async fn get_order(
     }
 }
 
+/// GET /v1/orders/:id/fills — the order's fills, oldest first
+async fn list_order_fills_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:read") {
+        return e.into_response();
+    }
+
+    match db::get_order(&state.pool, id, ctx.session_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "order not found"})),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "get order failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    }
+
+    match db::list_fills_for_order(&state.pool, id, ctx.session_id).await {
+        Ok(fills) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"order_id": id, "fills": fills})),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, "list order fills failed");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// DELETE /v1/orders/:id
 async fn cancel_order(
     State(state): State<Arc<AppState>>,
@@ -785,7 +1352,12 @@ async fn cancel_order(
 
     match state
         .ems
-        .enqueue_cancel(id, ctx.session_id, &harman::types::CancelReason::UserRequested)
+        .enqueue_cancel(
+            id,
+            ctx.session_id,
+            &harman::types::CancelReason::UserRequested,
+            &ctx.audit_actor(),
+        )
         .await
     {
         Ok(()) => {
@@ -838,6 +1410,10 @@ async fn amend_order(
         return e.into_response();
     }
 
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, 1).await {
+        return resp;
+    }
+
     // At least one field required
     if body.new_price_dollars.is_none() && body.new_quantity.is_none() {
         return (
@@ -849,7 +1425,16 @@ async fn amend_order(
 
     let new_price: Option<Decimal> = match &body.new_price_dollars {
         Some(s) => match s.parse::<Decimal>() {
-            Ok(d) if d > Decimal::ZERO && d < Decimal::ONE => Some(d),
+            Ok(d) if d > Decimal::ZERO && d < Decimal::ONE => {
+                if let Err(msg) = state.ems.risk_limits.check_tick(d) {
+                    return (
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        Json(serde_json::json!({"error": msg})),
+                    )
+                        .into_response();
+                }
+                Some(d)
+            }
             Ok(_) => {
                 return (
                     StatusCode::UNPROCESSABLE_ENTITY,
@@ -889,7 +1474,7 @@ async fn amend_order(
         None => None,
     };
 
-    match state.ems.enqueue_amend(id, ctx.session_id, new_price, new_qty).await {
+    match state.ems.enqueue_amend(id, ctx.session_id, new_price, new_qty, &ctx.audit_actor()).await {
         Ok(()) => {
             if state.auto_pump {
                 state.pump_trigger.notify(ctx.session_id);
@@ -937,6 +1522,10 @@ async fn decrease_order(
         return e.into_response();
     }
 
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, 1).await {
+        return resp;
+    }
+
     let reduce_by = match body.reduce_by.parse::<Decimal>() {
         Ok(d) if d > Decimal::ZERO => d,
         Ok(_) => {
@@ -955,7 +1544,7 @@ async fn decrease_order(
         }
     };
 
-    match state.ems.enqueue_decrease(id, ctx.session_id, reduce_by).await {
+    match state.ems.enqueue_decrease(id, ctx.session_id, reduce_by, &ctx.audit_actor()).await {
         Ok(()) => {
             if state.auto_pump {
                 state.pump_trigger.notify(ctx.session_id);
@@ -1059,6 +1648,7 @@ async fn health(State(state): State<Arc<AppState>>) -> impl IntoResponse {
 
 /// GET /metrics
 async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    state.db_pool_metrics.observe(&state.pool);
     let encoder = prometheus::TextEncoder::new();
     let families = state.registry.gather();
     match encoder.encode_to_string(&families) {
@@ -1107,6 +1697,61 @@ async fn me_handler(
         .into_response()
 }
 
+/// GET /v1/stream — requires harman:read, upgrades to a WebSocket that pushes
+/// JSON order/fill/group events for the caller's session
+async fn stream_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if let Err(e) = require_scope(&ctx, "harman:read") {
+        return e.into_response();
+    }
+
+    let rx = state.ems.events.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, rx, ctx.session_id))
+}
+
+async fn stream_events(
+    mut socket: WebSocket,
+    mut rx: tokio::sync::broadcast::Receiver<harman::events::StreamEvent>,
+    session_id: i64,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    if event.session_id() != session_id {
+                        continue;
+                    }
+                    let text = match serde_json::to_string(&event) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            tracing::error!(error = %e, "failed to serialize stream event");
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(session_id, skipped, "stream client lagged, events dropped");
+                }
+                Err(RecvError::Closed) => break,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                // Pings are answered by axum; ignore anything else the client sends
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    tracing::debug!(session_id, "stream client disconnected");
+}
+
 /// GET /v1/admin/positions
 ///
 /// Query parameters for positions endpoint
@@ -1170,10 +1815,22 @@ async fn risk_handler(
         return e.into_response();
     }
 
-    let risk_state = match db::compute_risk_state(&state.pool, ctx.session_id).await {
-        Ok(rs) => rs,
+    let risk_state = match db::compute_risk_state(&state.pool, ctx.session_id).await {
+        Ok(rs) => rs,
+        Err(e) => {
+            tracing::error!(error = %e, "risk state query failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    };
+
+    let session_max = match db::get_session_max_notional(&state.pool, ctx.session_id).await {
+        Ok(v) => v,
         Err(e) => {
-            tracing::error!(error = %e, "risk state query failed");
+            tracing::error!(error = %e, "session risk query failed");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": "internal error"})),
@@ -1182,10 +1839,10 @@ async fn risk_handler(
         }
     };
 
-    let session_max = match db::get_session_max_notional(&state.pool, ctx.session_id).await {
+    let open_contracts = match db::compute_open_contracts(&state.pool, ctx.session_id).await {
         Ok(v) => v,
         Err(e) => {
-            tracing::error!(error = %e, "session risk query failed");
+            tracing::error!(error = %e, "open contracts query failed");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": "internal error"})),
@@ -1194,10 +1851,28 @@ async fn risk_handler(
         }
     };
 
+    let session_max_contracts =
+        match db::get_session_max_open_contracts(&state.pool, ctx.session_id).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "session risk query failed");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": "internal error"})),
+                )
+                    .into_response();
+            }
+        };
+
     let global = state.ems.risk_limits.max_notional;
     let effective = session_max.unwrap_or(global);
     let available = effective - risk_state.open_notional;
 
+    // No contract cap when neither a session nor a global limit is set
+    let global_contracts = state.ems.risk_limits.max_open_contracts;
+    let effective_contracts = session_max_contracts.or(global_contracts);
+    let available_contracts = effective_contracts.map(|max| max - open_contracts);
+
     (
         StatusCode::OK,
         Json(serde_json::json!({
@@ -1205,6 +1880,10 @@ async fn risk_handler(
             "global_max_notional": global.to_string(),
             "open_notional": risk_state.open_notional.to_string(),
             "available_notional": available.to_string(),
+            "max_open_contracts": effective_contracts.map(|d| d.to_string()),
+            "global_max_open_contracts": global_contracts.map(|d| d.to_string()),
+            "open_contracts": open_contracts.to_string(),
+            "available_contracts": available_contracts.map(|d| d.to_string()),
             "session_id": ctx.session_id,
         })),
     )
@@ -1227,6 +1906,8 @@ async fn cache_invalidate_handler(
     }
     // Clear key→session cache
     state.key_sessions.clear();
+    // Rebuild rate limiters so per-session overrides are re-read
+    state.rate_limiters.clear();
 
     (StatusCode::OK, Json(serde_json::json!({"cleared": true}))).into_response()
 }
@@ -1464,9 +2145,13 @@ async fn admin_users_handler(
 }
 
 /// PUT /v1/admin/sessions/:id/risk
+///
+/// Replaces both overrides; an omitted field resets to the global limit.
 #[derive(Debug, Deserialize)]
 struct UpdateSessionRiskRequest {
     max_notional: Option<String>,
+    #[serde(default)]
+    max_open_contracts: Option<String>,
 }
 
 async fn update_session_risk_handler(
@@ -1493,16 +2178,42 @@ async fn update_session_risk_handler(
         None => None,
     };
 
-    match db::update_session_risk(&state.pool, session_id, &state.exchange_type, &state.environment, max_notional).await {
+    let max_open_contracts = match &body.max_open_contracts {
+        Some(s) => match s.parse::<Decimal>() {
+            Ok(d) if d >= Decimal::ZERO => Some(d),
+            _ => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "invalid max_open_contracts"})),
+                )
+                    .into_response();
+            }
+        },
+        None => None,
+    };
+
+    match db::update_session_risk(
+        &state.pool,
+        session_id,
+        &state.exchange_type,
+        &state.environment,
+        max_notional,
+        max_open_contracts,
+    )
+    .await
+    {
         Ok(true) => {
             let global = state.ems.risk_limits.max_notional;
-            tracing::info!(session_id, ?max_notional, "session risk updated");
+            let global_contracts = state.ems.risk_limits.max_open_contracts;
+            tracing::info!(session_id, ?max_notional, ?max_open_contracts, "session risk updated");
             (
                 StatusCode::OK,
                 Json(serde_json::json!({
                     "session_id": session_id,
                     "max_notional": max_notional.map(|d| d.to_string()),
                     "global_max_notional": global.to_string(),
+                    "max_open_contracts": max_open_contracts.map(|d| d.to_string()),
+                    "global_max_open_contracts": global_contracts.map(|d| d.to_string()),
                 })),
             )
                 .into_response()
@@ -1546,9 +2257,191 @@ async fn resume_session_handler(
         .into_response()
 }
 
-/// GET /v1/fills
+/// GET /v1/admin/sessions/:id/reconcile-status
+///
+/// Latest reconcile outcome for the session, from either the manual endpoint or
+/// the background auto-reconcile. 404 until the first pass since startup.
+async fn reconcile_status_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Path(session_id): Path<i64>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:admin") {
+        return e.into_response();
+    }
+
+    match state.oms.reconcile_status(session_id) {
+        Some(status) => (StatusCode::OK, Json(status)).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "no reconcile recorded for session"})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /v1/admin/sessions/:id/close
+#[derive(Debug, Deserialize)]
+pub struct CloseSessionQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Close a session so its API keys resolve to a fresh one on the next request.
+///
+/// Refuses with 409 while the session has open orders unless `?force=true`,
+/// which requests a cancel for every open order first; if any of those cancels
+/// can't be enqueued the session stays open and the call fails. Static-token
+/// requests stay bound to the startup session until restart.
+async fn close_session_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Path(session_id): Path<i64>,
+    Query(query): Query<CloseSessionQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:admin") {
+        return e.into_response();
+    }
+
+    // Scope check before touching any orders: another instance's session is a 404
+    match db::is_open_session(&state.pool, session_id, &state.exchange_type, &state.environment).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "session not found or already closed"})),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "session lookup failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    }
+
+    let open_orders = match db::list_open_orders(&state.pool, session_id).await {
+        Ok(orders) => orders,
+        Err(e) => {
+            tracing::error!(error = %e, "list open orders failed");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response();
+        }
+    };
+
+    if !open_orders.is_empty() && !query.force {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "session has open orders",
+                "open_orders": open_orders.len(),
+            })),
+        )
+            .into_response();
+    }
+
+    let mut cancel_requested = 0usize;
+    let mut cancel_failed = Vec::new();
+    for (order_id, order_state) in &open_orders {
+        if *order_state == OrderState::PendingCancel {
+            continue;
+        }
+        match state
+            .ems
+            .enqueue_cancel(
+                *order_id,
+                session_id,
+                &harman::types::CancelReason::SessionClosed,
+                &ctx.audit_actor(),
+            )
+            .await
+        {
+            Ok(()) => cancel_requested += 1,
+            Err(e) => {
+                tracing::warn!(order_id, session_id, error = %e, "close: cancel failed");
+                cancel_failed.push(*order_id);
+            }
+        }
+    }
+    // Pumping is by session id, so queued cancels still drain after the close
+    if state.auto_pump && cancel_requested > 0 {
+        state.pump_trigger.notify(session_id);
+    }
+
+    if !cancel_failed.is_empty() {
+        // An order that went terminal since it was listed (filled, or cancelled
+        // by the exchange) can't be cancelled but doesn't block the close
+        let still_open = match db::list_open_orders(&state.pool, session_id).await {
+            Ok(orders) => orders,
+            Err(e) => {
+                tracing::error!(error = %e, "list open orders failed");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": "internal error"})),
+                )
+                    .into_response();
+            }
+        };
+        cancel_failed.retain(|id| {
+            still_open
+                .iter()
+                .any(|(open_id, s)| open_id == id && *s != OrderState::PendingCancel)
+        });
+        if !cancel_failed.is_empty() {
+            tracing::error!(session_id, ?cancel_failed, "close: open orders left uncancelled, session kept open");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "failed to cancel open orders; session not closed",
+                    "cancel_requested": cancel_requested,
+                    "cancel_failed": cancel_failed,
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    match db::close_session(&state.pool, session_id, &state.exchange_type, &state.environment).await {
+        Ok(true) => {
+            state.key_sessions.retain(|_, id| *id != session_id);
+            state.rate_limiters.remove(&session_id);
+            tracing::info!(session_id, cancel_requested, "admin closed session");
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "session_id": session_id,
+                    "closed": true,
+                    "cancel_requested": cancel_requested,
+                })),
+            )
+                .into_response()
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "session not found or already closed"})),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, "close session failed");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /v1/fills — newest first, keyset-paginated via `cursor`
 #[derive(Debug, Deserialize)]
 pub struct ListFillsQuery {
+    pub cursor: Option<i64>,
     pub limit: Option<i64>,
 }
 
@@ -1561,10 +2454,14 @@ async fn list_fills_handler(
         return e.into_response();
     }
 
-    let limit = query.limit.unwrap_or(100).min(1000);
+    let limit = query.limit.unwrap_or(100);
 
-    match db::list_fills(&state.pool, ctx.session_id, limit).await {
-        Ok(fills) => (StatusCode::OK, Json(serde_json::json!({"fills": fills}))).into_response(),
+    match db::list_fills(&state.pool, ctx.session_id, query.cursor, limit).await {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"fills": page.items, "next_cursor": page.next_cursor})),
+        )
+            .into_response(),
         Err(e) => {
             tracing::error!(error = %e, "list fills failed");
             (
@@ -1576,9 +2473,80 @@ async fn list_fills_handler(
     }
 }
 
-/// GET /v1/audit
+/// GET /v1/positions/history
+#[derive(Debug, Deserialize)]
+pub struct PositionHistoryQuery {
+    /// Range start; defaults to midnight UTC of `to`'s day.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Range end; defaults to now.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Bucket width in seconds; defaults to 300.
+    pub interval_secs: Option<i64>,
+}
+
+/// Position and realized PnL over time per ticker/side, replayed from the
+/// session's fills. See `harman::position_history` for the PnL convention
+/// (average-cost basis, fees excluded). The range is capped at 24h.
+async fn position_history_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(ctx): Extension<SessionContext>,
+    Query(query): Query<PositionHistoryQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_scope(&ctx, "harman:read") {
+        return e.into_response();
+    }
+
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query.from.unwrap_or_else(|| {
+        to.date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc()
+    });
+    let interval_secs = query.interval_secs.unwrap_or(300);
+
+    if let Err(e) = position_history::validate_range(from, to, interval_secs) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response();
+    }
+
+    match db::list_fills_until(&state.pool, ctx.session_id, to).await {
+        Ok(fills) => {
+            let series = position_history::replay_fills(
+                &fills,
+                from,
+                to,
+                chrono::Duration::seconds(interval_secs),
+            );
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "from": from,
+                    "to": to,
+                    "interval_secs": interval_secs,
+                    "positions": series,
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "position history failed");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /v1/audit — newest first, keyset-paginated via `cursor`
 #[derive(Debug, Deserialize)]
 pub struct ListAuditQuery {
+    pub cursor: Option<i64>,
     pub limit: Option<i64>,
 }
 
@@ -1591,12 +2559,14 @@ async fn list_audit_handler(
         return e.into_response();
     }
 
-    let limit = query.limit.unwrap_or(100).min(1000);
+    let limit = query.limit.unwrap_or(100);
 
-    match db::list_audit_log(&state.pool, ctx.session_id, limit).await {
-        Ok(entries) => {
-            (StatusCode::OK, Json(serde_json::json!({"audit": entries}))).into_response()
-        }
+    match db::list_audit_log(&state.pool, ctx.session_id, query.cursor, limit).await {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"audit": page.items, "next_cursor": page.next_cursor})),
+        )
+            .into_response(),
         Err(e) => {
             tracing::error!(error = %e, "list audit log failed");
             (
@@ -1833,6 +2803,47 @@ async fn monitor_categories_handler(
         return (StatusCode::OK, Json(serde_json::json!({"categories": []}))).into_response();
     };
     let mut conn = conn.clone();
+    let exchange = &state.exchange_type;
+
+    // Prefer the per-exchange index maintained by ssmd-cache: one HGETALL with
+    // precomputed series counts. Fall back to the series scan below if absent.
+    let timer = state.monitor_metrics.redis_duration_seconds.start_timer();
+    let index_key = format!("monitor:categories:{}", exchange);
+    match redis::cmd("HGETALL")
+        .arg(&index_key)
+        .query_async::<std::collections::HashMap<String, String>>(&mut conn)
+        .await
+    {
+        Ok(index) if !index.is_empty() => {
+            timer.observe_duration();
+            let categories: Vec<serde_json::Value> = index
+                .into_iter()
+                .filter_map(|(name, val)| {
+                    let obj: serde_json::Value = serde_json::from_str(&val).unwrap_or_default();
+                    let series_count = obj.get("series_count").and_then(|c| c.as_u64()).unwrap_or(0);
+                    if series_count == 0 {
+                        return None;
+                    }
+                    Some(serde_json::json!({
+                        "name": name,
+                        "series_count": series_count,
+                    }))
+                })
+                .collect();
+            state.monitor_metrics.requests_total.with_label_values(&["categories", "ok"]).inc();
+            return (StatusCode::OK, Json(serde_json::json!({"categories": categories}))).into_response();
+        }
+        Ok(_) => {
+            timer.observe_duration();
+            tracing::debug!(key = %index_key, "category index absent, scanning series");
+        }
+        Err(e) => {
+            timer.observe_duration();
+            state.monitor_metrics.redis_errors_total.inc();
+            tracing::warn!(error = %e, key = %index_key, "Redis HGETALL category index failed, scanning series");
+        }
+    }
+
     let timer = state.monitor_metrics.redis_duration_seconds.start_timer();
     let result: std::collections::HashMap<String, String> = match redis::cmd("HGETALL")
         .arg("monitor:categories")
@@ -1849,7 +2860,6 @@ async fn monitor_categories_handler(
         }
     };
     timer.observe_duration();
-    let exchange = &state.exchange_type;
 
     // Category data may be overwritten across exchanges (e.g., Polymarket overwrites
     // Kalshi's "Crypto" entry). Instead of trusting the category-level fields, use the
@@ -2163,6 +3173,9 @@ fn order_to_json(order: &Order) -> serde_json::Value {
         "cancel_reason": order.cancel_reason,
         "group_id": order.group_id,
         "leg_role": order.leg_role.map(|r| r.to_string()),
+        "good_till": order.good_till.map(|t| t.to_rfc3339()),
+        "reduce_only": order.reduce_only,
+        "reject_reason": order.reject_reason,
         "created_at": order.created_at.to_rfc3339(),
         "updated_at": order.updated_at.to_rfc3339(),
     })
@@ -2174,6 +3187,9 @@ fn group_to_json(group: &OrderGroup, orders: &[Order]) -> serde_json::Value {
         "session_id": group.session_id,
         "group_type": group.group_type.to_string(),
         "state": group.state.to_string(),
+        "link_mode": group.link_mode.to_string(),
+        "peg_spread_dollars": group.peg.map(|p| p.spread_dollars.to_string()),
+        "peg_min_reprice_dollars": group.peg.map(|p| p.min_reprice_dollars.to_string()),
         "orders": orders.iter().map(order_to_json).collect::<Vec<_>>(),
         "created_at": group.created_at.to_rfc3339(),
         "updated_at": group.updated_at.to_rfc3339(),
@@ -2186,6 +3202,9 @@ pub struct CreateBracketRequest {
     pub entry: CreateOrderRequest,
     pub take_profit: CreateOrderRequest,
     pub stop_loss: CreateOrderRequest,
+    /// Base id for deriving leg client_order_ids when every leg's is nil
+    #[serde(default)]
+    pub base_client_order_id: Option<Uuid>,
 }
 
 async fn create_bracket_group(
@@ -2197,6 +3216,10 @@ async fn create_bracket_group(
         return e.into_response();
     }
 
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, 1).await {
+        return resp;
+    }
+
     if state.ems.is_shutting_down() {
         return (
             StatusCode::SERVICE_UNAVAILABLE,
@@ -2290,9 +3313,30 @@ async fn create_bracket_group(
         }
     }
 
-    let entry = to_order_request(&req.entry);
-    let tp = to_order_request(&req.take_profit);
-    let mut sl = to_order_request(&req.stop_loss);
+    if [&req.entry, &req.take_profit, &req.stop_loss]
+        .iter()
+        .any(|leg| leg.good_till.is_some())
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "good_till is not supported on group legs"})),
+        )
+            .into_response();
+    }
+
+    let mut legs = [
+        to_order_request(&req.entry),
+        to_order_request(&req.take_profit),
+        to_order_request(&req.stop_loss),
+    ];
+    if let Err(e) = assign_leg_client_order_ids(req.base_client_order_id, GroupType::Bracket, &mut legs) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response();
+    }
+    let [entry, tp, mut sl] = legs;
 
     // Force SL with trigger_price to Market (IOC) order type
     if sl.trigger_price.is_some() {
@@ -2336,6 +3380,15 @@ async fn create_bracket_group(
 pub struct CreateOcoRequest {
     pub leg1: CreateOrderRequest,
     pub leg2: CreateOrderRequest,
+    /// `peg` keeps leg2 at leg1's price plus their initial spread while both rest
+    #[serde(default)]
+    pub link_mode: LinkMode,
+    /// Smallest drift that triggers a peg reprice (default: one tick)
+    #[serde(default)]
+    pub min_reprice_dollars: Option<Decimal>,
+    /// Base id for deriving leg client_order_ids when every leg's is nil
+    #[serde(default)]
+    pub base_client_order_id: Option<Uuid>,
 }
 
 async fn create_oco_group(
@@ -2347,6 +3400,10 @@ async fn create_oco_group(
         return e.into_response();
     }
 
+    if let Err(resp) = check_rate_limit(&state, ctx.session_id, 1).await {
+        return resp;
+    }
+
     if state.ems.is_shutting_down() {
         return (
             StatusCode::SERVICE_UNAVAILABLE,
@@ -2363,10 +3420,53 @@ async fn create_oco_group(
             .into_response();
     }
 
-    let leg1 = to_order_request(&req.leg1);
-    let leg2 = to_order_request(&req.leg2);
+    if req.leg1.good_till.is_some() || req.leg2.good_till.is_some() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "good_till is not supported on group legs"})),
+        )
+            .into_response();
+    }
+
+    let peg = match req.link_mode {
+        LinkMode::None => None,
+        LinkMode::Peg => {
+            let is_market = |leg: &CreateOrderRequest| leg.order_type == Some(OrderType::Market);
+            if is_market(&req.leg1) || is_market(&req.leg2) {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "peg link_mode requires limit legs"})),
+                )
+                    .into_response();
+            }
+            let min_reprice_dollars = req
+                .min_reprice_dollars
+                .unwrap_or(state.ems.risk_limits.tick_size);
+            if min_reprice_dollars <= Decimal::ZERO {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "min_reprice_dollars must be positive"})),
+                )
+                    .into_response();
+            }
+            Some(PegConfig {
+                spread_dollars: req.leg2.price_dollars - req.leg1.price_dollars,
+                min_reprice_dollars,
+            })
+        }
+    };
+
+    let mut legs = [to_order_request(&req.leg1), to_order_request(&req.leg2)];
+    if let Err(e) = assign_leg_client_order_ids(req.base_client_order_id, GroupType::Oco, &mut legs) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response();
+    }
+    let [leg1, leg2] = legs;
 
-    match state.oms.create_oco(ctx.session_id, leg1, leg2).await {
+    match state.oms.create_oco(ctx.session_id, leg1, leg2, peg).await {
         Ok((group, orders)) => {
             if state.auto_pump {
                 state.pump_trigger.notify(ctx.session_id);
@@ -2527,5 +3627,7 @@ fn to_order_request(req: &CreateOrderRequest) -> OrderRequest {
         time_in_force: req.time_in_force,
         order_type: req.order_type.unwrap_or_default(),
         trigger_price: req.trigger_price,
+        good_till: None,
+        reduce_only: false,
     }
 }
@@ -2,15 +2,17 @@ pub mod api;
 pub mod pump;
 pub mod shutdown;
 
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
 use deadpool_postgres::Pool;
 use lru::LruCache;
 use tokio::sync::{RwLock, Semaphore};
 
+use harman::rate_limit::RateLimiter;
 use ssmd_harman_ems::Ems;
 use ssmd_harman_oms::Oms;
 use ssmd_harman_oms::runner::{OmsRunner, PumpTrigger};
@@ -64,11 +66,57 @@ impl MonitorMetrics {
     }
 }
 
-/// Cloudflare Access JWKS key (RSA)
+/// Prometheus gauges for DB pool saturation, sampled on each /metrics scrape
+pub struct DbPoolMetrics {
+    pub size: prometheus::IntGauge,
+    pub available: prometheus::IntGauge,
+    pub waiters: prometheus::IntGauge,
+}
+
+impl DbPoolMetrics {
+    pub fn new(registry: &prometheus::Registry) -> Self {
+        let size = prometheus::IntGauge::new(
+            "harman_db_pool_size",
+            "Current number of connections in the DB pool",
+        )
+        .unwrap();
+        let available = prometheus::IntGauge::new(
+            "harman_db_pool_available",
+            "Idle DB pool connections available for checkout",
+        )
+        .unwrap();
+        let waiters = prometheus::IntGauge::new(
+            "harman_db_pool_waiters",
+            "Tasks waiting for a DB pool connection",
+        )
+        .unwrap();
+
+        registry.register(Box::new(size.clone())).unwrap();
+        registry.register(Box::new(available.clone())).unwrap();
+        registry.register(Box::new(waiters.clone())).unwrap();
+
+        Self {
+            size,
+            available,
+            waiters,
+        }
+    }
+
+    /// Update the gauges from the pool's current status.
+    pub fn observe(&self, pool: &Pool) {
+        let status = pool.status();
+        self.size.set(status.size as i64);
+        self.available.set(status.available as i64);
+        self.waiters.set(status.waiting as i64);
+    }
+}
+
+/// Cloudflare Access JWKS key (RSA), with its decoding key built once at refresh
 pub struct CfJwk {
     pub kid: String,
     pub n: String,
     pub e: String,
+    pub decoding_key: jsonwebtoken::DecodingKey,
 }
 
 /// Cached auth validation result from data-ts
@@ -78,6 +126,13 @@ pub struct CachedAuth {
     pub cached_at: Instant,
 }
 
+impl CachedAuth {
+    /// Whether this entry is still within the auth cache TTL.
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        self.cached_at.elapsed() < ttl
+    }
+}
+
 /// Per-request session context, injected by auth middleware
 #[derive(Clone, Debug)]
 pub struct SessionContext {
@@ -87,6 +142,13 @@ pub struct SessionContext {
     pub email: Option<String>,
 }
 
+impl SessionContext {
+    /// Actor recorded on queue items and audit rows for this caller, e.g. `api:ab12cd`.
+    pub fn audit_actor(&self) -> String {
+        format!("api:{}", self.key_prefix)
+    }
+}
+
 /// Shared application state
 pub struct AppState {
     /// The EMS instance (owns exchange, risk_limits, shutting_down, pump)
@@ -110,8 +172,19 @@ pub struct AppState {
     pub pump_trigger: PumpTrigger,
     // Per-session state
     pub session_semaphores: DashMap<i64, Arc<Semaphore>>,
+    /// Per-session order rate limiters (token buckets), created on first use;
+    /// `None` for an unlimited session
+    pub rate_limiters: DashMap<i64, Option<RateLimiter>>,
+    /// Global order rate limit in requests/sec (0 = disabled unless the session
+    /// has an override)
+    pub order_rate_limit: u32,
     // Caches
     pub auth_cache: RwLock<LruCache<String, CachedAuth>>,
+    /// TTL applied to every auth_cache entry (API tokens and CF emails)
+    pub auth_cache_ttl: Duration,
+    /// Per-process random salt mixed into bearer-token cache keys, so an
+    /// in-memory key can't be matched against a leaked plain token hash
+    pub auth_cache_salt: [u8; 32],
     pub key_sessions: DashMap<String, i64>,
     /// Cached ticker list from secmaster (via data-ts), refreshed every 5 minutes
     pub ticker_cache: RwLock<Option<(std::time::Instant, Vec<String>)>>,
@@ -121,6 +194,8 @@ pub struct AppState {
     pub redis_conn: Option<redis::aio::MultiplexedConnection>,
     /// Prometheus metrics for monitor endpoints
     pub monitor_metrics: MonitorMetrics,
+    /// DB pool saturation gauges
+    pub db_pool_metrics: DbPoolMetrics,
     /// Exchange type (e.g., "kalshi")
     pub exchange_type: String,
     /// Exchange environment (e.g., "demo" or "prod")
@@ -129,7 +204,8 @@ pub struct AppState {
     pub cf_jwks_url: Option<String>,
     pub cf_aud: Option<String>,
     pub cf_iss: Option<String>,
-    pub cf_jwks: RwLock<Option<(Instant, Vec<CfJwk>)>>,
+    /// JWKS keyed by `kid`, with fetch time for the refresh TTL
+    pub cf_jwks: RwLock<Option<(Instant, HashMap<String, CfJwk>)>>,
     pub data_ts_api_key: Option<String>,
     pub data_ts_base_url: Option<String>,
 }
@@ -138,4 +214,11 @@ impl AppState {
     pub fn new_auth_cache() -> LruCache<String, CachedAuth> {
         LruCache::new(NonZeroUsize::new(512).unwrap())
     }
+
+    pub fn new_auth_cache_salt() -> [u8; 32] {
+        let mut salt = [0u8; 32];
+        salt[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+        salt[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+        salt
+    }
 }
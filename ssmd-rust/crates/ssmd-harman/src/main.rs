@@ -8,10 +8,12 @@ use lru::LruCache;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
-use ssmd_harman::{api, shutdown, AppState, MonitorMetrics};
-use ssmd_harman_ems::{Ems, EmsMetrics};
+use ssmd_harman::{api, shutdown, AppState, DbPoolMetrics, MonitorMetrics};
+use ssmd_harman_ems::exchange_call::ExchangeCallPolicy;
+use ssmd_harman_ems::{Ems, EmsMetrics, ShutdownMode};
 use ssmd_harman_oms::price_feed::NatsPriceFeed;
 use ssmd_harman_oms::price_monitor::PriceMonitor;
+use ssmd_harman_oms::reconciliation::MismatchThresholds;
 use ssmd_harman_oms::runner::OmsRunner;
 use ssmd_harman_oms::{Oms, OmsMetrics};
 
@@ -31,6 +33,10 @@ struct Args {
     #[arg(long, env = "MAX_NOTIONAL", default_value = "100")]
     max_notional: f64,
 
+    /// Maximum total unfilled contracts across open orders (unset = no cap)
+    #[arg(long, env = "MAX_OPEN_CONTRACTS")]
+    max_open_contracts: Option<rust_decimal::Decimal>,
+
     /// Maximum notional for a single order (fat-finger protection) in dollars
     #[arg(long, env = "MAX_ORDER_NOTIONAL", default_value = "25")]
     max_order_notional: f64,
@@ -39,6 +45,22 @@ struct Args {
     #[arg(long, env = "DAILY_LOSS_LIMIT", default_value = "50")]
     daily_loss_limit: f64,
 
+    /// Maximum open notional on any single ticker in dollars (unset = no cap)
+    #[arg(long, env = "MAX_TICKER_NOTIONAL")]
+    max_ticker_notional: Option<f64>,
+
+    /// Maximum number of open orders on any single ticker (unset = no cap)
+    #[arg(long, env = "MAX_OPEN_ORDERS_PER_TICKER")]
+    max_open_orders_per_ticker: Option<u32>,
+
+    /// Worst-case price in dollars for market orders (buys cap here, sells at 1 - price)
+    #[arg(long, env = "MARKET_ORDER_WORST_PRICE", default_value = "0.99")]
+    market_order_worst_price: rust_decimal::Decimal,
+
+    /// Minimum price increment in dollars; limit and amend prices must be a multiple of it
+    #[arg(long, env = "TICK_SIZE", default_value = "0.01")]
+    tick_size: rust_decimal::Decimal,
+
     /// Kalshi API base URL
     #[arg(
         long,
@@ -47,6 +69,19 @@ struct Args {
     )]
     kalshi_base_url: String,
 
+    /// Per-attempt timeout for pump calls to the exchange, in milliseconds
+    #[arg(long, env = "EXCHANGE_CALL_TIMEOUT_MS", default_value = "15000")]
+    exchange_call_timeout_ms: u64,
+
+    /// Retries for pump cancel/amend calls failing with a 5xx or connection
+    /// error (submits and decreases are never retried)
+    #[arg(long, env = "EXCHANGE_MAX_RETRIES", default_value = "2")]
+    exchange_max_retries: u32,
+
+    /// Backoff before the first retry, doubled after each, in milliseconds
+    #[arg(long, env = "EXCHANGE_RETRY_BACKOFF_MS", default_value = "200")]
+    exchange_retry_backoff_ms: u64,
+
     /// Enable auto-pump after order mutations
     #[arg(long, env = "AUTO_PUMP", default_value = "false")]
     auto_pump: bool,
@@ -54,18 +89,65 @@ struct Args {
     /// Auto-reconcile interval in seconds (0 = disabled)
     #[arg(long, env = "RECONCILE_INTERVAL_SECS", default_value = "0")]
     reconcile_interval_secs: u64,
+
+    /// Maximum number of sessions reconciled concurrently
+    #[arg(long, env = "RECONCILE_CONCURRENCY", default_value = "4")]
+    reconcile_concurrency: usize,
+
+    /// Position mismatch above this many contracts is "major"
+    #[arg(long, env = "RECONCILE_MAJOR_CONTRACTS", default_value = "1")]
+    reconcile_major_contracts: rust_decimal::Decimal,
+
+    /// Position mismatch above this notional (dollars) is "major"
+    #[arg(long, env = "RECONCILE_MAJOR_NOTIONAL", default_value = "10")]
+    reconcile_major_notional: rust_decimal::Decimal,
+
+    /// Position mismatch above this many contracts is "critical"
+    #[arg(long, env = "RECONCILE_CRITICAL_CONTRACTS", default_value = "10")]
+    reconcile_critical_contracts: rust_decimal::Decimal,
+
+    /// Position mismatch above this notional (dollars) is "critical"
+    #[arg(long, env = "RECONCILE_CRITICAL_NOTIONAL", default_value = "100")]
+    reconcile_critical_notional: rust_decimal::Decimal,
+
+    /// Suspend a session when reconciliation finds a critical position mismatch
+    #[arg(long, env = "RECONCILE_SUSPEND_ON_CRITICAL", default_value = "false")]
+    reconcile_suspend_on_critical: bool,
+
+    /// Per-session order mutation rate limit in requests/sec (0 = disabled for
+    /// sessions without an order_rate_limit override)
+    #[arg(long, env = "ORDER_RATE_LIMIT", default_value = "20")]
+    order_rate_limit: u32,
+
+    /// TTL in seconds for cached auth results (API tokens and CF emails)
+    #[arg(long, env = "AUTH_CACHE_TTL_SECS", default_value = "30")]
+    auth_cache_ttl_secs: u64,
+
+    /// Maximum DB pool connections (default: deadpool's CPU-based default)
+    #[arg(long, env = "DB_POOL_MAX_SIZE")]
+    db_pool_max_size: Option<usize>,
+
+    /// Milliseconds to wait for a DB pool connection before failing (default: wait indefinitely)
+    #[arg(long, env = "DB_POOL_TIMEOUT_MS")]
+    db_pool_timeout_ms: Option<u64>,
+
+    /// What to do with live orders on SIGTERM: drain (mass cancel + reject queue),
+    /// cancel_resting (cancel acknowledged orders, keep queue), or hold (stop only)
+    #[arg(long, env = "HARMAN_SHUTDOWN_MODE", default_value = "drain")]
+    shutdown_mode: ShutdownMode,
 }
 
 #[tokio::main]
 async fn main() {
-    // Init tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "ssmd_harman=info,harman=info,ssmd_harman_ems=info,ssmd_harman_oms=info".into()),
-        )
-        .json()
-        .init();
+    // Init tracing: JSON by default, HARMAN_LOG_FORMAT=pretty for local dev
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| "ssmd_harman=info,harman=info,ssmd_harman_ems=info,ssmd_harman_oms=info".into()),
+    );
+    match std::env::var("HARMAN_LOG_FORMAT").as_deref() {
+        Ok("pretty") => subscriber.pretty().init(),
+        _ => subscriber.json().init(),
+    }
 
     let args = Args::parse();
 
@@ -103,7 +185,17 @@ async fn main() {
     info!(listen_addr = %args.listen_addr, exchange_type = %exchange_type, environment = %environment, "ssmd-harman starting");
 
     // Create DB pool
-    let pool = harman::db::create_pool(&args.database_url).expect("failed to create DB pool");
+    let pool = harman::db::create_pool_with_limits(
+        &args.database_url,
+        args.db_pool_max_size,
+        args.db_pool_timeout_ms.map(Duration::from_millis),
+    )
+    .expect("failed to create DB pool");
+    info!(
+        max_size = pool.status().max_size,
+        timeout_ms = ?args.db_pool_timeout_ms,
+        "DB pool created"
+    );
 
     // Run migrations
     harman::db::run_migrations(&pool)
@@ -114,52 +206,15 @@ async fn main() {
     let exchange_base_url = std::env::var("EXCHANGE_BASE_URL")
         .unwrap_or_else(|_| args.kalshi_base_url.clone());
 
-    let exchange: Arc<dyn harman::exchange::ExchangeAdapter> = match exchange_type.as_str() {
-        "kalshi" => {
-            // Startup env validation: detect base URL / environment mismatch
-            let base_url_lower = args.kalshi_base_url.to_lowercase();
-            if environment == "prod" && base_url_lower.contains("demo") {
-                error!(
-                    environment = %environment,
-                    base_url = %args.kalshi_base_url,
-                    "FATAL: EXCHANGE_ENVIRONMENT=prod but KALSHI_BASE_URL contains 'demo'"
-                );
-                std::process::exit(1);
-            }
-            if environment == "demo" && !base_url_lower.contains("demo") {
-                error!(
-                    environment = %environment,
-                    base_url = %args.kalshi_base_url,
-                    "FATAL: EXCHANGE_ENVIRONMENT=demo but KALSHI_BASE_URL does not contain 'demo'"
-                );
-                std::process::exit(1);
-            }
-
-            let kalshi_config = ssmd_connector_lib::kalshi::config::KalshiConfig::from_env()
-                .expect("Kalshi credentials not configured");
-            let credentials = ssmd_connector_lib::kalshi::auth::KalshiCredentials::new(
-                kalshi_config.api_key,
-                &kalshi_config.private_key_pem,
-            )
-            .expect("invalid Kalshi credentials");
-            Arc::new(ssmd_exchange_kalshi::client::KalshiRestClient::new(
-                credentials,
-                args.kalshi_base_url.clone(),
-            ))
-        }
-        "test" => {
-            // Test exchange — uses Kalshi protocol against harman-test-exchange.
-            // No real credentials needed; dummy RSA key satisfies the type system
-            // and the test-exchange ignores auth headers entirely.
-            info!(base_url = %exchange_base_url, "using test exchange (Kalshi protocol)");
-            let credentials = ssmd_connector_lib::kalshi::auth::KalshiCredentials::dummy();
-            Arc::new(ssmd_exchange_kalshi::client::KalshiRestClient::new(
-                credentials,
-                exchange_base_url.clone(),
-            ))
-        }
-        other => {
-            error!(exchange_type = %other, "unsupported EXCHANGE_TYPE (expected: kalshi, test)");
+    let exchange = match build_exchange(
+        &exchange_type,
+        &environment,
+        &args.kalshi_base_url,
+        &exchange_base_url,
+    ) {
+        Ok(exchange) => exchange,
+        Err(e) => {
+            error!(exchange_type = %exchange_type, error = %e, "FATAL: failed to build exchange adapter");
             std::process::exit(1);
         }
     };
@@ -177,13 +232,49 @@ async fn main() {
         }
     }
 
+    if args.tick_size <= rust_decimal::Decimal::ZERO || args.tick_size >= rust_decimal::Decimal::ONE {
+        error!(tick_size = %args.tick_size, "TICK_SIZE must be between 0 and 1 exclusive");
+        std::process::exit(1);
+    }
+
+    if args.market_order_worst_price <= rust_decimal::Decimal::ZERO
+        || args.market_order_worst_price >= rust_decimal::Decimal::ONE
+        || !(args.market_order_worst_price % args.tick_size).is_zero()
+    {
+        error!(
+            market_order_worst_price = %args.market_order_worst_price,
+            tick_size = %args.tick_size,
+            "MARKET_ORDER_WORST_PRICE must be between 0 and 1 exclusive and a multiple of TICK_SIZE"
+        );
+        std::process::exit(1);
+    }
+
+    let mismatch_thresholds = MismatchThresholds {
+        major_contracts: args.reconcile_major_contracts,
+        major_notional: args.reconcile_major_notional,
+        critical_contracts: args.reconcile_critical_contracts,
+        critical_notional: args.reconcile_critical_notional,
+        suspend_on_critical: args.reconcile_suspend_on_critical,
+    };
+    if let Err(e) = mismatch_thresholds.validate() {
+        error!(error = %e, "invalid reconciliation mismatch thresholds");
+        std::process::exit(1);
+    }
+
     let risk_limits = harman::risk::RiskLimits {
         max_notional: rust_decimal::Decimal::from_f64_retain(args.max_notional)
             .unwrap_or(rust_decimal::Decimal::new(100, 0)),
+        max_open_contracts: args.max_open_contracts,
         max_order_notional: rust_decimal::Decimal::from_f64_retain(args.max_order_notional)
             .unwrap_or(rust_decimal::Decimal::new(25, 0)),
         daily_loss_limit: rust_decimal::Decimal::from_f64_retain(args.daily_loss_limit)
             .unwrap_or(rust_decimal::Decimal::new(50, 0)),
+        max_ticker_notional: args
+            .max_ticker_notional
+            .and_then(rust_decimal::Decimal::from_f64_retain),
+        max_open_orders_per_ticker: args.max_open_orders_per_ticker,
+        market_order_worst_price: args.market_order_worst_price,
+        tick_size: args.tick_size,
     };
 
     // Reset stale processing items (watchdog: clear items stuck in processing state)
@@ -219,11 +310,23 @@ async fn main() {
     // Create shared registry, EMS metrics first, then OMS metrics
     let registry = prometheus::Registry::new();
     let ems_metrics = EmsMetrics::new(&registry);
-    let ems = Arc::new(Ems::new(pool.clone(), exchange.clone(), risk_limits, ems_metrics, audit_sender.clone()));
+    let call_policy = ExchangeCallPolicy {
+        timeout: Duration::from_millis(args.exchange_call_timeout_ms),
+        max_retries: args.exchange_max_retries,
+        retry_backoff: Duration::from_millis(args.exchange_retry_backoff_ms),
+    };
+    let ems = Arc::new(
+        Ems::new(pool.clone(), exchange.clone(), risk_limits, ems_metrics, audit_sender.clone())
+            .with_call_policy(call_policy),
+    );
 
     let oms_metrics = Arc::new(OmsMetrics::new(&registry));
-    let oms = Arc::new(Oms::new(pool.clone(), exchange.clone(), ems.clone(), oms_metrics, audit_sender));
+    let oms = Arc::new(
+        Oms::new(pool.clone(), exchange.clone(), ems.clone(), oms_metrics, audit_sender)
+            .with_mismatch_thresholds(mismatch_thresholds),
+    );
     let monitor_metrics = MonitorMetrics::new(&registry);
+    let db_pool_metrics = DbPoolMetrics::new(&registry);
 
     // Optional WebSocket event stream for real-time order/fill/settlement events.
     // Requires KALSHI_WS_URL and Kalshi credentials (KALSHI_API_KEY + KALSHI_PRIVATE_KEY).
@@ -298,10 +401,13 @@ async fn main() {
         oms.clone(),
         reconcile_interval,
         startup_session_id,
+        &exchange_type,
+        &environment,
         event_stream,
         price_monitor_handle,
         pump_trigger.clone(),
-    ));
+    )
+    .with_reconcile_concurrency(args.reconcile_concurrency));
     if args.auto_pump {
         info!("auto-pump enabled");
     }
@@ -352,12 +458,17 @@ async fn main() {
         auto_pump: args.auto_pump,
         pump_trigger,
         session_semaphores: DashMap::new(),
+        rate_limiters: DashMap::new(),
+        order_rate_limit: args.order_rate_limit,
         auth_cache: RwLock::new(LruCache::new(NonZeroUsize::new(512).unwrap())),
+        auth_cache_ttl: Duration::from_secs(args.auth_cache_ttl_secs),
+        auth_cache_salt: AppState::new_auth_cache_salt(),
         key_sessions: DashMap::new(),
         ticker_cache: tokio::sync::RwLock::new(None),
         pump_semaphore: tokio::sync::Semaphore::new(1),
         redis_conn,
         monitor_metrics,
+        db_pool_metrics,
         exchange_type,
         environment,
         cf_jwks_url,
@@ -385,6 +496,21 @@ async fn main() {
         runner_state.runner.run(&runner_state.session_semaphores).await;
     });
 
+    // Spawn idempotency-key purge (expired entries are otherwise only
+    // replaced when their key is reused)
+    let purge_pool = state.pool.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            match harman::db::purge_idempotency_keys(&purge_pool, api::IDEMPOTENCY_TTL).await {
+                Ok(purged) if purged > 0 => info!(purged, "purged expired idempotency keys"),
+                Ok(_) => {}
+                Err(e) => error!(error = %e, "idempotency key purge failed"),
+            }
+        }
+    });
+
     // Spawn PriceMonitor if configured
     if let Some(monitor) = price_monitor_task {
         // Crash recovery: reload any orders in Monitoring state
@@ -424,8 +550,9 @@ async fn main() {
 
     // Spawn shutdown handler
     let shutdown_state = state.clone();
+    let shutdown_mode = args.shutdown_mode;
     let shutdown_handle = tokio::spawn(async move {
-        shutdown::wait_for_shutdown(shutdown_state).await;
+        shutdown::wait_for_shutdown(shutdown_state, shutdown_mode).await;
     });
 
     // Start API server
@@ -455,3 +582,70 @@ async fn main() {
 
     info!("ssmd-harman stopped");
 }
+
+/// Exchange types harman knows about but has no adapter for yet.
+const PLANNED_EXCHANGE_TYPES: &[&str] = &["polymarket", "kraken"];
+
+/// Build the exchange adapter for `EXCHANGE_TYPE`.
+///
+/// - `kalshi`: real Kalshi REST client against `kalshi_base_url`, after checking
+///   the URL matches `environment` (prod must not point at demo and vice versa).
+/// - `test`: Kalshi protocol against harman-test-exchange at `exchange_base_url`,
+///   with dummy credentials (the test exchange ignores auth headers).
+fn build_exchange(
+    exchange_type: &str,
+    environment: &str,
+    kalshi_base_url: &str,
+    exchange_base_url: &str,
+) -> Result<Arc<dyn harman::exchange::ExchangeAdapter>, String> {
+    match exchange_type {
+        "kalshi" => {
+            validate_kalshi_base_url(environment, kalshi_base_url)?;
+            let kalshi_config = ssmd_connector_lib::kalshi::config::KalshiConfig::from_env()
+                .map_err(|e| format!("Kalshi credentials not configured: {}", e))?;
+            let credentials = ssmd_connector_lib::kalshi::auth::KalshiCredentials::new(
+                kalshi_config.api_key,
+                &kalshi_config.private_key_pem,
+            )
+            .map_err(|e| format!("invalid Kalshi credentials: {}", e))?;
+            Ok(Arc::new(ssmd_exchange_kalshi::client::KalshiRestClient::new(
+                credentials,
+                kalshi_base_url.to_string(),
+            )))
+        }
+        "test" => {
+            info!(base_url = %exchange_base_url, "using test exchange (Kalshi protocol)");
+            let credentials = ssmd_connector_lib::kalshi::auth::KalshiCredentials::dummy();
+            Ok(Arc::new(ssmd_exchange_kalshi::client::KalshiRestClient::new(
+                credentials,
+                exchange_base_url.to_string(),
+            )))
+        }
+        other if PLANNED_EXCHANGE_TYPES.contains(&other) => Err(format!(
+            "EXCHANGE_TYPE={} has no harman adapter yet (supported: kalshi, test)",
+            other
+        )),
+        other => Err(format!(
+            "unsupported EXCHANGE_TYPE={} (expected: kalshi, test)",
+            other
+        )),
+    }
+}
+
+/// Detect a Kalshi base URL / environment mismatch before trading against it.
+fn validate_kalshi_base_url(environment: &str, base_url: &str) -> Result<(), String> {
+    let base_url_lower = base_url.to_lowercase();
+    if environment == "prod" && base_url_lower.contains("demo") {
+        return Err(format!(
+            "EXCHANGE_ENVIRONMENT=prod but KALSHI_BASE_URL contains 'demo' ({})",
+            base_url
+        ));
+    }
+    if environment == "demo" && !base_url_lower.contains("demo") {
+        return Err(format!(
+            "EXCHANGE_ENVIRONMENT=demo but KALSHI_BASE_URL does not contain 'demo' ({})",
+            base_url
+        ));
+    }
+    Ok(())
+}
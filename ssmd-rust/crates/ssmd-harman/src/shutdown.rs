@@ -1,18 +1,23 @@
 use std::sync::Arc;
 use tracing::info;
 
+use ssmd_harman_ems::ShutdownMode;
+
 use crate::AppState;
 
 /// Wait for shutdown signal (SIGTERM or ctrl-c) and initiate graceful shutdown.
 ///
 /// Signal listening stays in the binary. Shutdown execution is delegated to EMS.
-pub async fn wait_for_shutdown(state: Arc<AppState>) {
+pub async fn wait_for_shutdown(state: Arc<AppState>, mode: ShutdownMode) {
     shutdown_signal().await;
     info!("shutdown signal received");
     // Stop background tasks first (auto-pump, auto-reconcile)
     state.runner.shutdown();
-    // Then EMS shutdown (mass cancel + drain)
-    state.ems.shutdown().await;
+    // Then EMS shutdown (drain, cancel resting, or hold per mode)
+    state
+        .ems
+        .shutdown(mode, &state.exchange_type, &state.environment)
+        .await;
 }
 
 /// Listen for SIGTERM (Kubernetes pod termination) or ctrl-c.
@@ -50,7 +50,7 @@ async fn build_test_state(
     ));
     let oms_metrics = Arc::new(OmsMetrics::new(&registry));
     let oms = Arc::new(Oms::new(pool.clone(), exchange, ems.clone(), oms_metrics, audit_sender));
-    let runner = Arc::new(OmsRunner::new(oms.clone(), None, session_id, None, None));
+    let runner = Arc::new(OmsRunner::new(oms.clone(), None, session_id, "test", "test", None, None));
     let pump_trigger = runner.pump_trigger();
     Arc::new(AppState {
         ems,
@@ -66,12 +66,17 @@ async fn build_test_state(
         auto_pump: false,
         pump_trigger,
         session_semaphores: DashMap::new(),
+        rate_limiters: DashMap::new(),
+        order_rate_limit: 0,
         auth_cache: tokio::sync::RwLock::new(LruCache::new(NonZeroUsize::new(512).unwrap())),
+        auth_cache_ttl: std::time::Duration::from_secs(30),
+        auth_cache_salt: AppState::new_auth_cache_salt(),
         key_sessions: DashMap::new(),
         pump_semaphore: tokio::sync::Semaphore::new(1),
         ticker_cache: tokio::sync::RwLock::new(None),
         redis_conn: None,
         monitor_metrics: ssmd_harman::MonitorMetrics::new(&prometheus::Registry::new()),
+        db_pool_metrics: ssmd_harman::DbPoolMetrics::new(&prometheus::Registry::new()),
         exchange_type: "kalshi".to_string(),
         environment: "demo".to_string(),
         cf_jwks_url: None,
@@ -1255,6 +1260,8 @@ fn test_order_request(ticker: &str, side: Side, action: Action, qty: Decimal, pr
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     }
 }
 
@@ -1474,7 +1481,7 @@ async fn test_oco_creation() {
     let leg1 = test_order_request("KXTEST-OCO-1", Side::Yes, Action::Buy, Decimal::from(5), Decimal::new(40, 2));
     let leg2 = test_order_request("KXTEST-OCO-2", Side::No, Action::Buy, Decimal::from(5), Decimal::new(60, 2));
 
-    let (group, orders) = app_state.oms.create_oco(session_id, leg1, leg2).await.unwrap();
+    let (group, orders) = app_state.oms.create_oco(session_id, leg1, leg2, None).await.unwrap();
 
     assert_eq!(group.group_type, harman::types::GroupType::Oco);
     assert_eq!(orders.len(), 2);
@@ -1501,7 +1508,7 @@ async fn test_oco_fill_cancels_other() {
     let leg1 = test_order_request("KXTEST-OCO-3", Side::Yes, Action::Buy, Decimal::from(5), Decimal::new(40, 2));
     let leg2 = test_order_request("KXTEST-OCO-4", Side::No, Action::Buy, Decimal::from(5), Decimal::new(60, 2));
 
-    let (group, orders) = app_state.oms.create_oco(session_id, leg1, leg2).await.unwrap();
+    let (group, orders) = app_state.oms.create_oco(session_id, leg1, leg2, None).await.unwrap();
 
     // Simulate first leg submitted, acknowledged and filled (walk through valid transitions)
     db::update_order_state(
@@ -1535,6 +1542,56 @@ async fn test_oco_fill_cancels_other() {
     assert_eq!(group_now.state, harman::types::GroupState::Completed);
 }
 
+// Peg OCO: amending leg1 enqueues one amend on leg2 that keeps the spread
+#[tokio::test]
+#[ignore]
+async fn test_oco_peg_reprices_leg2() {
+    let (pool, session_id) = setup().await;
+
+    let mock = MockExchange::new();
+    let app_state = build_test_state(mock, pool.clone(), session_id).await;
+
+    let leg1 = test_order_request("KXTEST-PEG-1", Side::Yes, Action::Buy, Decimal::from(5), Decimal::new(40, 2));
+    let leg2 = test_order_request("KXTEST-PEG-2", Side::Yes, Action::Sell, Decimal::from(5), Decimal::new(60, 2));
+    let peg = harman::types::PegConfig {
+        spread_dollars: Decimal::new(20, 2),
+        min_reprice_dollars: Decimal::new(2, 2),
+    };
+
+    let (group, orders) = app_state.oms.create_oco(session_id, leg1, leg2, Some(peg)).await.unwrap();
+    assert_eq!(group.link_mode, harman::types::LinkMode::Peg);
+    assert_eq!(group.peg, Some(peg));
+
+    walk_to_state(&pool, orders[0].id, session_id, OrderState::Acknowledged).await;
+    walk_to_state(&pool, orders[1].id, session_id, OrderState::Acknowledged).await;
+
+    // In line with the spread: nothing to do
+    assert_eq!(app_state.oms.evaluate_triggers(session_id).await.unwrap(), 0);
+
+    // leg1 amended to $0.45 → leg2 should follow to $0.65
+    pool.get()
+        .await
+        .unwrap()
+        .execute("UPDATE prediction_orders SET price_dollars = $1 WHERE id = $2", &[&Decimal::new(45, 2), &orders[0].id])
+        .await
+        .unwrap();
+    assert_eq!(app_state.oms.evaluate_triggers(session_id).await.unwrap(), 1);
+    assert_order_state(&pool, orders[1].id, OrderState::PendingAmend).await.unwrap();
+
+    let metadata: serde_json::Value = pool
+        .get()
+        .await
+        .unwrap()
+        .query_one("SELECT metadata FROM order_queue WHERE order_id = $1 AND action = 'amend'", &[&orders[1].id])
+        .await
+        .unwrap()
+        .get("metadata");
+    assert_eq!(metadata["new_price_dollars"], "0.65");
+
+    // Amend in flight: no second reprice
+    assert_eq!(app_state.oms.evaluate_triggers(session_id).await.unwrap(), 0);
+}
+
 // =============================================================================
 // Test 36: Cancel group — staged legs cancelled directly, open legs get cancel
 // =============================================================================
@@ -1639,3 +1696,386 @@ async fn test_stable_session_idempotent_upsert() {
         .await.unwrap();
     assert_ne!(id1, id_demo, "different environment should get different session");
 }
+
+// =============================================================================
+// Test 39: Idempotency-Key store and replay
+//
+// Scenario: The first request claims the key; a concurrent duplicate sees it in
+// progress, and a reuse with a different body is a mismatch. The first response
+// is stored and replayed; a second store never overwrites it. A released claim
+// can be taken again, and so can one whose lease ran out.
+// =============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_idempotency_key_store_and_replay() {
+    let (pool, session_id) = setup().await;
+    let ttl = std::time::Duration::from_secs(60);
+    let lease = std::time::Duration::from_secs(30);
+    let key = format!("idem-{}", Uuid::new_v4());
+    let request = db::IdempotencyRequest {
+        method: "POST".to_string(),
+        path: "/v1/orders/1/amend".to_string(),
+        request_hash: "body-1".to_string(),
+    };
+
+    let claim = db::claim_idempotency_key(&pool, session_id, &key, &request, ttl, lease).await.unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::Claimed));
+
+    // A concurrent duplicate sees the claim, not a second Claimed
+    let claim = db::claim_idempotency_key(&pool, session_id, &key, &request, ttl, lease).await.unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::InProgress));
+
+    // The same key with a different body is rejected, not treated as a retry
+    let other_body = db::IdempotencyRequest {
+        request_hash: "body-2".to_string(),
+        ..request.clone()
+    };
+    let claim = db::claim_idempotency_key(&pool, session_id, &key, &other_body, ttl, lease).await.unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::Mismatch));
+
+    let first = db::IdempotentResponse {
+        method: request.method.clone(),
+        path: request.path.clone(),
+        status_code: 200,
+        response_body: r#"{"status":"pending_amend"}"#.to_string(),
+        response_hash: "hash-1".to_string(),
+    };
+    db::complete_idempotency_key(&pool, session_id, &key, &first).await.unwrap();
+
+    let second = db::IdempotentResponse {
+        status_code: 409,
+        response_body: r#"{"error":"conflict"}"#.to_string(),
+        response_hash: "hash-2".to_string(),
+        ..first.clone()
+    };
+    db::complete_idempotency_key(&pool, session_id, &key, &second).await.unwrap();
+
+    let replay = match db::claim_idempotency_key(&pool, session_id, &key, &request, ttl, lease).await.unwrap() {
+        db::IdempotencyClaim::Completed(stored) => stored,
+        other => panic!("stored response should be found, got {:?}", other),
+    };
+    assert_eq!(replay.status_code, 200);
+    assert_eq!(replay.response_body, first.response_body);
+    assert_eq!(replay.path, request.path);
+
+    // A completed entry is replayed even after the lease; only the ttl expires it
+    let claim = db::claim_idempotency_key(&pool, session_id, &key, &request, ttl, std::time::Duration::ZERO)
+        .await
+        .unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::Completed(_)));
+
+    // A released claim (5xx) can be claimed again by a retry
+    let retry_key = format!("idem-{}", Uuid::new_v4());
+    db::claim_idempotency_key(&pool, session_id, &retry_key, &request, ttl, lease).await.unwrap();
+    db::release_idempotency_key(&pool, session_id, &retry_key).await.unwrap();
+    let claim = db::claim_idempotency_key(&pool, session_id, &retry_key, &request, ttl, lease).await.unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::Claimed));
+
+    // A claim never completed or released (crashed or dropped handler) is taken
+    // over once its lease runs out
+    let abandoned_key = format!("idem-{}", Uuid::new_v4());
+    db::claim_idempotency_key(&pool, session_id, &abandoned_key, &request, ttl, lease).await.unwrap();
+    let claim = db::claim_idempotency_key(&pool, session_id, &abandoned_key, &request, ttl, lease).await.unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::InProgress));
+    let claim = db::claim_idempotency_key(&pool, session_id, &abandoned_key, &request, ttl, std::time::Duration::ZERO)
+        .await
+        .unwrap();
+    assert!(matches!(claim, db::IdempotencyClaim::Claimed));
+}
+
+// =============================================================================
+// Test 40: good_till expiry boundary
+//
+// Scenario: A resting GTC order is not expired one microsecond before its
+// good_till, and is expired (PendingCancel, reason expired, cancel enqueued)
+// when the sweep runs exactly at good_till.
+// =============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_good_till_expiry_boundary() {
+    let (pool, session_id) = setup().await;
+
+    let mock = MockExchange::new();
+    let app_state = build_test_state(mock, pool.clone(), session_id).await;
+
+    // Postgres stores microseconds; truncate so the boundary compares exactly
+    let good_till = chrono::Utc::now() + chrono::Duration::hours(1);
+    let good_till = chrono::DateTime::from_timestamp_micros(good_till.timestamp_micros()).unwrap();
+
+    let mut req = test_order_request("KXTEST-GTD", Side::Yes, Action::Buy, Decimal::from(1), Decimal::new(50, 2));
+    req.good_till = Some(good_till);
+    let order = app_state.ems.enqueue(session_id, &req, "test").await.unwrap();
+    assert_eq!(order.good_till, Some(good_till));
+    walk_to_state(&pool, order.id, session_id, OrderState::Acknowledged).await;
+
+    // Just before good_till: untouched
+    let expired = db::expire_orders(&pool, good_till - chrono::Duration::microseconds(1), "test", "test")
+        .await
+        .unwrap();
+    assert!(expired.iter().all(|e| e.order_id != order.id));
+    assert_order_state(&pool, order.id, OrderState::Acknowledged).await.unwrap();
+
+    // Exactly at good_till: expired
+    let queued_before = queue_count(&pool, session_id).await.unwrap();
+    let expired = db::expire_orders(&pool, good_till, "test", "test").await.unwrap();
+    assert!(expired.iter().any(|e| e.order_id == order.id && e.session_id == session_id));
+    assert_order_state(&pool, order.id, OrderState::PendingCancel).await.unwrap();
+    assert_eq!(queue_count(&pool, session_id).await.unwrap(), queued_before + 1);
+
+    let stored = db::get_order(&pool, order.id, session_id).await.unwrap().unwrap();
+    assert_eq!(stored.cancel_reason, Some(harman::types::CancelReason::Expired));
+    assert_eq!(stored.good_till, Some(good_till));
+
+    // A second sweep is a no-op for the order
+    let expired = db::expire_orders(&pool, good_till, "test", "test").await.unwrap();
+    assert!(expired.iter().all(|e| e.order_id != order.id));
+
+    // Another instance's sweeper never touches this session's orders
+    let mut other = test_order_request("KXTEST-GTD", Side::Yes, Action::Buy, Decimal::from(1), Decimal::new(50, 2));
+    other.good_till = Some(good_till);
+    let other = app_state.ems.enqueue(session_id, &other, "test").await.unwrap();
+    walk_to_state(&pool, other.id, session_id, OrderState::Acknowledged).await;
+    let expired = db::expire_orders(&pool, good_till, "test", "demo").await.unwrap();
+    assert!(expired.iter().all(|e| e.session_id != session_id));
+    assert_order_state(&pool, other.id, OrderState::Acknowledged).await.unwrap();
+}
+
+// =============================================================================
+// Test 41: Closed session rotates to a new session id
+//
+// Scenario: After close_session, the same key prefix for the same
+// (exchange, environment) resolves to a fresh session; closing twice is a no-op.
+// =============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_closed_session_key_resolves_to_new_session() {
+    let pool = setup_test_db().await.expect("setup_test_db failed");
+    let prefix = format!("close-{}", Uuid::new_v4());
+
+    // Use the demo environment so the shared test/test session stays open
+    let old_id = db::get_or_create_session(&pool, "test", "demo", Some(&prefix))
+        .await.unwrap();
+    assert!(db::list_open_orders(&pool, old_id).await.unwrap().is_empty());
+    assert!(db::is_open_session(&pool, old_id, "test", "demo").await.unwrap());
+    assert!(
+        !db::is_open_session(&pool, old_id, "test", "prod").await.unwrap(),
+        "another environment's instance must not see the session"
+    );
+
+    assert!(db::close_session(&pool, old_id, "test", "demo").await.unwrap());
+    assert!(!db::is_open_session(&pool, old_id, "test", "demo").await.unwrap());
+    assert!(!db::close_session(&pool, old_id, "test", "demo").await.unwrap(), "already closed");
+
+    let new_id = db::get_or_create_session(&pool, "test", "demo", Some(&prefix))
+        .await.unwrap();
+    assert_ne!(old_id, new_id, "closed session's key should resolve to a new session");
+
+    let startup = db::find_startup_session(&pool, "test", "demo").await.unwrap();
+    assert_eq!(startup, Some(new_id));
+
+    let open_ids = db::list_session_ids(&pool, "test", "demo").await.unwrap();
+    assert!(open_ids.contains(&new_id));
+    assert!(!open_ids.contains(&old_id));
+}
+
+// Audit rows written on behalf of an API caller carry `api:<key_prefix>` as the actor
+#[tokio::test]
+#[ignore]
+async fn test_audit_actor_records_key_prefix() {
+    let (pool, session_id) = setup().await;
+
+    let mock = MockExchange::new();
+    let app_state = build_test_state(mock, pool.clone(), session_id).await;
+    let ctx = ssmd_harman::SessionContext {
+        session_id,
+        scopes: vec!["harman:write".to_string()],
+        key_prefix: "ab12cd".to_string(),
+        email: None,
+    };
+
+    let req = test_order_request("KXTEST-ACTOR", Side::Yes, Action::Buy, Decimal::from(1), Decimal::new(50, 2));
+    let order = app_state.ems.enqueue(session_id, &req, &ctx.audit_actor()).await.unwrap();
+    walk_to_state(&pool, order.id, session_id, OrderState::Acknowledged).await;
+    app_state
+        .ems
+        .enqueue_cancel(order.id, session_id, &harman::types::CancelReason::UserRequested, &ctx.audit_actor())
+        .await
+        .unwrap();
+
+    let page = db::list_audit_log(&pool, session_id, None, 100).await.unwrap();
+    let entries: Vec<_> = page.items.iter().filter(|e| e.order_id == order.id).collect();
+    let created = entries.iter().find(|e| e.event == "created").expect("created audit row");
+    assert_eq!(created.actor, "api:ab12cd");
+    let cancel = entries.iter().find(|e| e.to_state == "pending_cancel").expect("cancel audit row");
+    assert_eq!(cancel.actor, "api:ab12cd");
+
+    let queue_actor: String = pool
+        .get()
+        .await
+        .unwrap()
+        .query_one("SELECT actor FROM order_queue WHERE order_id = $1 AND action = 'cancel'", &[&order.id])
+        .await
+        .unwrap()
+        .get("actor");
+    assert_eq!(queue_actor, "api:ab12cd");
+}
+
+// Auto-reconcile only lists sessions of this instance's exchange+environment,
+// including closed ones that still have open orders
+#[tokio::test]
+#[ignore]
+async fn test_active_sessions_scoped_to_instance() {
+    let (pool, session_id) = setup().await;
+    let other_id = db::get_or_create_session(&pool, "test", "demo", Some(&format!("scope-{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+
+    for sid in [session_id, other_id] {
+        let req = test_order_request("KXTEST-SCOPE", Side::Yes, Action::Buy, Decimal::from(1), Decimal::new(10, 2));
+        db::enqueue_order(&pool, &req, sid, &RiskLimits::default(), "test").await.unwrap();
+    }
+
+    let active = db::list_active_session_ids(&pool, "test", "test").await.unwrap();
+    assert!(active.contains(&session_id));
+    assert!(!active.contains(&other_id), "another environment's session must not be listed");
+
+    let active_demo = db::list_active_session_ids(&pool, "test", "demo").await.unwrap();
+    assert!(active_demo.contains(&other_id));
+
+    // A closed session keeps being reconciled until its orders settle
+    assert!(db::close_session(&pool, other_id, "test", "demo").await.unwrap());
+    let active_demo = db::list_active_session_ids(&pool, "test", "demo").await.unwrap();
+    assert!(active_demo.contains(&other_id), "closed session with open orders must still be listed");
+}
+
+// =============================================================================
+// Test 42: Batch rate limiting
+//
+// Scenario: With the global limit disabled (0), a session override of 5/s
+// still applies, and a batch is charged one token per order: a 3-order batch
+// passes, a second one is rejected with 429 and Retry-After.
+// =============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_order_batch_rate_limited_per_order() {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    let pool = setup_test_db().await.expect("setup_test_db failed");
+    let session_id = db::get_or_create_session(&pool, "test", "demo", Some(&format!("rl-{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+    pool.get()
+        .await
+        .unwrap()
+        .execute("UPDATE sessions SET order_rate_limit = 5 WHERE id = $1", &[&session_id])
+        .await
+        .unwrap();
+
+    // build_test_state leaves the global ORDER_RATE_LIMIT at 0
+    let state = build_test_state(MockExchange::new(), pool.clone(), session_id).await;
+    let app = ssmd_harman::api::router(state);
+
+    let batch = || {
+        let orders: Vec<_> = (0..3)
+            .map(|i| {
+                serde_json::json!({
+                    "client_order_id": Uuid::new_v4(),
+                    "ticker": format!("KXTEST-RL-{}", i),
+                    "side": "yes",
+                    "action": "buy",
+                    "quantity": "1",
+                    "price_dollars": "0.50",
+                })
+            })
+            .collect();
+        Request::post("/v1/orders/batch")
+            .header("authorization", "Bearer test-api-token")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "orders": orders }).to_string()))
+            .unwrap()
+    };
+
+    let first = app.clone().oneshot(batch()).await.unwrap();
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    // 2 tokens left: a second batch of 3 must wait
+    let second = app.oneshot(batch()).await.unwrap();
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after = second
+        .headers()
+        .get("retry-after")
+        .expect("429 should carry Retry-After")
+        .to_str()
+        .unwrap();
+    assert_eq!(retry_after, "1");
+}
+
+// =============================================================================
+// Test 43: Idempotency-Key retry after a rate-limited request
+//
+// Scenario: A request refused with 429 doesn't store its response, so the
+// same Idempotency-Key succeeds once the bucket refills instead of replaying
+// the 429.
+// =============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_idempotency_key_retry_after_rate_limit() {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    let pool = setup_test_db().await.expect("setup_test_db failed");
+    let session_id = db::get_or_create_session(&pool, "test", "demo", Some(&format!("rl-{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+    pool.get()
+        .await
+        .unwrap()
+        .execute("UPDATE sessions SET order_rate_limit = 1 WHERE id = $1", &[&session_id])
+        .await
+        .unwrap();
+
+    let state = build_test_state(MockExchange::new(), pool.clone(), session_id).await;
+    let app = ssmd_harman::api::router(state);
+
+    let order = |client_order_id: Uuid, ticker: &str, key: Option<&str>| {
+        let body = serde_json::json!({
+            "client_order_id": client_order_id,
+            "ticker": ticker,
+            "side": "yes",
+            "action": "buy",
+            "quantity": "1",
+            "price_dollars": "0.50",
+        });
+        let mut req = Request::post("/v1/orders")
+            .header("authorization", "Bearer test-api-token")
+            .header("content-type", "application/json");
+        if let Some(key) = key {
+            req = req.header("idempotency-key", key);
+        }
+        req.body(Body::from(body.to_string())).unwrap()
+    };
+
+    // Spend the only token
+    let first = app.clone().oneshot(order(Uuid::new_v4(), "KXTEST-IDEM-RL-1", None)).await.unwrap();
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    // The retry resends the identical request body
+    let key = format!("idem-{}", Uuid::new_v4());
+    let cid = Uuid::new_v4();
+    let limited = app.clone().oneshot(order(cid, "KXTEST-IDEM-RL-2", Some(&key))).await.unwrap();
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let retry = app.oneshot(order(cid, "KXTEST-IDEM-RL-2", Some(&key))).await.unwrap();
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert!(retry.headers().get("x-idempotent-replay").is_none());
+}
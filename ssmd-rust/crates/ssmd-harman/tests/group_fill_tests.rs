@@ -67,6 +67,8 @@ fn test_order_request(
         time_in_force: TimeInForce::Gtc,
         order_type: harman::types::OrderType::default(),
         trigger_price: None,
+        good_till: None,
+        reduce_only: false,
     }
 }
 
@@ -407,7 +409,7 @@ async fn test_handle_group_on_fill_oco() {
         Decimal::from(5), Decimal::new(60, 2),
     );
 
-    let (_group, orders) = oms.create_oco(session_id, leg1, leg2).await.unwrap();
+    let (_group, orders) = oms.create_oco(session_id, leg1, leg2, None).await.unwrap();
     let first = &orders[0];
 
     // Drain queue items
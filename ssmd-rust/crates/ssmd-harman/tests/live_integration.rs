@@ -382,6 +382,19 @@ impl TestClient {
         resp.json().await.expect("list_fills json")
     }
 
+    async fn list_order_fills(&self, id: i64) -> (reqwest::StatusCode, Value) {
+        let resp = self
+            .client
+            .get(format!("{}/v1/orders/{}/fills", self.base_url, id))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .expect("list_order_fills request");
+        let status = resp.status();
+        let json: Value = resp.json().await.expect("list_order_fills json");
+        (status, json)
+    }
+
     async fn list_audit(&self) -> Value {
         let resp = self
             .client
@@ -1266,4 +1279,19 @@ async fn test_live_fills_and_audit() {
         id,
         &audit_arr[..audit_arr.len().min(5)]
     );
+
+    // Per-order fills only contain fills for that order
+    let (status, json) = c.list_order_fills(id).await;
+    assert_eq!(status, 200, "order fills failed: {:?}", json);
+    let order_fills = json["fills"].as_array().expect("fills array");
+    assert!(
+        order_fills.iter().all(|f| f["order_id"].as_i64() == Some(id)),
+        "order fills should all belong to order {}: {:?}",
+        id,
+        order_fills
+    );
+
+    // Unknown order → 404
+    let (status, _) = c.list_order_fills(i64::MAX).await;
+    assert_eq!(status, 404, "fills for unknown order should 404");
 }
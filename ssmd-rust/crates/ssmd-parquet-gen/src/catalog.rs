@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::Result;
 use bytes::Bytes;
@@ -8,8 +8,10 @@ use tracing::{info, warn};
 
 use ssmd_schemas::SchemaRegistry;
 
-use crate::gcs::GcsClient;
-use crate::processor::{format_arrow_type, ManifestSchemaInfo, ParquetManifest, SchemaColumnDef};
+use crate::gcs::Storage;
+use crate::processor::{
+    format_arrow_type, ManifestSchemaInfo, ParquetManifest, Partition, SchemaColumnDef,
+};
 
 /// Root catalog aggregating all feeds
 #[derive(Debug, Serialize)]
@@ -32,6 +34,9 @@ pub struct FeedSummary {
     pub total_bytes: usize,
     pub total_rows: usize,
     pub dates: Vec<String>,
+    /// Layouts present across dates (`day`, `hour`); readers use this to
+    /// decide whether to expect `hour=HH/` directories under a date
+    pub partitions: BTreeSet<Partition>,
     pub schemas: BTreeMap<String, ManifestSchemaInfo>,
 }
 
@@ -70,8 +75,11 @@ const FEEDS: &[FeedDef] = &[
     },
 ];
 
+/// Objects listed per request when paging through manifests
+const MANIFEST_PAGE_SIZE: usize = 1000;
+
 /// Generate catalog.json from per-date manifests and write to GCS bucket root
-pub async fn generate_catalog(gcs: &GcsClient, output: &str) -> Result<()> {
+pub async fn generate_catalog(gcs: &dyn Storage, output: &str) -> Result<()> {
     let mut feeds = Vec::new();
 
     for def in FEEDS {
@@ -107,22 +115,9 @@ pub async fn generate_catalog(gcs: &GcsClient, output: &str) -> Result<()> {
     Ok(())
 }
 
-async fn build_feed_summary(gcs: &GcsClient, def: &FeedDef) -> Result<Option<FeedSummary>> {
-    // List all parquet-manifest.json files under {prefix}/{prefix}/{stream}/
+async fn build_feed_summary(gcs: &dyn Storage, def: &FeedDef) -> Result<Option<FeedSummary>> {
+    // Page through parquet-manifest.json files under {prefix}/{prefix}/{stream}/
     let search_prefix = format!("{}/{}/{}", def.prefix, def.prefix, def.stream);
-    let manifest_paths = gcs
-        .list_files_with_suffix(&search_prefix, "parquet-manifest.json")
-        .await?;
-
-    if manifest_paths.is_empty() {
-        return Ok(None);
-    }
-
-    info!(
-        feed = %def.feed,
-        count = manifest_paths.len(),
-        "Found manifest files"
-    );
 
     let registry = SchemaRegistry::for_feed(def.feed);
     let mut dates = Vec::new();
@@ -131,76 +126,106 @@ async fn build_feed_summary(gcs: &GcsClient, def: &FeedDef) -> Result<Option<Fee
     let mut total_rows: usize = 0;
     let mut schemas: BTreeMap<String, ManifestSchemaInfo> = BTreeMap::new();
     let mut message_types_set = BTreeMap::<String, ()>::new();
+    let mut manifest_count: usize = 0;
+    let mut partitions = BTreeSet::new();
+    let mut continuation_token: Option<String> = None;
 
-    for manifest_path in &manifest_paths {
-        let data = match gcs.get(manifest_path).await {
-            Ok(d) => d,
-            Err(e) => {
-                warn!(path = %manifest_path, error = %e, "Failed to read manifest, skipping");
-                continue;
-            }
-        };
+    loop {
+        let page = gcs
+            .list_page(&search_prefix, continuation_token.as_deref(), MANIFEST_PAGE_SIZE)
+            .await?;
 
-        let manifest: ParquetManifest = match serde_json::from_slice(&data) {
-            Ok(m) => m,
-            Err(e) => {
-                warn!(path = %manifest_path, error = %e, "Failed to parse manifest, skipping");
-                continue;
-            }
-        };
+        for manifest_path in page
+            .paths
+            .iter()
+            .filter(|p| p.ends_with("parquet-manifest.json"))
+        {
+            manifest_count += 1;
+            let data = match gcs.get(manifest_path).await {
+                Ok(d) => d,
+                Err(e) => {
+                    warn!(path = %manifest_path, error = %e, "Failed to read manifest, skipping");
+                    continue;
+                }
+            };
 
-        dates.push(manifest.date.clone());
+            let manifest: ParquetManifest = match serde_json::from_slice(&data) {
+                Ok(m) => m,
+                Err(e) => {
+                    warn!(path = %manifest_path, error = %e, "Failed to parse manifest, skipping");
+                    continue;
+                }
+            };
 
-        // Aggregate totals from manifest stats
-        for (msg_type, count) in &manifest.totals.records_written {
-            message_types_set.insert(msg_type.clone(), ());
-            total_rows += count;
-        }
+            dates.push(manifest.date.clone());
+            partitions.insert(manifest.partition);
 
-        // Use v2.0.0 files field if present, otherwise estimate from stats
-        if !manifest.files.is_empty() {
-            total_files += manifest.files.len();
-            total_bytes += manifest.files.iter().map(|f| f.bytes).sum::<usize>();
-        } else {
-            // v1.0.0 — count parquet files from records_written keys × hours
-            total_files += manifest.totals.records_written.len() * manifest.hours.len().max(1);
-        }
+            // Aggregate totals from manifest stats
+            for (msg_type, count) in &manifest.totals.records_written {
+                message_types_set.insert(msg_type.clone(), ());
+                total_rows += count;
+            }
 
-        // Merge schemas — prefer v2.0.0 manifest schemas, hydrate from registry for v1.0.0
-        if !manifest.schemas.is_empty() {
-            for (msg_type, schema_info) in manifest.schemas {
-                schemas.entry(msg_type).or_insert(schema_info);
+            // Use v2.0.0 files field if present, otherwise estimate from stats
+            if !manifest.files.is_empty() {
+                total_files += manifest.files.len();
+                total_bytes += manifest.files.iter().map(|f| f.bytes).sum::<usize>();
+            } else {
+                // v1.0.0 — count parquet files from records_written keys × hours
+                total_files += manifest.totals.records_written.len() * manifest.hours.len().max(1);
             }
-        } else {
-            // Hydrate from SchemaRegistry for v1.0.0 manifests
-            for msg_type in manifest.totals.records_written.keys() {
-                if schemas.contains_key(msg_type) {
-                    continue;
+
+            // Merge schemas — prefer v2.0.0 manifest schemas, hydrate from registry for v1.0.0
+            if !manifest.schemas.is_empty() {
+                for (msg_type, schema_info) in manifest.schemas {
+                    schemas.entry(msg_type).or_insert(schema_info);
                 }
-                if let Some(schema) = registry.get(msg_type) {
-                    let columns = schema
-                        .schema()
-                        .fields()
-                        .iter()
-                        .map(|f| SchemaColumnDef {
-                            name: f.name().clone(),
-                            arrow_type: format_arrow_type(f.data_type()),
-                            nullable: f.is_nullable(),
-                        })
-                        .collect();
-                    schemas.insert(
-                        msg_type.clone(),
-                        ManifestSchemaInfo {
-                            schema_name: schema.schema_name().to_string(),
-                            schema_version: schema.schema_version().to_string(),
-                            columns,
-                        },
-                    );
+            } else {
+                // Hydrate from SchemaRegistry for v1.0.0 manifests
+                for msg_type in manifest.totals.records_written.keys() {
+                    if schemas.contains_key(msg_type) {
+                        continue;
+                    }
+                    if let Some(schema) = registry.get(msg_type) {
+                        let columns = schema
+                            .schema()
+                            .fields()
+                            .iter()
+                            .map(|f| SchemaColumnDef {
+                                name: f.name().clone(),
+                                arrow_type: format_arrow_type(f.data_type()),
+                                nullable: f.is_nullable(),
+                            })
+                            .collect();
+                        schemas.insert(
+                            msg_type.clone(),
+                            ManifestSchemaInfo {
+                                schema_name: schema.schema_name().to_string(),
+                                schema_version: schema.schema_version().to_string(),
+                                columns,
+                            },
+                        );
+                    }
                 }
             }
         }
+
+        match page.next_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
     }
 
+    if manifest_count == 0 {
+        return Ok(None);
+    }
+
+    info!(
+        feed = %def.feed,
+        count = manifest_count,
+        "Found manifest files"
+    );
+
     dates.sort();
 
     let date_min = dates.first().cloned().unwrap_or_default();
@@ -218,6 +243,7 @@ async fn build_feed_summary(gcs: &GcsClient, def: &FeedDef) -> Result<Option<Fee
         total_bytes,
         total_rows,
         dates,
+        partitions,
         schemas,
     }))
 }
@@ -1,9 +1,67 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use bytes::Bytes;
 use object_store::gcp::GoogleCloudStorageBuilder;
 use object_store::path::Path as ObjectPath;
 use object_store::{ObjectStore, ObjectStoreExt, PutPayload};
+use std::path::Path;
 use std::sync::Arc;
+use tracing::warn;
+
+use crate::local_fs::LocalFsClient;
+
+/// One page of object paths from [`Storage::list_page`]
+pub struct ListPage {
+    pub paths: Vec<String>,
+    /// Pass back as `continuation_token` to fetch the next page; None when done
+    pub next_token: Option<String>,
+}
+
+/// Object storage used by the processor and catalog. Paths are
+/// `/`-separated keys relative to the bucket (or local root).
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// List all .jsonl.gz files under a prefix, sorted
+    async fn list_jsonl_files(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// List up to `max_keys` paths under a prefix, starting after
+    /// `continuation_token` (the `next_token` of the previous page).
+    async fn list_page(
+        &self,
+        prefix: &str,
+        continuation_token: Option<&str>,
+        max_keys: usize,
+    ) -> Result<ListPage>;
+
+    /// Download a file and return its bytes
+    async fn get(&self, path: &str) -> Result<Bytes>;
+
+    /// Download a file if it exists, returning None when it does not
+    async fn get_optional(&self, path: &str) -> Result<Option<Bytes>>;
+
+    /// Upload bytes to a path
+    async fn put(&self, path: &str, data: Bytes) -> Result<()>;
+
+    /// Upload via a temporary object and rename it into place, so readers
+    /// never see a partially written file at `path`. Without `overwrite` the
+    /// rename fails if `path` already exists; returns false in that case.
+    async fn put_atomic(&self, path: &str, data: Bytes, overwrite: bool) -> Result<bool>;
+
+    /// Check if a path exists
+    async fn exists(&self, path: &str) -> Result<bool>;
+}
+
+/// Pick the backend: `local_dir` or a `file://` bucket selects
+/// [`LocalFsClient`]; anything else is a GCS bucket name.
+pub fn open_storage(bucket: &str, local_dir: Option<&Path>) -> Result<Box<dyn Storage>> {
+    if let Some(dir) = local_dir {
+        return Ok(Box::new(LocalFsClient::new(dir)));
+    }
+    if let Some(dir) = bucket.strip_prefix("file://") {
+        return Ok(Box::new(LocalFsClient::new(dir)));
+    }
+    Ok(Box::new(GcsClient::from_env(bucket)?))
+}
 
 pub struct GcsClient {
     store: Arc<dyn ObjectStore>,
@@ -20,8 +78,18 @@ impl GcsClient {
         })
     }
 
-    /// List all .jsonl.gz files under a prefix
-    pub async fn list_jsonl_files(&self, prefix: &str) -> Result<Vec<String>> {
+    /// Delete a `put_atomic` temp object; already gone counts as deleted
+    async fn delete_tmp(&self, tmp_path: &ObjectPath) -> Result<()> {
+        match self.store.delete(tmp_path).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for GcsClient {
+    async fn list_jsonl_files(&self, prefix: &str) -> Result<Vec<String>> {
         use futures_util::StreamExt;
         let prefix_path = ObjectPath::from(prefix);
         let mut paths = Vec::new();
@@ -37,39 +105,74 @@ impl GcsClient {
         Ok(paths)
     }
 
-    /// Download a file and return its bytes
-    pub async fn get(&self, path: &str) -> Result<Bytes> {
+    /// Relies on GCS returning keys in lexicographic order.
+    async fn list_page(
+        &self,
+        prefix: &str,
+        continuation_token: Option<&str>,
+        max_keys: usize,
+    ) -> Result<ListPage> {
+        use futures_util::StreamExt;
+        let prefix_path = ObjectPath::from(prefix);
+        let mut stream = match continuation_token {
+            Some(token) => self
+                .store
+                .list_with_offset(Some(&prefix_path), &ObjectPath::from(token)),
+            None => self.store.list(Some(&prefix_path)),
+        };
+        let mut paths = Vec::with_capacity(max_keys);
+        let mut has_more = false;
+        while let Some(meta) = stream.next().await {
+            let meta = meta?;
+            if paths.len() == max_keys {
+                has_more = true;
+                break;
+            }
+            paths.push(meta.location.to_string());
+        }
+        let next_token = if has_more { paths.last().cloned() } else { None };
+        Ok(ListPage { paths, next_token })
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
         let obj_path = ObjectPath::from(path);
         let result = self.store.get(&obj_path).await?;
         Ok(result.bytes().await?)
     }
 
-    /// Upload bytes to a path
-    pub async fn put(&self, path: &str, data: Bytes) -> Result<()> {
+    async fn put(&self, path: &str, data: Bytes) -> Result<()> {
         let obj_path = ObjectPath::from(path);
         self.store.put(&obj_path, PutPayload::from(data)).await?;
         Ok(())
     }
 
-    /// List all files matching a suffix under a prefix
-    pub async fn list_files_with_suffix(&self, prefix: &str, suffix: &str) -> Result<Vec<String>> {
-        use futures_util::StreamExt;
-        let prefix_path = ObjectPath::from(prefix);
-        let mut paths = Vec::new();
-        let mut stream = self.store.list(Some(&prefix_path));
-        while let Some(meta) = stream.next().await {
-            let meta = meta?;
-            let path = meta.location.to_string();
-            if path.ends_with(suffix) {
-                paths.push(path);
+    async fn put_atomic(&self, path: &str, data: Bytes, overwrite: bool) -> Result<bool> {
+        let tmp_path = ObjectPath::from(format!("{}.inprogress", path));
+        let obj_path = ObjectPath::from(path);
+        self.store.put(&tmp_path, PutPayload::from(data)).await?;
+        let renamed = if overwrite {
+            self.store.rename(&tmp_path, &obj_path).await
+        } else {
+            self.store.rename_if_not_exists(&tmp_path, &obj_path).await
+        };
+        match renamed {
+            Ok(()) => Ok(true),
+            Err(object_store::Error::AlreadyExists { .. }) => {
+                self.delete_tmp(&tmp_path).await?;
+                Ok(false)
+            }
+            Err(e) => {
+                // Don't leave the temp object behind; the rename error is the
+                // one worth reporting
+                if let Err(cleanup) = self.delete_tmp(&tmp_path).await {
+                    warn!(path = %tmp_path, error = %cleanup, "failed to delete temp object");
+                }
+                Err(e.into())
             }
         }
-        paths.sort();
-        Ok(paths)
     }
 
-    /// Check if a path exists
-    pub async fn exists(&self, path: &str) -> Result<bool> {
+    async fn exists(&self, path: &str) -> Result<bool> {
         let obj_path = ObjectPath::from(path);
         match self.store.head(&obj_path).await {
             Ok(_) => Ok(true),
@@ -78,8 +181,7 @@ impl GcsClient {
         }
     }
 
-    /// Download a file if it exists, returning None on NotFound
-    pub async fn get_optional(&self, path: &str) -> Result<Option<Bytes>> {
+    async fn get_optional(&self, path: &str) -> Result<Option<Bytes>> {
         let obj_path = ObjectPath::from(path);
         match self.store.get(&obj_path).await {
             Ok(result) => Ok(Some(result.bytes().await?)),
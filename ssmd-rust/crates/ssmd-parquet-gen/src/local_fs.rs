@@ -0,0 +1,175 @@
+//! Local filesystem [`Storage`] backend, so the processor can run end-to-end
+//! against a directory (tests, CI, offline reprocessing).
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::gcs::{ListPage, Storage};
+
+/// Stores objects as files under `root`; object keys map to relative paths.
+pub struct LocalFsClient {
+    root: PathBuf,
+}
+
+impl LocalFsClient {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn full_path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// All object keys under `prefix`, sorted like a GCS listing
+    fn list_keys(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let start = self.full_path(prefix);
+        if start.is_dir() {
+            walk(&self.root, &start, &mut keys)?;
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+fn walk(root: &Path, dir: &Path, keys: &mut Vec<String>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk(root, &path, keys)?;
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            let key: Vec<_> = rel.iter().map(|c| c.to_string_lossy()).collect();
+            keys.push(key.join("/"));
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Storage for LocalFsClient {
+    async fn list_jsonl_files(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = self.list_keys(prefix)?;
+        keys.retain(|k| k.ends_with(".jsonl.gz"));
+        Ok(keys)
+    }
+
+    async fn list_page(
+        &self,
+        prefix: &str,
+        continuation_token: Option<&str>,
+        max_keys: usize,
+    ) -> Result<ListPage> {
+        let mut keys = self.list_keys(prefix)?;
+        if let Some(token) = continuation_token {
+            keys.retain(|k| k.as_str() > token);
+        }
+        let has_more = keys.len() > max_keys;
+        keys.truncate(max_keys);
+        let next_token = if has_more { keys.last().cloned() } else { None };
+        Ok(ListPage {
+            paths: keys,
+            next_token,
+        })
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        Ok(Bytes::from(tokio::fs::read(self.full_path(path)).await?))
+    }
+
+    async fn get_optional(&self, path: &str) -> Result<Option<Bytes>> {
+        match tokio::fs::read(self.full_path(path)).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn put(&self, path: &str, data: Bytes) -> Result<()> {
+        let full = self.full_path(path);
+        if let Some(parent) = full.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(full, data).await?;
+        Ok(())
+    }
+
+    async fn put_atomic(&self, path: &str, data: Bytes, overwrite: bool) -> Result<bool> {
+        let tmp_key = format!("{}.inprogress", path);
+        self.put(&tmp_key, data).await?;
+        let tmp = self.full_path(&tmp_key);
+        let dest = self.full_path(path);
+        if overwrite {
+            tokio::fs::rename(&tmp, &dest).await?;
+            return Ok(true);
+        }
+        // hard_link fails if dest exists, giving create-only semantics
+        let linked = tokio::fs::hard_link(&tmp, &dest).await;
+        tokio::fs::remove_file(&tmp).await?;
+        match linked {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.full_path(path)).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_put_atomic_create_only() {
+        let tmp = TempDir::new().unwrap();
+        let store = LocalFsClient::new(tmp.path());
+
+        assert!(store
+            .put_atomic("a/b/x.parquet", Bytes::from("one"), false)
+            .await
+            .unwrap());
+        assert!(!store
+            .put_atomic("a/b/x.parquet", Bytes::from("two"), false)
+            .await
+            .unwrap());
+        assert_eq!(
+            store.get("a/b/x.parquet").await.unwrap(),
+            Bytes::from("one")
+        );
+
+        assert!(store
+            .put_atomic("a/b/x.parquet", Bytes::from("three"), true)
+            .await
+            .unwrap());
+        assert_eq!(
+            store.get("a/b/x.parquet").await.unwrap(),
+            Bytes::from("three")
+        );
+        assert!(!store.exists("a/b/x.parquet.inprogress").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_page_continues_after_token() {
+        let tmp = TempDir::new().unwrap();
+        let store = LocalFsClient::new(tmp.path());
+        for key in ["p/1.json", "p/2.json", "p/3.json", "q/4.json"] {
+            store.put(key, Bytes::new()).await.unwrap();
+        }
+
+        let first = store.list_page("p", None, 2).await.unwrap();
+        assert_eq!(first.paths, vec!["p/1.json", "p/2.json"]);
+        let second = store
+            .list_page("p", first.next_token.as_deref(), 2)
+            .await
+            .unwrap();
+        assert_eq!(second.paths, vec!["p/3.json"]);
+        assert!(second.next_token.is_none());
+        assert!(store.get_optional("p/9.json").await.unwrap().is_none());
+    }
+}
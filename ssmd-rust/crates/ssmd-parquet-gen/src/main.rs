@@ -1,11 +1,13 @@
 use anyhow::{bail, Result};
 use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
 mod catalog;
 mod gcs;
+mod local_fs;
 mod processor;
 
 #[derive(Parser, Debug)]
@@ -53,9 +55,21 @@ struct Args {
     #[arg(long, default_value_t = false)]
     dry_run: bool,
 
+    /// Target row-group size in bytes (uncompressed, in-memory estimate)
+    #[arg(long, default_value_t = processor::DEFAULT_ROW_GROUP_BYTES)]
+    row_group_size: usize,
+
+    /// Output layout: `day` (flat per-date files) or `hour` (Hive-style `hour=HH/` partitions)
+    #[arg(long, value_enum, default_value = "day")]
+    partition: processor::Partition,
+
     /// Strict mode — exit non-zero if any messages have no registered schema
     #[arg(long, default_value_t = false)]
     strict: bool,
+
+    /// Read and write under this directory instead of GCS (also via `--bucket file://<dir>`)
+    #[arg(long, global = true)]
+    local_dir: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -84,8 +98,8 @@ async fn main() -> Result<()> {
     // Dispatch subcommand
     if let Some(Command::Catalog { bucket, output }) = args.command {
         info!(bucket = %bucket, output = %output, "Generating catalog");
-        let gcs = gcs::GcsClient::from_env(&bucket)?;
-        catalog::generate_catalog(&gcs, &output).await?;
+        let storage = gcs::open_storage(&bucket, args.local_dir.as_deref())?;
+        catalog::generate_catalog(storage.as_ref(), &output).await?;
         info!("Catalog generation complete");
         return Ok(());
     }
@@ -124,13 +138,15 @@ async fn main() -> Result<()> {
         hour_end = ?args.hour_end,
         overwrite = args.overwrite,
         dry_run = args.dry_run,
+        row_group_size = args.row_group_size,
+        partition = ?args.partition,
         "Starting parquet generation"
     );
 
-    let gcs = gcs::GcsClient::from_env(&bucket)?;
+    let storage = gcs::open_storage(&bucket, args.local_dir.as_deref())?;
 
     let stats = processor::process_date(
-        &gcs,
+        storage.as_ref(),
         prefix,
         &feed,
         &stream,
@@ -139,6 +155,8 @@ async fn main() -> Result<()> {
         args.hour_end,
         args.overwrite,
         args.dry_run,
+        args.row_group_size,
+        args.partition,
     )
     .await?;
 
@@ -1,9 +1,15 @@
 use std::collections::{BTreeMap, HashMap};
 use std::io::{BufRead, BufReader};
 use anyhow::{bail, Result};
+use arrow::array::{Array, AsArray, UInt32Array};
+use arrow::compute::{
+    cast, concat_batches, lexsort_to_indices, take_record_batch, SortColumn, SortOptions,
+};
+use arrow::datatypes::{DataType, Int64Type, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use bytes::Bytes;
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
 use flate2::read::GzDecoder;
 use parquet::arrow::ArrowWriter;
 use parquet::basic::Compression;
@@ -12,9 +18,28 @@ use parquet::file::properties::{EnabledStatistics, WriterProperties};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
-use ssmd_schemas::{detect_message_type, MessageSchema, SchemaRegistry};
+use ssmd_schemas::{align_batch, unify_schemas, MessageSchema, SchemaRegistry};
 
-use crate::gcs::GcsClient;
+use crate::gcs::Storage;
+
+/// Default target row-group size: ~128MB of in-memory column data
+pub const DEFAULT_ROW_GROUP_BYTES: usize = 128 * 1024 * 1024;
+
+/// Rows handed to the parquet writer per call; the row group is closed once
+/// its buffered size reaches the target, so this bounds the overshoot.
+const WRITE_CHUNK_ROWS: usize = 8192;
+
+/// Output layout under `{prefix}/{feed}/{stream}/{date}/`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Partition {
+    /// Flat per-date directory: `{type}_{HHMM}.parquet`, one file per archive hour.
+    #[default]
+    Day,
+    /// Hive-style `hour={HH}/{type}.parquet`, rows split by event-time hour.
+    Hour,
+}
 
 /// Per-file metadata entry in the manifest
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -105,6 +130,49 @@ where
     Ok(())
 }
 
+/// Outputs finished for a date, persisted as `parquet-checkpoint.json` next
+/// to the manifest after each (hour, type) completes. A restarted run skips
+/// anything listed here unless `--overwrite` is set.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Checkpoint {
+    /// Hour key ("14") -> message type -> files written for it
+    pub completed: BTreeMap<String, BTreeMap<String, Vec<ParquetFileEntry>>>,
+}
+
+impl Checkpoint {
+    /// Load the checkpoint at `path`; a missing or unreadable one starts fresh.
+    async fn load(gcs: &dyn Storage, path: &str) -> Self {
+        match gcs.get_optional(path).await {
+            Ok(Some(bytes)) => match serde_json::from_slice(&bytes) {
+                Ok(checkpoint) => {
+                    info!(path = %path, "Resuming from checkpoint");
+                    checkpoint
+                }
+                Err(e) => {
+                    warn!(path = %path, error = %e, "Failed to parse checkpoint, starting fresh");
+                    Self::default()
+                }
+            },
+            Ok(None) => Self::default(),
+            Err(e) => {
+                warn!(path = %path, error = %e, "Failed to read checkpoint, starting fresh");
+                Self::default()
+            }
+        }
+    }
+
+    async fn save(&self, gcs: &dyn Storage, path: &str) -> Result<()> {
+        gcs.put(path, Bytes::from(serde_json::to_vec(self)?)).await
+    }
+
+    fn completed_files(&self, hour_key: &str, msg_type: &str) -> Option<&[ParquetFileEntry]> {
+        self.completed
+            .get(hour_key)
+            .and_then(|types| types.get(msg_type))
+            .map(Vec::as_slice)
+    }
+}
+
 /// Stats for a single hour's processing
 #[derive(Debug, Default)]
 pub struct HourStats {
@@ -151,6 +219,10 @@ pub struct ParquetManifest {
     pub files: Vec<ParquetFileEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub schemas: BTreeMap<String, ManifestSchemaInfo>,
+    /// Layout of the files listed above; manifests written before hour
+    /// partitioning existed are `day`
+    #[serde(default)]
+    pub partition: Partition,
 }
 
 impl ManifestStats {
@@ -195,7 +267,7 @@ impl ManifestStats {
 /// Optional `hour_start`/`hour_end` filter processing to a range of hours (inclusive).
 #[allow(clippy::too_many_arguments)]
 pub async fn process_date(
-    gcs: &GcsClient,
+    gcs: &dyn Storage,
     gcs_prefix: &str,
     feed: &str,
     stream: &str,
@@ -204,6 +276,8 @@ pub async fn process_date(
     hour_end: Option<u32>,
     overwrite: bool,
     dry_run: bool,
+    row_group_bytes: usize,
+    partition: Partition,
 ) -> Result<Vec<HourStats>> {
     let registry = SchemaRegistry::for_feed(feed);
     let date_str = date.format("%Y-%m-%d").to_string();
@@ -258,6 +332,14 @@ pub async fn process_date(
         return Ok(Vec::new());
     }
 
+    // --overwrite redoes everything, so it starts from an empty checkpoint
+    let checkpoint_path = format!("{}/parquet-checkpoint.json", prefix);
+    let mut checkpoint = if overwrite {
+        Checkpoint::default()
+    } else {
+        Checkpoint::load(gcs, &checkpoint_path).await
+    };
+
     let mut all_stats = Vec::new();
 
     for hour_key in &hours {
@@ -278,6 +360,10 @@ pub async fn process_date(
             hour_files,
             hour_ts,
             overwrite,
+            row_group_bytes,
+            partition,
+            &mut checkpoint,
+            &checkpoint_path,
         )
         .await?;
 
@@ -285,7 +371,10 @@ pub async fn process_date(
     }
 
     if !all_stats.is_empty() {
-        write_or_merge_manifest(gcs, gcs_prefix, feed, stream, &date_str, &all_stats).await?;
+        write_or_merge_manifest(
+            gcs, gcs_prefix, feed, stream, &date_str, &all_stats, partition,
+        )
+        .await?;
     }
 
     Ok(all_stats)
@@ -300,7 +389,7 @@ pub async fn process_date(
 /// to the largest single type rather than all types combined.
 #[allow(clippy::too_many_arguments)]
 async fn process_hour(
-    gcs: &GcsClient,
+    gcs: &dyn Storage,
     registry: &SchemaRegistry,
     gcs_prefix: &str,
     feed: &str,
@@ -310,6 +399,10 @@ async fn process_hour(
     files: &[String],
     hour_ts: DateTime<Utc>,
     overwrite: bool,
+    row_group_bytes: usize,
+    partition: Partition,
+    checkpoint: &mut Checkpoint,
+    checkpoint_path: &str,
 ) -> Result<HourStats> {
     let mut stats = HourStats { hour_key: hour_key.to_string(), ..Default::default() };
     let hour_time_str = format!("{}00", hour_key);
@@ -349,7 +442,7 @@ async fn process_hour(
                 }
             };
 
-            let msg_type = match detect_message_type(feed, &json) {
+            let msg_type = match registry.detect(&json) {
                 Some(t) => t,
                 None => {
                     stats.lines_type_unknown += 1;
@@ -374,17 +467,31 @@ async fn process_hour(
     }
 
     // === Pass 2: Process one message type at a time ===
-    for (msg_type, count) in &type_counts {
+    for msg_type in type_counts.keys() {
         let schema = match registry.get(msg_type) {
             Some(s) => s,
             None => continue,
         };
 
+        // Finished by an earlier run that died before writing the manifest;
+        // carry its files forward so the manifest still lists them
+        if !overwrite {
+            if let Some(entries) = checkpoint.completed_files(hour_key, msg_type) {
+                info!(hour = %hour_key, msg_type = %msg_type, "Completed in checkpoint, skipping");
+                stats
+                    .records_by_type
+                    .insert(msg_type.clone(), entries.iter().map(|e| e.row_count).sum());
+                stats.files_written.extend(entries.iter().cloned());
+                continue;
+            }
+        }
+
         // Check if parquet already exists
-        let parquet_path = format!(
-            "{}/{}/{}/{}/{}_{}.parquet",
-            gcs_prefix, feed, stream, date_str, msg_type, hour_time_str
-        );
+        let date_dir = format!("{}/{}/{}/{}", gcs_prefix, feed, stream, date_str);
+        let parquet_path = match partition {
+            Partition::Day => format!("{}/{}_{}.parquet", date_dir, msg_type, hour_time_str),
+            Partition::Hour => hour_partition_path(&date_dir, hour_key, msg_type, hour_key),
+        };
 
         if !overwrite {
             match gcs.exists(&parquet_path).await {
@@ -399,11 +506,13 @@ async fn process_hour(
             }
         }
 
-        // Collect messages for THIS type only, re-reading cached compressed data
-        let mut messages: Vec<(Vec<u8>, u64, i64)> = Vec::with_capacity(*count);
+        // Collect messages for THIS type only, re-reading cached compressed data.
+        // Messages stay grouped by source file so each file parses to its own batch.
+        let mut messages_by_file: Vec<Vec<(Vec<u8>, u64, i64)>> = Vec::with_capacity(cached_files.len());
         let mut seq: u64 = 0;
 
         for compressed in &cached_files {
+            let mut messages = Vec::new();
             let _ = for_each_gzip_line(compressed, |line| {
                 if line.trim().is_empty() {
                     return;
@@ -414,7 +523,7 @@ async fn process_hour(
                     Err(_) => return,
                 };
 
-                let detected = match detect_message_type(feed, &json) {
+                let detected = match registry.detect(&json) {
                     Some(t) => t,
                     None => return,
                 };
@@ -424,22 +533,23 @@ async fn process_hour(
                 }
 
                 seq += 1;
-                let nats_seq = json
-                    .get("_nats_seq")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(seq);
-                let recv_at = json
-                    .get("_received_at")
-                    .and_then(|v| v.as_i64())
-                    .unwrap_or(fallback_received_at);
+                let (nats_seq, recv_at) = extract_metadata(&json, seq, fallback_received_at);
 
                 messages.push((line.as_bytes().to_vec(), nats_seq, recv_at));
             });
+            if !messages.is_empty() {
+                messages_by_file.push(messages);
+            }
         }
 
-        stats.parse_batch_input.insert(msg_type.clone(), messages.len());
+        let input_count: usize = messages_by_file.iter().map(Vec::len).sum();
+        stats.parse_batch_input.insert(msg_type.clone(), input_count);
 
-        let batch = match schema.parse_batch(&messages) {
+        let batches = match messages_by_file
+            .iter()
+            .map(|messages| schema.parse_batch(messages))
+            .collect::<Result<Vec<_>, _>>()
+        {
             Ok(b) => b,
             Err(e) => {
                 warn!(msg_type = %msg_type, error = %e, "Failed to parse batch, skipping");
@@ -447,47 +557,115 @@ async fn process_hour(
             }
         };
 
+        // Drop messages before writing parquet — batches own the Arrow arrays now
+        drop(messages_by_file);
+
+        let batch = match unify_batches(&batches) {
+            Ok(b) => b,
+            Err(e) => {
+                warn!(msg_type = %msg_type, error = %e, "Failed to unify batch schemas, skipping");
+                continue;
+            }
+        };
+        drop(batches);
+
         if batch.num_rows() == 0 {
             bail!(
                 "parse_batch returned 0 rows for type '{}' with {} input messages — likely schema field name mismatch",
-                msg_type, messages.len()
+                msg_type, input_count
             );
         }
 
-        let dropped = messages.len().saturating_sub(batch.num_rows());
+        let dropped = input_count.saturating_sub(batch.num_rows());
         if dropped > 0 {
             stats.parse_batch_dropped.insert(msg_type.clone(), dropped);
         }
 
-        // Drop messages before writing parquet — batch owns the Arrow arrays now
-        drop(messages);
+        let batch = match sort_by_timestamp(&batch, schema.timestamp_column()) {
+            Ok(b) => b,
+            Err(e) => {
+                warn!(msg_type = %msg_type, error = %e, "Failed to sort batch by timestamp, skipping");
+                continue;
+            }
+        };
+
+        // Day layout writes the whole archive hour to one file; hour layout
+        // splits it by event-time hour, so rows near a boundary land in the
+        // neighbouring partition
+        let outputs = match partition {
+            Partition::Day => vec![(hour_key.to_string(), parquet_path, batch)],
+            Partition::Hour => {
+                let default_hour = hour_key.parse::<u32>().unwrap_or_default();
+                let split = match split_by_event_hour(
+                    &batch,
+                    schema.timestamp_column(),
+                    hour_ts.date_naive(),
+                    default_hour,
+                ) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        warn!(msg_type = %msg_type, error = %e, "Failed to split batch by hour, skipping");
+                        continue;
+                    }
+                };
+                split
+                    .into_iter()
+                    .map(|(hour, part)| {
+                        let hh = format!("{:02}", hour);
+                        let path = hour_partition_path(&date_dir, &hh, msg_type, hour_key);
+                        (hh, path, part)
+                    })
+                    .collect()
+            }
+        };
 
-        let parquet_bytes = write_parquet_to_bytes(&batch, schema)?;
-        let bytes_len = parquet_bytes.len();
+        let mut type_entries = Vec::new();
+        let mut all_written = true;
+        for (hh, path, part) in outputs {
+            let parquet_bytes = write_parquet_to_bytes(&part, schema, row_group_bytes)?;
+            let bytes_len = parquet_bytes.len();
+
+            if !gcs
+                .put_atomic(&path, Bytes::from(parquet_bytes), overwrite)
+                .await?
+            {
+                // Another processor wrote this hour between our existence check and now
+                info!(path = %path, "Parquet written concurrently, skipping (use --overwrite to replace)");
+                all_written = false;
+                continue;
+            }
 
-        gcs.put(&parquet_path, Bytes::from(parquet_bytes)).await?;
+            info!(
+                path = %path,
+                records = part.num_rows(),
+                bytes = bytes_len,
+                "Wrote parquet file"
+            );
 
-        info!(
-            path = %parquet_path,
-            records = batch.num_rows(),
-            bytes = bytes_len,
-            "Wrote parquet file"
-        );
+            stats.parquet_files_written += 1;
+            *stats.records_by_type.entry(msg_type.clone()).or_default() += part.num_rows();
+            stats.bytes_written += bytes_len;
+            let entry = ParquetFileEntry {
+                path,
+                message_type: msg_type.clone(),
+                hour: format!("{}00", hh),
+                bytes: bytes_len,
+                row_count: part.num_rows(),
+                schema_name: schema.schema_name().to_string(),
+                schema_version: schema.schema_version().to_string(),
+            };
+            type_entries.push(entry.clone());
+            stats.files_written.push(entry);
+        }
 
-        stats.parquet_files_written += 1;
-        stats
-            .records_by_type
-            .insert(msg_type.clone(), batch.num_rows());
-        stats.bytes_written += bytes_len;
-        stats.files_written.push(ParquetFileEntry {
-            path: parquet_path.clone(),
-            message_type: msg_type.clone(),
-            hour: hour_time_str.clone(),
-            bytes: bytes_len,
-            row_count: batch.num_rows(),
-            schema_name: schema.schema_name().to_string(),
-            schema_version: schema.schema_version().to_string(),
-        });
+        if all_written {
+            checkpoint
+                .completed
+                .entry(hour_key.to_string())
+                .or_default()
+                .insert(msg_type.clone(), type_entries);
+            checkpoint.save(gcs, checkpoint_path).await?;
+        }
     }
 
     info!(
@@ -527,6 +705,20 @@ fn group_files_by_hour(files: &[String]) -> HashMap<String, Vec<String>> {
     by_hour
 }
 
+/// Extract archiver-injected metadata from a parsed JSON line.
+/// Returns (nats_seq, received_at_micros) using fallback values if absent.
+/// The connector's `_capture_ts` is preferred over the archiver's `_received_at`.
+fn extract_metadata(json: &serde_json::Value, fallback_seq: u64, fallback_received_at: i64) -> (u64, i64) {
+    let nats_seq = json.get("_nats_seq")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(fallback_seq);
+    let received_at = json.get("_capture_ts")
+        .or_else(|| json.get("_received_at"))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(fallback_received_at);
+    (nats_seq, received_at)
+}
+
 fn parse_hour_timestamp(date: &NaiveDate, hour_key: &str) -> Option<DateTime<Utc>> {
     let hour = hour_key.parse::<u32>().ok()?;
     if hour >= 24 {
@@ -537,24 +729,16 @@ fn parse_hour_timestamp(date: &NaiveDate, hour_key: &str) -> Option<DateTime<Utc
 
 #[cfg(test)]
 mod tests {
-    use super::{group_files_by_hour, parse_hour_timestamp};
+    use super::{group_files_by_hour, parse_hour_timestamp, unify_batches};
+    use std::sync::Arc;
+    use arrow::array::{Array, Int64Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
     use chrono::NaiveDate;
     use flate2::{write::GzEncoder, Compression};
     use std::io::Write;
 
-    use super::for_each_gzip_line;
-
-    /// Extract archiver-injected metadata from a parsed JSON line.
-    /// Returns (nats_seq, received_at_micros) using fallback values if absent.
-    fn extract_metadata(json: &serde_json::Value, fallback_seq: u64, fallback_received_at: i64) -> (u64, i64) {
-        let nats_seq = json.get("_nats_seq")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(fallback_seq);
-        let received_at = json.get("_received_at")
-            .and_then(|v| v.as_i64())
-            .unwrap_or(fallback_received_at);
-        (nats_seq, received_at)
-    }
+    use super::{extract_metadata, for_each_gzip_line};
 
     #[test]
     fn test_extract_metadata_with_injected_fields() {
@@ -566,6 +750,16 @@ mod tests {
         assert_eq!(recv_at, 1707667200123456);
     }
 
+    #[test]
+    fn test_extract_metadata_prefers_capture_ts() {
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{"type":"trade","_received_at":2000,"_capture_ts":1500,"_nats_seq":3}"#,
+        ).unwrap();
+        let (seq, recv_at) = extract_metadata(&json, 0, 0);
+        assert_eq!(seq, 3);
+        assert_eq!(recv_at, 1500);
+    }
+
     #[test]
     fn test_extract_metadata_falls_back_for_old_files() {
         let json: serde_json::Value = serde_json::from_str(
@@ -614,6 +808,29 @@ mod tests {
         assert!(parse_hour_timestamp(&date, "xx").is_none());
     }
 
+    #[test]
+    fn test_unify_batches_mixed_schema_versions() {
+        let old_schema = Arc::new(Schema::new(vec![Field::new("price", DataType::Int64, false)]));
+        let new_schema = Arc::new(Schema::new(vec![
+            Field::new("price", DataType::Int64, false),
+            Field::new("count", DataType::Int64, false),
+        ]));
+        let old = RecordBatch::try_new(old_schema, vec![Arc::new(Int64Array::from(vec![1, 2]))]).unwrap();
+        let new = RecordBatch::try_new(
+            new_schema,
+            vec![Arc::new(Int64Array::from(vec![3])), Arc::new(Int64Array::from(vec![7]))],
+        )
+        .unwrap();
+
+        let merged = unify_batches(&[old, new]).unwrap();
+        assert_eq!(merged.num_rows(), 3);
+        assert_eq!(merged.num_columns(), 2);
+        let count = merged.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert!(count.is_null(0));
+        assert!(count.is_null(1));
+        assert_eq!(count.value(2), 7);
+    }
+
     #[test]
     fn test_streaming_reader_handles_large_line_count() {
         let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
@@ -637,18 +854,192 @@ mod tests {
 
         assert_eq!(non_empty_lines, 10_000);
     }
+
+    #[test]
+    fn test_parquet_rows_sorted_by_timestamp() {
+        use super::{sort_by_timestamp, write_parquet_to_bytes, DEFAULT_ROW_GROUP_BYTES};
+        use arrow::array::TimestampMicrosecondArray;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use ssmd_schemas::kalshi::KalshiTradeSchema;
+        use ssmd_schemas::MessageSchema;
+
+        let schema = KalshiTradeSchema;
+        let messages: Vec<(Vec<u8>, u64, i64)> = [1707667203, 1707667200, 1707667205, 1707667200, 1707667201]
+            .iter()
+            .enumerate()
+            .map(|(i, ts)| {
+                let json = format!(
+                    r#"{{"type":"trade","msg":{{"trade_id":"t{}","market_ticker":"KXBTC-123","price":55,"count":1,"side":"yes","ts":{}}}}}"#,
+                    i, ts
+                );
+                (json.into_bytes(), i as u64, 0)
+            })
+            .collect();
+        let batch = schema.parse_batch(&messages).unwrap();
+        let sorted = sort_by_timestamp(&batch, schema.timestamp_column()).unwrap();
+
+        let bytes = write_parquet_to_bytes(&sorted, &schema, DEFAULT_ROW_GROUP_BYTES).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes)).unwrap();
+        assert!(builder.metadata().row_group(0).column(0).statistics().is_some());
+
+        let mut ts = Vec::new();
+        for b in builder.build().unwrap() {
+            let b = b.unwrap();
+            let col = b
+                .column_by_name("ts")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<TimestampMicrosecondArray>()
+                .unwrap();
+            ts.extend(col.values().iter().copied());
+        }
+        assert_eq!(ts.len(), 5);
+        assert!(ts.windows(2).all(|w| w[0] <= w[1]), "timestamps not sorted: {:?}", ts);
+    }
+
+    #[test]
+    fn test_split_by_event_hour() {
+        use super::{hour_partition_path, split_by_event_hour};
+
+        // 2024-02-11 00:59:59, 01:00:00, null, 2024-02-10 23:59:59 (previous day)
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "timestamp_ms",
+            DataType::Int64,
+            true,
+        )]));
+        let ts = Int64Array::from(vec![
+            Some(1_707_613_199_000),
+            Some(1_707_613_200_000),
+            None,
+            Some(1_707_609_599_000),
+        ]);
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(ts)]).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 2, 11).unwrap();
+
+        let split = split_by_event_hour(&batch, "timestamp_ms", date, 0).unwrap();
+        let rows: Vec<(u32, usize)> = split.iter().map(|(h, b)| (*h, b.num_rows())).collect();
+        assert_eq!(rows, vec![(0, 3), (1, 1)]);
+
+        assert_eq!(
+            hour_partition_path("p/kalshi/crypto/2024-02-11", "01", "trade", "01"),
+            "p/kalshi/crypto/2024-02-11/hour=01/trade.parquet"
+        );
+        assert_eq!(
+            hour_partition_path("p/kalshi/crypto/2024-02-11", "01", "trade", "00"),
+            "p/kalshi/crypto/2024-02-11/hour=01/trade_0000.parquet"
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        use super::{Checkpoint, ParquetFileEntry};
+
+        let entry = ParquetFileEntry {
+            path: "p/kalshi/crypto/2024-02-11/trade_1400.parquet".to_string(),
+            message_type: "trade".to_string(),
+            hour: "1400".to_string(),
+            bytes: 10,
+            row_count: 3,
+            schema_name: "kalshi_trade".to_string(),
+            schema_version: "1.0.0".to_string(),
+        };
+        let mut checkpoint = Checkpoint::default();
+        checkpoint
+            .completed
+            .entry("14".to_string())
+            .or_default()
+            .insert("trade".to_string(), vec![entry]);
+
+        let json = serde_json::to_vec(&checkpoint).unwrap();
+        let restored: Checkpoint = serde_json::from_slice(&json).unwrap();
+        let files = restored.completed_files("14", "trade").unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].row_count, 3);
+        assert!(restored.completed_files("14", "ticker").is_none());
+        assert!(restored.completed_files("15", "trade").is_none());
+    }
+
+    #[test]
+    fn test_manifest_without_partition_defaults_to_day() {
+        use super::{ParquetManifest, Partition};
+
+        let manifest: ParquetManifest = serde_json::from_str(
+            r#"{"feed":"kalshi","stream":"crypto","date":"2024-02-11","generated_at":"x","version":"2.0.0","hours":{},"totals":{"files_read":0,"lines_total":0,"lines_empty":0,"lines_json_error":0,"lines_type_unknown":0,"lines_no_schema":{},"parse_batch_input":{},"parse_batch_dropped":{},"records_written":{}}}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.partition, Partition::Day);
+    }
+
+    #[tokio::test]
+    async fn test_process_date_local_fs() {
+        use super::{process_date, Partition, DEFAULT_ROW_GROUP_BYTES};
+        use crate::gcs::Storage;
+        use crate::local_fs::LocalFsClient;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = LocalFsClient::new(tmp.path());
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        for (i, ts) in [1707660000, 1707660001, 1707660002].iter().enumerate() {
+            writeln!(
+                encoder,
+                r#"{{"type":"trade","msg":{{"trade_id":"t{}","market_ticker":"KXBTC-123","price":55,"count":1,"side":"yes","ts":{}}},"_received_at":{},"_nats_seq":{}}}"#,
+                i,
+                ts,
+                ts * 1_000_000,
+                i + 1
+            )
+            .unwrap();
+        }
+        store
+            .put(
+                "p/kalshi/crypto/2024-02-11/1400.jsonl.gz",
+                encoder.finish().unwrap().into(),
+            )
+            .await
+            .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 11).unwrap();
+        let stats = process_date(
+            &store,
+            "p",
+            "kalshi",
+            "crypto",
+            &date,
+            None,
+            None,
+            false,
+            false,
+            DEFAULT_ROW_GROUP_BYTES,
+            Partition::Day,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].records_by_type.get("trade"), Some(&3));
+        assert!(store
+            .exists("p/kalshi/crypto/2024-02-11/trade_1400.parquet")
+            .await
+            .unwrap());
+        assert!(store
+            .exists("p/kalshi/crypto/2024-02-11/parquet-manifest.json")
+            .await
+            .unwrap());
+    }
 }
 
 /// Write or merge parquet-manifest.json to GCS.
 /// If an existing manifest is present, merge new hour stats into it
 /// (supporting incremental 6-hour runs). Otherwise create fresh.
 async fn write_or_merge_manifest(
-    gcs: &GcsClient,
+    gcs: &dyn Storage,
     gcs_prefix: &str,
     feed: &str,
     stream: &str,
     date_str: &str,
     all_stats: &[HourStats],
+    partition: Partition,
 ) -> Result<()> {
     let manifest_path = format!(
         "{}/{}/{}/{}/parquet-manifest.json",
@@ -679,7 +1070,7 @@ async fn write_or_merge_manifest(
 
     // Merge new hour stats — only update hours that produced new parquet files
     for stats in all_stats {
-        if stats.parquet_files_written == 0 {
+        if stats.files_written.is_empty() {
             continue;
         }
         let hour_key_4digit = format!("{}00", stats.hour_key);
@@ -737,6 +1128,7 @@ async fn write_or_merge_manifest(
         totals,
         files,
         schemas,
+        partition,
     };
 
     let json_bytes = serde_json::to_vec_pretty(&manifest)?;
@@ -746,13 +1138,111 @@ async fn write_or_merge_manifest(
     Ok(())
 }
 
-/// Write a RecordBatch to Parquet bytes in memory
-fn write_parquet_to_bytes(batch: &RecordBatch, schema: &dyn MessageSchema) -> Result<Vec<u8>> {
+/// Combine per-file batches into one, unifying schemas so files written under
+/// different schema versions (e.g. an added column) land in a single parquet.
+fn unify_batches(batches: &[RecordBatch]) -> Result<RecordBatch, ArrowError> {
+    let schemas: Vec<SchemaRef> = batches.iter().map(|b| b.schema()).collect();
+    let target = unify_schemas(&schemas)?;
+    let aligned = batches
+        .iter()
+        .map(|b| align_batch(b, &target))
+        .collect::<Result<Vec<_>, _>>()?;
+    concat_batches(&target, &aligned)
+}
+
+/// Path of a file in the hour layout. Rows from the archive hour matching
+/// the partition go to `{type}.parquet`; rows that spilled over from a
+/// neighbouring archive hour get that hour as a suffix so the two never
+/// overwrite each other.
+fn hour_partition_path(date_dir: &str, hour: &str, msg_type: &str, source_hour: &str) -> String {
+    if hour == source_hour {
+        format!("{}/hour={}/{}.parquet", date_dir, hour, msg_type)
+    } else {
+        format!(
+            "{}/hour={}/{}_{}00.parquet",
+            date_dir, hour, msg_type, source_hour
+        )
+    }
+}
+
+/// Split rows by the UTC hour of `column`, preserving row order within each
+/// hour. Timestamp columns are read in their own unit; Int64 columns are
+/// epoch milliseconds (the `*_ms` convention used by the schemas). Rows with
+/// a null event time, or one outside `date`, stay in `default_hour`.
+fn split_by_event_hour(
+    batch: &RecordBatch,
+    column: &str,
+    date: NaiveDate,
+    default_hour: u32,
+) -> Result<Vec<(u32, RecordBatch)>, ArrowError> {
+    let unchanged = || Ok(vec![(default_hour, batch.clone())]);
+    let Some(ts) = batch.column_by_name(column) else {
+        return unchanged();
+    };
+    let to_micros: fn(i64) -> i64 = match ts.data_type() {
+        DataType::Timestamp(TimeUnit::Second, _) => |v| v * 1_000_000,
+        DataType::Timestamp(TimeUnit::Millisecond, _) | DataType::Int64 => |v| v * 1_000,
+        DataType::Timestamp(TimeUnit::Microsecond, _) => |v| v,
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => |v| v / 1_000,
+        _ => return unchanged(),
+    };
+    let values = cast(ts, &DataType::Int64)?;
+    let values = values.as_primitive::<Int64Type>();
+
+    let mut by_hour: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for row in 0..batch.num_rows() {
+        let hour = (!values.is_null(row))
+            .then(|| DateTime::from_timestamp_micros(to_micros(values.value(row))))
+            .flatten()
+            .filter(|t| t.date_naive() == date)
+            .map(|t| t.hour())
+            .unwrap_or(default_hour);
+        by_hour.entry(hour).or_default().push(row as u32);
+    }
+
+    by_hour
+        .into_iter()
+        .map(|(hour, rows)| Ok((hour, take_record_batch(batch, &UInt32Array::from(rows))?)))
+        .collect()
+}
+
+/// Sort rows by `column` (ties broken by `_nats_seq`) so each row group covers
+/// a narrow time range and min/max statistics prune well. Batches without the
+/// column are returned unchanged.
+fn sort_by_timestamp(batch: &RecordBatch, column: &str) -> Result<RecordBatch, ArrowError> {
+    let Some(ts) = batch.column_by_name(column) else {
+        return Ok(batch.clone());
+    };
+    let mut keys = vec![SortColumn {
+        values: ts.clone(),
+        options: Some(SortOptions {
+            descending: false,
+            nulls_first: true,
+        }),
+    }];
+    if let Some(seq) = batch.column_by_name("_nats_seq") {
+        keys.push(SortColumn {
+            values: seq.clone(),
+            options: None,
+        });
+    }
+    let indices = lexsort_to_indices(&keys, None)?;
+    take_record_batch(batch, &indices)
+}
+
+/// Write a RecordBatch to Parquet bytes in memory, closing a row group each
+/// time its buffered size reaches `row_group_bytes`.
+fn write_parquet_to_bytes(
+    batch: &RecordBatch,
+    schema: &dyn MessageSchema,
+    row_group_bytes: usize,
+) -> Result<Vec<u8>> {
     let props = WriterProperties::builder()
         .set_compression(Compression::SNAPPY)
-        .set_max_row_group_size(100_000)
         .set_data_page_size_limit(1024 * 1024) // 1MB
-        .set_statistics_enabled(EnabledStatistics::Chunk)
+        // Page-level min/max plus column/offset indexes, so readers can skip
+        // row groups and pages by timestamp
+        .set_statistics_enabled(EnabledStatistics::Page)
         .set_created_by("ssmd-parquet-gen".to_string())
         .set_key_value_metadata(Some(vec![
             KeyValue::new(
@@ -768,7 +1258,15 @@ fn write_parquet_to_bytes(batch: &RecordBatch, schema: &dyn MessageSchema) -> Re
 
     let mut buf = Vec::new();
     let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props))?;
-    writer.write(batch)?;
+    let mut offset = 0;
+    while offset < batch.num_rows() {
+        let len = WRITE_CHUNK_ROWS.min(batch.num_rows() - offset);
+        writer.write(&batch.slice(offset, len))?;
+        if writer.in_progress_size() >= row_group_bytes {
+            writer.flush()?;
+        }
+        offset += len;
+    }
     writer.close()?;
     Ok(buf)
 }
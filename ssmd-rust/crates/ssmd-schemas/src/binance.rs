@@ -83,6 +83,22 @@ impl MessageSchema for BinanceTradeSchema {
         "trade"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "exchange_ts_ms"
+    }
+
+    /// Dedup on (`data.s`, `data.t`) — trade ids are only unique per symbol.
+    /// Falls back to `nats_seq` when either is absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        let inner = json.get("data");
+        let symbol = inner.and_then(|d| d.get("s")).and_then(|v| v.as_str());
+        let tid = inner.and_then(|d| d.get("t")).and_then(|v| v.as_i64());
+        match (symbol, tid) {
+            (Some(symbol), Some(tid)) => Some(crate::dedup_hash(&(symbol, tid))),
+            _ => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut symbol = StringBuilder::new();
         let mut price = Float64Builder::new();
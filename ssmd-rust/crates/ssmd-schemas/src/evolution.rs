@@ -0,0 +1,155 @@
+//! Schema evolution helpers for reading data written under multiple schema
+//! versions (e.g. a day that mixes 1.0.0 and 1.1.0 files).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, ArrayRef};
+use arrow::datatypes::{Field, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+/// Produce a superset schema containing every column from `schemas`.
+///
+/// Columns keep the order of their first appearance. A column missing from
+/// any input schema becomes nullable. Columns with the same name but
+/// different data types are an error.
+pub fn unify_schemas(schemas: &[Arc<Schema>]) -> Result<Arc<Schema>, ArrowError> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut seen_in: Vec<usize> = Vec::new();
+    let mut metadata = HashMap::new();
+
+    for schema in schemas {
+        for field in schema.fields() {
+            match index.get(field.name()) {
+                Some(&i) => {
+                    let existing = &fields[i];
+                    if existing.data_type() != field.data_type() {
+                        return Err(ArrowError::SchemaError(format!(
+                            "column '{}' has conflicting types: {} vs {}",
+                            field.name(),
+                            existing.data_type(),
+                            field.data_type()
+                        )));
+                    }
+                    if field.is_nullable() && !existing.is_nullable() {
+                        fields[i] = existing.clone().with_nullable(true);
+                    }
+                    seen_in[i] += 1;
+                }
+                None => {
+                    index.insert(field.name().clone(), fields.len());
+                    fields.push(field.as_ref().clone());
+                    seen_in.push(1);
+                }
+            }
+        }
+        for (k, v) in schema.metadata() {
+            metadata.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+    }
+
+    let fields: Vec<Field> = fields
+        .into_iter()
+        .zip(seen_in)
+        .map(|(field, count)| {
+            if count < schemas.len() {
+                field.with_nullable(true)
+            } else {
+                field
+            }
+        })
+        .collect();
+
+    Ok(Arc::new(Schema::new_with_metadata(fields, metadata)))
+}
+
+/// Pad `batch` to `target`, reordering columns by name and filling columns
+/// the batch lacks with nulls.
+pub fn align_batch(batch: &RecordBatch, target: &Arc<Schema>) -> Result<RecordBatch, ArrowError> {
+    let source = batch.schema();
+    let columns: Vec<ArrayRef> = target
+        .fields()
+        .iter()
+        .map(|field| match source.index_of(field.name()) {
+            Ok(i) => {
+                let column = batch.column(i);
+                if column.data_type() != field.data_type() {
+                    return Err(ArrowError::SchemaError(format!(
+                        "column '{}' has type {} but target expects {}",
+                        field.name(),
+                        column.data_type(),
+                        field.data_type()
+                    )));
+                }
+                Ok(column.clone())
+            }
+            Err(_) => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<Result<_, _>>()?;
+
+    RecordBatch::try_new(target.clone(), columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, Int64Array, StringArray};
+    use arrow::datatypes::DataType;
+
+    fn v1() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("ticker", DataType::Utf8, false),
+            Field::new("price", DataType::Int64, false),
+        ]))
+    }
+
+    fn v2() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("ticker", DataType::Utf8, false),
+            Field::new("price", DataType::Int64, false),
+            Field::new("count", DataType::Int64, false),
+        ]))
+    }
+
+    #[test]
+    fn test_unify_adds_missing_columns_as_nullable() {
+        let unified = unify_schemas(&[v1(), v2()]).unwrap();
+        assert_eq!(unified.fields().len(), 3);
+        assert!(!unified.field_with_name("ticker").unwrap().is_nullable());
+        assert!(!unified.field_with_name("price").unwrap().is_nullable());
+        assert!(unified.field_with_name("count").unwrap().is_nullable());
+    }
+
+    #[test]
+    fn test_unify_identical_schemas_is_noop() {
+        let unified = unify_schemas(&[v2(), v2()]).unwrap();
+        assert_eq!(unified.as_ref(), v2().as_ref());
+    }
+
+    #[test]
+    fn test_unify_conflicting_types_errors() {
+        let other = Arc::new(Schema::new(vec![Field::new("price", DataType::Utf8, false)]));
+        assert!(unify_schemas(&[v1(), other]).is_err());
+    }
+
+    #[test]
+    fn test_align_batch_pads_missing_column() {
+        let batch = RecordBatch::try_new(
+            v1(),
+            vec![
+                Arc::new(StringArray::from(vec!["A", "B"])),
+                Arc::new(Int64Array::from(vec![10, 20])),
+            ],
+        )
+        .unwrap();
+        let target = unify_schemas(&[v1(), v2()]).unwrap();
+        let aligned = align_batch(&batch, &target).unwrap();
+
+        assert_eq!(aligned.num_columns(), 3);
+        assert_eq!(aligned.num_rows(), 2);
+        let count = aligned.column(2);
+        assert_eq!(count.null_count(), 2);
+    }
+}
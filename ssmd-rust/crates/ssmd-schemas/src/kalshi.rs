@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use arrow::array::*;
-use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use tracing::error;
@@ -20,24 +20,48 @@ fn append_optional_i64(builder: &mut Int64Builder, value: Option<&serde_json::Va
     }
 }
 
-/// Helper to append an optional i64 from either an integer field or a dollar-string field.
+/// Helper to append an optional Unix-seconds field as a microsecond timestamp.
+fn append_optional_secs(
+    builder: &mut TimestampMicrosecondBuilder,
+    value: Option<&serde_json::Value>,
+) {
+    match value.and_then(|v| v.as_i64()) {
+        Some(v) => builder.append_value(v * 1_000_000),
+        None => builder.append_null(),
+    }
+}
+
+/// Read an optional cents value from either an integer field or a dollar-string field.
 /// Kalshi changed their WS format: old = integer cents, new = dollar string like "0.9700".
-fn append_cents_or_dollars(
-    builder: &mut Int64Builder,
+fn cents_or_dollars(
     msg: &serde_json::Value,
     cents_field: &str,
     dollars_field: &str,
+) -> Option<i64> {
+    msg.get(cents_field).and_then(|v| v.as_i64()).or_else(|| {
+        msg.get(dollars_field)
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(|d| (d * 100.0).round() as i64)
+    })
+}
+
+/// Helper to append an optional cents value alongside its dollar equivalent.
+/// Both columns are null when the source field is missing.
+fn append_cents_with_dollars(
+    cents: &mut Int64Builder,
+    dollars: &mut Float64Builder,
+    value: Option<i64>,
 ) {
-    if let Some(v) = msg.get(cents_field).and_then(|v| v.as_i64()) {
-        builder.append_value(v);
-    } else if let Some(d) = msg
-        .get(dollars_field)
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<f64>().ok())
-    {
-        builder.append_value((d * 100.0).round() as i64);
-    } else {
-        builder.append_null();
+    match value {
+        Some(c) => {
+            cents.append_value(c);
+            dollars.append_value(c as f64 / 100.0);
+        }
+        None => {
+            cents.append_null();
+            dollars.append_null();
+        }
     }
 }
 
@@ -78,6 +102,11 @@ impl KalshiTickerSchema {
             Field::new("last_price", DataType::Int64, true),
             Field::new("volume", DataType::Int64, true),
             Field::new("open_interest", DataType::Int64, true),
+            Field::new("yes_bid_dollars", DataType::Float64, true),
+            Field::new("yes_ask_dollars", DataType::Float64, true),
+            Field::new("no_bid_dollars", DataType::Float64, true),
+            Field::new("no_ask_dollars", DataType::Float64, true),
+            Field::new("last_price_dollars", DataType::Float64, true),
             Field::new("ts", ts_type(), false),
             Field::new("exchange_clock", DataType::Int64, true),
             Field::new("sid", DataType::Int64, true),
@@ -94,7 +123,7 @@ impl MessageSchema for KalshiTickerSchema {
     }
 
     fn schema_version(&self) -> &str {
-        "1.3.0"
+        "1.4.0"
     }
 
     fn schema(&self) -> Arc<Schema> {
@@ -105,6 +134,10 @@ impl MessageSchema for KalshiTickerSchema {
         "ticker"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "ts"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut market_ticker = StringBuilder::new();
         let mut yes_bid = Int64Builder::new();
@@ -114,6 +147,11 @@ impl MessageSchema for KalshiTickerSchema {
         let mut last_price = Int64Builder::new();
         let mut volume = Int64Builder::new();
         let mut open_interest = Int64Builder::new();
+        let mut yes_bid_dollars = Float64Builder::new();
+        let mut yes_ask_dollars = Float64Builder::new();
+        let mut no_bid_dollars = Float64Builder::new();
+        let mut no_ask_dollars = Float64Builder::new();
+        let mut last_price_dollars = Float64Builder::new();
         let mut ts = TimestampMicrosecondBuilder::new();
         let mut exchange_clock = Int64Builder::new();
         let mut sid = Int64Builder::new();
@@ -150,11 +188,31 @@ impl MessageSchema for KalshiTickerSchema {
             };
 
             market_ticker.append_value(ticker);
-            append_cents_or_dollars(&mut yes_bid, msg, "yes_bid", "yes_bid_dollars");
-            append_cents_or_dollars(&mut yes_ask, msg, "yes_ask", "yes_ask_dollars");
-            append_cents_or_dollars(&mut no_bid, msg, "no_bid", "no_bid_dollars");
-            append_cents_or_dollars(&mut no_ask, msg, "no_ask", "no_ask_dollars");
-            append_cents_or_dollars(&mut last_price, msg, "price", "price_dollars");
+            append_cents_with_dollars(
+                &mut yes_bid,
+                &mut yes_bid_dollars,
+                cents_or_dollars(msg, "yes_bid", "yes_bid_dollars"),
+            );
+            append_cents_with_dollars(
+                &mut yes_ask,
+                &mut yes_ask_dollars,
+                cents_or_dollars(msg, "yes_ask", "yes_ask_dollars"),
+            );
+            append_cents_with_dollars(
+                &mut no_bid,
+                &mut no_bid_dollars,
+                cents_or_dollars(msg, "no_bid", "no_bid_dollars"),
+            );
+            append_cents_with_dollars(
+                &mut no_ask,
+                &mut no_ask_dollars,
+                cents_or_dollars(msg, "no_ask", "no_ask_dollars"),
+            );
+            append_cents_with_dollars(
+                &mut last_price,
+                &mut last_price_dollars,
+                cents_or_dollars(msg, "price", "price_dollars"),
+            );
             append_int_or_fp(&mut volume, msg, "volume", "volume_fp");
             append_int_or_fp(&mut open_interest, msg, "open_interest", "open_interest_fp");
             ts.append_value(ts_secs * 1_000_000);
@@ -176,6 +234,11 @@ impl MessageSchema for KalshiTickerSchema {
                 Arc::new(last_price.finish()),
                 Arc::new(volume.finish()),
                 Arc::new(open_interest.finish()),
+                Arc::new(yes_bid_dollars.finish()),
+                Arc::new(yes_ask_dollars.finish()),
+                Arc::new(no_bid_dollars.finish()),
+                Arc::new(no_ask_dollars.finish()),
+                Arc::new(last_price_dollars.finish()),
                 Arc::new(ts.finish().with_timezone("UTC")),
                 Arc::new(exchange_clock.finish()),
                 Arc::new(sid.finish()),
@@ -198,6 +261,7 @@ impl KalshiTradeSchema {
         Schema::new(vec![
             Field::new("market_ticker", DataType::Utf8, false),
             Field::new("price", DataType::Int64, false),
+            Field::new("price_dollars", DataType::Float64, false),
             Field::new("count", DataType::Int64, false),
             Field::new("side", DataType::Utf8, false),
             Field::new("ts", ts_type(), false),
@@ -217,7 +281,7 @@ impl MessageSchema for KalshiTradeSchema {
     }
 
     fn schema_version(&self) -> &str {
-        "1.3.0"
+        "1.4.0"
     }
 
     fn schema(&self) -> Arc<Schema> {
@@ -228,9 +292,26 @@ impl MessageSchema for KalshiTradeSchema {
         "trade"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "ts"
+    }
+
+    /// Dedup on `msg.trade_id`, falling back to `nats_seq` when absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        match json
+            .get("msg")
+            .and_then(|m| m.get("trade_id"))
+            .and_then(|v| v.as_str())
+        {
+            Some(tid) => Some(crate::dedup_hash(tid)),
+            None => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut market_ticker = StringBuilder::new();
         let mut price = Int64Builder::new();
+        let mut price_dollars = Float64Builder::new();
         let mut count = Int64Builder::new();
         let mut side = StringBuilder::new();
         let mut ts = TimestampMicrosecondBuilder::new();
@@ -275,25 +356,15 @@ impl MessageSchema for KalshiTradeSchema {
                 .get("yes_price")
                 .or_else(|| msg.get("price"))
                 .and_then(|v| v.as_i64())
+                .or_else(|| cents_or_dollars(msg, "yes_price", "yes_price_dollars"))
             {
                 Some(v) => v,
                 None => {
-                    // New format: yes_price_dollars is a dollar string like "0.8900"
-                    match msg
-                        .get("yes_price_dollars")
-                        .and_then(|v| v.as_str())
-                        .and_then(|s| s.parse::<f64>().ok())
-                        .map(|d| (d * 100.0).round() as i64)
-                    {
-                        Some(v) => v,
-                        None => {
-                            error!(
-                                ticker = ticker,
-                                "Kalshi trade missing price field, skipping"
-                            );
-                            continue;
-                        }
-                    }
+                    error!(
+                        ticker = ticker,
+                        "Kalshi trade missing price field, skipping"
+                    );
+                    continue;
                 }
             };
             // Kalshi WS sends "count" (int) or "count_fp" (string like "3.00")
@@ -343,6 +414,7 @@ impl MessageSchema for KalshiTradeSchema {
 
             market_ticker.append_value(ticker);
             price.append_value(p);
+            price_dollars.append_value(p as f64 / 100.0);
             count.append_value(c);
             side.append_value(s);
             ts.append_value(t * 1_000_000);
@@ -359,6 +431,7 @@ impl MessageSchema for KalshiTradeSchema {
             vec![
                 Arc::new(market_ticker.finish()),
                 Arc::new(price.finish()),
+                Arc::new(price_dollars.finish()),
                 Arc::new(count.finish()),
                 Arc::new(side.finish()),
                 Arc::new(ts.finish().with_timezone("UTC")),
@@ -387,10 +460,28 @@ impl KalshiLifecycleSchema {
             Field::new("open_ts", ts_type(), true),
             Field::new("close_ts", ts_type(), true),
             Field::new("additional_metadata", DataType::Utf8, true),
+            Field::new("lifecycle_stage", DataType::Utf8, true),
+            Field::new("effective_ts", ts_type(), true),
+            Field::new("determination_ts", ts_type(), true),
+            Field::new("settled_ts", ts_type(), true),
+            Field::new("result", DataType::Utf8, true),
             Field::new("_nats_seq", DataType::UInt64, false),
             Field::new("_received_at", ts_type(), false),
         ])
     }
+
+    /// Map a Kalshi `event_type` onto the stage analysts query by, along with
+    /// the `msg` field holding the time that stage takes effect. `created` and
+    /// `deactivated` (a trading pause) have no stage.
+    fn stage(event_type: &str) -> Option<(&'static str, &'static str)> {
+        match event_type {
+            "activated" => Some(("open", "open_ts")),
+            "close_date_updated" => Some(("close", "close_ts")),
+            "determined" => Some(("determined", "determination_ts")),
+            "settled" => Some(("settled", "settled_ts")),
+            _ => None,
+        }
+    }
 }
 
 impl MessageSchema for KalshiLifecycleSchema {
@@ -399,7 +490,7 @@ impl MessageSchema for KalshiLifecycleSchema {
     }
 
     fn schema_version(&self) -> &str {
-        "1.0.0"
+        "1.1.0"
     }
 
     fn schema(&self) -> Arc<Schema> {
@@ -416,6 +507,11 @@ impl MessageSchema for KalshiLifecycleSchema {
         let mut open_ts = TimestampMicrosecondBuilder::new();
         let mut close_ts = TimestampMicrosecondBuilder::new();
         let mut additional_metadata = StringBuilder::new();
+        let mut lifecycle_stage = StringBuilder::new();
+        let mut effective_ts = TimestampMicrosecondBuilder::new();
+        let mut determination_ts = TimestampMicrosecondBuilder::new();
+        let mut settled_ts = TimestampMicrosecondBuilder::new();
+        let mut result = StringBuilder::new();
         let mut nats_seq = UInt64Builder::new();
         let mut received_at = TimestampMicrosecondBuilder::new();
 
@@ -449,14 +545,8 @@ impl MessageSchema for KalshiLifecycleSchema {
             market_ticker.append_value(ticker);
             event_type.append_value(et);
 
-            match msg.get("open_ts").and_then(|v| v.as_i64()) {
-                Some(v) => open_ts.append_value(v * 1_000_000),
-                None => open_ts.append_null(),
-            }
-            match msg.get("close_ts").and_then(|v| v.as_i64()) {
-                Some(v) => close_ts.append_value(v * 1_000_000),
-                None => close_ts.append_null(),
-            }
+            append_optional_secs(&mut open_ts, msg.get("open_ts"));
+            append_optional_secs(&mut close_ts, msg.get("close_ts"));
             match msg.get("additional_metadata") {
                 Some(v) if !v.is_null() => {
                     additional_metadata.append_value(v.to_string());
@@ -464,6 +554,24 @@ impl MessageSchema for KalshiLifecycleSchema {
                 _ => additional_metadata.append_null(),
             }
 
+            match Self::stage(et) {
+                Some((stage, ts_field)) => {
+                    lifecycle_stage.append_value(stage);
+                    append_optional_secs(&mut effective_ts, msg.get(ts_field));
+                }
+                None => {
+                    lifecycle_stage.append_null();
+                    effective_ts.append_null();
+                }
+            }
+            append_optional_secs(&mut determination_ts, msg.get("determination_ts"));
+            append_optional_secs(&mut settled_ts, msg.get("settled_ts"));
+            // Kalshi sends an empty string until the market is determined
+            match msg.get("result").and_then(|v| v.as_str()) {
+                Some(r) if !r.is_empty() => result.append_value(r),
+                _ => result.append_null(),
+            }
+
             nats_seq.append_value(*seq);
             received_at.append_value(*recv_at);
         }
@@ -476,6 +584,262 @@ impl MessageSchema for KalshiLifecycleSchema {
                 Arc::new(open_ts.finish().with_timezone("UTC")),
                 Arc::new(close_ts.finish().with_timezone("UTC")),
                 Arc::new(additional_metadata.finish()),
+                Arc::new(lifecycle_stage.finish()),
+                Arc::new(effective_ts.finish().with_timezone("UTC")),
+                Arc::new(determination_ts.finish().with_timezone("UTC")),
+                Arc::new(settled_ts.finish().with_timezone("UTC")),
+                Arc::new(result.finish()),
+                Arc::new(nats_seq.finish()),
+                Arc::new(received_at.finish().with_timezone("UTC")),
+            ],
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// KalshiOrderbookSchema
+// ---------------------------------------------------------------------------
+
+/// Handles both `orderbook_snapshot` and `orderbook_delta`. A snapshot row
+/// carries the full `yes`/`no` books; a delta row (`delta = true`) carries the
+/// single changed level on its side, with `quantity` being the signed change.
+/// Replay a market's book by ordering on `(sid, exchange_seq)`.
+pub struct KalshiOrderbookSchema;
+
+impl KalshiOrderbookSchema {
+    fn level_fields() -> Fields {
+        Fields::from(vec![
+            Field::new("price", DataType::Int64, false),
+            Field::new("quantity", DataType::Int64, false),
+        ])
+    }
+
+    fn level_item() -> Arc<Field> {
+        Arc::new(Field::new(
+            "item",
+            DataType::Struct(Self::level_fields()),
+            false,
+        ))
+    }
+
+    fn levels_builder() -> ListBuilder<StructBuilder> {
+        ListBuilder::new(StructBuilder::from_fields(Self::level_fields(), 0))
+            .with_field(Self::level_item())
+    }
+
+    fn arrow_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("market_ticker", DataType::Utf8, false),
+            Field::new("market_id", DataType::Utf8, true),
+            Field::new("delta", DataType::Boolean, false),
+            Field::new("yes", DataType::List(Self::level_item()), false),
+            Field::new("no", DataType::List(Self::level_item()), false),
+            Field::new("ts", ts_type(), true),
+            Field::new("exchange_seq", DataType::Int64, true),
+            Field::new("sid", DataType::Int64, true),
+            Field::new("_shard_id", DataType::Int64, true),
+            Field::new("_nats_seq", DataType::UInt64, false),
+            Field::new("_received_at", ts_type(), false),
+        ])
+    }
+}
+
+/// Read `[price, quantity]` levels from `msg[cents_field]` (integer cents) or,
+/// failing that, `msg[dollars_field]` (dollar-string prices). Malformed
+/// levels are logged and dropped.
+fn book_levels(msg: &serde_json::Value, cents_field: &str, dollars_field: &str) -> Vec<(i64, i64)> {
+    let (levels, in_dollars) = match msg.get(cents_field).and_then(|v| v.as_array()) {
+        Some(l) => (l, false),
+        None => match msg.get(dollars_field).and_then(|v| v.as_array()) {
+            Some(l) => (l, true),
+            None => return Vec::new(),
+        },
+    };
+    levels
+        .iter()
+        .filter_map(|level| {
+            let price = level.get(0).and_then(|p| {
+                if in_dollars {
+                    p.as_str()
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .map(|d| (d * 100.0).round() as i64)
+                } else {
+                    p.as_i64()
+                }
+            });
+            let quantity = level.get(1).and_then(|q| {
+                q.as_i64().or_else(|| {
+                    q.as_str()
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .map(|d| d as i64)
+                })
+            });
+            match (price, quantity) {
+                (Some(p), Some(q)) => Some((p, q)),
+                _ => {
+                    error!(level = %level, "Kalshi orderbook level malformed, skipping");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn append_levels(builder: &mut ListBuilder<StructBuilder>, levels: &[(i64, i64)]) {
+    let values = builder.values();
+    for (price, quantity) in levels {
+        values
+            .field_builder::<Int64Builder>(0)
+            .unwrap()
+            .append_value(*price);
+        values
+            .field_builder::<Int64Builder>(1)
+            .unwrap()
+            .append_value(*quantity);
+        values.append(true);
+    }
+    builder.append(true);
+}
+
+impl MessageSchema for KalshiOrderbookSchema {
+    fn schema_name(&self) -> &str {
+        "kalshi_orderbook"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Arc::new(Self::arrow_schema())
+    }
+
+    fn message_type(&self) -> &str {
+        "orderbook"
+    }
+
+    /// Dedup on `(msg.market_ticker, seq)`, falling back to `nats_seq` when
+    /// either is absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        let ticker = json
+            .get("msg")
+            .and_then(|m| m.get("market_ticker"))
+            .and_then(|v| v.as_str());
+        match (ticker, json.get("seq").and_then(|v| v.as_i64())) {
+            (Some(ticker), Some(seq)) => Some(crate::dedup_hash(&(ticker, seq))),
+            _ => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
+    fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
+        let mut market_ticker = StringBuilder::new();
+        let mut market_id = StringBuilder::new();
+        let mut delta = BooleanBuilder::new();
+        let mut yes = Self::levels_builder();
+        let mut no = Self::levels_builder();
+        let mut ts = TimestampMicrosecondBuilder::new();
+        let mut exchange_seq = Int64Builder::new();
+        let mut sid = Int64Builder::new();
+        let mut shard_id_col = Int64Builder::new();
+        let mut nats_seq = UInt64Builder::new();
+        let mut received_at = TimestampMicrosecondBuilder::new();
+
+        for (data, seq, recv_at) in messages {
+            let json: serde_json::Value = serde_json::from_slice(data)
+                .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+
+            let is_delta = match json.get("type").and_then(|v| v.as_str()) {
+                Some("orderbook_snapshot") => false,
+                Some("orderbook_delta") => true,
+                other => {
+                    error!(msg_type = ?other, "Kalshi orderbook has unexpected 'type', skipping");
+                    continue;
+                }
+            };
+            let msg = match json.get("msg") {
+                Some(m) => m,
+                None => {
+                    error!("Kalshi orderbook missing 'msg' field, skipping");
+                    continue;
+                }
+            };
+            let ticker = match msg.get("market_ticker").and_then(|v| v.as_str()) {
+                Some(t) => t,
+                None => {
+                    error!("Kalshi orderbook missing 'market_ticker', skipping");
+                    continue;
+                }
+            };
+
+            let (yes_levels, no_levels) = if is_delta {
+                let price = cents_or_dollars(msg, "price", "price_dollars");
+                let change = msg.get("delta").and_then(|v| v.as_i64()).or_else(|| {
+                    msg.get("delta_fp")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .map(|d| d as i64)
+                });
+                let level = match (price, change) {
+                    (Some(p), Some(c)) => vec![(p, c)],
+                    _ => {
+                        error!(
+                            ticker = ticker,
+                            "Kalshi orderbook delta missing price/delta, skipping"
+                        );
+                        continue;
+                    }
+                };
+                match msg.get("side").and_then(|v| v.as_str()) {
+                    Some("yes") => (level, Vec::new()),
+                    Some("no") => (Vec::new(), level),
+                    _ => {
+                        error!(
+                            ticker = ticker,
+                            "Kalshi orderbook delta missing 'side', skipping"
+                        );
+                        continue;
+                    }
+                }
+            } else {
+                (
+                    book_levels(msg, "yes", "yes_dollars"),
+                    book_levels(msg, "no", "no_dollars"),
+                )
+            };
+
+            market_ticker.append_value(ticker);
+            market_id.append_option(msg.get("market_id").and_then(|v| v.as_str()));
+            delta.append_value(is_delta);
+            append_levels(&mut yes, &yes_levels);
+            append_levels(&mut no, &no_levels);
+            // Deltas carry an RFC3339 `ts`; snapshots have none
+            match msg
+                .get("ts")
+                .and_then(|v| v.as_str())
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            {
+                Some(t) => ts.append_value(t.timestamp_micros()),
+                None => ts.append_null(),
+            }
+            append_optional_i64(&mut exchange_seq, json.get("seq"));
+            append_optional_i64(&mut sid, json.get("sid"));
+            append_optional_i64(&mut shard_id_col, json.get("_shard_id"));
+            nats_seq.append_value(*seq);
+            received_at.append_value(*recv_at);
+        }
+
+        RecordBatch::try_new(
+            Arc::new(Self::arrow_schema()),
+            vec![
+                Arc::new(market_ticker.finish()),
+                Arc::new(market_id.finish()),
+                Arc::new(delta.finish()),
+                Arc::new(yes.finish()),
+                Arc::new(no.finish()),
+                Arc::new(ts.finish().with_timezone("UTC")),
+                Arc::new(exchange_seq.finish()),
+                Arc::new(sid.finish()),
+                Arc::new(shard_id_col.finish()),
                 Arc::new(nats_seq.finish()),
                 Arc::new(received_at.finish().with_timezone("UTC")),
             ],
@@ -496,7 +860,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(batch.num_rows(), 1);
-        assert_eq!(batch.num_columns(), 14);
+        assert_eq!(batch.num_columns(), 19);
 
         let col = batch
             .column(0)
@@ -521,7 +885,7 @@ mod tests {
 
         // ts: 1707667200 seconds → 1707667200_000_000 micros
         let col = batch
-            .column(8)
+            .column(13)
             .as_any()
             .downcast_ref::<TimestampMicrosecondArray>()
             .unwrap();
@@ -529,7 +893,7 @@ mod tests {
 
         // exchange_clock
         let col = batch
-            .column(9)
+            .column(14)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -537,7 +901,7 @@ mod tests {
 
         // sid
         let col = batch
-            .column(10)
+            .column(15)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -545,7 +909,7 @@ mod tests {
 
         // _shard_id
         let col = batch
-            .column(11)
+            .column(16)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -553,7 +917,7 @@ mod tests {
 
         // _nats_seq
         let col = batch
-            .column(12)
+            .column(17)
             .as_any()
             .downcast_ref::<UInt64Array>()
             .unwrap();
@@ -587,9 +951,17 @@ mod tests {
             .unwrap();
         assert!(col.is_null(0));
 
+        // yes_bid_dollars should be null alongside yes_bid
+        let col = batch
+            .column(8)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!(col.is_null(0));
+
         // exchange_clock should be null
         let col = batch
-            .column(9)
+            .column(14)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -597,7 +969,7 @@ mod tests {
 
         // sid should be null (not in envelope)
         let col = batch
-            .column(10)
+            .column(15)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -638,6 +1010,14 @@ mod tests {
         // open_interest: "13018.00" → 13018
         let col = batch.column(7).as_any().downcast_ref::<Int64Array>().unwrap();
         assert_eq!(col.value(0), 13018);
+
+        // yes_bid_dollars: 97 cents → 0.97
+        let col = batch.column(8).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert!((col.value(0) - 0.97).abs() < 1e-9);
+
+        // last_price_dollars: 97 cents → 0.97
+        let col = batch.column(12).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert!((col.value(0) - 0.97).abs() < 1e-9);
     }
 
     #[test]
@@ -649,7 +1029,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(batch.num_rows(), 1);
-        assert_eq!(batch.num_columns(), 11);
+        assert_eq!(batch.num_columns(), 12);
 
         let ticker = batch
             .column(0)
@@ -665,15 +1045,22 @@ mod tests {
             .unwrap();
         assert_eq!(price.value(0), 55);
 
-        let count = batch
+        let price_dollars = batch
             .column(2)
             .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!((price_dollars.value(0) - 0.55).abs() < 1e-9);
+
+        let count = batch
+            .column(3)
+            .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
         assert_eq!(count.value(0), 10);
 
         let side = batch
-            .column(3)
+            .column(4)
             .as_any()
             .downcast_ref::<StringArray>()
             .unwrap();
@@ -681,7 +1068,7 @@ mod tests {
 
         // trade_id
         let tid = batch
-            .column(5)
+            .column(6)
             .as_any()
             .downcast_ref::<StringArray>()
             .unwrap();
@@ -689,7 +1076,7 @@ mod tests {
 
         // exchange_seq
         let eseq = batch
-            .column(6)
+            .column(7)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -697,7 +1084,7 @@ mod tests {
 
         // sid
         let sid = batch
-            .column(7)
+            .column(8)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -705,7 +1092,7 @@ mod tests {
 
         // _shard_id
         let shard = batch
-            .column(8)
+            .column(9)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -722,7 +1109,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(batch.num_rows(), 1);
-        assert_eq!(batch.num_columns(), 11);
+        assert_eq!(batch.num_columns(), 12);
 
         let price = batch
             .column(1)
@@ -732,7 +1119,7 @@ mod tests {
         assert_eq!(price.value(0), 55);
 
         let side = batch
-            .column(3)
+            .column(4)
             .as_any()
             .downcast_ref::<StringArray>()
             .unwrap();
@@ -763,7 +1150,7 @@ mod tests {
         assert_eq!(prices.value(1), 20);
 
         let sides = batch
-            .column(3)
+            .column(4)
             .as_any()
             .downcast_ref::<StringArray>()
             .unwrap();
@@ -784,7 +1171,7 @@ mod tests {
 
         // exchange_seq should be null
         let eseq = batch
-            .column(6)
+            .column(7)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -792,7 +1179,7 @@ mod tests {
 
         // sid should be present (it's in the envelope even without seq)
         let sid = batch
-            .column(7)
+            .column(8)
             .as_any()
             .downcast_ref::<Int64Array>()
             .unwrap();
@@ -800,7 +1187,7 @@ mod tests {
 
         // trade_id should still be present
         let tid = batch
-            .column(5)
+            .column(6)
             .as_any()
             .downcast_ref::<StringArray>()
             .unwrap();
@@ -888,6 +1275,136 @@ mod tests {
         assert!(meta.is_null(0));
     }
 
+    #[test]
+    fn test_parse_kalshi_lifecycle_settled_with_result() {
+        let schema = KalshiLifecycleSchema;
+        let json = br#"{"type":"market_lifecycle_v2","sid":13,"msg":{"market_ticker":"KXBTCD-26JAN2310-T105000","event_type":"settled","open_ts":1737554400,"close_ts":1737558000,"determination_ts":1737558060,"settled_ts":1737558300,"result":"yes"}}"#;
+        let batch = schema
+            .parse_batch(&[(json.to_vec(), 100, 1000)])
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+
+        let col = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let stage = col("lifecycle_stage");
+        let stage = stage.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(stage.value(0), "settled");
+
+        // effective_ts for a settlement is settled_ts
+        let effective = col("effective_ts");
+        let effective = effective
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(effective.value(0), 1737558300_000_000);
+
+        let determined = col("determination_ts");
+        let determined = determined
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(determined.value(0), 1737558060_000_000);
+
+        let result = col("result");
+        let result = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result.value(0), "yes");
+    }
+
+    #[test]
+    fn test_parse_kalshi_lifecycle_unmapped_event_type() {
+        let schema = KalshiLifecycleSchema;
+        let json = br#"{"type":"market_lifecycle_v2","sid":1,"msg":{"market_ticker":"KXTEST","event_type":"created","open_ts":1737554400,"result":""}}"#;
+        let batch = schema
+            .parse_batch(&[(json.to_vec(), 1, 1000)])
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert!(batch.column_by_name("lifecycle_stage").unwrap().is_null(0));
+        assert!(batch.column_by_name("effective_ts").unwrap().is_null(0));
+        assert!(batch.column_by_name("result").unwrap().is_null(0));
+    }
+
+    #[test]
+    fn test_parse_kalshi_orderbook_snapshot() {
+        let schema = KalshiOrderbookSchema;
+        let json = br#"{"type":"orderbook_snapshot","sid":5,"seq":1,"msg":{"market_ticker":"KXBTCD-26FEB14-T100000","market_id":"abc-123-uuid","yes":[[45,100],[46,250]],"no_dollars":[["0.53",120]]}}"#;
+        let batch = schema
+            .parse_batch(&[(json.to_vec(), 1, 1000)])
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.schema(), schema.schema());
+
+        let delta = batch.column_by_name("delta").unwrap();
+        let delta = delta.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(!delta.value(0));
+
+        let levels = |name: &str| {
+            let col = batch.column_by_name(name).unwrap();
+            let list = col.as_any().downcast_ref::<ListArray>().unwrap();
+            let row = list.value(0);
+            let row = row.as_any().downcast_ref::<StructArray>().unwrap();
+            let price = row.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+            let qty = row.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+            (0..row.len())
+                .map(|i| (price.value(i), qty.value(i)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(levels("yes"), vec![(45, 100), (46, 250)]);
+        // Dollar-string levels are converted to cents
+        assert_eq!(levels("no"), vec![(53, 120)]);
+
+        assert!(batch.column_by_name("ts").unwrap().is_null(0));
+    }
+
+    #[test]
+    fn test_parse_kalshi_orderbook_delta() {
+        let schema = KalshiOrderbookSchema;
+        let json = br#"{"type":"orderbook_delta","sid":5,"seq":42,"msg":{"market_ticker":"KXBTCD-26FEB14-T100000","market_id":"abc-123-uuid","price_dollars":"0.46","delta_fp":"-50.00","side":"yes","ts":"2026-02-14T15:30:00Z"}}"#;
+        let missing_side = br#"{"type":"orderbook_delta","seq":43,"msg":{"market_ticker":"KXBTCD-26FEB14-T100000","price":46,"delta":5}}"#;
+        let batch = schema
+            .parse_batch(&[(json.to_vec(), 1, 1000), (missing_side.to_vec(), 2, 1000)])
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+
+        let delta = batch.column_by_name("delta").unwrap();
+        let delta = delta.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(delta.value(0));
+
+        let yes = batch.column_by_name("yes").unwrap();
+        let yes = yes.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(yes.value_length(0), 1);
+        let no = batch.column_by_name("no").unwrap();
+        let no = no.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(no.value_length(0), 0);
+
+        let level = yes.value(0);
+        let level = level.as_any().downcast_ref::<StructArray>().unwrap();
+        let price = level
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let qty = level
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!((price.value(0), qty.value(0)), (46, -50));
+
+        let ts = batch.column_by_name("ts").unwrap();
+        let ts = ts
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(ts.value(0), 1771083000_000_000);
+
+        let seq = batch.column_by_name("exchange_seq").unwrap();
+        let seq = seq.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(seq.value(0), 42);
+    }
+
     #[test]
     fn test_skip_missing_msg_field() {
         let schema = KalshiTickerSchema;
@@ -926,7 +1443,7 @@ mod tests {
         let schema = KalshiTickerSchema;
         let batch = schema.parse_batch(&[]).unwrap();
         assert_eq!(batch.num_rows(), 0);
-        assert_eq!(batch.num_columns(), 14);
+        assert_eq!(batch.num_columns(), 19);
     }
 
 }
@@ -189,6 +189,10 @@ impl MessageSchema for KrakenTradeSchema {
         "trade"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "timestamp"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut symbol = StringBuilder::new();
         let mut side = StringBuilder::new();
@@ -68,6 +68,10 @@ impl MessageSchema for KrakenFuturesTickerSchema {
         "ticker"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "time"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut product_id = StringBuilder::new();
         let mut bid = Float64Builder::new();
@@ -228,6 +232,18 @@ impl MessageSchema for KrakenFuturesTradeSchema {
         "trade"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "time"
+    }
+
+    /// Dedup on the exchange `uid`, falling back to `nats_seq` when absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        match json.get("uid").and_then(|v| v.as_str()) {
+            Some(uid) => Some(crate::dedup_hash(uid)),
+            None => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut product_id = StringBuilder::new();
         let mut uid = StringBuilder::new();
@@ -331,6 +347,273 @@ impl MessageSchema for KrakenFuturesTradeSchema {
     }
 }
 
+// ---------------------------------------------------------------------------
+// KrakenFuturesTickerLiteSchema
+// ---------------------------------------------------------------------------
+
+/// `ticker_lite` feed: top of book, last and 24h stats without the funding,
+/// mark or open-interest fields of the full ticker. Kraken may omit `time`
+/// on this feed, so it is nullable and rows sort by `_received_at`.
+pub struct KrakenFuturesTickerLiteSchema;
+
+impl KrakenFuturesTickerLiteSchema {
+    fn arrow_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("product_id", DataType::Utf8, false),
+            Field::new("bid", DataType::Float64, true),
+            Field::new("ask", DataType::Float64, true),
+            Field::new("last", DataType::Float64, true),
+            Field::new("change", DataType::Float64, true),
+            Field::new("premium", DataType::Float64, true),
+            Field::new("volume", DataType::Float64, true),
+            Field::new("volume_quote", DataType::Float64, true),
+            Field::new("tag", DataType::Utf8, true),
+            Field::new("pair", DataType::Utf8, true),
+            Field::new("dtm", DataType::Int64, true),
+            Field::new("time", ts_type(), true),
+            Field::new("_nats_seq", DataType::UInt64, false),
+            Field::new("_received_at", ts_type(), false),
+        ])
+    }
+}
+
+impl MessageSchema for KrakenFuturesTickerLiteSchema {
+    fn schema_name(&self) -> &str {
+        "kraken_futures_ticker_lite"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Arc::new(Self::arrow_schema())
+    }
+
+    fn message_type(&self) -> &str {
+        "ticker_lite"
+    }
+
+    /// Dedup on (`product_id`, `time`), falling back to `nats_seq` when
+    /// either is absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        let pid = json.get("product_id").and_then(|v| v.as_str());
+        match (pid, json.get("time").and_then(|v| v.as_i64())) {
+            (Some(pid), Some(ts)) => Some(crate::dedup_hash(&(pid, ts))),
+            _ => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
+    fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
+        let mut product_id = StringBuilder::new();
+        let mut bid = Float64Builder::new();
+        let mut ask = Float64Builder::new();
+        let mut last = Float64Builder::new();
+        let mut change = Float64Builder::new();
+        let mut premium = Float64Builder::new();
+        let mut volume_b = Float64Builder::new();
+        let mut volume_quote = Float64Builder::new();
+        let mut tag = StringBuilder::new();
+        let mut pair = StringBuilder::new();
+        let mut dtm = Int64Builder::new();
+        let mut time = TimestampMicrosecondBuilder::new();
+        let mut nats_seq = UInt64Builder::new();
+        let mut received_at = TimestampMicrosecondBuilder::new();
+
+        for (data, seq, recv_at) in messages {
+            let json: serde_json::Value = serde_json::from_slice(data)
+                .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+
+            let pid = match json.get("product_id").and_then(|v| v.as_str()) {
+                Some(s) => s,
+                None => {
+                    error!("Kraken Futures ticker_lite missing 'product_id', skipping");
+                    continue;
+                }
+            };
+
+            macro_rules! opt_f64 {
+                ($builder:expr, $field:expr) => {
+                    match json.get($field).and_then(|v| v.as_f64()) {
+                        Some(v) => $builder.append_value(v),
+                        None => $builder.append_null(),
+                    }
+                };
+            }
+
+            product_id.append_value(pid);
+            opt_f64!(bid, "bid");
+            opt_f64!(ask, "ask");
+            opt_f64!(last, "last");
+            opt_f64!(change, "change");
+            opt_f64!(premium, "premium");
+            opt_f64!(volume_b, "volume");
+            opt_f64!(volume_quote, "volumeQuote");
+            tag.append_option(json.get("tag").and_then(|v| v.as_str()));
+            pair.append_option(json.get("pair").and_then(|v| v.as_str()));
+            dtm.append_option(json.get("dtm").and_then(|v| v.as_i64()));
+            time.append_option(
+                json.get("time")
+                    .and_then(|v| v.as_i64())
+                    .map(epoch_ms_to_micros),
+            );
+            nats_seq.append_value(*seq);
+            received_at.append_value(*recv_at);
+        }
+
+        RecordBatch::try_new(
+            Arc::new(Self::arrow_schema()),
+            vec![
+                Arc::new(product_id.finish()),
+                Arc::new(bid.finish()),
+                Arc::new(ask.finish()),
+                Arc::new(last.finish()),
+                Arc::new(change.finish()),
+                Arc::new(premium.finish()),
+                Arc::new(volume_b.finish()),
+                Arc::new(volume_quote.finish()),
+                Arc::new(tag.finish()),
+                Arc::new(pair.finish()),
+                Arc::new(dtm.finish()),
+                Arc::new(time.finish().with_timezone("UTC")),
+                Arc::new(nats_seq.finish()),
+                Arc::new(received_at.finish().with_timezone("UTC")),
+            ],
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// KrakenFuturesFundingRateSchema
+// ---------------------------------------------------------------------------
+
+/// `funding_rate` messages: one funding observation per perpetual.
+///
+/// The v1 WebSocket has no dedicated funding feed (funding also rides on the
+/// full `ticker`), so these are flat messages using the ticker's field names,
+/// e.g. from a REST poller: `{"feed":"funding_rate","product_id":...,
+/// "funding_rate":...,"time":...}`.
+pub struct KrakenFuturesFundingRateSchema;
+
+impl KrakenFuturesFundingRateSchema {
+    fn arrow_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("product_id", DataType::Utf8, false),
+            Field::new("funding_rate", DataType::Float64, false),
+            Field::new("relative_funding_rate", DataType::Float64, true),
+            Field::new("funding_rate_prediction", DataType::Float64, true),
+            Field::new("next_funding_rate_time", DataType::Int64, true),
+            Field::new("mark_price", DataType::Float64, true),
+            Field::new("index_price", DataType::Float64, true),
+            Field::new("time", ts_type(), false),
+            Field::new("_nats_seq", DataType::UInt64, false),
+            Field::new("_received_at", ts_type(), false),
+        ])
+    }
+}
+
+impl MessageSchema for KrakenFuturesFundingRateSchema {
+    fn schema_name(&self) -> &str {
+        "kraken_futures_funding_rate"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Arc::new(Self::arrow_schema())
+    }
+
+    fn message_type(&self) -> &str {
+        "funding_rate"
+    }
+
+    fn timestamp_column(&self) -> &str {
+        "time"
+    }
+
+    /// Dedup on (`product_id`, `time`), so the same observation polled or
+    /// replayed twice collapses; falls back to `nats_seq` when either is absent.
+    fn dedup_key(&self, json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        let pid = json.get("product_id").and_then(|v| v.as_str());
+        match (pid, json.get("time").and_then(|v| v.as_i64())) {
+            (Some(pid), Some(ts)) => Some(crate::dedup_hash(&(pid, ts))),
+            _ => Some(crate::dedup_hash(&nats_seq)),
+        }
+    }
+
+    fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
+        let mut product_id = StringBuilder::new();
+        let mut funding_rate = Float64Builder::new();
+        let mut relative_funding_rate = Float64Builder::new();
+        let mut funding_rate_prediction = Float64Builder::new();
+        let mut next_funding_rate_time = Int64Builder::new();
+        let mut mark_price = Float64Builder::new();
+        let mut index_price = Float64Builder::new();
+        let mut time = TimestampMicrosecondBuilder::new();
+        let mut nats_seq = UInt64Builder::new();
+        let mut received_at = TimestampMicrosecondBuilder::new();
+
+        for (data, seq, recv_at) in messages {
+            let json: serde_json::Value = serde_json::from_slice(data)
+                .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+
+            let pid = match json.get("product_id").and_then(|v| v.as_str()) {
+                Some(s) => s,
+                None => {
+                    error!("Kraken Futures funding_rate missing 'product_id', skipping");
+                    continue;
+                }
+            };
+            let rate = match json.get("funding_rate").and_then(|v| v.as_f64()) {
+                Some(v) => v,
+                None => {
+                    error!("Kraken Futures funding_rate missing 'funding_rate', skipping");
+                    continue;
+                }
+            };
+            let ts_ms = match json.get("time").and_then(|v| v.as_i64()) {
+                Some(v) => v,
+                None => {
+                    error!("Kraken Futures funding_rate missing 'time', skipping");
+                    continue;
+                }
+            };
+
+            product_id.append_value(pid);
+            funding_rate.append_value(rate);
+            relative_funding_rate
+                .append_option(json.get("relative_funding_rate").and_then(|v| v.as_f64()));
+            funding_rate_prediction
+                .append_option(json.get("funding_rate_prediction").and_then(|v| v.as_f64()));
+            next_funding_rate_time
+                .append_option(json.get("next_funding_rate_time").and_then(|v| v.as_i64()));
+            mark_price.append_option(json.get("markPrice").and_then(|v| v.as_f64()));
+            index_price.append_option(json.get("index").and_then(|v| v.as_f64()));
+            time.append_value(epoch_ms_to_micros(ts_ms));
+            nats_seq.append_value(*seq);
+            received_at.append_value(*recv_at);
+        }
+
+        RecordBatch::try_new(
+            Arc::new(Self::arrow_schema()),
+            vec![
+                Arc::new(product_id.finish()),
+                Arc::new(funding_rate.finish()),
+                Arc::new(relative_funding_rate.finish()),
+                Arc::new(funding_rate_prediction.finish()),
+                Arc::new(next_funding_rate_time.finish()),
+                Arc::new(mark_price.finish()),
+                Arc::new(index_price.finish()),
+                Arc::new(time.finish().with_timezone("UTC")),
+                Arc::new(nats_seq.finish()),
+                Arc::new(received_at.finish().with_timezone("UTC")),
+            ],
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,4 +696,75 @@ mod tests {
         assert_eq!(batch.num_rows(), 0);
         assert_eq!(batch.num_columns(), 10);
     }
+
+    #[test]
+    fn test_parse_futures_ticker_lite() {
+        let schema = KrakenFuturesTickerLiteSchema;
+        let json = br#"{"feed":"ticker_lite","product_id":"PI_XBTUSD","bid":34932,"ask":34949.5,"change":3.37,"premium":0.1,"volume":264126741,"tag":"perpetual","pair":"XBT:USD","dtm":0,"maturityTime":0,"volumeQuote":264126741}"#;
+        let batch = schema.parse_batch(&[(json.to_vec(), 3, 2000)]).unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.num_columns(), 14);
+
+        let pid = batch.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(pid.value(0), "PI_XBTUSD");
+
+        let ask_val = batch.column(2).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(ask_val.value(0), 34949.5);
+
+        // `last` absent → null
+        assert!(batch.column(3).is_null(0));
+
+        let tag_val = batch.column(8).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(tag_val.value(0), "perpetual");
+
+        // No `time` on this message → null; rows sort by _received_at
+        assert!(batch.column(11).is_null(0));
+        assert_eq!(schema.timestamp_column(), "_received_at");
+    }
+
+    #[test]
+    fn test_parse_futures_funding_rate() {
+        let schema = KrakenFuturesFundingRateSchema;
+        let json = br#"{"feed":"funding_rate","product_id":"PF_ETHUSD","funding_rate":-0.005,"relative_funding_rate":-0.0000026,"funding_rate_prediction":-0.014,"next_funding_rate_time":1770922800000,"markPrice":1907.459,"index":1907.83,"time":1770920339237}"#;
+        let batch = schema.parse_batch(&[(json.to_vec(), 7, 3000)]).unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.num_columns(), 10);
+
+        let fr = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(fr.value(0), -0.005);
+
+        let next = batch.column(4).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(next.value(0), 1770922800000);
+
+        let ts = batch.column(7).as_any().downcast_ref::<TimestampMicrosecondArray>().unwrap();
+        assert_eq!(ts.value(0), 1770920339237 * 1000);
+    }
+
+    #[test]
+    fn test_funding_rate_skips_missing_rate() {
+        let schema = KrakenFuturesFundingRateSchema;
+        let json = br#"{"feed":"funding_rate","product_id":"PF_ETHUSD","time":1770920339237}"#;
+        let batch = schema.parse_batch(&[(json.to_vec(), 1, 1000)]).unwrap();
+        assert_eq!(batch.num_rows(), 0);
+    }
+
+    #[test]
+    fn test_funding_rate_dedup_key() {
+        let schema = KrakenFuturesFundingRateSchema;
+        let a: serde_json::Value = serde_json::from_str(
+            r#"{"product_id":"PF_ETHUSD","funding_rate":-0.005,"time":1770920339237}"#,
+        )
+        .unwrap();
+        let b: serde_json::Value = serde_json::from_str(
+            r#"{"product_id":"PF_XBTUSD","funding_rate":-0.005,"time":1770920339237}"#,
+        )
+        .unwrap();
+
+        // Same observation redelivered under a new nats_seq collapses
+        assert_eq!(schema.dedup_key(&a, 1), schema.dedup_key(&a, 2));
+        // Different products at the same time don't
+        assert_ne!(schema.dedup_key(&a, 1), schema.dedup_key(&b, 1));
+    }
 }
@@ -1,4 +1,6 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use arrow::datatypes::Schema;
@@ -6,12 +8,15 @@ use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 
 pub mod binance;
+pub mod evolution;
 pub mod kalshi;
 pub mod kraken;
 pub mod kraken_futures;
 pub mod massive;
 pub mod polymarket;
 
+pub use evolution::{align_batch, unify_schemas};
+
 /// Trait for converting raw JSON messages to Arrow RecordBatches.
 pub trait MessageSchema: Send + Sync {
     /// Schema name for versioning (e.g., "kalshi_trade").
@@ -26,19 +31,89 @@ pub trait MessageSchema: Send + Sync {
     /// Message type name (e.g., "ticker", "trade").
     fn message_type(&self) -> &str;
 
+    /// Column holding the event time; parquet rows are sorted by it.
+    ///
+    /// Defaults to `_received_at`. Schemas with an exchange timestamp
+    /// override this to name that column instead.
+    fn timestamp_column(&self) -> &str {
+        "_received_at"
+    }
+
     /// Parse a batch of JSON messages into a RecordBatch.
     /// Each entry is (raw_json_bytes, nats_seq, received_at_micros).
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError>;
+
+    /// Key identifying the same event across redeliveries, for dedup.
+    ///
+    /// The default hashes `nats_seq`, which only collapses redeliveries of the
+    /// same stream message. Schemas whose messages carry a natural event id
+    /// override this and document the fields used; they fall back to
+    /// `nats_seq` when the id is absent so distinct events never collapse.
+    fn dedup_key(&self, _json: &serde_json::Value, nats_seq: u64) -> Option<u64> {
+        Some(dedup_hash(&nats_seq))
+    }
 }
 
+/// Stable-within-process hash used by [`MessageSchema::dedup_key`] implementations.
+pub fn dedup_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Caller-supplied message type detection, used for messages the built-in
+/// feed conventions don't recognise (e.g. private/experimental feeds).
+pub type MessageTypeDetector = Box<dyn Fn(&serde_json::Value) -> Option<String> + Send + Sync>;
+
 /// Registry mapping (feed, detected_type) to the right schema.
 pub struct SchemaRegistry {
     feed: String,
     schemas: HashMap<String, Box<dyn MessageSchema>>,
+    detector: Option<MessageTypeDetector>,
+}
+
+/// Builder for a [`SchemaRegistry`], pre-populated with the built-in schemas
+/// for the feed so callers only register what they add or override.
+pub struct SchemaRegistryBuilder {
+    feed: String,
+    schemas: HashMap<String, Box<dyn MessageSchema>>,
+    detector: Option<MessageTypeDetector>,
+}
+
+impl SchemaRegistryBuilder {
+    /// Register (or replace) the schema for a message type.
+    pub fn register(mut self, message_type: &str, schema: Box<dyn MessageSchema>) -> Self {
+        self.schemas.insert(message_type.to_string(), schema);
+        self
+    }
+
+    /// Set a fallback message type detector for messages the built-in
+    /// detection returns None for.
+    pub fn detector<F>(mut self, detector: F) -> Self
+    where
+        F: Fn(&serde_json::Value) -> Option<String> + Send + Sync + 'static,
+    {
+        self.detector = Some(Box::new(detector));
+        self
+    }
+
+    pub fn build(self) -> SchemaRegistry {
+        SchemaRegistry {
+            feed: self.feed,
+            schemas: self.schemas,
+            detector: self.detector,
+        }
+    }
 }
 
 impl SchemaRegistry {
+    /// Registry with the built-in schemas for `feed`.
     pub fn for_feed(feed: &str) -> Self {
+        Self::builder(feed).build()
+    }
+
+    /// Start building a registry for `feed`, seeded with its built-in schemas.
+    pub fn builder(feed: &str) -> SchemaRegistryBuilder {
         let mut schemas: HashMap<String, Box<dyn MessageSchema>> = HashMap::new();
 
         match feed {
@@ -55,6 +130,15 @@ impl SchemaRegistry {
                     "market_lifecycle_v2".to_string(),
                     Box::new(kalshi::KalshiLifecycleSchema),
                 );
+                // One schema for both; rows are told apart by the `delta` column
+                schemas.insert(
+                    "orderbook_snapshot".to_string(),
+                    Box::new(kalshi::KalshiOrderbookSchema),
+                );
+                schemas.insert(
+                    "orderbook_delta".to_string(),
+                    Box::new(kalshi::KalshiOrderbookSchema),
+                );
             }
             "kraken" | "kraken-spot" => {
                 schemas.insert(
@@ -72,6 +156,14 @@ impl SchemaRegistry {
                     "trade".to_string(),
                     Box::new(kraken_futures::KrakenFuturesTradeSchema),
                 );
+                schemas.insert(
+                    "ticker_lite".to_string(),
+                    Box::new(kraken_futures::KrakenFuturesTickerLiteSchema),
+                );
+                schemas.insert(
+                    "funding_rate".to_string(),
+                    Box::new(kraken_futures::KrakenFuturesFundingRateSchema),
+                );
             }
             "polymarket" => {
                 schemas.insert(
@@ -106,9 +198,10 @@ impl SchemaRegistry {
             _ => {}
         }
 
-        SchemaRegistry {
+        SchemaRegistryBuilder {
             feed: feed.to_string(),
             schemas,
+            detector: None,
         }
     }
 
@@ -116,11 +209,18 @@ impl SchemaRegistry {
         self.schemas.get(message_type).map(|s| s.as_ref())
     }
 
+    /// Detect the message type using the feed's built-in conventions, falling
+    /// back to the caller-supplied detector if one was registered.
+    pub fn detect(&self, json: &serde_json::Value) -> Option<String> {
+        detect_message_type(&self.feed, json)
+            .or_else(|| self.detector.as_ref().and_then(|detect| detect(json)))
+    }
+
     pub fn detect_and_get(
         &self,
         json: &serde_json::Value,
     ) -> Option<(&str, &dyn MessageSchema)> {
-        let msg_type = detect_message_type(&self.feed, json)?;
+        let msg_type = self.detect(json)?;
         let schema = self.schemas.get(&msg_type)?;
         Some((schema.message_type(), schema.as_ref()))
     }
@@ -137,7 +237,8 @@ pub fn detect_message_type(feed: &str, json: &serde_json::Value) -> Option<Strin
             json.get("data")?.get("e")?.as_str().map(String::from)
         }
         "kalshi" => {
-            // Kalshi uses "type" field: "ticker", "trade", "market_lifecycle_v2", etc.
+            // Kalshi uses "type" field: "ticker", "trade", "market_lifecycle_v2",
+            // "orderbook_snapshot", "orderbook_delta", etc.
             json.get("type")?.as_str().map(String::from)
         }
         "kraken" | "kraken-spot" => {
@@ -147,7 +248,8 @@ pub fn detect_message_type(feed: &str, json: &serde_json::Value) -> Option<Strin
             json.get("channel")?.as_str().map(String::from)
         }
         "kraken-futures" => {
-            // Kraken Futures V1 uses flat "feed" field: "ticker", "trade", "ticker_lite", etc.
+            // Kraken Futures V1 uses flat "feed" field: "ticker", "trade", "ticker_lite",
+            // "funding_rate", etc.
             // Skip snapshot/subscription messages (have "event" field)
             if json.get("event").is_some() {
                 return None;
@@ -184,6 +286,8 @@ mod tests {
         assert!(reg.get("ticker").is_some());
         assert!(reg.get("trade").is_some());
         assert!(reg.get("market_lifecycle_v2").is_some());
+        assert!(reg.get("orderbook_snapshot").is_some());
+        assert!(reg.get("orderbook_delta").is_some());
         assert!(reg.get("unknown").is_none());
     }
 
@@ -210,6 +314,8 @@ mod tests {
         let reg = SchemaRegistry::for_feed("kraken-futures");
         assert!(reg.get("ticker").is_some());
         assert!(reg.get("trade").is_some());
+        assert!(reg.get("ticker_lite").is_some());
+        assert!(reg.get("funding_rate").is_some());
         assert!(reg.get("heartbeat").is_none());
     }
 
@@ -235,6 +341,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_kraken_futures_ticker_lite() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"feed":"ticker_lite","product_id":"PI_XBTUSD","bid":34932}"#)
+                .unwrap();
+        let reg = SchemaRegistry::for_feed("kraken-futures");
+        let (msg_type, schema) = reg.detect_and_get(&json).unwrap();
+        assert_eq!(msg_type, "ticker_lite");
+        assert_eq!(schema.schema_name(), "kraken_futures_ticker_lite");
+    }
+
     #[test]
     fn test_detect_kraken_futures_event_skipped() {
         let json: serde_json::Value =
@@ -280,6 +397,32 @@ mod tests {
         assert!(reg.get("ticker").is_none());
     }
 
+    #[test]
+    fn test_builder_registers_custom_schema_for_unknown_feed() {
+        let reg = SchemaRegistry::builder("private-feed")
+            .register("ticker", Box::new(kalshi::KalshiTickerSchema))
+            .detector(|json| json.get("kind")?.as_str().map(String::from))
+            .build();
+        assert!(reg.get("ticker").is_some());
+
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"kind":"ticker","msg":{}}"#).unwrap();
+        let (msg_type, _) = reg.detect_and_get(&json).unwrap();
+        assert_eq!(msg_type, "ticker");
+
+        let json: serde_json::Value = serde_json::from_str(r#"{"type":"ticker"}"#).unwrap();
+        assert!(reg.detect(&json).is_none());
+    }
+
+    #[test]
+    fn test_builder_keeps_builtin_schemas() {
+        let reg = SchemaRegistry::builder("kalshi")
+            .register("custom", Box::new(kraken::KrakenTradeSchema))
+            .build();
+        assert!(reg.get("ticker").is_some());
+        assert!(reg.get("custom").is_some());
+    }
+
     #[test]
     fn test_detect_kalshi_ticker() {
         let json: serde_json::Value =
@@ -401,7 +544,50 @@ mod tests {
             serde_json::from_str(r#"{"type":"ticker","msg":{}}"#).unwrap();
         let (msg_type, schema) = reg.detect_and_get(&json).unwrap();
         assert_eq!(msg_type, "ticker");
-        assert_eq!(schema.schema().fields().len(), 14);
+        assert_eq!(schema.schema().fields().len(), 19);
+    }
+
+    #[test]
+    fn test_dedup_key_defaults_to_nats_seq() {
+        let reg = SchemaRegistry::for_feed("kalshi");
+        let ticker = reg.get("ticker").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"type":"ticker","msg":{"market_ticker":"A"}}"#).unwrap();
+        // Identical payloads at different stream positions are distinct events
+        assert_ne!(ticker.dedup_key(&json, 1), ticker.dedup_key(&json, 2));
+        assert_eq!(ticker.dedup_key(&json, 1), ticker.dedup_key(&json, 1));
+    }
+
+    #[test]
+    fn test_dedup_key_kalshi_trade_uses_trade_id() {
+        let reg = SchemaRegistry::for_feed("kalshi");
+        let trade = reg.get("trade").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"type":"trade","msg":{"trade_id":"tid-1","price":10}}"#)
+                .unwrap();
+        // Redelivery under a new stream sequence still dedups on trade_id
+        assert_eq!(trade.dedup_key(&json, 1), trade.dedup_key(&json, 99));
+
+        let no_id: serde_json::Value =
+            serde_json::from_str(r#"{"type":"trade","msg":{"price":10}}"#).unwrap();
+        assert_ne!(trade.dedup_key(&no_id, 1), trade.dedup_key(&no_id, 2));
+    }
+
+    #[test]
+    fn test_dedup_key_kalshi_orderbook_uses_ticker_and_seq() {
+        let reg = SchemaRegistry::for_feed("kalshi");
+        let book = reg.get("orderbook_delta").unwrap();
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{"type":"orderbook_delta","seq":42,"msg":{"market_ticker":"A","price":46}}"#,
+        )
+        .unwrap();
+        assert_eq!(book.dedup_key(&json, 1), book.dedup_key(&json, 99));
+
+        let other: serde_json::Value = serde_json::from_str(
+            r#"{"type":"orderbook_delta","seq":42,"msg":{"market_ticker":"B","price":46}}"#,
+        )
+        .unwrap();
+        assert_ne!(book.dedup_key(&json, 1), book.dedup_key(&other, 1));
     }
 
     #[test]
@@ -409,14 +595,14 @@ mod tests {
         let reg = SchemaRegistry::for_feed("kalshi");
         let ticker = reg.get("ticker").unwrap();
         assert_eq!(ticker.schema_name(), "kalshi_ticker");
-        assert_eq!(ticker.schema_version(), "1.3.0");
+        assert_eq!(ticker.schema_version(), "1.4.0");
 
         let trade = reg.get("trade").unwrap();
         assert_eq!(trade.schema_name(), "kalshi_trade");
-        assert_eq!(trade.schema_version(), "1.3.0");
+        assert_eq!(trade.schema_version(), "1.4.0");
 
         let lifecycle = reg.get("market_lifecycle_v2").unwrap();
         assert_eq!(lifecycle.schema_name(), "kalshi_lifecycle");
-        assert_eq!(lifecycle.schema_version(), "1.0.0");
+        assert_eq!(lifecycle.schema_version(), "1.1.0");
     }
 }
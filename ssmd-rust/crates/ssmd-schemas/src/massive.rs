@@ -49,6 +49,10 @@ impl MessageSchema for MassiveTradeSchema {
         "trade"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "exchange_ts_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut symbol = StringBuilder::new();
         let mut price = Float64Builder::new();
@@ -178,6 +182,10 @@ impl MessageSchema for MassiveQuoteSchema {
         "quote"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "exchange_ts_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut symbol = StringBuilder::new();
         let mut bid = Float64Builder::new();
@@ -468,6 +476,10 @@ impl MessageSchema for MassiveOhlcv1sSchema {
         "ohlcv_1s"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "start_ts_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         parse_agg_batch("A", "ohlcv_1s", messages)
     }
@@ -493,6 +505,10 @@ impl MessageSchema for MassiveOhlcv1mSchema {
         "ohlcv_1m"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "start_ts_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         parse_agg_batch("AM", "ohlcv_1m", messages)
     }
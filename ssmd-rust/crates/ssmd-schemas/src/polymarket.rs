@@ -66,6 +66,10 @@ impl MessageSchema for PolymarketBookSchema {
         "book"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "timestamp_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut asset_id = StringBuilder::new();
         let mut market = StringBuilder::new();
@@ -185,6 +189,10 @@ impl MessageSchema for PolymarketTradeSchema {
         "last_trade_price"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "timestamp_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut asset_id = StringBuilder::new();
         let mut market = StringBuilder::new();
@@ -310,6 +318,10 @@ impl MessageSchema for PolymarketPriceChangeSchema {
         "price_change"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "timestamp_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut market = StringBuilder::new();
         let mut timestamp_ms = Int64Builder::new();
@@ -461,6 +473,10 @@ impl MessageSchema for PolymarketBestBidAskSchema {
         "best_bid_ask"
     }
 
+    fn timestamp_column(&self) -> &str {
+        "timestamp_ms"
+    }
+
     fn parse_batch(&self, messages: &[(Vec<u8>, u64, i64)]) -> Result<RecordBatch, ArrowError> {
         let mut market = StringBuilder::new();
         let mut asset_id = StringBuilder::new();
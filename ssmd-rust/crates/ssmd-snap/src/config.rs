@@ -1,5 +1,7 @@
 use clap::Parser;
 
+use crate::snap::SnapMode;
+
 /// ssmd-snap: NATS ticker stream → Redis snapshot service
 #[derive(Parser, Debug)]
 #[command(name = "ssmd-snap")]
@@ -12,11 +14,13 @@ pub struct Config {
     #[arg(long, env = "REDIS_URL", default_value = "redis://localhost:6379")]
     pub redis_url: String,
 
-    /// JSON array of subscriptions: [{"stream":"...","feed":"...","subject":"..."}]
+    /// JSON array of subscriptions: [{"stream":"...","feed":"...","subject":"...","mode":"overwrite|merge"}].
+    /// A stream may carry a `:ttl=N` suffix (e.g. "PROD_POLYMARKET:ttl=600")
+    /// to override SNAP_TTL_SECS for that stream.
     #[arg(long, env = "SNAP_SUBSCRIPTIONS")]
     pub subscriptions: String,
 
-    /// Redis key TTL in seconds
+    /// Redis key TTL in seconds (default for streams without a `:ttl=N` override)
     #[arg(long, env = "SNAP_TTL_SECS", default_value = "60")]
     pub ttl_secs: u64,
 
@@ -31,11 +35,39 @@ pub struct Subscription {
     pub stream: String,
     pub feed: String,
     pub subject: String,
+    /// Overwrite (default) or merge deltas into the existing snapshot
+    #[serde(default)]
+    pub mode: SnapMode,
+    /// TTL override from the stream's `:ttl=N` suffix
+    #[serde(skip)]
+    pub ttl_secs: Option<u64>,
 }
 
 /// Parse the subscriptions JSON string into a list of Subscription structs.
 pub fn parse_subscriptions(json_str: &str) -> Vec<Subscription> {
-    serde_json::from_str(json_str).expect("failed to parse SNAP_SUBSCRIPTIONS JSON")
+    let mut subs: Vec<Subscription> =
+        serde_json::from_str(json_str).expect("failed to parse SNAP_SUBSCRIPTIONS JSON");
+    for sub in &mut subs {
+        let (stream, ttl_secs) = parse_stream(&sub.stream)
+            .unwrap_or_else(|e| panic!("invalid SNAP_SUBSCRIPTIONS stream: {}", e));
+        sub.stream = stream;
+        sub.ttl_secs = ttl_secs;
+    }
+    subs
+}
+
+/// Split a stream spec `NAME[:ttl=N]` into the stream name and optional TTL override.
+pub fn parse_stream(spec: &str) -> Result<(String, Option<u64>), String> {
+    let Some((name, suffix)) = spec.split_once(':') else {
+        return Ok((spec.to_string(), None));
+    };
+    let ttl = suffix
+        .strip_prefix("ttl=")
+        .ok_or_else(|| format!("{}: unknown suffix '{}', expected ':ttl=N'", spec, suffix))?;
+    match ttl.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok((name.to_string(), Some(secs))),
+        _ => Err(format!("{}: ttl must be a positive number of seconds, got '{}'", spec, ttl)),
+    }
 }
 
 #[cfg(test)]
@@ -55,5 +87,31 @@ mod tests {
         assert_eq!(subs[0].subject, "prod.kalshi.crypto.json.ticker.>");
         assert_eq!(subs[1].feed, "kraken-futures");
         assert_eq!(subs[1].subject, "prod.kraken-futures.json.ticker.>");
+        assert_eq!(subs[0].mode, SnapMode::Overwrite);
+        assert_eq!(subs[0].ttl_secs, None);
+    }
+
+    #[test]
+    fn test_parse_subscription_ttl_override() {
+        let json = r#"[{"stream":"PROD_POLYMARKET:ttl=600","feed":"polymarket","subject":"prod.polymarket.json.ticker.>"}]"#;
+        let subs = parse_subscriptions(json);
+        assert_eq!(subs[0].stream, "PROD_POLYMARKET");
+        assert_eq!(subs[0].ttl_secs, Some(600));
+    }
+
+    #[test]
+    fn test_parse_stream_rejects_bad_ttl() {
+        assert_eq!(parse_stream("PROD_KALSHI").unwrap(), ("PROD_KALSHI".to_string(), None));
+        assert!(parse_stream("PROD_KALSHI:ttl=abc").is_err());
+        assert!(parse_stream("PROD_KALSHI:ttl=0").is_err());
+        assert!(parse_stream("PROD_KALSHI:ttl=").is_err());
+        assert!(parse_stream("PROD_KALSHI:ttls=60").is_err());
+    }
+
+    #[test]
+    fn test_parse_subscription_merge_mode() {
+        let json = r#"[{"stream":"PROD_POLYMARKET","feed":"polymarket","subject":"prod.polymarket.json.ticker.>","mode":"merge"}]"#;
+        let subs = parse_subscriptions(json);
+        assert_eq!(subs[0].mode, SnapMode::Merge);
     }
 }
@@ -62,10 +62,14 @@ async fn main() {
 
     // Spawn a snap task per subscription
     for sub in subscriptions {
+        let ttl = sub.ttl_secs.unwrap_or(config.ttl_secs);
         info!(
             stream = %sub.stream,
             feed = %sub.feed,
             subject = %sub.subject,
+            mode = ?sub.mode,
+            ttl_secs = ttl,
+            ttl_override = sub.ttl_secs.is_some(),
             "spawning snap consumer"
         );
 
@@ -73,11 +77,11 @@ async fn main() {
             stream_name: sub.stream,
             feed: sub.feed,
             filter_subject: sub.subject,
+            mode: sub.mode,
         };
 
         let js = js.clone();
         let redis_conn = redis_conn.clone();
-        let ttl = config.ttl_secs;
         let m = metrics.clone();
 
         tokio::spawn(async move {
@@ -5,13 +5,44 @@ use std::time::Duration;
 
 use crate::metrics::Metrics;
 
+/// How messages are written to their `snap:{feed}:{ticker}` key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapMode {
+    /// Replace the snapshot with each message (trades still merge into the
+    /// last ticker).
+    #[default]
+    Overwrite,
+    /// Merge each message's fields into the existing snapshot, for feeds that
+    /// send deltas (e.g. Polymarket `price_change`).
+    Merge,
+}
+
 /// Configuration for a single stream subscription.
 pub struct StreamConfig {
     pub stream_name: String,
     pub feed: String,
     pub filter_subject: String,
+    pub mode: SnapMode,
 }
 
+/// Attempts at a merge write before giving up on a contended key
+const MAX_MERGE_ATTEMPTS: u32 = 5;
+
+/// Compare-and-set for merge mode: write ARGV[3] with TTL ARGV[4] only if the
+/// key still holds ARGV[2] (ARGV[1] = "1") or is still absent (ARGV[1] = "0").
+/// Returns 1 on write, 0 if another writer got there first.
+const MERGE_CAS_SCRIPT: &str = r#"
+local cur = redis.call('GET', KEYS[1])
+if ARGV[1] == '1' then
+  if cur ~= ARGV[2] then return 0 end
+elseif cur then
+  return 0
+end
+redis.call('SET', KEYS[1], ARGV[3], 'EX', ARGV[4])
+return 1
+"#;
+
 /// Run the snap loop for a single stream: subscribe to NATS ticker subjects
 /// and write each message to Redis with a TTL.
 pub async fn run_snap(
@@ -27,6 +58,7 @@ pub async fn run_snap(
         feed,
         filter = %stream_config.filter_subject,
         ttl_secs,
+        mode = ?stream_config.mode,
         "starting snap consumer"
     );
 
@@ -118,30 +150,23 @@ async fn run_snap_inner(
 
         let mut conn = redis_conn.clone();
 
-        let final_data = if is_trade {
-            // For trade messages: merge into existing snap to preserve bid/ask
-            let existing: Option<Vec<u8>> = redis::cmd("GET")
-                .arg(&redis_key)
-                .query_async(&mut conn)
-                .await
-                .unwrap_or(None);
-
-            if let Some(existing_bytes) = existing {
-                merge_trade_into_snap(&existing_bytes, &enriched).unwrap_or(enriched)
-            } else {
-                enriched
+        let result = match stream_config.mode {
+            SnapMode::Overwrite => {
+                overwrite_snap(&mut conn, &redis_key, enriched, is_trade, ttl_secs).await
+            }
+            SnapMode::Merge => {
+                match merge_snap(&mut conn, &redis_key, &enriched, is_trade, ttl_secs).await {
+                    Ok(true) => Ok(()),
+                    Ok(false) => {
+                        tracing::warn!(feed, key = %redis_key, "merge write lost every retry, dropping update");
+                        metrics.errors.with_label_values(&[feed, "conflict"]).inc();
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                }
             }
-        } else {
-            enriched
         };
 
-        // Pipeline SET + EXPIRE in one round trip
-        let result: Result<(), redis::RedisError> = redis::pipe()
-            .set(&redis_key, &final_data)
-            .expire(&redis_key, ttl_secs as i64)
-            .query_async(&mut conn)
-            .await;
-
         match result {
             Ok(()) => {
                 metrics.redis_writes.with_label_values(&[feed]).inc();
@@ -156,6 +181,104 @@ async fn run_snap_inner(
     Ok(())
 }
 
+/// Write the message as the new snapshot. Trades merge into the existing
+/// snapshot to keep bid/ask; the read-modify-write is unguarded.
+async fn overwrite_snap(
+    conn: &mut redis::aio::MultiplexedConnection,
+    redis_key: &str,
+    enriched: Vec<u8>,
+    is_trade: bool,
+    ttl_secs: u64,
+) -> Result<(), redis::RedisError> {
+    let final_data = if is_trade {
+        // For trade messages: merge into existing snap to preserve bid/ask
+        let existing: Option<Vec<u8>> = redis::cmd("GET")
+            .arg(redis_key)
+            .query_async(conn)
+            .await
+            .unwrap_or(None);
+
+        if let Some(existing_bytes) = existing {
+            merge_trade_into_snap(&existing_bytes, &enriched).unwrap_or(enriched)
+        } else {
+            enriched
+        }
+    } else {
+        enriched
+    };
+
+    // Pipeline SET + EXPIRE in one round trip
+    redis::pipe()
+        .set(redis_key, &final_data)
+        .expire(redis_key, ttl_secs as i64)
+        .query_async(conn)
+        .await
+}
+
+/// Merge the message into the existing snapshot and write it back with a
+/// refreshed TTL. The write is a compare-and-set against the value that was
+/// read, retried on conflict, so concurrent updates to the same key don't
+/// clobber each other. Returns `false` if every attempt lost a race.
+async fn merge_snap(
+    conn: &mut redis::aio::MultiplexedConnection,
+    redis_key: &str,
+    enriched: &[u8],
+    is_trade: bool,
+    ttl_secs: u64,
+) -> Result<bool, redis::RedisError> {
+    let script = redis::Script::new(MERGE_CAS_SCRIPT);
+    for _ in 0..MAX_MERGE_ATTEMPTS {
+        let existing: Option<Vec<u8>> = redis::cmd("GET").arg(redis_key).query_async(conn).await?;
+
+        let merged = match &existing {
+            Some(existing) if is_trade => merge_trade_into_snap(existing, enriched),
+            Some(existing) => merge_fields_into_snap(existing, enriched),
+            None => None,
+        }
+        // Nothing (parseable) to merge into: the message becomes the snapshot
+        .unwrap_or_else(|| enriched.to_vec());
+
+        let written: i64 = script
+            .key(redis_key)
+            .arg(if existing.is_some() { "1" } else { "0" })
+            .arg(existing.as_deref().unwrap_or_default())
+            .arg(merged)
+            .arg(ttl_secs)
+            .invoke_async(conn)
+            .await?;
+        if written == 1 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Merge a delta message's fields into an existing snap entry.
+///
+/// Top-level fields in the delta replace the snapshot's; nested objects (e.g.
+/// Kalshi's `msg` wrapper) are merged one level deep so absent inner fields
+/// survive. Arrays are replaced wholesale.
+fn merge_fields_into_snap(existing: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let mut snap: serde_json::Value = serde_json::from_slice(existing).ok()?;
+    let delta_val: serde_json::Value = serde_json::from_slice(delta).ok()?;
+
+    let snap_obj = snap.as_object_mut()?;
+    for (k, v) in delta_val.as_object()? {
+        match (snap_obj.get_mut(k), v) {
+            (Some(serde_json::Value::Object(inner)), serde_json::Value::Object(delta_inner)) => {
+                for (ik, iv) in delta_inner {
+                    inner.insert(ik.clone(), iv.clone());
+                }
+            }
+            _ => {
+                snap_obj.insert(k.clone(), v.clone());
+            }
+        }
+    }
+
+    serde_json::to_vec(&snap).ok()
+}
+
 /// Merge trade data into an existing snap (ticker) entry.
 ///
 /// Preserves ticker fields (bid/ask/volume/OI) while updating trade fields
@@ -399,6 +522,40 @@ mod tests {
         assert_eq!(v["side"], "buy");
     }
 
+    #[test]
+    fn test_merge_fields_keeps_prior_fields() {
+        let snap = br#"{"market":"0xabc","event_type":"price_change","best_bid":"0.41","best_ask":"0.43","volume":"1200","_snap_at":1000}"#;
+        let delta = br#"{"market":"0xabc","event_type":"price_change","best_bid":"0.42","_snap_at":2000}"#;
+
+        let merged = merge_fields_into_snap(snap, delta).unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&merged).unwrap();
+
+        assert_eq!(v["best_bid"], "0.42");
+        assert_eq!(v["_snap_at"], 2000);
+        // Untouched by the delta
+        assert_eq!(v["best_ask"], "0.43");
+        assert_eq!(v["volume"], "1200");
+    }
+
+    #[test]
+    fn test_merge_fields_nested_objects_one_level() {
+        let snap = br#"{"type":"ticker","msg":{"market_ticker":"KXTEST","yes_bid":90,"yes_ask":91},"tags":[1,2]}"#;
+        let delta = br#"{"type":"ticker","msg":{"yes_ask":92},"tags":[3]}"#;
+
+        let merged = merge_fields_into_snap(snap, delta).unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&merged).unwrap();
+
+        assert_eq!(v["msg"]["yes_bid"], 90);
+        assert_eq!(v["msg"]["yes_ask"], 92);
+        assert_eq!(v["msg"]["market_ticker"], "KXTEST");
+        assert_eq!(v["tags"], serde_json::json!([3]));
+    }
+
+    #[test]
+    fn test_merge_fields_unparseable_existing() {
+        assert!(merge_fields_into_snap(b"not json", br#"{"a":1}"#).is_none());
+    }
+
     #[test]
     fn test_merge_no_existing_returns_none() {
         assert!(merge_trade_into_snap(b"not json", b"{}").is_none());